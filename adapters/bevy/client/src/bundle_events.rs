@@ -0,0 +1,95 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Update};
+use bevy_ecs::{
+    entity::Entity,
+    event::{Event, EventReader, EventWriter},
+    schedule::IntoSystemConfigs,
+};
+
+use naia_bevy_shared::{BeforeReceiveEvents, ComponentKind, Replicate};
+
+use crate::{events::SpawnEntityWithComponentsEvent, systems::before_receive_events};
+
+/// A set of replicated Components, which can be waited on to arrive together
+/// with a spawned Entity through `SpawnBundleEvent`. Implemented for tuples
+/// of up to 8 Components
+pub trait ReplicateBundle: Send + Sync + 'static {
+    fn component_kinds() -> Vec<ComponentKind>;
+}
+
+macro_rules! impl_replicate_bundle {
+    ($($component:ident),+) => {
+        impl<$($component: Replicate),+> ReplicateBundle for ($($component,)+) {
+            fn component_kinds() -> Vec<ComponentKind> {
+                vec![$(ComponentKind::of::<$component>()),+]
+            }
+        }
+    };
+}
+
+impl_replicate_bundle!(A);
+impl_replicate_bundle!(A, B);
+impl_replicate_bundle!(A, B, C);
+impl_replicate_bundle!(A, B, C, D);
+impl_replicate_bundle!(A, B, C, D, E);
+impl_replicate_bundle!(A, B, C, D, E, F);
+impl_replicate_bundle!(A, B, C, D, E, F, G);
+impl_replicate_bundle!(A, B, C, D, E, F, G, H);
+
+// SpawnBundleEvent
+/// Fired when an Entity is spawned along with at least every Component in
+/// bundle B, in the same processing pass. Requires
+/// `ClientConfig::spawn_with_components_events`, and registering with
+/// `App::add_spawn_bundle_event()`
+#[derive(Event)]
+pub struct SpawnBundleEvent<T: Send + Sync + 'static, B: ReplicateBundle> {
+    pub entity: Entity,
+    phantom_t: PhantomData<T>,
+    phantom_b: PhantomData<B>,
+}
+
+impl<T: Send + Sync + 'static, B: ReplicateBundle> SpawnBundleEvent<T, B> {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            phantom_t: PhantomData,
+            phantom_b: PhantomData,
+        }
+    }
+}
+
+// App Extension Methods
+pub trait AppRegisterBundleEvents {
+    fn add_spawn_bundle_event<T: Send + Sync + 'static, B: ReplicateBundle>(&mut self)
+        -> &mut Self;
+}
+
+impl AppRegisterBundleEvents for App {
+    fn add_spawn_bundle_event<T: Send + Sync + 'static, B: ReplicateBundle>(
+        &mut self,
+    ) -> &mut Self {
+        self.add_event::<SpawnBundleEvent<T, B>>().add_systems(
+            Update,
+            spawn_bundle_events::<T, B>
+                .in_set(BeforeReceiveEvents)
+                .after(before_receive_events::<T>),
+        );
+        self
+    }
+}
+
+fn spawn_bundle_events<T: Send + Sync + 'static, B: ReplicateBundle>(
+    mut spawn_reader: EventReader<SpawnEntityWithComponentsEvent<T>>,
+    mut bundle_writer: EventWriter<SpawnBundleEvent<T, B>>,
+) {
+    let bundle_kinds = B::component_kinds();
+    for event in spawn_reader.read() {
+        if bundle_kinds
+            .iter()
+            .all(|kind| event.component_kinds.contains(kind))
+        {
+            bundle_writer.send(SpawnBundleEvent::<T, B>::new(event.entity));
+        }
+    }
+}
@@ -6,13 +6,14 @@ use bevy_ecs::{
 };
 
 use naia_bevy_shared::{
-    Channel, EntityAndGlobalEntityConverter, EntityAuthStatus, EntityDoesNotExistError,
-    GlobalEntity, Message, Request, Response, ResponseReceiveKey, ResponseSendKey, Tick,
+    Channel, ConnectionQuality, EntityAndGlobalEntityConverter, EntityAuthStatus,
+    EntityDoesNotExistError, GlobalEntity, Message, MessageReceiptKey, Request, Response,
+    ResponseReceiveKey, ResponseSendKey, Tick,
 };
 use naia_client::{
-    shared::{GameInstant, SocketConfig},
+    shared::{ChannelLatencyStats, GameInstant, SocketConfig},
     transport::Socket,
-    Client as NaiaClient, ConnectionStatus, NaiaClientError,
+    Client as NaiaClient, ConnectionStatus, NaiaClientError, TickSyncDiagnostics,
 };
 
 use crate::ReplicationConfig;
@@ -75,6 +76,14 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.jitter()
     }
 
+    pub fn connection_quality(&mut self) -> ConnectionQuality {
+        self.client.client.connection_quality()
+    }
+
+    pub fn channel_latency<C: Channel>(&self) -> Option<ChannelLatencyStats> {
+        self.client.client.channel_latency::<C>()
+    }
+
     // Config
     pub fn socket_config(&self) -> &SocketConfig {
         self.client.client.socket_config()
@@ -85,6 +94,23 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.send_message::<C, M>(message);
     }
 
+    pub fn send_message_with_receipt<C: Channel, M: Message>(
+        &mut self,
+        message: &M,
+    ) -> Result<MessageReceiptKey, NaiaClientError> {
+        self.client
+            .client
+            .send_message_with_receipt::<C, M>(message)
+    }
+
+    pub fn receipt_received(&self, receipt_key: &MessageReceiptKey) -> bool {
+        self.client.client.receipt_received(receipt_key)
+    }
+
+    pub fn send_stream_message(&mut self, bytes: &[u8]) {
+        self.client.client.send_stream_message(bytes);
+    }
+
     pub fn send_tick_buffer_message<C: Channel, M: Message>(&mut self, tick: &Tick, message: &M) {
         self.client
             .client
@@ -99,6 +125,16 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.send_request::<C, Q>(request)
     }
 
+    pub fn send_request_with_timeout<C: Channel, Q: Request>(
+        &mut self,
+        request: &Q,
+        timeout: Duration,
+    ) -> Result<ResponseReceiveKey<Q::Response>, NaiaClientError> {
+        self.client
+            .client
+            .send_request_with_timeout::<C, Q>(request, timeout)
+    }
+
     pub fn send_response<S: Response>(
         &mut self,
         response_key: &ResponseSendKey<S>,
@@ -140,6 +176,14 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.tick_duration()
     }
 
+    pub fn game_time_now(&self) -> Option<GameInstant> {
+        self.client.client.game_time_now()
+    }
+
+    pub fn tick_sync_diagnostics(&self) -> Option<TickSyncDiagnostics> {
+        self.client.client.tick_sync_diagnostics()
+    }
+
     // Interpolation
 
     pub fn client_interpolation(&self) -> Option<f32> {
@@ -150,6 +194,16 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.server_interpolation()
     }
 
+    // Ownership
+
+    pub fn owned_entities(&self) -> Vec<Entity> {
+        self.client.client.owned_entities()
+    }
+
+    pub fn server_owned_entities(&self) -> Vec<Entity> {
+        self.client.client.server_owned_entities()
+    }
+
     // Entity Registration
 
     pub(crate) fn enable_replication(&mut self, entity: &Entity) {
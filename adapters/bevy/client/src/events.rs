@@ -2,13 +2,14 @@ use std::{any::Any, collections::HashMap, marker::PhantomData};
 
 use bevy_ecs::{entity::Entity, prelude::Event};
 
-use naia_client::{Events, NaiaClientError};
+use naia_client::{Events, NaiaClientError, TickSyncKind};
 
 use naia_bevy_shared::{
-    Channel, ChannelKind, ComponentKind, Message, MessageContainer, MessageKind, Replicate,
-    Request, ResponseSendKey, Tick,
+    Channel, ChannelKind, ComponentKind, ConnectionParams, ConnectionQualityLevel, Message,
+    MessageContainer, MessageKind, MessageReceiptKey, Replicate, Request, ResponseReceiveKey,
+    ResponseSendKey, Tick,
 };
-use naia_client::shared::GlobalResponseId;
+use naia_client::shared::{GameInstant, GlobalRequestId, GlobalResponseId};
 
 // ConnectEvent
 #[derive(Event)]
@@ -24,6 +25,22 @@ impl<T> ConnectEvent<T> {
     }
 }
 
+// ConnectionReadyEvent
+#[derive(Event)]
+pub struct ConnectionReadyEvent<T> {
+    pub params: ConnectionParams,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> ConnectionReadyEvent<T> {
+    pub fn new(params: ConnectionParams) -> Self {
+        Self {
+            params,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
 // DisconnectEvent
 #[derive(Event)]
 pub struct DisconnectEvent<T> {
@@ -68,6 +85,54 @@ impl<T> ErrorEvent<T> {
     }
 }
 
+// ConnectionQualityChangedEvent
+#[derive(Event)]
+pub struct ConnectionQualityChangedEvent<T> {
+    pub level: ConnectionQualityLevel,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> ConnectionQualityChangedEvent<T> {
+    pub fn new(level: ConnectionQualityLevel) -> Self {
+        Self {
+            level,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
+// MessageDeliveredEvent
+#[derive(Event)]
+pub struct MessageDeliveredEvent<T> {
+    pub receipt_key: MessageReceiptKey,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> MessageDeliveredEvent<T> {
+    pub fn new(receipt_key: MessageReceiptKey) -> Self {
+        Self {
+            receipt_key,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
+// StreamMessageEvent
+#[derive(Event)]
+pub struct StreamMessageEvent<T> {
+    pub bytes: Vec<u8>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> StreamMessageEvent<T> {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
 // MessageEvents
 #[derive(Event)]
 pub struct MessageEvents<T> {
@@ -105,6 +170,30 @@ impl<T> MessageEvents<T> {
 
         output
     }
+
+    /// Like `read()`, but also gives the GameInstant each Message was sent
+    /// at, on this Client's clock, if Channel C tracks latency
+    pub fn read_timed<C: Channel, M: Message>(&self) -> Vec<(Option<GameInstant>, M)> {
+        let mut output = Vec::new();
+
+        let channel_kind = ChannelKind::of::<C>();
+        if let Some(message_map) = self.inner.get(&channel_kind) {
+            let message_kind = MessageKind::of::<M>();
+            if let Some(messages) = message_map.get(&message_kind) {
+                for boxed_message in messages {
+                    let send_instant = boxed_message.send_instant();
+                    let boxed_any = boxed_message.clone().to_boxed_any();
+                    let message: M = Box::<dyn Any + 'static>::downcast::<M>(boxed_any)
+                        .ok()
+                        .map(|boxed_m| *boxed_m)
+                        .unwrap();
+                    output.push((send_instant, message));
+                }
+            }
+        }
+
+        output
+    }
 }
 
 // RequestEvents
@@ -149,17 +238,49 @@ impl<T> RequestEvents<T> {
     }
 }
 
+// RequestTimeoutEvents
+#[derive(Event)]
+pub struct RequestTimeoutEvents<T> {
+    inner: HashMap<MessageKind, Vec<GlobalRequestId>>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> From<&mut Events<Entity>> for RequestTimeoutEvents<T> {
+    fn from(events: &mut Events<Entity>) -> Self {
+        Self {
+            inner: events.take_request_timeouts(),
+            phantom_t: PhantomData,
+        }
+    }
+}
+
+impl<T> RequestTimeoutEvents<T> {
+    pub fn read<Q: Request>(&self) -> Vec<ResponseReceiveKey<Q::Response>> {
+        let request_kind = MessageKind::of::<Q>();
+        let Some(request_ids) = self.inner.get(&request_kind) else {
+            return Vec::new();
+        };
+
+        request_ids
+            .iter()
+            .map(|request_id| ResponseReceiveKey::new(*request_id))
+            .collect()
+    }
+}
+
 // ClientTickEvent
 #[derive(Event)]
 pub struct ClientTickEvent<T> {
     pub tick: Tick,
+    pub kind: TickSyncKind,
     phantom_t: PhantomData<T>,
 }
 
 impl<T> ClientTickEvent<T> {
-    pub fn new(tick: Tick) -> Self {
+    pub fn new(tick: Tick, kind: TickSyncKind) -> Self {
         Self {
             tick,
+            kind,
             phantom_t: PhantomData,
         }
     }
@@ -169,13 +290,15 @@ impl<T> ClientTickEvent<T> {
 #[derive(Event)]
 pub struct ServerTickEvent<T> {
     pub tick: Tick,
+    pub kind: TickSyncKind,
     phantom_t: PhantomData<T>,
 }
 
 impl<T> ServerTickEvent<T> {
-    pub fn new(tick: Tick) -> Self {
+    pub fn new(tick: Tick, kind: TickSyncKind) -> Self {
         Self {
             tick,
+            kind,
             phantom_t: PhantomData,
         }
     }
@@ -197,6 +320,26 @@ impl<T> SpawnEntityEvent<T> {
     }
 }
 
+// SpawnEntityWithComponentsEvent
+/// Fired when `ClientConfig::spawn_with_components_events` is enabled, see
+/// `naia_client::SpawnEntityWithComponentsEvent`
+#[derive(Event)]
+pub struct SpawnEntityWithComponentsEvent<T> {
+    pub entity: Entity,
+    pub component_kinds: Vec<ComponentKind>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> SpawnEntityWithComponentsEvent<T> {
+    pub fn new(entity: Entity, component_kinds: Vec<ComponentKind>) -> Self {
+        Self {
+            entity,
+            component_kinds,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
 // DespawnEntityEvent
 #[derive(Event)]
 pub struct DespawnEntityEvent<T> {
@@ -1,14 +1,18 @@
 pub use naia_bevy_shared::{
-    sequence_greater_than, sequence_less_than, wrapping_diff, EntityAuthStatus, GameInstant,
-    Random, ReceiveEvents, Replicate, ResponseSendKey, Tick, Timer,
+    game_instant_greater_than, game_instant_less_than, sequence_greater_than, sequence_less_than,
+    wrapping_diff, ChangeDetectionMode, ConnectionQuality, ConnectionQualityLevel,
+    EntityAuthStatus, GameDuration, GameInstant, Random, ReceiveEvents, Replicate, ResponseSendKey,
+    Tick, TickExt, Timer,
 };
 pub use naia_client::{
     shared::{default_channels, Instant, Message, ResponseReceiveKey},
     transport, ClientConfig, CommandHistory, NaiaClientError, ReplicationConfig,
+    TickSyncDiagnostics, TickSyncKind,
 };
 
 pub mod events;
 
+mod bundle_events;
 mod client;
 mod commands;
 pub mod component_events;
@@ -16,6 +20,7 @@ mod components;
 mod plugin;
 mod systems;
 
+pub use bundle_events::{AppRegisterBundleEvents, ReplicateBundle, SpawnBundleEvent};
 pub use client::Client;
 pub use commands::CommandsExt;
 pub use components::{ClientOwned, ServerOwned};
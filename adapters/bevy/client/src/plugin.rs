@@ -3,17 +3,21 @@ use std::{marker::PhantomData, ops::DerefMut, sync::Mutex};
 use bevy_app::{App, Plugin as PluginType, Update};
 use bevy_ecs::{entity::Entity, schedule::IntoSystemConfigs};
 
-use crate::events::RequestEvents;
-use naia_bevy_shared::{BeforeReceiveEvents, Protocol, SharedPlugin, WorldData};
+use crate::events::{RequestEvents, RequestTimeoutEvents};
+use naia_bevy_shared::{
+    BeforeReceiveEvents, ChangeDetectionMode, Protocol, SharedPlugin, WorldData,
+};
 use naia_client::{Client, ClientConfig};
 
 use super::{
     client::ClientWrapper,
     events::{
-        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, InsertComponentEvents,
+        ClientTickEvent, ConnectEvent, ConnectionQualityChangedEvent, ConnectionReadyEvent,
+        DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent, EntityAuthGrantedEvent,
+        EntityAuthResetEvent, ErrorEvent, InsertComponentEvents, MessageDeliveredEvent,
         MessageEvents, PublishEntityEvent, RejectEvent, RemoveComponentEvents, ServerTickEvent,
-        SpawnEntityEvent, UnpublishEntityEvent, UpdateComponentEvents,
+        SpawnEntityEvent, SpawnEntityWithComponentsEvent, StreamMessageEvent, UnpublishEntityEvent,
+        UpdateComponentEvents,
     },
     systems::before_receive_events,
 };
@@ -34,6 +38,7 @@ impl PluginConfig {
 
 pub struct Plugin<T> {
     config: Mutex<Option<PluginConfig>>,
+    change_detection: ChangeDetectionMode,
     phantom_t: PhantomData<T>,
 }
 
@@ -42,9 +47,16 @@ impl<T> Plugin<T> {
         let config = PluginConfig::new(client_config, protocol);
         Self {
             config: Mutex::new(Some(config)),
+            change_detection: ChangeDetectionMode::default(),
             phantom_t: PhantomData,
         }
     }
+
+    /// See `SharedPlugin::with_change_detection()`
+    pub fn with_change_detection(mut self, change_detection: ChangeDetectionMode) -> Self {
+        self.change_detection = change_detection;
+        self
+    }
 }
 
 impl<T: Sync + Send + 'static> PluginType for Plugin<T> {
@@ -52,10 +64,13 @@ impl<T: Sync + Send + 'static> PluginType for Plugin<T> {
         let mut config = self.config.lock().unwrap().deref_mut().take().unwrap();
 
         let mut world_data = config.protocol.take_world_data();
-        world_data.add_systems(app);
 
+        // another connection may already have registered the same Components
         if let Some(old_world_data) = app.world_mut().remove_resource::<WorldData>() {
+            world_data.add_missing_systems(app, &old_world_data);
             world_data.merge(old_world_data);
+        } else {
+            world_data.add_systems(app);
         }
 
         app.insert_resource(world_data);
@@ -65,19 +80,25 @@ impl<T: Sync + Send + 'static> PluginType for Plugin<T> {
 
         app
             // SHARED PLUGIN //
-            .add_plugins(SharedPlugin::<T>::new())
+            .add_plugins(SharedPlugin::<T>::new().with_change_detection(self.change_detection))
             // RESOURCES //
             .insert_resource(client)
             // EVENTS //
             .add_event::<ConnectEvent<T>>()
+            .add_event::<ConnectionReadyEvent<T>>()
             .add_event::<DisconnectEvent<T>>()
             .add_event::<RejectEvent<T>>()
             .add_event::<ErrorEvent<T>>()
+            .add_event::<ConnectionQualityChangedEvent<T>>()
             .add_event::<MessageEvents<T>>()
+            .add_event::<StreamMessageEvent<T>>()
+            .add_event::<MessageDeliveredEvent<T>>()
             .add_event::<RequestEvents<T>>()
+            .add_event::<RequestTimeoutEvents<T>>()
             .add_event::<ClientTickEvent<T>>()
             .add_event::<ServerTickEvent<T>>()
             .add_event::<SpawnEntityEvent<T>>()
+            .add_event::<SpawnEntityWithComponentsEvent<T>>()
             .add_event::<DespawnEntityEvent<T>>()
             .add_event::<PublishEntityEvent<T>>()
             .add_event::<UnpublishEntityEvent<T>>()
@@ -12,18 +12,21 @@ use naia_bevy_shared::{HostOwned, HostSyncEvent, WorldMutType, WorldProxyMut};
 
 mod naia_events {
     pub use naia_client::{
-        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, PublishEntityEvent, RejectEvent,
-        ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent,
+        ClientTickEvent, ConnectEvent, ConnectionQualityChangedEvent, ConnectionReadyEvent,
+        DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent, EntityAuthGrantedEvent,
+        EntityAuthResetEvent, ErrorEvent, MessageDeliveredEvent, PublishEntityEvent, RejectEvent,
+        ServerTickEvent, SpawnEntityEvent, SpawnEntityWithComponentsEvent, UnpublishEntityEvent,
     };
 }
 
 mod bevy_events {
     pub use crate::events::{
-        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, InsertComponentEvents,
+        ClientTickEvent, ConnectEvent, ConnectionQualityChangedEvent, ConnectionReadyEvent,
+        DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent, EntityAuthGrantedEvent,
+        EntityAuthResetEvent, ErrorEvent, InsertComponentEvents, MessageDeliveredEvent,
         MessageEvents, PublishEntityEvent, RejectEvent, RemoveComponentEvents, RequestEvents,
-        ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent, UpdateComponentEvents,
+        RequestTimeoutEvents, ServerTickEvent, SpawnEntityEvent, SpawnEntityWithComponentsEvent,
+        StreamMessageEvent, UnpublishEntityEvent, UpdateComponentEvents,
     };
 }
 
@@ -78,6 +81,18 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
 
         // Receive Events
         let mut events = client.client.receive(world.proxy_mut());
+
+        // Stream Message Event
+        let stream_messages = client.client.receive_stream_messages();
+        if !stream_messages.is_empty() {
+            let mut event_writer = world
+                .get_resource_mut::<Events<bevy_events::StreamMessageEvent<T>>>()
+                .unwrap();
+            for bytes in stream_messages {
+                event_writer.send(bevy_events::StreamMessageEvent::<T>::new(bytes));
+            }
+        }
+
         if !events.is_empty() {
             if events.has::<naia_events::ConnectEvent>() {
                 // Connect Event
@@ -89,6 +104,16 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 }
             }
 
+            // Connection Ready Event
+            if events.has::<naia_events::ConnectionReadyEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::ConnectionReadyEvent<T>>>()
+                    .unwrap();
+                for params in events.read::<naia_events::ConnectionReadyEvent>() {
+                    event_writer.send(bevy_events::ConnectionReadyEvent::<T>::new(params));
+                }
+            }
+
             // Disconnect Event
             if events.has::<naia_events::DisconnectEvent>() {
                 let mut event_writer = world
@@ -119,13 +144,33 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 }
             }
 
+            // Connection Quality Changed Event
+            if events.has::<naia_events::ConnectionQualityChangedEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::ConnectionQualityChangedEvent<T>>>()
+                    .unwrap();
+                for level in events.read::<naia_events::ConnectionQualityChangedEvent>() {
+                    event_writer.send(bevy_events::ConnectionQualityChangedEvent::<T>::new(level));
+                }
+            }
+
+            // Message Delivered Event
+            if events.has::<naia_events::MessageDeliveredEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::MessageDeliveredEvent<T>>>()
+                    .unwrap();
+                for receipt_key in events.read::<naia_events::MessageDeliveredEvent>() {
+                    event_writer.send(bevy_events::MessageDeliveredEvent::<T>::new(receipt_key));
+                }
+            }
+
             // Client Tick Event
             if events.has::<naia_events::ClientTickEvent>() {
                 let mut event_writer = world
                     .get_resource_mut::<Events<bevy_events::ClientTickEvent<T>>>()
                     .unwrap();
-                for tick in events.read::<naia_events::ClientTickEvent>() {
-                    event_writer.send(bevy_events::ClientTickEvent::<T>::new(tick));
+                for (tick, kind) in events.read::<naia_events::ClientTickEvent>() {
+                    event_writer.send(bevy_events::ClientTickEvent::<T>::new(tick, kind));
                 }
             }
 
@@ -134,8 +179,8 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 let mut event_writer = world
                     .get_resource_mut::<Events<bevy_events::ServerTickEvent<T>>>()
                     .unwrap();
-                for tick in events.read::<naia_events::ServerTickEvent>() {
-                    event_writer.send(bevy_events::ServerTickEvent::<T>::new(tick));
+                for (tick, kind) in events.read::<naia_events::ServerTickEvent>() {
+                    event_writer.send(bevy_events::ServerTickEvent::<T>::new(tick, kind));
                 }
             }
 
@@ -155,6 +200,14 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 event_writer.send(bevy_events::RequestEvents::from(&mut events));
             }
 
+            // Request Timeout Event
+            if events.has_request_timeouts() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::RequestTimeoutEvents<T>>>()
+                    .unwrap();
+                event_writer.send(bevy_events::RequestTimeoutEvents::from(&mut events));
+            }
+
             // Spawn Entity Event
             if events.has::<naia_events::SpawnEntityEvent>() {
                 let mut event_writer = world
@@ -171,6 +224,21 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 }
             }
 
+            // Spawn Entity With Components Event
+            if events.has::<naia_events::SpawnEntityWithComponentsEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::SpawnEntityWithComponentsEvent<T>>>()
+                    .unwrap();
+                for (entity, component_kinds) in
+                    events.read::<naia_events::SpawnEntityWithComponentsEvent>()
+                {
+                    event_writer.send(bevy_events::SpawnEntityWithComponentsEvent::<T>::new(
+                        entity,
+                        component_kinds,
+                    ));
+                }
+            }
+
             // Despawn Entity Event
             if events.has::<naia_events::DespawnEntityEvent>() {
                 let mut event_writer = world
@@ -213,7 +281,10 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 }
                 for entity in auth_granted_entities {
                     if world.get_entity(entity).is_ok() {
-                        world.entity_mut(entity).insert(HostOwned::new::<T>());
+                        world
+                            .entity_mut(entity)
+                            .insert(HostOwned::new::<T>())
+                            .remove::<ServerOwned>();
                     } else {
                         warn!(
                             "Granted auth to an entity that no longer exists! {:?}",
@@ -245,7 +316,11 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 }
                 for entity in auth_reset_entities {
                     if world.get_entity(entity).is_ok() {
-                        world.entity_mut(entity).remove::<HostOwned>();
+                        let mut entity_mut = world.entity_mut(entity);
+                        entity_mut.remove::<HostOwned>();
+                        if client.client.entity_owner(&entity).is_server() {
+                            entity_mut.insert(ServerOwned);
+                        }
                     } else {
                         warn!(
                             "Reset auth to an entity that no longer exists! {:?}",
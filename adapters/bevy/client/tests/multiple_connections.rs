@@ -0,0 +1,282 @@
+use std::{thread::sleep, time::Duration};
+
+use bevy_app::App;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, Events},
+    system::{Commands, SystemState},
+};
+
+use naia_bevy_client::{
+    default_channels::UnorderedReliableChannel,
+    events::{ConnectEvent, MessageEvents},
+    Client, ClientConfig, CommandsExt,
+};
+use naia_bevy_server::{
+    events::{AuthEvents, DespawnEntityEvent, InsertComponentEvents, SpawnEntityEvent},
+    Server, ServerConfig,
+};
+use naia_bevy_shared::{Message, Property, Protocol, Replicate};
+use naia_test::LocalTransport;
+
+#[derive(Message)]
+pub struct Auth;
+
+#[derive(Message)]
+pub struct Greeting {
+    pub text: String,
+}
+
+#[derive(Component, Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+struct MainServer;
+
+struct ChatServer;
+
+// the same Protocol is used for both connections
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .enable_client_authoritative_entities()
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_message::<Greeting>()
+        .add_component::<Position>()
+        .build()
+}
+
+fn server_app(transport: &LocalTransport) -> App {
+    let mut app = App::new();
+    app.add_plugins(naia_bevy_server::Plugin::new(
+        ServerConfig::default(),
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Server> = SystemState::new(app.world_mut());
+    state
+        .get_mut(app.world_mut())
+        .listen(transport.server_socket());
+    app
+}
+
+fn client_config() -> ClientConfig {
+    ClientConfig {
+        send_handshake_interval: Duration::from_millis(10),
+        handshake_pings: 2,
+        ..Default::default()
+    }
+}
+
+fn connect<T: Send + Sync + 'static>(app: &mut App, transport: &LocalTransport) {
+    let mut state: SystemState<Client<T>> = SystemState::new(app.world_mut());
+    let mut client = state.get_mut(app.world_mut());
+    client.auth(Auth);
+    client.connect(transport.client_socket());
+}
+
+fn is_connected<T: Send + Sync + 'static>(app: &mut App) -> bool {
+    let mut state: SystemState<Client<T>> = SystemState::new(app.world_mut());
+    let client = state.get_mut(app.world_mut());
+    client.connection_status().is_connected()
+}
+
+fn drain<E: Event>(app: &mut App) -> Vec<E> {
+    app.world_mut()
+        .resource_mut::<Events<E>>()
+        .drain()
+        .collect()
+}
+
+fn accept_connections(server_app: &mut App) {
+    let auth_events: Vec<AuthEvents> = drain(server_app);
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let mut server = state.get_mut(server_app.world_mut());
+    for events in auth_events {
+        for (user_key, _) in events.read::<Auth>() {
+            server.accept_connection(&user_key);
+        }
+    }
+}
+
+fn send_greeting(server_app: &mut App, text: &str) {
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let mut server = state.get_mut(server_app.world_mut());
+    let greeting = Greeting {
+        text: text.to_string(),
+    };
+    for user_key in server.user_keys() {
+        server.send_message::<UnorderedReliableChannel, Greeting>(&user_key, &greeting);
+    }
+}
+
+// Everything observed by the test, accumulated across App updates
+#[derive(Default)]
+struct Observed {
+    main_connects: usize,
+    chat_connects: usize,
+    main_greetings: Vec<String>,
+    chat_greetings: Vec<String>,
+    main_server_spawns: Vec<Entity>,
+    chat_server_spawns: Vec<Entity>,
+    main_server_inserts: usize,
+    main_server_despawns: usize,
+}
+
+struct Harness {
+    client_app: App,
+    main_server_app: App,
+    chat_server_app: App,
+    observed: Observed,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let main_transport = LocalTransport::new();
+        let chat_transport = LocalTransport::new();
+
+        let mut client_app = App::new();
+        client_app
+            .add_plugins(naia_bevy_client::Plugin::<MainServer>::new(
+                client_config(),
+                protocol(),
+            ))
+            .add_plugins(naia_bevy_client::Plugin::<ChatServer>::new(
+                client_config(),
+                protocol(),
+            ));
+        client_app.finish();
+        client_app.update();
+
+        let main_server_app = server_app(&main_transport);
+        let chat_server_app = server_app(&chat_transport);
+
+        connect::<MainServer>(&mut client_app, &main_transport);
+        connect::<ChatServer>(&mut client_app, &chat_transport);
+
+        Self {
+            client_app,
+            main_server_app,
+            chat_server_app,
+            observed: Observed::default(),
+        }
+    }
+
+    fn update(&mut self) {
+        sleep(Duration::from_millis(5));
+        self.client_app.update();
+        self.main_server_app.update();
+        self.chat_server_app.update();
+        accept_connections(&mut self.main_server_app);
+        accept_connections(&mut self.chat_server_app);
+
+        let observed = &mut self.observed;
+        observed.main_connects += drain::<ConnectEvent<MainServer>>(&mut self.client_app).len();
+        observed.chat_connects += drain::<ConnectEvent<ChatServer>>(&mut self.client_app).len();
+        for events in drain::<MessageEvents<MainServer>>(&mut self.client_app) {
+            for greeting in events.read::<UnorderedReliableChannel, Greeting>() {
+                observed.main_greetings.push(greeting.text);
+            }
+        }
+        for events in drain::<MessageEvents<ChatServer>>(&mut self.client_app) {
+            for greeting in events.read::<UnorderedReliableChannel, Greeting>() {
+                observed.chat_greetings.push(greeting.text);
+            }
+        }
+        for SpawnEntityEvent(_, entity) in drain(&mut self.main_server_app) {
+            observed.main_server_spawns.push(entity);
+        }
+        for SpawnEntityEvent(_, entity) in drain(&mut self.chat_server_app) {
+            observed.chat_server_spawns.push(entity);
+        }
+        for events in drain::<InsertComponentEvents>(&mut self.main_server_app) {
+            observed.main_server_inserts += events.read::<Position>().len();
+        }
+        observed.main_server_despawns +=
+            drain::<DespawnEntityEvent>(&mut self.main_server_app).len();
+    }
+
+    fn update_until(&mut self, condition: impl Fn(&mut Self) -> bool) {
+        for _ in 0..400 {
+            if condition(self) {
+                return;
+            }
+            self.update();
+        }
+        assert!(condition(self), "condition was never met");
+    }
+
+    fn connect_both(&mut self) {
+        self.update_until(|harness| {
+            is_connected::<MainServer>(&mut harness.client_app)
+                && is_connected::<ChatServer>(&mut harness.client_app)
+        });
+    }
+}
+
+#[test]
+fn connections_receive_only_their_own_events() {
+    let mut harness = Harness::new();
+    harness.connect_both();
+
+    send_greeting(&mut harness.main_server_app, "from main");
+    send_greeting(&mut harness.chat_server_app, "from chat");
+    harness.update_until(|harness| {
+        !harness.observed.main_greetings.is_empty() && !harness.observed.chat_greetings.is_empty()
+    });
+
+    // give any misrouted events time to show up
+    for _ in 0..10 {
+        harness.update();
+    }
+
+    assert_eq!(harness.observed.main_connects, 1);
+    assert_eq!(harness.observed.chat_connects, 1);
+    assert_eq!(
+        harness.observed.main_greetings,
+        vec!["from main".to_string()]
+    );
+    assert_eq!(
+        harness.observed.chat_greetings,
+        vec!["from chat".to_string()]
+    );
+}
+
+#[test]
+fn entities_replicate_only_to_their_own_connection() {
+    let mut harness = Harness::new();
+    harness.connect_both();
+
+    // spawn an Entity replicated to the main server only
+    let entity = {
+        let world = harness.client_app.world_mut();
+        let mut state: SystemState<(Commands, Client<MainServer>)> = SystemState::new(world);
+        let (mut commands, mut client) = state.get_mut(world);
+        let entity = commands
+            .spawn_empty()
+            .enable_replication(&mut client)
+            .insert(Position::new_complete(3))
+            .id();
+        state.apply(world);
+        entity
+    };
+
+    harness.update_until(|harness| harness.observed.main_server_inserts > 0);
+
+    harness.client_app.world_mut().despawn(entity);
+    harness.update_until(|harness| harness.observed.main_server_despawns > 0);
+
+    for _ in 0..10 {
+        harness.update();
+    }
+
+    assert_eq!(harness.observed.main_server_spawns.len(), 1);
+    assert_eq!(harness.observed.main_server_inserts, 1);
+    assert_eq!(harness.observed.main_server_despawns, 1);
+    assert!(harness.observed.chat_server_spawns.is_empty());
+}
@@ -7,7 +7,7 @@ use bevy_ecs::{
 use naia_bevy_shared::{EntityAuthStatus, HostOwned, WorldProxyMut};
 use naia_server::{ReplicationConfig, UserKey};
 
-use crate::{plugin::Singleton, server::ServerWrapper, Server};
+use crate::{plugin::Singleton, server::ServerWrapper, systems::sync_client_owned, Server};
 
 // Bevy Commands Extension
 pub trait CommandsExt<'a> {
@@ -22,6 +22,11 @@ pub trait CommandsExt<'a> {
         user_key: &UserKey,
     ) -> &'a mut EntityCommands<'a>;
     fn take_authority(&'a mut self, server: &mut Server) -> &'a mut EntityCommands<'a>;
+    fn spawn_owned_by(
+        &'a mut self,
+        server: &mut Server,
+        user_key: &UserKey,
+    ) -> &'a mut EntityCommands<'a>;
     fn authority(&'a self, server: &Server) -> Option<EntityAuthStatus>;
     fn pause_replication(&'a mut self, server: &mut Server) -> &'a mut EntityCommands<'a>;
     fn resume_replication(&'a mut self, server: &mut Server) -> &'a mut EntityCommands<'a>;
@@ -52,15 +57,19 @@ impl<'a> CommandsExt<'a> for EntityCommands<'a> {
     }
 
     fn replication_config(&'a self, server: &Server) -> Option<ReplicationConfig> {
-        server.replication_config(&self.id())
+        server.entity_replication_config(&self.id())
     }
 
     fn give_authority(
         &'a mut self,
         _server: &mut Server,
-        _user_key: &UserKey,
+        user_key: &UserKey,
     ) -> &'a mut EntityCommands<'a> {
-        todo!()
+        let entity = self.id();
+        let mut commands = self.commands();
+        let command = GiveAuthorityCommand::new(entity, *user_key);
+        commands.queue(command);
+        self
     }
 
     fn take_authority(&'a mut self, server: &mut Server) -> &'a mut EntityCommands<'a> {
@@ -68,6 +77,20 @@ impl<'a> CommandsExt<'a> for EntityCommands<'a> {
         return self;
     }
 
+    fn spawn_owned_by(
+        &'a mut self,
+        server: &mut Server,
+        user_key: &UserKey,
+    ) -> &'a mut EntityCommands<'a> {
+        server.enable_replication(&self.id());
+        self.insert(HostOwned::new::<Singleton>());
+        let entity = self.id();
+        let mut commands = self.commands();
+        let command = GiveAuthorityCommand::new(entity, *user_key);
+        commands.queue(command);
+        self
+    }
+
     fn authority(&'a self, server: &Server) -> Option<EntityAuthStatus> {
         server.entity_authority_status(&self.id())
     }
@@ -103,6 +126,39 @@ impl BevyCommand for ConfigureReplicationCommand {
                 &self.entity,
                 self.config,
             );
+            sync_client_owned(world, &server.0, &self.entity);
+        });
+    }
+}
+
+//// GiveAuthorityCommand Command ////
+pub(crate) struct GiveAuthorityCommand {
+    entity: Entity,
+    user_key: UserKey,
+}
+
+impl GiveAuthorityCommand {
+    pub fn new(entity: Entity, user_key: UserKey) -> Self {
+        Self { entity, user_key }
+    }
+}
+
+impl BevyCommand for GiveAuthorityCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_scope(|world, mut server: Mut<ServerWrapper>| {
+            if server.0.entity_replication_config(&self.entity)
+                != Some(ReplicationConfig::Delegated)
+            {
+                server.0.configure_entity_replication(
+                    &mut world.proxy_mut(),
+                    &self.entity,
+                    ReplicationConfig::Delegated,
+                );
+            }
+            if let Err(error) = server.0.entity_give_authority(&self.user_key, &self.entity) {
+                panic!("{}", error);
+            }
+            sync_client_owned(world, &server.0, &self.entity);
         });
     }
 }
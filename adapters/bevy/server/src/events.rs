@@ -8,10 +8,14 @@ use bevy_ecs::{
 };
 
 use naia_bevy_shared::{
-    Channel, ChannelKind, ComponentKind, Message, MessageContainer, MessageKind, Replicate,
-    Request, ResponseSendKey, Tick,
+    Channel, ChannelKind, ComponentKind, ConnectionParams, ConnectionQualityLevel, Message,
+    MessageContainer, MessageKind, MessageReceiptKey, Replicate, Request, ResponseReceiveKey,
+    ResponseSendKey, Tick,
+};
+use naia_server::{
+    shared::{GameInstant, GlobalRequestId, GlobalResponseId},
+    Events, NaiaServerError, User, UserKey,
 };
-use naia_server::{shared::GlobalResponseId, Events, NaiaServerError, User, UserKey};
 
 // ConnectEvent
 #[derive(Event)]
@@ -25,6 +29,26 @@ pub struct DisconnectEvent(pub UserKey, pub User);
 #[derive(Event)]
 pub struct ErrorEvent(pub NaiaServerError);
 
+// ConnectionQualityChangedEvent
+#[derive(Event)]
+pub struct ConnectionQualityChangedEvent(pub UserKey, pub ConnectionQualityLevel);
+
+// ConnectionReadyEvent
+#[derive(Event)]
+pub struct ConnectionReadyEvent(pub UserKey, pub ConnectionParams);
+
+// InputGapEvent
+#[derive(Event)]
+pub struct InputGapEvent(pub UserKey, pub Tick, pub u16);
+
+// StreamMessageEvent
+#[derive(Event)]
+pub struct StreamMessageEvent(pub UserKey, pub Vec<u8>);
+
+// MessageDeliveredEvent
+#[derive(Event)]
+pub struct MessageDeliveredEvent(pub UserKey, pub MessageReceiptKey);
+
 // TickEventReader
 #[derive(Resource)]
 pub(crate) struct CachedTickEventsState {
@@ -87,6 +111,25 @@ impl MessageEvents {
 
         Vec::new()
     }
+
+    /// Like `read()`, but also gives the GameInstant each Message was sent
+    /// at, on the Server's clock, if Channel C tracks latency
+    pub fn read_timed<C: Channel, M: Message>(&self) -> Vec<(UserKey, Option<GameInstant>, M)> {
+        let channel_kind = ChannelKind::of::<C>();
+        let Some(message_map) = self.inner.get(&channel_kind) else {
+            return Vec::new();
+        };
+        let message_kind = MessageKind::of::<M>();
+        let Some(messages) = message_map.get(&message_kind) else {
+            return Vec::new();
+        };
+        let send_instants = messages.iter().map(|(_, message)| message.send_instant());
+        convert_messages::<M>(messages)
+            .into_iter()
+            .zip(send_instants)
+            .map(|((user_key, message), send_instant)| (user_key, send_instant, message))
+            .collect()
+    }
 }
 
 fn convert_messages<M: Message>(
@@ -151,6 +194,34 @@ impl RequestEvents {
     }
 }
 
+// RequestTimeoutEvents
+#[derive(Event)]
+pub struct RequestTimeoutEvents {
+    inner: HashMap<MessageKind, Vec<(UserKey, GlobalRequestId)>>,
+}
+
+impl<E: Copy> From<&mut Events<E>> for RequestTimeoutEvents {
+    fn from(events: &mut Events<E>) -> Self {
+        Self {
+            inner: events.take_request_timeouts(),
+        }
+    }
+}
+
+impl RequestTimeoutEvents {
+    pub fn read<Q: Request>(&self) -> Vec<(UserKey, ResponseReceiveKey<Q::Response>)> {
+        let request_kind = MessageKind::of::<Q>();
+        let Some(request_ids) = self.inner.get(&request_kind) else {
+            return Vec::new();
+        };
+
+        request_ids
+            .iter()
+            .map(|(user_key, request_id)| (*user_key, ResponseReceiveKey::new(*request_id)))
+            .collect()
+    }
+}
+
 // SpawnEntityEvent
 #[derive(Event)]
 pub struct SpawnEntityEvent(pub UserKey, pub Entity);
@@ -1,11 +1,15 @@
-pub use naia_bevy_shared::{EntityAuthStatus, Random, ReceiveEvents, Replicate, Tick};
+pub use naia_bevy_shared::{
+    ChangeDetectionMode, ConnectionQuality, ConnectionQualityLevel, EntityAuthStatus, Random,
+    ReceiveEvents, Replicate, Tick, TickExt,
+};
 pub use naia_server::{
     shared::{
         default_channels, BigMap, BigMapKey, BitReader, BitWrite, BitWriter, ConstBitLength,
-        FileBitWriter, ResponseReceiveKey, SerdeErr, SignedInteger, SignedVariableInteger,
-        UnsignedInteger, UnsignedVariableInteger,
+        FileBitWriter, ResponseReceiveKey, SerdeEnum, SerdeErr, SignedInteger,
+        SignedVariableInteger, UnsignedInteger, UnsignedVariableInteger,
     },
-    transport, ReplicationConfig, RoomKey, SerdeBevy as Serde, ServerConfig, UserKey,
+    transport, EntityOwner, IncomingPacketTap, OutgoingPacketTap, ReplicationConfig, RoomKey,
+    SerdeBevy as Serde, ServerConfig, UserKey,
 };
 
 pub mod events;
@@ -15,8 +19,10 @@ mod components;
 mod plugin;
 mod server;
 mod systems;
+mod tick_buffer;
 
 pub use commands::CommandsExt;
 pub use components::{ClientOwned, ServerOwned};
 pub use plugin::Plugin;
 pub use server::Server;
+pub use tick_buffer::TickBuffer;
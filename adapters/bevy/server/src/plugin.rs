@@ -3,17 +3,22 @@ use std::{ops::DerefMut, sync::Mutex};
 use bevy_app::{App, Last, Plugin as PluginType, Startup, Update};
 use bevy_ecs::{entity::Entity, schedule::IntoSystemConfigs};
 
-use naia_bevy_shared::{BeforeReceiveEvents, Protocol, SendPackets, SharedPlugin};
+use naia_bevy_shared::{
+    BeforeReceiveEvents, ChangeDetectionMode, Protocol, SendPackets, SharedPlugin,
+};
 use naia_server::{Server, ServerConfig};
 
 use super::{
     events::{
-        AuthEvents, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent,
-        InsertComponentEvents, MessageEvents, PublishEntityEvent, RemoveComponentEvents,
-        RequestEvents, SpawnEntityEvent, TickEvent, UnpublishEntityEvent, UpdateComponentEvents,
+        AuthEvents, ConnectEvent, ConnectionQualityChangedEvent, ConnectionReadyEvent,
+        DespawnEntityEvent, DisconnectEvent, ErrorEvent, InputGapEvent, InsertComponentEvents,
+        MessageDeliveredEvent, MessageEvents, PublishEntityEvent, RemoveComponentEvents,
+        RequestEvents, RequestTimeoutEvents, SpawnEntityEvent, StreamMessageEvent, TickEvent,
+        UnpublishEntityEvent, UpdateComponentEvents,
     },
     server::ServerWrapper,
     systems::{before_receive_events, send_packets, send_packets_init},
+    tick_buffer::TickBufferMessagesResource,
 };
 
 struct PluginConfig {
@@ -35,6 +40,7 @@ pub struct Singleton;
 
 pub struct Plugin {
     config: Mutex<Option<PluginConfig>>,
+    change_detection: ChangeDetectionMode,
 }
 
 impl Plugin {
@@ -42,8 +48,15 @@ impl Plugin {
         let config = PluginConfig::new(server_config, protocol);
         Self {
             config: Mutex::new(Some(config)),
+            change_detection: ChangeDetectionMode::default(),
         }
     }
+
+    /// See `SharedPlugin::with_change_detection()`
+    pub fn with_change_detection(mut self, change_detection: ChangeDetectionMode) -> Self {
+        self.change_detection = change_detection;
+        self
+    }
 }
 
 impl PluginType for Plugin {
@@ -59,16 +72,25 @@ impl PluginType for Plugin {
 
         app
             // SHARED PLUGIN //
-            .add_plugins(SharedPlugin::<Singleton>::new())
+            .add_plugins(
+                SharedPlugin::<Singleton>::new().with_change_detection(self.change_detection),
+            )
             // RESOURCES //
             .insert_resource(server)
+            .init_resource::<TickBufferMessagesResource>()
             // EVENTS //
             .add_event::<ConnectEvent>()
             .add_event::<DisconnectEvent>()
             .add_event::<ErrorEvent>()
+            .add_event::<ConnectionQualityChangedEvent>()
+            .add_event::<ConnectionReadyEvent>()
+            .add_event::<InputGapEvent>()
             .add_event::<TickEvent>()
             .add_event::<MessageEvents>()
+            .add_event::<StreamMessageEvent>()
+            .add_event::<MessageDeliveredEvent>()
             .add_event::<RequestEvents>()
+            .add_event::<RequestTimeoutEvents>()
             .add_event::<AuthEvents>()
             .add_event::<SpawnEntityEvent>()
             .add_event::<DespawnEntityEvent>()
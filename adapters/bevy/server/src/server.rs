@@ -6,14 +6,17 @@ use bevy_ecs::{
 };
 
 use naia_server::{
-    shared::SocketConfig, transport::Socket, NaiaServerError, ReplicationConfig, RoomKey, RoomMut,
-    RoomRef, Server as NaiaServer, TickBufferMessages, UserKey, UserMut, UserRef, UserScopeMut,
-    UserScopeRef,
+    shared::{ChannelLatencyStats, GameInstant, SocketConfig},
+    transport::Socket,
+    EntityOwner, IncomingPacketTap, NaiaServerError, OutgoingPacketTap, ReplicationConfig, RoomKey,
+    RoomMut, RoomRef, Server as NaiaServer, TickBufferMessages, UserKey, UserMut, UserRef,
+    UserScopeMut, UserScopeRef,
 };
 
 use naia_bevy_shared::{
-    Channel, EntityAndGlobalEntityConverter, EntityAuthStatus, EntityDoesNotExistError,
-    GlobalEntity, Message, Request, Response, ResponseReceiveKey, ResponseSendKey, Tick,
+    Channel, ConnectionQuality, EntityAndGlobalEntityConverter, EntityAuthStatus,
+    EntityDoesNotExistError, GlobalEntity, Message, MessageReceiptKey, Request, Response,
+    ResponseReceiveKey, ResponseSendKey, Tick,
 };
 
 #[derive(Resource)]
@@ -35,10 +38,30 @@ impl<'w> Server<'w> {
         self.server.0.listen(socket);
     }
 
+    pub fn listen_named<S: Into<Box<dyn Socket>>>(&mut self, name: &str, socket: S) {
+        self.server.0.listen_named(name, socket);
+    }
+
     pub fn is_listening(&self) -> bool {
         self.server.0.is_listening()
     }
 
+    pub fn sockets_count(&self) -> usize {
+        self.server.0.sockets_count()
+    }
+
+    pub fn user_socket_index(&self, user_key: &UserKey) -> Option<usize> {
+        self.server.0.user_socket_index(user_key)
+    }
+
+    pub fn user_socket_name(&self, user_key: &UserKey) -> Option<&str> {
+        self.server.0.user_socket_name(user_key)
+    }
+
+    pub fn socket_index(&self, name: &str) -> Option<usize> {
+        self.server.0.socket_index(name)
+    }
+
     pub fn accept_connection(&mut self, user_key: &UserKey) {
         self.server.0.accept_connection(user_key);
     }
@@ -47,21 +70,58 @@ impl<'w> Server<'w> {
         self.server.0.reject_connection(user_key);
     }
 
+    pub fn reject_with_status(&mut self, user_key: &UserKey, status: u16) {
+        self.server.0.reject_with_status(user_key, status);
+    }
+
     // Config
     pub fn socket_config(&self) -> &SocketConfig {
         self.server.0.socket_config()
     }
 
+    pub fn set_outgoing_tap(&mut self, tap: Option<OutgoingPacketTap>) {
+        self.server.0.set_outgoing_tap(tap);
+    }
+
+    pub fn set_incoming_tap(&mut self, tap: Option<IncomingPacketTap>) {
+        self.server.0.set_incoming_tap(tap);
+    }
+
+    pub fn set_rng_seed(&mut self, seed: u64) {
+        self.server.0.set_rng_seed(seed);
+    }
+
     //// Messages ////
     pub fn send_message<C: Channel, M: Message>(&mut self, user_key: &UserKey, message: &M) {
         self.server.0.send_message::<C, M>(user_key, message)
     }
 
+    pub fn send_message_with_receipt<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) -> Result<MessageReceiptKey, NaiaServerError> {
+        self.server
+            .0
+            .send_message_with_receipt::<C, M>(user_key, message)
+    }
+
+    pub fn receipt_received(&self, receipt_key: &MessageReceiptKey) -> bool {
+        self.server.0.receipt_received(receipt_key)
+    }
+
+    pub fn send_stream(&mut self, user_key: &UserKey, bytes: &[u8]) {
+        self.server.0.send_stream(user_key, bytes);
+    }
+
     /// Sends a message to all connected users using a given channel
     pub fn broadcast_message<C: Channel, M: Message>(&mut self, message: &M) {
         self.server.0.broadcast_message::<C, M>(message);
     }
 
+    /// The Plugin already receives the Messages for each Tick as it happens,
+    /// so this returns nothing for a Tick it has processed. Prefer the
+    /// `TickBuffer` system param
     pub fn receive_tick_buffer_messages(&mut self, tick: &Tick) -> TickBufferMessages {
         self.server.0.receive_tick_buffer_messages(tick)
     }
@@ -75,6 +135,17 @@ impl<'w> Server<'w> {
         self.server.0.send_request::<C, Q>(user_key, request)
     }
 
+    pub fn send_request_with_timeout<C: Channel, Q: Request>(
+        &mut self,
+        user_key: &UserKey,
+        request: &Q,
+        timeout: Duration,
+    ) -> Result<ResponseReceiveKey<Q::Response>, NaiaServerError> {
+        self.server
+            .0
+            .send_request_with_timeout::<C, Q>(user_key, request, timeout)
+    }
+
     pub fn send_response<S: Response>(
         &mut self,
         response_key: &ResponseSendKey<S>,
@@ -96,6 +167,14 @@ impl<'w> Server<'w> {
         self.server.0.scope_checks()
     }
 
+    pub fn scope_changes(&mut self) -> impl Iterator<Item = (RoomKey, UserKey, Entity)> {
+        self.server.0.scope_changes()
+    }
+
+    pub fn reevaluate_all_scopes(&mut self) {
+        self.server.0.reevaluate_all_scopes();
+    }
+
     //// Users ////
 
     pub fn user_exists(&self, user_key: &UserKey) -> bool {
@@ -152,6 +231,10 @@ impl<'w> Server<'w> {
         self.server.0.rooms_count()
     }
 
+    pub fn entity_rooms(&self, entity: &Entity) -> Vec<RoomKey> {
+        self.server.0.entity_rooms(entity)
+    }
+
     //// Ticks ////
 
     pub fn current_tick(&self) -> Tick {
@@ -162,6 +245,10 @@ impl<'w> Server<'w> {
         self.server.0.average_tick_duration()
     }
 
+    pub fn game_time_now(&self) -> GameInstant {
+        self.server.0.game_time_now()
+    }
+
     //// Network Conditions ////
 
     pub fn jitter(&self, user_key: &UserKey) -> Option<f32> {
@@ -172,6 +259,48 @@ impl<'w> Server<'w> {
         self.server.0.rtt(user_key)
     }
 
+    pub fn connection_quality(&mut self, user_key: &UserKey) -> Option<ConnectionQuality> {
+        self.server.0.connection_quality(user_key)
+    }
+
+    pub fn channel_latency<C: Channel>(&self) -> Option<ChannelLatencyStats> {
+        self.server.0.channel_latency::<C>()
+    }
+
+    //// Entities ////
+
+    pub fn entity_owner(&self, entity: &Entity) -> EntityOwner {
+        self.server.0.entity_owner(entity)
+    }
+
+    pub fn user_owned_entities(&self, user_key: &UserKey) -> Vec<Entity> {
+        self.server.0.user_owned_entities(user_key)
+    }
+
+    pub fn server_owned_entities(&self) -> Vec<Entity> {
+        self.server.0.server_owned_entities()
+    }
+
+    pub fn entity_authority_status(&self, entity: &Entity) -> Option<EntityAuthStatus> {
+        self.server.0.entity_authority_status(entity)
+    }
+
+    pub fn entity_replication_config(&self, entity: &Entity) -> Option<ReplicationConfig> {
+        self.server.0.entity_replication_config(entity)
+    }
+
+    pub fn despawn_entity_after_ack(&mut self, entity: &Entity) -> Result<(), NaiaServerError> {
+        self.server.0.despawn_entity_after_ack(entity)
+    }
+
+    pub fn entity_despawn_pending(&self, entity: &Entity) -> bool {
+        self.server.0.entity_despawn_pending(entity)
+    }
+
+    pub fn set_entity_auth_idle_timeout(&mut self, entity: &Entity, timeout: Option<Duration>) {
+        self.server.0.entity_set_auth_idle_timeout(entity, timeout);
+    }
+
     // Entity Replication
 
     pub(crate) fn enable_replication(&mut self, entity: &Entity) {
@@ -190,17 +319,9 @@ impl<'w> Server<'w> {
         self.server.0.resume_entity_replication(entity);
     }
 
-    pub(crate) fn replication_config(&self, entity: &Entity) -> Option<ReplicationConfig> {
-        self.server.0.entity_replication_config(entity)
-    }
-
     pub(crate) fn entity_take_authority(&mut self, entity: &Entity) {
         self.server.0.entity_take_authority(entity);
     }
-
-    pub(crate) fn entity_authority_status(&self, entity: &Entity) -> Option<EntityAuthStatus> {
-        self.server.0.entity_authority_status(entity)
-    }
 }
 
 impl<'w> EntityAndGlobalEntityConverter<Entity> for Server<'w> {
@@ -1,6 +1,7 @@
 use std::ops::DerefMut;
 
 use bevy_ecs::{
+    entity::Entity,
     event::{EventReader, Events},
     system::SystemState,
     world::{Mut, World},
@@ -9,23 +10,29 @@ use bevy_ecs::{
 use log::warn;
 
 use naia_bevy_shared::{HostOwned, HostSyncEvent, WorldMutType, WorldProxy, WorldProxyMut};
-use naia_server::EntityOwner;
+use naia_server::{EntityOwner, Server as NaiaServer};
 
-use crate::{plugin::Singleton, server::ServerWrapper, ClientOwned, EntityAuthStatus};
+use crate::{
+    plugin::Singleton, server::ServerWrapper, tick_buffer::TickBufferMessagesResource, ClientOwned,
+    EntityAuthStatus,
+};
 
 mod naia_events {
     pub use naia_server::{
-        ConnectEvent, DelegateEntityEvent, DespawnEntityEvent, DisconnectEvent,
-        EntityAuthGrantEvent, EntityAuthResetEvent, ErrorEvent, PublishEntityEvent,
-        SpawnEntityEvent, TickEvent, UnpublishEntityEvent,
+        ConnectEvent, ConnectionQualityChangedEvent, ConnectionReadyEvent, DelegateEntityEvent,
+        DespawnEntityEvent, DisconnectEvent, EntityAuthGrantEvent, EntityAuthResetEvent,
+        ErrorEvent, InputGapEvent, MessageDeliveredEvent, PublishEntityEvent, SpawnEntityEvent,
+        StreamMessageEvent, TickEvent, UnpublishEntityEvent,
     };
 }
 
 mod bevy_events {
     pub use crate::events::{
-        AuthEvents, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent,
-        InsertComponentEvents, MessageEvents, PublishEntityEvent, RemoveComponentEvents,
-        RequestEvents, SpawnEntityEvent, TickEvent, UnpublishEntityEvent, UpdateComponentEvents,
+        AuthEvents, ConnectEvent, ConnectionQualityChangedEvent, ConnectionReadyEvent,
+        DespawnEntityEvent, DisconnectEvent, ErrorEvent, InputGapEvent, InsertComponentEvents,
+        MessageDeliveredEvent, MessageEvents, PublishEntityEvent, RemoveComponentEvents,
+        RequestEvents, RequestTimeoutEvents, SpawnEntityEvent, StreamMessageEvent, TickEvent,
+        UnpublishEntityEvent, UpdateComponentEvents,
     };
 }
 
@@ -38,10 +45,10 @@ pub fn before_receive_events(world: &mut World) {
         }
 
         // Host Component Updates
-        let mut host_component_event_reader = world
-            .get_resource_mut::<Events<HostSyncEvent>>()
-            .unwrap();
-        let host_component_events: Vec<HostSyncEvent> = host_component_event_reader.drain().collect();
+        let mut host_component_event_reader =
+            world.get_resource_mut::<Events<HostSyncEvent>>().unwrap();
+        let host_component_events: Vec<HostSyncEvent> =
+            host_component_event_reader.drain().collect();
         for event in host_component_events {
             match event {
                 HostSyncEvent::Insert(_host_id, entity, component_kind) => {
@@ -50,18 +57,25 @@ pub fn before_receive_events(world: &mut World) {
                         continue;
                     }
                     let mut world_proxy = world.proxy_mut();
-                    let Some(mut component_mut) = world_proxy.component_mut_of_kind(&entity, &component_kind) else {
+                    let Some(mut component_mut) =
+                        world_proxy.component_mut_of_kind(&entity, &component_kind)
+                    else {
                         warn!("could not find Component in World which has just been inserted!");
                         continue;
                     };
-                    server.0.insert_component_worldless(&entity, DerefMut::deref_mut(&mut component_mut));
+                    server.0.insert_component_worldless(
+                        &entity,
+                        DerefMut::deref_mut(&mut component_mut),
+                    );
                 }
                 HostSyncEvent::Remove(_host_id, entity, component_kind) => {
                     if server.0.entity_authority_status(&entity) == Some(EntityAuthStatus::Denied) {
                         // if auth status is denied, that means the client is performing this operation and it's already being handled
                         continue;
                     }
-                    server.0.remove_component_worldless(&entity, &component_kind);
+                    server
+                        .0
+                        .remove_component_worldless(&entity, &component_kind);
                 }
                 HostSyncEvent::Despawn(_host_id, entity) => {
                     if server.0.entity_authority_status(&entity) == Some(EntityAuthStatus::Denied) {
@@ -73,10 +87,12 @@ pub fn before_receive_events(world: &mut World) {
             }
         }
 
+        // Tick Buffer Messages are only kept for the frame their Tick happened in
+        world.resource_mut::<TickBufferMessagesResource>().clear();
+
         // Receive Events
         let mut events = server.0.receive(world.proxy_mut());
         if !events.is_empty() {
-
             // Connect Event
             if events.has::<naia_events::ConnectEvent>() {
                 let mut event_writer = world
@@ -87,6 +103,16 @@ pub fn before_receive_events(world: &mut World) {
                 }
             }
 
+            // Connection Ready Event
+            if events.has::<naia_events::ConnectionReadyEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::ConnectionReadyEvent>>()
+                    .unwrap();
+                for (user_key, params) in events.read::<naia_events::ConnectionReadyEvent>() {
+                    event_writer.send(bevy_events::ConnectionReadyEvent(user_key, params));
+                }
+            }
+
             // Disconnect Event
             if events.has::<naia_events::DisconnectEvent>() {
                 let mut event_writer = world
@@ -107,13 +133,69 @@ pub fn before_receive_events(world: &mut World) {
                 }
             }
 
+            // Connection Quality Changed Event
+            if events.has::<naia_events::ConnectionQualityChangedEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::ConnectionQualityChangedEvent>>()
+                    .unwrap();
+                for (user_key, level) in events.read::<naia_events::ConnectionQualityChangedEvent>()
+                {
+                    event_writer.send(bevy_events::ConnectionQualityChangedEvent(user_key, level));
+                }
+            }
+
+            // Input Gap Event
+            if events.has::<naia_events::InputGapEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::InputGapEvent>>()
+                    .unwrap();
+                for (user_key, tick, consecutive_misses) in
+                    events.read::<naia_events::InputGapEvent>()
+                {
+                    event_writer.send(bevy_events::InputGapEvent(
+                        user_key,
+                        tick,
+                        consecutive_misses,
+                    ));
+                }
+            }
+
             // Tick Event
             if events.has::<naia_events::TickEvent>() {
                 let mut event_writer = world
                     .get_resource_mut::<Events<bevy_events::TickEvent>>()
                     .unwrap();
-                for tick in events.read::<naia_events::TickEvent>() {
-                    event_writer.send(bevy_events::TickEvent(tick));
+                let ticks: Vec<_> = events.read::<naia_events::TickEvent>().collect();
+                for tick in &ticks {
+                    event_writer.send(bevy_events::TickEvent(*tick));
+                }
+
+                // Tick Buffer Messages, for each Tick sent above
+                for tick in ticks {
+                    let messages = server.0.receive_tick_buffer_messages(&tick);
+                    world
+                        .resource_mut::<TickBufferMessagesResource>()
+                        .push(tick, messages);
+                }
+            }
+
+            // Stream Message Event
+            if events.has::<naia_events::StreamMessageEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::StreamMessageEvent>>()
+                    .unwrap();
+                for (user_key, bytes) in events.read::<naia_events::StreamMessageEvent>() {
+                    event_writer.send(bevy_events::StreamMessageEvent(user_key, bytes));
+                }
+            }
+
+            // Message Delivered Event
+            if events.has::<naia_events::MessageDeliveredEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::MessageDeliveredEvent>>()
+                    .unwrap();
+                for (user_key, receipt_key) in events.read::<naia_events::MessageDeliveredEvent>() {
+                    event_writer.send(bevy_events::MessageDeliveredEvent(user_key, receipt_key));
                 }
             }
 
@@ -133,6 +215,14 @@ pub fn before_receive_events(world: &mut World) {
                 event_writer.send(bevy_events::RequestEvents::from(&mut events));
             }
 
+            // Request Timeout Event
+            if events.has_request_timeouts() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::RequestTimeoutEvents>>()
+                    .unwrap();
+                event_writer.send(bevy_events::RequestTimeoutEvents::from(&mut events));
+            }
+
             // Auth Event
             if events.has_auths() {
                 let mut event_writer = world
@@ -152,10 +242,7 @@ pub fn before_receive_events(world: &mut World) {
                     event_writer.send(bevy_events::SpawnEntityEvent(user_key, entity));
                 }
                 for entity in spawned_entities {
-                    let EntityOwner::Client(user_key) = server.0.entity_owner(&entity) else {
-                        panic!("spawned entity that doesn't belong to a client ... shouldn't be possible.");
-                    };
-                    world.entity_mut(entity).insert(ClientOwned(user_key));
+                    sync_client_owned(world, &server.0, &entity);
                 }
             }
 
@@ -174,9 +261,14 @@ pub fn before_receive_events(world: &mut World) {
                 let mut event_writer = world
                     .get_resource_mut::<Events<bevy_events::PublishEntityEvent>>()
                     .unwrap();
+                let mut changed_entities = Vec::new();
                 for (user_key, entity) in events.read::<naia_events::PublishEntityEvent>() {
+                    changed_entities.push(entity);
                     event_writer.send(bevy_events::PublishEntityEvent(user_key, entity));
                 }
+                for entity in changed_entities {
+                    sync_client_owned(world, &server.0, &entity);
+                }
             }
 
             // Unpublish Entity Event
@@ -184,15 +276,23 @@ pub fn before_receive_events(world: &mut World) {
                 let mut event_writer = world
                     .get_resource_mut::<Events<bevy_events::UnpublishEntityEvent>>()
                     .unwrap();
+                let mut changed_entities = Vec::new();
                 for (user_key, entity) in events.read::<naia_events::UnpublishEntityEvent>() {
+                    changed_entities.push(entity);
                     event_writer.send(bevy_events::UnpublishEntityEvent(user_key, entity));
                 }
+                for entity in changed_entities {
+                    sync_client_owned(world, &server.0, &entity);
+                }
             }
 
             // Delegate Entity Event
             if events.has::<naia_events::DelegateEntityEvent>() {
                 for (_, entity) in events.read::<naia_events::DelegateEntityEvent>() {
-                    world.entity_mut(entity).insert(HostOwned::new::<Singleton>());
+                    world
+                        .entity_mut(entity)
+                        .insert(HostOwned::new::<Singleton>());
+                    sync_client_owned(world, &server.0, &entity);
                 }
             }
 
@@ -200,15 +300,17 @@ pub fn before_receive_events(world: &mut World) {
             if events.has::<naia_events::EntityAuthGrantEvent>() {
                 for (_, entity) in events.read::<naia_events::EntityAuthGrantEvent>() {
                     world.entity_mut(entity).remove::<HostOwned>();
+                    sync_client_owned(world, &server.0, &entity);
                 }
             }
 
             // Entity Auth Reset Event
             if events.has::<naia_events::EntityAuthResetEvent>() {
-                for entity in events.read::<naia_events::EntityAuthResetEvent>() {
+                for (entity, _) in events.read::<naia_events::EntityAuthResetEvent>() {
                     if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
                         entity_mut.insert(HostOwned::new::<Singleton>());
                     }
+                    sync_client_owned(world, &server.0, &entity);
                 }
             }
 
@@ -227,8 +329,7 @@ pub fn before_receive_events(world: &mut World) {
                 let mut event_writer = world
                     .get_resource_mut::<Events<bevy_events::UpdateComponentEvents>>()
                     .unwrap();
-                event_writer
-                    .send(bevy_events::UpdateComponentEvents::new(updates));
+                event_writer.send(bevy_events::UpdateComponentEvents::new(updates));
             }
 
             // Remove Component Event
@@ -244,6 +345,23 @@ pub fn before_receive_events(world: &mut World) {
     });
 }
 
+/// Keeps the `ClientOwned` marker on an Entity in line with its current owner
+pub(crate) fn sync_client_owned(world: &mut World, server: &NaiaServer<Entity>, entity: &Entity) {
+    let Ok(mut entity_mut) = world.get_entity_mut(*entity) else {
+        return;
+    };
+    match server.entity_owner(entity) {
+        EntityOwner::Client(user_key)
+        | EntityOwner::ClientWaiting(user_key)
+        | EntityOwner::ClientPublic(user_key) => {
+            entity_mut.insert(ClientOwned(user_key));
+        }
+        EntityOwner::Server | EntityOwner::Local => {
+            entity_mut.remove::<ClientOwned>();
+        }
+    }
+}
+
 pub fn send_packets_init(world: &mut World) {
     let tick_event_state: SystemState<EventReader<bevy_events::TickEvent>> =
         SystemState::new(world);
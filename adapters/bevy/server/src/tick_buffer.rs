@@ -0,0 +1,60 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{ResMut, Resource, SystemParam};
+
+use naia_bevy_shared::{Channel, ChannelKind, Message, Tick};
+use naia_server::{TickBufferMessages, UserKey};
+
+/// Tick-buffered Messages for every Tick the Server processed this frame.
+/// Filled by the Plugin in `BeforeReceiveEvents`, right after each `TickEvent`
+/// is sent, so both are visible to systems in `ReceiveEvents`
+#[derive(Resource, Default)]
+pub struct TickBufferMessagesResource {
+    ticks: Vec<(Tick, TickBufferMessages)>,
+}
+
+impl TickBufferMessagesResource {
+    pub(crate) fn clear(&mut self) {
+        self.ticks.clear();
+    }
+
+    pub(crate) fn push(&mut self, tick: Tick, messages: TickBufferMessages) {
+        self.ticks.push((tick, messages));
+    }
+}
+
+// TickBuffer
+
+/// Reads the Messages of type `M` sent through the tick-buffered Channel `C`,
+/// for each Tick the Server processed this frame
+#[derive(SystemParam)]
+pub struct TickBuffer<'w, C: Channel, M: Message> {
+    messages: ResMut<'w, TickBufferMessagesResource>,
+    phantom: PhantomData<(C, M)>,
+}
+
+impl<'w, C: Channel, M: Message> TickBuffer<'w, C, M> {
+    /// Takes every `M` received for this frame's Ticks, oldest Tick first.
+    /// Messages are only yielded once, so a second reader of the same Channel
+    /// & Message in the same frame sees nothing
+    pub fn read(&mut self) -> Vec<(UserKey, Tick, M)> {
+        let mut output = Vec::new();
+        for (tick, messages) in self.messages.ticks.iter_mut() {
+            for (user_key, message) in messages.read::<C, M>() {
+                output.push((user_key, *tick, message));
+            }
+        }
+        output
+    }
+
+    /// Returns, per Tick processed this frame, the Users which sent nothing
+    /// through `C` for that Tick
+    pub fn missing(&self) -> Vec<(Tick, Vec<UserKey>)> {
+        let channel_kind = ChannelKind::of::<C>();
+        self.messages
+            .ticks
+            .iter()
+            .map(|(tick, messages)| (*tick, messages.missing_for_tick(&channel_kind)))
+            .collect()
+    }
+}
@@ -0,0 +1,198 @@
+use std::{thread::sleep, time::Duration};
+
+use bevy_app::{App, Update};
+use bevy_ecs::{
+    component::Component,
+    event::Events,
+    system::{Commands, Query, Res, Resource, SystemState},
+};
+
+use naia_bevy_client::{events::UpdateComponentEvents, Client, ClientConfig};
+use naia_bevy_server::{
+    events::AuthEvents, ChangeDetectionMode, CommandsExt, RoomKey, Server, ServerConfig,
+};
+use naia_bevy_shared::{Message, Property, Protocol, Replicate};
+use naia_test::LocalTransport;
+
+#[derive(Message)]
+pub struct Auth;
+
+#[derive(Component, Replicate)]
+pub struct Position {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+}
+
+struct Main;
+
+// The value the Server's system writes to every Position, every frame
+#[derive(Resource)]
+struct WrittenX(i16);
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+// writes unconditionally, as many Bevy systems do
+fn write_position(written_x: Res<WrittenX>, mut query: Query<&mut Position>) {
+    for mut position in query.iter_mut() {
+        *position.x = written_x.0;
+    }
+}
+
+// Apps
+
+fn server_app(transport: &LocalTransport, change_detection: ChangeDetectionMode) -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        naia_bevy_server::Plugin::new(ServerConfig::default(), protocol())
+            .with_change_detection(change_detection),
+    )
+    .insert_resource(WrittenX(3))
+    .add_systems(Update, write_position);
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Server> = SystemState::new(app.world_mut());
+    state
+        .get_mut(app.world_mut())
+        .listen(transport.server_socket());
+    app
+}
+
+fn client_app(transport: &LocalTransport) -> App {
+    let mut app = App::new();
+    let client_config = ClientConfig {
+        send_handshake_interval: Duration::from_millis(10),
+        handshake_pings: 2,
+        ..Default::default()
+    };
+    app.add_plugins(naia_bevy_client::Plugin::<Main>::new(
+        client_config,
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Client<Main>> = SystemState::new(app.world_mut());
+    let mut client = state.get_mut(app.world_mut());
+    client.auth(Auth);
+    client.connect(transport.client_socket());
+    app
+}
+
+fn accept_connections(server_app: &mut App, room_key: &RoomKey) {
+    let auth_events: Vec<AuthEvents> = server_app
+        .world_mut()
+        .resource_mut::<Events<AuthEvents>>()
+        .drain()
+        .collect();
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let mut server = state.get_mut(server_app.world_mut());
+    for events in auth_events {
+        for (user_key, _) in events.read::<Auth>() {
+            server.accept_connection(&user_key);
+        }
+    }
+    for user_key in server.user_keys() {
+        if server.user(&user_key).room_count() == 0 {
+            server.room_mut(room_key).add_user(&user_key);
+        }
+    }
+    for (_, user_key, entity) in server.scope_checks() {
+        server.user_scope_mut(&user_key).include(&entity);
+    }
+}
+
+// Returns how many Position updates the Client received
+fn update(server_app: &mut App, client_app: &mut App, room_key: &RoomKey) -> usize {
+    sleep(Duration::from_millis(5));
+    client_app.update();
+    server_app.update();
+    accept_connections(server_app, room_key);
+
+    client_app
+        .world_mut()
+        .resource_mut::<Events<UpdateComponentEvents<Main>>>()
+        .drain()
+        .map(|events| events.read::<Position>().len())
+        .sum()
+}
+
+fn client_position(client_app: &mut App) -> Option<(i16, i16)> {
+    let world = client_app.world_mut();
+    let mut query = world.query::<&Position>();
+    query
+        .iter(world)
+        .next()
+        .map(|position| (*position.x, *position.y))
+}
+
+// Connects a Client in a Room with a replicated Position, then counts the
+// Position updates it receives over a number of frames in which the Server
+// writes the same value each frame
+fn updates_from_identical_writes(change_detection: ChangeDetectionMode) -> usize {
+    let transport = LocalTransport::new();
+    let mut server_app = server_app(&transport, change_detection);
+    let mut client_app = client_app(&transport);
+
+    let room_key = {
+        let mut state: SystemState<(Commands, Server)> = SystemState::new(server_app.world_mut());
+        let (mut commands, mut server) = state.get_mut(server_app.world_mut());
+        let room_key = server.make_room().key();
+        let entity = commands
+            .spawn_empty()
+            .enable_replication(&mut server)
+            .insert(Position::new_complete(3, 4))
+            .id();
+        server.room_mut(&room_key).add_entity(&entity);
+        state.apply(server_app.world_mut());
+        room_key
+    };
+
+    for _ in 0..400 {
+        if client_position(&mut client_app).is_some() {
+            break;
+        }
+        update(&mut server_app, &mut client_app, &room_key);
+    }
+    assert_eq!(client_position(&mut client_app), Some((3, 4)));
+
+    // let anything queued before the Entity arrived drain
+    for _ in 0..10 {
+        update(&mut server_app, &mut client_app, &room_key);
+    }
+
+    let mut updates = 0;
+    for _ in 0..30 {
+        updates += update(&mut server_app, &mut client_app, &room_key);
+    }
+
+    // a write which does change the value is still sent
+    server_app.world_mut().resource_mut::<WrittenX>().0 = 8;
+    for _ in 0..400 {
+        if client_position(&mut client_app) == Some((8, 4)) {
+            break;
+        }
+        update(&mut server_app, &mut client_app, &room_key);
+    }
+    assert_eq!(client_position(&mut client_app), Some((8, 4)));
+
+    updates
+}
+
+#[test]
+fn identical_writes_send_no_updates_in_equality_mode() {
+    let updates = updates_from_identical_writes(ChangeDetectionMode::ValueEquality);
+    assert_eq!(updates, 0);
+}
+
+#[test]
+fn identical_writes_send_updates_by_default() {
+    let updates = updates_from_identical_writes(ChangeDetectionMode::Always);
+    assert!(updates > 0);
+}
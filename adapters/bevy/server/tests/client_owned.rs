@@ -0,0 +1,152 @@
+use std::{thread::sleep, time::Duration};
+
+use bevy_app::App;
+use bevy_ecs::{
+    entity::Entity,
+    event::Events,
+    system::{Commands, SystemState},
+};
+
+use naia_bevy_client::{Client, ClientConfig, CommandsExt as ClientCommandsExt};
+use naia_bevy_server::{
+    events::AuthEvents, ClientOwned, EntityOwner, ReplicationConfig, Server, ServerConfig,
+};
+use naia_bevy_shared::{Message, Protocol};
+use naia_test::LocalTransport;
+
+#[derive(Message)]
+pub struct Auth;
+
+struct Main;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .build()
+}
+
+// Apps
+
+fn server_app(transport: &LocalTransport) -> App {
+    let mut app = App::new();
+    app.add_plugins(naia_bevy_server::Plugin::new(
+        ServerConfig::default(),
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Server> = SystemState::new(app.world_mut());
+    state
+        .get_mut(app.world_mut())
+        .listen(transport.server_socket());
+    app
+}
+
+fn client_app(transport: &LocalTransport) -> App {
+    let mut app = App::new();
+    let client_config = ClientConfig {
+        send_handshake_interval: Duration::from_millis(10),
+        handshake_pings: 2,
+        ..Default::default()
+    };
+    app.add_plugins(naia_bevy_client::Plugin::<Main>::new(
+        client_config,
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Client<Main>> = SystemState::new(app.world_mut());
+    let mut client = state.get_mut(app.world_mut());
+    client.auth(Auth);
+    client.connect(transport.client_socket());
+    app
+}
+
+fn accept_connections(server_app: &mut App) {
+    let auth_events: Vec<AuthEvents> = server_app
+        .world_mut()
+        .resource_mut::<Events<AuthEvents>>()
+        .drain()
+        .collect();
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let mut server = state.get_mut(server_app.world_mut());
+    for events in auth_events {
+        for (user_key, _) in events.read::<Auth>() {
+            server.accept_connection(&user_key);
+        }
+    }
+}
+
+fn client_is_connected(client_app: &mut App) -> bool {
+    let mut state: SystemState<Client<Main>> = SystemState::new(client_app.world_mut());
+    let client = state.get_mut(client_app.world_mut());
+    client.connection_status().is_connected()
+}
+
+fn update(server_app: &mut App, client_app: &mut App) {
+    sleep(Duration::from_millis(5));
+    client_app.update();
+    server_app.update();
+    accept_connections(server_app);
+}
+
+fn client_owned_entities(server_app: &mut App) -> Vec<(Entity, ClientOwned)> {
+    let world = server_app.world_mut();
+    let mut query = world.query::<(Entity, &ClientOwned)>();
+    query
+        .iter(world)
+        .map(|(entity, client_owned)| (entity, ClientOwned(client_owned.0)))
+        .collect()
+}
+
+#[test]
+fn client_owned_marker_follows_client_spawn() {
+    let transport = LocalTransport::new();
+    let mut server_app = server_app(&transport);
+    let mut client_app = client_app(&transport);
+
+    for _ in 0..400 {
+        if client_is_connected(&mut client_app) {
+            break;
+        }
+        update(&mut server_app, &mut client_app);
+    }
+    assert!(
+        client_is_connected(&mut client_app),
+        "client never connected"
+    );
+
+    // spawn a replicated Entity on the Client
+    {
+        let mut state: SystemState<(Commands, Client<Main>)> =
+            SystemState::new(client_app.world_mut());
+        let (mut commands, mut client) = state.get_mut(client_app.world_mut());
+        commands.spawn_empty().enable_replication(&mut client);
+        state.apply(client_app.world_mut());
+    }
+
+    for _ in 0..400 {
+        if !client_owned_entities(&mut server_app).is_empty() {
+            break;
+        }
+        update(&mut server_app, &mut client_app);
+    }
+
+    let client_owned = client_owned_entities(&mut server_app);
+    assert_eq!(client_owned.len(), 1, "expected one ClientOwned Entity");
+    let (entity, ClientOwned(user_key)) = client_owned[0];
+
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let server = state.get_mut(server_app.world_mut());
+    assert_eq!(server.user_keys(), vec![user_key]);
+    assert_eq!(server.entity_owner(&entity), EntityOwner::Client(user_key));
+    assert_eq!(
+        server.entity_replication_config(&entity),
+        Some(ReplicationConfig::Private)
+    );
+    assert_eq!(server.entity_authority_status(&entity), None);
+}
@@ -0,0 +1,184 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
+
+use bevy_app::App;
+use bevy_ecs::{event::Events, system::SystemState};
+
+use naia_bevy_client::{Client, ClientConfig};
+use naia_bevy_server::{events::AuthEvents, Server, ServerConfig};
+use naia_bevy_shared::{Message, Protocol};
+use naia_test::LocalTransport;
+
+#[derive(Message)]
+pub struct Auth;
+
+struct Main;
+
+type Tapped = Arc<Mutex<Vec<(SocketAddr, Vec<u8>)>>>;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_message::<Auth>()
+        .build()
+}
+
+// Apps
+
+fn server_app(transport: &LocalTransport, outgoing: &Tapped, incoming: &Tapped) -> App {
+    let mut app = App::new();
+    app.add_plugins(naia_bevy_server::Plugin::new(
+        ServerConfig::default(),
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Server> = SystemState::new(app.world_mut());
+    let mut server = state.get_mut(app.world_mut());
+
+    let outgoing = outgoing.clone();
+    server.set_outgoing_tap(Some(Box::new(move |address, payload| {
+        outgoing.lock().unwrap().push((*address, payload.to_vec()));
+    })));
+    let incoming = incoming.clone();
+    server.set_incoming_tap(Some(Box::new(move |address, payload| {
+        incoming.lock().unwrap().push((*address, payload.to_vec()));
+    })));
+
+    server.listen(transport.server_socket());
+    app
+}
+
+fn client_app(transport: &LocalTransport) -> App {
+    let mut app = App::new();
+    let client_config = ClientConfig {
+        send_handshake_interval: Duration::from_millis(10),
+        handshake_pings: 2,
+        ..Default::default()
+    };
+    app.add_plugins(naia_bevy_client::Plugin::<Main>::new(
+        client_config,
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Client<Main>> = SystemState::new(app.world_mut());
+    let mut client = state.get_mut(app.world_mut());
+    client.auth(Auth);
+    client.connect(transport.client_socket());
+    app
+}
+
+fn accept_connections(server_app: &mut App) {
+    let auth_events: Vec<AuthEvents> = server_app
+        .world_mut()
+        .resource_mut::<Events<AuthEvents>>()
+        .drain()
+        .collect();
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let mut server = state.get_mut(server_app.world_mut());
+    for events in auth_events {
+        for (user_key, _) in events.read::<Auth>() {
+            server.accept_connection(&user_key);
+        }
+    }
+}
+
+fn client_is_connected(client_app: &mut App) -> bool {
+    let mut state: SystemState<Client<Main>> = SystemState::new(client_app.world_mut());
+    let client = state.get_mut(client_app.world_mut());
+    client.connection_status().is_connected()
+}
+
+fn update(server_app: &mut App, client_app: &mut App) {
+    sleep(Duration::from_millis(5));
+    client_app.update();
+    server_app.update();
+    accept_connections(server_app);
+}
+
+fn payloads(tapped: &Tapped) -> Vec<Vec<u8>> {
+    tapped
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, payload)| payload.clone())
+        .collect()
+}
+
+#[test]
+fn taps_see_the_bytes_on_the_wire() {
+    let transport = LocalTransport::new();
+    let outgoing = Tapped::default();
+    let incoming = Tapped::default();
+    let mut server_app = server_app(&transport, &outgoing, &incoming);
+    let mut client_app = client_app(&transport);
+
+    for _ in 0..400 {
+        if client_is_connected(&mut client_app) {
+            break;
+        }
+        update(&mut server_app, &mut client_app);
+    }
+    assert!(
+        client_is_connected(&mut client_app),
+        "client never connected"
+    );
+
+    // let some post-handshake traffic flow too
+    for _ in 0..20 {
+        update(&mut server_app, &mut client_app);
+    }
+
+    // every packet the Client read was seen by the outgoing tap first, in order
+    let client_received = transport.client_received();
+    let tapped_outgoing = payloads(&outgoing);
+    assert!(!client_received.is_empty());
+    assert!(tapped_outgoing.len() >= client_received.len());
+    assert_eq!(
+        tapped_outgoing[..client_received.len()],
+        client_received[..]
+    );
+
+    // every packet the Server read was seen by the incoming tap
+    assert_eq!(payloads(&incoming), transport.server_received());
+
+    // all traffic was to & from the one Client
+    let addresses: Vec<SocketAddr> = outgoing
+        .lock()
+        .unwrap()
+        .iter()
+        .chain(incoming.lock().unwrap().iter())
+        .map(|(address, _)| *address)
+        .collect();
+    assert!(addresses.windows(2).all(|pair| pair[0] == pair[1]));
+}
+
+#[test]
+fn removed_tap_sees_nothing() {
+    let transport = LocalTransport::new();
+    let outgoing = Tapped::default();
+    let incoming = Tapped::default();
+    let mut server_app = server_app(&transport, &outgoing, &incoming);
+    {
+        let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+        let mut server = state.get_mut(server_app.world_mut());
+        server.set_outgoing_tap(None);
+        server.set_incoming_tap(None);
+    }
+    let mut client_app = client_app(&transport);
+
+    for _ in 0..20 {
+        update(&mut server_app, &mut client_app);
+    }
+
+    assert!(!transport.client_received().is_empty());
+    assert!(outgoing.lock().unwrap().is_empty());
+    assert!(incoming.lock().unwrap().is_empty());
+}
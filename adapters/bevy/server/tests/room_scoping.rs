@@ -0,0 +1,178 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use bevy_app::App;
+use bevy_ecs::{
+    component::Component,
+    event::Events,
+    system::{Commands, SystemState},
+};
+
+use naia_bevy_client::{Client, ClientConfig};
+use naia_bevy_server::{events::AuthEvents, CommandsExt, RoomKey, Server, ServerConfig};
+use naia_bevy_shared::{Message, Property, Protocol, Replicate};
+use naia_test::{LocalClientSocket, LocalNetwork};
+
+#[derive(Message)]
+pub struct Auth;
+
+#[derive(Component, Replicate)]
+pub struct Marker {
+    pub id: Property<u8>,
+}
+
+struct Main;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_message::<Auth>()
+        .add_component::<Marker>()
+        .build()
+}
+
+// Apps
+
+fn server_app(network: &LocalNetwork) -> App {
+    let mut app = App::new();
+    app.add_plugins(naia_bevy_server::Plugin::new(
+        ServerConfig::default(),
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Server> = SystemState::new(app.world_mut());
+    state
+        .get_mut(app.world_mut())
+        .listen(network.server_socket());
+    app
+}
+
+fn client_app(socket: LocalClientSocket) -> App {
+    let mut app = App::new();
+    let client_config = ClientConfig {
+        send_handshake_interval: Duration::from_millis(10),
+        handshake_pings: 2,
+        ..Default::default()
+    };
+    app.add_plugins(naia_bevy_client::Plugin::<Main>::new(
+        client_config,
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Client<Main>> = SystemState::new(app.world_mut());
+    let mut client = state.get_mut(app.world_mut());
+    client.auth(Auth);
+    client.connect(socket);
+    app
+}
+
+// Each Client is put in the Room matching the address it connected from
+fn accept_connections(server_app: &mut App, rooms: &[(SocketAddr, RoomKey)]) {
+    let auth_events: Vec<AuthEvents> = server_app
+        .world_mut()
+        .resource_mut::<Events<AuthEvents>>()
+        .drain()
+        .collect();
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let mut server = state.get_mut(server_app.world_mut());
+    for events in auth_events {
+        for (user_key, _) in events.read::<Auth>() {
+            server.accept_connection(&user_key);
+        }
+    }
+
+    for user_key in server.user_keys() {
+        let address = server.user(&user_key).address();
+        let Some((_, room_key)) = rooms
+            .iter()
+            .find(|(room_address, _)| *room_address == address)
+        else {
+            panic!("unexpected Client address {}", address);
+        };
+        if server.user(&user_key).room_count() == 0 {
+            server.room_mut(room_key).add_user(&user_key);
+        }
+    }
+
+    // Rooms only limit which Entities are checked, include every candidate
+    for (_, user_key, entity) in server.scope_checks() {
+        server.user_scope_mut(&user_key).include(&entity);
+    }
+}
+
+fn update(server_app: &mut App, client_apps: &mut [App], rooms: &[(SocketAddr, RoomKey)]) {
+    sleep(Duration::from_millis(5));
+    for client_app in client_apps.iter_mut() {
+        client_app.update();
+    }
+    server_app.update();
+    accept_connections(server_app, rooms);
+}
+
+fn spawn_in_room(server_app: &mut App, room_key: &RoomKey, id: u8) {
+    let mut state: SystemState<(Commands, Server)> = SystemState::new(server_app.world_mut());
+    let (mut commands, mut server) = state.get_mut(server_app.world_mut());
+    let entity = commands
+        .spawn_empty()
+        .enable_replication(&mut server)
+        .insert(Marker::new_complete(id))
+        .id();
+    server.room_mut(room_key).add_entity(&entity);
+    state.apply(server_app.world_mut());
+}
+
+fn seen_markers(client_app: &mut App) -> Vec<u8> {
+    let world = client_app.world_mut();
+    let mut query = world.query::<&Marker>();
+    let mut ids: Vec<u8> = query.iter(world).map(|marker| *marker.id).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn clients_only_see_entities_in_their_room() {
+    let network = LocalNetwork::new();
+    let mut server_app = server_app(&network);
+
+    let (red_room, blue_room) = {
+        let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+        let mut server = state.get_mut(server_app.world_mut());
+        (server.make_room().key(), server.make_room().key())
+    };
+    spawn_in_room(&mut server_app, &red_room, 1);
+    spawn_in_room(&mut server_app, &blue_room, 2);
+
+    // two Clients in the red Room, one in the blue Room
+    let mut rooms = Vec::new();
+    let mut client_apps = Vec::new();
+    for room_key in [red_room, red_room, blue_room] {
+        let (socket, address) = network.add_client();
+        rooms.push((address, room_key));
+        client_apps.push(client_app(socket));
+    }
+
+    for _ in 0..400 {
+        if client_apps
+            .iter_mut()
+            .all(|client_app| !seen_markers(client_app).is_empty())
+        {
+            break;
+        }
+        update(&mut server_app, &mut client_apps, &rooms);
+    }
+    // let any out-of-scope Entity have the chance to arrive, if it were to
+    for _ in 0..20 {
+        update(&mut server_app, &mut client_apps, &rooms);
+    }
+
+    assert_eq!(seen_markers(&mut client_apps[0]), vec![1]);
+    assert_eq!(seen_markers(&mut client_apps[1]), vec![1]);
+    assert_eq!(seen_markers(&mut client_apps[2]), vec![2]);
+
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let server = state.get_mut(server_app.world_mut());
+    assert_eq!(server.users_count(), 3);
+}
@@ -0,0 +1,179 @@
+use std::{thread::sleep, time::Duration};
+
+use bevy_app::{App, Update};
+use bevy_ecs::{
+    event::{EventReader, Events},
+    schedule::IntoSystemConfigs,
+    system::{ResMut, Resource, SystemState},
+};
+
+use naia_bevy_client::{events::ClientTickEvent, Client, ClientConfig};
+use naia_bevy_server::{
+    events::{AuthEvents, TickEvent},
+    ReceiveEvents, Server, ServerConfig, TickBuffer, UserKey,
+};
+use naia_bevy_shared::{
+    Channel, ChannelDirection, ChannelMode, Message, Protocol, Tick, TickBufferSettings,
+};
+use naia_test::LocalTransport;
+
+#[derive(Message)]
+pub struct Auth;
+
+#[derive(Channel)]
+pub struct InputChannel;
+
+#[derive(Message)]
+pub struct Input {
+    pub value: u8,
+}
+
+struct Main;
+
+#[derive(Resource, Default)]
+struct Received {
+    inputs: Vec<(UserKey, Tick, u8)>,
+    // Ticks of inputs which arrived in a frame without a matching TickEvent
+    unmatched: Vec<Tick>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_channel::<InputChannel>(
+            ChannelDirection::ClientToServer,
+            ChannelMode::TickBuffered(TickBufferSettings::default()),
+        )
+        .add_message::<Auth>()
+        .add_message::<Input>()
+        .build()
+}
+
+fn receive_inputs(
+    mut tick_reader: EventReader<TickEvent>,
+    mut tick_buffer: TickBuffer<InputChannel, Input>,
+    mut received: ResMut<Received>,
+) {
+    let ticks: Vec<Tick> = tick_reader.read().map(|TickEvent(tick)| *tick).collect();
+    for (user_key, tick, input) in tick_buffer.read() {
+        if !ticks.contains(&tick) {
+            received.unmatched.push(tick);
+        }
+        received.inputs.push((user_key, tick, input.value));
+    }
+}
+
+// Apps
+
+fn server_app(transport: &LocalTransport) -> App {
+    let mut app = App::new();
+    app.add_plugins(naia_bevy_server::Plugin::new(
+        ServerConfig::default(),
+        protocol(),
+    ));
+    app.init_resource::<Received>();
+    app.add_systems(Update, receive_inputs.in_set(ReceiveEvents));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Server> = SystemState::new(app.world_mut());
+    let mut server = state.get_mut(app.world_mut());
+    server.listen(transport.server_socket());
+    app
+}
+
+fn client_app(transport: &LocalTransport) -> App {
+    let mut app = App::new();
+    let client_config = ClientConfig {
+        send_handshake_interval: Duration::from_millis(10),
+        handshake_pings: 2,
+        ..Default::default()
+    };
+    app.add_plugins(naia_bevy_client::Plugin::<Main>::new(
+        client_config,
+        protocol(),
+    ));
+    app.finish();
+    app.update();
+
+    let mut state: SystemState<Client<Main>> = SystemState::new(app.world_mut());
+    let mut client = state.get_mut(app.world_mut());
+    client.auth(Auth);
+    client.connect(transport.client_socket());
+    app
+}
+
+fn accept_connections(server_app: &mut App) {
+    let auth_events: Vec<AuthEvents> = server_app
+        .world_mut()
+        .resource_mut::<Events<AuthEvents>>()
+        .drain()
+        .collect();
+    let mut state: SystemState<Server> = SystemState::new(server_app.world_mut());
+    let mut server = state.get_mut(server_app.world_mut());
+    for events in auth_events {
+        for (user_key, _) in events.read::<Auth>() {
+            server.accept_connection(&user_key);
+        }
+    }
+}
+
+// sends one Input per Client Tick, valued by the Tick it was sent on
+fn send_inputs(client_app: &mut App) {
+    let ticks: Vec<Tick> = client_app
+        .world_mut()
+        .resource_mut::<Events<ClientTickEvent<Main>>>()
+        .drain()
+        .map(|event| event.tick)
+        .collect();
+    let mut state: SystemState<Client<Main>> = SystemState::new(client_app.world_mut());
+    let mut client = state.get_mut(client_app.world_mut());
+    for tick in ticks {
+        client.send_tick_buffer_message::<InputChannel, Input>(
+            &tick,
+            &Input {
+                value: (tick % 256) as u8,
+            },
+        );
+    }
+}
+
+fn update(server_app: &mut App, client_app: &mut App) {
+    sleep(Duration::from_millis(5));
+    client_app.update();
+    send_inputs(client_app);
+    server_app.update();
+    accept_connections(server_app);
+}
+
+#[test]
+fn tick_buffer_yields_inputs_with_their_tick() {
+    let transport = LocalTransport::new();
+    let mut server_app = server_app(&transport);
+    let mut client_app = client_app(&transport);
+
+    for _ in 0..400 {
+        if server_app.world().resource::<Received>().inputs.len() >= 10 {
+            break;
+        }
+        update(&mut server_app, &mut client_app);
+    }
+
+    let received = server_app.world().resource::<Received>();
+    assert!(received.inputs.len() >= 10, "too few inputs received");
+    assert!(received.unmatched.is_empty());
+
+    // each Input is yielded once, on the Server Tick it was sent for
+    let mut ticks: Vec<Tick> = received.inputs.iter().map(|(_, tick, _)| *tick).collect();
+    assert!(received
+        .inputs
+        .iter()
+        .all(|(_, tick, value)| *value == (*tick % 256) as u8));
+    let count = ticks.len();
+    ticks.dedup();
+    assert_eq!(ticks.len(), count);
+    assert!(received
+        .inputs
+        .iter()
+        .all(|(user_key, _, _)| *user_key == received.inputs[0].0));
+}
@@ -1,19 +1,35 @@
 use std::any::TypeId;
 
 use bevy_ecs::{
+    change_detection::DetectChangesMut,
     component::Component,
     entity::Entity,
     event::EventWriter,
     prelude::Event,
-    query::{Added, Changed},
+    query::{Added, Changed, Or, With},
     removal_detection::RemovedComponents,
-    system::{Query, ResMut},
+    system::{Query, ResMut, Resource},
 };
 
 use naia_shared::{ComponentKind, Replicate};
 
 use crate::{HostOwned, HostOwnedMap};
 
+/// How writes to the Properties of host-owned Components are turned into
+/// updates sent over the network
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChangeDetectionMode {
+    /// Every write to a Property queues an update of it, even if the value
+    /// written is the same as the one it replaces
+    #[default]
+    Always,
+    /// Writes to Properties are compared against their values from before,
+    /// once per frame in `HostSyncChangeTracking`, and only Properties whose
+    /// values actually changed are updated. Systems which write a Component
+    /// unconditionally then cost nothing while its values stay the same
+    ValueEquality,
+}
+
 #[derive(Event)]
 pub enum HostSyncEvent {
     Insert(TypeId, Entity, ComponentKind),
@@ -87,3 +103,27 @@ pub fn on_component_removed<R: Replicate + Component>(
         }
     }
 }
+
+// HostOwned Components which were just added, or whose Entity just became
+// HostOwned
+type NewlyHostOwned<R> = (With<HostOwned>, Or<(Added<R>, Added<HostOwned>)>);
+
+pub fn defer_component_mutations<R: Replicate + Component>(
+    mut query: Query<&mut R, NewlyHostOwned<R>>,
+) {
+    for mut component in query.iter_mut() {
+        component
+            .bypass_change_detection()
+            .set_defer_mutations(true);
+    }
+}
+
+pub fn flush_component_mutations<R: Replicate + Component>(mut query: Query<&mut R, Changed<R>>) {
+    // not limited to HostOwned Components, so that writes made before an
+    // Entity stopped being HostOwned are still flushed
+    for mut component in query.iter_mut() {
+        component
+            .bypass_change_detection()
+            .flush_deferred_mutations();
+    }
+}
@@ -1,12 +1,20 @@
 use std::{any::Any, marker::PhantomData};
 
 use bevy_app::{App, Update};
-use bevy_ecs::{component::Component, entity::Entity, schedule::IntoSystemConfigs, world::World};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    schedule::{common_conditions::resource_equals, IntoSystemConfigs},
+    world::World,
+};
 
 use naia_shared::{GlobalWorldManagerType, ReplicaDynMutWrapper, ReplicaDynRefWrapper, Replicate};
 
 use super::{
-    change_detection::{on_component_added, on_component_removed},
+    change_detection::{
+        defer_component_mutations, flush_component_mutations, on_component_added,
+        on_component_removed, ChangeDetectionMode,
+    },
     component_ref::{ComponentDynMut, ComponentDynRef},
     system_set::HostSyncChangeTracking,
 };
@@ -78,6 +86,16 @@ impl<R: Replicate + Component> ComponentAccess for ComponentAccessor<R> {
                 .chain()
                 .in_set(HostSyncChangeTracking),
         );
+        app.add_systems(
+            Update,
+            (
+                defer_component_mutations::<R>,
+                flush_component_mutations::<R>,
+            )
+                .chain()
+                .in_set(HostSyncChangeTracking)
+                .run_if(resource_equals(ChangeDetectionMode::ValueEquality)),
+        );
     }
 
     fn component<'w>(&self, world: &'w World, entity: &Entity) -> Option<ReplicaDynRefWrapper<'w>> {
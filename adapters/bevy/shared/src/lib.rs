@@ -1,17 +1,20 @@
 pub use naia_shared::{
-    sequence_greater_than, sequence_less_than, wrapping_diff, BitReader, BitWrite, BitWriter,
-    Channel, ChannelDirection, ChannelKind, ChannelMode, ComponentFieldUpdate, ComponentKind,
-    ComponentKinds, ComponentUpdate, ConstBitLength, DiffMask, EntityAndGlobalEntityConverter,
-    EntityAuthAccessor, EntityAuthStatus, EntityDoesNotExistError, EntityProperty,
-    FakeEntityConverter, GameInstant, GlobalEntity, HostEntity, HostEntityAuthStatus, Instant,
-    LinkConditionerConfig, LocalEntityAndGlobalEntityConverter,
-    LocalEntityAndGlobalEntityConverterMut, MessageBevy as Message, MessageBuilder,
-    MessageContainer, MessageKind, MessageKinds, Named, OwnedBitReader, Property, PropertyMutate,
-    PropertyMutator, Random, ReliableSettings, RemoteEntity, ReplicaDynMut, ReplicaDynRef,
-    ReplicateBevy as Replicate, ReplicateBuilder, Request, Response, ResponseReceiveKey,
-    ResponseSendKey, SerdeBevyShared as Serde, SerdeErr, SerdeIntegerConversion, SignedInteger,
-    SignedVariableInteger, Tick, TickBufferSettings, Timer, UnsignedInteger,
-    UnsignedVariableInteger, WorldMutType, WorldRefType, MTU_SIZE_BYTES,
+    game_instant_greater_than, game_instant_less_than, sequence_greater_than, sequence_less_than,
+    wrapping_diff, BitReader, BitWrite, BitWriter, Channel, ChannelDirection, ChannelKind,
+    ChannelLatencyStats, ChannelMode, ChannelSettings, ComponentFieldUpdate, ComponentKind,
+    ComponentKinds, ComponentUpdate, ConnectionParams, ConnectionQuality, ConnectionQualityLevel,
+    ConstBitLength, DiffMask, EntityAndGlobalEntityConverter, EntityAuthAccessor, EntityAuthStatus,
+    EntityDoesNotExistError, EntityProperty, EnumProperty, FakeEntityConverter, GameDuration,
+    GameInstant, GlobalEntity, HostEntity, HostEntityAuthStatus, Instant, LinkConditionerConfig,
+    LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, MapProperty,
+    MessageBevy as Message, MessageBuilder, MessageContainer, MessageKind, MessageKinds,
+    MessageReceiptKey, Named, OverflowPolicy, OwnedBitReader, Property, PropertyMutate,
+    PropertyMutator, Quantization, Random, ReliableSettings, RemoteEntity, ReplicaDynMut,
+    ReplicaDynRef, ReplicateBevy as Replicate, ReplicateBuilder, Request, Response,
+    ResponseReceiveKey, ResponseSendKey, SerdeBevyShared as Serde, SerdeEnum, SerdeErr,
+    SerdeIntegerConversion, SignedInteger, SignedVariableInteger, Tick, TickBufferSettings,
+    TickExt, Timer, UnsignedInteger, UnsignedVariableInteger, VecProperty, WorldMutType,
+    WorldRefType, MTU_SIZE_BITS, MTU_SIZE_BYTES,
 };
 
 mod change_detection;
@@ -25,7 +28,7 @@ mod system_set;
 mod world_data;
 mod world_proxy;
 
-pub use change_detection::HostSyncEvent;
+pub use change_detection::{ChangeDetectionMode, HostSyncEvent};
 pub use component_access::{AppTag, ComponentAccess, ComponentAccessor};
 pub use components::{HostOwned, HostOwnedMap};
 pub use plugin::SharedPlugin;
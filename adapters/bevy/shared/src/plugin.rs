@@ -6,21 +6,30 @@ use bevy_ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs};
 use log::info;
 
 use crate::{
-    change_detection::{on_despawn, on_host_owned_added, HostSyncEvent},
+    change_detection::{on_despawn, on_host_owned_added, ChangeDetectionMode, HostSyncEvent},
     system_set::{BeforeHostSyncChangeTracking, HostSyncChangeTracking},
     BeforeReceiveEvents, HostOwnedMap, ReceiveEvents,
 };
 
 pub struct SharedPlugin<T: Send + Sync + 'static> {
+    change_detection: ChangeDetectionMode,
     phantom_t: PhantomData<T>,
 }
 
 impl<T: Send + Sync + 'static> SharedPlugin<T> {
     pub fn new() -> Self {
         Self {
+            change_detection: ChangeDetectionMode::default(),
             phantom_t: PhantomData,
         }
     }
+
+    /// Picks how writes to host-owned Components are turned into updates.
+    /// Every connection in an App shares the mode of the first one added
+    pub fn with_change_detection(mut self, change_detection: ChangeDetectionMode) -> Self {
+        self.change_detection = change_detection;
+        self
+    }
 }
 
 impl<T: Send + Sync + 'static> PluginType for SharedPlugin<T> {
@@ -29,9 +38,14 @@ impl<T: Send + Sync + 'static> PluginType for SharedPlugin<T> {
             info!("attempted to add SharedPlugin twice to App");
             return;
         }
+        if app.world().contains_resource::<HostOwnedMap>() {
+            // already added for another connection, the systems below serve every connection
+            return;
+        }
         app
             // RESOURCES //
             .init_resource::<HostOwnedMap>()
+            .insert_resource(self.change_detection)
             // EVENTS //
             .add_event::<HostSyncEvent>()
             // SYSTEM SETS //
@@ -58,6 +58,20 @@ impl WorldData {
         }
     }
 
+    /// Adds systems only for the Component kinds which `registered` doesn't
+    /// have, as its systems will already have been added to the App
+    pub fn add_missing_systems(&self, app: &mut App, registered: &Self) {
+        for (kind, accessor_any) in &self.kind_to_accessor_map {
+            if registered.kind_to_accessor_map.contains_key(kind) {
+                continue;
+            }
+            let accessor = accessor_any
+                .downcast_ref::<Box<dyn ComponentAccess>>()
+                .unwrap();
+            accessor.add_systems(app);
+        }
+    }
+
     // Entities //
 
     pub(crate) fn entities(&self) -> Vec<Entity> {
@@ -1,5 +1,15 @@
 pub use naia_client::{
-    transport, Client, ClientConfig, ClientTickEvent, ConnectEvent, DespawnEntityEvent,
-    DisconnectEvent, ErrorEvent, InsertComponentEvent, RemoveComponentEvent, SpawnEntityEvent,
+    transport, Client, ClientConfig, ClientTickEvent, ConnectEvent, ConnectionQualityChangedEvent,
+    DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent, EntityAuthGrantedEvent,
+    EntityAuthResetEvent, ErrorEvent, Events, InsertComponentEvent, MessageEvent,
+    PublishEntityEvent, RejectEvent, RemoveComponentEvent, ReplicationConfig, RequestEvent,
+    RequestTimeoutEvent, ServerTickEvent, SpawnEntityEvent, SpawnEntityWithComponentsEvent,
+    TickSyncKind, TimedMessageEvent, TimedRequestEvent, UnpublishEntityEvent, UpdateComponentEvent,
 };
-pub use naia_hecs_shared::{Protocol, WorldWrapper};
+pub use naia_hecs_shared::{
+    EntityAuthStatus, Protocol, ResponseReceiveKey, ResponseSendKey, WorldWrapper,
+};
+
+mod world_ext;
+
+pub use world_ext::WorldExt;
@@ -0,0 +1,72 @@
+use hecs::Entity;
+
+use naia_client::{Client, ReplicationConfig};
+use naia_hecs_shared::{EntityAuthStatus, WorldMutType, WorldWrapper};
+
+// Hecs World Extension
+pub trait WorldExt {
+    fn enable_replication(&mut self, client: &mut Client<Entity>, entity: &Entity);
+    fn disable_replication(&mut self, client: &mut Client<Entity>, entity: &Entity);
+    fn configure_replication(
+        &mut self,
+        client: &mut Client<Entity>,
+        entity: &Entity,
+        config: ReplicationConfig,
+    );
+    fn replication_config(
+        &self,
+        client: &Client<Entity>,
+        entity: &Entity,
+    ) -> Option<ReplicationConfig>;
+    fn request_authority(&mut self, client: &mut Client<Entity>, entity: &Entity);
+    fn release_authority(&mut self, client: &mut Client<Entity>, entity: &Entity);
+    fn authority(&self, client: &Client<Entity>, entity: &Entity) -> Option<EntityAuthStatus>;
+}
+
+impl WorldExt for WorldWrapper {
+    fn enable_replication(&mut self, client: &mut Client<Entity>, entity: &Entity) {
+        client.enable_entity_replication(entity);
+
+        // hecs has no change detection, so register the Components the Entity already has
+        let mut world = self;
+        for component_kind in world.component_kinds(entity) {
+            if let Some(mut component) = world.component_mut_of_kind(entity, &component_kind) {
+                client.insert_component_worldless(entity, &mut *component);
+            }
+        }
+    }
+
+    fn disable_replication(&mut self, client: &mut Client<Entity>, entity: &Entity) {
+        client.disable_entity_replication(entity);
+    }
+
+    fn configure_replication(
+        &mut self,
+        client: &mut Client<Entity>,
+        entity: &Entity,
+        config: ReplicationConfig,
+    ) {
+        let mut world = self;
+        client.configure_entity_replication(&mut world, entity, config);
+    }
+
+    fn replication_config(
+        &self,
+        client: &Client<Entity>,
+        entity: &Entity,
+    ) -> Option<ReplicationConfig> {
+        client.entity_replication_config(entity)
+    }
+
+    fn request_authority(&mut self, client: &mut Client<Entity>, entity: &Entity) {
+        client.entity_request_authority(entity);
+    }
+
+    fn release_authority(&mut self, client: &mut Client<Entity>, entity: &Entity) {
+        client.entity_release_authority(entity);
+    }
+
+    fn authority(&self, client: &Client<Entity>, entity: &Entity) -> Option<EntityAuthStatus> {
+        client.entity_authority_status(entity)
+    }
+}
@@ -1,5 +1,16 @@
-pub use naia_hecs_shared::{Protocol, Random, WorldProxy, WorldProxyMut, WorldWrapper};
+pub use naia_hecs_shared::{
+    EntityAuthStatus, Protocol, Random, ResponseReceiveKey, ResponseSendKey, WorldProxy,
+    WorldProxyMut, WorldWrapper,
+};
 pub use naia_server::{
-    transport, AuthEvent, ConnectEvent, DisconnectEvent, ErrorEvent, RoomKey, Server, ServerConfig,
-    TickEvent,
+    transport, AuthEvent, ConnectEvent, ConnectionQualityChangedEvent, DelegateEntityEvent,
+    DespawnEntityEvent, DisconnectEvent, EntityAuthGrantEvent, EntityAuthResetEvent, ErrorEvent,
+    Events, InputGapEvent, InsertComponentEvent, MessageEvent, NaiaServerError, PublishEntityEvent,
+    RemoveComponentEvent, ReplicationConfig, RequestEvent, RequestTimeoutEvent, RoomKey, Server,
+    ServerConfig, SpawnEntityEvent, TickEvent, TimedMessageEvent, TimedRequestEvent,
+    UnpublishEntityEvent, UpdateComponentEvent, UserKey,
 };
+
+mod world_ext;
+
+pub use world_ext::WorldExt;
@@ -0,0 +1,92 @@
+use hecs::Entity;
+
+use naia_hecs_shared::{EntityAuthStatus, WorldMutType, WorldWrapper};
+use naia_server::{NaiaServerError, ReplicationConfig, Server, UserKey};
+
+// Hecs World Extension
+pub trait WorldExt {
+    fn enable_replication(&mut self, server: &mut Server<Entity>, entity: &Entity);
+    fn disable_replication(&mut self, server: &mut Server<Entity>, entity: &Entity);
+    fn configure_replication(
+        &mut self,
+        server: &mut Server<Entity>,
+        entity: &Entity,
+        config: ReplicationConfig,
+    );
+    fn replication_config(
+        &self,
+        server: &Server<Entity>,
+        entity: &Entity,
+    ) -> Option<ReplicationConfig>;
+    fn give_authority(
+        &mut self,
+        server: &mut Server<Entity>,
+        entity: &Entity,
+        user_key: &UserKey,
+    ) -> Result<(), NaiaServerError>;
+    fn take_authority(&mut self, server: &mut Server<Entity>, entity: &Entity);
+    fn authority(&self, server: &Server<Entity>, entity: &Entity) -> Option<EntityAuthStatus>;
+    fn pause_replication(&mut self, server: &mut Server<Entity>, entity: &Entity);
+    fn resume_replication(&mut self, server: &mut Server<Entity>, entity: &Entity);
+}
+
+impl WorldExt for WorldWrapper {
+    fn enable_replication(&mut self, server: &mut Server<Entity>, entity: &Entity) {
+        server.enable_entity_replication(entity);
+
+        // hecs has no change detection, so register the Components the Entity already has
+        let mut world = self;
+        for component_kind in world.component_kinds(entity) {
+            if let Some(mut component) = world.component_mut_of_kind(entity, &component_kind) {
+                server.insert_component_worldless(entity, &mut *component);
+            }
+        }
+    }
+
+    fn disable_replication(&mut self, server: &mut Server<Entity>, entity: &Entity) {
+        server.disable_entity_replication(entity);
+    }
+
+    fn configure_replication(
+        &mut self,
+        server: &mut Server<Entity>,
+        entity: &Entity,
+        config: ReplicationConfig,
+    ) {
+        let mut world = self;
+        server.configure_entity_replication(&mut world, entity, config);
+    }
+
+    fn replication_config(
+        &self,
+        server: &Server<Entity>,
+        entity: &Entity,
+    ) -> Option<ReplicationConfig> {
+        server.entity_replication_config(entity)
+    }
+
+    fn give_authority(
+        &mut self,
+        server: &mut Server<Entity>,
+        entity: &Entity,
+        user_key: &UserKey,
+    ) -> Result<(), NaiaServerError> {
+        server.entity_give_authority(user_key, entity)
+    }
+
+    fn take_authority(&mut self, server: &mut Server<Entity>, entity: &Entity) {
+        server.entity_take_authority(entity);
+    }
+
+    fn authority(&self, server: &Server<Entity>, entity: &Entity) -> Option<EntityAuthStatus> {
+        server.entity_authority_status(entity)
+    }
+
+    fn pause_replication(&mut self, server: &mut Server<Entity>, entity: &Entity) {
+        server.pause_entity_replication(entity);
+    }
+
+    fn resume_replication(&mut self, server: &mut Server<Entity>, entity: &Entity) {
+        server.resume_entity_replication(entity);
+    }
+}
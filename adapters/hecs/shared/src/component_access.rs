@@ -2,7 +2,7 @@ use std::{any::Any, marker::PhantomData};
 
 use hecs::{Entity, World};
 
-use naia_shared::{ReplicaDynMutWrapper, ReplicaDynRefWrapper, Replicate};
+use naia_shared::{GlobalWorldManagerType, ReplicaDynMutWrapper, ReplicaDynRefWrapper, Replicate};
 
 use super::component_ref::{ComponentDynMut, ComponentDynRef};
 
@@ -27,6 +27,20 @@ pub trait ComponentAccess {
         entity: &Entity,
         boxed_component: Box<dyn Replicate>,
     );
+    fn component_publish(
+        &self,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        world: &mut World,
+        entity: &Entity,
+    );
+    fn component_unpublish(&self, world: &mut World, entity: &Entity);
+    fn component_enable_delegation(
+        &self,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        world: &mut World,
+        entity: &Entity,
+    );
+    fn component_disable_delegation(&self, world: &mut World, entity: &Entity);
 }
 
 // ComponentAccessor
@@ -97,4 +111,54 @@ impl<R: Replicate> ComponentAccess for ComponentAccessor<R> {
         let inner: R = *(boxed_any.downcast::<R>().unwrap());
         world.insert_one(*entity, inner).unwrap();
     }
+
+    fn component_publish(
+        &self,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        world: &mut World,
+        entity: &Entity,
+    ) {
+        if let Ok(mut component_mut) = world.get::<&mut R>(*entity) {
+            let component_kind = component_mut.kind();
+            let diff_mask_size = component_mut.diff_mask_size();
+            let mutator =
+                global_world_manager.register_component(entity, &component_kind, diff_mask_size);
+            component_mut.publish(&mutator);
+        }
+    }
+
+    fn component_unpublish(&self, world: &mut World, entity: &Entity) {
+        if let Ok(mut component_mut) = world.get::<&mut R>(*entity) {
+            component_mut.unpublish();
+        }
+    }
+
+    fn component_enable_delegation(
+        &self,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        world: &mut World,
+        entity: &Entity,
+    ) {
+        if let Ok(mut component_mut) = world.get::<&mut R>(*entity) {
+            let accessor = global_world_manager.get_entity_auth_accessor(entity);
+            if global_world_manager.entity_needs_mutator_for_delegation(entity) {
+                let component_kind = component_mut.kind();
+                let diff_mask_size = component_mut.diff_mask_size();
+                let mutator = global_world_manager.register_component(
+                    entity,
+                    &component_kind,
+                    diff_mask_size,
+                );
+                component_mut.enable_delegation(&accessor, Some(&mutator));
+            } else {
+                component_mut.enable_delegation(&accessor, None);
+            }
+        }
+    }
+
+    fn component_disable_delegation(&self, world: &mut World, entity: &Entity) {
+        if let Ok(mut component_mut) = world.get::<&mut R>(*entity) {
+            component_mut.disable_delegation();
+        }
+    }
 }
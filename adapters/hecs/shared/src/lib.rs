@@ -1,12 +1,15 @@
 pub use naia_shared::{
-    BitReader, BitWrite, BitWriter, Channel, ChannelDirection, ChannelMode, ComponentFieldUpdate,
-    ComponentKind, ComponentKinds, ComponentUpdate, ConstBitLength, DiffMask, EntityAuthAccessor,
-    EntityProperty, GlobalEntity, HostEntity, LinkConditionerConfig,
-    LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, MessageBuilder,
-    MessageContainer, MessageHecs as Message, MessageKind, MessageKinds, Named, OwnedBitReader,
-    OwnedLocalEntity, Property, PropertyMutate, PropertyMutator, Random, ReliableSettings,
-    RemoteEntity, ReplicaDynMut, ReplicaDynRef, ReplicateBuilder, ReplicateHecs as Replicate,
-    SerdeErr, SerdeHecs as Serde, TickBufferSettings, UnsignedInteger,
+    BitReader, BitWrite, BitWriter, Channel, ChannelDirection, ChannelLatencyStats, ChannelMode,
+    ChannelSettings, ComponentFieldUpdate, ComponentKind, ComponentKinds, ComponentUpdate,
+    ConstBitLength, DiffMask, EntityAuthAccessor, EntityAuthStatus, EntityProperty, EnumProperty,
+    GlobalEntity, HostEntity, LinkConditionerConfig, LocalEntityAndGlobalEntityConverter,
+    LocalEntityAndGlobalEntityConverterMut, MapProperty, MessageBuilder, MessageContainer,
+    MessageHecs as Message, MessageKind, MessageKinds, MessageReceiptKey, Named, OverflowPolicy,
+    OwnedBitReader, OwnedLocalEntity, Property, PropertyMutate, PropertyMutator, Quantization,
+    Random, ReliableSettings, RemoteEntity, ReplicaDynMut, ReplicaDynRef, ReplicateBuilder,
+    ReplicateHecs as Replicate, Request, Response, ResponseReceiveKey, ResponseSendKey, SerdeEnum,
+    SerdeErr, SerdeHecs as Serde, SignedInteger, TickBufferSettings, UnsignedInteger,
+    UnsignedVariableInteger, VecProperty, WorldMutType, WorldRefType, MTU_SIZE_BITS,
 };
 
 mod component_access;
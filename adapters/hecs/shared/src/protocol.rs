@@ -3,8 +3,8 @@ use std::time::Duration;
 use hecs::World;
 
 use naia_shared::{
-    Channel, ChannelDirection, ChannelMode, ComponentKind, CompressionConfig,
-    LinkConditionerConfig, Message, Protocol as InnerProtocol, ProtocolPlugin, Replicate,
+    Channel, ChannelDirection, ChannelMode, ChannelSettings, ComponentKind, CompressionConfig,
+    LinkConditionerConfig, Message, Protocol as InnerProtocol, ProtocolPlugin, Replicate, Request,
     SocketConfig,
 };
 
@@ -53,6 +53,16 @@ impl Protocol {
         self
     }
 
+    pub fn enable_client_authoritative_entities(&mut self) -> &mut Self {
+        self.inner.enable_client_authoritative_entities();
+        self
+    }
+
+    pub fn enable_streams(&mut self) -> &mut Self {
+        self.inner.enable_streams();
+        self
+    }
+
     pub fn rtc_endpoint(&mut self, path: String) -> &mut Self {
         self.inner.rtc_endpoint(path);
         self
@@ -82,11 +92,24 @@ impl Protocol {
         self
     }
 
+    pub fn add_channel_with_settings<C: Channel>(
+        &mut self,
+        settings: ChannelSettings,
+    ) -> &mut Self {
+        self.inner.add_channel_with_settings::<C>(settings);
+        self
+    }
+
     pub fn add_message<M: Message>(&mut self) -> &mut Self {
         self.inner.add_message::<M>();
         self
     }
 
+    pub fn add_request<Q: Request>(&mut self) -> &mut Self {
+        self.inner.add_request::<Q>();
+        self
+    }
+
     pub fn add_component<C: Replicate>(&mut self) -> &mut Self {
         self.inner.add_component::<C>();
         self.world_data
@@ -96,6 +119,25 @@ impl Protocol {
         self
     }
 
+    /// See `naia_shared::Protocol::with_stable_ids()`
+    pub fn with_stable_ids(&mut self) -> &mut Self {
+        self.inner.with_stable_ids();
+        self
+    }
+
+    /// See `naia_shared::Protocol::merge()`
+    pub fn merge(&mut self, other: Protocol) -> &mut Self {
+        let Protocol { inner, world_data } = other;
+        self.inner.merge(inner);
+        if let Some(other_world_data) = world_data {
+            self.world_data
+                .as_mut()
+                .expect("shouldn't happen")
+                .merge(other_world_data);
+        }
+        self
+    }
+
     pub fn lock(&mut self) {
         self.inner.lock();
     }
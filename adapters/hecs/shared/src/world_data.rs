@@ -23,6 +23,10 @@ impl WorldData {
         }
     }
 
+    pub fn merge(&mut self, other: Self) {
+        self.kind_to_accessor_map.extend(other.kind_to_accessor_map);
+    }
+
     #[allow(clippy::borrowed_box)]
     pub(crate) fn component_access(
         &self,
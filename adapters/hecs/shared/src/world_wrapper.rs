@@ -261,52 +261,78 @@ impl WorldMutType<Entity> for &mut WorldWrapper {
 
     fn entity_publish(
         &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        entity: &Entity,
     ) {
-        todo!()
+        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+            WorldMutType::<Entity>::component_publish(
+                self,
+                global_world_manager,
+                entity,
+                &component_kind,
+            );
+        }
     }
 
     fn component_publish(
         &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
-        _component_kind: &ComponentKind,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        entity: &Entity,
+        component_kind: &ComponentKind,
     ) {
-        todo!()
+        if let Some(accessor) = self.data.component_access(component_kind) {
+            accessor.component_publish(global_world_manager, &mut self.inner, entity);
+        }
     }
 
-    fn entity_unpublish(&mut self, _entity: &Entity) {
-        todo!()
+    fn entity_unpublish(&mut self, entity: &Entity) {
+        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+            WorldMutType::<Entity>::component_unpublish(self, entity, &component_kind);
+        }
     }
 
-    fn component_unpublish(&mut self, _entity: &Entity, _component_kind: &ComponentKind) {
-        todo!()
+    fn component_unpublish(&mut self, entity: &Entity, component_kind: &ComponentKind) {
+        if let Some(accessor) = self.data.component_access(component_kind) {
+            accessor.component_unpublish(&mut self.inner, entity);
+        }
     }
 
     fn entity_enable_delegation(
         &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        entity: &Entity,
     ) {
-        todo!()
+        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+            WorldMutType::<Entity>::component_enable_delegation(
+                self,
+                global_world_manager,
+                entity,
+                &component_kind,
+            );
+        }
     }
 
     fn component_enable_delegation(
         &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
-        _component_kind: &ComponentKind,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        entity: &Entity,
+        component_kind: &ComponentKind,
     ) {
-        todo!()
+        if let Some(accessor) = self.data.component_access(component_kind) {
+            accessor.component_enable_delegation(global_world_manager, &mut self.inner, entity);
+        }
     }
 
-    fn entity_disable_delegation(&mut self, _entity: &Entity) {
-        todo!()
+    fn entity_disable_delegation(&mut self, entity: &Entity) {
+        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+            WorldMutType::<Entity>::component_disable_delegation(self, entity, &component_kind);
+        }
     }
 
-    fn component_disable_delegation(&mut self, _entity: &Entity, _component_kind: &ComponentKind) {
-        todo!()
+    fn component_disable_delegation(&mut self, entity: &Entity, component_kind: &ComponentKind) {
+        if let Some(accessor) = self.data.component_access(component_kind) {
+            accessor.component_disable_delegation(&mut self.inner, entity);
+        }
     }
 }
 
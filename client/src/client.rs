@@ -1,27 +1,42 @@
-use std::{any::Any, collections::VecDeque, hash::Hash, net::SocketAddr, time::Duration};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    net::SocketAddr,
+    time::Duration,
+};
 
 use log::{info, warn};
 
 use naia_shared::{
-    BitWriter, Channel, ChannelKind, ComponentKind, EntityAndGlobalEntityConverter,
-    EntityAndLocalEntityConverter, EntityAuthStatus, EntityConverterMut, EntityDoesNotExistError,
-    EntityEventMessage, EntityResponseEvent, FakeEntityConverter, GameInstant, GlobalEntity,
-    GlobalRequestId, GlobalResponseId, GlobalWorldManagerType, Instant, Message, MessageContainer,
-    PacketType, Protocol, RemoteEntity, Replicate, ReplicatedComponent, Request, Response,
-    ResponseReceiveKey, ResponseSendKey, Serde, SharedGlobalWorldManager, SocketConfig,
-    StandardHeader, SystemChannel, Tick, WorldMutType, WorldRefType,
+    handshake::{read_handshake_payload, HandshakeError},
+    BitWriter, Channel, ChannelKind, ChannelLatencyStats, ComponentKind, CompressionStats,
+    ConnectionParams, ConnectionQuality, EntityAndGlobalEntityConverter,
+    EntityAndLocalEntityConverter, EntityAuthStatus, EntityChannelDebug, EntityConverterMut,
+    EntityDoesNotExistError, EntityEventMessage, EntityResponseEvent, FakeEntityConverter,
+    GameInstant, GlobalEntity, GlobalRequestId, GlobalResponseId, GlobalWorldManagerType,
+    HeartbeatPayload, HostEntityAuthStatus, HostType, Instant, Message, MessageContainer,
+    MessageKind, MessageKinds, MessageReceiptKey, PacketType, Protocol, RemoteEntity, Replicate,
+    ReplicatedComponent, Request, ResponderResult, Response, ResponseReceiveKey, ResponseSendKey,
+    Serde, SharedGlobalWorldManager, SocketConfig, StandardHeader, StreamChannel, StreamMessage,
+    SystemChannel, Tick, WorldMutType, WorldRefType,
 };
 
 use super::{client_config::ClientConfig, error::NaiaClientError, events::Events};
 use crate::{
     connection::{base_time_manager::BaseTimeManager, connection::Connection, io::Io},
     handshake::{HandshakeManager, HandshakeResult, Handshaker},
+    request::{erase_responder, Responder, ResponderOutcome},
     transport::{IdentityReceiverResult, Socket},
     world::{
-        entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
+        authority_writes::{AuthDeniedReason, AuthorityWrites},
+        component_snapshots::ComponentSnapshots,
+        entity_mut::EntityMut,
+        entity_owner::EntityOwner,
+        entity_ref::EntityRef,
         global_world_manager::GlobalWorldManager,
     },
-    ReplicationConfig,
+    ReplicationConfig, TickSyncDiagnostics,
 };
 
 /// Client can send/receive messages to/from a server, and has a pool of
@@ -38,8 +53,16 @@ pub struct Client<E: Copy + Eq + Hash + Send + Sync> {
     handshake_manager: Box<dyn Handshaker>,
     manual_disconnect: bool,
     waitlist_messages: VecDeque<(ChannelKind, Box<dyn Message>)>,
+    heartbeat_payload: Option<HeartbeatPayload>,
+    next_receipt_id: u64,
+    undelivered_receipts: HashSet<MessageReceiptKey>,
+    responders: HashMap<(ChannelKind, MessageKind), Responder>,
+    // set when a sending tick passes, until packets are written for it
+    sending_tick_pending: bool,
     // World
     global_world_manager: GlobalWorldManager<E>,
+    component_snapshots: ComponentSnapshots<E>,
+    authority_writes: AuthorityWrites<E>,
     // Events
     incoming_events: Events<E>,
     // Hacky
@@ -52,10 +75,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         let mut protocol: Protocol = protocol.into();
         protocol.lock();
 
+        if let Some(clock) = &client_config.connection.clock {
+            naia_shared::set_thread_clock(Some(clock.clone()));
+        }
+
         let handshake_manager = HandshakeManager::new(
             client_config.send_handshake_interval,
             client_config.ping_interval,
             client_config.handshake_pings,
+            protocol.schema_hash(),
+            client_config.connection.encryption.is_some(),
         );
 
         let compression_config = protocol.compression.clone();
@@ -70,15 +99,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             io: Io::new(
                 &client_config.connection.bandwidth_measure_duration,
                 &compression_config,
+                client_config.connection.encryption.is_some(),
+                client_config.connection.packet_checksums,
             ),
             server_connection: None,
             handshake_manager: Box::new(handshake_manager),
             manual_disconnect: false,
             waitlist_messages: VecDeque::new(),
+            heartbeat_payload: None,
+            next_receipt_id: 0,
+            undelivered_receipts: HashSet::new(),
+            responders: HashMap::new(),
+            sending_tick_pending: false,
             // World
             global_world_manager: GlobalWorldManager::new(),
+            component_snapshots: ComponentSnapshots::new(client_config.component_snapshot_capacity),
+            authority_writes: AuthorityWrites::new(),
             // Events
-            incoming_events: Events::new(),
+            incoming_events: Events::new(client_config.spawn_with_components_events),
             // Hacky
             queued_entity_auth_release_messages: Vec::new(),
         }
@@ -192,11 +230,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
         for _ in 0..10 {
             let writer = self.handshake_manager.write_disconnect();
-            if self.io.send_packet(writer.to_packet()).is_err() {
-                // TODO: pass this on and handle above
-                warn!("Client Error: Cannot send disconnect packet to Server");
-            }
+            self.io.queue_packet(writer.to_packet());
         }
+        self.flush_packets();
 
         self.manual_disconnect = true;
     }
@@ -211,19 +247,57 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
     /// Must call this regularly (preferably at the beginning of every draw
     /// frame), in a loop until it returns None.
     /// Retrieves incoming update data from the server, and maintains the connection.
+    /// This runs `receive_packets()`, `process_incoming()`, `queue_outgoing()`,
+    /// and `flush_packets()` in order, which can instead be called separately.
     pub fn receive<W: WorldMutType<E>>(&mut self, mut world: W) -> Events<E> {
+        self.receive_packets();
+        let events = self.process_incoming_inner(&mut world);
+        self.queue_outgoing_inner(&world);
+        self.flush_packets();
+        events
+    }
+
+    /// Maintains the connection with the Server, and reads every packet
+    /// waiting on the Socket into internal buffers. Pings & acks which this
+    /// produces are queued until `flush_packets()`, and the packets read are
+    /// not applied until `process_incoming()`.
+    pub fn receive_packets(&mut self) {
         // Need to run this to maintain connection with server, and receive packets
         // until none left
         self.maintain_socket();
+    }
+
+    /// Applies packets read by `receive_packets()` to the World once a tick
+    /// has passed, and returns all resulting events.
+    pub fn process_incoming<W: WorldMutType<E>>(&mut self, mut world: W) -> Events<E> {
+        self.process_incoming_inner(&mut world)
+    }
+
+    /// Writes messages & entity updates into packets if a sending tick has
+    /// passed since the last call, queueing them until `flush_packets()`.
+    /// Sends handshake packets while connecting.
+    pub fn queue_outgoing<W: WorldRefType<E>>(&mut self, world: W) {
+        self.queue_outgoing_inner(&world);
+    }
+
+    /// Writes every queued packet to the Socket. This is the only stage
+    /// which sends, aside from `disconnect()`.
+    pub fn flush_packets(&mut self) {
+        if self.io.is_loaded() && self.io.flush_packets().is_err() {
+            // TODO: pass this on and handle above
+            warn!("Client Error: Cannot send packet to Server");
+        }
+    }
 
+    fn process_incoming_inner<W: WorldMutType<E>>(&mut self, world: &mut W) -> Events<E> {
         self.send_queued_auth_release_messages();
 
         let mut response_events = None;
 
         // all other operations
         if self.is_disconnecting() {
-            self.disconnect_with_events(&mut world);
-            return std::mem::take(&mut self.incoming_events);
+            self.disconnect_with_events(world);
+            return self.take_incoming_events();
         }
 
         let now = Instant::now();
@@ -232,7 +306,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             let (receiving_tick_happened, sending_tick_happened) =
                 connection.time_manager.collect_ticks(&now);
 
-            if let Some((prev_receiving_tick, current_receiving_tick)) = receiving_tick_happened {
+            if let Some((prev_receiving_tick, current_receiving_tick, receiving_kind)) =
+                receiving_tick_happened
+            {
                 // read packets on tick boundary, de-jittering
                 if connection
                     .read_buffered_packets(&self.protocol, &mut self.global_world_manager)
@@ -246,14 +322,15 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 response_events = Some(connection.process_packets(
                     &mut self.global_world_manager,
                     &self.protocol,
-                    &mut world,
+                    world,
                     &now,
                     &mut self.incoming_events,
                 ));
 
                 let mut index_tick = prev_receiving_tick.wrapping_add(1);
                 loop {
-                    self.incoming_events.push_server_tick(index_tick);
+                    self.incoming_events
+                        .push_server_tick(index_tick, receiving_kind);
 
                     if index_tick == current_receiving_tick {
                         break;
@@ -262,33 +339,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 }
             }
 
-            if let Some((prev_sending_tick, current_sending_tick)) = sending_tick_happened {
-                // send outgoing packets
-
-                // collect waiting auth release messages
-                if let Some(mut entities) = connection
-                    .base
-                    .host_world_manager
-                    .world_channel
-                    .collect_auth_release_messages()
-                {
-                    self.queued_entity_auth_release_messages
-                        .append(&mut entities);
-                }
-
-                // send packets
-                connection.send_packets(
-                    &self.protocol,
-                    &now,
-                    &mut self.io,
-                    &world,
-                    &self.global_world_manager,
-                );
+            if let Some((prev_sending_tick, current_sending_tick, sending_kind)) =
+                sending_tick_happened
+            {
+                // outgoing packets are written by `queue_outgoing()`
+                self.sending_tick_pending = true;
 
                 // insert tick events in total range
                 let mut index_tick = prev_sending_tick.wrapping_add(1);
                 loop {
-                    self.incoming_events.push_client_tick(index_tick);
+                    self.incoming_events
+                        .push_client_tick(index_tick, sending_kind);
 
                     if index_tick == current_sending_tick {
                         break;
@@ -296,22 +357,81 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                     index_tick = index_tick.wrapping_add(1);
                 }
             }
-        } else {
-            if self.io.is_loaded() {
-                if let Some(outgoing_packet) = self.handshake_manager.send() {
-                    if self.io.send_packet(outgoing_packet).is_err() {
-                        // TODO: pass this on and handle above
-                        warn!("Client Error: Cannot send handshake packet to Server");
-                    }
-                }
-            }
         }
 
+        // report Messages sent with a receipt which are now fully delivered
+        self.handle_delivered_receipts();
+
+        // answer the Requests which registered responders handle
+        self.handle_responders();
+
         if let Some(events) = response_events {
-            self.process_response_events(&mut world, events);
+            self.process_response_events(world, events);
+        }
+
+        // drop the writes which waited too long for Authority
+        for entity in self
+            .authority_writes
+            .drop_timed_out(&self.client_config.authority_write_timeout)
+        {
+            self.incoming_events
+                .push_authority_writes_dropped(entity, AuthDeniedReason::TimedOut);
         }
 
-        std::mem::take(&mut self.incoming_events)
+        self.take_incoming_events()
+    }
+
+    fn handle_delivered_receipts(&mut self) {
+        let Some(connection) = &mut self.server_connection else {
+            return;
+        };
+        for receipt_key in connection.base.message_manager.take_delivered_receipts() {
+            self.undelivered_receipts.remove(&receipt_key);
+            self.incoming_events.push_message_delivered(receipt_key);
+        }
+    }
+
+    // takes this batch of Events, starting the next one with whatever world
+    // events were held back from it
+    fn take_incoming_events(&mut self) -> Events<E> {
+        let next_events = Events::new(self.client_config.spawn_with_components_events);
+        let mut events = std::mem::replace(&mut self.incoming_events, next_events);
+        let deferred_world_events = events.take_deferred_world_events();
+        self.incoming_events
+            .push_world_events(deferred_world_events);
+        events
+    }
+
+    fn queue_outgoing_inner<W: WorldRefType<E>>(&mut self, world: &W) {
+        if let Some(connection) = &mut self.server_connection {
+            if !std::mem::take(&mut self.sending_tick_pending) {
+                return;
+            }
+
+            // collect waiting auth release messages
+            if let Some(mut entities) = connection
+                .base
+                .host_world_manager
+                .world_channel
+                .collect_auth_release_messages()
+            {
+                self.queued_entity_auth_release_messages
+                    .append(&mut entities);
+            }
+
+            // send packets
+            connection.send_packets(
+                &self.protocol,
+                &Instant::now(),
+                &mut self.io,
+                world,
+                &self.global_world_manager,
+            );
+        } else if self.io.is_loaded() {
+            if let Some(outgoing_packet) = self.handshake_manager.send() {
+                self.io.queue_packet(outgoing_packet);
+            }
+        }
     }
 
     // Messages
@@ -322,6 +442,106 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.send_message_inner(&ChannelKind::of::<C>(), cloned_message);
     }
 
+    /// Queues up a Message to be sent to the Server, like `send_message()`,
+    /// returning a receipt which is reported through `MessageDeliveredEvent`
+    /// once the Server has acknowledged every fragment of the Message.
+    /// Returns an error if the Channel is not reliable, as delivery over it
+    /// would never be confirmed, or if not yet connected to the Server
+    pub fn send_message_with_receipt<C: Channel, M: Message>(
+        &mut self,
+        message: &M,
+    ) -> Result<MessageReceiptKey, NaiaClientError> {
+        let channel_kind = ChannelKind::of::<C>();
+        let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
+        if !channel_settings.can_send_to_server() {
+            panic!("Cannot send message to Server on this Channel");
+        }
+        if !channel_settings.reliable() {
+            return Err(NaiaClientError::from_message(
+                "Message receipts can only be tracked over reliable Channels",
+            ));
+        }
+
+        let Some(connection) = &mut self.server_connection else {
+            return Err(NaiaClientError::from_message(
+                "currently not connected to server",
+            ));
+        };
+
+        let receipt_key = MessageReceiptKey::new(self.next_receipt_id);
+        self.next_receipt_id = self.next_receipt_id.wrapping_add(1);
+
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let mut message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        if channel_settings.tracks_latency() {
+            let time_manager = &connection.time_manager;
+            message.set_send_instant(time_manager.instant_to_server(&time_manager.game_time_now()));
+        }
+        connection.base.message_manager.send_message_with_receipt(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            message,
+            receipt_key,
+        );
+        self.undelivered_receipts.insert(receipt_key);
+
+        Ok(receipt_key)
+    }
+
+    /// Returns whether the Message sent with the given receipt has been
+    /// delivered in full. A Message still in flight when the connection is
+    /// lost is never reported as delivered
+    pub fn receipt_received(&self, receipt_key: &MessageReceiptKey) -> bool {
+        !self.undelivered_receipts.contains(receipt_key)
+    }
+
+    /// Queues up a stream payload to be sent to the Server. Streams are
+    /// reliable & ordered, and payloads larger than a packet are fragmented,
+    /// so this suits large one-off payloads. Received on the Server as a
+    /// `StreamMessageEvent`. Panics unless streams are enabled with
+    /// `Protocol::enable_streams()`
+    pub fn send_stream_message(&mut self, bytes: &[u8]) {
+        if !self.protocol.streams {
+            panic!("Cannot send stream payloads: streams are not enabled! Enable them in the Protocol, with the `enable_streams()` method, on both the Client & Server");
+        }
+        let message = StreamMessage::new(bytes);
+        self.send_message::<StreamChannel, StreamMessage>(&message);
+    }
+
+    /// Returns the stream payloads received from the Server since the last
+    /// call, in the order they were sent
+    pub fn receive_stream_messages(&mut self) -> Vec<Vec<u8>> {
+        let Some(connection) = &mut self.server_connection else {
+            return Vec::new();
+        };
+        connection.take_stream_messages()
+    }
+
+    /// Sets a function producing a Message to send to the Server with each
+    /// Heartbeat, once per heartbeat interval, received as a
+    /// `HeartbeatPayloadEvent`. The function is called once immediately, and
+    /// returns an error if the Message is larger than
+    /// `HEARTBEAT_PAYLOAD_MAX_BYTES`. Later oversized Messages are not sent
+    pub fn set_heartbeat_payload<M: Message>(
+        &mut self,
+        provider: impl FnMut() -> Option<M> + Send + Sync + 'static,
+    ) -> Result<(), NaiaClientError> {
+        let heartbeat_payload =
+            HeartbeatPayload::new(self.client_config.connection.heartbeat_interval, provider)
+                .map_err(|error| NaiaClientError::Wrapped(Box::new(error)))?;
+        self.heartbeat_payload = Some(heartbeat_payload);
+        Ok(())
+    }
+
+    /// Stops sending a payload with each Heartbeat
+    pub fn clear_heartbeat_payload(&mut self) {
+        self.heartbeat_payload = None;
+    }
+
     fn send_message_inner(&mut self, channel_kind: &ChannelKind, message_box: Box<dyn Message>) {
         let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
         if !channel_settings.can_send_to_server() {
@@ -337,7 +557,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 &self.global_world_manager,
                 &mut connection.base.local_world_manager,
             );
-            let message = MessageContainer::from_write(message_box, &mut converter);
+            let mut message = MessageContainer::from_write(message_box, &mut converter);
+            if channel_settings.tracks_latency() {
+                let time_manager = &connection.time_manager;
+                message.set_send_instant(
+                    time_manager.instant_to_server(&time_manager.game_time_now()),
+                );
+            }
             connection.base.message_manager.send_message(
                 &self.protocol.message_kinds,
                 &mut converter,
@@ -361,6 +587,28 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         Ok(ResponseReceiveKey::new(id))
     }
 
+    /// Sends a Request, giving up on it if no Response has arrived within
+    /// `timeout`. A Request which times out is cancelled, so a Response
+    /// arriving afterwards is dropped, and is reported through
+    /// `RequestTimeoutEvent<Q>`
+    pub fn send_request_with_timeout<C: Channel, Q: Request>(
+        &mut self,
+        request: &Q,
+        timeout: Duration,
+    ) -> Result<ResponseReceiveKey<Q::Response>, NaiaClientError> {
+        let response_key = self.send_request::<C, Q>(request)?;
+        let mut deadline = Instant::now();
+        deadline.add_millis(timeout.as_millis() as u32);
+        if let Some(connection) = &mut self.server_connection {
+            connection.global_request_manager.set_deadline(
+                &response_key.request_id(),
+                MessageKind::of::<Q>(),
+                deadline,
+            );
+        }
+        Ok(response_key)
+    }
+
     fn send_request_inner(
         &mut self,
         channel_kind: &ChannelKind,
@@ -385,7 +633,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         );
 
         let request_id = connection.global_request_manager.create_request_id();
-        let message = MessageContainer::from_write(request_box, &mut converter);
+        let mut message = MessageContainer::from_write(request_box, &mut converter);
+        if channel_settings.tracks_latency() {
+            let time_manager = &connection.time_manager;
+            message.set_send_instant(time_manager.instant_to_server(&time_manager.game_time_now()));
+        }
         connection.base.message_manager.send_request(
             &self.protocol.message_kinds,
             &mut converter,
@@ -430,7 +682,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             &mut connection.base.local_world_manager,
         );
 
-        let response = MessageContainer::from_write(response_box, &mut converter);
+        let mut response = MessageContainer::from_write(response_box, &mut converter);
+        if connection
+            .base
+            .message_manager
+            .tracks_latency(&channel_kind)
+        {
+            let time_manager = &connection.time_manager;
+            response
+                .set_send_instant(time_manager.instant_to_server(&time_manager.game_time_now()));
+        }
         connection.base.message_manager.send_response(
             &self.protocol.message_kinds,
             &mut converter,
@@ -441,6 +702,59 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         return true;
     }
 
+    /// Registers a handler to answer each Request of type Q the Server sends
+    /// over Channel C, as it is processed. Requests the handler declines are
+    /// reported through `RequestEvent` as usual. Replaces any handler already
+    /// registered for C & Q.
+    ///
+    /// Handlers aren't given the Client, so they can't call back into it;
+    /// whatever they need should be owned by them or shared with them. If
+    /// that state is behind a lock, the thread driving the Client must not
+    /// hold it while receiving. Deferred Requests are answered with
+    /// `send_response()`, from outside the handler
+    pub fn register_responder<C: Channel, Q: Request>(
+        &mut self,
+        handler: impl FnMut(Q) -> ResponderResult<Q::Response> + Send + Sync + 'static,
+    ) {
+        self.responders.insert(
+            (ChannelKind::of::<C>(), MessageKind::of::<Q>()),
+            erase_responder(handler),
+        );
+    }
+
+    /// Removes the handler registered for Requests of type Q over Channel C,
+    /// returning whether there was one
+    pub fn unregister_responder<C: Channel, Q: Request>(&mut self) -> bool {
+        self.responders
+            .remove(&(ChannelKind::of::<C>(), MessageKind::of::<Q>()))
+            .is_some()
+    }
+
+    fn handle_responders(&mut self) {
+        if self.responders.is_empty() {
+            return;
+        }
+        let mut responses = Vec::new();
+        for ((channel_kind, message_kind), responder) in self.responders.iter_mut() {
+            let requests = self
+                .incoming_events
+                .take_requests_of_kind(channel_kind, message_kind);
+            for (response_id, request) in requests {
+                match responder(&response_id, request) {
+                    ResponderOutcome::Respond(response) => responses.push((response_id, response)),
+                    ResponderOutcome::Deferred => {}
+                    ResponderOutcome::Declined(request) => {
+                        self.incoming_events
+                            .push_request(channel_kind, response_id, request);
+                    }
+                }
+            }
+        }
+        for (response_id, response) in responses {
+            self.send_response_inner(&response_id, response);
+        }
+    }
+
     pub fn receive_response<S: Response>(
         &mut self,
         response_key: &ResponseReceiveKey<S>,
@@ -461,6 +775,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             .unwrap();
         return Some(response);
     }
+
+    /// Cancels an in-flight Request. Any Response which arrives afterwards will
+    /// be dropped rather than matched. Returns whether a live Request was cancelled.
+    pub fn cancel_request<S: Response>(&mut self, response_key: &ResponseReceiveKey<S>) -> bool {
+        let Some(connection) = &mut self.server_connection else {
+            return false;
+        };
+        let request_id = response_key.request_id();
+        connection
+            .global_request_manager
+            .cancel_request_id(&request_id)
+    }
     //
 
     fn on_connect(&mut self) {
@@ -504,6 +830,35 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         }
     }
 
+    // Prediction
+
+    /// Stores a copy of every Component on the Entities not owned by the
+    /// Server, such as locally duplicated prediction Entities, keyed on the
+    /// given Tick. Replaces any snapshot of the same or a later Tick
+    pub fn store_component_snapshot<W: WorldRefType<E>>(&mut self, world: W, tick: &Tick) {
+        let mut snapshot = HashMap::new();
+        for entity in world.entities() {
+            // Server-owned Components can't be written by the Client
+            if self.entity_owner(&entity).is_server() {
+                continue;
+            }
+            for (component_kind, _, _, _) in self.protocol.component_kinds.iter() {
+                if let Some(component) = world.component_of_kind(&entity, &component_kind) {
+                    snapshot.insert((entity, component_kind), component.copy_to_box());
+                }
+            }
+        }
+        self.component_snapshots.insert(*tick, snapshot);
+    }
+
+    /// Sets every Component stored by `store_component_snapshot()` for the
+    /// given Tick back to its stored state. Snapshots of later Ticks are
+    /// discarded, as they are expected to be re-simulated & stored again.
+    /// Returns whether a snapshot for the Tick was found
+    pub fn rollback_to<W: WorldMutType<E>>(&mut self, mut world: W, tick: &Tick) -> bool {
+        self.component_snapshots.rollback_to(&mut world, tick)
+    }
+
     // Entities
 
     /// Creates a new Entity and returns an EntityMut which can be used for
@@ -556,11 +911,137 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         world.entities()
     }
 
+    /// Gets the owner of the Entity. A delegated Entity is owned by this
+    /// Client while it holds Authority over it
     pub fn entity_owner(&self, entity: &E) -> EntityOwner {
-        if let Some(owner) = self.global_world_manager.entity_owner(entity) {
-            return owner;
+        let Some(owner) = self.global_world_manager.entity_owner(entity) else {
+            return EntityOwner::Local;
+        };
+        if owner.is_server()
+            && self.global_world_manager.entity_authority_status(entity)
+                == Some(EntityAuthStatus::Granted)
+        {
+            return EntityOwner::Client;
+        }
+        return owner;
+    }
+
+    /// Return a list of all Entities owned by this Client, either spawned by
+    /// it or delegated Entities it holds Authority over
+    pub fn owned_entities(&self) -> Vec<E> {
+        self.owned_entities_by(EntityOwner::Client)
+    }
+
+    /// Return a list of all Entities owned by the Server, leaving out
+    /// delegated Entities while this Client holds Authority over them
+    pub fn server_owned_entities(&self) -> Vec<E> {
+        self.owned_entities_by(EntityOwner::Server)
+    }
+
+    fn owned_entities_by(&self, owner: EntityOwner) -> Vec<E> {
+        self.global_world_manager
+            .entities()
+            .into_iter()
+            .filter(|entity| self.entity_owner(entity) == owner)
+            .collect()
+    }
+
+    /// Returns whether the Entity's Components can be written to right now:
+    /// a delegated Entity only can while Authority over it is held or
+    /// requested, & an Entity owned by the Server never can
+    pub fn can_mutate(&self, entity: &E) -> bool {
+        if let Some(auth_status) = self.global_world_manager.entity_authority_status(entity) {
+            return HostEntityAuthStatus::new(HostType::Client, auth_status).can_mutate();
         }
-        return EntityOwner::Local;
+        !self.entity_owner(entity).is_server()
+    }
+
+    /// Requests Authority over a delegated Entity, & holds back the given
+    /// write until it is granted. Held back writes, & those to Entities which
+    /// may already be written to, are run by the next call to
+    /// `run_authority_writes()` with the same World type. If Authority is
+    /// denied, or not granted within `ClientConfig::authority_write_timeout`,
+    /// the write is dropped & an AuthDeniedEvent is received instead
+    pub fn request_authority_then<W: 'static>(
+        &mut self,
+        entity: &E,
+        write: impl FnOnce(&mut W) + Send + 'static,
+    ) {
+        match self.global_world_manager.entity_authority_status(entity) {
+            Some(EntityAuthStatus::Granted) => {
+                self.authority_writes.queue_ready(write);
+            }
+            Some(EntityAuthStatus::Available) => {
+                self.entity_request_authority(entity);
+                self.authority_writes.queue_pending(entity, write);
+            }
+            Some(EntityAuthStatus::Requested) => {
+                self.authority_writes.queue_pending(entity, write);
+            }
+            Some(EntityAuthStatus::Releasing) | Some(EntityAuthStatus::Denied) => {
+                self.incoming_events
+                    .push_authority_writes_dropped(*entity, AuthDeniedReason::Denied);
+            }
+            None => {
+                // not delegated, so there's no Authority to wait for
+                if self.can_mutate(entity) {
+                    self.authority_writes.queue_ready(write);
+                } else {
+                    self.incoming_events
+                        .push_authority_writes_dropped(*entity, AuthDeniedReason::Denied);
+                }
+            }
+        }
+    }
+
+    /// Runs the writes queued with `request_authority_then()` whose Authority
+    /// has been granted. Call this after `receive()`
+    pub fn run_authority_writes<W: 'static>(&mut self, world: &mut W) {
+        self.authority_writes.run(world);
+    }
+
+    // Local scope
+
+    /// Stop applying incoming Component updates to the given Entity, to save
+    /// the cost of deserializing updates the application does not care about
+    /// (i.e. Entities outside of a minimized viewport). The Server is not
+    /// informed, and Spawn/Despawn/Insert/Remove events are still processed.
+    ///
+    /// This is a local optimization only: while an Entity is ignored its
+    /// Components will hold stale state. Call `unignore_entity()` to request
+    /// a full update from the Server.
+    pub fn ignore_entity(&mut self, entity: &E) {
+        let Some(connection) = &mut self.server_connection else {
+            warn!("Cannot ignore Entity while disconnected");
+            return;
+        };
+        connection.base.remote_world_manager.ignore_entity(entity);
+    }
+
+    /// Resume applying incoming Component updates to an Entity previously
+    /// passed to `ignore_entity()`, and request the Entity's full state from
+    /// the Server so that any stale Components are brought up to date.
+    pub fn unignore_entity(&mut self, entity: &E) {
+        let Some(connection) = &mut self.server_connection else {
+            return;
+        };
+        if !connection.base.remote_world_manager.unignore_entity(entity) {
+            return;
+        }
+        let message = EntityEventMessage::new_request_resync(&self.global_world_manager, entity);
+        self.send_message::<SystemChannel, EntityEventMessage>(&message);
+    }
+
+    /// Returns whether incoming Component updates to the given Entity are
+    /// being ignored
+    pub fn entity_is_ignored(&self, entity: &E) -> bool {
+        let Some(connection) = &self.server_connection else {
+            return false;
+        };
+        connection
+            .base
+            .remote_world_manager
+            .entity_is_ignored(entity)
     }
 
     // Replicate options & authority management
@@ -738,6 +1219,33 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             .time_manager.jitter()
     }
 
+    /// Gets the rolling quality of the connection to the Server: packet loss,
+    /// round trip time, jitter and bytes carried each second
+    pub fn connection_quality(&mut self) -> ConnectionQuality {
+        self.server_connection
+            .as_mut()
+            .expect("it is expected that you should verify whether the client is connected before calling this method")
+            .connection_quality(&mut self.io)
+    }
+
+    /// Totals of the bytes compressed for the Server & the bytes they were
+    /// compressed into, since connecting. None if client-to-server
+    /// compression is not enabled in the Protocol
+    pub fn compression_stats(&self) -> Option<CompressionStats> {
+        self.io.compression_stats()
+    }
+
+    /// Gets percentiles of how long recent Messages & Requests received from
+    /// the Server over Channel C took to arrive. Only Channels configured
+    /// `with_latency_tracking(true)` are measured
+    pub fn channel_latency<C: Channel>(&self) -> Option<ChannelLatencyStats> {
+        let connection = self.server_connection.as_ref()?;
+        connection
+            .base
+            .message_manager
+            .channel_latency(&ChannelKind::of::<C>())
+    }
+
     // Ticks
 
     /// Gets the current tick of the Client
@@ -778,6 +1286,28 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         return None;
     }
 
+    /// Gets the current GameInstant of the Client's clock, which is kept in
+    /// sync with the Server's. The send instants of `TimedMessageEvent` &
+    /// `TimedRequestEvent` are given on this clock
+    pub fn game_time_now(&self) -> Option<GameInstant> {
+        let connection = self.server_connection.as_ref()?;
+        return Some(connection.time_manager.game_time_now());
+    }
+
+    /// Gets a snapshot of the internal tick synchronization state, for diagnostics
+    pub fn tick_sync_diagnostics(&self) -> Option<TickSyncDiagnostics> {
+        let connection = self.server_connection.as_ref()?;
+        return Some(connection.time_manager.tick_sync_diagnostics());
+    }
+
+    /// Gets a snapshot of every channel receiving Entity actions from the
+    /// Server, including any actions waiting on an earlier one, for diagnosing
+    /// an Entity which has stopped updating
+    pub fn entity_channels_debug(&self) -> Option<Vec<EntityChannelDebug<RemoteEntity>>> {
+        let connection = self.server_connection.as_ref()?;
+        return Some(connection.base.remote_world_reader.debug_dump());
+    }
+
     // Interpolation
 
     /// Gets the interpolation tween amount for the current frame, for use by entities on the Client Tick (i.e. predicted)
@@ -805,6 +1335,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.io.incoming_bandwidth()
     }
 
+    /// The number of packets received during the current connection which were
+    /// dropped because their checksum did not match, see
+    /// `ConnectionConfig::packet_checksums`
+    pub fn corrupt_packets_count(&self) -> u64 {
+        self.io.corrupt_packets()
+    }
+
     // Crate-Public methods
 
     /// Despawns the Entity, if it exists.
@@ -1054,6 +1591,34 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
                 // push outgoing event
                 self.incoming_events.push_auth_grant(*entity);
+                self.authority_writes.grant(entity);
+            }
+            (EntityAuthStatus::Available, EntityAuthStatus::Granted)
+            | (EntityAuthStatus::Denied, EntityAuthStatus::Granted) => {
+                // Authority transferred by the Server, without a request
+
+                let Some(connection) = &mut self.server_connection else {
+                    return;
+                };
+                // Migrate Entity from Remote -> Host connection
+                let component_kinds = self.global_world_manager.component_kinds(entity).unwrap();
+                let new_host_entity = connection.base.host_world_manager.track_remote_entity(
+                    &mut connection.base.local_world_manager,
+                    entity,
+                    component_kinds,
+                );
+
+                // the Server needs our Host Entity, which a request would have carried
+                let message = EntityEventMessage::new_request_authority(
+                    &self.global_world_manager,
+                    entity,
+                    new_host_entity,
+                );
+                self.send_message::<SystemChannel, EntityEventMessage>(&message);
+
+                // push outgoing event
+                self.incoming_events.push_auth_grant(*entity);
+                self.authority_writes.grant(entity);
             }
             (EntityAuthStatus::Releasing, EntityAuthStatus::Available)
             | (EntityAuthStatus::Granted, EntityAuthStatus::Available) => {
@@ -1075,6 +1640,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 // push outgoing event
                 self.incoming_events.push_auth_deny(*entity);
             }
+            (EntityAuthStatus::Requested, EntityAuthStatus::Denied) => {
+                // another host was granted Authority before our request arrived
+
+                // get rid of reserved host entity
+                if let Some(connection) = &mut self.server_connection {
+                    connection
+                        .base
+                        .local_world_manager
+                        .remove_reserved_host_entity(entity);
+                }
+
+                // push outgoing event
+                self.incoming_events.push_auth_deny(*entity);
+                if self.authority_writes.drop_pending(entity) {
+                    self.incoming_events
+                        .push_authority_writes_dropped(*entity, AuthDeniedReason::Denied);
+                }
+            }
             (EntityAuthStatus::Denied, EntityAuthStatus::Available) => {
                 // push outgoing event
                 self.incoming_events.push_auth_reset(*entity);
@@ -1096,6 +1679,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             (EntityAuthStatus::Available, EntityAuthStatus::Available) => {
                 // auth was released before it was granted, continue as normal
             }
+            (EntityAuthStatus::Denied, EntityAuthStatus::Denied) => {
+                // told of the same grant twice, as delegation was enabled
+                // while it was being made, continue as normal
+            }
             (_, _) => {
                 panic!(
                     "-- Entity updated authority, not handled -- {:?} -> {:?}",
@@ -1145,6 +1732,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                     self.io = Io::new(
                         &self.client_config.connection.bandwidth_measure_duration,
                         &self.protocol.compression,
+                        self.client_config.connection.encryption.is_some(),
+                        self.client_config.connection.packet_checksums,
                     );
 
                     if code == 401 {
@@ -1173,26 +1762,67 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             match self.io.recv_reader() {
                 Ok(Some(mut reader)) => {
                     match self.handshake_manager.recv(&mut reader) {
-                        Some(HandshakeResult::Connected(time_manager)) => {
+                        Some(HandshakeResult::Connected(
+                            mut time_manager,
+                            welcome_payload,
+                            packet_cipher,
+                        )) => {
+                            if let Some(packet_cipher) = packet_cipher {
+                                self.io.set_packet_cipher(*packet_cipher);
+                            }
+
+                            time_manager.set_tick_sync(
+                                self.client_config.tick_offset_bias,
+                                self.client_config.tick_resync_threshold,
+                            );
+
                             // new connect!
                             self.server_connection = Some(Connection::new(
                                 &self.client_config.connection,
                                 &self.protocol.channel_kinds,
-                                time_manager,
+                                *time_manager,
                                 &self.global_world_manager,
                             ));
                             self.on_connect();
 
+                            if let Some(payload) = welcome_payload {
+                                match read_handshake_payload(&self.protocol.message_kinds, &payload)
+                                {
+                                    Ok(welcome) => self.incoming_events.push_welcome(welcome),
+                                    Err(_) => warn!("Could not read welcome Message"),
+                                }
+                            }
+
                             let server_addr = self.server_address_unwrapped();
-                            self.incoming_events.push_connection(&server_addr);
+                            let rtt = self.rtt();
+                            let params = ConnectionParams::client(
+                                self.protocol.compression.as_ref(),
+                                Some(rtt),
+                            );
+                            self.incoming_events.push_connection(&server_addr, params);
+
+                            // packets queued behind the connect response belong
+                            // to the connection, so are read from there
+                            return;
+                        }
+                        Some(HandshakeResult::Rejected(error)) => {
+                            warn!("{}", error);
+                            let server_addr = self.server_address_unwrapped();
+                            self.incoming_events.clear();
+                            self.incoming_events.push_rejection(&server_addr);
+                            if let HandshakeError::Rejected { code, payload } = error {
+                                let message = payload.and_then(|payload| {
+                                    read_handshake_payload(&self.protocol.message_kinds, &payload)
+                                        .ok()
+                                });
+                                self.incoming_events.push_rejection_details(code, message);
+                            } else {
+                                self.incoming_events
+                                    .push_error(NaiaClientError::Wrapped(Box::new(error)));
+                            }
+                            self.disconnect_reset_connection();
+                            return;
                         }
-                        // Some(HandshakeResult::Rejected) => {
-                        //     let server_addr = self.server_address_unwrapped();
-                        //     self.incoming_events.clear();
-                        //     self.incoming_events.push_rejection(&server_addr);
-                        //     self.disconnect_reset_connection();
-                        //     return;
-                        // }
                         None => {}
                     }
                 }
@@ -1216,9 +1846,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             panic!("Should have checked for this above");
         };
 
-        Self::handle_heartbeats(connection, &mut self.io);
+        Self::handle_heartbeats(
+            connection,
+            &mut self.io,
+            &self.protocol.message_kinds,
+            self.heartbeat_payload.as_mut(),
+        );
         Self::handle_pings(connection, &mut self.io);
-        Self::handle_empty_acks(connection, &mut self.io);
+        Self::handle_empty_acks(connection, &mut self.io, &self.protocol.message_kinds);
 
         // receive from socket
         loop {
@@ -1226,8 +1861,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 Ok(Some(mut reader)) => {
                     connection.base.mark_heard();
 
-                    let header = StandardHeader::de(&mut reader)
-                        .expect("unable to parse header from incoming packet");
+                    let Ok(header) = StandardHeader::de(&mut reader) else {
+                        warn!("Client Error: cannot read malformed packet header");
+                        continue;
+                    };
 
                     match header.packet_type {
                         PacketType::Data
@@ -1277,11 +1914,20 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                             }
                         }
                         PacketType::Heartbeat => {
-                            // already marked as heard, job done
+                            // already marked as heard, Heartbeats from older
+                            // Servers end here, which reads as no payload
+                            if let Ok(Some(payload)) =
+                                HeartbeatPayload::read(&self.protocol.message_kinds, &mut reader)
+                            {
+                                self.incoming_events.push_heartbeat_payload(payload);
+                            }
                         }
                         PacketType::Ping => {
                             let Ok(ping_index) = BaseTimeManager::read_ping(&mut reader) else {
-                                panic!("unable to read ping index");
+                                warn!(
+                                    "Client Error: Cannot read malformed ping packet from Server"
+                                );
+                                continue;
                             };
                             BaseTimeManager::send_pong(connection, &mut self.io, ping_index);
                         }
@@ -1306,23 +1952,65 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 }
             }
         }
+
+        // give up on requests whose responses did not arrive in time
+        for (request_id, request_kind) in connection
+            .global_request_manager
+            .expire_requests(&Instant::now())
+        {
+            self.incoming_events
+                .push_request_timeout(&request_kind, request_id);
+        }
+
+        // grade the connection, now that the acks received have been processed
+        let quality = connection.connection_quality(&mut self.io);
+        self.io.set_connection_quality(quality);
+        if let Some(level) = connection.base.update_quality_level(&quality) {
+            self.incoming_events.push_connection_quality_change(level);
+        }
     }
 
-    fn handle_heartbeats(connection: &mut Connection<E>, io: &mut Io) {
+    fn handle_heartbeats(
+        connection: &mut Connection<E>,
+        io: &mut Io,
+        message_kinds: &MessageKinds,
+        heartbeat_payload: Option<&mut HeartbeatPayload>,
+    ) {
+        // a payload is sent every heartbeat interval, even while busy
+        let heartbeat_payload =
+            heartbeat_payload.filter(|heartbeat_payload| heartbeat_payload.should_send());
+
         // send heartbeats
-        if connection.base.should_send_heartbeat() {
-            Self::send_heartbeat_packet(connection, io);
+        if let Some(heartbeat_payload) = heartbeat_payload {
+            Self::send_heartbeat_packet(
+                connection,
+                io,
+                message_kinds,
+                Some(&mut *heartbeat_payload),
+            );
+            heartbeat_payload.mark_sent();
+        } else if connection.base.should_send_heartbeat() {
+            Self::send_heartbeat_packet(connection, io, message_kinds, None);
         }
     }
 
-    fn handle_empty_acks(connection: &mut Connection<E>, io: &mut Io) {
+    fn handle_empty_acks(
+        connection: &mut Connection<E>,
+        io: &mut Io,
+        message_kinds: &MessageKinds,
+    ) {
         // send empty acks
         if connection.base.should_send_empty_ack() {
-            Self::send_heartbeat_packet(connection, io);
+            Self::send_heartbeat_packet(connection, io, message_kinds, None);
         }
     }
 
-    fn send_heartbeat_packet(connection: &mut Connection<E>, io: &mut Io) {
+    fn send_heartbeat_packet(
+        connection: &mut Connection<E>,
+        io: &mut Io,
+        message_kinds: &MessageKinds,
+        heartbeat_payload: Option<&mut HeartbeatPayload>,
+    ) {
         let mut writer = BitWriter::new();
 
         // write header
@@ -1330,11 +2018,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             .base
             .write_header(PacketType::Heartbeat, &mut writer);
 
+        // write payload
+        HeartbeatPayload::write(heartbeat_payload, message_kinds, &mut writer);
+
         // send packet
-        if io.send_packet(writer.to_packet()).is_err() {
-            // TODO: pass this on and handle above
-            warn!("Client Error: Cannot send heartbeat packet to Server");
-        }
+        io.queue_packet(writer.to_packet());
         connection.base.mark_sent();
     }
 
@@ -1380,16 +2068,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.io = Io::new(
             &self.client_config.connection.bandwidth_measure_duration,
             &self.protocol.compression,
+            self.client_config.connection.encryption.is_some(),
+            self.client_config.connection.packet_checksums,
         );
 
         self.handshake_manager = Box::new(HandshakeManager::new(
             self.client_config.send_handshake_interval,
             self.client_config.ping_interval,
             self.client_config.handshake_pings,
+            self.protocol.schema_hash(),
+            self.client_config.connection.encryption.is_some(),
         ));
 
         self.manual_disconnect = false;
+        self.sending_tick_pending = false;
         self.global_world_manager = GlobalWorldManager::new();
+        self.component_snapshots =
+            ComponentSnapshots::new(self.client_config.component_snapshot_capacity);
+        self.authority_writes = AuthorityWrites::new();
         self.queued_entity_auth_release_messages = Vec::new();
     }
 
@@ -1473,6 +2169,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 EntityResponseEvent::EntityUpdateAuthority(entity, new_auth_status) => {
                     self.entity_update_authority(&entity, new_auth_status);
                 }
+                EntityResponseEvent::EntityRequestResync(_entity) => {
+                    panic!("Client should never receive an EntityRequestResync event");
+                }
                 EntityResponseEvent::EntityMigrateResponse(world_entity, remote_entity) => {
                     self.entity_complete_delegation(world, &world_entity);
                     self.add_redundant_remote_entity_to_host(&world_entity, remote_entity);
@@ -1481,6 +2180,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                         .entity_update_authority(&world_entity, EntityAuthStatus::Granted);
 
                     self.incoming_events.push_auth_grant(world_entity);
+                    self.authority_writes.grant(&world_entity);
                 }
             }
         }
@@ -1562,3 +2262,171 @@ impl ConnectionStatus {
         self == &ConnectionStatus::Disconnecting
     }
 }
+
+#[cfg(test)]
+mod ignore_entity_tests {
+    use std::collections::HashMap;
+
+    use naia_demo_world::{Entity, World, WorldRefType};
+    use naia_shared::{
+        BitReader, BitWriter, ComponentKind, ComponentUpdate, DiffMask, EntityAction, EntityEvent,
+        FakeEntityConverter, Instant, LocalWorldManager, Property, Protocol, RemoteEntity,
+        RemoteWorldEvents, RemoteWorldManager, Replicate, Tick,
+    };
+
+    use crate::world::global_world_manager::GlobalWorldManager;
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<u8>,
+    }
+
+    struct TestClient {
+        protocol: Protocol,
+        world: World,
+        global_world_manager: GlobalWorldManager<Entity>,
+        local_world_manager: LocalWorldManager<Entity>,
+        remote_world_manager: RemoteWorldManager<Entity>,
+    }
+
+    impl TestClient {
+        fn new() -> Self {
+            Self {
+                protocol: Protocol::builder().add_component::<Position>().build(),
+                world: World::default(),
+                global_world_manager: GlobalWorldManager::new(),
+                local_world_manager: LocalWorldManager::new(0),
+                remote_world_manager: RemoteWorldManager::new(),
+            }
+        }
+
+        fn process(&mut self, world_events: RemoteWorldEvents<Entity>) -> Vec<EntityEvent<Entity>> {
+            self.remote_world_manager.process_world_events(
+                &self.global_world_manager,
+                &mut self.local_world_manager,
+                &self.protocol.component_kinds,
+                &mut self.world.proxy_mut(),
+                &Instant::now(),
+                world_events,
+            )
+        }
+
+        fn spawn_position(&mut self, x: u8) -> Entity {
+            let remote_entity = RemoteEntity::new(1);
+            let component_kind = ComponentKind::of::<Position>();
+            let mut incoming_components: HashMap<
+                (RemoteEntity, ComponentKind),
+                Box<dyn Replicate>,
+            > = HashMap::new();
+            incoming_components.insert((remote_entity, component_kind), self.position(x));
+            let events = self.process(RemoteWorldEvents {
+                incoming_actions: vec![EntityAction::SpawnEntity(
+                    remote_entity,
+                    vec![component_kind],
+                )],
+                incoming_components,
+                incoming_updates: Vec::new(),
+            });
+            let Some(EntityEvent::SpawnEntity(entity)) = events.first() else {
+                panic!("expected Entity to be spawned");
+            };
+            *entity
+        }
+
+        // a Component as read off the wire from the Server
+        fn position(&self, x: u8) -> Box<dyn Replicate> {
+            let mut writer = BitWriter::new();
+            Position::new_complete(x).write(
+                &self.protocol.component_kinds,
+                &mut writer,
+                &mut FakeEntityConverter,
+            );
+            let bytes = writer.to_bytes();
+
+            let mut reader = BitReader::new(&bytes);
+            self.protocol
+                .component_kinds
+                .read(&mut reader, &FakeEntityConverter)
+                .unwrap()
+        }
+
+        // a full update, as sent by the Server in response to a resync request
+        fn position_update(&self, x: u8) -> ComponentUpdate {
+            let component = Position::new_complete(x);
+            let mut diff_mask = DiffMask::new(component.diff_mask_size());
+            diff_mask.fill();
+
+            let mut writer = BitWriter::new();
+            ComponentKind::of::<Position>().ser(&self.protocol.component_kinds, &mut writer);
+            component.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+            let bytes = writer.to_bytes();
+
+            let mut reader = BitReader::new(&bytes);
+            self.protocol
+                .component_kinds
+                .read_create_update(&mut reader)
+                .unwrap()
+        }
+
+        // returns the number of updates applied to the world
+        fn receive_updates(
+            &mut self,
+            incoming_updates: Vec<(Tick, Entity, ComponentUpdate)>,
+        ) -> usize {
+            let events = self.process(RemoteWorldEvents {
+                incoming_actions: Vec::new(),
+                incoming_components: HashMap::new(),
+                incoming_updates,
+            });
+            events
+                .iter()
+                .filter(|event| matches!(event, EntityEvent::UpdateComponent(..)))
+                .count()
+        }
+
+        fn position_x(&self, entity: &Entity) -> u8 {
+            let world = self.world.proxy();
+            let component = world
+                .component_of_kind(entity, &ComponentKind::of::<Position>())
+                .unwrap();
+            let position = component.to_any().downcast_ref::<Position>().unwrap();
+            *position.x
+        }
+    }
+
+    #[test]
+    fn ignored_entity_skips_updates() {
+        let mut client = TestClient::new();
+        let entity = client.spawn_position(0);
+
+        client.remote_world_manager.ignore_entity(&entity);
+
+        let updates = (1..=100)
+            .map(|x| (x as Tick, entity, client.position_update(x as u8)))
+            .collect();
+        let applied = client.receive_updates(updates);
+
+        assert_eq!(applied, 0);
+        assert_eq!(client.position_x(&entity), 0);
+    }
+
+    #[test]
+    fn unignored_entity_is_resynced() {
+        let mut client = TestClient::new();
+        let entity = client.spawn_position(0);
+
+        client.remote_world_manager.ignore_entity(&entity);
+        let update = client.position_update(7);
+        client.receive_updates(vec![(1, entity, update)]);
+        assert_eq!(client.position_x(&entity), 0);
+
+        assert!(client.remote_world_manager.unignore_entity(&entity));
+        assert!(!client.remote_world_manager.unignore_entity(&entity));
+
+        let resync = client.position_update(9);
+        let applied = client.receive_updates(vec![(2, entity, resync)]);
+
+        assert_eq!(applied, 1);
+        assert_eq!(client.position_x(&entity), 9);
+    }
+}
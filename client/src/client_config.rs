@@ -17,6 +17,24 @@ pub struct ClientConfig {
     /// taking longer. Keep in mind that the network measurements affect how likely commands
     /// are able to arrive at the server before processing.
     pub handshake_pings: u8,
+    /// Extra ticks of buffer to hold on both sides of the Server's tick: the
+    /// Client reads Server updates this many ticks later, and sends commands
+    /// this many ticks earlier. Useful for Clients on very poor connections,
+    /// at the cost of added latency.
+    pub tick_offset_bias: Option<i8>,
+    /// How many ticks the Client's clock may drift from its target before it
+    /// is snapped straight to the target, instead of being gradually sped up
+    /// or slowed down. If None, the clock is always smoothed.
+    pub tick_resync_threshold: Option<u16>,
+    /// Whether to fire a SpawnEntityWithComponentsEvent for each spawned
+    /// Entity, carrying every Component inserted alongside the spawn
+    pub spawn_with_components_events: bool,
+    /// How many Ticks of snapshots taken with
+    /// `Client::store_component_snapshot()` are kept, older ones are discarded
+    pub component_snapshot_capacity: usize,
+    /// How long writes queued with `Client::request_authority_then()` wait
+    /// for Authority to be granted, before they are dropped
+    pub authority_write_timeout: Duration,
 }
 
 impl Default for ClientConfig {
@@ -26,6 +44,11 @@ impl Default for ClientConfig {
             send_handshake_interval: Duration::from_millis(250),
             ping_interval: Duration::from_secs(1),
             handshake_pings: 10,
+            tick_offset_bias: None,
+            tick_resync_threshold: None,
+            spawn_with_components_events: false,
+            component_snapshot_capacity: 64,
+            authority_write_timeout: Duration::from_secs(5),
         }
     }
 }
@@ -49,10 +49,7 @@ impl BaseTimeManager {
         let writer = self.write_ping();
 
         // send packet
-        if io.send_packet(writer.to_packet()).is_err() {
-            // TODO: pass this on and handle above
-            warn!("Client Error: Cannot send ping packet to Server");
-        }
+        io.queue_packet(writer.to_packet());
     }
 
     pub(crate) fn read_ping(reader: &mut BitReader) -> Result<PingIndex, SerdeErr> {
@@ -76,10 +73,7 @@ impl BaseTimeManager {
         ping_index.ser(&mut writer);
 
         // send packet
-        if io.send_packet(writer.to_packet()).is_err() {
-            // TODO: pass this on and handle above
-            warn!("Client Error: Cannot send pong packet to Server");
-        }
+        io.queue_packet(writer.to_packet());
         connection.base.mark_sent();
     }
 
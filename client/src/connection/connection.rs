@@ -4,9 +4,10 @@ use log::warn;
 
 use naia_shared::{
     BaseConnection, BitReader, BitWriter, ChannelKind, ChannelKinds, ConnectionConfig,
-    EntityEventMessage, EntityEventMessageAction, EntityResponseEvent, HostType, HostWorldEvents,
-    Instant, OwnedBitReader, PacketType, Protocol, Serde, SerdeErr, StandardHeader, SystemChannel,
-    Tick, WorldMutType, WorldRefType,
+    ConnectionQuality, EntityEventMessage, EntityEventMessageAction, EntityResponseEvent, HostType,
+    HostWorldEvents, Instant, MessageContainer, OwnedBitReader, PacketType, Protocol, Serde,
+    SerdeErr, StandardHeader, StreamChannel, StreamMessage, SystemChannel, Tick, WorldMutType,
+    WorldRefType,
 };
 
 use crate::request::GlobalRequestManager;
@@ -18,6 +19,7 @@ use crate::{
     events::Events,
     request::GlobalResponseManager,
     world::global_world_manager::GlobalWorldManager,
+    NaiaClientError,
 };
 
 pub struct Connection<E: Copy + Eq + Hash + Send + Sync> {
@@ -30,6 +32,8 @@ pub struct Connection<E: Copy + Eq + Hash + Send + Sync> {
     // Request/Response
     pub global_request_manager: GlobalRequestManager,
     pub global_response_manager: GlobalResponseManager,
+    /// Stream payloads received from the Server, until they are taken
+    stream_messages: Vec<Vec<u8>>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
@@ -55,21 +59,41 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             jitter_buffer: TickQueue::new(),
             global_request_manager: GlobalRequestManager::new(),
             global_response_manager: GlobalResponseManager::new(),
+            stream_messages: Vec::new(),
         };
 
-        let existing_entities = global_world_manager.entities();
-        for entity in existing_entities {
-            let component_kinds = global_world_manager.component_kinds(&entity).unwrap();
-            connection.base.host_world_manager.init_entity(
-                &mut connection.base.local_world_manager,
-                &entity,
-                component_kinds,
-            );
-        }
+        let existing_entities = global_world_manager
+            .entities()
+            .into_iter()
+            .map(|entity| {
+                let component_kinds = global_world_manager.component_kinds(&entity).unwrap();
+                (entity, component_kinds)
+            })
+            .collect();
+        connection
+            .base
+            .host_world_manager
+            .init_entities_batch(&mut connection.base.local_world_manager, existing_entities);
 
         connection
     }
 
+    /// Measures this connection's quality, from packet loss, the pings and the
+    /// bytes the Io has carried
+    pub fn connection_quality(&mut self, io: &mut Io) -> ConnectionQuality {
+        self.base.connection_quality(
+            self.time_manager.rtt(),
+            self.time_manager.jitter(),
+            io.incoming_bytes_per_second(),
+            io.outgoing_bytes_per_second(),
+        )
+    }
+
+    /// Takes the stream payloads received from the Server so far
+    pub fn take_stream_messages(&mut self) -> Vec<Vec<u8>> {
+        std::mem::take(&mut self.stream_messages)
+    }
+
     // Incoming data
 
     pub fn process_incoming_header(&mut self, header: &StandardHeader) {
@@ -158,8 +182,20 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                         }
                     };
                 }
+            } else if channel_kind == ChannelKind::of::<StreamChannel>() {
+                for message in messages {
+                    let Some(stream_message) =
+                        Box::<dyn Any + 'static>::downcast::<StreamMessage>(message.to_boxed_any())
+                            .ok()
+                            .map(|boxed_m| *boxed_m)
+                    else {
+                        panic!("Received unknown message over StreamChannel!");
+                    };
+                    self.stream_messages.push(stream_message.into_bytes());
+                }
             } else {
                 for message in messages {
+                    let message = self.receive_send_instant(&channel_kind, message);
                     incoming_events.push_message(&channel_kind, message);
                 }
             }
@@ -170,6 +206,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         // Requests
         for (channel_kind, requests) in requests {
             for (local_response_id, request) in requests {
+                let request = self.receive_send_instant(&channel_kind, request);
                 let global_response_id = self
                     .global_response_manager
                     .create_response_id(&channel_kind, &local_response_id);
@@ -193,9 +230,38 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             remote_events,
         );
         response_events.extend(incoming_events.receive_world_events(world_events));
+
+        for error in self
+            .base
+            .remote_world_reader
+            .take_stalled_channel_errors(now)
+        {
+            incoming_events.push_error(NaiaClientError::Wrapped(Box::new(error)));
+        }
+
         response_events
     }
 
+    // Records how long a received Message took to arrive, and moves the
+    // instant it was sent at from the Server's clock onto this Client's
+    fn receive_send_instant(
+        &mut self,
+        channel_kind: &ChannelKind,
+        mut message: MessageContainer,
+    ) -> MessageContainer {
+        let Some(send_instant) = message.send_instant() else {
+            return message;
+        };
+        let now = self.time_manager.game_time_now();
+        self.base.message_manager.record_latency(
+            channel_kind,
+            &message,
+            &self.time_manager.instant_to_server(&now),
+        );
+        message.set_send_instant(self.time_manager.instant_from_server(&send_instant));
+        message
+    }
+
     // Outgoing data
 
     /// Collect and send any outgoing packets from client to server
@@ -220,8 +286,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             &rtt_millis,
         );
 
-        let mut any_sent = false;
-        loop {
+        let mut packets_sent = 0;
+        while self.base.may_send_packet(packets_sent) {
             if self.send_packet(
                 protocol,
                 now,
@@ -230,12 +296,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                 global_world_manager,
                 &mut host_world_events,
             ) {
-                any_sent = true;
+                packets_sent += 1;
             } else {
                 break;
             }
         }
-        if any_sent {
+        if packets_sent > 0 {
             self.base.mark_sent();
         }
     }
@@ -263,10 +329,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             );
 
             // send packet
-            if io.send_packet(writer.to_packet()).is_err() {
-                // TODO: pass this on and handle above
-                warn!("Client Error: Cannot send data packet to Server");
-            }
+            io.queue_packet(writer.to_packet());
 
             return true;
         }
@@ -1,7 +1,11 @@
-use std::{net::SocketAddr, time::Duration};
+use std::{collections::VecDeque, net::SocketAddr, time::Duration};
+
+use log::warn;
 
 use naia_shared::{
-    BandwidthMonitor, BitReader, CompressionConfig, Decoder, Encoder, OutgoingPacket,
+    append_checksum, is_handshake_packet, open_packet, seal_packet, verify_checksum,
+    BandwidthMonitor, BitReader, ByteRateMonitor, CompressionConfig, CompressionStats,
+    ConnectionQuality, Decoder, DecoderError, Encoder, OutgoingPacket, PacketCipher,
 };
 
 use crate::{
@@ -18,14 +22,29 @@ pub struct Io {
     packet_receiver: Option<Box<dyn PacketReceiver>>,
     outgoing_bandwidth_monitor: Option<BandwidthMonitor>,
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
+    outgoing_byte_rate: ByteRateMonitor,
+    incoming_byte_rate: ByteRateMonitor,
     outgoing_encoder: Option<Encoder>,
+    // the last graded quality of the connection, which Adaptive compression
+    // picks its level from
+    connection_quality: ConnectionQuality,
     incoming_decoder: Option<Decoder>,
+    encryption_enabled: bool,
+    packet_cipher: Option<PacketCipher>,
+    packet_checksums: bool,
+    corrupt_packets: u64,
+    // holds the most recently decrypted packet, which the returned BitReader borrows
+    incoming_plaintext: Vec<u8>,
+    // encoded packets waiting for the next flush
+    outgoing_packets: VecDeque<Box<[u8]>>,
 }
 
 impl Io {
     pub fn new(
         bandwidth_measure_duration: &Option<Duration>,
         compression_config: &Option<CompressionConfig>,
+        encryption_enabled: bool,
+        packet_checksums: bool,
     ) -> Self {
         let outgoing_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
         let incoming_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
@@ -50,8 +69,17 @@ impl Io {
             packet_receiver: None,
             outgoing_bandwidth_monitor,
             incoming_bandwidth_monitor,
+            outgoing_byte_rate: ByteRateMonitor::new(),
+            incoming_byte_rate: ByteRateMonitor::new(),
             outgoing_encoder,
+            connection_quality: ConnectionQuality::default(),
             incoming_decoder,
+            encryption_enabled,
+            packet_cipher: None,
+            packet_checksums,
+            corrupt_packets: 0,
+            incoming_plaintext: Vec::new(),
+            outgoing_packets: VecDeque::new(),
         }
     }
 
@@ -74,6 +102,10 @@ impl Io {
         self.packet_sender.is_some()
     }
 
+    pub fn set_packet_cipher(&mut self, cipher: PacketCipher) {
+        self.packet_cipher = Some(cipher);
+    }
+
     pub fn is_authenticated(&self) -> bool {
         self.authenticated
     }
@@ -93,44 +125,88 @@ impl Io {
         id_result
     }
 
-    pub fn send_packet(&mut self, packet: OutgoingPacket) -> Result<(), NaiaClientError> {
+    /// Encodes a packet and queues it, to be sent by the next `flush_packets()`
+    pub fn queue_packet(&mut self, packet: OutgoingPacket) {
+        if self.packet_sender.is_none() {
+            panic!("Cannot call Client.queue_packet() until you call Client.connect()!");
+        }
+
         // get payload
         let mut payload = packet.slice();
 
+        // handshake packets carry the key exchange, so are never encrypted
+        let is_handshake = self.encryption_enabled && is_handshake_packet(payload);
+
         // Compression
         if let Some(encoder) = &mut self.outgoing_encoder {
-            payload = encoder.encode(payload);
+            payload = encoder.encode_for(payload, &self.connection_quality);
+        }
+
+        // Encryption
+        let sealed;
+        if self.encryption_enabled {
+            let cipher_opt = if is_handshake {
+                None
+            } else {
+                self.packet_cipher.as_mut()
+            };
+            sealed = seal_packet(cipher_opt, payload);
+            payload = &sealed;
+        }
+
+        // Checksum
+        let checksummed;
+        if self.packet_checksums {
+            checksummed = append_checksum(payload);
+            payload = &checksummed;
         }
 
         // Bandwidth monitoring
         if let Some(monitor) = &mut self.outgoing_bandwidth_monitor {
             monitor.record_packet(payload.len());
         }
+        self.outgoing_byte_rate.record_packet(payload.len());
+
+        self.outgoing_packets.push_back(payload.into());
+    }
 
-        self.packet_sender
+    /// Hands every queued packet to the PacketSender, in the order they were
+    /// queued. Returns an error if any of them could not be sent
+    pub fn flush_packets(&mut self) -> Result<(), NaiaClientError> {
+        if self.outgoing_packets.is_empty() {
+            return Ok(());
+        }
+        let packet_sender = self
+            .packet_sender
             .as_mut()
-            .expect("Cannot call Client.send_packet() until you call Client.connect()!")
-            .send(payload)
-            .map_err(|_| NaiaClientError::SendError)
+            .expect("Cannot call Client.flush_packets() until you call Client.connect()!");
+
+        let mut result = Ok(());
+        for payload in self.outgoing_packets.drain(..) {
+            if packet_sender.send(&payload).is_err() {
+                result = Err(NaiaClientError::SendError);
+            }
+        }
+        result
     }
 
     pub fn recv_reader(&mut self) -> Result<Option<BitReader<'_>>, NaiaClientError> {
+        if self.encryption_enabled || self.packet_checksums || self.incoming_decoder.is_some() {
+            return self.recv_verified_reader();
+        }
+
         let receive_result = self
             .packet_receiver
             .as_mut()
             .expect("Cannot call Client.receive_packet() until you call Client.connect()!")
             .receive();
 
-        if let Ok(Some(mut payload)) = receive_result {
+        if let Ok(Some(payload)) = receive_result {
             // Bandwidth monitoring
             if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
                 monitor.record_packet(payload.len());
             }
-
-            // Decompression
-            if let Some(decoder) = &mut self.incoming_decoder {
-                payload = decoder.decode(payload);
-            }
+            self.incoming_byte_rate.record_packet(payload.len());
 
             Ok(Some(BitReader::new(payload)))
         } else {
@@ -140,6 +216,83 @@ impl Io {
         }
     }
 
+    // packets which fail their checksum, cannot be decrypted or cannot be
+    // decompressed are dropped, and the next packet is read instead
+    fn recv_verified_reader(&mut self) -> Result<Option<BitReader<'_>>, NaiaClientError> {
+        loop {
+            let receive_result = self
+                .packet_receiver
+                .as_mut()
+                .expect("Cannot call Client.receive_packet() until you call Client.connect()!")
+                .receive();
+
+            let mut payload = match receive_result {
+                Ok(Some(payload)) => payload,
+                Ok(None) => return Ok(None),
+                Err(_) => return Err(NaiaClientError::RecvError),
+            };
+
+            // Bandwidth monitoring
+            if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
+                monitor.record_packet(payload.len());
+            }
+            self.incoming_byte_rate.record_packet(payload.len());
+
+            // Checksum, verified before anything else reads the packet
+            if self.packet_checksums {
+                match verify_checksum(payload) {
+                    Ok(body) => payload = body,
+                    Err(error) => {
+                        self.corrupt_packets += 1;
+                        warn!("Dropping packet from Server: {}", error);
+                        continue;
+                    }
+                }
+            }
+
+            // Decryption
+            let (plaintext, was_encrypted) = if self.encryption_enabled {
+                match open_packet(self.packet_cipher.as_mut(), payload) {
+                    Ok(opened) => opened,
+                    Err(error) => {
+                        warn!("Dropping packet from Server: {}", error);
+                        continue;
+                    }
+                }
+            } else {
+                (payload.to_vec(), false)
+            };
+
+            // Decompression
+            self.incoming_plaintext = match &mut self.incoming_decoder {
+                Some(decoder) => match decoder.decode(&plaintext) {
+                    Ok(decoded) => decoded.to_vec(),
+                    Err(error) => {
+                        warn!("Dropping packet from Server: {}", error);
+                        continue;
+                    }
+                },
+                None => plaintext,
+            };
+
+            // once keys are established, only handshake packets may arrive in plaintext
+            if !was_encrypted
+                && self.packet_cipher.is_some()
+                && !is_handshake_packet(&self.incoming_plaintext)
+            {
+                warn!("Dropping packet from Server: {}", DecoderError::Unencrypted);
+                continue;
+            }
+
+            return Ok(Some(BitReader::new(&self.incoming_plaintext)));
+        }
+    }
+
+    /// The number of received packets dropped for failing their checksum
+    pub fn corrupt_packets(&self) -> u64 {
+        self.corrupt_packets
+    }
+
     pub fn server_addr(&self) -> Result<SocketAddr, NaiaClientError> {
         if let Some(packet_sender) = self.packet_sender.as_ref() {
             if let ServerAddr::Found(server_addr) = packet_sender.server_addr() {
@@ -167,4 +320,25 @@ impl Io {
             .expect("Need to call `enable_bandwidth_monitor()` on Io before calling this")
             .bandwidth();
     }
+
+    /// Records the latest quality of the connection, to pick the level
+    /// outgoing packets are compressed at
+    pub fn set_connection_quality(&mut self, quality: ConnectionQuality) {
+        self.connection_quality = quality;
+    }
+
+    /// Totals of the bytes compressed so far, if compression is enabled
+    pub fn compression_stats(&self) -> Option<CompressionStats> {
+        self.outgoing_encoder
+            .as_ref()
+            .map(|encoder| encoder.stats())
+    }
+
+    pub fn outgoing_bytes_per_second(&mut self) -> f32 {
+        self.outgoing_byte_rate.bytes_per_second()
+    }
+
+    pub fn incoming_bytes_per_second(&mut self) -> f32 {
+        self.incoming_byte_rate.bytes_per_second()
+    }
 }
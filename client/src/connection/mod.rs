@@ -5,4 +5,5 @@ pub mod connection;
 pub mod io;
 pub mod tick_buffer_sender;
 pub mod tick_queue;
+pub mod tick_sync;
 pub mod time_manager;
@@ -0,0 +1,34 @@
+use naia_shared::Tick;
+
+/// Describes how the Client's tick clock arrived at a given tick
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TickSyncKind {
+    /// The tick advanced on its own, with the clock running at (or being
+    /// smoothly adjusted towards) the Server's pace
+    Smoothed,
+    /// The clock advanced several ticks within a single update, so this tick
+    /// was emitted back-to-back with its neighbours
+    Skipped,
+    /// The clock drifted further than `ClientConfig::tick_resync_threshold`
+    /// from its target and was snapped straight to it. Interpolation buffers
+    /// and other tick-based history may be discontinuous across this tick
+    Snapped,
+}
+
+/// A read-only snapshot of the Client's tick synchronization state, useful
+/// for debugging overlays & connection quality reporting
+#[derive(Clone, Debug)]
+pub struct TickSyncDiagnostics {
+    /// The tick at which the Client is currently reading Server updates
+    pub client_receiving_tick: Tick,
+    /// The tick at which the Client is currently sending commands
+    pub client_sending_tick: Tick,
+    /// The estimate of the Server's current tick
+    pub server_tick_estimate: Tick,
+    /// The speed multiplier applied to the Client's sending clock on the last
+    /// update, where 1.0 is real time, above 1.0 is catching up, and below 1.0
+    /// is slowing down
+    pub offset_smoothing: f32,
+    /// The most recent round trip time samples, in milliseconds, oldest first
+    pub rtt_samples: Vec<u32>,
+}
@@ -1,11 +1,15 @@
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
 
 use naia_shared::{
     sequence_greater_than, sequence_less_than, wrapping_diff, BitReader, GameInstant, Instant,
     SerdeErr, Tick, Timer,
 };
 
-use crate::connection::{base_time_manager::BaseTimeManager, io::Io};
+use crate::connection::{
+    base_time_manager::BaseTimeManager,
+    io::Io,
+    tick_sync::{TickSyncDiagnostics, TickSyncKind},
+};
 
 pub struct TimeManager {
     base: BaseTimeManager,
@@ -18,6 +22,12 @@ pub struct TimeManager {
     pruned_rtt_avg: f32,
     raw_rtt_avg: f32,
     rtt_stdv: f32,
+    rtt_samples: VecDeque<u32>,
+
+    // Sync
+    tick_offset_bias: i8,
+    tick_resync_threshold: Option<u16>,
+    offset_smoothing: f32,
 
     // Ticks
     accumulator: f32,
@@ -90,6 +100,11 @@ impl TimeManager {
             pruned_rtt_avg,
             raw_rtt_avg: pruned_rtt_avg,
             rtt_stdv,
+            rtt_samples: VecDeque::new(),
+
+            tick_offset_bias: 0,
+            tick_resync_threshold: None,
+            offset_smoothing: SAFE_SPEED,
 
             accumulator: 0.0,
 
@@ -110,6 +125,32 @@ impl TimeManager {
         }
     }
 
+    pub(crate) fn set_tick_sync(
+        &mut self,
+        tick_offset_bias: Option<i8>,
+        tick_resync_threshold: Option<u16>,
+    ) {
+        self.tick_offset_bias = tick_offset_bias.unwrap_or(0);
+        self.tick_resync_threshold = tick_resync_threshold;
+
+        // Nothing has ticked yet, so the bias can be applied right away
+        let bias_ms = self.tick_offset_bias_millis();
+        self.client_receiving_instant = self.client_receiving_instant.add_signed_millis(-bias_ms);
+        self.client_sending_instant = self.client_sending_instant.add_signed_millis(bias_ms);
+        self.client_receiving_tick = instant_to_tick(
+            &self.server_tick,
+            &self.server_tick_instant,
+            self.server_tick_duration_avg,
+            &self.client_receiving_instant,
+        );
+        self.client_sending_tick = instant_to_tick(
+            &self.server_tick,
+            &self.server_tick_instant,
+            self.server_tick_duration_avg,
+            &self.client_sending_instant,
+        );
+    }
+
     // Base
 
     pub fn send_ping(&mut self, io: &mut Io) -> bool {
@@ -138,6 +179,11 @@ impl TimeManager {
         let offset_sample = offset_millis as f32;
         let rtt_sample = rtt_millis as f32;
 
+        self.rtt_samples.push_back(rtt_millis);
+        if self.rtt_samples.len() > RTT_SAMPLE_COUNT {
+            self.rtt_samples.pop_front();
+        }
+
         self.raw_offset_avg = (0.9 * self.raw_offset_avg) + (0.1 * offset_sample);
         self.raw_rtt_avg = (0.9 * self.raw_rtt_avg) + (0.1 * rtt_sample);
 
@@ -165,6 +211,23 @@ impl TimeManager {
     //     self.base.game_time_since(previous_instant)
     // }
 
+    /// Converts a GameInstant on this Client's clock to the Server's clock,
+    /// using the offset between the two measured by pings
+    pub(crate) fn instant_to_server(&self, instant: &GameInstant) -> GameInstant {
+        instant.add_signed_millis(-self.server_offset_millis())
+    }
+
+    /// Converts a GameInstant on the Server's clock to this Client's clock
+    pub(crate) fn instant_from_server(&self, instant: &GameInstant) -> GameInstant {
+        instant.add_signed_millis(self.server_offset_millis())
+    }
+
+    // how far this Client's clock is ahead of the Server's, which the
+    // handshake will have mostly corrected already
+    fn server_offset_millis(&self) -> i32 {
+        self.pruned_offset_avg.round() as i32
+    }
+
     // Tick
 
     pub(crate) fn recv_tick_instant(
@@ -217,10 +280,10 @@ impl TimeManager {
     pub(crate) fn collect_ticks(
         &mut self,
         now: &Instant,
-    ) -> (Option<(Tick, Tick)>, Option<(Tick, Tick)>) {
+    ) -> (Option<TickRange>, Option<TickRange>) {
         // updates client_receiving_tick
-        // returns (Some(start_tick, end_tick), None) if a client_receiving_tick has incremented
-        // returns (None, Some(start_tick, end_tick)) if a client_sending_tick or server_receivable_tick has incremented
+        // returns (Some(start_tick, end_tick, kind), None) if a client_receiving_tick has incremented
+        // returns (None, Some(start_tick, end_tick, kind)) if a client_sending_tick or server_receivable_tick has incremented
         let prev_client_receiving_tick = self.client_receiving_tick;
         let prev_client_sending_tick = self.client_sending_tick;
 
@@ -240,11 +303,13 @@ impl TimeManager {
         let latency_ms: u32 = self.latency().round() as u32;
         let major_jitter_ms: u32 = (self.jitter() * 3.0).round() as u32;
         let tick_duration_ms: u32 = self.server_tick_duration_avg.round() as u32;
+        let bias_ms: i32 = self.tick_offset_bias_millis();
 
         // Client Receiving
-        {
+        let client_receiving_speed = {
             let client_receiving_target =
-                get_client_receiving_target(&now, latency_ms, major_jitter_ms, tick_duration_ms);
+                get_client_receiving_target(&now, latency_ms, major_jitter_ms, tick_duration_ms)
+                    .add_signed_millis(-bias_ms);
             adjust_time(
                 &self.server_tick,
                 &self.server_tick_instant,
@@ -252,19 +317,21 @@ impl TimeManager {
                 &mut self.client_receiving_tick,
                 &mut self.client_receiving_instant,
                 &client_receiving_target,
+                self.tick_resync_threshold,
                 millis_elapsed,
-            );
-        }
+            )
+        };
 
         // Client Sending
-        {
+        let client_sending_speed = {
             let client_sending_target = get_client_sending_target(
                 &now,
                 latency_ms,
                 major_jitter_ms,
                 tick_duration_ms,
                 self.server_speedup_potential,
-            );
+            )
+            .add_signed_millis(bias_ms);
             adjust_time(
                 &self.server_tick,
                 &self.server_tick_instant,
@@ -272,9 +339,11 @@ impl TimeManager {
                 &mut self.client_sending_tick,
                 &mut self.client_sending_instant,
                 &client_sending_target,
+                self.tick_resync_threshold,
                 millis_elapsed,
-            );
-        }
+            )
+        };
+        self.offset_smoothing = client_sending_speed.unwrap_or(SAFE_SPEED);
 
         // Server Receivable
         {
@@ -287,6 +356,7 @@ impl TimeManager {
                 &mut self.server_receivable_tick,
                 &mut self.server_receivable_instant,
                 &server_receivable_target,
+                self.tick_resync_threshold,
                 millis_elapsed,
             );
         }
@@ -296,19 +366,55 @@ impl TimeManager {
         let sending_incremented = self.client_sending_tick != prev_client_sending_tick;
 
         let output_receiving = match receiving_incremented {
-            true => Some((prev_client_receiving_tick, self.client_receiving_tick)),
+            true => Some((
+                prev_client_receiving_tick,
+                self.client_receiving_tick,
+                tick_sync_kind(
+                    prev_client_receiving_tick,
+                    self.client_receiving_tick,
+                    client_receiving_speed,
+                ),
+            )),
             false => None,
         };
         let output_sending = match sending_incremented {
-            true => Some((prev_client_sending_tick, self.client_sending_tick)),
+            true => Some((
+                prev_client_sending_tick,
+                self.client_sending_tick,
+                tick_sync_kind(
+                    prev_client_sending_tick,
+                    self.client_sending_tick,
+                    client_sending_speed,
+                ),
+            )),
             false => None,
         };
 
         return (output_receiving, output_sending);
     }
 
+    fn tick_offset_bias_millis(&self) -> i32 {
+        ((self.tick_offset_bias as f32) * self.server_tick_duration_avg).round() as i32
+    }
+
     // Stats
 
+    pub(crate) fn tick_sync_diagnostics(&self) -> TickSyncDiagnostics {
+        let server_tick_estimate = instant_to_tick(
+            &self.server_tick,
+            &self.server_tick_instant,
+            self.server_tick_duration_avg,
+            &self.game_time_now(),
+        );
+        TickSyncDiagnostics {
+            client_receiving_tick: self.client_receiving_tick,
+            client_sending_tick: self.client_sending_tick,
+            server_tick_estimate,
+            offset_smoothing: self.offset_smoothing,
+            rtt_samples: self.rtt_samples.iter().copied().collect(),
+        }
+    }
+
     pub(crate) fn client_interpolation(&self) -> f32 {
         let mut output = self.get_interp(self.client_sending_tick, &self.client_sending_instant);
         output = {
@@ -373,6 +479,11 @@ impl TimeManager {
     }
 }
 
+/// (start_tick, end_tick, kind) of ticks which have elapsed since the last check
+pub(crate) type TickRange = (Tick, Tick, TickSyncKind);
+
+// returns the speed the clock was advanced at, or None if it was snapped to the target
+#[allow(clippy::too_many_arguments)]
 fn adjust_time(
     server_tick: &Tick,
     server_tick_instant: &GameInstant,
@@ -380,14 +491,22 @@ fn adjust_time(
     tick: &mut Tick,
     tick_instant: &mut GameInstant,
     target_instant: &GameInstant,
+    resync_threshold: Option<u16>,
     millis_elapsed: u32,
-) {
+) -> Option<f32> {
     let default_next_instant = tick_instant.add_millis(millis_elapsed);
-    let speed = offset_to_speed(default_next_instant.offset_from(target_instant));
-    *tick_instant = tick_instant.add_millis(((millis_elapsed as f32) * speed).round() as u32);
-    if tick_instant.is_more_than(target_instant) {
-        *tick_instant = target_instant.clone();
-    }
+    let offset = default_next_instant.offset_from(target_instant);
+    let speed = if exceeds_resync_threshold(offset, resync_threshold, server_tick_duration_avg) {
+        *tick_instant = *target_instant;
+        None
+    } else {
+        let speed = offset_to_speed(offset);
+        *tick_instant = tick_instant.add_millis(((millis_elapsed as f32) * speed).round() as u32);
+        if tick_instant.is_more_than(target_instant) {
+            *tick_instant = target_instant.clone();
+        }
+        Some(speed)
+    };
     let new_tick = instant_to_tick(
         server_tick,
         server_tick_instant,
@@ -399,6 +518,28 @@ fn adjust_time(
     } else {
         *tick = new_tick;
     }
+    return speed;
+}
+
+fn exceeds_resync_threshold(
+    offset_ms: i32,
+    resync_threshold: Option<u16>,
+    tick_duration_avg: f32,
+) -> bool {
+    let Some(threshold) = resync_threshold else {
+        return false;
+    };
+    return (offset_ms.unsigned_abs() as f32) > (threshold as f32) * tick_duration_avg;
+}
+
+fn tick_sync_kind(prev_tick: Tick, tick: Tick, speed: Option<f32>) -> TickSyncKind {
+    if speed.is_none() {
+        return TickSyncKind::Snapped;
+    }
+    if wrapping_diff(prev_tick, tick) > 1 {
+        return TickSyncKind::Skipped;
+    }
+    return TickSyncKind::Smoothed;
 }
 
 fn instant_to_tick(
@@ -466,6 +607,7 @@ const RANGE_MAX: f32 = 20.0;
 const RANGE_MIN: f32 = 20.0;
 const SPEED_MAX: f32 = 10.0;
 const SPEED_MIN: f32 = 1.0 / SPEED_MAX;
+const RTT_SAMPLE_COUNT: usize = 32;
 
 // Tests
 #[cfg(test)]
@@ -504,3 +646,68 @@ mod offset_to_speed_tests {
         assert_eq!(offset_to_speed(offset), 0.8);
     }
 }
+
+#[cfg(test)]
+mod adjust_time_tests {
+    use naia_shared::{GameInstant, Instant, Tick};
+
+    use crate::connection::{
+        tick_sync::TickSyncKind,
+        time_manager::{adjust_time, tick_sync_kind},
+    };
+
+    const TICK_DURATION: f32 = 10.0;
+
+    // Advances a clock sitting at tick 0 by 10ms, towards a target `target_ms` ahead of it
+    fn adjust(target_ms: u32, resync_threshold: Option<u16>) -> (Tick, Option<f32>) {
+        let server_tick_instant = GameInstant::new(&Instant::now());
+        let mut tick: Tick = 0;
+        let mut tick_instant = server_tick_instant.clone();
+        let target_instant = server_tick_instant.add_millis(target_ms);
+        let speed = adjust_time(
+            &0,
+            &server_tick_instant,
+            TICK_DURATION,
+            &mut tick,
+            &mut tick_instant,
+            &target_instant,
+            resync_threshold,
+            10,
+        );
+        (tick, speed)
+    }
+
+    #[test]
+    fn smooths_without_threshold() {
+        let (tick, speed) = adjust(1000, None);
+
+        assert!(speed.unwrap() > 1.0);
+        assert!(tick < 100);
+    }
+
+    #[test]
+    fn smooths_within_threshold() {
+        let (_, speed) = adjust(1000, Some(200));
+
+        assert!(speed.unwrap() > 1.0);
+    }
+
+    #[test]
+    fn snaps_past_threshold() {
+        let (tick, speed) = adjust(1000, Some(20));
+
+        assert_eq!(speed, None);
+        assert_eq!(tick, 100);
+    }
+
+    #[test]
+    fn kinds() {
+        assert_eq!(tick_sync_kind(4, 5, Some(1.0)), TickSyncKind::Smoothed);
+        assert_eq!(tick_sync_kind(4, 7, Some(1.0)), TickSyncKind::Skipped);
+        assert_eq!(
+            tick_sync_kind(u16::MAX, 0, Some(1.0)),
+            TickSyncKind::Smoothed
+        );
+        assert_eq!(tick_sync_kind(4, 5, None), TickSyncKind::Snapped);
+    }
+}
@@ -1,21 +1,44 @@
-use std::{collections::HashMap, marker::PhantomData, mem, net::SocketAddr, vec::IntoIter};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+    mem,
+    net::SocketAddr,
+    vec::IntoIter,
+};
 
 use naia_shared::{
-    Channel, ChannelKind, ComponentKind, EntityEvent, EntityResponseEvent, GlobalResponseId,
-    Message, MessageContainer, MessageKind, Replicate, Request, ResponseSendKey, Tick,
+    Channel, ChannelKind, ComponentKind, ConnectionParams, ConnectionQualityLevel, EntityEvent,
+    EntityResponseEvent, GameInstant, GlobalRequestId, GlobalResponseId, Message, MessageContainer,
+    MessageKind, MessageReceiptKey, Replicate, Request, ResponseReceiveKey, ResponseSendKey, Tick,
 };
 
-use crate::NaiaClientError;
+use crate::{AuthDeniedReason, NaiaClientError, TickSyncKind};
 
+/// Events received by the Client since the last call to `Client::receive()`.
+///
+/// Within one batch of Events, the world events of any given Entity always
+/// happen in the order: Spawn → Inserts → Updates → Removes → Despawn, so
+/// they can be read in that order without ever seeing an Entity's events out
+/// of sequence. Should a Component be removed and then re-inserted within the
+/// same batch, the re-insert, and anything after it for that Entity, is held
+/// back until the next batch.
 pub struct Events<E: Copy> {
     connections: Vec<SocketAddr>,
+    connection_params: Vec<ConnectionParams>,
     rejections: Vec<SocketAddr>,
+    rejection_details: Vec<(u16, Option<MessageContainer>)>,
+    welcomes: Vec<MessageContainer>,
+    heartbeat_payloads: Vec<MessageContainer>,
     disconnections: Vec<SocketAddr>,
-    client_ticks: Vec<Tick>,
-    server_ticks: Vec<Tick>,
+    client_ticks: Vec<(Tick, TickSyncKind)>,
+    server_ticks: Vec<(Tick, TickSyncKind)>,
     errors: Vec<NaiaClientError>,
+    connection_quality_changes: Vec<ConnectionQualityLevel>,
+    delivered_messages: Vec<MessageReceiptKey>,
     messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<MessageContainer>>>,
     requests: HashMap<ChannelKind, HashMap<MessageKind, Vec<(GlobalResponseId, MessageContainer)>>>,
+    request_timeouts: HashMap<MessageKind, Vec<GlobalRequestId>>,
     spawns: Vec<E>,
     despawns: Vec<E>,
     publishes: Vec<E>,
@@ -23,29 +46,41 @@ pub struct Events<E: Copy> {
     auth_grants: Vec<E>,
     auth_denies: Vec<E>,
     auth_resets: Vec<E>,
+    authority_writes_dropped: Vec<(E, AuthDeniedReason)>,
     inserts: HashMap<ComponentKind, Vec<E>>,
     removes: HashMap<ComponentKind, Vec<(E, Box<dyn Replicate>)>>,
     updates: HashMap<ComponentKind, Vec<(Tick, E)>>,
+    spawns_with_components: Option<Vec<(E, Vec<ComponentKind>)>>,
+    removed_components: HashSet<(E, ComponentKind)>,
+    deferred_entities: HashSet<E>,
+    deferred_world_events: Vec<EntityEvent<E>>,
     empty: bool,
 }
 
 impl<E: Copy> Default for Events<E> {
     fn default() -> Self {
-        Events::new()
+        Events::new(false)
     }
 }
 
 impl<E: Copy> Events<E> {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(spawn_with_components_events: bool) -> Self {
         Self {
             connections: Vec::new(),
+            connection_params: Vec::new(),
             rejections: Vec::new(),
+            rejection_details: Vec::new(),
+            welcomes: Vec::new(),
+            heartbeat_payloads: Vec::new(),
             disconnections: Vec::new(),
             client_ticks: Vec::new(),
             server_ticks: Vec::new(),
             errors: Vec::new(),
+            connection_quality_changes: Vec::new(),
+            delivered_messages: Vec::new(),
             messages: HashMap::new(),
             requests: HashMap::new(),
+            request_timeouts: HashMap::new(),
             spawns: Vec::new(),
             despawns: Vec::new(),
             publishes: Vec::new(),
@@ -53,9 +88,14 @@ impl<E: Copy> Events<E> {
             auth_grants: Vec::new(),
             auth_denies: Vec::new(),
             auth_resets: Vec::new(),
+            authority_writes_dropped: Vec::new(),
             inserts: HashMap::new(),
             removes: HashMap::new(),
             updates: HashMap::new(),
+            spawns_with_components: spawn_with_components_events.then(Vec::new),
+            removed_components: HashSet::new(),
+            deferred_entities: HashSet::new(),
+            deferred_world_events: Vec::new(),
             empty: true,
         }
     }
@@ -92,6 +132,14 @@ impl<E: Copy> Events<E> {
         mem::take(&mut self.requests)
     }
 
+    // These methods are exposed for adapter crates ... prefer using Events.read::<SomeEvent>() instead.
+    pub fn has_request_timeouts(&self) -> bool {
+        !self.request_timeouts.is_empty()
+    }
+    pub fn take_request_timeouts(&mut self) -> HashMap<MessageKind, Vec<GlobalRequestId>> {
+        mem::take(&mut self.request_timeouts)
+    }
+
     // These methods are exposed for adapter crates ... prefer using Events.read::<SomeEvent>() instead.
     pub fn has_inserts(&self) -> bool {
         !self.inserts.is_empty()
@@ -130,8 +178,9 @@ impl<E: Copy> Events<E> {
 
     // Crate-public
 
-    pub(crate) fn push_connection(&mut self, socket_addr: &SocketAddr) {
+    pub(crate) fn push_connection(&mut self, socket_addr: &SocketAddr, params: ConnectionParams) {
         self.connections.push(*socket_addr);
+        self.connection_params.push(params);
         self.empty = false;
     }
 
@@ -140,11 +189,36 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_rejection_details(&mut self, code: u16, message: Option<MessageContainer>) {
+        self.rejection_details.push((code, message));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_welcome(&mut self, message: MessageContainer) {
+        self.welcomes.push(message);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_heartbeat_payload(&mut self, payload: MessageContainer) {
+        self.heartbeat_payloads.push(payload);
+        self.empty = false;
+    }
+
     pub(crate) fn push_disconnection(&mut self, socket_addr: &SocketAddr) {
         self.disconnections.push(*socket_addr);
         self.empty = false;
     }
 
+    pub(crate) fn push_connection_quality_change(&mut self, level: ConnectionQualityLevel) {
+        self.connection_quality_changes.push(level);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_message_delivered(&mut self, receipt_key: MessageReceiptKey) {
+        self.delivered_messages.push(receipt_key);
+        self.empty = false;
+    }
+
     pub(crate) fn push_message(&mut self, channel_kind: &ChannelKind, message: MessageContainer) {
         if !self.messages.contains_key(&channel_kind) {
             self.messages.insert(*channel_kind, HashMap::new());
@@ -181,13 +255,36 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
-    pub(crate) fn push_client_tick(&mut self, tick: Tick) {
-        self.client_ticks.push(tick);
+    pub(crate) fn take_requests_of_kind(
+        &mut self,
+        channel_kind: &ChannelKind,
+        message_kind: &MessageKind,
+    ) -> Vec<(GlobalResponseId, MessageContainer)> {
+        self.requests
+            .get_mut(channel_kind)
+            .and_then(|channel_map| channel_map.remove(message_kind))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn push_request_timeout(
+        &mut self,
+        request_kind: &MessageKind,
+        request_id: GlobalRequestId,
+    ) {
+        self.request_timeouts
+            .entry(*request_kind)
+            .or_default()
+            .push(request_id);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_client_tick(&mut self, tick: Tick, kind: TickSyncKind) {
+        self.client_ticks.push((tick, kind));
         self.empty = false;
     }
 
-    pub(crate) fn push_server_tick(&mut self, tick: Tick) {
-        self.server_ticks.push(tick);
+    pub(crate) fn push_server_tick(&mut self, tick: Tick, kind: TickSyncKind) {
+        self.server_ticks.push((tick, kind));
         self.empty = false;
     }
 
@@ -231,6 +328,11 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_authority_writes_dropped(&mut self, entity: E, reason: AuthDeniedReason) {
+        self.authority_writes_dropped.push((entity, reason));
+        self.empty = false;
+    }
+
     pub(crate) fn push_insert(&mut self, entity: E, component_kind: ComponentKind) {
         if !self.inserts.contains_key(&component_kind) {
             self.inserts.insert(component_kind, Vec::new());
@@ -259,48 +361,21 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
-    pub(crate) fn receive_world_events(
-        &mut self,
-        entity_events: Vec<EntityEvent<E>>,
-    ) -> Vec<EntityResponseEvent<E>> {
-        let mut response_events = Vec::new();
-        for event in entity_events {
-            match event {
-                EntityEvent::SpawnEntity(entity) => {
-                    self.push_spawn(entity);
-                    response_events.push(EntityResponseEvent::SpawnEntity(entity));
-                }
-                EntityEvent::DespawnEntity(entity) => {
-                    self.push_despawn(entity);
-                    response_events.push(EntityResponseEvent::DespawnEntity(entity));
-                }
-                EntityEvent::InsertComponent(entity, component_kind) => {
-                    self.push_insert(entity, component_kind);
-                    response_events
-                        .push(EntityResponseEvent::InsertComponent(entity, component_kind));
-                }
-                EntityEvent::RemoveComponent(entity, component_box) => {
-                    let kind = component_box.kind();
-                    self.push_remove(entity, component_box);
-                    response_events.push(EntityResponseEvent::RemoveComponent(entity, kind));
-                }
-                EntityEvent::UpdateComponent(tick, entity, component_kind) => {
-                    self.push_update(tick, entity, component_kind);
-                }
-            }
-        }
-        response_events
-    }
-
     pub(crate) fn clear(&mut self) {
         self.connections.clear();
+        self.connection_params.clear();
         self.rejections.clear();
+        self.rejection_details.clear();
+        self.welcomes.clear();
+        self.heartbeat_payloads.clear();
         self.disconnections.clear();
         self.client_ticks.clear();
         self.server_ticks.clear();
         self.errors.clear();
+        self.connection_quality_changes.clear();
         self.messages.clear();
         self.requests.clear();
+        self.request_timeouts.clear();
         self.spawns.clear();
         self.despawns.clear();
         self.publishes.clear();
@@ -308,11 +383,117 @@ impl<E: Copy> Events<E> {
         self.auth_grants.clear();
         self.auth_denies.clear();
         self.auth_resets.clear();
+        self.authority_writes_dropped.clear();
         self.inserts.clear();
         self.removes.clear();
         self.updates.clear();
+        if let Some(spawns_with_components) = &mut self.spawns_with_components {
+            spawns_with_components.clear();
+        }
+        self.removed_components.clear();
+        self.deferred_entities.clear();
+        self.deferred_world_events.clear();
         self.empty = true;
     }
+
+    /// Takes the world events held back for the next batch, see `Events`
+    pub(crate) fn take_deferred_world_events(&mut self) -> Vec<EntityEvent<E>> {
+        mem::take(&mut self.deferred_world_events)
+    }
+}
+
+impl<E: Copy + Eq + Hash> Events<E> {
+    pub(crate) fn receive_world_events(
+        &mut self,
+        entity_events: Vec<EntityEvent<E>>,
+    ) -> Vec<EntityResponseEvent<E>> {
+        let mut response_events = Vec::new();
+        for event in &entity_events {
+            match event {
+                EntityEvent::SpawnEntity(entity) => {
+                    response_events.push(EntityResponseEvent::SpawnEntity(*entity));
+                }
+                EntityEvent::DespawnEntity(entity) => {
+                    response_events.push(EntityResponseEvent::DespawnEntity(*entity));
+                }
+                EntityEvent::InsertComponent(entity, component_kind) => {
+                    response_events.push(EntityResponseEvent::InsertComponent(
+                        *entity,
+                        *component_kind,
+                    ));
+                }
+                EntityEvent::RemoveComponent(entity, component_box) => {
+                    response_events.push(EntityResponseEvent::RemoveComponent(
+                        *entity,
+                        component_box.kind(),
+                    ));
+                }
+                EntityEvent::UpdateComponent(_, _, _) => {}
+            }
+        }
+        self.push_world_events(entity_events);
+        response_events
+    }
+
+    /// Pushes world events in the order they happened, holding back any that
+    /// would break the per-Entity ordering documented on `Events`
+    pub(crate) fn push_world_events(&mut self, entity_events: Vec<EntityEvent<E>>) {
+        // Entities spawned in this pass, which have had nothing but inserts since
+        let mut spawning: HashMap<E, usize> = HashMap::new();
+
+        for event in entity_events {
+            let entity = match &event {
+                EntityEvent::SpawnEntity(entity)
+                | EntityEvent::DespawnEntity(entity)
+                | EntityEvent::InsertComponent(entity, _)
+                | EntityEvent::RemoveComponent(entity, _)
+                | EntityEvent::UpdateComponent(_, entity, _) => *entity,
+            };
+            if let EntityEvent::InsertComponent(_, component_kind) = &event {
+                if self.removed_components.contains(&(entity, *component_kind)) {
+                    // read in order, this insert would seem to come before the remove
+                    self.deferred_entities.insert(entity);
+                }
+            }
+            if self.deferred_entities.contains(&entity) {
+                self.deferred_world_events.push(event);
+                self.empty = false;
+                continue;
+            }
+
+            match event {
+                EntityEvent::SpawnEntity(entity) => {
+                    self.push_spawn(entity);
+                    if let Some(spawns_with_components) = &mut self.spawns_with_components {
+                        spawning.insert(entity, spawns_with_components.len());
+                        spawns_with_components.push((entity, Vec::new()));
+                    }
+                }
+                EntityEvent::InsertComponent(entity, component_kind) => {
+                    self.push_insert(entity, component_kind);
+                    if let (Some(index), Some(spawns_with_components)) =
+                        (spawning.get(&entity), &mut self.spawns_with_components)
+                    {
+                        spawns_with_components[*index].1.push(component_kind);
+                    }
+                }
+                EntityEvent::UpdateComponent(tick, entity, component_kind) => {
+                    spawning.remove(&entity);
+                    self.push_update(tick, entity, component_kind);
+                }
+                EntityEvent::RemoveComponent(entity, component_box) => {
+                    spawning.remove(&entity);
+                    self.removed_components
+                        .insert((entity, component_box.kind()));
+                    self.push_remove(entity, component_box);
+                }
+                EntityEvent::DespawnEntity(entity) => {
+                    spawning.remove(&entity);
+                    self.push_despawn(entity);
+                }
+            }
+        }
+    }
 }
 
 // Event Trait
@@ -339,6 +520,23 @@ impl<E: Copy> Event<E> for ConnectEvent {
     }
 }
 
+// ConnectionReadyEvent
+/// Fired alongside ConnectEvent, with the parameters the connection was
+/// established with. Its `initial_rtt` is the one measured while connecting
+pub struct ConnectionReadyEvent;
+impl<E: Copy> Event<E> for ConnectionReadyEvent {
+    type Iter = IntoIter<ConnectionParams>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.connection_params);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.connection_params.is_empty()
+    }
+}
+
 // RejectEvent
 pub struct RejectEvent;
 impl<E: Copy> Event<E> for RejectEvent {
@@ -354,6 +552,123 @@ impl<E: Copy> Event<E> for RejectEvent {
     }
 }
 
+// RejectedEvent
+/// Fired alongside RejectEvent when the Server rejected the connection with
+/// `reject_connection_with()`. Yields the rejection code, and the rejection
+/// Message if one of type M was attached.
+pub struct RejectedEvent<M: Message> {
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, M: Message> Event<E> for RejectedEvent<M> {
+    type Iter = IntoIter<(u16, Option<M>)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        let mut output_list: Vec<(u16, Option<M>)> = Vec::new();
+        let mut other_list = Vec::new();
+
+        for (code, message_opt) in std::mem::take(&mut events.rejection_details) {
+            match message_opt {
+                None => output_list.push((code, None)),
+                Some(message) => {
+                    if message.kind() == message_kind {
+                        let message = message.to_boxed_any().downcast::<M>().unwrap();
+                        output_list.push((code, Some(*message)));
+                    } else {
+                        other_list.push((code, Some(message)));
+                    }
+                }
+            }
+        }
+        events.rejection_details = other_list;
+
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        events
+            .rejection_details
+            .iter()
+            .any(|(_, message_opt)| match message_opt {
+                None => true,
+                Some(message) => message.kind() == message_kind,
+            })
+    }
+}
+
+// WelcomeEvent
+/// Fired before ConnectEvent when the Server accepted the connection with
+/// `accept_connection_with()` and attached a welcome Message of type M
+pub struct WelcomeEvent<M: Message> {
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, M: Message> Event<E> for WelcomeEvent<M> {
+    type Iter = IntoIter<M>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        let mut output_list: Vec<M> = Vec::new();
+        let mut other_list = Vec::new();
+
+        for message in std::mem::take(&mut events.welcomes) {
+            if message.kind() == message_kind {
+                let message = message.to_boxed_any().downcast::<M>().unwrap();
+                output_list.push(*message);
+            } else {
+                other_list.push(message);
+            }
+        }
+        events.welcomes = other_list;
+
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        events
+            .welcomes
+            .iter()
+            .any(|message| message.kind() == message_kind)
+    }
+}
+
+// HeartbeatPayloadEvent
+/// Fired when the Server's Heartbeat carries a payload of type M, see
+/// `Server::set_heartbeat_payload()`
+pub struct HeartbeatPayloadEvent<M: Message> {
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, M: Message> Event<E> for HeartbeatPayloadEvent<M> {
+    type Iter = IntoIter<M>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        let mut output_list: Vec<M> = Vec::new();
+        let mut other_list = Vec::new();
+
+        for message in std::mem::take(&mut events.heartbeat_payloads) {
+            if message.kind() == message_kind {
+                let message = message.to_boxed_any().downcast::<M>().unwrap();
+                output_list.push(*message);
+            } else {
+                other_list.push(message);
+            }
+        }
+        events.heartbeat_payloads = other_list;
+
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        events
+            .heartbeat_payloads
+            .iter()
+            .any(|message| message.kind() == message_kind)
+    }
+}
+
 // DisconnectEvent
 pub struct DisconnectEvent;
 impl<E: Copy> Event<E> for DisconnectEvent {
@@ -372,7 +687,7 @@ impl<E: Copy> Event<E> for DisconnectEvent {
 // Client Tick Event
 pub struct ClientTickEvent;
 impl<E: Copy> Event<E> for ClientTickEvent {
-    type Iter = IntoIter<Tick>;
+    type Iter = IntoIter<(Tick, TickSyncKind)>;
 
     fn iter(events: &mut Events<E>) -> Self::Iter {
         let list = std::mem::take(&mut events.client_ticks);
@@ -387,7 +702,7 @@ impl<E: Copy> Event<E> for ClientTickEvent {
 // Server Tick Event
 pub struct ServerTickEvent;
 impl<E: Copy> Event<E> for ServerTickEvent {
-    type Iter = IntoIter<Tick>;
+    type Iter = IntoIter<(Tick, TickSyncKind)>;
 
     fn iter(events: &mut Events<E>) -> Self::Iter {
         let list = std::mem::take(&mut events.server_ticks);
@@ -414,6 +729,41 @@ impl<E: Copy> Event<E> for ErrorEvent {
     }
 }
 
+// Connection Quality Changed Event
+/// Fired when the connection to the Server moves between the Good, Degraded
+/// and Bad levels set by `ConnectionConfig::connection_quality_thresholds`
+pub struct ConnectionQualityChangedEvent;
+impl<E: Copy> Event<E> for ConnectionQualityChangedEvent {
+    type Iter = IntoIter<ConnectionQualityLevel>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.connection_quality_changes);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.connection_quality_changes.is_empty()
+    }
+}
+
+// Message Delivered Event
+/// Fired once a Message sent with `Client::send_message_with_receipt()` has
+/// been acknowledged by the Server, with every fragment of it delivered.
+/// Yields the receipt returned when sending it
+pub struct MessageDeliveredEvent;
+impl<E: Copy> Event<E> for MessageDeliveredEvent {
+    type Iter = IntoIter<MessageReceiptKey>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.delivered_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.delivered_messages.is_empty()
+    }
+}
+
 // Message Event
 pub struct MessageEvent<C: Channel, M: Message> {
     phantom_c: PhantomData<C>,
@@ -451,6 +801,42 @@ impl<E: Copy, C: Channel, M: Message> Event<E> for MessageEvent<C, M> {
     }
 }
 
+// Timed Message Event
+/// Like `MessageEvent<C, M>`, but also yields the GameInstant each Message was
+/// sent at, on this Client's clock, if Channel C tracks latency. Reading
+/// either event takes the Messages, so only read one of them for C & M
+pub struct TimedMessageEvent<C: Channel, M: Message> {
+    phantom_c: PhantomData<C>,
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, C: Channel, M: Message> Event<E> for TimedMessageEvent<C, M> {
+    type Iter = IntoIter<(Option<GameInstant>, M)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let Some(channel_map) = events.messages.get_mut(&channel_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        let Some(boxed_list) = channel_map.remove(&message_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+
+        let mut output_list = Vec::new();
+        for boxed_message in boxed_list {
+            let send_instant = boxed_message.send_instant();
+            let boxed_any = boxed_message.to_boxed_any();
+            let message = boxed_any.downcast::<M>().unwrap();
+            output_list.push((send_instant, *message));
+        }
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        <MessageEvent<C, M> as Event<E>>::has(events)
+    }
+}
+
 // Request Event
 pub struct RequestEvent<C: Channel, Q: Request> {
     phantom_c: PhantomData<C>,
@@ -490,6 +876,70 @@ impl<E: Copy, C: Channel, Q: Request> Event<E> for RequestEvent<C, Q> {
     }
 }
 
+// Timed Request Event
+/// Like `RequestEvent<C, Q>`, but also yields the GameInstant each Request was
+/// sent at, on this Client's clock, if Channel C tracks latency. Reading
+/// either event takes the Requests, so only read one of them for C & Q
+pub struct TimedRequestEvent<C: Channel, Q: Request> {
+    phantom_c: PhantomData<C>,
+    phantom_m: PhantomData<Q>,
+}
+impl<E: Copy, C: Channel, Q: Request> Event<E> for TimedRequestEvent<C, Q> {
+    type Iter = IntoIter<(ResponseSendKey<Q::Response>, Option<GameInstant>, Q)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let Some(channel_map) = events.requests.get_mut(&channel_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+        let message_kind: MessageKind = MessageKind::of::<Q>();
+        let Some(requests) = channel_map.remove(&message_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+
+        let mut output_list = Vec::new();
+        for (global_response_id, boxed_request) in requests {
+            let send_instant = boxed_request.send_instant();
+            let boxed_any = boxed_request.to_boxed_any();
+            let request = boxed_any.downcast::<Q>().unwrap();
+            let response_send_key = ResponseSendKey::<Q::Response>::new(global_response_id);
+            output_list.push((response_send_key, send_instant, *request));
+        }
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        <RequestEvent<C, Q> as Event<E>>::has(events)
+    }
+}
+
+// Request Timeout Event
+/// Fired when a Request of type Q, sent with `Client::send_request_with_timeout()`,
+/// got no Response in time. Yields the key the Response would have been received with
+pub struct RequestTimeoutEvent<Q: Request> {
+    phantom_q: PhantomData<Q>,
+}
+impl<E: Copy, Q: Request> Event<E> for RequestTimeoutEvent<Q> {
+    type Iter = IntoIter<ResponseReceiveKey<Q::Response>>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let request_kind: MessageKind = MessageKind::of::<Q>();
+        let Some(request_ids) = events.request_timeouts.remove(&request_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+        let output_list: Vec<ResponseReceiveKey<Q::Response>> = request_ids
+            .into_iter()
+            .map(ResponseReceiveKey::new)
+            .collect();
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let request_kind: MessageKind = MessageKind::of::<Q>();
+        return events.request_timeouts.contains_key(&request_kind);
+    }
+}
+
 // Spawn Entity Event
 pub struct SpawnEntityEvent;
 impl<E: Copy> Event<E> for SpawnEntityEvent {
@@ -505,6 +955,32 @@ impl<E: Copy> Event<E> for SpawnEntityEvent {
     }
 }
 
+// Spawn Entity With Components Event
+/// Fired alongside SpawnEntityEvent, when enabled by
+/// `ClientConfig::spawn_with_components_events`. Yields each spawned Entity
+/// along with the kinds of every Component inserted on it in the same
+/// processing pass, so it can be handled in one go. Those inserts are still
+/// also yielded by InsertComponentEvent
+pub struct SpawnEntityWithComponentsEvent;
+impl<E: Copy> Event<E> for SpawnEntityWithComponentsEvent {
+    type Iter = IntoIter<(E, Vec<ComponentKind>)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = match &mut events.spawns_with_components {
+            Some(list) => std::mem::take(list),
+            None => Vec::new(),
+        };
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        match &events.spawns_with_components {
+            Some(list) => !list.is_empty(),
+            None => false,
+        }
+    }
+}
+
 // Despawn Entity Event
 pub struct DespawnEntityEvent;
 impl<E: Copy> Event<E> for DespawnEntityEvent {
@@ -595,6 +1071,22 @@ impl<E: Copy> Event<E> for EntityAuthDeniedEvent {
     }
 }
 
+// Auth Denied Event, for writes queued with `Client::request_authority_then()`
+// which were dropped without ever running
+pub struct AuthDeniedEvent;
+impl<E: Copy> Event<E> for AuthDeniedEvent {
+    type Iter = IntoIter<(E, AuthDeniedReason)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.authority_writes_dropped);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.authority_writes_dropped.is_empty()
+    }
+}
+
 // Insert Component Event
 pub struct InsertComponentEvent<C: Replicate> {
     phantom_c: PhantomData<C>,
@@ -668,3 +1160,133 @@ impl<E: Copy, C: Replicate> Event<E> for RemoveComponentEvent<C> {
         events.removes.contains_key(&component_kind)
     }
 }
+
+#[cfg(test)]
+mod ordering_tests {
+    use naia_demo_world::{Entity, World, WorldMutType};
+    use naia_shared::{ComponentKind, EntityEvent, Property, Replicate, Tick};
+
+    use super::{
+        DespawnEntityEvent, Events, InsertComponentEvent, RemoveComponentEvent, SpawnEntityEvent,
+        SpawnEntityWithComponentsEvent, UpdateComponentEvent,
+    };
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<u8>,
+    }
+
+    #[derive(Replicate)]
+    pub struct Color {
+        pub value: Property<u8>,
+    }
+
+    fn entities(count: usize) -> Vec<Entity> {
+        let mut world = World::default();
+        let mut world = world.proxy_mut();
+        (0..count).map(|_| world.spawn_entity()).collect()
+    }
+
+    fn removed_position(entity: Entity) -> EntityEvent<Entity> {
+        EntityEvent::RemoveComponent(entity, Box::new(Position::new_complete(0)))
+    }
+
+    #[test]
+    fn spawn_collects_inserts_of_same_pass() {
+        let entities = entities(2);
+        let (a, b) = (entities[0], entities[1]);
+        let position = ComponentKind::of::<Position>();
+        let color = ComponentKind::of::<Color>();
+
+        let mut events = Events::new(true);
+        events.receive_world_events(vec![
+            EntityEvent::SpawnEntity(a),
+            EntityEvent::SpawnEntity(b),
+            EntityEvent::InsertComponent(a, position),
+            EntityEvent::InsertComponent(b, color),
+            EntityEvent::InsertComponent(a, color),
+            EntityEvent::UpdateComponent(Tick::default(), b, color),
+            // not part of b's spawn, as it came after an update
+            EntityEvent::InsertComponent(b, position),
+        ]);
+        // a later pass no longer counts as part of the spawn
+        events.receive_world_events(vec![EntityEvent::InsertComponent(b, position)]);
+
+        let spawns: Vec<_> = events.read::<SpawnEntityWithComponentsEvent>().collect();
+        assert!(spawns == vec![(a, vec![position, color]), (b, vec![color])]);
+
+        // the separate events are still all there
+        assert_eq!(events.read::<SpawnEntityEvent>().count(), 2);
+        assert_eq!(events.read::<InsertComponentEvent<Position>>().count(), 3);
+        assert_eq!(events.read::<InsertComponentEvent<Color>>().count(), 2);
+    }
+
+    #[test]
+    fn spawn_with_components_is_opt_in() {
+        let entity = entities(1)[0];
+        let mut events = Events::new(false);
+        events.receive_world_events(vec![
+            EntityEvent::SpawnEntity(entity),
+            EntityEvent::InsertComponent(entity, ComponentKind::of::<Position>()),
+        ]);
+        assert!(!events.has::<SpawnEntityWithComponentsEvent>());
+        assert_eq!(events.read::<SpawnEntityEvent>().count(), 1);
+    }
+
+    #[test]
+    fn reinsert_after_remove_is_deferred_to_next_batch() {
+        let entities = entities(2);
+        let (a, b) = (entities[0], entities[1]);
+        let position = ComponentKind::of::<Position>();
+        let color = ComponentKind::of::<Color>();
+
+        let mut events = Events::new(false);
+        let response_events = events.receive_world_events(vec![
+            removed_position(a),
+            EntityEvent::InsertComponent(b, color),
+            EntityEvent::InsertComponent(a, position),
+            EntityEvent::UpdateComponent(Tick::default(), a, position),
+            EntityEvent::DespawnEntity(a),
+        ]);
+        // the world still hears of everything straight away
+        assert_eq!(response_events.len(), 4);
+
+        // only the remove is in this batch for a, b is unaffected
+        assert_eq!(events.read::<RemoveComponentEvent<Position>>().count(), 1);
+        assert_eq!(events.read::<InsertComponentEvent<Position>>().count(), 0);
+        assert_eq!(events.read::<UpdateComponentEvent<Position>>().count(), 0);
+        assert_eq!(events.read::<DespawnEntityEvent>().count(), 0);
+        assert_eq!(events.read::<InsertComponentEvent<Color>>().count(), 1);
+
+        // the rest arrives, in order, in the next
+        let mut next_events = Events::new(false);
+        next_events.push_world_events(events.take_deferred_world_events());
+        assert!(!next_events.is_empty());
+        let inserted: Vec<_> = next_events
+            .read::<InsertComponentEvent<Position>>()
+            .collect();
+        assert!(inserted == vec![a]);
+        assert_eq!(
+            next_events.read::<UpdateComponentEvent<Position>>().count(),
+            1
+        );
+        let despawned: Vec<_> = next_events.read::<DespawnEntityEvent>().collect();
+        assert!(despawned == vec![a]);
+        assert!(next_events.take_deferred_world_events().is_empty());
+    }
+
+    #[test]
+    fn other_components_are_not_deferred() {
+        let entity = entities(1)[0];
+        let color = ComponentKind::of::<Color>();
+
+        let mut events = Events::new(false);
+        events.receive_world_events(vec![
+            removed_position(entity),
+            EntityEvent::InsertComponent(entity, color),
+        ]);
+        assert_eq!(events.read::<RemoveComponentEvent<Position>>().count(), 1);
+        assert_eq!(events.read::<InsertComponentEvent<Color>>().count(), 1);
+        assert!(events.take_deferred_world_events().is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+//! Entry points for fuzzing the Client's packet parsing. Each takes the raw
+//! bytes of a packet received from the Server and runs them through the same
+//! deserialization path as `Client::receive()` would, against fresh
+//! connection state & without any sockets, returning an error for malformed
+//! input rather than panicking.
+
+use naia_shared::{
+    BaseConnection, BitReader, ConnectionConfig, GameInstant, HostType, Instant, PacketType,
+    Protocol, Serde, SerdeErr, StandardHeader, Tick,
+};
+
+use crate::world::global_world_manager::GlobalWorldManager;
+
+// the Entity type is irrelevant to parsing
+type FuzzEntity = u32;
+
+/// Reads a Data packet sent from the Server, including the messages & world
+/// events it carries
+pub fn read_client_data_packet(protocol: &Protocol, bytes: &[u8]) -> Result<(), SerdeErr> {
+    let mut reader = BitReader::new(bytes);
+    let header = StandardHeader::de(&mut reader)?;
+    if header.packet_type != PacketType::Data {
+        return Err(SerdeErr);
+    }
+
+    let global_world_manager = GlobalWorldManager::<FuzzEntity>::new();
+    let mut connection = BaseConnection::new(
+        &None,
+        HostType::Client,
+        0,
+        &ConnectionConfig::default(),
+        &protocol.channel_kinds,
+        &global_world_manager,
+    );
+    connection.process_incoming_header(&header, &mut []);
+
+    let server_tick = Tick::de(&mut reader)?;
+    GameInstant::de(&mut reader)?;
+    connection.read_packet(
+        protocol,
+        &server_tick,
+        &global_world_manager,
+        true,
+        &mut reader,
+    )?;
+
+    // reassembled & waiting messages are released here
+    connection.message_manager.receive_messages(
+        &protocol.message_kinds,
+        &Instant::now(),
+        &global_world_manager,
+        &connection.local_world_manager,
+        &mut connection.remote_world_manager.entity_waitlist,
+    );
+    connection.message_manager.receive_requests_and_responses();
+    connection.remote_world_reader.take_incoming_events();
+
+    Ok(())
+}
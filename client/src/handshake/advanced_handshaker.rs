@@ -3,13 +3,17 @@ use std::time::Duration;
 use log::warn;
 
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, IdentityToken, OutgoingPacket, PacketType,
-    Serde, StandardHeader, Timer, Timestamp as stamp_time,
+    handshake::{HandshakeError, HandshakeHeader, CHALLENGE_REQUEST_PADDING_BYTES},
+    BitReader, BitWriter, IdentityToken, KeyExchange, OutgoingPacket, PacketType, Serde,
+    StandardHeader, Timer, Timestamp as stamp_time,
 };
 
 use crate::{
     connection::time_manager::TimeManager,
-    handshake::{handshake_time_manager::HandshakeTimeManager, HandshakeResult, Handshaker},
+    handshake::{
+        derive_packet_cipher, handshake_time_manager::HandshakeTimeManager, HandshakeResult,
+        Handshaker,
+    },
 };
 
 type Timestamp = u64;
@@ -50,6 +54,9 @@ pub struct HandshakeManager {
     identity_token: Option<IdentityToken>,
     pre_connection_timestamp: Timestamp,
     pre_connection_digest: Option<Vec<u8>>,
+    schema_hash: u64,
+    // an error if encryption is enabled, but this build can't perform the key exchange
+    key_exchange: Option<Result<KeyExchange, HandshakeError>>,
 }
 
 impl Handshaker for HandshakeManager {
@@ -80,7 +87,10 @@ impl Handshaker for HandshakeManager {
                 }
             }
             HandshakeState::AwaitingValidateResponse => {
-                let writer = self.write_validate_request();
+                let Some(identity_token) = &self.identity_token else {
+                    return None;
+                };
+                let writer = self.write_validate_request(identity_token);
                 return Some(writer.to_packet());
             }
             HandshakeState::TimeSync(time_manager) => {
@@ -102,6 +112,9 @@ impl Handshaker for HandshakeManager {
 
     // Call this regularly so handshake manager can process incoming requests
     fn recv(&mut self, reader: &mut BitReader) -> Option<HandshakeResult> {
+        if let Some(Err(error)) = &self.key_exchange {
+            return Some(HandshakeResult::Rejected(error.clone()));
+        }
         let header_result = StandardHeader::de(reader);
         if header_result.is_err() {
             return None;
@@ -125,7 +138,10 @@ impl Handshaker for HandshakeManager {
                         return None;
                     }
                     HandshakeHeader::ServerConnectResponse => {
-                        return self.recv_connect_response();
+                        return self.recv_connect_response(reader);
+                    }
+                    HandshakeHeader::ServerRejectResponse => {
+                        return self.recv_reject_response(reader);
                     }
                     HandshakeHeader::ClientChallengeRequest
                     | HandshakeHeader::ClientValidateRequest
@@ -174,7 +190,13 @@ impl Handshaker for HandshakeManager {
 }
 
 impl HandshakeManager {
-    pub fn new(send_interval: Duration, ping_interval: Duration, handshake_pings: u8) -> Self {
+    pub fn new(
+        send_interval: Duration,
+        ping_interval: Duration,
+        handshake_pings: u8,
+        schema_hash: u64,
+        encryption_enabled: bool,
+    ) -> Self {
         let mut handshake_timer = Timer::new(send_interval);
         handshake_timer.ring_manual();
 
@@ -188,6 +210,8 @@ impl HandshakeManager {
             connection_state: HandshakeState::AwaitingChallengeResponse,
             ping_interval,
             handshake_pings,
+            schema_hash,
+            key_exchange: encryption_enabled.then(KeyExchange::new),
         }
     }
 
@@ -199,13 +223,21 @@ impl HandshakeManager {
 
         self.pre_connection_timestamp.ser(&mut writer);
         identity_token.ser(&mut writer);
+        self.schema_hash.ser(&mut writer);
+
+        // the Server won't answer a request smaller than its response
+        vec![0u8; CHALLENGE_REQUEST_PADDING_BYTES].ser(&mut writer);
 
         writer
     }
 
     // Step 2 of Handshake
+    // also received while awaiting the validate response, should the Server
+    // send a fresh cookie in place of an expired one
     fn recv_challenge_response(&mut self, reader: &mut BitReader) {
-        if self.connection_state == HandshakeState::AwaitingChallengeResponse {
+        if self.connection_state == HandshakeState::AwaitingChallengeResponse
+            || self.connection_state == HandshakeState::AwaitingValidateResponse
+        {
             let timestamp_result = Timestamp::de(reader);
             if timestamp_result.is_err() {
                 return;
@@ -226,15 +258,18 @@ impl HandshakeManager {
     }
 
     // Step 3 of Handshake
-    fn write_validate_request(&self) -> BitWriter {
+    fn write_validate_request(&self, identity_token: &IdentityToken) -> BitWriter {
         let mut writer = BitWriter::new();
 
         StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
         HandshakeHeader::ClientValidateRequest.ser(&mut writer);
 
-        // write timestamp & digest into payload
+        // echo timestamp & cookie back, proving this Client received them
         self.write_signed_timestamp(&mut writer);
 
+        // only now does the Server identify the User
+        identity_token.ser(&mut writer);
+
         writer
     }
 
@@ -252,18 +287,59 @@ impl HandshakeManager {
         StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
         HandshakeHeader::ClientConnectRequest.ser(&mut writer);
 
+        let public_key = self
+            .key_exchange
+            .as_ref()
+            .and_then(|key_exchange| key_exchange.as_ref().ok())
+            .map(|key_exchange| key_exchange.public_key().to_vec());
+        public_key.ser(&mut writer);
+
         writer
     }
 
     // Step 6 of Handshake
-    fn recv_connect_response(&mut self) -> Option<HandshakeResult> {
+    fn recv_connect_response(&mut self, reader: &mut BitReader) -> Option<HandshakeResult> {
+        if !matches!(
+            self.connection_state,
+            HandshakeState::AwaitingConnectResponse(_)
+        ) {
+            return None;
+        }
+        let Ok(welcome_payload) = Option::<Vec<u8>>::de(reader) else {
+            warn!("Could not read welcome payload");
+            return None;
+        };
+        let Ok(server_public_key) = Option::<Vec<u8>>::de(reader) else {
+            warn!("Could not read server public key");
+            return None;
+        };
+        let packet_cipher = match derive_packet_cipher(self.key_exchange.take(), server_public_key)
+        {
+            Ok(packet_cipher) => packet_cipher,
+            Err(error) => return Some(HandshakeResult::Rejected(error)),
+        };
         let HandshakeState::AwaitingConnectResponse(time_manager) =
             std::mem::replace(&mut self.connection_state, HandshakeState::Connected)
         else {
             return None;
         };
 
-        return Some(HandshakeResult::Connected(time_manager));
+        return Some(HandshakeResult::Connected(
+            Box::new(time_manager),
+            welcome_payload,
+            packet_cipher,
+        ));
+    }
+
+    fn recv_reject_response(&mut self, reader: &mut BitReader) -> Option<HandshakeResult> {
+        if self.connection_state == HandshakeState::Connected {
+            return None;
+        }
+        let Ok(error) = HandshakeError::de(reader) else {
+            warn!("Could not read HandshakeError");
+            return None;
+        };
+        Some(HandshakeResult::Rejected(error))
     }
 
     fn write_signed_timestamp(&self, writer: &mut BitWriter) {
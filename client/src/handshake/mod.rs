@@ -1,6 +1,9 @@
 mod handshake_time_manager;
 
-use naia_shared::{BitReader, BitWriter, IdentityToken, OutgoingPacket};
+use naia_shared::{
+    handshake::HandshakeError, BitReader, BitWriter, HostType, IdentityToken, KeyExchange,
+    OutgoingPacket, PacketCipher,
+};
 
 use crate::connection::time_manager::TimeManager;
 
@@ -15,7 +18,10 @@ cfg_if! {
 }
 
 pub enum HandshakeResult {
-    Connected(TimeManager),
+    // carries the welcome payload, if the Server attached one, and the
+    // connection's keys, if encryption is enabled
+    Connected(Box<TimeManager>, Option<Vec<u8>>, Option<Box<PacketCipher>>),
+    Rejected(HandshakeError),
 }
 
 pub trait Handshaker: Send + Sync {
@@ -25,3 +31,18 @@ pub trait Handshaker: Send + Sync {
     fn recv(&mut self, reader: &mut BitReader) -> Option<HandshakeResult>;
     fn write_disconnect(&self) -> BitWriter;
 }
+
+// completes the Client's half of the key exchange, once the Server's public key is received
+fn derive_packet_cipher(
+    key_exchange: Option<Result<KeyExchange, HandshakeError>>,
+    server_public_key: Option<Vec<u8>>,
+) -> Result<Option<Box<PacketCipher>>, HandshakeError> {
+    match (key_exchange.transpose()?, server_public_key) {
+        (None, None) => Ok(None),
+        (Some(key_exchange), Some(server_public_key)) => key_exchange
+            .into_cipher(HostType::Client, &server_public_key)
+            .map(|packet_cipher| Some(Box::new(packet_cipher)))
+            .ok_or(HandshakeError::EncryptionMismatch),
+        _ => Err(HandshakeError::EncryptionMismatch),
+    }
+}
@@ -3,13 +3,17 @@ use std::time::Duration;
 use log::{info, warn};
 
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, IdentityToken, OutgoingPacket, PacketType,
-    Serde, StandardHeader, Timer,
+    handshake::{HandshakeError, HandshakeHeader},
+    BitReader, BitWriter, IdentityToken, KeyExchange, OutgoingPacket, PacketType, Serde,
+    StandardHeader, Timer,
 };
 
 use crate::{
     connection::time_manager::TimeManager,
-    handshake::{handshake_time_manager::HandshakeTimeManager, HandshakeResult, Handshaker},
+    handshake::{
+        derive_packet_cipher, handshake_time_manager::HandshakeTimeManager, HandshakeResult,
+        Handshaker,
+    },
 };
 
 enum HandshakeState {
@@ -44,6 +48,9 @@ pub struct HandshakeManager {
     identity_token: Option<IdentityToken>,
     ping_interval: Duration,
     handshake_pings: u8,
+    schema_hash: u64,
+    // an error if encryption is enabled, but this build can't perform the key exchange
+    key_exchange: Option<Result<KeyExchange, HandshakeError>>,
 }
 
 impl Handshaker for HandshakeManager {
@@ -98,6 +105,9 @@ impl Handshaker for HandshakeManager {
 
     // Call this regularly so handshake manager can process incoming requests
     fn recv(&mut self, reader: &mut BitReader) -> Option<HandshakeResult> {
+        if let Some(Err(error)) = &self.key_exchange {
+            return Some(HandshakeResult::Rejected(error.clone()));
+        }
         let header_result = StandardHeader::de(reader);
         if header_result.is_err() {
             return None;
@@ -117,7 +127,10 @@ impl Handshaker for HandshakeManager {
                     }
                     HandshakeHeader::ServerConnectResponse => {
                         info!("CLIENT HANDSHAKE: Received ServerConnectResponse, transitioning to Connected");
-                        return self.recv_connect_response();
+                        return self.recv_connect_response(reader);
+                    }
+                    HandshakeHeader::ServerRejectResponse => {
+                        return self.recv_reject_response(reader);
                     }
                     HandshakeHeader::ClientIdentifyRequest
                     | HandshakeHeader::ClientConnectRequest
@@ -169,7 +182,13 @@ impl Handshaker for HandshakeManager {
 }
 
 impl HandshakeManager {
-    pub fn new(send_interval: Duration, ping_interval: Duration, handshake_pings: u8) -> Self {
+    pub fn new(
+        send_interval: Duration,
+        ping_interval: Duration,
+        handshake_pings: u8,
+        schema_hash: u64,
+        encryption_enabled: bool,
+    ) -> Self {
         let mut handshake_timer = Timer::new(send_interval);
         handshake_timer.ring_manual();
 
@@ -181,6 +200,8 @@ impl HandshakeManager {
             connection_state: HandshakeState::AwaitingIdentifyResponse,
             ping_interval,
             handshake_pings,
+            schema_hash,
+            key_exchange: encryption_enabled.then(KeyExchange::new),
         }
     }
 
@@ -191,6 +212,7 @@ impl HandshakeManager {
         HandshakeHeader::ClientIdentifyRequest.ser(&mut writer);
 
         identity_token.ser(&mut writer);
+        self.schema_hash.ser(&mut writer);
 
         writer
     }
@@ -211,17 +233,58 @@ impl HandshakeManager {
         StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
         HandshakeHeader::ClientConnectRequest.ser(&mut writer);
 
+        let public_key = self
+            .key_exchange
+            .as_ref()
+            .and_then(|key_exchange| key_exchange.as_ref().ok())
+            .map(|key_exchange| key_exchange.public_key().to_vec());
+        public_key.ser(&mut writer);
+
         writer
     }
 
     // Step 6 of Handshake
-    fn recv_connect_response(&mut self) -> Option<HandshakeResult> {
+    fn recv_connect_response(&mut self, reader: &mut BitReader) -> Option<HandshakeResult> {
+        if !matches!(
+            self.connection_state,
+            HandshakeState::AwaitingConnectResponse(_)
+        ) {
+            return None;
+        }
+        let Ok(welcome_payload) = Option::<Vec<u8>>::de(reader) else {
+            warn!("Could not read welcome payload");
+            return None;
+        };
+        let Ok(server_public_key) = Option::<Vec<u8>>::de(reader) else {
+            warn!("Could not read server public key");
+            return None;
+        };
+        let packet_cipher = match derive_packet_cipher(self.key_exchange.take(), server_public_key)
+        {
+            Ok(packet_cipher) => packet_cipher,
+            Err(error) => return Some(HandshakeResult::Rejected(error)),
+        };
         let HandshakeState::AwaitingConnectResponse(time_manager) =
             std::mem::replace(&mut self.connection_state, HandshakeState::Connected)
         else {
             return None;
         };
 
-        return Some(HandshakeResult::Connected(time_manager));
+        return Some(HandshakeResult::Connected(
+            Box::new(time_manager),
+            welcome_payload,
+            packet_cipher,
+        ));
+    }
+
+    fn recv_reject_response(&mut self, reader: &mut BitReader) -> Option<HandshakeResult> {
+        if self.connection_state == HandshakeState::Connected {
+            return None;
+        }
+        let Ok(error) = HandshakeError::de(reader) else {
+            warn!("Could not read HandshakeError");
+            return None;
+        };
+        Some(HandshakeResult::Rejected(error))
     }
 }
@@ -16,9 +16,10 @@ extern crate cfg_if;
 pub mod transport;
 pub mod shared {
     pub use naia_shared::{
-        default_channels, sequence_greater_than, GameInstant, GlobalRequestId, GlobalResponseId,
-        Instant, LinkConditionerConfig, Message, Protocol, Random, ResponseReceiveKey,
-        SocketConfig, Tick,
+        default_channels, game_instant_greater_than, game_instant_less_than, sequence_greater_than,
+        ChannelLatencyStats, GameDuration, GameInstant, GlobalRequestId, GlobalResponseId, Instant,
+        LinkConditionerConfig, Message, MessageReceiptKey, Protocol, Random, ResponseReceiveKey,
+        SocketConfig, Tick, TickExt,
     };
 }
 
@@ -32,16 +33,27 @@ mod handshake;
 mod request;
 mod world;
 
+cfg_if! {
+    if #[cfg(feature = "fuzz")] {
+        pub mod fuzz_targets;
+    }
+}
+
 pub use client::{Client, ConnectionStatus};
 pub use client_config::ClientConfig;
 pub use command_history::CommandHistory;
+pub use connection::tick_sync::{TickSyncDiagnostics, TickSyncKind};
 pub use error::NaiaClientError;
 pub use events::{
-    ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-    EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, Events, InsertComponentEvent,
-    MessageEvent, PublishEntityEvent, RejectEvent, RemoveComponentEvent, RequestEvent,
-    ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent, UpdateComponentEvent,
+    AuthDeniedEvent, ClientTickEvent, ConnectEvent, ConnectionQualityChangedEvent,
+    ConnectionReadyEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
+    EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, Events, HeartbeatPayloadEvent,
+    InsertComponentEvent, MessageDeliveredEvent, MessageEvent, PublishEntityEvent, RejectEvent,
+    RejectedEvent, RemoveComponentEvent, RequestEvent, RequestTimeoutEvent, ServerTickEvent,
+    SpawnEntityEvent, SpawnEntityWithComponentsEvent, TimedMessageEvent, TimedRequestEvent,
+    UnpublishEntityEvent, UpdateComponentEvent, WelcomeEvent,
 };
 pub use world::{
-    entity_mut::EntityMut, entity_ref::EntityRef, replication_config::ReplicationConfig,
+    authority_writes::AuthDeniedReason, entity_mut::EntityMut, entity_owner::EntityOwner,
+    entity_ref::EntityRef, replication_config::ReplicationConfig,
 };
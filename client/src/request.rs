@@ -1,12 +1,21 @@
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap, time::Duration};
 
 use naia_shared::{
-    ChannelKind, GlobalRequestId, GlobalResponseId, LocalResponseId, MessageContainer,
+    ChannelKind, GlobalRequestId, GlobalResponseId, Instant, LocalResponseId, Message,
+    MessageContainer, MessageKind, Request, ResponderResult, ResponseSendKey,
 };
 
+// how long a late response to a cancelled request is waited for, before it's
+// assumed never to arrive
+const CANCELLED_REQUEST_TTL: Duration = Duration::from_secs(60);
+
 // GlobalRequestManager
 pub struct GlobalRequestManager {
     map: HashMap<GlobalRequestId, Option<MessageContainer>>,
+    // when each request was cancelled, while its response may still arrive
+    cancelled: HashMap<GlobalRequestId, Instant>,
+    // Request kind & deadline, for requests sent with a timeout
+    deadlines: HashMap<GlobalRequestId, (MessageKind, Instant)>,
     next_id: u64,
 }
 
@@ -14,6 +23,8 @@ impl GlobalRequestManager {
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            cancelled: HashMap::new(),
+            deadlines: HashMap::new(),
             next_id: 0,
         }
     }
@@ -27,6 +38,35 @@ impl GlobalRequestManager {
         id
     }
 
+    /// Gives up on the request if no response has arrived by `deadline`
+    pub(crate) fn set_deadline(
+        &mut self,
+        request_id: &GlobalRequestId,
+        request_kind: MessageKind,
+        deadline: Instant,
+    ) {
+        self.deadlines.insert(*request_id, (request_kind, deadline));
+    }
+
+    /// Cancels every request whose deadline has passed without a response,
+    /// returning the id & kind of each. Also forgets requests cancelled long
+    /// enough ago that their response isn't coming
+    pub(crate) fn expire_requests(&mut self, now: &Instant) -> Vec<(GlobalRequestId, MessageKind)> {
+        self.cancelled
+            .retain(|_, cancelled_at| cancelled_at.elapsed(now) < CANCELLED_REQUEST_TTL);
+
+        let expired: Vec<(GlobalRequestId, MessageKind)> = self
+            .deadlines
+            .iter()
+            .filter(|(_, (_, deadline))| !deadline.is_after(now))
+            .map(|(request_id, (request_kind, _))| (*request_id, *request_kind))
+            .collect();
+        for (request_id, _) in &expired {
+            self.cancel_request_id(request_id);
+        }
+        expired
+    }
+
     pub(crate) fn destroy_request_id(
         &mut self,
         request_id: &GlobalRequestId,
@@ -35,18 +75,40 @@ impl GlobalRequestManager {
             return None;
         };
         if response_opt.is_some() {
+            self.deadlines.remove(request_id);
             let response_opt = self.map.remove(request_id).unwrap();
             return Some(response_opt.unwrap());
         }
         return None;
     }
 
+    /// Removes a pending request. If the response has not arrived yet, it will be
+    /// dropped on arrival. Returns whether a live request was cancelled.
+    pub(crate) fn cancel_request_id(&mut self, request_id: &GlobalRequestId) -> bool {
+        self.deadlines.remove(request_id);
+        let Some(response_opt) = self.map.remove(request_id) else {
+            return false;
+        };
+        if response_opt.is_none() {
+            self.cancelled.insert(*request_id, Instant::now());
+        }
+        true
+    }
+
     pub(crate) fn receive_response(
         &mut self,
         request_id: &GlobalRequestId,
         response: MessageContainer,
     ) {
-        let response_opt = self.map.get_mut(request_id).unwrap();
+        if self.cancelled.remove(request_id).is_some() {
+            // request was cancelled, drop the late response
+            return;
+        }
+        self.deadlines.remove(request_id);
+        let Some(response_opt) = self.map.get_mut(request_id) else {
+            // cancelled too long ago to be remembered
+            return;
+        };
         *response_opt = Some(response);
     }
 }
@@ -86,3 +148,106 @@ impl GlobalResponseManager {
         self.map.remove(global_response_id)
     }
 }
+
+// Responder
+/// A handler registered with `Client::register_responder()`, with its Request
+/// & Response types erased
+pub(crate) type Responder =
+    Box<dyn FnMut(&GlobalResponseId, MessageContainer) -> ResponderOutcome + Send + Sync>;
+
+pub(crate) enum ResponderOutcome {
+    Respond(Box<dyn Message>),
+    Deferred,
+    Declined(MessageContainer),
+}
+
+pub(crate) fn erase_responder<Q: Request>(
+    mut handler: impl FnMut(Q) -> ResponderResult<Q::Response> + Send + Sync + 'static,
+) -> Responder {
+    Box::new(move |response_id, container| {
+        // keep the container, in case the Request falls through
+        let request: Q = Box::<dyn Any + 'static>::downcast::<Q>(container.clone().to_boxed_any())
+            .ok()
+            .map(|boxed_q| *boxed_q)
+            .unwrap();
+        match handler(request) {
+            ResponderResult::Respond(response) => ResponderOutcome::Respond(Box::new(response)),
+            ResponderResult::Defer(defer) => {
+                defer(ResponseSendKey::new(*response_id));
+                ResponderOutcome::Deferred
+            }
+            ResponderResult::DeclineToHandle => ResponderOutcome::Declined(container),
+        }
+    })
+}
+
+#[cfg(test)]
+mod cancel_request_tests {
+    use naia_shared::{FakeEntityConverter, Instant, Message, MessageContainer, MessageKind};
+
+    use crate::request::{GlobalRequestManager, CANCELLED_REQUEST_TTL};
+
+    #[derive(Message)]
+    pub struct TestResponse {
+        pub value: u8,
+    }
+
+    #[test]
+    fn cancelled_request_drops_late_response() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id();
+
+        assert!(manager.cancel_request_id(&request_id));
+        let response = MessageContainer::from_write(
+            Box::new(TestResponse { value: 7 }),
+            &mut FakeEntityConverter,
+        );
+        manager.receive_response(&request_id, response);
+
+        assert!(manager.destroy_request_id(&request_id).is_none());
+        assert!(!manager.cancel_request_id(&request_id));
+    }
+
+    #[test]
+    fn cancelled_request_is_forgotten_if_no_response_arrives() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id();
+        assert!(manager.cancel_request_id(&request_id));
+
+        let mut now = Instant::now();
+        assert!(manager.expire_requests(&now).is_empty());
+        assert_eq!(manager.cancelled.len(), 1);
+
+        now.add_millis(CANCELLED_REQUEST_TTL.as_millis() as u32);
+        assert!(manager.expire_requests(&now).is_empty());
+        assert!(manager.cancelled.is_empty());
+
+        // a response which turns up after all is still dropped
+        let response = MessageContainer::from_write(
+            Box::new(TestResponse { value: 7 }),
+            &mut FakeEntityConverter,
+        );
+        manager.receive_response(&request_id, response);
+        assert!(manager.destroy_request_id(&request_id).is_none());
+    }
+
+    #[test]
+    fn expired_request_is_cancelled() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id();
+        let now = Instant::now();
+        manager.set_deadline(&request_id, MessageKind::of::<TestResponse>(), now.clone());
+
+        assert!(
+            manager.expire_requests(&now) == vec![(request_id, MessageKind::of::<TestResponse>())]
+        );
+
+        let response = MessageContainer::from_write(
+            Box::new(TestResponse { value: 7 }),
+            &mut FakeEntityConverter,
+        );
+        manager.receive_response(&request_id, response);
+        assert!(manager.destroy_request_id(&request_id).is_none());
+        assert!(manager.expire_requests(&now).is_empty());
+    }
+}
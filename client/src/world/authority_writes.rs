@@ -0,0 +1,105 @@
+use std::{any::Any, collections::HashMap, hash::Hash, time::Duration};
+
+use log::warn;
+
+use naia_shared::Instant;
+
+// a `Box<dyn FnOnce(&mut W) + Send>`, for the World type W it was queued with
+type AuthorityWrite = Box<dyn Any + Send>;
+
+/// Why writes queued with `Client::request_authority_then()` were dropped
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthDeniedReason {
+    /// Authority over the Entity was denied, as another host holds it
+    Denied,
+    /// Authority over the Entity was not granted within
+    /// `ClientConfig::authority_write_timeout`
+    TimedOut,
+}
+
+struct PendingWrites {
+    requested_at: Instant,
+    writes: Vec<AuthorityWrite>,
+}
+
+/// Writes to Delegated Entities, held back until Authority over them is
+/// granted
+pub struct AuthorityWrites<E: Copy + Eq + Hash> {
+    pending: HashMap<E, PendingWrites>,
+    ready: Vec<AuthorityWrite>,
+}
+
+impl<E: Copy + Eq + Hash> AuthorityWrites<E> {
+    pub fn new() -> Self {
+        Self {
+            pending: HashMap::new(),
+            ready: Vec::new(),
+        }
+    }
+
+    /// Holds back a write until Authority over the Entity is granted
+    pub fn queue_pending<W: 'static>(
+        &mut self,
+        entity: &E,
+        write: impl FnOnce(&mut W) + Send + 'static,
+    ) {
+        self.pending
+            .entry(*entity)
+            .or_insert_with(|| PendingWrites {
+                requested_at: Instant::now(),
+                writes: Vec::new(),
+            })
+            .writes
+            .push(erase_write(write));
+    }
+
+    /// Queues a write to run on the next `run()`
+    pub fn queue_ready<W: 'static>(&mut self, write: impl FnOnce(&mut W) + Send + 'static) {
+        self.ready.push(erase_write(write));
+    }
+
+    /// Authority over the Entity was granted, so its writes are run next
+    pub fn grant(&mut self, entity: &E) {
+        if let Some(pending) = self.pending.remove(entity) {
+            self.ready.extend(pending.writes);
+        }
+    }
+
+    /// Drops the writes held back for the Entity, returning whether there
+    /// were any
+    pub fn drop_pending(&mut self, entity: &E) -> bool {
+        self.pending.remove(entity).is_some()
+    }
+
+    /// Drops the writes which have waited longer than `timeout` for
+    /// Authority, returning their Entities
+    pub fn drop_timed_out(&mut self, timeout: &Duration) -> Vec<E> {
+        let now = Instant::now();
+        let timed_out: Vec<E> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.requested_at.elapsed(&now) >= *timeout)
+            .map(|(entity, _)| *entity)
+            .collect();
+        for entity in &timed_out {
+            self.pending.remove(entity);
+        }
+        timed_out
+    }
+
+    /// Runs every write whose Authority has been granted
+    pub fn run<W: 'static>(&mut self, world: &mut W) {
+        for write in std::mem::take(&mut self.ready) {
+            let Ok(write) = write.downcast::<Box<dyn FnOnce(&mut W) + Send>>() else {
+                warn!("Dropping a write queued for Authority, as it was queued for a different World type");
+                continue;
+            };
+            write(world);
+        }
+    }
+}
+
+fn erase_write<W: 'static>(write: impl FnOnce(&mut W) + Send + 'static) -> AuthorityWrite {
+    let write: Box<dyn FnOnce(&mut W) + Send> = Box::new(write);
+    Box::new(write)
+}
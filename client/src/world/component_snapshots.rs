@@ -0,0 +1,70 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use naia_shared::{sequence_greater_than, ComponentKind, Replicate, Tick, WorldMutType};
+
+type Snapshot<E> = HashMap<(E, ComponentKind), Box<dyn Replicate>>;
+
+/// Copies of the Client's Components, keyed on the Tick they were taken at,
+/// so that predicted state can be rolled back & re-simulated once the
+/// authoritative state for an older Tick arrives
+pub struct ComponentSnapshots<E: Copy + Eq + Hash> {
+    // front is oldest, back is newest
+    buffer: VecDeque<(Tick, Snapshot<E>)>,
+    capacity: usize,
+}
+
+impl<E: Copy + Eq + Hash> ComponentSnapshots<E> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Stores a snapshot for the given Tick, replacing any snapshot taken
+    /// for the same or a later Tick
+    pub fn insert(&mut self, tick: Tick, snapshot: Snapshot<E>) {
+        self.remove_from(&tick);
+        self.buffer.push_back((tick, snapshot));
+        while self.buffer.len() > self.capacity {
+            self.buffer.pop_front();
+        }
+    }
+
+    /// Sets every Component in the snapshot for the given Tick back to its
+    /// stored state, and discards the snapshots of later Ticks. Returns
+    /// whether a snapshot for the Tick was found
+    pub fn rollback_to<W: WorldMutType<E>>(&mut self, world: &mut W, tick: &Tick) -> bool {
+        let Some(snapshot) = self.snapshot_mut(tick) else {
+            return false;
+        };
+        for ((entity, component_kind), stored) in snapshot.iter() {
+            // Components removed since the snapshot stay removed
+            if let Some(mut component) = world.component_mut_of_kind(entity, component_kind) {
+                component.mirror(stored.as_ref());
+            }
+        }
+        self.remove_from(&tick.wrapping_add(1));
+        true
+    }
+
+    fn snapshot_mut(&mut self, tick: &Tick) -> Option<&mut Snapshot<E>> {
+        self.buffer
+            .iter_mut()
+            .find(|(snapshot_tick, _)| snapshot_tick == tick)
+            .map(|(_, snapshot)| snapshot)
+    }
+
+    // removes the snapshots of the given Tick and every later one
+    fn remove_from(&mut self, tick: &Tick) {
+        while let Some((back_tick, _)) = self.buffer.back() {
+            if sequence_greater_than(*tick, *back_tick) {
+                return;
+            }
+            self.buffer.pop_back();
+        }
+    }
+}
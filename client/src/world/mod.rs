@@ -1,3 +1,5 @@
+pub mod authority_writes;
+pub mod component_snapshots;
 pub mod entity_mut;
 pub mod entity_owner;
 pub mod entity_ref;
@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate log;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use log::LevelFilter;
+use simple_logger::SimpleLogger;
+
+use naia_demo_world::World;
+use naia_server::{
+    shared::default_channels::UnorderedReliableChannel, transport::webrtc, AuthEvent, ConnectEvent,
+    DisconnectEvent, ErrorEvent, MessageEvent, Server, ServerConfig, ServerRunner,
+};
+
+use naia_basic_demo_shared::{protocol, Auth, StringMessage};
+
+// A chat server: every Message a User sends is relayed to all Users
+#[tokio::main]
+async fn main() {
+    SimpleLogger::new()
+        .with_level(LevelFilter::Info)
+        .init()
+        .expect("A logger was already initialized");
+
+    info!("Naia Async Chat Server Demo started");
+
+    let server_addresses = webrtc::ServerAddrs::new(
+        "127.0.0.1:14191"
+            .parse()
+            .expect("could not parse Signaling address/port"),
+        // IP Address to listen on for UDP WebRTC data channels
+        "127.0.0.1:14192"
+            .parse()
+            .expect("could not parse WebRTC data address/port"),
+        // The public WebRTC IP address to advertise
+        "http://127.0.0.1:14192",
+    );
+    let protocol = protocol();
+    let socket = webrtc::Socket::new(&server_addresses, &protocol.socket);
+    let mut server = Server::new(ServerConfig::default(), protocol);
+    server.listen(socket);
+
+    let world = Arc::new(Mutex::new(World::default()));
+    let (mut events, control) = ServerRunner::spawn(server, world, Duration::from_millis(3));
+
+    while let Some(mut events) = events.next().await {
+        for (user_key, auth) in events.read::<AuthEvent<Auth>>() {
+            if auth.username == "charlie" && auth.password == "12345" {
+                info!("accepting connection for user_key: {:?}", user_key);
+                control.accept_connection(&user_key);
+            } else {
+                info!("rejecting connection for user_key: {:?}", user_key);
+                control.reject_connection(&user_key);
+            }
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            info!("Naia Server connected to: {:?}", user_key);
+        }
+        for (_user_key, user) in events.read::<DisconnectEvent>() {
+            info!("Naia Server disconnected from: {:?}", user.address());
+        }
+        for (user_key, message) in
+            events.read::<MessageEvent<UnorderedReliableChannel, StringMessage>>()
+        {
+            let contents = format!("{:?}: {}", user_key, *message.contents);
+            info!("Server relay -> {}", contents);
+            control.broadcast_message::<UnorderedReliableChannel, _>(StringMessage::new(contents));
+        }
+        for error in events.read::<ErrorEvent>() {
+            info!("Naia Server Error: {}", error);
+        }
+    }
+}
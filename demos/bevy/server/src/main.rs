@@ -40,6 +40,7 @@ fn main() {
                 events::disconnect_events,
                 events::error_events,
                 events::tick_events,
+                events::key_command_events,
                 events::spawn_entity_events,
                 events::despawn_entity_events,
                 events::publish_entity_events,
@@ -10,7 +10,7 @@ use naia_bevy_server::{
         InsertComponentEvents, PublishEntityEvent, RemoveComponentEvents, RequestEvents,
         SpawnEntityEvent, TickEvent, UnpublishEntityEvent, UpdateComponentEvents,
     },
-    CommandsExt, Random, ReplicationConfig, Server,
+    CommandsExt, Random, ReplicationConfig, Server, TickBuffer,
 };
 
 use naia_bevy_demo_shared::{
@@ -114,7 +114,6 @@ pub fn error_events(mut event_reader: EventReader<ErrorEvent>) {
 
 pub fn tick_events(
     mut server: Server,
-    mut position_query: Query<&mut Position>,
     mut global: ResMut<Global>,
     mut tick_reader: EventReader<TickEvent>,
 ) {
@@ -125,17 +124,6 @@ pub fn tick_events(
 
         // All game logic should happen here, on a tick event
 
-        let mut messages = server.receive_tick_buffer_messages(server_tick);
-        for (_user_key, key_command) in messages.read::<PlayerCommandChannel, KeyCommand>() {
-            let Some(entity) = &key_command.entity.get(&server) else {
-                continue;
-            };
-            let Ok(mut position) = position_query.get_mut(*entity) else {
-                continue;
-            };
-            shared_behavior::process_command(&key_command, &mut position);
-        }
-
         // Send a request to all clients
         if server_tick % 100 == 0 {
             for user_key in server.user_keys() {
@@ -175,6 +163,23 @@ pub fn tick_events(
     }
 }
 
+pub fn key_command_events(
+    server: Server,
+    mut tick_buffer: TickBuffer<PlayerCommandChannel, KeyCommand>,
+    mut position_query: Query<&mut Position>,
+) {
+    // Commands from every Tick processed this frame, in Tick order
+    for (_user_key, _tick, key_command) in tick_buffer.read() {
+        let Some(entity) = &key_command.entity.get(&server) else {
+            continue;
+        };
+        let Ok(mut position) = position_query.get_mut(*entity) else {
+            continue;
+        };
+        shared_behavior::process_command(&key_command, &mut position);
+    }
+}
+
 pub fn request_events(mut server: Server, mut event_reader: EventReader<RequestEvents>) {
     for events in event_reader.read() {
         for (user_key, response_send_key, request) in events.read::<RequestChannel, BasicRequest>()
@@ -1,9 +1,9 @@
-use std::{any::Any, collections::HashMap};
+use std::collections::HashMap;
 
 use naia_shared::{
-    BigMap, ComponentFieldUpdate, ComponentKind, ComponentUpdate, GlobalWorldManagerType,
-    LocalEntityAndGlobalEntityConverter, ReplicaDynMutWrapper, ReplicaDynRefWrapper,
-    ReplicaMutWrapper, ReplicaRefWrapper, Replicate, SerdeErr, WorldMutType, WorldRefType,
+    BigMap, ComponentFieldUpdate, ComponentKind, LocalEntityAndGlobalEntityConverter,
+    ProxyWorldMut, ReplicaDynMutWrapper, ReplicaDynRefWrapper, ReplicaMutWrapper,
+    ReplicaRefWrapper, Replicate, SerdeErr, WorldMutType, WorldRefType,
 };
 
 use super::{
@@ -41,6 +41,14 @@ impl World {
     }
 }
 
+impl ProxyWorldMut<Entity> for World {
+    type Proxy<'w> = WorldMut<'w>;
+
+    fn proxy_mut(&mut self) -> WorldMut<'_> {
+        WorldMut::new(self)
+    }
+}
+
 // WorldRef //
 
 pub struct WorldRef<'w> {
@@ -76,10 +84,6 @@ impl<'w> WorldRefType<Entity> for WorldRef<'w> {
         entities(self.world)
     }
 
-    fn has_component<R: Replicate>(&self, entity: &Entity) -> bool {
-        has_component::<R>(self.world, entity)
-    }
-
     fn has_component_of_kind(&self, entity: &Entity, component_kind: &ComponentKind) -> bool {
         has_component_of_type(self.world, entity, component_kind)
     }
@@ -106,10 +110,6 @@ impl<'w> WorldRefType<Entity> for WorldMut<'w> {
         entities(self.world)
     }
 
-    fn has_component<R: Replicate>(&self, entity: &Entity) -> bool {
-        has_component::<R>(self.world, entity)
-    }
-
     fn has_component_of_kind(&self, entity: &Entity, component_kind: &ComponentKind) -> bool {
         has_component_of_type(self.world, entity, component_kind)
     }
@@ -133,28 +133,6 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         self.world.entities.insert(component_map)
     }
 
-    fn local_duplicate_entity(&mut self, entity: &Entity) -> Entity {
-        let new_entity = self.spawn_entity();
-
-        self.local_duplicate_components(&new_entity, entity);
-
-        new_entity
-    }
-
-    fn local_duplicate_components(&mut self, new_entity: &Entity, old_entity: &Entity) {
-        for component_kind in self.component_kinds(old_entity) {
-            let mut boxed_option: Option<Box<dyn Replicate>> = None;
-            if let Some(component) = self.component_of_kind(old_entity, &component_kind) {
-                boxed_option = Some(component.copy_to_box());
-            }
-            if let Some(boxed_component) = boxed_option {
-                self.insert_boxed_component(new_entity, boxed_component);
-            } else {
-                panic!("this shouldn't happen");
-            }
-        }
-    }
-
     fn despawn_entity(&mut self, entity: &Entity) {
         self.world.entities.remove(entity);
     }
@@ -199,19 +177,6 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         None
     }
 
-    fn component_apply_update(
-        &mut self,
-        converter: &dyn LocalEntityAndGlobalEntityConverter,
-        entity: &Entity,
-        component_kind: &ComponentKind,
-        update: ComponentUpdate,
-    ) -> Result<(), SerdeErr> {
-        if let Some(mut component) = component_mut_of_kind(self.world, entity, component_kind) {
-            component.read_apply_update(converter, update)?;
-        }
-        Ok(())
-    }
-
     fn component_apply_field_update(
         &mut self,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
@@ -225,48 +190,6 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         Ok(())
     }
 
-    fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
-        for component_kind in self.component_kinds(old_entity) {
-            self.mirror_components(new_entity, old_entity, &component_kind);
-        }
-    }
-
-    fn mirror_components(
-        &mut self,
-        mutable_entity: &Entity,
-        immutable_entity: &Entity,
-        component_kind: &ComponentKind,
-    ) {
-        let immutable_component_opt: Option<Box<dyn Replicate>> = {
-            if let Some(immutable_component_map) = self.world.entities.get(immutable_entity) {
-                if let Some(immutable_component) = immutable_component_map.get(component_kind) {
-                    Some(immutable_component.copy_to_box())
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        };
-        if let Some(immutable_component) = immutable_component_opt {
-            if let Some(mutable_component_map) = self.world.entities.get_mut(mutable_entity) {
-                if let Some(mutable_component) = mutable_component_map.get_mut(component_kind) {
-                    mutable_component.mirror(immutable_component.as_ref());
-                }
-            }
-        }
-    }
-
-    fn insert_component<R: Replicate>(&mut self, entity: &Entity, component: R) {
-        if let Some(component_map) = self.world.entities.get_mut(entity) {
-            let component_kind = ComponentKind::of::<R>();
-            if component_map.contains_key(&component_kind) {
-                panic!("Entity already has a Component of that type!");
-            }
-            component_map.insert(component_kind, Box::new(component));
-        }
-    }
-
     fn insert_boxed_component(&mut self, entity: &Entity, boxed_component: Box<dyn Replicate>) {
         if let Some(component_map) = self.world.entities.get_mut(entity) {
             let component_kind = boxed_component.kind();
@@ -277,17 +200,6 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         }
     }
 
-    fn remove_component<R: Replicate>(&mut self, entity: &Entity) -> Option<R> {
-        if let Some(component_map) = self.world.entities.get_mut(entity) {
-            if let Some(boxed_component) = component_map.remove(&ComponentKind::of::<R>()) {
-                return Box::<dyn Any + 'static>::downcast::<R>(boxed_component.to_boxed_any())
-                    .ok()
-                    .map(|boxed_c| *boxed_c);
-            }
-        }
-        None
-    }
-
     fn remove_component_of_kind(
         &mut self,
         entity: &Entity,
@@ -299,73 +211,6 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
 
         None
     }
-
-    fn entity_publish(
-        &mut self,
-        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        entity: &Entity,
-    ) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
-            WorldMutType::<Entity>::component_publish(
-                self,
-                global_world_manager,
-                entity,
-                &component_kind,
-            );
-        }
-    }
-
-    fn component_publish(
-        &mut self,
-        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        entity: &Entity,
-        component_kind: &ComponentKind,
-    ) {
-        if let Some(component_map) = self.world.entities.get_mut(entity) {
-            if let Some(component) = component_map.get_mut(component_kind) {
-                let diff_mask_size = component.diff_mask_size();
-                let mutator = global_world_manager.register_component(
-                    entity,
-                    &component_kind,
-                    diff_mask_size,
-                );
-                component.publish(&mutator);
-            }
-        }
-    }
-
-    fn entity_unpublish(&mut self, _entity: &Entity) {
-        todo!()
-    }
-
-    fn component_unpublish(&mut self, _entity: &Entity, _component_kind: &ComponentKind) {
-        todo!()
-    }
-
-    fn entity_enable_delegation(
-        &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
-    ) {
-        todo!()
-    }
-
-    fn component_enable_delegation(
-        &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
-        _component_kind: &ComponentKind,
-    ) {
-        todo!()
-    }
-
-    fn entity_disable_delegation(&mut self, _entity: &Entity) {
-        todo!()
-    }
-
-    fn component_disable_delegation(&mut self, _entity: &Entity, _component_kind: &ComponentKind) {
-        todo!()
-    }
 }
 
 // private methods //
@@ -384,14 +229,6 @@ fn entities(world: &World) -> Vec<Entity> {
     output
 }
 
-fn has_component<R: Replicate>(world: &World, entity: &Entity) -> bool {
-    if let Some(component_map) = world.entities.get(entity) {
-        return component_map.contains_key(&ComponentKind::of::<R>());
-    }
-
-    false
-}
-
 fn has_component_of_type(world: &World, entity: &Entity, component_kind: &ComponentKind) -> bool {
     if let Some(component_map) = world.entities.get(entity) {
         return component_map.contains_key(component_kind);
@@ -4,7 +4,9 @@ use log::info;
 
 use hecs::Entity;
 
-use naia_hecs_client::{Client as NaiaClient, ClientConfig, WorldWrapper as World};
+use naia_hecs_client::{
+    Client as NaiaClient, ClientConfig, ReplicationConfig, WorldExt, WorldWrapper as World,
+};
 
 use naia_hecs_demo_shared::{protocol, Auth};
 
@@ -18,6 +20,7 @@ pub struct App {
     pub message_count: u32,
     pub entity_to_id_map: HashMap<Entity, u32>,
     pub next_id: u32,
+    pub owned_entity: Option<Entity>,
 }
 
 impl App {
@@ -37,6 +40,17 @@ impl App {
     }
 
     pub fn tick(&mut self) {
-        //info!("tick");
+        // once our Entity has been published, delegate it to the Server
+        let Some(entity) = self.owned_entity else {
+            return;
+        };
+        if self.world.replication_config(&self.client, &entity) == Some(ReplicationConfig::Public) {
+            info!("delegating owned entity");
+            self.world.configure_replication(
+                &mut self.client,
+                &entity,
+                ReplicationConfig::Delegated,
+            );
+        }
     }
 }
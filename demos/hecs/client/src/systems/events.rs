@@ -1,8 +1,9 @@
 use log::info;
 
 use naia_hecs_client::{
-    ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent,
-    InsertComponentEvent, RemoveComponentEvent, SpawnEntityEvent,
+    ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
+    EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, InsertComponentEvent,
+    RemoveComponentEvent, ReplicationConfig, SpawnEntityEvent, WorldExt,
 };
 use naia_hecs_demo_shared::{Marker, Name, Position};
 
@@ -18,6 +19,13 @@ pub fn process_events(app: &mut App) {
     // Connect Events
     for server_address in events.read::<ConnectEvent>() {
         info!("Client connected to: {}", server_address);
+
+        // spawn an Entity of our own, and publish it
+        let entity = app.world.spawn((Position::new(0, 0),));
+        app.world.enable_replication(&mut app.client, &entity);
+        app.world
+            .configure_replication(&mut app.client, &entity, ReplicationConfig::Public);
+        app.owned_entity = Some(entity);
     }
 
     // Disconnect Events
@@ -67,6 +75,25 @@ pub fn process_events(app: &mut App) {
         info!("deletion of entity: {id}");
     }
 
+    // Authority Events
+    for entity in events.read::<EntityAuthGrantedEvent>() {
+        if app.owned_entity == Some(entity) {
+            info!("authority over owned entity granted");
+        }
+    }
+    for entity in events.read::<EntityAuthDeniedEvent>() {
+        if app.owned_entity == Some(entity) {
+            info!("authority over owned entity denied");
+        }
+    }
+    for entity in events.read::<EntityAuthResetEvent>() {
+        if app.owned_entity == Some(entity) {
+            // the Server has taken authority back, ask for it again
+            info!("authority over owned entity reset, requesting it");
+            app.world.request_authority(&mut app.client, &entity);
+        }
+    }
+
     // Tick Events
     for _ in events.read::<ClientTickEvent>() {
         app.tick();
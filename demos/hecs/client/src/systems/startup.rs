@@ -27,5 +27,6 @@ pub fn app_init(
         message_count: 0,
         entity_to_id_map: HashMap::new(),
         next_id: 0,
+        owned_entity: None,
     }
 }
@@ -23,6 +23,7 @@ pub struct App {
     pub main_room_key: RoomKey,
     pub tick_count: u32,
     pub has_marker: HashSet<Entity>,
+    pub client_entities: HashSet<Entity>,
 }
 
 impl App {
@@ -1,7 +1,10 @@
 use std::{thread::sleep, time::Duration};
 
 use naia_hecs_demo_shared::Auth;
-use naia_hecs_server::{AuthEvent, ConnectEvent, DisconnectEvent, ErrorEvent, TickEvent};
+use naia_hecs_server::{
+    AuthEvent, ConnectEvent, DelegateEntityEvent, DisconnectEvent, EntityAuthGrantEvent,
+    ErrorEvent, PublishEntityEvent, TickEvent, WorldExt,
+};
 
 use crate::app::App;
 
@@ -31,7 +34,23 @@ pub fn process_events(app: &mut App) {
             app.has_user = true;
         }
         for (_user_key, user) in events.read::<DisconnectEvent>() {
-            info!("Naia Server disconnected from: {:?}", user.address);
+            info!("Naia Server disconnected from: {:?}", user.address());
+        }
+        for (_user_key, entity) in events.read::<PublishEntityEvent>() {
+            info!("Client Entity has been published");
+
+            // Add newly public Entity to the main Room
+            app.server.room_mut(&app.main_room_key).add_entity(&entity);
+            app.client_entities.insert(entity);
+        }
+        for (_user_key, entity) in events.read::<DelegateEntityEvent>() {
+            info!("Client Entity has been delegated, Server taking Authority");
+
+            app.world.take_authority(&mut app.server, &entity);
+        }
+        for (user_key, _entity) in events.read::<EntityAuthGrantEvent>() {
+            let address = app.server.user(&user_key).address();
+            info!("Client {} has been granted Authority", address);
         }
         for _ in events.read::<TickEvent>() {
             app.tick();
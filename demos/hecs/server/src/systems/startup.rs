@@ -58,5 +58,6 @@ pub fn app_init(
         main_room_key,
         tick_count: 0,
         has_marker: HashSet::new(),
+        client_entities: HashSet::new(),
     }
 }
@@ -17,6 +17,9 @@ pub fn march_and_mark(app: &mut App) {
     let mut entities_to_respawn: Vec<Entity> = Vec::new();
 
     for (entity, position) in app.world.query_mut::<&mut Position>() {
+        if app.client_entities.contains(&entity) {
+            continue;
+        }
         *position.x += 1;
 
         if *position.x == 100 {
@@ -102,14 +105,18 @@ pub fn check_scopes(app: &mut App) {
     let server = &mut app.server;
     let world = &app.world;
     for (_, user_key, entity) in server.scope_checks() {
+        if app.client_entities.contains(&entity) {
+            server.user_scope_mut(&user_key).include(&entity);
+            continue;
+        }
         if let Ok(entity_ref) = world.entity(entity) {
             if let Some(position) = entity_ref.get::<&Position>() {
                 let x = *position.x;
 
                 if (50..=200).contains(&x) {
-                    server.user_scope(&user_key).include(&entity);
+                    server.user_scope_mut(&user_key).include(&entity);
                 } else {
-                    server.user_scope(&user_key).exclude(&entity);
+                    server.user_scope_mut(&user_key).exclude(&entity);
                 }
             }
         }
@@ -18,6 +18,7 @@ pub fn protocol() -> Protocol {
         // Config
         .tick_interval(Duration::from_millis(25))
         .link_condition(LinkConditionerConfig::average_condition())
+        .enable_client_authoritative_entities()
         // Channels
         .add_default_channels()
         // Messages
@@ -236,7 +236,7 @@ impl App {
         }
 
         // Client Tick Events
-        for client_tick in events.read::<ClientTickEvent>() {
+        for (client_tick, _) in events.read::<ClientTickEvent>() {
             let Some(owned_entity) = &self.owned_entity else {
                 continue;
             };
@@ -46,7 +46,7 @@ impl App {
         let mut no_packets = false;
 
         match self.auth_receiver.receive() {
-            Ok(Some((address, payload))) => {
+            Ok(Some((address, _headers, payload))) => {
                 let auth_from_client = String::from_utf8_lossy(payload);
                 info!(
                     "Server incoming Auth <- {}: [{}]",
@@ -0,0 +1,16 @@
+#![no_main]
+
+use std::sync::OnceLock;
+
+use libfuzzer_sys::fuzz_target;
+
+use naia_shared::Protocol;
+use naia_test::fuzz_protocol;
+
+static PROTOCOL: OnceLock<Protocol> = OnceLock::new();
+
+fuzz_target!(|bytes: &[u8]| {
+    let protocol = PROTOCOL.get_or_init(fuzz_protocol);
+    // malformed packets must be rejected with an error, never a panic
+    let _ = naia_client::fuzz_targets::read_client_data_packet(protocol, bytes);
+});
@@ -4,7 +4,9 @@ use naia_shared::BandwidthMonitor as SingleBandwidthMonitor;
 
 pub struct BandwidthMonitor {
     total_monitor: SingleBandwidthMonitor,
+    socket_monitors: HashMap<usize, SingleBandwidthMonitor>,
     client_monitors: HashMap<SocketAddr, SingleBandwidthMonitor>,
+    client_breakdown_monitors: HashMap<SocketAddr, HashMap<String, SingleBandwidthMonitor>>,
     bandwidth_measure_duration: Duration,
 }
 
@@ -13,7 +15,9 @@ impl BandwidthMonitor {
         BandwidthMonitor {
             bandwidth_measure_duration,
             total_monitor: SingleBandwidthMonitor::new(bandwidth_measure_duration),
+            socket_monitors: HashMap::new(),
             client_monitors: HashMap::new(),
+            client_breakdown_monitors: HashMap::new(),
         }
     }
 
@@ -22,17 +26,35 @@ impl BandwidthMonitor {
             *address,
             SingleBandwidthMonitor::new(self.bandwidth_measure_duration),
         );
+        self.client_breakdown_monitors
+            .insert(*address, HashMap::new());
     }
 
     pub fn delete_client(&mut self, address: &SocketAddr) {
         self.client_monitors.remove(address);
+        self.client_breakdown_monitors.remove(address);
     }
 
-    pub fn record_packet(&mut self, address: &SocketAddr, bytes: usize) {
+    pub fn record_packet(&mut self, socket_index: usize, address: &SocketAddr, bytes: usize) {
         if let Some(client_monitor) = self.client_monitors.get_mut(address) {
             client_monitor.record_packet(bytes);
 
             self.total_monitor.record_packet(bytes);
+
+            self.socket_monitors
+                .entry(socket_index)
+                .or_insert_with(|| SingleBandwidthMonitor::new(self.bandwidth_measure_duration))
+                .record_packet(bytes);
+        }
+    }
+
+    // records bytes attributed to a single source (i.e. a Channel) within a packet
+    pub fn record_breakdown(&mut self, address: &SocketAddr, source: String, bytes: usize) {
+        if let Some(breakdown_monitors) = self.client_breakdown_monitors.get_mut(address) {
+            breakdown_monitors
+                .entry(source)
+                .or_insert_with(|| SingleBandwidthMonitor::new(self.bandwidth_measure_duration))
+                .record_packet(bytes);
         }
     }
 
@@ -40,10 +62,25 @@ impl BandwidthMonitor {
         self.total_monitor.bandwidth()
     }
 
+    pub fn socket_bandwidth(&mut self, socket_index: usize) -> f32 {
+        self.socket_monitors
+            .get_mut(&socket_index)
+            .map_or(0.0, |monitor| monitor.bandwidth())
+    }
+
     pub fn client_bandwidth(&mut self, address: &SocketAddr) -> f32 {
         self.client_monitors
             .get_mut(address)
             .expect("client associated with address does not exist")
             .bandwidth()
     }
+
+    pub fn client_bandwidth_breakdown(&mut self, address: &SocketAddr) -> HashMap<String, f32> {
+        self.client_breakdown_monitors
+            .get_mut(address)
+            .expect("client associated with address does not exist")
+            .iter_mut()
+            .map(|(source, monitor)| (source.clone(), monitor.bandwidth()))
+            .collect()
+    }
 }
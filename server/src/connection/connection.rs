@@ -1,11 +1,12 @@
-use std::{any::Any, hash::Hash, net::SocketAddr};
+use std::{any::Any, collections::HashMap, hash::Hash, net::SocketAddr};
 
 use log::warn;
 
 use naia_shared::{
     BaseConnection, BigMapKey, BitReader, BitWriter, ChannelKind, ChannelKinds, ConnectionConfig,
-    EntityEventMessage, EntityResponseEvent, HostType, HostWorldEvents, Instant, PacketType,
-    Protocol, Serde, SerdeErr, StandardHeader, SystemChannel, Tick, WorldMutType, WorldRefType,
+    ConnectionQuality, EntityEvent, EntityEventMessage, EntityResponseEvent, GameInstant, HostType,
+    HostWorldEvents, Instant, MessageContainer, PacketType, Protocol, Serde, SerdeErr,
+    StandardHeader, StreamChannel, StreamMessage, SystemChannel, Tick, WorldMutType, WorldRefType,
 };
 
 use crate::request::{GlobalRequestManager, GlobalResponseManager};
@@ -15,9 +16,11 @@ use crate::{
         tick_buffer_receiver::TickBufferReceiver,
     },
     events::Events,
+    server::MessageValidator,
     time_manager::TimeManager,
     user::UserKey,
     world::global_world_manager::GlobalWorldManager,
+    NaiaServerError,
 };
 
 use super::ping_manager::PingManager;
@@ -29,6 +32,9 @@ pub struct Connection<E: Copy + Eq + Hash + Send + Sync> {
     pub ping_manager: PingManager,
     tick_buffer: TickBufferReceiver,
     pub manual_disconnect: bool,
+    // when an update or auth message for each delegated Entity was last
+    // received from this User
+    entities_last_heard: HashMap<E, Instant>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
@@ -39,6 +45,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         user_key: &UserKey,
         channel_kinds: &ChannelKinds,
         global_world_manager: &GlobalWorldManager<E>,
+        join_tick: &Tick,
     ) -> Self {
         Self {
             address: *user_address,
@@ -52,8 +59,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                 global_world_manager,
             ),
             ping_manager: PingManager::new(ping_config),
-            tick_buffer: TickBufferReceiver::new(channel_kinds),
+            tick_buffer: TickBufferReceiver::new(channel_kinds, join_tick),
             manual_disconnect: false,
+            entities_last_heard: HashMap::new(),
         }
     }
 
@@ -63,6 +71,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         self.base.process_incoming_header(header, &mut []);
     }
 
+    /// Record that an update or auth message for the Entity was received
+    /// from this User
+    pub fn mark_entity_heard(&mut self, entity: &E, now: &Instant) {
+        self.entities_last_heard.insert(*entity, now.clone());
+    }
+
+    /// When an update or auth message for the Entity was last received from
+    /// this User, if ever
+    pub fn entity_last_heard(&self, entity: &E) -> Option<&Instant> {
+        self.entities_last_heard.get(entity)
+    }
+
+    pub fn forget_entity_heard(&mut self, entity: &E) {
+        self.entities_last_heard.remove(entity);
+    }
+
     /// Read packet data received from a client, storing necessary data in an internal buffer
     pub fn read_packet(
         &mut self,
@@ -99,9 +123,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         &mut self,
         protocol: &Protocol,
         now: &Instant,
+        game_time_now: &GameInstant,
         global_world_manager: &mut GlobalWorldManager<E>,
         global_request_manager: &mut GlobalRequestManager,
         global_response_manager: &mut GlobalResponseManager,
+        message_validator: Option<&MessageValidator>,
         world: &mut W,
         incoming_events: &mut Events<E>,
     ) -> Vec<EntityResponseEvent<E>> {
@@ -126,6 +152,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                     };
                     match event_message.entity.get(global_world_manager) {
                         Some(entity) => {
+                            if global_world_manager.entity_is_delegated(&entity) {
+                                self.mark_entity_heard(&entity, now);
+                            }
                             response_events.push(event_message.action.to_response_event(&entity));
                         }
                         None => {
@@ -136,8 +165,34 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                         }
                     };
                 }
+            } else if channel_kind == ChannelKind::of::<StreamChannel>() {
+                for message in messages {
+                    let Some(stream_message) =
+                        Box::<dyn Any + 'static>::downcast::<StreamMessage>(message.to_boxed_any())
+                            .ok()
+                            .map(|boxed_m| *boxed_m)
+                    else {
+                        panic!("Received unknown message over StreamChannel!");
+                    };
+                    incoming_events
+                        .push_stream_message(&self.user_key, stream_message.into_bytes());
+                }
             } else {
                 for message in messages {
+                    if message_rejected(
+                        message_validator,
+                        &self.user_key,
+                        &channel_kind,
+                        &message,
+                        incoming_events,
+                    ) {
+                        continue;
+                    }
+                    self.base.message_manager.record_latency(
+                        &channel_kind,
+                        &message,
+                        game_time_now,
+                    );
                     incoming_events.push_message(&self.user_key, &channel_kind, message);
                 }
             }
@@ -148,6 +203,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         // Requests
         for (channel_kind, requests) in requests {
             for (local_response_id, request) in requests {
+                if message_rejected(
+                    message_validator,
+                    &self.user_key,
+                    &channel_kind,
+                    &request,
+                    incoming_events,
+                ) {
+                    continue;
+                }
+                self.base
+                    .message_manager
+                    .record_latency(&channel_kind, &request, game_time_now);
                 let global_response_id = global_response_manager.create_response_id(
                     &self.user_key,
                     &channel_kind,
@@ -177,20 +244,70 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                 now,
                 remote_events,
             );
+            for world_event in &world_events {
+                let entity = match world_event {
+                    EntityEvent::InsertComponent(entity, _)
+                    | EntityEvent::RemoveComponent(entity, _)
+                    | EntityEvent::UpdateComponent(_, entity, _) => entity,
+                    _ => continue,
+                };
+                if global_world_manager.entity_is_delegated(entity) {
+                    self.mark_entity_heard(entity, now);
+                }
+            }
             response_events
                 .extend(incoming_events.receive_entity_events(&self.user_key, world_events));
+
+            for error in self
+                .base
+                .remote_world_reader
+                .take_stalled_channel_errors(now)
+            {
+                incoming_events.push_error(NaiaServerError::Wrapped(Box::new(error)));
+            }
         }
 
         return response_events;
     }
 
-    pub fn tick_buffer_messages(&mut self, tick: &Tick, messages: &mut TickBufferMessages) {
+    /// Collects the tick-buffered Messages for the given Tick, returning how
+    /// many Ticks in a row the User has now sent none for
+    pub fn tick_buffer_messages(
+        &mut self,
+        tick: &Tick,
+        message_validator: Option<&MessageValidator>,
+        messages: &mut TickBufferMessages,
+        incoming_events: &mut Events<E>,
+    ) -> u16 {
         let channel_messages = self.tick_buffer.receive_messages(tick);
         for (channel_kind, received_messages) in channel_messages {
             for message in received_messages {
+                if message_rejected(
+                    message_validator,
+                    &self.user_key,
+                    &channel_kind,
+                    &message,
+                    incoming_events,
+                ) {
+                    continue;
+                }
                 messages.push_message(&self.user_key, &channel_kind, message);
             }
         }
+        for channel_kind in self.tick_buffer.missing_channels(tick) {
+            messages.push_missing(&self.user_key, &channel_kind);
+        }
+        self.tick_buffer.consecutive_misses()
+    }
+
+    /// How many Ticks' worth of tick-buffered Messages are waiting to be read
+    pub fn tick_buffer_depth(&self) -> usize {
+        self.tick_buffer.depth()
+    }
+
+    /// Discards all tick-buffered Messages waiting to be read
+    pub fn clear_tick_buffer(&mut self) {
+        self.tick_buffer.clear();
     }
 
     // Outgoing data
@@ -212,8 +329,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             &rtt_millis,
         );
 
-        let mut any_sent = false;
-        loop {
+        let mut packets_sent = 0;
+        while self.base.may_send_packet(packets_sent) {
             if self.send_packet(
                 protocol,
                 now,
@@ -223,12 +340,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                 time_manager,
                 &mut host_world_events,
             ) {
-                any_sent = true;
+                packets_sent += 1;
             } else {
                 break;
             }
         }
-        if any_sent {
+        if packets_sent > 0 {
             self.base.mark_sent();
         }
     }
@@ -261,12 +378,55 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                 warn!("Server Error: Cannot send data packet to {}", &self.address);
             }
 
+            self.record_bandwidth_breakdown(protocol, io);
+
             return true;
         }
 
         false
     }
 
+    /// Measures this connection's quality, from packet loss, the pings and the
+    /// bytes the Io has carried for it
+    pub fn connection_quality(&mut self, io: &mut Io) -> ConnectionQuality {
+        self.base.connection_quality(
+            self.ping_manager.rtt_average,
+            self.ping_manager.jitter_average,
+            io.incoming_bytes_per_second(&self.address),
+            io.outgoing_bytes_per_second(&self.address),
+        )
+    }
+
+    /// Start tallying the bits written by each Channel and Component kind, to
+    /// be reported to the outgoing bandwidth monitor
+    pub fn enable_bandwidth_breakdown(&mut self) {
+        self.base.message_manager.enable_written_bits_tally();
+        self.base.host_world_manager.enable_written_bits_tally();
+    }
+
+    fn record_bandwidth_breakdown(&mut self, protocol: &Protocol, io: &mut Io) {
+        if let Some(channel_bits) = self.base.message_manager.take_written_channel_bits() {
+            for (channel_kind, bits) in channel_bits {
+                let channel_name = protocol.channel_kinds.kind_to_name(&channel_kind);
+                io.record_outgoing_breakdown(
+                    &self.address,
+                    format!("channel:{}", channel_name),
+                    bits,
+                );
+            }
+        }
+        if let Some(component_bits) = self.base.host_world_manager.take_written_component_bits() {
+            for (component_kind, bits) in component_bits {
+                let component_name = protocol.component_kinds.kind_to_name(&component_kind);
+                io.record_outgoing_breakdown(
+                    &self.address,
+                    format!("component:{}", component_name),
+                    bits,
+                );
+            }
+        }
+    }
+
     fn write_packet<W: WorldRefType<E>>(
         &mut self,
         protocol: &Protocol,
@@ -313,3 +473,108 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         writer
     }
 }
+
+// whether the validator, if any, rejects a Message received from the User, in
+// which case the rejection is reported instead
+fn message_rejected<E: Copy>(
+    message_validator: Option<&MessageValidator>,
+    user_key: &UserKey,
+    channel_kind: &ChannelKind,
+    message: &MessageContainer,
+    incoming_events: &mut Events<E>,
+) -> bool {
+    let Some(validator) = message_validator else {
+        return false;
+    };
+    if validator(user_key, channel_kind, message) {
+        return false;
+    }
+    incoming_events.push_message_rejected(user_key, channel_kind, message.kind());
+    true
+}
+
+#[cfg(test)]
+mod bandwidth_breakdown_tests {
+    use std::{net::SocketAddr, time::Duration};
+
+    use naia_shared::{
+        default_channels::{SequencedUnreliableChannel, UnorderedUnreliableChannel},
+        BigMapKey, BitWriter, ChannelKind, ConnectionConfig, FakeEntityConverter, Message,
+        MessageContainer, Protocol,
+    };
+
+    use crate::{
+        connection::{connection::Connection, io::Io, ping_config::PingConfig},
+        world::global_world_manager::GlobalWorldManager,
+        EntityIdRange, UserKey,
+    };
+
+    #[derive(Message)]
+    pub struct Chat {
+        pub text: String,
+    }
+
+    #[test]
+    fn channels_are_tracked_separately() {
+        let protocol = Protocol::builder()
+            .add_default_channels()
+            .add_message::<Chat>()
+            .build();
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let global_world_manager = GlobalWorldManager::<u32>::new(&EntityIdRange::default());
+        let mut connection = Connection::new(
+            &ConnectionConfig::default(),
+            &PingConfig::default(),
+            &address,
+            &UserKey::from_u64(0),
+            &protocol.channel_kinds,
+            &global_world_manager,
+            &0,
+        );
+        connection.enable_bandwidth_breakdown();
+
+        let mut io = Io::new(&Some(Duration::from_secs(1)), &None, false, false);
+        io.register_client(&address);
+
+        // send a short Message on one Channel and a long one on another
+        let mut converter = FakeEntityConverter;
+        for (channel_kind, text) in [
+            (ChannelKind::of::<UnorderedUnreliableChannel>(), "hi"),
+            (
+                ChannelKind::of::<SequencedUnreliableChannel>(),
+                "a considerably longer chat message",
+            ),
+        ] {
+            let message = MessageContainer::from_write(
+                Box::new(Chat {
+                    text: text.to_string(),
+                }),
+                &mut converter,
+            );
+            connection.base.message_manager.send_message(
+                &protocol.message_kinds,
+                &mut converter,
+                &channel_kind,
+                message,
+            );
+        }
+
+        let mut writer = BitWriter::new();
+        let mut has_written = false;
+        connection.base.message_manager.write_messages(
+            &protocol,
+            &mut converter,
+            &mut writer,
+            0,
+            &mut has_written,
+        );
+        connection.record_bandwidth_breakdown(&protocol, &mut io);
+
+        let breakdown = io.outgoing_bandwidth_breakdown(&address);
+        assert_eq!(breakdown.len(), 2);
+        let short_bandwidth = breakdown["channel:UnorderedUnreliableChannel"];
+        let long_bandwidth = breakdown["channel:SequencedUnreliableChannel"];
+        assert!(short_bandwidth > 0.0);
+        assert!(long_bandwidth > short_bandwidth);
+    }
+}
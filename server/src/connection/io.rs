@@ -1,26 +1,80 @@
-use std::{net::SocketAddr, panic, time::Duration};
+use std::{collections::HashMap, net::SocketAddr, panic, time::Duration};
 
-use naia_shared::{CompressionConfig, Decoder, Encoder, OutgoingPacket, OwnedBitReader};
+use log::warn;
+
+use naia_shared::{
+    append_checksum, is_handshake_packet, open_packet, seal_packet, verify_checksum,
+    ByteRateMonitor, CompressionConfig, CompressionStats, ConnectionQuality, Decoder, DecoderError,
+    Encoder, OutgoingPacket, OwnedBitReader, PacketCipher,
+};
 
 use super::bandwidth_monitor::BandwidthMonitor;
+#[cfg(feature = "metrics")]
+use crate::metrics::PacketTypeCounts;
 use crate::{
     error::NaiaServerError,
     transport::{PacketReceiver, PacketSender},
 };
 
+/// Observes every packet the Server sends, along with its destination address.
+/// The payload is seen as it goes out on the wire, after any compression,
+/// encryption, and checksum
+pub type OutgoingPacketTap = Box<dyn FnMut(&SocketAddr, &[u8]) + Send + Sync>;
+
+/// Observes every packet the Server receives, along with its source address.
+/// The payload is seen as it came in off the wire, before it is verified,
+/// decrypted, or decompressed, so this includes packets which are then dropped
+pub type IncomingPacketTap = Box<dyn FnMut(&SocketAddr, &[u8]) + Send + Sync>;
+
+struct IoSocket {
+    name: Option<String>,
+    packet_sender: Box<dyn PacketSender>,
+    packet_receiver: Box<dyn PacketReceiver>,
+}
+
 pub struct Io {
-    packet_sender: Option<Box<dyn PacketSender>>,
-    packet_receiver: Option<Box<dyn PacketReceiver>>,
+    sockets: Vec<IoSocket>,
+    // the Socket each connected Client's packets are sent through
+    address_sockets: HashMap<SocketAddr, usize>,
+    // the address & Socket of the last packet received, so that senders which
+    // are not yet connected are answered through the Socket they used
+    last_received: Option<(SocketAddr, usize)>,
+    // the Socket `recv_reader()` is draining, in the order they were loaded
+    receiving_socket: usize,
     outgoing_bandwidth_monitor: Option<BandwidthMonitor>,
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
+    // only kept for connected Clients, so unknown senders cannot grow these
+    outgoing_byte_rates: HashMap<SocketAddr, ByteRateMonitor>,
+    incoming_byte_rates: HashMap<SocketAddr, ByteRateMonitor>,
     outgoing_encoder: Option<Encoder>,
+    // only kept for connected Clients, & only while compressing
+    compression_stats: HashMap<SocketAddr, CompressionStats>,
+    // the last graded quality of each connection, which Adaptive compression
+    // picks its level from
+    connection_qualities: HashMap<SocketAddr, ConnectionQuality>,
     incoming_decoder: Option<Decoder>,
+    encryption_enabled: bool,
+    packet_ciphers: HashMap<SocketAddr, PacketCipher>,
+    packet_checksums: bool,
+    corrupt_packets: u64,
+    outgoing_tap: Option<OutgoingPacketTap>,
+    incoming_tap: Option<IncomingPacketTap>,
+    #[cfg(feature = "metrics")]
+    packets_sent: PacketTypeCounts,
+    #[cfg(feature = "metrics")]
+    packets_received: PacketTypeCounts,
+    #[cfg(feature = "metrics")]
+    bytes_sent: u64,
+    #[cfg(feature = "metrics")]
+    bytes_received: u64,
 }
 
 impl Io {
     pub fn new(
         bandwidth_measure_duration: &Option<Duration>,
         compression_config: &Option<CompressionConfig>,
+        encryption_enabled: bool,
+        packet_checksums: bool,
     ) -> Self {
         let outgoing_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
         let incoming_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
@@ -39,30 +93,122 @@ impl Io {
         });
 
         Io {
-            packet_sender: None,
-            packet_receiver: None,
+            sockets: Vec::new(),
+            address_sockets: HashMap::new(),
+            last_received: None,
+            receiving_socket: 0,
             outgoing_bandwidth_monitor,
             incoming_bandwidth_monitor,
+            outgoing_byte_rates: HashMap::new(),
+            incoming_byte_rates: HashMap::new(),
             outgoing_encoder,
+            compression_stats: HashMap::new(),
+            connection_qualities: HashMap::new(),
             incoming_decoder,
+            encryption_enabled,
+            packet_ciphers: HashMap::new(),
+            packet_checksums,
+            corrupt_packets: 0,
+            outgoing_tap: None,
+            incoming_tap: None,
+            #[cfg(feature = "metrics")]
+            packets_sent: PacketTypeCounts::default(),
+            #[cfg(feature = "metrics")]
+            packets_received: PacketTypeCounts::default(),
+            #[cfg(feature = "metrics")]
+            bytes_sent: 0,
+            #[cfg(feature = "metrics")]
+            bytes_received: 0,
         }
     }
 
+    /// Adds a Socket's packet sender/receiver, returning the index of the
+    /// Socket. Packets are received from every loaded Socket
     pub fn load(
         &mut self,
         packet_sender: Box<dyn PacketSender>,
         packet_receiver: Box<dyn PacketReceiver>,
-    ) {
-        if self.packet_sender.is_some() {
-            panic!("Packet sender/receiver already loaded! Cannot do this twice!");
+    ) -> usize {
+        self.sockets.push(IoSocket {
+            name: None,
+            packet_sender,
+            packet_receiver,
+        });
+        self.sockets.len() - 1
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        !self.sockets.is_empty()
+    }
+
+    pub fn sockets_count(&self) -> usize {
+        self.sockets.len()
+    }
+
+    /// Names a loaded Socket. Panics if another Socket already has the name
+    pub fn name_socket(&mut self, socket_index: usize, name: String) {
+        if self.socket_index(&name).is_some() {
+            panic!("A Socket named `{}` is already being listened on", name);
+        }
+        self.sockets[socket_index].name = Some(name);
+    }
+
+    pub fn socket_name(&self, socket_index: usize) -> Option<&str> {
+        self.sockets.get(socket_index)?.name.as_deref()
+    }
+
+    pub fn socket_index(&self, name: &str) -> Option<usize> {
+        self.sockets
+            .iter()
+            .position(|socket| socket.name.as_deref() == Some(name))
+    }
+
+    /// Sends all further packets to the address through the Socket its last
+    /// packet arrived on. Called once a Client's handshake completes
+    pub fn bind_client_socket(&mut self, address: &SocketAddr) {
+        if let Some(socket_index) = self.socket_for(address) {
+            self.address_sockets.insert(*address, socket_index);
         }
+    }
 
-        self.packet_sender = Some(packet_sender);
-        self.packet_receiver = Some(packet_receiver);
+    pub fn unbind_client_socket(&mut self, address: &SocketAddr) {
+        self.address_sockets.remove(address);
     }
 
-    pub fn is_loaded(&self) -> bool {
-        self.packet_sender.is_some()
+    /// The index of the Socket a connected Client's packets are sent through
+    pub fn client_socket(&self, address: &SocketAddr) -> Option<usize> {
+        self.address_sockets.get(address).copied()
+    }
+
+    fn socket_for(&self, address: &SocketAddr) -> Option<usize> {
+        if let Some(socket_index) = self.address_sockets.get(address) {
+            return Some(*socket_index);
+        }
+        if let Some((last_address, socket_index)) = &self.last_received {
+            if last_address == address {
+                return Some(*socket_index);
+            }
+        }
+        if self.sockets.len() == 1 {
+            return Some(0);
+        }
+        None
+    }
+
+    pub fn set_packet_cipher(&mut self, address: &SocketAddr, cipher: PacketCipher) {
+        self.packet_ciphers.insert(*address, cipher);
+    }
+
+    pub fn remove_packet_cipher(&mut self, address: &SocketAddr) {
+        self.packet_ciphers.remove(address);
+    }
+
+    pub fn set_outgoing_tap(&mut self, tap: Option<OutgoingPacketTap>) {
+        self.outgoing_tap = tap;
+    }
+
+    pub fn set_incoming_tap(&mut self, tap: Option<IncomingPacketTap>) {
+        self.incoming_tap = tap;
     }
 
     pub fn send_packet(
@@ -70,52 +216,237 @@ impl Io {
         address: &SocketAddr,
         packet: OutgoingPacket,
     ) -> Result<(), NaiaServerError> {
+        if self.sockets.is_empty() {
+            panic!("Cannot call Server.send_packet() until you call Server.listen()!");
+        }
+        let Some(socket_index) = self.socket_for(address) else {
+            return Err(NaiaServerError::SendError(*address));
+        };
+
         // get payload
         let mut payload = packet.slice();
 
+        #[cfg(feature = "metrics")]
+        self.packets_sent.record_payload(payload);
+
+        // handshake packets carry the key exchange, so are never encrypted
+        let is_handshake = self.encryption_enabled && is_handshake_packet(payload);
+
         // Compression
         if let Some(encoder) = &mut self.outgoing_encoder {
-            payload = encoder.encode(payload);
+            let input_bytes = payload.len();
+            let quality = self
+                .connection_qualities
+                .get(address)
+                .copied()
+                .unwrap_or_default();
+            payload = encoder.encode_for(payload, &quality);
+            if let Some(stats) = self.compression_stats.get_mut(address) {
+                stats.record(input_bytes, payload.len());
+            }
+        }
+
+        // Encryption
+        let sealed;
+        if self.encryption_enabled {
+            let cipher_opt = if is_handshake {
+                None
+            } else {
+                self.packet_ciphers.get_mut(address)
+            };
+            sealed = seal_packet(cipher_opt, payload);
+            payload = &sealed;
+        }
+
+        // Checksum
+        let checksummed;
+        if self.packet_checksums {
+            checksummed = append_checksum(payload);
+            payload = &checksummed;
         }
 
         // Bandwidth monitoring
         if let Some(monitor) = &mut self.outgoing_bandwidth_monitor {
-            monitor.record_packet(address, payload.len());
+            monitor.record_packet(socket_index, address, payload.len());
+        }
+        if let Some(monitor) = self.outgoing_byte_rates.get_mut(address) {
+            monitor.record_packet(payload.len());
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            self.bytes_sent += payload.len() as u64;
         }
 
-        self.packet_sender
-            .as_ref()
-            .expect("Cannot call Server.send_packet() until you call Server.listen()!")
+        if let Some(tap) = &mut self.outgoing_tap {
+            tap(address, payload);
+        }
+
+        self.sockets[socket_index]
+            .packet_sender
             .send(address, payload)
             .map_err(|_| NaiaServerError::SendError(*address))
     }
 
     pub fn recv_reader(&mut self) -> Result<Option<(SocketAddr, OwnedBitReader)>, NaiaServerError> {
-        let receive_result = self
-            .packet_receiver
-            .as_mut()
-            .expect("Cannot call Server.receive_packet() until you call Server.listen()!")
-            .receive();
-
-        match receive_result {
-            Ok(Some((address, mut payload))) => {
-                // Bandwidth monitoring
-                if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
-                    monitor.record_packet(&address, payload.len());
-                }
+        if self.sockets.is_empty() {
+            panic!("Cannot call Server.receive_packet() until you call Server.listen()!");
+        }
+        loop {
+            let socket_index = self.receiving_socket;
+            let Some(socket) = self.sockets.get_mut(socket_index) else {
+                // every Socket has been drained
+                self.receiving_socket = 0;
+                return Ok(None);
+            };
 
-                // Decompression
-                if let Some(decoder) = &mut self.incoming_decoder {
-                    payload = decoder.decode(payload);
-                }
+            match socket.packet_receiver.receive() {
+                Ok(Some((address, mut payload))) => {
+                    self.last_received = Some((address, socket_index));
+
+                    if let Some(tap) = &mut self.incoming_tap {
+                        tap(&address, payload);
+                    }
+
+                    // Bandwidth monitoring
+                    if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
+                        monitor.record_packet(socket_index, &address, payload.len());
+                    }
+                    if let Some(monitor) = self.incoming_byte_rates.get_mut(&address) {
+                        monitor.record_packet(payload.len());
+                    }
+
+                    #[cfg(feature = "metrics")]
+                    {
+                        self.bytes_received += payload.len() as u64;
+                    }
+
+                    // Checksum, verified before anything else reads the packet
+                    if self.packet_checksums {
+                        match verify_checksum(payload) {
+                            Ok(body) => payload = body,
+                            Err(error) => {
+                                self.corrupt_packets += 1;
+                                warn!("Dropping packet from {}: {}", address, error);
+                                continue;
+                            }
+                        }
+                    }
 
-                Ok(Some((address, OwnedBitReader::new(payload))))
+                    // Decryption
+                    let opened;
+                    let mut was_encrypted = false;
+                    if self.encryption_enabled {
+                        match open_packet(self.packet_ciphers.get_mut(&address), payload) {
+                            Ok((plaintext, encrypted)) => {
+                                opened = plaintext;
+                                payload = &opened;
+                                was_encrypted = encrypted;
+                            }
+                            Err(error) => {
+                                warn!("Dropping packet from {}: {}", address, error);
+                                continue;
+                            }
+                        }
+                    }
+
+                    // Decompression
+                    if let Some(decoder) = &mut self.incoming_decoder {
+                        match decoder.decode(payload) {
+                            Ok(decoded) => payload = decoded,
+                            Err(error) => {
+                                warn!("Dropping packet from {}: {}", address, error);
+                                continue;
+                            }
+                        }
+                    }
+
+                    // once keys are established, only handshake packets may arrive in plaintext
+                    if self.encryption_enabled
+                        && !was_encrypted
+                        && self.packet_ciphers.contains_key(&address)
+                        && !is_handshake_packet(payload)
+                    {
+                        warn!(
+                            "Dropping packet from {}: {}",
+                            address,
+                            DecoderError::Unencrypted
+                        );
+                        continue;
+                    }
+
+                    #[cfg(feature = "metrics")]
+                    self.packets_received.record_payload(payload);
+
+                    return Ok(Some((address, OwnedBitReader::new(payload))));
+                }
+                Ok(None) => {
+                    self.receiving_socket += 1;
+                }
+                Err(_) => {
+                    // move on, so that one failing Socket does not hold up the others
+                    self.receiving_socket += 1;
+                    return Err(NaiaServerError::RecvError);
+                }
             }
-            Ok(None) => Ok(None),
-            Err(_) => Err(NaiaServerError::RecvError),
         }
     }
 
+    /// The number of received packets dropped for failing their checksum
+    pub fn corrupt_packets(&self) -> u64 {
+        self.corrupt_packets
+    }
+
+    pub fn track_byte_rates(&mut self, address: &SocketAddr) {
+        self.outgoing_byte_rates
+            .insert(*address, ByteRateMonitor::new());
+        self.incoming_byte_rates
+            .insert(*address, ByteRateMonitor::new());
+    }
+
+    pub fn untrack_byte_rates(&mut self, address: &SocketAddr) {
+        self.outgoing_byte_rates.remove(address);
+        self.incoming_byte_rates.remove(address);
+    }
+
+    pub fn track_compression_stats(&mut self, address: &SocketAddr) {
+        if self.outgoing_encoder.is_some() {
+            self.compression_stats
+                .insert(*address, CompressionStats::new());
+        }
+    }
+
+    pub fn untrack_compression_stats(&mut self, address: &SocketAddr) {
+        self.compression_stats.remove(address);
+        self.connection_qualities.remove(address);
+    }
+
+    /// Records the latest quality of a Client's connection, to pick the level
+    /// its packets are compressed at
+    pub fn set_connection_quality(&mut self, address: &SocketAddr, quality: ConnectionQuality) {
+        if self.compression_stats.contains_key(address) {
+            self.connection_qualities.insert(*address, quality);
+        }
+    }
+
+    /// Totals of the bytes compressed for the given Client, if compression is
+    /// enabled & it is connected
+    pub fn compression_stats(&self, address: &SocketAddr) -> Option<CompressionStats> {
+        self.compression_stats.get(address).copied()
+    }
+
+    pub fn outgoing_bytes_per_second(&mut self, address: &SocketAddr) -> f32 {
+        self.outgoing_byte_rates
+            .get_mut(address)
+            .map_or(0.0, |monitor| monitor.bytes_per_second())
+    }
+
+    pub fn incoming_bytes_per_second(&mut self, address: &SocketAddr) -> f32 {
+        self.incoming_byte_rates
+            .get_mut(address)
+            .map_or(0.0, |monitor| monitor.bytes_per_second())
+    }
+
     pub fn bandwidth_monitor_enabled(&self) -> bool {
         self.outgoing_bandwidth_monitor.is_some() && self.incoming_bandwidth_monitor.is_some()
     }
@@ -166,6 +497,37 @@ impl Io {
             .client_bandwidth(address);
     }
 
+    pub fn record_outgoing_breakdown(&mut self, address: &SocketAddr, source: String, bits: u32) {
+        self.outgoing_bandwidth_monitor
+            .as_mut()
+            .expect("Need to call `enable_bandwidth_monitor()` on Io before calling this")
+            .record_breakdown(address, source, bits.div_ceil(8) as usize);
+    }
+
+    pub fn outgoing_bandwidth_breakdown(&mut self, address: &SocketAddr) -> HashMap<String, f32> {
+        return self
+            .outgoing_bandwidth_monitor
+            .as_mut()
+            .expect("Need to call `enable_bandwidth_monitor()` on Io before calling this")
+            .client_bandwidth_breakdown(address);
+    }
+
+    pub fn outgoing_bandwidth_through_socket(&mut self, socket_index: usize) -> f32 {
+        return self
+            .outgoing_bandwidth_monitor
+            .as_mut()
+            .expect("Need to call `enable_bandwidth_monitor()` on Io before calling this")
+            .socket_bandwidth(socket_index);
+    }
+
+    pub fn incoming_bandwidth_through_socket(&mut self, socket_index: usize) -> f32 {
+        return self
+            .incoming_bandwidth_monitor
+            .as_mut()
+            .expect("Need to call `enable_bandwidth_monitor()` on Io before calling this")
+            .socket_bandwidth(socket_index);
+    }
+
     pub fn incoming_bandwidth_from_client(&mut self, address: &SocketAddr) -> f32 {
         return self
             .incoming_bandwidth_monitor
@@ -173,4 +535,24 @@ impl Io {
             .expect("Need to call `enable_bandwidth_monitor()` on Io before calling this")
             .client_bandwidth(address);
     }
+
+    #[cfg(feature = "metrics")]
+    pub fn packets_sent(&self) -> PacketTypeCounts {
+        self.packets_sent
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn packets_received(&self) -> PacketTypeCounts {
+        self.packets_received
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
 }
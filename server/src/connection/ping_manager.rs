@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use naia_shared::{BitReader, BitWriter, PingIndex, PingStore, Serde, Timer};
 
 use crate::{connection::ping_config::PingConfig, time_manager::TimeManager};
@@ -29,6 +31,11 @@ impl PingManager {
         self.ping_timer.ringing()
     }
 
+    /// Sets the interval between pings, in place of the PingConfig's
+    pub fn set_ping_interval(&mut self, ping_interval: Duration) {
+        self.ping_timer.set_duration(ping_interval);
+    }
+
     /// Get an outgoing ping payload
     pub fn write_ping(&mut self, writer: &mut BitWriter, time_manager: &TimeManager) {
         self.ping_timer.reset();
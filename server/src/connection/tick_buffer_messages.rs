@@ -6,6 +6,7 @@ use crate::{events, UserKey};
 
 pub struct TickBufferMessages {
     messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
+    missing: HashMap<ChannelKind, Vec<UserKey>>,
     empty: bool,
 }
 
@@ -13,6 +14,7 @@ impl TickBufferMessages {
     pub fn new() -> Self {
         Self {
             messages: HashMap::new(),
+            missing: HashMap::new(),
             empty: true,
         }
     }
@@ -27,7 +29,21 @@ impl TickBufferMessages {
         self.empty = false;
     }
 
+    pub(crate) fn push_missing(&mut self, user_key: &UserKey, channel_kind: &ChannelKind) {
+        self.missing
+            .entry(*channel_kind)
+            .or_default()
+            .push(*user_key);
+    }
+
     pub fn read<C: Channel, M: Message>(&mut self) -> Vec<(UserKey, M)> {
         return events::read_channel_messages::<C, M>(&mut self.messages);
     }
+
+    /// Returns the connected Users which sent no Messages on the given
+    /// tick-buffered Channel for this Tick. Users are not reported for Ticks
+    /// before the one they connected on
+    pub fn missing_for_tick(&self, channel_kind: &ChannelKind) -> Vec<UserKey> {
+        self.missing.get(channel_kind).cloned().unwrap_or_default()
+    }
 }
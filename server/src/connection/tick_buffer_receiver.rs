@@ -1,19 +1,26 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
 use naia_shared::{
     BitReader, ChannelKind, ChannelKinds, ChannelMode, EntityAndGlobalEntityConverter,
     EntityAndLocalEntityConverter, EntityConverter, MessageContainer, Protocol, Serde, SerdeErr,
-    Tick,
+    Tick, TickExt,
 };
 
 use crate::connection::tick_buffer_receiver_channel::TickBufferReceiverChannel;
 
 pub struct TickBufferReceiver {
     channel_receivers: HashMap<ChannelKind, TickBufferReceiverChannel>,
+    // the Server Tick the User connected on, no input is expected before it
+    join_tick: Tick,
+    // how many Ticks in a row no tick-buffered Message was received for
+    consecutive_misses: u16,
 }
 
 impl TickBufferReceiver {
-    pub fn new(channel_kinds: &ChannelKinds) -> Self {
+    pub fn new(channel_kinds: &ChannelKinds, join_tick: &Tick) -> Self {
         // initialize receivers
         let mut channel_receivers = HashMap::new();
         for (channel_kind, channel_settings) in channel_kinds.channels() {
@@ -25,7 +32,11 @@ impl TickBufferReceiver {
             }
         }
 
-        Self { channel_receivers }
+        Self {
+            channel_receivers,
+            join_tick: *join_tick,
+            consecutive_misses: 0,
+        }
     }
 
     // Incoming Messages
@@ -50,8 +61,10 @@ impl TickBufferReceiver {
             // read channel index
             let channel_kind = ChannelKind::de(&protocol.channel_kinds, reader)?;
 
-            // continue read inside channel
-            let channel = self.channel_receivers.get_mut(&channel_kind).unwrap();
+            // continue read inside channel, which must be tick-buffered
+            let Some(channel) = self.channel_receivers.get_mut(&channel_kind) else {
+                return Err(SerdeErr);
+            };
             channel.read_messages(
                 &converter,
                 &protocol.message_kinds,
@@ -70,10 +83,105 @@ impl TickBufferReceiver {
         host_tick: &Tick,
     ) -> Vec<(ChannelKind, Vec<MessageContainer>)> {
         let mut output = Vec::new();
+        let mut received_any = false;
         for (channel_kind, channel) in &mut self.channel_receivers {
             let messages = channel.receive_messages(host_tick);
+            received_any |= !messages.is_empty();
             output.push((*channel_kind, messages));
         }
+
+        if received_any || !self.expects_input(host_tick) {
+            self.consecutive_misses = 0;
+        } else {
+            self.consecutive_misses = self.consecutive_misses.saturating_add(1);
+        }
+
         output
     }
+
+    /// The tick-buffered Channels which received no Messages for the given
+    /// [`Tick`], once it has been read with `receive_messages()`
+    pub fn missing_channels(&self, host_tick: &Tick) -> Vec<ChannelKind> {
+        if !self.expects_input(host_tick) {
+            return Vec::new();
+        }
+        self.channel_receivers
+            .iter()
+            .filter(|(_, channel)| !channel.received_for_tick(host_tick))
+            .map(|(channel_kind, _)| *channel_kind)
+            .collect()
+    }
+
+    /// How many Ticks in a row, up to the last one read, the User sent no
+    /// tick-buffered Messages for on any Channel
+    pub fn consecutive_misses(&self) -> u16 {
+        self.consecutive_misses
+    }
+
+    /// How many distinct Ticks have Messages waiting in the buffer, on any
+    /// Channel, including Ticks which have already passed but not been read
+    pub fn depth(&self) -> usize {
+        self.channel_receivers
+            .values()
+            .flat_map(|channel| channel.buffered_ticks())
+            .collect::<HashSet<Tick>>()
+            .len()
+    }
+
+    /// Discards every buffered Message, on every Channel
+    pub fn clear(&mut self) {
+        for channel in self.channel_receivers.values_mut() {
+            channel.clear();
+        }
+    }
+
+    fn expects_input(&self, host_tick: &Tick) -> bool {
+        !self.channel_receivers.is_empty() && !host_tick.is_before(self.join_tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::{
+        Channel, ChannelDirection, ChannelMode, ChannelSettings, Protocol, TickBufferSettings,
+    };
+
+    use super::TickBufferReceiver;
+
+    #[derive(Channel)]
+    pub struct InputChannel;
+
+    fn receiver(join_tick: u16) -> TickBufferReceiver {
+        let protocol = Protocol::builder()
+            .add_channel_with_settings::<InputChannel>(ChannelSettings::new(
+                ChannelMode::TickBuffered(TickBufferSettings::default()),
+                ChannelDirection::ClientToServer,
+            ))
+            .build();
+        TickBufferReceiver::new(&protocol.channel_kinds, &join_tick)
+    }
+
+    #[test]
+    fn ticks_before_join_are_not_missing() {
+        let mut receiver = receiver(100);
+
+        receiver.receive_messages(&99);
+        assert!(receiver.missing_channels(&99).is_empty());
+        assert_eq!(receiver.consecutive_misses(), 0);
+
+        receiver.receive_messages(&100);
+        assert_eq!(receiver.missing_channels(&100).len(), 1);
+        assert_eq!(receiver.consecutive_misses(), 1);
+    }
+
+    #[test]
+    fn misses_accumulate_across_wrapping_ticks() {
+        let mut receiver = receiver(u16::MAX - 1);
+
+        for tick in [u16::MAX - 1, u16::MAX, 0, 1] {
+            receiver.receive_messages(&tick);
+            assert_eq!(receiver.missing_channels(&tick).len(), 1);
+        }
+        assert_eq!(receiver.consecutive_misses(), 4);
+    }
 }
@@ -2,7 +2,7 @@ use std::collections::{HashMap, VecDeque};
 
 use naia_shared::{
     sequence_greater_than, BitReader, LocalEntityAndGlobalEntityConverter, MessageContainer,
-    MessageKinds, Serde, SerdeErr, ShortMessageIndex, Tick, TickBufferSettings,
+    MessageKinds, OverflowPolicy, Serde, SerdeErr, ShortMessageIndex, Tick, TickBufferSettings,
     UnsignedVariableInteger,
 };
 
@@ -10,18 +10,40 @@ use naia_shared::{
 /// client tick.
 pub struct TickBufferReceiverChannel {
     incoming_messages: IncomingMessages,
+    last_received_tick: Option<Tick>,
 }
 
 impl TickBufferReceiverChannel {
-    pub fn new(_settings: TickBufferSettings) -> Self {
+    pub fn new(settings: TickBufferSettings) -> Self {
         Self {
-            incoming_messages: IncomingMessages::new(),
+            incoming_messages: IncomingMessages::new(&settings),
+            last_received_tick: None,
         }
     }
 
     /// Read the stored buffer-data corresponding to the given [`Tick`]
     pub fn receive_messages(&mut self, host_tick: &Tick) -> Vec<MessageContainer> {
-        self.incoming_messages.collect(host_tick)
+        let messages = self.incoming_messages.collect(host_tick);
+        if !messages.is_empty() {
+            self.last_received_tick = Some(*host_tick);
+        }
+        messages
+    }
+
+    /// Whether any Messages were received for the given [`Tick`], once it has
+    /// been read with `receive_messages()`
+    pub fn received_for_tick(&self, host_tick: &Tick) -> bool {
+        self.last_received_tick == Some(*host_tick)
+    }
+
+    /// The Ticks which currently have Messages waiting in the buffer
+    pub fn buffered_ticks(&self) -> impl Iterator<Item = Tick> + '_ {
+        self.incoming_messages.buffer.iter().map(|(tick, _)| *tick)
+    }
+
+    /// Discards every Message waiting in the buffer
+    pub fn clear(&mut self) {
+        self.incoming_messages.buffer.clear();
     }
 
     /// Given incoming packet data, read transmitted Messages and store
@@ -76,7 +98,7 @@ impl TickBufferReceiverChannel {
         for _ in 0..message_count {
             // read message id diff, add to last read id
             let id_diff = UnsignedVariableInteger::<2>::de(reader)?.get() as ShortMessageIndex;
-            let message_index: ShortMessageIndex = last_read_message_index + id_diff;
+            let message_index: ShortMessageIndex = last_read_message_index.wrapping_add(id_diff);
             last_read_message_index = message_index;
 
             // read payload
@@ -102,12 +124,16 @@ struct IncomingMessages {
     /// Buffer containing messages from the client, along with the corresponding tick
     /// We do not store anything for empty ticks
     buffer: VecDeque<(Tick, HashMap<ShortMessageIndex, MessageContainer>)>,
+    max_buffered_ticks: usize,
+    overflow_policy: OverflowPolicy,
 }
 
 impl IncomingMessages {
-    pub fn new() -> Self {
+    pub fn new(settings: &TickBufferSettings) -> Self {
         Self {
             buffer: VecDeque::new(),
+            max_buffered_ticks: settings.max_buffered_ticks,
+            overflow_policy: settings.overflow_policy,
         }
     }
 
@@ -120,19 +146,12 @@ impl IncomingMessages {
         message_index: ShortMessageIndex, // this is used to de-dupe messages
         new_message: MessageContainer,
     ) -> bool {
-        // TODO:
-        //  * add unit test?
-        //  * should there be a maximum buffer size?
-
         if sequence_greater_than(*message_tick, *host_tick) {
             let mut index = self.buffer.len();
 
             //in the case of empty vec
             if index == 0 {
-                let mut map = HashMap::new();
-                map.insert(message_index, new_message);
-                self.buffer.push_back((*message_tick, map));
-                return true;
+                return self.insert_tick(0, message_tick, message_index, new_message);
             }
 
             let mut insert = false;
@@ -162,18 +181,12 @@ impl IncomingMessages {
 
                 if insert {
                     // found correct position to insert node
-                    let mut new_messages = HashMap::new();
-                    new_messages.insert(message_index, new_message);
-                    self.buffer.insert(index + 1, (*message_tick, new_messages));
-                    return true;
+                    return self.insert_tick(index + 1, message_tick, message_index, new_message);
                 }
 
                 if index == 0 {
                     //traversed the whole vec, push front
-                    let mut new_messages = HashMap::new();
-                    new_messages.insert(message_index, new_message);
-                    self.buffer.push_front((*message_tick, new_messages));
-                    return true;
+                    return self.insert_tick(0, message_tick, message_index, new_message);
                 }
             }
         } else {
@@ -182,6 +195,45 @@ impl IncomingMessages {
         }
     }
 
+    /// Adds an entry for a Tick not yet in the buffer at the given position,
+    /// applying the overflow policy if the buffer already holds
+    /// `max_buffered_ticks` Ticks
+    fn insert_tick(
+        &mut self,
+        mut index: usize,
+        message_tick: &Tick,
+        message_index: ShortMessageIndex,
+        new_message: MessageContainer,
+    ) -> bool {
+        if self.buffer.len() >= self.max_buffered_ticks {
+            match self.overflow_policy {
+                OverflowPolicy::DropOldest => {
+                    if index == 0 {
+                        // the incoming Tick is the oldest
+                        return false;
+                    }
+                    self.buffer.pop_front();
+                    index -= 1;
+                }
+                OverflowPolicy::DropNewest => {
+                    if index == self.buffer.len() {
+                        // the incoming Tick is the newest
+                        return false;
+                    }
+                    self.buffer.pop_back();
+                }
+                OverflowPolicy::Reject => {
+                    return false;
+                }
+            }
+        }
+
+        let mut new_messages = HashMap::new();
+        new_messages.insert(message_index, new_message);
+        self.buffer.insert(index, (*message_tick, new_messages));
+        true
+    }
+
     /// Delete from the buffer all data that is older than the provided [`Tick`]
     fn prune_outdated_commands(&mut self, host_tick: &Tick) {
         loop {
@@ -222,3 +274,79 @@ impl IncomingMessages {
         output
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::{Message, MessageContainer, OverflowPolicy, Tick, TickBufferSettings};
+
+    use super::IncomingMessages;
+
+    #[derive(Message)]
+    pub struct Input {
+        pub value: u8,
+    }
+
+    fn incoming(overflow_policy: OverflowPolicy) -> IncomingMessages {
+        IncomingMessages::new(&TickBufferSettings {
+            max_buffered_ticks: 4,
+            overflow_policy,
+            ..TickBufferSettings::default()
+        })
+    }
+
+    fn insert(incoming: &mut IncomingMessages, message_tick: Tick) -> bool {
+        let message = MessageContainer::from_read(Box::new(Input { value: 1 }));
+        incoming.insert(&0, &message_tick, 0, message)
+    }
+
+    fn buffered_ticks(incoming: &IncomingMessages) -> Vec<Tick> {
+        incoming.buffer.iter().map(|(tick, _)| *tick).collect()
+    }
+
+    // buffers ticks 10..=13, then floods ticks far in the future
+    fn flood(incoming: &mut IncomingMessages) -> Vec<bool> {
+        for tick in 10..14 {
+            assert!(insert(incoming, tick));
+        }
+        (100..110).map(|tick| insert(incoming, tick)).collect()
+    }
+
+    #[test]
+    fn drop_newest_keeps_earliest_ticks() {
+        let mut incoming = incoming(OverflowPolicy::DropNewest);
+        assert!(flood(&mut incoming).iter().all(|inserted| !inserted));
+        assert_eq!(buffered_ticks(&incoming), vec![10, 11, 12, 13]);
+
+        // an earlier Tick still displaces the latest one
+        assert!(insert(&mut incoming, 5));
+        assert_eq!(buffered_ticks(&incoming), vec![5, 10, 11, 12]);
+    }
+
+    #[test]
+    fn drop_oldest_keeps_latest_ticks() {
+        let mut incoming = incoming(OverflowPolicy::DropOldest);
+        assert!(flood(&mut incoming).iter().all(|inserted| *inserted));
+        assert_eq!(buffered_ticks(&incoming), vec![106, 107, 108, 109]);
+
+        // a Tick earlier than everything buffered is the one dropped
+        assert!(!insert(&mut incoming, 5));
+        assert_eq!(buffered_ticks(&incoming), vec![106, 107, 108, 109]);
+    }
+
+    #[test]
+    fn reject_leaves_buffer_untouched() {
+        let mut incoming = incoming(OverflowPolicy::Reject);
+        assert!(flood(&mut incoming).iter().all(|inserted| !inserted));
+        assert!(!insert(&mut incoming, 5));
+        assert_eq!(buffered_ticks(&incoming), vec![10, 11, 12, 13]);
+
+        // more Messages for an already buffered Tick are still accepted
+        let message = MessageContainer::from_read(Box::new(Input { value: 2 }));
+        assert!(incoming.insert(&0, &12, 1, message));
+
+        // & once Ticks are read, there is room again
+        assert_eq!(incoming.collect(&10).len(), 1);
+        assert!(insert(&mut incoming, 100));
+        assert_eq!(buffered_ticks(&incoming), vec![11, 12, 13, 100]);
+    }
+}
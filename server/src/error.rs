@@ -6,6 +6,8 @@ pub enum NaiaServerError {
     Wrapped(Box<dyn Error>),
     SendError(SocketAddr),
     RecvError,
+    EntityDoesNotExist,
+    RoomDoesNotExist,
 }
 
 impl NaiaServerError {
@@ -25,6 +27,12 @@ impl fmt::Display for NaiaServerError {
             NaiaServerError::RecvError => {
                 write!(f, "Naia Server Error: RecvError")
             }
+            NaiaServerError::EntityDoesNotExist => {
+                write!(f, "Naia Server Error: No Entity exists for given Key!")
+            }
+            NaiaServerError::RoomDoesNotExist => {
+                write!(f, "Naia Server Error: No Room exists for given Key!")
+            }
         }
     }
 }
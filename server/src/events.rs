@@ -3,31 +3,46 @@ use std::{any::Any, collections::HashMap, marker::PhantomData, mem, vec::IntoIte
 use log::warn;
 
 use naia_shared::{
-    Channel, ChannelKind, ComponentKind, EntityEvent, EntityResponseEvent, GlobalResponseId,
-    Message, MessageContainer, MessageKind, Replicate, Request, ResponseSendKey, Tick,
+    Channel, ChannelKind, ComponentKind, ConnectionParams, ConnectionQualityLevel, EntityEvent,
+    EntityResponseEvent, GameInstant, GlobalRequestId, GlobalResponseId, Message, MessageContainer,
+    MessageKind, MessageReceiptKey, Replicate, Request, ResponseReceiveKey, ResponseSendKey,
+    ScopeBacklog, Tick,
 };
 
-use super::user::{User, UserKey};
+use super::{
+    room::{RoomCleanupReason, RoomKey},
+    user::{User, UserKey},
+};
 use crate::NaiaServerError;
 
 pub struct Events<E: Copy> {
     connections: Vec<UserKey>,
+    connection_params: Vec<(UserKey, ConnectionParams)>,
     disconnections: Vec<(UserKey, User)>,
     ticks: Vec<Tick>,
     errors: Vec<NaiaServerError>,
+    connection_quality_changes: Vec<(UserKey, ConnectionQualityLevel)>,
+    scope_backlogs: Vec<(UserKey, ScopeBacklog)>,
+    input_gaps: Vec<(UserKey, Tick, u16)>,
+    delivered_messages: Vec<(UserKey, MessageReceiptKey)>,
+    rejected_messages: Vec<(UserKey, ChannelKind, MessageKind)>,
     auths: HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>,
+    heartbeat_payloads: HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>,
     messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
     requests: HashMap<
         ChannelKind,
         HashMap<MessageKind, Vec<(UserKey, GlobalResponseId, MessageContainer)>>,
     >,
+    request_timeouts: HashMap<MessageKind, Vec<(UserKey, GlobalRequestId)>>,
     spawns: Vec<(UserKey, E)>,
     despawns: Vec<(UserKey, E)>,
     publishes: Vec<(UserKey, E)>,
     unpublishes: Vec<(UserKey, E)>,
     delegates: Vec<(UserKey, E)>,
     auth_grants: Vec<(UserKey, E)>,
-    auth_resets: Vec<E>,
+    auth_resets: Vec<(E, EntityAuthResetReason)>,
+    room_destroys: Vec<(RoomKey, RoomCleanupReason)>,
+    stream_messages: Vec<(UserKey, Vec<u8>)>,
     inserts: HashMap<ComponentKind, Vec<(UserKey, E)>>,
     removes: HashMap<ComponentKind, Vec<(UserKey, E, Box<dyn Replicate>)>>,
     updates: HashMap<ComponentKind, Vec<(UserKey, E)>>,
@@ -38,12 +53,20 @@ impl<E: Copy> Events<E> {
     pub(crate) fn new() -> Self {
         Self {
             connections: Vec::new(),
+            connection_params: Vec::new(),
             disconnections: Vec::new(),
             ticks: Vec::new(),
             errors: Vec::new(),
+            connection_quality_changes: Vec::new(),
+            scope_backlogs: Vec::new(),
+            input_gaps: Vec::new(),
+            delivered_messages: Vec::new(),
+            rejected_messages: Vec::new(),
             auths: HashMap::new(),
+            heartbeat_payloads: HashMap::new(),
             messages: HashMap::new(),
             requests: HashMap::new(),
+            request_timeouts: HashMap::new(),
             spawns: Vec::new(),
             despawns: Vec::new(),
             publishes: Vec::new(),
@@ -51,6 +74,8 @@ impl<E: Copy> Events<E> {
             delegates: Vec::new(),
             auth_grants: Vec::new(),
             auth_resets: Vec::new(),
+            room_destroys: Vec::new(),
+            stream_messages: Vec::new(),
             inserts: HashMap::new(),
             removes: HashMap::new(),
             updates: HashMap::new(),
@@ -95,6 +120,16 @@ impl<E: Copy> Events<E> {
         mem::take(&mut self.requests)
     }
 
+    // These methods are exposed for adapter crates ... prefer using Events.read::<SomeEvent>() instead.
+    pub fn has_request_timeouts(&self) -> bool {
+        !self.request_timeouts.is_empty()
+    }
+    pub fn take_request_timeouts(
+        &mut self,
+    ) -> HashMap<MessageKind, Vec<(UserKey, GlobalRequestId)>> {
+        mem::take(&mut self.request_timeouts)
+    }
+
     // This method is exposed for adapter crates ... prefer using Events.read::<SomeEvent>() instead.
     pub fn has_auths(&self) -> bool {
         !self.auths.is_empty()
@@ -143,8 +178,9 @@ impl<E: Copy> Events<E> {
 
     // Crate-public
 
-    pub(crate) fn push_connection(&mut self, user_key: &UserKey) {
+    pub(crate) fn push_connection(&mut self, user_key: &UserKey, params: ConnectionParams) {
         self.connections.push(*user_key);
+        self.connection_params.push((*user_key, params));
         self.empty = false;
     }
 
@@ -153,6 +189,50 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_connection_quality_change(
+        &mut self,
+        user_key: &UserKey,
+        level: ConnectionQualityLevel,
+    ) {
+        self.connection_quality_changes.push((*user_key, level));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_scope_backlog(&mut self, user_key: &UserKey, backlog: ScopeBacklog) {
+        self.scope_backlogs.push((*user_key, backlog));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_input_gap(
+        &mut self,
+        user_key: &UserKey,
+        tick: &Tick,
+        consecutive_misses: u16,
+    ) {
+        self.input_gaps.push((*user_key, *tick, consecutive_misses));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_message_delivered(
+        &mut self,
+        user_key: &UserKey,
+        receipt_key: MessageReceiptKey,
+    ) {
+        self.delivered_messages.push((*user_key, receipt_key));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_message_rejected(
+        &mut self,
+        user_key: &UserKey,
+        channel_kind: &ChannelKind,
+        message_kind: MessageKind,
+    ) {
+        self.rejected_messages
+            .push((*user_key, *channel_kind, message_kind));
+        self.empty = false;
+    }
+
     pub(crate) fn push_auth(&mut self, user_key: &UserKey, auth_message: MessageContainer) {
         let message_type_id = auth_message.kind();
         if !self.auths.contains_key(&message_type_id) {
@@ -163,6 +243,14 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_heartbeat_payload(&mut self, user_key: &UserKey, payload: MessageContainer) {
+        self.heartbeat_payloads
+            .entry(payload.kind())
+            .or_default()
+            .push((*user_key, payload));
+        self.empty = false;
+    }
+
     pub(crate) fn push_message(
         &mut self,
         user_key: &UserKey,
@@ -194,6 +282,35 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn take_requests_of_kind(
+        &mut self,
+        channel_kind: &ChannelKind,
+        message_kind: &MessageKind,
+    ) -> Vec<(UserKey, GlobalResponseId, MessageContainer)> {
+        self.requests
+            .get_mut(channel_kind)
+            .and_then(|channel_map| channel_map.remove(message_kind))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn push_request_timeout(
+        &mut self,
+        user_key: &UserKey,
+        request_kind: &MessageKind,
+        request_id: GlobalRequestId,
+    ) {
+        self.request_timeouts
+            .entry(*request_kind)
+            .or_default()
+            .push((*user_key, request_id));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_stream_message(&mut self, user_key: &UserKey, bytes: Vec<u8>) {
+        self.stream_messages.push((*user_key, bytes));
+        self.empty = false;
+    }
+
     pub(crate) fn push_tick(&mut self, tick: Tick) {
         self.ticks.push(tick);
         self.empty = false;
@@ -234,8 +351,13 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
-    pub(crate) fn push_auth_reset(&mut self, entity: &E) {
-        self.auth_resets.push(*entity);
+    pub(crate) fn push_auth_reset(&mut self, entity: &E, reason: EntityAuthResetReason) {
+        self.auth_resets.push((*entity, reason));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_room_destroyed(&mut self, room_key: &RoomKey, reason: RoomCleanupReason) {
+        self.room_destroys.push((*room_key, reason));
         self.empty = false;
     }
 
@@ -362,6 +484,23 @@ impl<E: Copy> Event<E> for ConnectEvent {
     }
 }
 
+// ConnectionReadyEvent
+/// Fired alongside ConnectEvent, with the parameters the connection was
+/// established with
+pub struct ConnectionReadyEvent;
+impl<E: Copy> Event<E> for ConnectionReadyEvent {
+    type Iter = IntoIter<(UserKey, ConnectionParams)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.connection_params);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.connection_params.is_empty()
+    }
+}
+
 // DisconnectEvent
 pub struct DisconnectEvent;
 impl<E: Copy> Event<E> for DisconnectEvent {
@@ -407,7 +546,101 @@ impl<E: Copy> Event<E> for ErrorEvent {
     }
 }
 
+// Connection Quality Changed Event
+/// Fired when a User's connection moves between the Good, Degraded and Bad
+/// levels set by `ConnectionConfig::connection_quality_thresholds`
+pub struct ConnectionQualityChangedEvent;
+impl<E: Copy> Event<E> for ConnectionQualityChangedEvent {
+    type Iter = IntoIter<(UserKey, ConnectionQualityLevel)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.connection_quality_changes);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.connection_quality_changes.is_empty()
+    }
+}
+
+// Scope Backlog Event
+/// Fired when a User's connection becomes backlogged with Entity actions past
+/// the thresholds of `ConnectionConfig::scope_backlog`, meaning spawns &
+/// inserts are reaching its Client late. Fires again only once the backlog
+/// has cleared & built up anew
+pub struct ScopeBacklogEvent;
+impl<E: Copy> Event<E> for ScopeBacklogEvent {
+    type Iter = IntoIter<(UserKey, ScopeBacklog)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.scope_backlogs);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.scope_backlogs.is_empty()
+    }
+}
+
+// Input Gap Event
+/// Fired for each Tick processed with `Server::receive_tick_buffer_messages()`
+/// for which a User has sent no tick-buffered Messages for at least
+/// `ServerConfig::input_gap_threshold` Ticks in a row. Yields the User, the
+/// Tick, and how many Ticks in a row their input has been missing for
+pub struct InputGapEvent;
+impl<E: Copy> Event<E> for InputGapEvent {
+    type Iter = IntoIter<(UserKey, Tick, u16)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.input_gaps);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.input_gaps.is_empty()
+    }
+}
+
+// Message Delivered Event
+/// Fired once a Message sent with `Server::send_message_with_receipt()` has
+/// been acknowledged by the Client, with every fragment of it delivered.
+/// Yields the User it was sent to & the receipt returned when sending it
+pub struct MessageDeliveredEvent;
+impl<E: Copy> Event<E> for MessageDeliveredEvent {
+    type Iter = IntoIter<(UserKey, MessageReceiptKey)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.delivered_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.delivered_messages.is_empty()
+    }
+}
+
+// Message Rejected Event
+/// Fired for each Message a Client sent which the validator set with
+/// `Server::set_message_validator()` rejected. Yields the User who sent it,
+/// the Channel it was sent over & the kind of Message it was
+pub struct MessageRejectedEvent;
+impl<E: Copy> Event<E> for MessageRejectedEvent {
+    type Iter = IntoIter<(UserKey, ChannelKind, MessageKind)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.rejected_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.rejected_messages.is_empty()
+    }
+}
+
 // Auth Event
+/// Fired when a Client asks to connect with an auth Message of type M. Any
+/// other headers the Client's auth request carried (e.g. a bearer token) are
+/// available from `Server::user(&user_key).auth_headers()`
 pub struct AuthEvent<M: Message> {
     phantom_m: PhantomData<M>,
 }
@@ -429,6 +662,30 @@ impl<E: Copy, M: Message> Event<E> for AuthEvent<M> {
     }
 }
 
+// Heartbeat Payload Event
+/// Fired when a Client's Heartbeat carries a payload of type M, see
+/// `Client::set_heartbeat_payload()`
+pub struct HeartbeatPayloadEvent<M: Message> {
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, M: Message> Event<E> for HeartbeatPayloadEvent<M> {
+    type Iter = IntoIter<(UserKey, M)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        return if let Some(messages) = events.heartbeat_payloads.remove(&message_kind) {
+            IntoIterator::into_iter(read_messages(messages))
+        } else {
+            IntoIterator::into_iter(Vec::new())
+        };
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        return events.heartbeat_payloads.contains_key(&message_kind);
+    }
+}
+
 // Message Event
 pub struct MessageEvent<C: Channel, M: Message> {
     phantom_c: PhantomData<C>,
@@ -452,6 +709,44 @@ impl<E: Copy, C: Channel, M: Message> Event<E> for MessageEvent<C, M> {
     }
 }
 
+// Timed Message Event
+/// Like `MessageEvent<C, M>`, but also yields the GameInstant each Message was
+/// sent at, on this Server's clock, if Channel C tracks latency. Reading
+/// either event takes the Messages, so only read one of them for C & M
+pub struct TimedMessageEvent<C: Channel, M: Message> {
+    phantom_c: PhantomData<C>,
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, C: Channel, M: Message> Event<E> for TimedMessageEvent<C, M> {
+    type Iter = IntoIter<(UserKey, Option<GameInstant>, M)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let Some(channel_map) = events.messages.get_mut(&channel_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        let Some(messages) = channel_map.remove(&message_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+
+        let mut output_list = Vec::new();
+        for (user_key, message) in messages {
+            let send_instant = message.send_instant();
+            let message: M = Box::<dyn Any + 'static>::downcast::<M>(message.to_boxed_any())
+                .ok()
+                .map(|boxed_m| *boxed_m)
+                .unwrap();
+            output_list.push((user_key, send_instant, message));
+        }
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        <MessageEvent<C, M> as Event<E>>::has(events)
+    }
+}
+
 pub(crate) fn read_channel_messages<C: Channel, M: Message>(
     messages: &mut HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
 ) -> Vec<(UserKey, M)> {
@@ -542,6 +837,93 @@ impl<E: Copy, C: Channel, Q: Request> Event<E> for RequestEvent<C, Q> {
     }
 }
 
+// Timed Request Event
+/// Like `RequestEvent<C, Q>`, but also yields the GameInstant each Request was
+/// sent at, on this Server's clock, if Channel C tracks latency. Reading
+/// either event takes the Requests, so only read one of them for C & Q
+pub struct TimedRequestEvent<C: Channel, Q: Request> {
+    phantom_c: PhantomData<C>,
+    phantom_m: PhantomData<Q>,
+}
+impl<E: Copy, C: Channel, Q: Request> Event<E> for TimedRequestEvent<C, Q> {
+    type Iter = IntoIter<(
+        UserKey,
+        ResponseSendKey<Q::Response>,
+        Option<GameInstant>,
+        Q,
+    )>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let Some(channel_map) = events.requests.get_mut(&channel_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+        let message_kind: MessageKind = MessageKind::of::<Q>();
+        let Some(requests) = channel_map.remove(&message_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+
+        let mut output_list = Vec::new();
+        for (user_key, global_response_id, request) in requests {
+            let send_instant = request.send_instant();
+            let request: Q = Box::<dyn Any + 'static>::downcast::<Q>(request.to_boxed_any())
+                .ok()
+                .map(|boxed_q| *boxed_q)
+                .unwrap();
+            let response_send_key = ResponseSendKey::<Q::Response>::new(global_response_id);
+            output_list.push((user_key, response_send_key, send_instant, request));
+        }
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        <RequestEvent<C, Q> as Event<E>>::has(events)
+    }
+}
+
+// Request Timeout Event
+/// Fired when a Request of type Q, sent with `Server::send_request_with_timeout()`,
+/// got no Response in time. Yields the User it was sent to, and the key the
+/// Response would have been received with
+pub struct RequestTimeoutEvent<Q: Request> {
+    phantom_q: PhantomData<Q>,
+}
+impl<E: Copy, Q: Request> Event<E> for RequestTimeoutEvent<Q> {
+    type Iter = IntoIter<(UserKey, ResponseReceiveKey<Q::Response>)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let request_kind: MessageKind = MessageKind::of::<Q>();
+        let Some(request_ids) = events.request_timeouts.remove(&request_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+        let output_list: Vec<(UserKey, ResponseReceiveKey<Q::Response>)> = request_ids
+            .into_iter()
+            .map(|(user_key, request_id)| (user_key, ResponseReceiveKey::new(request_id)))
+            .collect();
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let request_kind: MessageKind = MessageKind::of::<Q>();
+        return events.request_timeouts.contains_key(&request_kind);
+    }
+}
+
+// Stream Message Event
+pub struct StreamMessageEvent;
+impl<E: Copy> Event<E> for StreamMessageEvent {
+    type Iter = IntoIter<(UserKey, Vec<u8>)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.stream_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.stream_messages.is_empty()
+    }
+}
+
 // Spawn Entity Event
 pub struct SpawnEntityEvent;
 impl<E: Copy> Event<E> for SpawnEntityEvent {
@@ -635,7 +1017,7 @@ impl<E: Copy> Event<E> for EntityAuthGrantEvent {
 // Entity Auth Reset Event
 pub struct EntityAuthResetEvent;
 impl<E: Copy> Event<E> for EntityAuthResetEvent {
-    type Iter = IntoIter<E>;
+    type Iter = IntoIter<(E, EntityAuthResetReason)>;
 
     fn iter(events: &mut Events<E>) -> Self::Iter {
         let list = std::mem::take(&mut events.auth_resets);
@@ -647,6 +1029,38 @@ impl<E: Copy> Event<E> for EntityAuthResetEvent {
     }
 }
 
+// EntityAuthResetReason
+/// Why authority over a delegated Entity went back to being Available
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntityAuthResetReason {
+    /// The Server took authority, through `Server::entity_take_authority()`
+    ManualReset,
+    /// The User holding authority released it
+    Released,
+    /// Nothing was heard about the Entity from the User holding authority
+    /// within the auth idle timeout
+    IdleTimeout,
+    /// The User holding authority disconnected
+    OwnerDisconnected,
+    /// The User holding authority was made an observer
+    OwnerObserving,
+}
+
+// Room Destroyed Event
+pub struct RoomDestroyedEvent;
+impl<E: Copy> Event<E> for RoomDestroyedEvent {
+    type Iter = IntoIter<(RoomKey, RoomCleanupReason)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.room_destroys);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.room_destroys.is_empty()
+    }
+}
+
 // Insert Component Event
 pub struct InsertComponentEvent<C: Replicate> {
     phantom_c: PhantomData<C>,
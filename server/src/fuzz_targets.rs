@@ -0,0 +1,91 @@
+//! Entry points for fuzzing the Server's packet parsing. Each takes the raw
+//! bytes of a packet received from a Client and runs them through the same
+//! deserialization path as `Server::receive()` would, against fresh
+//! connection state & without any sockets, returning an error for malformed
+//! input rather than panicking.
+
+use std::net::SocketAddr;
+
+use naia_shared::{
+    BigMapKey, BitReader, ConnectionConfig, Instant, PacketType, Protocol, Serde, SerdeErr,
+    StandardHeader, Tick,
+};
+
+use crate::{
+    connection::{connection::Connection, ping_config::PingConfig},
+    handshake::{HandshakeManager, Handshaker},
+    world::global_world_manager::GlobalWorldManager,
+    EntityIdRange, HandshakeCookieConfig, UserKey,
+};
+
+// the Entity type is irrelevant to parsing
+type FuzzEntity = u32;
+
+fn fuzz_address() -> SocketAddr {
+    SocketAddr::from(([127, 0, 0, 1], 14191))
+}
+
+/// Reads a Data packet sent from a Client, including the tick-buffered
+/// messages, messages & world events it carries
+pub fn read_server_data_packet(protocol: &Protocol, bytes: &[u8]) -> Result<(), SerdeErr> {
+    let mut reader = BitReader::new(bytes);
+    let header = StandardHeader::de(&mut reader)?;
+    if header.packet_type != PacketType::Data {
+        return Err(SerdeErr);
+    }
+
+    let mut global_world_manager = GlobalWorldManager::<FuzzEntity>::new(&EntityIdRange::default());
+    let mut connection = Connection::new(
+        &ConnectionConfig::default(),
+        &PingConfig::default(),
+        &fuzz_address(),
+        &UserKey::from_u64(0),
+        &protocol.channel_kinds,
+        &global_world_manager,
+        &0,
+    );
+    connection.process_incoming_header(&header);
+
+    let client_tick = Tick::de(&mut reader)?;
+    connection.read_packet(
+        protocol,
+        0,
+        client_tick,
+        &mut reader,
+        &mut global_world_manager,
+    )?;
+
+    // reassembled & waiting messages are released here
+    connection.base.message_manager.receive_messages(
+        &protocol.message_kinds,
+        &Instant::now(),
+        &global_world_manager,
+        &connection.base.local_world_manager,
+        &mut connection.base.remote_world_manager.entity_waitlist,
+    );
+    connection
+        .base
+        .message_manager
+        .receive_requests_and_responses();
+    connection.base.remote_world_reader.take_incoming_events();
+
+    Ok(())
+}
+
+/// Reads a Handshake packet sent from a Client which is not yet connected
+pub fn read_handshake(protocol: &Protocol, bytes: &[u8]) -> Result<(), SerdeErr> {
+    let mut reader = BitReader::new(bytes);
+    let header = StandardHeader::de(&mut reader)?;
+    if header.packet_type != PacketType::Handshake {
+        return Err(SerdeErr);
+    }
+
+    let mut handshake_manager = HandshakeManager::new(
+        protocol.schema_hash(),
+        false,
+        &HandshakeCookieConfig::default(),
+    );
+    handshake_manager.process_incoming(&fuzz_address(), &mut reader, false)?;
+
+    Ok(())
+}
@@ -4,13 +4,14 @@ use log::warn;
 use ring::{hmac, rand};
 
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, OutgoingPacket, PacketType, Serde, SerdeErr,
-    StandardHeader,
+    handshake::{HandshakeError, HandshakeHeader, CHALLENGE_REQUEST_PADDING_BYTES},
+    BitReader, BitWriter, OutgoingPacket, PacketCipher, PacketType, Serde, SerdeErr,
+    StandardHeader, Timestamp as stamp_time,
 };
 
 use crate::{
-    handshake::{cache_map::CacheMap, HandshakeAction, Handshaker},
-    UserKey,
+    handshake::{connection_keys::ConnectionKeys, HandshakeOutcome, Handshaker},
+    HandshakeCookieConfig, UserKey,
 };
 
 type Timestamp = u64;
@@ -20,11 +21,16 @@ pub struct HandshakeManager {
     authenticated_and_identified_users: HashMap<SocketAddr, UserKey>,
     authenticated_unidentified_users: HashMap<IdentityToken, UserKey>,
     identity_token_map: HashMap<UserKey, IdentityToken>,
+    // each rejection & when it was made, until its Client comes to collect it
+    rejected_identity_tokens: HashMap<IdentityToken, (HandshakeError, Timestamp)>,
+    welcome_payloads: HashMap<UserKey, Vec<u8>>,
     been_handshaked_users: HashMap<SocketAddr, UserKey>,
+    connection_keys: ConnectionKeys,
 
     connection_hash_key: hmac::Key,
+    cookie_lifetime_secs: u64,
     address_to_timestamp_map: HashMap<SocketAddr, Timestamp>,
-    timestamp_digest_map: CacheMap<Timestamp, Vec<u8>>,
+    schema_hash: u64,
 }
 
 impl Handshaker for HandshakeManager {
@@ -35,16 +41,29 @@ impl Handshaker for HandshakeManager {
             .insert(*user_key, identity_token.clone());
     }
 
+    fn set_welcome_payload(&mut self, user_key: &UserKey, payload: Vec<u8>) {
+        self.welcome_payloads.insert(*user_key, payload);
+    }
+
+    fn reject_identity_token(&mut self, identity_token: &IdentityToken, error: HandshakeError) {
+        let now = stamp_time::now();
+        self.expire_rejected_identity_tokens(now);
+        self.rejected_identity_tokens
+            .insert(identity_token.clone(), (error, now));
+    }
+
     // address is optional because user may not have been identified yet
     fn delete_user(&mut self, user_key: &UserKey, address_opt: Option<SocketAddr>) {
         if let Some(identity_token) = self.identity_token_map.remove(user_key) {
             self.authenticated_unidentified_users
                 .remove(&identity_token);
         }
+        self.welcome_payloads.remove(user_key);
         if let Some(address) = address_opt {
             self.authenticated_and_identified_users.remove(&address);
             self.been_handshaked_users.remove(&address);
             self.address_to_timestamp_map.remove(&address);
+            self.connection_keys.remove(&address);
         }
     }
 
@@ -54,168 +73,264 @@ impl Handshaker for HandshakeManager {
             .copied()
     }
 
-    fn maintain_handshake(
+    fn take_packet_cipher(&mut self, address: &SocketAddr) -> Option<PacketCipher> {
+        self.connection_keys.take_packet_cipher(address)
+    }
+
+    // Step 5 of Handshake
+    fn write_connect_response(
+        &self,
+        address: &SocketAddr,
+        server_public_key: Option<Vec<u8>>,
+    ) -> BitWriter {
+        let mut writer = BitWriter::new();
+        StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
+        HandshakeHeader::ServerConnectResponse.ser(&mut writer);
+        let welcome_payload = self
+            .been_handshaked_users
+            .get(address)
+            .and_then(|user_key| self.welcome_payloads.get(user_key));
+        welcome_payload.cloned().ser(&mut writer);
+        server_public_key.ser(&mut writer);
+        writer
+    }
+
+    fn write_reject_response(&self, error: &HandshakeError) -> BitWriter {
+        let mut writer = BitWriter::new();
+        StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
+        HandshakeHeader::ServerRejectResponse.ser(&mut writer);
+        error.ser(&mut writer);
+        writer
+    }
+
+    fn process_incoming(
         &mut self,
         address: &SocketAddr,
         reader: &mut BitReader,
         has_connection: bool,
-    ) -> Result<HandshakeAction, SerdeErr> {
+    ) -> Result<HandshakeOutcome, SerdeErr> {
         let handshake_header = HandshakeHeader::de(reader)?;
 
         // Handshake stuff
         match handshake_header {
             HandshakeHeader::ClientChallengeRequest => {
-                if let Ok((timestamp, id_token)) = self.recv_challenge_request(reader) {
-                    if let Some(user_key) = self.authenticated_unidentified_users.remove(&id_token)
+                // nothing is kept for the Client until it echoes the cookie
+                // back, proving it is really at `address`
+                if let Ok((timestamp, id_token, client_schema_hash)) =
+                    self.recv_challenge_request(reader)
+                {
+                    if client_schema_hash != self.schema_hash {
+                        let error = HandshakeError::SchemaMismatch {
+                            server_hash: self.schema_hash,
+                            client_hash: client_schema_hash,
+                        };
+                        let user_key_opt = self
+                            .authenticated_unidentified_users
+                            .get(&id_token)
+                            .copied();
+                        let packet = self.write_reject_response(&error).to_packet();
+                        return Ok(HandshakeOutcome::RejectConnection(
+                            user_key_opt,
+                            packet,
+                            error,
+                        ));
+                    }
+
+                    self.expire_rejected_identity_tokens(stamp_time::now());
+                    if let Some((error, _)) = self.rejected_identity_tokens.remove(&id_token) {
+                        let packet = self.write_reject_response(&error).to_packet();
+                        return Ok(HandshakeOutcome::SendPacket(packet));
+                    }
+
+                    if !self
+                        .authenticated_unidentified_users
+                        .contains_key(&id_token)
                     {
-                        // remove identity token from map
-                        if self.identity_token_map.remove(&user_key).is_none() {
-                            panic!("Server Error: Identity Token not found for user_key: {:?}. Shouldn't be possible.", user_key);
-                        }
-
-                        // User is authenticated and identified
-                        self.authenticated_and_identified_users
-                            .insert(*address, user_key);
-                    } else {
                         // commented out because it's pretty common to get multiple ClientChallengeRequest which would trigger this
                         //warn!("Server Error: User not authenticated for: {:?}, with token: {}", address, identity_token);
 
-                        return Ok(HandshakeAction::None);
+                        return Ok(HandshakeOutcome::None);
                     }
 
-                    let identify_response = self.write_challenge_response(&timestamp).to_packet();
+                    let challenge_response = self
+                        .write_challenge_response(address, &timestamp, stamp_time::now())
+                        .to_packet();
 
-                    return Ok(HandshakeAction::SendPacket(identify_response));
+                    return Ok(HandshakeOutcome::SendPacket(challenge_response));
                 } else {
-                    return Ok(HandshakeAction::None);
+                    return Ok(HandshakeOutcome::None);
                 }
             }
             HandshakeHeader::ClientValidateRequest => {
-                if self.recv_validate_request(address, reader) {
-                    if self.been_handshaked_users.contains_key(address) {
-                        // send validate response
-                        let writer = self.write_validate_response();
-                        return Ok(HandshakeAction::SendPacket(writer.to_packet()));
-                    } else {
-                        // info!("checking authenticated users for {}", address);
-                        if let Some(user_key) = self.authenticated_and_identified_users.get(address)
-                        {
-                            let user_key = *user_key;
-                            let address = *address;
-                            let packet = self.user_finish_handshake(&address, &user_key);
-                            return Ok(HandshakeAction::SendPacket(packet));
-                        } else {
-                            warn!("Server Error: Cannot find user by address {}", address);
-                            return Ok(HandshakeAction::None);
-                        }
-                    }
-                } else {
-                    // do nothing
-                    return Ok(HandshakeAction::None);
+                // Verify that the cookie was issued by this server instance,
+                // to this address
+                let Some((timestamp, issued_at)) = self.cookie_validate(address, reader) else {
+                    warn!("Handshake Error from {}: Invalid cookie", address);
+                    return Ok(HandshakeOutcome::None);
+                };
+                let now = stamp_time::now();
+                if now.saturating_sub(issued_at) > self.cookie_lifetime_secs {
+                    // send a fresh cookie to retry with
+                    let packet = self
+                        .write_challenge_response(address, &timestamp, now)
+                        .to_packet();
+                    return Ok(HandshakeOutcome::SendPacket(packet));
+                }
+                // Cookie is valid
+                let id_token = IdentityToken::de(reader)?;
+
+                if self.been_handshaked_users.contains_key(address) {
+                    // send validate response
+                    let writer = self.write_validate_response();
+                    return Ok(HandshakeOutcome::SendPacket(writer.to_packet()));
                 }
+
+                let Some(user_key) = self.authenticated_unidentified_users.remove(&id_token) else {
+                    warn!("Server Error: User not authenticated for {}", address);
+                    return Ok(HandshakeOutcome::None);
+                };
+
+                // remove identity token from map
+                if self.identity_token_map.remove(&user_key).is_none() {
+                    panic!("Server Error: Identity Token not found for user_key: {:?}. Shouldn't be possible.", user_key);
+                }
+
+                // User is authenticated and identified
+                self.authenticated_and_identified_users
+                    .insert(*address, user_key);
+                self.address_to_timestamp_map.insert(*address, timestamp);
+
+                let packet = self.user_finish_handshake(address, &user_key);
+                return Ok(HandshakeOutcome::SendPacket(packet));
             }
             HandshakeHeader::ClientConnectRequest => {
+                let client_public_key = Option::<Vec<u8>>::de(reader)?;
+                let server_public_key = match self
+                    .connection_keys
+                    .recv_client_public_key(address, client_public_key)
+                {
+                    Ok(server_public_key) => server_public_key,
+                    Err(error) => {
+                        let user_key_opt = self.been_handshaked_users.get(address).copied();
+                        let packet = self.write_reject_response(&error).to_packet();
+                        return Ok(HandshakeOutcome::RejectConnection(
+                            user_key_opt,
+                            packet,
+                            error,
+                        ));
+                    }
+                };
+
                 // send connect response
-                let writer = self.write_connect_response();
-                let packet = writer.to_packet();
+                let packet = self
+                    .write_connect_response(address, server_public_key)
+                    .to_packet();
 
                 if has_connection {
-                    return Ok(HandshakeAction::SendPacket(packet));
+                    return Ok(HandshakeOutcome::SendPacket(packet));
                 } else {
-                    let user_key = *self
-                        .been_handshaked_users
-                        .get(address)
-                        .expect("should be a user by now, from validation step");
-
-                    return Ok(HandshakeAction::FinalizeConnection(user_key, packet));
+                    let Some(user_key) = self.been_handshaked_users.get(address) else {
+                        warn!(
+                            "Server Error: Connect request before validation from {}",
+                            address
+                        );
+                        return Ok(HandshakeOutcome::None);
+                    };
+
+                    return Ok(HandshakeOutcome::FinalizeConnection(*user_key, packet));
                 }
             }
             HandshakeHeader::Disconnect => {
                 if self.verify_disconnect_request(address, reader) {
-                    let user_key = *self
-                        .been_handshaked_users
-                        .get(address)
-                        .expect("should be a user by now, from validation step");
-                    return Ok(HandshakeAction::DisconnectUser(user_key));
-                } else {
-                    return Ok(HandshakeAction::None);
+                    if let Some(user_key) = self.been_handshaked_users.get(address) {
+                        return Ok(HandshakeOutcome::DisconnectUser(*user_key));
+                    }
                 }
+                return Ok(HandshakeOutcome::None);
             }
             _ => {
                 warn!(
                     "Server Error: Unexpected handshake header: {:?} from {}",
                     handshake_header, address
                 );
-                return Ok(HandshakeAction::None);
+                return Ok(HandshakeOutcome::None);
             }
         }
     }
 }
 
 impl HandshakeManager {
-    pub fn new() -> Self {
-        let connection_hash_key =
-            hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap();
+    pub fn new(
+        schema_hash: u64,
+        encryption_enabled: bool,
+        cookie_config: &HandshakeCookieConfig,
+    ) -> Self {
+        let connection_hash_key = match &cookie_config.secret {
+            Some(secret) => hmac::Key::new(hmac::HMAC_SHA256, secret),
+            None => hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap(),
+        };
 
         Self {
             authenticated_and_identified_users: HashMap::new(),
             authenticated_unidentified_users: HashMap::new(),
             identity_token_map: HashMap::new(),
+            rejected_identity_tokens: HashMap::new(),
+            welcome_payloads: HashMap::new(),
             been_handshaked_users: HashMap::new(),
+            connection_keys: ConnectionKeys::new(encryption_enabled),
 
             connection_hash_key,
+            cookie_lifetime_secs: cookie_config.lifetime.as_secs(),
             address_to_timestamp_map: HashMap::new(),
-            timestamp_digest_map: CacheMap::with_capacity(64),
+            schema_hash,
         }
     }
 
+    // forgets rejections whose Client never came to collect them, within the
+    // lifetime of the cookie it would have been issued
+    fn expire_rejected_identity_tokens(&mut self, now: Timestamp) {
+        let lifetime_secs = self.cookie_lifetime_secs;
+        self.rejected_identity_tokens
+            .retain(|_, (_, rejected_at)| now.saturating_sub(*rejected_at) <= lifetime_secs);
+    }
+
     // Step 1 of Handshake
     fn recv_challenge_request(
         &mut self,
         reader: &mut BitReader,
-    ) -> Result<(Timestamp, IdentityToken), SerdeErr> {
+    ) -> Result<(Timestamp, IdentityToken, u64), SerdeErr> {
         let timestamp = Timestamp::de(reader)?;
         let identity_token = IdentityToken::de(reader)?;
+        let schema_hash = u64::de(reader)?;
+
+        // too small a request could be answered with a larger reply
+        let padding = Vec::<u8>::de(reader)?;
+        if padding.len() < CHALLENGE_REQUEST_PADDING_BYTES {
+            return Err(SerdeErr);
+        }
 
-        Ok((timestamp, identity_token))
+        Ok((timestamp, identity_token, schema_hash))
     }
 
     // Step 2 of Handshake
-    fn write_challenge_response(&mut self, timestamp: &Timestamp) -> BitWriter {
+    fn write_challenge_response(
+        &self,
+        address: &SocketAddr,
+        timestamp: &Timestamp,
+        issued_at: u64,
+    ) -> BitWriter {
         let mut writer = BitWriter::new();
         StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
         HandshakeHeader::ServerChallengeResponse.ser(&mut writer);
         timestamp.ser(&mut writer);
 
-        if !self.timestamp_digest_map.contains_key(timestamp) {
-            let tag = hmac::sign(&self.connection_hash_key, &timestamp.to_le_bytes());
-            let tag_vec: Vec<u8> = Vec::from(tag.as_ref());
-            self.timestamp_digest_map.insert(*timestamp, tag_vec);
-        }
-
-        //write timestamp digest
-        self.timestamp_digest_map
-            .get_unchecked(timestamp)
+        // write cookie, which the Client echoes back as is
+        self.write_cookie(address, timestamp, issued_at)
             .ser(&mut writer);
 
         writer
     }
 
-    // Step 3 of Handshake
-    fn recv_validate_request(&mut self, address: &SocketAddr, reader: &mut BitReader) -> bool {
-        // Verify that timestamp hash has been written by this
-        // server instance
-        let Some(timestamp) = self.timestamp_validate(reader) else {
-            warn!("Handshake Error from {}: Invalid timestamp hash", address);
-            return false;
-        };
-        // Timestamp hash is valid
-
-        self.address_to_timestamp_map.insert(*address, timestamp);
-
-        return true;
-    }
-
     // Step 4 of Handshake
     fn write_validate_response(&self) -> BitWriter {
         let mut writer = BitWriter::new();
@@ -224,18 +339,10 @@ impl HandshakeManager {
         writer
     }
 
-    // Step 5 of Handshake
-    fn write_connect_response(&self) -> BitWriter {
-        let mut writer = BitWriter::new();
-        StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
-        HandshakeHeader::ServerConnectResponse.ser(&mut writer);
-        writer
-    }
-
     fn verify_disconnect_request(&mut self, address: &SocketAddr, reader: &mut BitReader) -> bool {
-        // Verify that timestamp hash has been written by this
-        // server instance
-        if let Some(new_timestamp) = self.timestamp_validate(reader) {
+        // Verify that the cookie was issued by this server instance, to this
+        // address. It may have been issued long ago
+        if let Some((new_timestamp, _)) = self.cookie_validate(address, reader) {
             if let Some(old_timestamp) = self.address_to_timestamp_map.get(address) {
                 if *old_timestamp == new_timestamp {
                     return true;
@@ -246,38 +353,52 @@ impl HandshakeManager {
         false
     }
 
-    // fn write_reject_response(&self) -> BitWriter {
-    //     let mut writer = BitWriter::new();
-    //     StandardHeader::new(PacketType::ServerRejectResponse, 0, 0, 0).ser(&mut writer);
-    //     writer
-    // }
+    // a cookie is the time it was issued at, followed by a digest of that,
+    // the Client's address & timestamp
+    fn write_cookie(&self, address: &SocketAddr, timestamp: &Timestamp, issued_at: u64) -> Vec<u8> {
+        let mut cookie = issued_at.to_le_bytes().to_vec();
+        let tag = hmac::sign(
+            &self.connection_hash_key,
+            &Self::cookie_message(address, timestamp, issued_at),
+        );
+        cookie.extend_from_slice(tag.as_ref());
+        cookie
+    }
+
+    fn cookie_message(address: &SocketAddr, timestamp: &Timestamp, issued_at: u64) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(&timestamp.to_le_bytes());
+        message.extend_from_slice(&issued_at.to_le_bytes());
+        message.extend_from_slice(address.to_string().as_bytes());
+        message
+    }
 
-    fn timestamp_validate(&self, reader: &mut BitReader) -> Option<Timestamp> {
+    // returns the Client's timestamp & when the cookie was issued
+    fn cookie_validate(
+        &self,
+        address: &SocketAddr,
+        reader: &mut BitReader,
+    ) -> Option<(Timestamp, u64)> {
         // Read timestamp
-        let timestamp_result = Timestamp::de(reader);
-        if timestamp_result.is_err() {
-            return None;
-        }
-        let timestamp = timestamp_result.unwrap();
+        let timestamp = Timestamp::de(reader).ok()?;
 
-        // Read digest
-        let digest_bytes_result = Vec::<u8>::de(reader);
-        if digest_bytes_result.is_err() {
+        // Read cookie
+        let cookie = Vec::<u8>::de(reader).ok()?;
+        if cookie.len() < 8 {
             return None;
         }
-        let digest_bytes = digest_bytes_result.unwrap();
+        let (issued_at_bytes, digest_bytes) = cookie.split_at(8);
+        let issued_at = u64::from_le_bytes(issued_at_bytes.try_into().ok()?);
 
-        // Verify that timestamp hash has been written by this server instance
-        let validation_result = hmac::verify(
+        // Verify that the cookie has been written by this server instance
+        hmac::verify(
             &self.connection_hash_key,
-            &timestamp.to_le_bytes(),
-            &digest_bytes,
-        );
-        if validation_result.is_err() {
-            None
-        } else {
-            Some(timestamp)
-        }
+            &Self::cookie_message(address, &timestamp, issued_at),
+            digest_bytes,
+        )
+        .ok()?;
+
+        Some((timestamp, issued_at))
     }
 
     fn user_finish_handshake(&mut self, addr: &SocketAddr, user_key: &UserKey) -> OutgoingPacket {
@@ -290,3 +411,214 @@ impl HandshakeManager {
         packet
     }
 }
+
+#[cfg(test)]
+mod cookie_tests {
+    use std::{net::SocketAddr, time::Duration};
+
+    use naia_shared::{
+        handshake::{HandshakeError, HandshakeHeader, CHALLENGE_REQUEST_PADDING_BYTES},
+        BigMapKey, BitReader, BitWriter, OutgoingPacket, Serde, StandardHeader,
+        Timestamp as stamp_time,
+    };
+
+    use super::{HandshakeManager, IdentityToken, Timestamp};
+    use crate::{
+        handshake::{HandshakeOutcome, Handshaker},
+        HandshakeCookieConfig, UserKey,
+    };
+
+    const SCHEMA_HASH: u64 = 7;
+    const TIMESTAMP: Timestamp = 1234;
+
+    fn manager() -> (HandshakeManager, IdentityToken, UserKey) {
+        let mut manager = HandshakeManager::new(
+            SCHEMA_HASH,
+            false,
+            &HandshakeCookieConfig {
+                secret: Some(b"secret".to_vec()),
+                lifetime: Duration::from_secs(10),
+            },
+        );
+        let identity_token = "token".to_string();
+        let user_key = UserKey::from_u64(0);
+        manager.authenticate_user(&identity_token, &user_key);
+        (manager, identity_token, user_key)
+    }
+
+    fn address(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn challenge_request(identity_token: &IdentityToken, padding: usize) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientChallengeRequest.ser(&mut writer);
+        TIMESTAMP.ser(&mut writer);
+        identity_token.ser(&mut writer);
+        SCHEMA_HASH.ser(&mut writer);
+        vec![0u8; padding].ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    fn validate_request(cookie: &Vec<u8>, identity_token: &IdentityToken) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientValidateRequest.ser(&mut writer);
+        TIMESTAMP.ser(&mut writer);
+        cookie.ser(&mut writer);
+        identity_token.ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    fn process(
+        manager: &mut HandshakeManager,
+        address: &SocketAddr,
+        bytes: &[u8],
+    ) -> HandshakeOutcome {
+        let mut reader = BitReader::new(bytes);
+        manager
+            .process_incoming(address, &mut reader, false)
+            .unwrap()
+    }
+
+    // reads the cookie out of a ServerChallengeResponse
+    fn read_cookie(packet: &OutgoingPacket) -> Vec<u8> {
+        let mut reader = BitReader::new(packet.slice());
+        StandardHeader::de(&mut reader).unwrap();
+        assert_eq!(
+            HandshakeHeader::de(&mut reader).unwrap(),
+            HandshakeHeader::ServerChallengeResponse
+        );
+        assert_eq!(Timestamp::de(&mut reader).unwrap(), TIMESTAMP);
+        Vec::<u8>::de(&mut reader).unwrap()
+    }
+
+    fn holds_no_state(manager: &HandshakeManager, address: &SocketAddr) -> bool {
+        manager.authenticated_and_identified_users.is_empty()
+            && manager.been_handshaked_users.is_empty()
+            && manager.address_to_timestamp_map.is_empty()
+            && manager.get_user_for_address(address).is_none()
+    }
+
+    #[test]
+    fn spoofed_source_allocates_no_state() {
+        let (mut manager, identity_token, _) = manager();
+        let spoofed = address(1000);
+
+        for _ in 0..10 {
+            let request = challenge_request(&identity_token, CHALLENGE_REQUEST_PADDING_BYTES);
+            let HandshakeOutcome::SendPacket(packet) = process(&mut manager, &spoofed, &request)
+            else {
+                panic!("expected a cookie");
+            };
+            // the reply is no bigger than the request
+            assert!(packet.slice().len() <= request.len());
+        }
+        // the spoofed sender never echoes the cookie back
+        assert!(holds_no_state(&manager, &spoofed));
+        assert!(manager
+            .authenticated_unidentified_users
+            .contains_key(&identity_token));
+    }
+
+    #[test]
+    fn unpadded_challenge_is_ignored() {
+        let (mut manager, identity_token, _) = manager();
+        let request = challenge_request(&identity_token, CHALLENGE_REQUEST_PADDING_BYTES - 1);
+        let mut reader = BitReader::new(&request);
+        assert!(matches!(
+            manager.process_incoming(&address(1000), &mut reader, false),
+            Ok(HandshakeOutcome::None)
+        ));
+    }
+
+    #[test]
+    fn echoed_cookie_identifies_user() {
+        let (mut manager, identity_token, user_key) = manager();
+        let client = address(1000);
+
+        let request = challenge_request(&identity_token, CHALLENGE_REQUEST_PADDING_BYTES);
+        let HandshakeOutcome::SendPacket(packet) = process(&mut manager, &client, &request) else {
+            panic!("expected a cookie");
+        };
+        let cookie = read_cookie(&packet);
+
+        // the cookie is only good for the address it was sent to
+        let other = address(2000);
+        let request = validate_request(&cookie, &identity_token);
+        assert!(matches!(
+            process(&mut manager, &other, &request),
+            HandshakeOutcome::None
+        ));
+        assert!(holds_no_state(&manager, &other));
+
+        assert!(matches!(
+            process(&mut manager, &client, &request),
+            HandshakeOutcome::SendPacket(_)
+        ));
+        assert_eq!(manager.get_user_for_address(&client), Some(user_key));
+    }
+
+    #[test]
+    fn expired_cookie_is_replaced() {
+        let (mut manager, identity_token, _) = manager();
+        let client = address(1000);
+
+        let expired_at = stamp_time::now() - 60;
+        let cookie = manager.write_cookie(&client, &TIMESTAMP, expired_at);
+        let request = validate_request(&cookie, &identity_token);
+        let HandshakeOutcome::SendPacket(packet) = process(&mut manager, &client, &request) else {
+            panic!("expected a fresh cookie");
+        };
+        assert!(holds_no_state(&manager, &client));
+
+        let fresh_cookie = read_cookie(&packet);
+        assert_ne!(fresh_cookie, cookie);
+        let request = validate_request(&fresh_cookie, &identity_token);
+        process(&mut manager, &client, &request);
+        assert!(manager.get_user_for_address(&client).is_some());
+    }
+
+    #[test]
+    fn uncollected_rejection_expires() {
+        let (mut manager, _, _) = manager();
+        let rejected = || HandshakeError::Rejected {
+            code: 503,
+            payload: None,
+        };
+
+        // a Client rejected a minute ago, which never sent its identity token
+        let stale_token = "stale".to_string();
+        manager.reject_identity_token(&stale_token, rejected());
+        manager
+            .rejected_identity_tokens
+            .get_mut(&stale_token)
+            .unwrap()
+            .1 -= 60;
+
+        // is forgotten once another Client is rejected
+        let fresh_token = "fresh".to_string();
+        manager.reject_identity_token(&fresh_token, rejected());
+        assert!(!manager.rejected_identity_tokens.contains_key(&stale_token));
+        assert!(manager.rejected_identity_tokens.contains_key(&fresh_token));
+
+        // so if it turns up after all, its token is unknown
+        let client = address(1000);
+        let request = challenge_request(&stale_token, CHALLENGE_REQUEST_PADDING_BYTES);
+        assert!(matches!(
+            process(&mut manager, &client, &request),
+            HandshakeOutcome::None
+        ));
+    }
+
+    #[test]
+    fn shared_secret_is_accepted_across_servers() {
+        let (first, identity_token, user_key) = manager();
+        let (mut second, _, _) = manager();
+        let client = address(1000);
+
+        let cookie = first.write_cookie(&client, &TIMESTAMP, stamp_time::now());
+        let request = validate_request(&cookie, &identity_token);
+        process(&mut second, &client, &request);
+        assert_eq!(second.get_user_for_address(&client), Some(user_key));
+    }
+}
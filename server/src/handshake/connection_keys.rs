@@ -0,0 +1,66 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use naia_shared::{handshake::HandshakeError, HostType, KeyExchange, PacketCipher};
+
+// Performs the Server's half of the key exchange for each connecting Client
+pub struct ConnectionKeys {
+    encryption_enabled: bool,
+    // the Client's public key, and the Server's public key sent in response
+    public_keys: HashMap<SocketAddr, (Vec<u8>, Vec<u8>)>,
+    new_ciphers: HashMap<SocketAddr, PacketCipher>,
+}
+
+impl ConnectionKeys {
+    pub fn new(encryption_enabled: bool) -> Self {
+        Self {
+            encryption_enabled,
+            public_keys: HashMap::new(),
+            new_ciphers: HashMap::new(),
+        }
+    }
+
+    // Returns the Server's public key to send back to the Client, if any.
+    // ClientConnectRequests are resent until answered, so a repeated public key
+    // is answered without performing the key exchange again
+    pub fn recv_client_public_key(
+        &mut self,
+        address: &SocketAddr,
+        client_public_key: Option<Vec<u8>>,
+    ) -> Result<Option<Vec<u8>>, HandshakeError> {
+        if !self.encryption_enabled {
+            if client_public_key.is_some() {
+                return Err(HandshakeError::EncryptionMismatch);
+            }
+            return Ok(None);
+        }
+        let Some(client_public_key) = client_public_key else {
+            return Err(HandshakeError::EncryptionMismatch);
+        };
+
+        if let Some((old_client_public_key, server_public_key)) = self.public_keys.get(address) {
+            if *old_client_public_key == client_public_key {
+                return Ok(Some(server_public_key.clone()));
+            }
+        }
+
+        let key_exchange = KeyExchange::new()?;
+        let server_public_key = key_exchange.public_key().to_vec();
+        let Some(cipher) = key_exchange.into_cipher(HostType::Server, &client_public_key) else {
+            return Err(HandshakeError::EncryptionMismatch);
+        };
+        self.new_ciphers.insert(*address, cipher);
+        self.public_keys
+            .insert(*address, (client_public_key, server_public_key.clone()));
+
+        Ok(Some(server_public_key))
+    }
+
+    pub fn take_packet_cipher(&mut self, address: &SocketAddr) -> Option<PacketCipher> {
+        self.new_ciphers.remove(address)
+    }
+
+    pub fn remove(&mut self, address: &SocketAddr) {
+        self.public_keys.remove(address);
+        self.new_ciphers.remove(address);
+    }
+}
@@ -1,13 +1,16 @@
 use std::net::SocketAddr;
 
-use naia_shared::{BitReader, IdentityToken, OutgoingPacket, SerdeErr};
+use naia_shared::{
+    handshake::HandshakeError, BitReader, BitWriter, IdentityToken, OutgoingPacket, PacketCipher,
+    SerdeErr,
+};
 
 use crate::UserKey;
 
+mod connection_keys;
+
 cfg_if! {
     if #[cfg(feature = "transport_udp")] {
-        mod cache_map;
-
         mod advanced_handshaker;
         pub use advanced_handshaker::HandshakeManager;
     } else {
@@ -16,25 +19,59 @@ cfg_if! {
     }
 }
 
+/// Server side of the connection handshake. Operates only on packet contents,
+/// so any transport can drive it: pass each incoming Handshake packet to
+/// `process_incoming()` & act on the returned `HandshakeOutcome`.
 pub trait Handshaker: Send + Sync {
     fn authenticate_user(&mut self, identity_token: &IdentityToken, user_key: &UserKey);
 
+    // the payload is delivered to the Client along with the connect response
+    fn set_welcome_payload(&mut self, user_key: &UserKey, payload: Vec<u8>);
+
+    // the Client holding this identity token will be sent the error in response to its first handshake request
+    fn reject_identity_token(&mut self, identity_token: &IdentityToken, error: HandshakeError);
+
     // address is optional because user may not have been identified yet
     fn delete_user(&mut self, user_key: &UserKey, address_opt: Option<SocketAddr>);
 
     fn get_user_for_address(&self, address: &SocketAddr) -> Option<UserKey>;
 
-    fn maintain_handshake(
+    // the keys derived for a Client during its most recent ClientConnectRequest, if encryption is enabled
+    fn take_packet_cipher(&mut self, address: &SocketAddr) -> Option<PacketCipher>;
+
+    /// Reads a Handshake packet from the Client at `address`, with `reader`
+    /// positioned just after the packet's `StandardHeader`. `has_connection`
+    /// is whether a different User is already connected from `address`.
+    fn process_incoming(
         &mut self,
         address: &SocketAddr,
         reader: &mut BitReader,
         has_connection: bool,
-    ) -> Result<HandshakeAction, SerdeErr>;
+    ) -> Result<HandshakeOutcome, SerdeErr>;
+
+    /// Writes the response to a ClientConnectRequest from `address`, carrying
+    /// the User's welcome payload & the key for the Client to derive
+    /// encryption keys with, if enabled
+    fn write_connect_response(
+        &self,
+        address: &SocketAddr,
+        server_public_key: Option<Vec<u8>>,
+    ) -> BitWriter;
+
+    /// Writes a response refusing the handshake, which the Client reports
+    /// as `error`
+    fn write_reject_response(&self, error: &HandshakeError) -> BitWriter;
 }
 
-pub enum HandshakeAction {
+/// What the transport driving a `Handshaker` should do after an incoming packet
+pub enum HandshakeOutcome {
     None,
+    /// The handshake is complete: start a connection for the User & send
+    /// the packet
     FinalizeConnection(UserKey, OutgoingPacket),
     SendPacket(OutgoingPacket),
     DisconnectUser(UserKey),
+    /// Send the packet, then forget the Client. The UserKey is only known if
+    /// the rejected Client presented a valid identity token
+    RejectConnection(Option<UserKey>, OutgoingPacket, HandshakeError),
 }
@@ -3,19 +3,26 @@ use std::{collections::HashMap, net::SocketAddr};
 use log::warn;
 
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, IdentityToken, PacketType, Serde, SerdeErr,
-    StandardHeader,
+    handshake::{HandshakeError, HandshakeHeader},
+    BitReader, BitWriter, IdentityToken, PacketCipher, PacketType, Serde, SerdeErr, StandardHeader,
+    Timestamp as stamp_time,
 };
 
 use crate::{
-    handshake::{HandshakeAction, Handshaker},
-    UserKey,
+    handshake::{connection_keys::ConnectionKeys, HandshakeOutcome, Handshaker},
+    HandshakeCookieConfig, UserKey,
 };
 
 pub struct HandshakeManager {
     authenticated_and_identified_users: HashMap<SocketAddr, UserKey>,
     authenticated_unidentified_users: HashMap<IdentityToken, UserKey>,
     identity_token_map: HashMap<UserKey, IdentityToken>,
+    // each rejection & when it was made, until its Client comes to collect it
+    rejected_identity_tokens: HashMap<IdentityToken, (HandshakeError, u64)>,
+    rejection_lifetime_secs: u64,
+    welcome_payloads: HashMap<UserKey, Vec<u8>>,
+    connection_keys: ConnectionKeys,
+    schema_hash: u64,
 }
 
 impl Handshaker for HandshakeManager {
@@ -26,14 +33,27 @@ impl Handshaker for HandshakeManager {
             .insert(*user_key, identity_token.clone());
     }
 
+    fn set_welcome_payload(&mut self, user_key: &UserKey, payload: Vec<u8>) {
+        self.welcome_payloads.insert(*user_key, payload);
+    }
+
+    fn reject_identity_token(&mut self, identity_token: &IdentityToken, error: HandshakeError) {
+        let now = stamp_time::now();
+        self.expire_rejected_identity_tokens(now);
+        self.rejected_identity_tokens
+            .insert(identity_token.clone(), (error, now));
+    }
+
     // address is optional because user may not have been identified yet
     fn delete_user(&mut self, user_key: &UserKey, address_opt: Option<SocketAddr>) {
         if let Some(identity_token) = self.identity_token_map.remove(user_key) {
             self.authenticated_unidentified_users
                 .remove(&identity_token);
         }
+        self.welcome_payloads.remove(user_key);
         if let Some(address) = address_opt {
             self.authenticated_and_identified_users.remove(&address);
+            self.connection_keys.remove(&address);
         }
     }
 
@@ -43,18 +63,71 @@ impl Handshaker for HandshakeManager {
             .copied()
     }
 
-    fn maintain_handshake(
+    fn take_packet_cipher(&mut self, address: &SocketAddr) -> Option<PacketCipher> {
+        self.connection_keys.take_packet_cipher(address)
+    }
+
+    // Step 3 of Handshake
+    fn write_connect_response(
+        &self,
+        address: &SocketAddr,
+        server_public_key: Option<Vec<u8>>,
+    ) -> BitWriter {
+        let mut writer = BitWriter::new();
+        StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
+        HandshakeHeader::ServerConnectResponse.ser(&mut writer);
+        let welcome_payload = self
+            .authenticated_and_identified_users
+            .get(address)
+            .and_then(|user_key| self.welcome_payloads.get(user_key));
+        welcome_payload.cloned().ser(&mut writer);
+        server_public_key.ser(&mut writer);
+        writer
+    }
+
+    fn write_reject_response(&self, error: &HandshakeError) -> BitWriter {
+        let mut writer = BitWriter::new();
+        StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
+        HandshakeHeader::ServerRejectResponse.ser(&mut writer);
+        error.ser(&mut writer);
+        writer
+    }
+
+    fn process_incoming(
         &mut self,
         address: &SocketAddr,
         reader: &mut BitReader,
         has_connection: bool,
-    ) -> Result<HandshakeAction, SerdeErr> {
+    ) -> Result<HandshakeOutcome, SerdeErr> {
         let handshake_header = HandshakeHeader::de(reader)?;
 
         // Handshake stuff
         match handshake_header {
             HandshakeHeader::ClientIdentifyRequest => {
-                if let Ok(id_token) = self.recv_identify_request(reader) {
+                if let Ok((id_token, client_schema_hash)) = self.recv_identify_request(reader) {
+                    if client_schema_hash != self.schema_hash {
+                        let error = HandshakeError::SchemaMismatch {
+                            server_hash: self.schema_hash,
+                            client_hash: client_schema_hash,
+                        };
+                        let user_key_opt = self
+                            .authenticated_unidentified_users
+                            .get(&id_token)
+                            .copied();
+                        let packet = self.write_reject_response(&error).to_packet();
+                        return Ok(HandshakeOutcome::RejectConnection(
+                            user_key_opt,
+                            packet,
+                            error,
+                        ));
+                    }
+
+                    self.expire_rejected_identity_tokens(stamp_time::now());
+                    if let Some((error, _)) = self.rejected_identity_tokens.remove(&id_token) {
+                        let packet = self.write_reject_response(&error).to_packet();
+                        return Ok(HandshakeOutcome::SendPacket(packet));
+                    }
+
                     if let Some(user_key) = self.authenticated_unidentified_users.remove(&id_token)
                     {
                         // remove identity token from map
@@ -69,20 +142,41 @@ impl Handshaker for HandshakeManager {
                         // commented out because it's pretty common to get multiple ClientIdentifyRequests which would trigger this
                         //warn!("Server Error: User not authenticated for: {:?}, with token: {}", address, identity_token);
 
-                        return Ok(HandshakeAction::None);
+                        return Ok(HandshakeOutcome::None);
                     }
 
                     let identify_response = Self::write_identity_response().to_packet();
 
-                    return Ok(HandshakeAction::SendPacket(identify_response));
+                    return Ok(HandshakeOutcome::SendPacket(identify_response));
                 } else {
-                    return Ok(HandshakeAction::None);
+                    return Ok(HandshakeOutcome::None);
                 }
             }
             HandshakeHeader::ClientConnectRequest => {
+                let client_public_key = Option::<Vec<u8>>::de(reader)?;
+                let server_public_key = match self
+                    .connection_keys
+                    .recv_client_public_key(address, client_public_key)
+                {
+                    Ok(server_public_key) => server_public_key,
+                    Err(error) => {
+                        let user_key_opt = self
+                            .authenticated_and_identified_users
+                            .get(address)
+                            .copied();
+                        let packet = self.write_reject_response(&error).to_packet();
+                        return Ok(HandshakeOutcome::RejectConnection(
+                            user_key_opt,
+                            packet,
+                            error,
+                        ));
+                    }
+                };
+
                 // send connect response
-                let writer = self.write_connect_response();
-                let packet = writer.to_packet();
+                let packet = self
+                    .write_connect_response(address, server_public_key)
+                    .to_packet();
 
                 warn!(">>> HANDSHAKE: ClientConnectRequest from {}", address);
                 warn!("    has_connection: {}", has_connection);
@@ -95,30 +189,26 @@ impl Handshaker for HandshakeManager {
 
                 if has_connection {
                     warn!("    RESULT: Sending SendPacket (NO ConnectEvent will fire!)");
-                    return Ok(HandshakeAction::SendPacket(packet));
+                    return Ok(HandshakeOutcome::SendPacket(packet));
                 } else {
                     let Some(user_key) = self.authenticated_and_identified_users.get(address)
                     else {
                         warn!("Server Error: User not authenticated for: {:?}", address);
-                        return Ok(HandshakeAction::None);
+                        return Ok(HandshakeOutcome::None);
                     };
 
                     warn!(
                         "    RESULT: Sending FinalizeConnection for {:?} (ConnectEvent WILL fire)",
                         user_key
                     );
-                    return Ok(HandshakeAction::FinalizeConnection(*user_key, packet));
+                    return Ok(HandshakeOutcome::FinalizeConnection(*user_key, packet));
                 }
             }
             HandshakeHeader::Disconnect => {
-                if self.verify_disconnect_request(address, reader) {
-                    let user_key = *self
-                        .authenticated_and_identified_users
-                        .get(address)
-                        .expect("Server Error: User not authenticated for disconnect request. Shouldn't be possible.");
-                    return Ok(HandshakeAction::DisconnectUser(user_key));
+                if let Some(user_key) = self.verify_disconnect_request(address, reader) {
+                    return Ok(HandshakeOutcome::DisconnectUser(user_key));
                 } else {
-                    return Ok(HandshakeAction::None);
+                    return Ok(HandshakeOutcome::None);
                 }
             }
             _ => {
@@ -126,24 +216,48 @@ impl Handshaker for HandshakeManager {
                     "Server Error: Unexpected handshake header: {:?} from {}",
                     handshake_header, address
                 );
-                return Ok(HandshakeAction::None);
+                return Ok(HandshakeOutcome::None);
             }
         }
     }
 }
 
 impl HandshakeManager {
-    pub fn new() -> Self {
+    // transports using the simple handshake have their own session handshake,
+    // so no cookies are needed, though rejections last as long as one would
+    pub fn new(
+        schema_hash: u64,
+        encryption_enabled: bool,
+        cookie_config: &HandshakeCookieConfig,
+    ) -> Self {
         Self {
             authenticated_and_identified_users: HashMap::new(),
             authenticated_unidentified_users: HashMap::new(),
             identity_token_map: HashMap::new(),
+            rejected_identity_tokens: HashMap::new(),
+            rejection_lifetime_secs: cookie_config.lifetime.as_secs(),
+            welcome_payloads: HashMap::new(),
+            connection_keys: ConnectionKeys::new(encryption_enabled),
+            schema_hash,
         }
     }
 
+    // forgets rejections whose Client never came to collect them
+    fn expire_rejected_identity_tokens(&mut self, now: u64) {
+        let lifetime_secs = self.rejection_lifetime_secs;
+        self.rejected_identity_tokens
+            .retain(|_, (_, rejected_at)| now.saturating_sub(*rejected_at) <= lifetime_secs);
+    }
+
     // Step 1 of Handshake
-    fn recv_identify_request(&mut self, reader: &mut BitReader) -> Result<IdentityToken, SerdeErr> {
-        IdentityToken::de(reader)
+    fn recv_identify_request(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<(IdentityToken, u64), SerdeErr> {
+        let identity_token = IdentityToken::de(reader)?;
+        let schema_hash = u64::de(reader)?;
+
+        Ok((identity_token, schema_hash))
     }
 
     // Step 2 of Handshake
@@ -155,28 +269,509 @@ impl HandshakeManager {
         writer
     }
 
-    // Step 3 of Handshake
-    fn write_connect_response(&self) -> BitWriter {
-        let mut writer = BitWriter::new();
-        StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
-        HandshakeHeader::ServerConnectResponse.ser(&mut writer);
-        writer
-    }
-
     fn verify_disconnect_request(
         &mut self,
-        _address: &SocketAddr,
+        address: &SocketAddr,
         _reader: &mut BitReader,
-    ) -> bool {
-        // To verify that timestamp hash has been written by this
-        // server instance
+    ) -> Option<UserKey> {
+        // The simple handshake has no signed timestamp to verify, so only
+        // honor disconnect requests from an identified address
+        self.authenticated_and_identified_users
+            .get(address)
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod schema_hash_tests {
+    use std::net::SocketAddr;
+
+    use naia_shared::{
+        handshake::{HandshakeError, HandshakeHeader},
+        BigMapKey, BitReader, BitWriter, Message, Protocol, Serde, StandardHeader,
+    };
+
+    use crate::{
+        handshake::{HandshakeManager, HandshakeOutcome, Handshaker},
+        HandshakeCookieConfig, UserKey,
+    };
+
+    #[derive(Message)]
+    pub struct Chat {
+        pub text: String,
+    }
+
+    #[derive(Message)]
+    pub struct Move {
+        pub x: u8,
+    }
+
+    fn protocol_chat_first() -> Protocol {
+        Protocol::builder()
+            .add_message::<Chat>()
+            .add_message::<Move>()
+            .build()
+    }
+
+    fn protocol_move_first() -> Protocol {
+        Protocol::builder()
+            .add_message::<Move>()
+            .add_message::<Chat>()
+            .build()
+    }
+
+    fn identify_request(identity_token: &String, schema_hash: u64) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientIdentifyRequest.ser(&mut writer);
+        identity_token.ser(&mut writer);
+        schema_hash.ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    #[test]
+    fn registration_order_changes_schema_hash() {
+        assert_eq!(
+            protocol_chat_first().schema_hash(),
+            protocol_chat_first().schema_hash()
+        );
+        assert_ne!(
+            protocol_chat_first().schema_hash(),
+            protocol_move_first().schema_hash()
+        );
+    }
+
+    #[test]
+    fn mismatched_schema_rejects_handshake() {
+        let server_hash = protocol_chat_first().schema_hash();
+        let client_hash = protocol_move_first().schema_hash();
+
+        let mut manager =
+            HandshakeManager::new(server_hash, false, &HandshakeCookieConfig::default());
+        let identity_token = "token".to_string();
+        let user_key = UserKey::from_u64(0);
+        manager.authenticate_user(&identity_token, &user_key);
+
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let bytes = identify_request(&identity_token, client_hash);
+        let mut reader = BitReader::new(&bytes);
+
+        let Ok(HandshakeOutcome::RejectConnection(rejected_user_key, packet, error)) =
+            manager.process_incoming(&address, &mut reader, false)
+        else {
+            panic!("expected handshake to be rejected");
+        };
+        assert_eq!(rejected_user_key, Some(user_key));
+        assert_eq!(
+            error,
+            HandshakeError::SchemaMismatch {
+                server_hash,
+                client_hash,
+            }
+        );
+        assert!(manager.get_user_for_address(&address).is_none());
+
+        // the rejection is readable by the Client
+        let mut reader = BitReader::new(packet.slice());
+        StandardHeader::de(&mut reader).unwrap();
+        assert_eq!(
+            HandshakeHeader::de(&mut reader).unwrap(),
+            HandshakeHeader::ServerRejectResponse
+        );
+        assert_eq!(HandshakeError::de(&mut reader).unwrap(), error);
+    }
+
+    #[test]
+    fn matching_schema_accepts_handshake() {
+        let schema_hash = protocol_chat_first().schema_hash();
+
+        let mut manager =
+            HandshakeManager::new(schema_hash, false, &HandshakeCookieConfig::default());
+        let identity_token = "token".to_string();
+        let user_key = UserKey::from_u64(0);
+        manager.authenticate_user(&identity_token, &user_key);
+
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let bytes = identify_request(&identity_token, schema_hash);
+        let mut reader = BitReader::new(&bytes);
+
+        assert!(matches!(
+            manager.process_incoming(&address, &mut reader, false),
+            Ok(HandshakeOutcome::SendPacket(_))
+        ));
+        assert_eq!(manager.get_user_for_address(&address), Some(user_key));
+    }
+}
+
+#[cfg(test)]
+mod handshake_payload_tests {
+    use std::net::SocketAddr;
+
+    use naia_shared::{
+        handshake::{
+            read_handshake_payload, write_handshake_payload, HandshakeError, HandshakeHeader,
+        },
+        BigMapKey, BitReader, BitWriter, Message, Protocol, Serde, StandardHeader,
+    };
+
+    use crate::{
+        handshake::{HandshakeManager, HandshakeOutcome, Handshaker},
+        HandshakeCookieConfig, UserKey,
+    };
+
+    #[derive(Message)]
+    pub struct Notice {
+        pub text: String,
+    }
+
+    fn protocol() -> Protocol {
+        Protocol::builder().add_message::<Notice>().build()
+    }
+
+    fn notice_text(protocol: &Protocol, payload: &[u8]) -> String {
+        let message = read_handshake_payload(&protocol.message_kinds, payload).unwrap();
+        let notice = message.to_boxed_any().downcast::<Notice>().unwrap();
+        notice.text
+    }
+
+    fn request(header: HandshakeHeader, identity_token: &String, schema_hash: u64) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        header.ser(&mut writer);
+        if header == HandshakeHeader::ClientIdentifyRequest {
+            identity_token.ser(&mut writer);
+            schema_hash.ser(&mut writer);
+        }
+        if header == HandshakeHeader::ClientConnectRequest {
+            // no public key, as encryption is disabled
+            None::<Vec<u8>>.ser(&mut writer);
+        }
+        writer.to_bytes()
+    }
+
+    #[test]
+    fn rejection_carries_code_and_message() {
+        let protocol = protocol();
+        let schema_hash = protocol.schema_hash();
+        let mut manager =
+            HandshakeManager::new(schema_hash, false, &HandshakeCookieConfig::default());
+
+        let payload = write_handshake_payload(
+            &protocol.message_kinds,
+            Box::new(Notice {
+                text: "server full, try again in 30s".to_string(),
+            }),
+        );
+        let identity_token = "token".to_string();
+        manager.reject_identity_token(
+            &identity_token,
+            HandshakeError::Rejected { code: 503, payload },
+        );
+
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let bytes = request(
+            HandshakeHeader::ClientIdentifyRequest,
+            &identity_token,
+            schema_hash,
+        );
+        let mut reader = BitReader::new(&bytes);
+        let Ok(HandshakeOutcome::SendPacket(packet)) =
+            manager.process_incoming(&address, &mut reader, false)
+        else {
+            panic!("expected a reject response");
+        };
+        assert!(manager.get_user_for_address(&address).is_none());
+
+        let mut reader = BitReader::new(packet.slice());
+        StandardHeader::de(&mut reader).unwrap();
+        assert_eq!(
+            HandshakeHeader::de(&mut reader).unwrap(),
+            HandshakeHeader::ServerRejectResponse
+        );
+        let HandshakeError::Rejected { code, payload } = HandshakeError::de(&mut reader).unwrap()
+        else {
+            panic!("expected a Rejected error");
+        };
+        assert_eq!(code, 503);
+        assert_eq!(
+            notice_text(&protocol, &payload.unwrap()),
+            "server full, try again in 30s"
+        );
+    }
+
+    #[test]
+    fn uncollected_rejection_expires() {
+        let protocol = protocol();
+        let schema_hash = protocol.schema_hash();
+        let mut manager =
+            HandshakeManager::new(schema_hash, false, &HandshakeCookieConfig::default());
+        let rejected = || HandshakeError::Rejected {
+            code: 503,
+            payload: None,
+        };
+
+        // a Client rejected a minute ago, which never sent its identity token
+        let stale_token = "stale".to_string();
+        manager.reject_identity_token(&stale_token, rejected());
+        manager
+            .rejected_identity_tokens
+            .get_mut(&stale_token)
+            .unwrap()
+            .1 -= 60;
+
+        // is forgotten once another Client is rejected
+        let fresh_token = "fresh".to_string();
+        manager.reject_identity_token(&fresh_token, rejected());
+        assert!(!manager.rejected_identity_tokens.contains_key(&stale_token));
+        assert!(manager.rejected_identity_tokens.contains_key(&fresh_token));
+
+        // so if it turns up after all, its token is unknown
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let bytes = request(
+            HandshakeHeader::ClientIdentifyRequest,
+            &stale_token,
+            schema_hash,
+        );
+        let mut reader = BitReader::new(&bytes);
+        assert!(matches!(
+            manager.process_incoming(&address, &mut reader, false),
+            Ok(HandshakeOutcome::None)
+        ));
+    }
+
+    #[test]
+    fn connect_response_carries_welcome() {
+        let protocol = protocol();
+        let schema_hash = protocol.schema_hash();
+        let mut manager =
+            HandshakeManager::new(schema_hash, false, &HandshakeCookieConfig::default());
+
+        let identity_token = "token".to_string();
+        let user_key = UserKey::from_u64(0);
+        manager.authenticate_user(&identity_token, &user_key);
+        let payload = write_handshake_payload(
+            &protocol.message_kinds,
+            Box::new(Notice {
+                text: "welcome".to_string(),
+            }),
+        )
+        .unwrap();
+        manager.set_welcome_payload(&user_key, payload);
 
-        todo!()
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let bytes = request(
+            HandshakeHeader::ClientIdentifyRequest,
+            &identity_token,
+            schema_hash,
+        );
+        let mut reader = BitReader::new(&bytes);
+        manager
+            .process_incoming(&address, &mut reader, false)
+            .unwrap();
+
+        let bytes = request(
+            HandshakeHeader::ClientConnectRequest,
+            &identity_token,
+            schema_hash,
+        );
+        let mut reader = BitReader::new(&bytes);
+        let Ok(HandshakeOutcome::FinalizeConnection(connected_user_key, packet)) =
+            manager.process_incoming(&address, &mut reader, false)
+        else {
+            panic!("expected connection to be finalized");
+        };
+        assert_eq!(connected_user_key, user_key);
+
+        let mut reader = BitReader::new(packet.slice());
+        StandardHeader::de(&mut reader).unwrap();
+        assert_eq!(
+            HandshakeHeader::de(&mut reader).unwrap(),
+            HandshakeHeader::ServerConnectResponse
+        );
+        let welcome_payload = Option::<Vec<u8>>::de(&mut reader).unwrap().unwrap();
+        assert_eq!(notice_text(&protocol, &welcome_payload), "welcome");
+        // no public key, as encryption is disabled
+        assert!(Option::<Vec<u8>>::de(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn client_encryption_rejected_when_server_has_none() {
+        let protocol = protocol();
+        let schema_hash = protocol.schema_hash();
+        let mut manager =
+            HandshakeManager::new(schema_hash, false, &HandshakeCookieConfig::default());
+
+        let identity_token = "token".to_string();
+        let user_key = UserKey::from_u64(0);
+        manager.authenticate_user(&identity_token, &user_key);
+
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let bytes = request(
+            HandshakeHeader::ClientIdentifyRequest,
+            &identity_token,
+            schema_hash,
+        );
+        let mut reader = BitReader::new(&bytes);
+        manager
+            .process_incoming(&address, &mut reader, false)
+            .unwrap();
+
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientConnectRequest.ser(&mut writer);
+        Some(vec![7u8; 32]).ser(&mut writer);
+        let bytes = writer.to_bytes();
+        let mut reader = BitReader::new(&bytes);
+        let Ok(HandshakeOutcome::RejectConnection(rejected_user_key, packet, error)) =
+            manager.process_incoming(&address, &mut reader, false)
+        else {
+            panic!("expected connection to be rejected");
+        };
+        assert_eq!(rejected_user_key, Some(user_key));
+        assert_eq!(error, HandshakeError::EncryptionMismatch);
+        assert!(manager.take_packet_cipher(&address).is_none());
+
+        let mut reader = BitReader::new(packet.slice());
+        StandardHeader::de(&mut reader).unwrap();
+        assert_eq!(
+            HandshakeHeader::de(&mut reader).unwrap(),
+            HandshakeHeader::ServerRejectResponse
+        );
+        assert_eq!(HandshakeError::de(&mut reader).unwrap(), error);
     }
 
-    // fn write_reject_response(&self) -> BitWriter {
-    //     let mut writer = BitWriter::new();
-    //     StandardHeader::new(PacketType::ServerRejectResponse, 0, 0, 0).ser(&mut writer);
-    //     writer
-    // }
+    #[test]
+    fn oversized_payload_is_refused() {
+        let protocol = protocol();
+        let payload = write_handshake_payload(
+            &protocol.message_kinds,
+            Box::new(Notice {
+                text: "x".repeat(1024),
+            }),
+        );
+        assert!(payload.is_none());
+    }
+}
+
+#[cfg(test)]
+mod full_handshake_tests {
+    use std::net::SocketAddr;
+
+    use naia_shared::{
+        handshake::HandshakeHeader, BigMapKey, BitReader, BitWriter, Serde, StandardHeader,
+    };
+
+    use crate::{
+        handshake::{HandshakeManager, HandshakeOutcome, Handshaker},
+        HandshakeCookieConfig, UserKey,
+    };
+
+    fn identify_request(identity_token: &String, schema_hash: u64) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientIdentifyRequest.ser(&mut writer);
+        identity_token.ser(&mut writer);
+        schema_hash.ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    fn connect_request() -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientConnectRequest.ser(&mut writer);
+        // no public key, as encryption is disabled
+        None::<Vec<u8>>.ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    fn disconnect_request() -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::Disconnect.ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    fn response_header(bytes: &[u8]) -> HandshakeHeader {
+        let mut reader = BitReader::new(bytes);
+        StandardHeader::de(&mut reader).unwrap();
+        HandshakeHeader::de(&mut reader).unwrap()
+    }
+
+    #[test]
+    fn drives_full_handshake() {
+        let schema_hash = 7;
+        let mut manager =
+            HandshakeManager::new(schema_hash, false, &HandshakeCookieConfig::default());
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let identity_token = "token".to_string();
+        let user_key = UserKey::from_u64(0);
+
+        // an unauthenticated Client is ignored
+        let bytes = identify_request(&identity_token, schema_hash);
+        let mut reader = BitReader::new(&bytes);
+        assert!(matches!(
+            manager.process_incoming(&address, &mut reader, false),
+            Ok(HandshakeOutcome::None)
+        ));
+
+        // once authenticated, the Client is identified
+        manager.authenticate_user(&identity_token, &user_key);
+        manager.set_welcome_payload(&user_key, vec![1, 2, 3]);
+        let mut reader = BitReader::new(&bytes);
+        let Ok(HandshakeOutcome::SendPacket(packet)) =
+            manager.process_incoming(&address, &mut reader, false)
+        else {
+            panic!("expected an identify response");
+        };
+        assert_eq!(
+            response_header(packet.slice()),
+            HandshakeHeader::ServerIdentifyResponse
+        );
+        assert_eq!(manager.get_user_for_address(&address), Some(user_key));
+
+        // the connect request finalizes the connection
+        let bytes = connect_request();
+        let mut reader = BitReader::new(&bytes);
+        let Ok(HandshakeOutcome::FinalizeConnection(connected_user_key, packet)) =
+            manager.process_incoming(&address, &mut reader, false)
+        else {
+            panic!("expected the connection to be finalized");
+        };
+        assert_eq!(connected_user_key, user_key);
+        assert_eq!(
+            packet.slice(),
+            manager
+                .write_connect_response(&address, None)
+                .to_packet()
+                .slice()
+        );
+        let mut reader = BitReader::new(packet.slice());
+        StandardHeader::de(&mut reader).unwrap();
+        assert_eq!(
+            HandshakeHeader::de(&mut reader).unwrap(),
+            HandshakeHeader::ServerConnectResponse
+        );
+        assert_eq!(
+            Option::<Vec<u8>>::de(&mut reader).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+
+        // a resent connect request is answered without connecting again
+        let mut reader = BitReader::new(&bytes);
+        assert!(matches!(
+            manager.process_incoming(&address, &mut reader, true),
+            Ok(HandshakeOutcome::SendPacket(_))
+        ));
+
+        // the Client can disconnect until the User is deleted
+        let bytes = disconnect_request();
+        let mut reader = BitReader::new(&bytes);
+        let Ok(HandshakeOutcome::DisconnectUser(disconnected_user_key)) =
+            manager.process_incoming(&address, &mut reader, false)
+        else {
+            panic!("expected the User to be disconnected");
+        };
+        assert_eq!(disconnected_user_key, user_key);
+
+        manager.delete_user(&user_key, Some(address));
+        let mut reader = BitReader::new(&bytes);
+        assert!(matches!(
+            manager.process_incoming(&address, &mut reader, false),
+            Ok(HandshakeOutcome::None)
+        ));
+    }
 }
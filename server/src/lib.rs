@@ -16,41 +16,70 @@ extern crate cfg_if;
 pub mod transport;
 pub mod shared {
     pub use naia_shared::{
-        default_channels, BigMap, BigMapKey, BitReader, BitWrite, BitWriter, ConstBitLength,
-        FileBitWriter, GlobalResponseId, Random, ResponseReceiveKey, Serde, SerdeErr,
-        SignedInteger, SignedVariableInteger, SocketConfig, UnsignedInteger,
-        UnsignedVariableInteger,
+        default_channels, BigMap, BigMapKey, BitReader, BitWrite, BitWriter, ChannelLatencyStats,
+        ConstBitLength, FileBitWriter, GameInstant, GlobalRequestId, GlobalResponseId,
+        MessageReceiptKey, Random, ResponseReceiveKey, Serde, SerdeEnum, SerdeErr, SignedInteger,
+        SignedVariableInteger, SocketConfig, UnsignedInteger, UnsignedVariableInteger,
     };
 }
 
 pub use naia_shared::SerdeBevyServer as SerdeBevy;
 
+cfg_if! {
+    if #[cfg(feature = "metrics")] {
+        mod metrics;
+        pub use metrics::{ChannelMessageCounts, MetricsSnapshot, PacketTypeCounts};
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "fuzz")] {
+        pub mod fuzz_targets;
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "async_runtime")] {
+        mod runner;
+        pub use runner::{ControlHandle, EventsStream, ServerRunner};
+    }
+}
+
 mod connection;
 mod error;
 mod events;
-mod handshake;
+pub mod handshake;
 mod request;
 mod room;
 mod server;
 mod server_config;
+mod spatial_grid;
 mod time_manager;
 mod user;
 mod user_scope;
 mod world;
 
-pub use connection::tick_buffer_messages::TickBufferMessages;
+pub use connection::{
+    io::{IncomingPacketTap, OutgoingPacketTap},
+    tick_buffer_messages::TickBufferMessages,
+};
 pub use error::NaiaServerError;
 pub use events::{
-    AuthEvent, ConnectEvent, DelegateEntityEvent, DespawnEntityEvent, DisconnectEvent,
-    EntityAuthGrantEvent, EntityAuthResetEvent, ErrorEvent, Events, InsertComponentEvent,
-    MessageEvent, PublishEntityEvent, RemoveComponentEvent, RequestEvent, SpawnEntityEvent,
-    TickEvent, UnpublishEntityEvent, UpdateComponentEvent,
+    AuthEvent, ConnectEvent, ConnectionQualityChangedEvent, ConnectionReadyEvent,
+    DelegateEntityEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthGrantEvent,
+    EntityAuthResetEvent, EntityAuthResetReason, ErrorEvent, Events, HeartbeatPayloadEvent,
+    InputGapEvent, InsertComponentEvent, MessageDeliveredEvent, MessageEvent, MessageRejectedEvent,
+    PublishEntityEvent, RemoveComponentEvent, RequestEvent, RequestTimeoutEvent,
+    RoomDestroyedEvent, ScopeBacklogEvent, SpawnEntityEvent, StreamMessageEvent, TickEvent,
+    TimedMessageEvent, TimedRequestEvent, UnpublishEntityEvent, UpdateComponentEvent,
 };
-pub use room::{RoomKey, RoomMut, RoomRef};
-pub use server::Server;
-pub use server_config::ServerConfig;
+pub use room::{RoomCleanupPolicy, RoomCleanupReason, RoomKey, RoomMut, RoomRef};
+pub use server::{InsertComponentHandler, MessageValidator, Server};
+pub use server_config::{EntityIdRange, HandshakeCookieConfig, ServerConfig};
+pub use spatial_grid::{GridCell, SpatialGrid};
 pub use user::{User, UserKey, UserMut, UserRef};
 pub use user_scope::{UserScopeMut, UserScopeRef};
 pub use world::{
-    entity_mut::EntityMut, entity_owner::EntityOwner, replication_config::ReplicationConfig,
+    entity_export::EntityExport, entity_mut::EntityMut, entity_owner::EntityOwner,
+    replication_config::ReplicationConfig, scope_cache::ScopeCheck, world_snapshot::SnapshotError,
 };
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, time::Duration};
+
+use naia_shared::{BitReader, ChannelKind, ChannelLatencyStats, PacketType, Serde, Timer};
+
+/// A point-in-time view of the Server's internal counters, suitable for
+/// exporting to an external monitoring system such as Prometheus
+#[derive(Clone, Debug, Default)]
+pub struct MetricsSnapshot {
+    /// Packets sent, broken down by PacketType
+    pub packets_sent: PacketTypeCounts,
+    /// Packets received, broken down by PacketType
+    pub packets_received: PacketTypeCounts,
+    /// Total bytes sent over the socket (after compression)
+    pub bytes_sent: u64,
+    /// Total bytes received over the socket (before decompression)
+    pub bytes_received: u64,
+    /// Packets dropped for failing their checksum
+    pub packets_corrupt: u64,
+    /// Current outgoing bandwidth in kbps, or 0.0 if bandwidth monitoring is disabled
+    pub outgoing_bandwidth: f64,
+    /// Current incoming bandwidth in kbps, or 0.0 if bandwidth monitoring is disabled
+    pub incoming_bandwidth: f64,
+    /// Number of Users with an established connection
+    pub connected_users: u64,
+    /// Number of Rooms
+    pub rooms: u64,
+    /// Number of Entities being replicated
+    pub replicated_entities: u64,
+    /// Message counts for each Channel, summed across currently connected Users
+    pub channel_messages: HashMap<ChannelKind, ChannelMessageCounts>,
+    /// Latency percentiles for each latency tracked Channel which has received
+    /// Messages, across currently connected Users
+    pub channel_latencies: HashMap<ChannelKind, ChannelLatencyStats>,
+    /// Number of reliable Messages re-transmitted, summed across currently connected Users
+    pub messages_resent: u64,
+    /// Number of packets never acknowledged, summed across currently connected Users
+    pub packets_dropped: u64,
+}
+
+/// Packet counts broken down by PacketType
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PacketTypeCounts {
+    pub data: u64,
+    pub heartbeat: u64,
+    pub handshake: u64,
+    pub ping: u64,
+    pub pong: u64,
+}
+
+impl PacketTypeCounts {
+    /// Reads the PacketType from the front of a raw payload and records it
+    pub(crate) fn record_payload(&mut self, payload: &[u8]) {
+        let mut reader = BitReader::new(payload);
+        let Ok(packet_type) = PacketType::de(&mut reader) else {
+            return;
+        };
+        match packet_type {
+            PacketType::Data => self.data += 1,
+            PacketType::Heartbeat => self.heartbeat += 1,
+            PacketType::Handshake => self.handshake += 1,
+            PacketType::Ping => self.ping += 1,
+            PacketType::Pong => self.pong += 1,
+        }
+    }
+
+    /// Total number of packets, of any PacketType
+    pub fn total(&self) -> u64 {
+        self.data + self.heartbeat + self.handshake + self.ping + self.pong
+    }
+}
+
+/// Message counts for a single Channel
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChannelMessageCounts {
+    pub sent: u64,
+    pub received: u64,
+}
+
+pub(crate) struct MetricsInterval {
+    timer: Timer,
+    callback: Box<dyn FnMut(&MetricsSnapshot) + Send + Sync>,
+}
+
+impl MetricsInterval {
+    pub fn new(
+        interval: Duration,
+        callback: impl FnMut(&MetricsSnapshot) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            timer: Timer::new(interval),
+            callback: Box::new(callback),
+        }
+    }
+
+    pub fn ringing(&self) -> bool {
+        self.timer.ringing()
+    }
+
+    pub fn fire(&mut self, snapshot: &MetricsSnapshot) {
+        self.timer.reset();
+        (self.callback)(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod packet_type_counts_tests {
+    use naia_shared::{BitWriter, PacketType, Serde, StandardHeader};
+
+    use crate::metrics::PacketTypeCounts;
+
+    fn payload(packet_type: PacketType) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        StandardHeader::new(packet_type, 0, 0, 0).ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    #[test]
+    fn records_each_packet_type() {
+        let mut counts = PacketTypeCounts::default();
+        counts.record_payload(&payload(PacketType::Data));
+        counts.record_payload(&payload(PacketType::Data));
+        counts.record_payload(&payload(PacketType::Heartbeat));
+        counts.record_payload(&payload(PacketType::Pong));
+
+        assert_eq!(counts.data, 2);
+        assert_eq!(counts.heartbeat, 1);
+        assert_eq!(counts.pong, 1);
+        assert_eq!(counts.total(), 4);
+    }
+}
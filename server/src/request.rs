@@ -1,14 +1,23 @@
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap, time::Duration};
 
 use naia_shared::{
-    ChannelKind, GlobalRequestId, GlobalResponseId, LocalResponseId, MessageContainer,
+    ChannelKind, GlobalRequestId, GlobalResponseId, Instant, LocalResponseId, Message,
+    MessageContainer, MessageKind, Request, ResponderResult, ResponseSendKey,
 };
 
 use crate::UserKey;
 
+// how long a late response to a cancelled request is waited for, before it's
+// assumed never to arrive
+const CANCELLED_REQUEST_TTL: Duration = Duration::from_secs(60);
+
 // GlobalRequestManager
 pub struct GlobalRequestManager {
     map: HashMap<GlobalRequestId, (UserKey, Option<MessageContainer>)>,
+    // when each request was cancelled, while its response may still arrive
+    cancelled: HashMap<GlobalRequestId, Instant>,
+    // Request kind & deadline, for requests sent with a timeout
+    deadlines: HashMap<GlobalRequestId, (MessageKind, Instant)>,
     next_id: u64,
 }
 
@@ -16,6 +25,8 @@ impl GlobalRequestManager {
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            cancelled: HashMap::new(),
+            deadlines: HashMap::new(),
             next_id: 0,
         }
     }
@@ -29,6 +40,43 @@ impl GlobalRequestManager {
         id
     }
 
+    /// Gives up on the request if no response has arrived by `deadline`
+    pub(crate) fn set_deadline(
+        &mut self,
+        request_id: &GlobalRequestId,
+        request_kind: MessageKind,
+        deadline: Instant,
+    ) {
+        self.deadlines.insert(*request_id, (request_kind, deadline));
+    }
+
+    /// Cancels every request whose deadline has passed without a response,
+    /// returning the id, kind & recipient of each. Also forgets requests
+    /// cancelled long enough ago that their response isn't coming
+    pub(crate) fn expire_requests(
+        &mut self,
+        now: &Instant,
+    ) -> Vec<(GlobalRequestId, MessageKind, UserKey)> {
+        self.cancelled
+            .retain(|_, cancelled_at| cancelled_at.elapsed(now) < CANCELLED_REQUEST_TTL);
+
+        let expired: Vec<(GlobalRequestId, MessageKind)> = self
+            .deadlines
+            .iter()
+            .filter(|(_, (_, deadline))| !deadline.is_after(now))
+            .map(|(request_id, (request_kind, _))| (*request_id, *request_kind))
+            .collect();
+        let mut output = Vec::new();
+        for (request_id, request_kind) in expired {
+            let Some((user_key, _)) = self.map.get(&request_id) else {
+                continue;
+            };
+            output.push((request_id, request_kind, *user_key));
+            self.cancel_request_id(&request_id);
+        }
+        output
+    }
+
     pub(crate) fn destroy_request_id(
         &mut self,
         request_id: &GlobalRequestId,
@@ -37,18 +85,40 @@ impl GlobalRequestManager {
             return None;
         };
         if response_opt.is_some() {
+            self.deadlines.remove(request_id);
             let (user_key, response_opt) = self.map.remove(request_id).unwrap();
             return Some((user_key, response_opt.unwrap()));
         }
         return None;
     }
 
+    /// Removes a pending request. If the response has not arrived yet, it will be
+    /// dropped on arrival. Returns whether a live request was cancelled.
+    pub(crate) fn cancel_request_id(&mut self, request_id: &GlobalRequestId) -> bool {
+        self.deadlines.remove(request_id);
+        let Some((_, response_opt)) = self.map.remove(request_id) else {
+            return false;
+        };
+        if response_opt.is_none() {
+            self.cancelled.insert(*request_id, Instant::now());
+        }
+        true
+    }
+
     pub(crate) fn receive_response(
         &mut self,
         request_id: &GlobalRequestId,
         response: MessageContainer,
     ) {
-        let (_, response_opt) = self.map.get_mut(request_id).unwrap();
+        if self.cancelled.remove(request_id).is_some() {
+            // request was cancelled, drop the late response
+            return;
+        }
+        self.deadlines.remove(request_id);
+        let Some((_, response_opt)) = self.map.get_mut(request_id) else {
+            // cancelled too long ago to be remembered
+            return;
+        };
         *response_opt = Some(response);
     }
 }
@@ -95,3 +165,132 @@ impl GlobalResponseManager {
         self.map.remove(global_response_id)
     }
 }
+
+// Responder
+/// A handler registered with `Server::register_responder()`, with its Request
+/// & Response types erased
+pub(crate) type Responder =
+    Box<dyn FnMut(&UserKey, &GlobalResponseId, MessageContainer) -> ResponderOutcome + Send + Sync>;
+
+pub(crate) enum ResponderOutcome {
+    Respond(Box<dyn Message>),
+    Deferred,
+    Declined(MessageContainer),
+}
+
+pub(crate) fn erase_responder<Q: Request>(
+    mut handler: impl FnMut(&UserKey, Q) -> ResponderResult<Q::Response> + Send + Sync + 'static,
+) -> Responder {
+    Box::new(move |user_key, response_id, container| {
+        // keep the container, in case the Request falls through
+        let request: Q = Box::<dyn Any + 'static>::downcast::<Q>(container.clone().to_boxed_any())
+            .ok()
+            .map(|boxed_q| *boxed_q)
+            .unwrap();
+        match handler(user_key, request) {
+            ResponderResult::Respond(response) => ResponderOutcome::Respond(Box::new(response)),
+            ResponderResult::Defer(defer) => {
+                defer(ResponseSendKey::new(*response_id));
+                ResponderOutcome::Deferred
+            }
+            ResponderResult::DeclineToHandle => ResponderOutcome::Declined(container),
+        }
+    })
+}
+
+#[cfg(test)]
+mod cancel_request_tests {
+    use naia_shared::{
+        BigMapKey, FakeEntityConverter, Instant, Message, MessageContainer, MessageKind,
+    };
+
+    use crate::{
+        request::{GlobalRequestManager, CANCELLED_REQUEST_TTL},
+        UserKey,
+    };
+
+    #[derive(Message)]
+    pub struct TestResponse {
+        pub value: u8,
+    }
+
+    fn test_user_key() -> UserKey {
+        UserKey::from_u64(0)
+    }
+
+    fn test_response() -> MessageContainer {
+        MessageContainer::from_write(
+            Box::new(TestResponse { value: 7 }),
+            &mut FakeEntityConverter,
+        )
+    }
+
+    #[test]
+    fn cancelled_request_drops_late_response() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id(&test_user_key());
+
+        assert!(manager.cancel_request_id(&request_id));
+        manager.receive_response(&request_id, test_response());
+
+        assert!(manager.destroy_request_id(&request_id).is_none());
+    }
+
+    #[test]
+    fn cancel_unknown_request_returns_false() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id(&test_user_key());
+        manager.receive_response(&request_id, test_response());
+        assert!(manager.destroy_request_id(&request_id).is_some());
+
+        assert!(!manager.cancel_request_id(&request_id));
+    }
+
+    #[test]
+    fn expired_request_is_cancelled() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id(&test_user_key());
+        let now = Instant::now();
+        manager.set_deadline(&request_id, MessageKind::of::<TestResponse>(), now.clone());
+
+        let expired = manager.expire_requests(&now);
+        assert_eq!(expired.len(), 1);
+        assert!(expired[0].0 == request_id);
+        assert!(expired[0].2 == test_user_key());
+
+        manager.receive_response(&request_id, test_response());
+        assert!(manager.destroy_request_id(&request_id).is_none());
+        assert!(manager.expire_requests(&now).is_empty());
+    }
+
+    #[test]
+    fn cancelled_request_is_forgotten_if_no_response_arrives() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id(&test_user_key());
+        assert!(manager.cancel_request_id(&request_id));
+
+        let mut now = Instant::now();
+        assert!(manager.expire_requests(&now).is_empty());
+        assert_eq!(manager.cancelled.len(), 1);
+
+        now.add_millis(CANCELLED_REQUEST_TTL.as_millis() as u32);
+        assert!(manager.expire_requests(&now).is_empty());
+        assert!(manager.cancelled.is_empty());
+
+        // a response which turns up after all is still dropped
+        manager.receive_response(&request_id, test_response());
+        assert!(manager.destroy_request_id(&request_id).is_none());
+    }
+
+    #[test]
+    fn answered_request_does_not_expire() {
+        let mut manager = GlobalRequestManager::new();
+        let request_id = manager.create_request_id(&test_user_key());
+        let now = Instant::now();
+        manager.set_deadline(&request_id, MessageKind::of::<TestResponse>(), now.clone());
+        manager.receive_response(&request_id, test_response());
+
+        assert!(manager.expire_requests(&now).is_empty());
+        assert!(manager.destroy_request_id(&request_id).is_some());
+    }
+}
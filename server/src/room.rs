@@ -8,7 +8,7 @@ use naia_shared::{BigMapKey, Channel, ChannelKind, Message};
 use super::user::UserKey;
 
 // RoomKey
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub struct RoomKey(u64);
 
 impl BigMapKey for RoomKey {
@@ -21,11 +21,35 @@ impl BigMapKey for RoomKey {
     }
 }
 
+// RoomCleanupPolicy
+/// What happens to the Entities in a Room when it is destroyed automatically
+/// after its last User leaves
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RoomCleanupPolicy {
+    /// Entities are removed from the Room but otherwise left alone
+    #[default]
+    LeaveEntities,
+    /// Server-owned Entities in the Room are despawned
+    DespawnEntities,
+}
+
+// RoomCleanupReason
+/// Why a Room was destroyed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoomCleanupReason {
+    /// The Room was destroyed through `RoomMut::destroy()`
+    Manual,
+    /// The Room was set to auto-destroy and its last User left or disconnected
+    Empty,
+}
+
 // Room
 pub struct Room<E: Copy + Eq + Hash> {
     users: HashSet<UserKey>,
     entities: HashSet<E>,
     entity_removal_queue: VecDeque<(UserKey, E)>,
+    auto_destroy_policy: Option<RoomCleanupPolicy>,
+    paused: bool,
 }
 
 impl<E: Copy + Eq + Hash> Room<E> {
@@ -34,9 +58,31 @@ impl<E: Copy + Eq + Hash> Room<E> {
             users: HashSet::new(),
             entities: HashSet::new(),
             entity_removal_queue: VecDeque::new(),
+            auto_destroy_policy: None,
+            paused: false,
         }
     }
 
+    // Cleanup
+
+    pub(crate) fn auto_destroy_policy(&self) -> Option<RoomCleanupPolicy> {
+        self.auto_destroy_policy
+    }
+
+    pub(crate) fn set_auto_destroy_policy(&mut self, policy: Option<RoomCleanupPolicy>) {
+        self.auto_destroy_policy = policy;
+    }
+
+    // Replication
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub(crate) fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
     // Users
 
     pub(crate) fn has_user(&self, user_key: &UserKey) -> bool {
@@ -167,6 +213,24 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> RoomMut<'s, E> {
         self.server.room_destroy(&self.key);
     }
 
+    /// Sets whether the Room is destroyed once its last User leaves or
+    /// disconnects, leaving its Entities alone. The Room is destroyed during
+    /// the next `Server::receive()`, emitting a `RoomDestroyedEvent`
+    pub fn set_auto_destroy_when_empty(&mut self, auto_destroy: bool) -> &mut Self {
+        let policy = auto_destroy.then_some(RoomCleanupPolicy::default());
+        self.server.room_set_auto_destroy(&self.key, policy);
+
+        self
+    }
+
+    /// Like `set_auto_destroy_when_empty(true)`, with the given policy applied
+    /// to the Room's Entities when it is destroyed
+    pub fn set_auto_destroy_policy(&mut self, policy: RoomCleanupPolicy) -> &mut Self {
+        self.server.room_set_auto_destroy(&self.key, Some(policy));
+
+        self
+    }
+
     // Users
 
     pub fn has_user(&self, user_key: &UserKey) -> bool {
@@ -0,0 +1,233 @@
+use std::{
+    hash::Hash,
+    pin::Pin,
+    sync::{mpsc, Arc, Mutex},
+    task::{Context, Poll},
+    thread,
+    time::Duration,
+};
+
+use futures_core::Stream;
+use log::warn;
+use tokio::sync::{mpsc as async_mpsc, oneshot};
+
+use naia_shared::{Channel, Message, ProxyWorldMut};
+
+use crate::{Events, RoomKey, Server, TickEvent, UserKey};
+
+type Command<E> = Box<dyn FnOnce(&mut Server<E>) + Send>;
+
+/// Drives a Server on a thread of its own, so that it can be used from async
+/// code without a hand-written update loop.
+///
+/// The runner receives packets whenever `poll_interval` passes or the
+/// Server's next Tick is due, whichever is sooner, & sends all updates on
+/// each Tick. The World is only locked while it does so, and each non-empty
+/// batch of Events is handed to the returned [`EventsStream`]. Commands given
+/// to the [`ControlHandle`] run on the runner's thread, in order, before its
+/// next update.
+///
+/// ```no_run
+/// # use std::{sync::{Arc, Mutex}, time::Duration};
+/// # use naia_server::{ConnectEvent, Server, ServerRunner};
+/// # async fn run(server: Server<naia_demo_world::Entity>) {
+/// let world = Arc::new(Mutex::new(naia_demo_world::World::default()));
+/// let (mut events, control) = ServerRunner::spawn(server, world, Duration::from_millis(3));
+/// while let Some(mut events) = events.next().await {
+///     for user_key in events.read::<ConnectEvent>() {
+///         let room_key = control.make_room().await.unwrap();
+///         control.execute(move |server| {
+///             server.room_mut(&room_key).add_user(&user_key);
+///         });
+///     }
+/// }
+/// # }
+/// ```
+pub struct ServerRunner;
+
+impl ServerRunner {
+    /// Moves the Server onto a new thread which drives it until shut down,
+    /// either through the ControlHandle or by dropping the EventsStream
+    pub fn spawn<E, W>(
+        server: Server<E>,
+        world: Arc<Mutex<W>>,
+        poll_interval: Duration,
+    ) -> (EventsStream<E>, ControlHandle<E>)
+    where
+        E: Copy + Eq + Hash + Send + Sync + 'static,
+        W: ProxyWorldMut<E> + Send + 'static,
+    {
+        let (events_sender, events_receiver) = async_mpsc::unbounded_channel();
+        let (command_sender, command_receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            run(
+                server,
+                world,
+                poll_interval,
+                events_sender,
+                command_receiver,
+            )
+        });
+
+        (
+            EventsStream {
+                receiver: events_receiver,
+            },
+            ControlHandle {
+                sender: command_sender,
+            },
+        )
+    }
+}
+
+fn run<E, W>(
+    mut server: Server<E>,
+    world: Arc<Mutex<W>>,
+    poll_interval: Duration,
+    events_sender: async_mpsc::UnboundedSender<Events<E>>,
+    command_receiver: mpsc::Receiver<RunnerCommand<E>>,
+) where
+    E: Copy + Eq + Hash + Send + Sync + 'static,
+    W: ProxyWorldMut<E>,
+{
+    loop {
+        // commands from dropped ControlHandles are simply done with
+        while let Ok(command) = command_receiver.try_recv() {
+            match command {
+                RunnerCommand::Execute(command) => command(&mut server),
+                RunnerCommand::Shutdown => return,
+            }
+        }
+
+        let events = {
+            let Ok(mut world) = world.lock() else {
+                warn!("ServerRunner stopped, as its World's lock was poisoned");
+                return;
+            };
+            let events = server.receive(world.proxy_mut());
+            // updates go out once per Tick, as they would from a manual loop
+            if events.has::<TickEvent>() {
+                server.send_all_updates(world.proxy_mut());
+            }
+            events
+        };
+
+        if !events.is_empty() && events_sender.send(events).is_err() {
+            // nothing is listening for Events anymore
+            return;
+        }
+        if events_sender.is_closed() {
+            return;
+        }
+
+        thread::sleep(poll_interval.min(server.duration_until_next_tick()));
+    }
+}
+
+enum RunnerCommand<E: Copy + Eq + Hash + Send + Sync> {
+    Execute(Command<E>),
+    Shutdown,
+}
+
+/// The Events of a [`ServerRunner`], one non-empty batch per update. Ends
+/// once the runner shuts down
+pub struct EventsStream<E: Copy> {
+    receiver: async_mpsc::UnboundedReceiver<Events<E>>,
+}
+
+impl<E: Copy> EventsStream<E> {
+    /// Waits for the next batch of Events, or None once the runner has shut
+    /// down
+    pub async fn next(&mut self) -> Option<Events<E>> {
+        self.receiver.recv().await
+    }
+}
+
+impl<E: Copy> Stream for EventsStream<E> {
+    type Item = Events<E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Queues commands for the Server driven by a [`ServerRunner`]. Cheap to
+/// clone, & safe to use from any thread or task. Commands given after the
+/// runner has shut down are dropped
+pub struct ControlHandle<E: Copy + Eq + Hash + Send + Sync> {
+    sender: mpsc::Sender<RunnerCommand<E>>,
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync> Clone for ControlHandle<E> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync + 'static> ControlHandle<E> {
+    /// Runs the given closure on the runner's thread, before its next update
+    pub fn execute(&self, command: impl FnOnce(&mut Server<E>) + Send + 'static) {
+        let _ = self.sender.send(RunnerCommand::Execute(Box::new(command)));
+    }
+
+    /// Runs the given closure on the runner's thread, & waits for what it
+    /// returns. None if the runner shut down first
+    pub async fn query<R: Send + 'static>(
+        &self,
+        query: impl FnOnce(&mut Server<E>) -> R + Send + 'static,
+    ) -> Option<R> {
+        let (sender, receiver) = oneshot::channel();
+        self.execute(move |server| {
+            let _ = sender.send(query(server));
+        });
+        receiver.await.ok()
+    }
+
+    /// Queues up a Message to be sent to the given User
+    pub fn send_message<C: Channel, M: Message>(&self, user_key: &UserKey, message: M) {
+        let user_key = *user_key;
+        self.execute(move |server| server.send_message::<C, M>(&user_key, &message));
+    }
+
+    /// Queues up a Message to be sent to every connected User
+    pub fn broadcast_message<C: Channel, M: Message>(&self, message: M) {
+        self.execute(move |server| server.broadcast_message::<C, M>(&message));
+    }
+
+    /// Accepts the connection of a User which sent an auth Message
+    pub fn accept_connection(&self, user_key: &UserKey) {
+        let user_key = *user_key;
+        self.execute(move |server| server.accept_connection(&user_key));
+    }
+
+    /// Rejects the connection of a User which sent an auth Message
+    pub fn reject_connection(&self, user_key: &UserKey) {
+        let user_key = *user_key;
+        self.execute(move |server| server.reject_connection(&user_key));
+    }
+
+    /// Disconnects the given User, if they still exist
+    pub fn disconnect_user(&self, user_key: &UserKey) {
+        let user_key = *user_key;
+        self.execute(move |server| {
+            if server.user_exists(&user_key) {
+                server.user_mut(&user_key).disconnect();
+            }
+        });
+    }
+
+    /// Makes a new Room, returning its key. None if the runner shut down
+    /// first
+    pub async fn make_room(&self) -> Option<RoomKey> {
+        self.query(|server| server.make_room().key()).await
+    }
+
+    /// Stops the runner after any commands queued before this one, which ends
+    /// its EventsStream
+    pub fn shutdown(&self) {
+        let _ = self.sender.send(RunnerCommand::Shutdown);
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     any::Any,
     collections::{hash_set::Iter, HashMap, HashSet},
     hash::Hash,
+    io::{Read, Write},
     net::SocketAddr,
     panic,
     time::Duration,
@@ -10,38 +11,70 @@ use std::{
 use log::{info, warn};
 
 use naia_shared::{
-    BigMap, BitReader, BitWriter, Channel, ChannelKind, ComponentKind,
-    EntityAndGlobalEntityConverter, EntityAndLocalEntityConverter, EntityAuthStatus,
-    EntityConverterMut, EntityDoesNotExistError, EntityEventMessage, EntityResponseEvent,
-    FakeEntityConverter, GlobalEntity, GlobalRequestId, GlobalResponseId, GlobalWorldManagerType,
-    Instant, Message, MessageContainer, PacketType, Protocol, RemoteEntity, Replicate,
-    ReplicatedComponent, Request, Response, ResponseReceiveKey, ResponseSendKey, Serde, SerdeErr,
-    SharedGlobalWorldManager, SocketConfig, StandardHeader, SystemChannel, Tick, Timer,
-    WorldMutType, WorldRefType,
+    handshake::{write_handshake_payload, HandshakeError, MAX_HANDSHAKE_PAYLOAD_BYTES},
+    BigMap, BitReader, BitWriter, Channel, ChannelKind, ChannelLatencyStats, ComponentKind,
+    CompressionStats, ConnectionParams, ConnectionQuality, EntityAndGlobalEntityConverter,
+    EntityAndLocalEntityConverter, EntityAuthStatus, EntityChannelDebug, EntityConverterMut,
+    EntityDoesNotExistError, EntityEventMessage, EntityResponseEvent, FakeEntityConverter,
+    FileBitWriter, GameInstant, GlobalEntity, GlobalRequestId, GlobalResponseId,
+    GlobalWorldManagerType, HeartbeatPayload, HostWorldEvents, Instant, Message, MessageContainer,
+    MessageKind, MessageKinds, MessageReceiptKey, PacketType, Protocol, RemoteEntity, Replicate,
+    ReplicatedComponent, Request, ResponderResult, Response, ResponseReceiveKey, ResponseSendKey,
+    ScopeBacklog, Serde, SerdeErr, SharedGlobalWorldManager, SocketConfig, StandardHeader,
+    StreamChannel, StreamMessage, SystemChannel, Tick, Timer, WorldMutType, WorldRefType,
 };
 
 use super::{
     error::NaiaServerError,
-    events::Events,
-    room::{Room, RoomKey, RoomMut, RoomRef},
+    events::{EntityAuthResetReason, Events},
+    room::{Room, RoomCleanupPolicy, RoomCleanupReason, RoomKey, RoomMut, RoomRef},
     server_config::ServerConfig,
     user::{User, UserKey, UserMut, UserRef},
     user_scope::{UserScopeMut, UserScopeRef},
 };
+#[cfg(feature = "metrics")]
+use crate::metrics::{ChannelMessageCounts, MetricsInterval, MetricsSnapshot};
 use crate::{
-    connection::{connection::Connection, io::Io, tick_buffer_messages::TickBufferMessages},
-    handshake::{HandshakeAction, HandshakeManager, Handshaker},
-    request::{GlobalRequestManager, GlobalResponseManager},
+    connection::{
+        connection::Connection,
+        io::{IncomingPacketTap, Io, OutgoingPacketTap},
+        tick_buffer_messages::TickBufferMessages,
+    },
+    handshake::{HandshakeManager, HandshakeOutcome, Handshaker},
+    request::{
+        erase_responder, GlobalRequestManager, GlobalResponseManager, Responder, ResponderOutcome,
+    },
     time_manager::TimeManager,
     transport::{AuthReceiver, AuthSender, Socket},
     world::{
-        entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
-        entity_room_map::EntityRoomMap, entity_scope_map::EntityScopeMap,
-        global_world_manager::GlobalWorldManager, server_auth_handler::AuthOwner,
+        entity_export::{
+            EntityExport, EntityExportContents, ExportedAuthority, ExportedComponent,
+            UnmappedEntityConverter,
+        },
+        entity_mut::EntityMut,
+        entity_owner::EntityOwner,
+        entity_ref::EntityRef,
+        entity_room_map::EntityRoomMap,
+        entity_scope_map::EntityScopeMap,
+        global_world_manager::GlobalWorldManager,
+        scope_cache::{ScopeCache, ScopeCheck},
+        scope_dependencies::ScopeDependencies,
+        server_auth_handler::AuthOwner,
+        world_snapshot::{read_snapshot, write_snapshot, SnapshotError},
     },
     ReplicationConfig,
 };
 
+/// Called with the User & Entity whenever a Client inserts a Component of the
+/// kind it was registered for, see `Server::on_insert_component()`
+pub type InsertComponentHandler<E> = Box<dyn FnMut(&UserKey, &E) + Send + Sync>;
+
+/// Called with each Message a Client sends, along with the User & Channel it
+/// was sent by, before it is reported. Returns whether the Message should be
+/// accepted, see `Server::set_message_validator()`
+pub type MessageValidator =
+    Box<dyn Fn(&UserKey, &ChannelKind, &MessageContainer) -> bool + Send + Sync>;
+
 /// A server that uses either UDP or WebRTC communication to send/receive
 /// messages to/from connected clients, and syncs registered entities to
 /// clients to whom they are in-scope
@@ -50,28 +83,48 @@ pub struct Server<E: Copy + Eq + Hash + Send + Sync> {
     server_config: ServerConfig,
     protocol: Protocol,
     io: Io,
-    auth_io: Option<(Box<dyn AuthSender>, Box<dyn AuthReceiver>)>,
+    // one per Socket, in the order they were listened on
+    auth_io: Vec<(Box<dyn AuthSender>, Box<dyn AuthReceiver>)>,
     heartbeat_timer: Timer,
+    heartbeat_payload: Option<HeartbeatPayload>,
     timeout_timer: Timer,
     ping_timer: Timer,
     handshake_manager: Box<dyn Handshaker>,
+    // State of the rng behind the Server's internal choices
+    rng_state: u64,
     // Users
     users: BigMap<UserKey, User>,
     user_connections: HashMap<SocketAddr, Connection<E>>,
     user_key_to_addr: HashMap<UserKey, SocketAddr>,
     // Rooms
     rooms: BigMap<RoomKey, Room<E>>,
+    emptied_rooms: HashSet<RoomKey>,
     // Entities
+    despawns_after_ack: HashSet<E>,
+    // per-Entity overrides of `ServerConfig::auth_idle_timeout`
+    auth_idle_timeouts: HashMap<E, Option<Duration>>,
+    insert_component_handlers: HashMap<ComponentKind, Vec<InsertComponentHandler<E>>>,
+    // Messages
+    message_validator: Option<MessageValidator>,
     entity_room_map: EntityRoomMap<E>,
     entity_scope_map: EntityScopeMap<E>,
+    scope_cache: ScopeCache<E>,
+    scope_dependencies: ScopeDependencies<E>,
     global_world_manager: GlobalWorldManager<E>,
     // Events
     incoming_events: Events<E>,
     // Requests/Responses
     global_request_manager: GlobalRequestManager,
     global_response_manager: GlobalResponseManager,
+    responders: HashMap<(ChannelKind, MessageKind), Responder>,
+    // Message receipts
+    next_receipt_id: u64,
+    undelivered_receipts: HashSet<MessageReceiptKey>,
     // Ticks
     time_manager: TimeManager,
+    // Metrics
+    #[cfg(feature = "metrics")]
+    metrics_interval: Option<MetricsInterval>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
@@ -80,11 +133,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         let mut protocol: Protocol = protocol.into();
         protocol.lock();
 
+        if let Some(clock) = &server_config.connection.clock {
+            naia_shared::set_thread_clock(Some(clock.clone()));
+        }
+
         let time_manager = TimeManager::new(protocol.tick_interval);
 
+        let schema_hash = protocol.schema_hash();
+
         let io = Io::new(
             &server_config.connection.bandwidth_measure_duration,
             &protocol.compression,
+            server_config.connection.encryption.is_some(),
+            server_config.connection.packet_checksums,
         );
 
         Self {
@@ -93,39 +154,80 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             protocol,
             // Connection
             io,
-            auth_io: None,
+            auth_io: Vec::new(),
             heartbeat_timer: Timer::new(server_config.connection.heartbeat_interval),
+            heartbeat_payload: None,
             timeout_timer: Timer::new(server_config.connection.disconnection_timeout_duration),
             ping_timer: Timer::new(server_config.ping.ping_interval),
-            handshake_manager: Box::new(HandshakeManager::new()),
+            handshake_manager: Box::new(HandshakeManager::new(
+                schema_hash,
+                server_config.connection.encryption.is_some(),
+                &server_config.handshake_cookie,
+            )),
+            rng_state: fastrand::u64(..),
             // Users
             users: BigMap::new(),
             user_connections: HashMap::new(),
             user_key_to_addr: HashMap::new(),
             // Rooms
             rooms: BigMap::new(),
+            emptied_rooms: HashSet::new(),
             // Entities
+            despawns_after_ack: HashSet::new(),
+            auth_idle_timeouts: HashMap::new(),
+            insert_component_handlers: HashMap::new(),
+            message_validator: None,
             entity_room_map: EntityRoomMap::new(),
             entity_scope_map: EntityScopeMap::new(),
-            global_world_manager: GlobalWorldManager::new(),
+            scope_cache: ScopeCache::new(),
+            scope_dependencies: ScopeDependencies::new(),
+            global_world_manager: GlobalWorldManager::new(&server_config.entity_id_range),
             // Events
             incoming_events: Events::new(),
             // Requests/Responses
             global_request_manager: GlobalRequestManager::new(),
             global_response_manager: GlobalResponseManager::new(),
+            responders: HashMap::new(),
+            // Message receipts
+            next_receipt_id: 0,
+            undelivered_receipts: HashSet::new(),
             // Ticks
             time_manager,
+            // Metrics
+            #[cfg(feature = "metrics")]
+            metrics_interval: None,
         }
     }
 
-    /// Listen at the given addresses
+    /// Listen at the given addresses. May be called again with other
+    /// Sockets, i.e. to accept native Clients over UDP and browser Clients
+    /// over WebRTC at the same time, in which case each Client is sent
+    /// packets through the Socket its handshake arrived on. Sockets are
+    /// indexed in the order they were listened on, or may be named with
+    /// `listen_named()`, and the addresses of Clients must not collide across
+    /// them
     pub fn listen<S: Into<Box<dyn Socket>>>(&mut self, socket: S) {
+        self.listen_inner(socket);
+    }
+
+    /// Listen at the given addresses, like `listen()`, naming the Socket so
+    /// that it can be referred to without tracking the order Sockets were
+    /// listened on, i.e. `listen_named("webrtc", socket)`. Panics if another
+    /// Socket already has the name
+    pub fn listen_named<S: Into<Box<dyn Socket>>>(&mut self, name: &str, socket: S) {
+        let socket_index = self.listen_inner(socket);
+        self.io.name_socket(socket_index, name.to_string());
+    }
+
+    fn listen_inner<S: Into<Box<dyn Socket>>>(&mut self, socket: S) -> usize {
         let boxed_socket: Box<dyn Socket> = socket.into();
         let (auth_sender, auth_receiver, packet_sender, packet_receiver) = boxed_socket.listen();
 
-        self.io.load(packet_sender, packet_receiver);
+        let socket_index = self.io.load(packet_sender, packet_receiver);
 
-        self.auth_io = Some((auth_sender, auth_receiver));
+        self.auth_io.push((auth_sender, auth_receiver));
+
+        socket_index
     }
 
     /// Returns whether or not the Server has initialized correctly and is
@@ -134,19 +236,96 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.io.is_loaded()
     }
 
+    /// Returns the number of Sockets the Server is listening on
+    pub fn sockets_count(&self) -> usize {
+        self.io.sockets_count()
+    }
+
+    /// Returns the index of the Socket the given User's Client is connected
+    /// through, in the order the Sockets were listened on
+    pub fn user_socket_index(&self, user_key: &UserKey) -> Option<usize> {
+        let user = self.users.get(user_key)?;
+        self.io.client_socket(&user.address_opt()?)
+    }
+
+    /// Returns the name of the Socket the given User's Client is connected
+    /// through, if it was listened on with `listen_named()`
+    pub fn user_socket_name(&self, user_key: &UserKey) -> Option<&str> {
+        let socket_index = self.user_socket_index(user_key)?;
+        self.io.socket_name(socket_index)
+    }
+
+    /// Returns the index of the Socket listened on with the given name, i.e.
+    /// to pass to `outgoing_bandwidth_through_socket()`
+    pub fn socket_index(&self, name: &str) -> Option<usize> {
+        self.io.socket_index(name)
+    }
+
     /// Returns socket config
     pub fn socket_config(&self) -> &SocketConfig {
         &self.protocol.socket
     }
 
+    /// Installs a callback which observes the bytes of every outgoing packet
+    /// just as they are handed to the Socket, or removes it if None. Useful
+    /// for recording replays or proxying traffic
+    pub fn set_outgoing_tap(&mut self, tap: Option<OutgoingPacketTap>) {
+        self.io.set_outgoing_tap(tap);
+    }
+
+    /// Installs a callback which observes the bytes of every incoming packet
+    /// just as they are read from the Socket, or removes it if None
+    pub fn set_incoming_tap(&mut self, tap: Option<IncomingPacketTap>) {
+        self.io.set_incoming_tap(tap);
+    }
+
+    /// Seeds the random number generator behind the Server's internal
+    /// choices, such as the order in which Users are sent packets each call
+    /// to `send_all_updates()`. Two Servers given the same seed & the same
+    /// inputs make the same choices, which is useful for reproducible tests
+    /// and lockstep simulations. Encryption keys & handshake secrets are
+    /// never drawn from this generator
+    pub fn set_rng_seed(&mut self, seed: u64) {
+        self.rng_state = seed;
+    }
+
     /// Must be called regularly, maintains connection to and receives messages
     /// from all Clients
-    pub fn receive<W: WorldMutType<E>>(&mut self, world: W) -> Events<E> {
+    pub fn receive<W: WorldMutType<E>>(&mut self, mut world: W) -> Events<E> {
         let now = Instant::now();
 
         // Need to run this to maintain connection with all clients, and receive packets
         // until none left
-        self.maintain_socket(world, &now);
+        self.maintain_socket(&mut world, &now);
+
+        // grade each connection, now that this call's acks have been processed
+        self.handle_connection_quality();
+        self.handle_scope_backlogs();
+
+        // report Messages sent with a receipt which are now fully delivered
+        self.handle_delivered_receipts();
+
+        // answer the Requests which registered responders handle
+        self.handle_responders();
+
+        // give up on requests whose responses did not arrive in time
+        for (request_id, request_kind, user_key) in
+            self.global_request_manager.expire_requests(&now)
+        {
+            self.incoming_events
+                .push_request_timeout(&user_key, &request_kind, request_id);
+        }
+
+        // take back authority from Users which have gone quiet about their
+        // delegated Entities
+        self.reclaim_idle_authority(&now);
+
+        // destroy Rooms emptied since the last call, now that disconnected Users
+        // have been cleaned up
+        self.destroy_empty_rooms(&mut world);
+
+        // despawn Entities whose last updates were acknowledged by this call
+        self.despawn_acked_entities(&mut world);
 
         // tick event
         if self.time_manager.recv_server_tick(&now) {
@@ -154,6 +333,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 .push_tick(self.time_manager.current_tick());
         }
 
+        #[cfg(feature = "metrics")]
+        self.handle_metrics_interval();
+
         // return all received messages and reset the buffer
         std::mem::replace(&mut self.incoming_events, Events::<E>::new())
     }
@@ -168,6 +350,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             return;
         };
         let auth_addr = user.take_auth_address();
+        let auth_socket = user.auth_socket();
 
         // info!("adding authenticated user {}", &auth_addr);
         let identity_token = naia_shared::generate_identity_token();
@@ -177,7 +360,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
 
         let (auth_sender, _) = self
             .auth_io
-            .as_mut()
+            .get(auth_socket)
             .expect("Auth should be set up by this point");
         if auth_sender.accept(&auth_addr, &identity_token).is_err() {
             info!(
@@ -192,15 +375,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     /// Rejects an incoming Client User, terminating their attempt to establish
     /// a connection with the Server
     pub fn reject_connection(&mut self, user_key: &UserKey) {
+        self.reject_with_status(user_key, 401);
+    }
+
+    /// Rejects an incoming Client User like `reject_connection`, answering their
+    /// auth request with the given HTTP status code (e.g. 403 for a valid but
+    /// unauthorized token). Clients treat 401 as a rejection and any other
+    /// code as an error
+    pub fn reject_with_status(&mut self, user_key: &UserKey, status: u16) {
         if let Some(user) = self.users.get_mut(user_key) {
             let auth_addr = user.take_auth_address();
+            let auth_socket = user.auth_socket();
 
             // info!("rejecting authenticated user {:?}", &auth_addr);
             let (auth_sender, _) = self
                 .auth_io
-                .as_mut()
+                .get(auth_socket)
                 .expect("Auth should be set up by this point");
-            if auth_sender.reject(&auth_addr).is_err() {
+            if auth_sender.reject(&auth_addr, status).is_err() {
                 warn!(
                     "Server Error: Cannot send auth reject message to {:?}",
                     &auth_addr
@@ -212,6 +404,83 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    /// Accepts an incoming Client User like `accept_connection`, additionally
+    /// delivering a welcome Message to the Client before its ConnectEvent fires.
+    /// Returns an error if the Message does not fit within a single packet.
+    pub fn accept_connection_with<M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        welcome: Option<M>,
+    ) -> Result<(), NaiaServerError> {
+        if !self.users.contains_key(user_key) {
+            info!("unknown user is finalizing connection...");
+            return Ok(());
+        }
+        if let Some(welcome) = welcome {
+            let payload = self.handshake_payload(welcome)?;
+            self.handshake_manager
+                .set_welcome_payload(user_key, payload);
+        }
+
+        self.accept_connection(user_key);
+
+        Ok(())
+    }
+
+    /// Rejects an incoming Client User like `reject_connection`, but hands the
+    /// Client a code and an optional Message describing why (e.g. "server full,
+    /// try again in 30s"). Returns an error if the Message does not fit within
+    /// a single packet, in which case the User is left untouched.
+    pub fn reject_connection_with<M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        code: u16,
+        message: Option<M>,
+    ) -> Result<(), NaiaServerError> {
+        let payload = match message {
+            Some(message) => Some(self.handshake_payload(message)?),
+            None => None,
+        };
+        let Some(user) = self.users.get_mut(user_key) else {
+            return Ok(());
+        };
+        let auth_addr = user.take_auth_address();
+        let auth_socket = user.auth_socket();
+
+        // the rejection is carried by the handshake, so the Client still needs
+        // an identity token to begin one
+        let identity_token = naia_shared::generate_identity_token();
+        self.handshake_manager
+            .reject_identity_token(&identity_token, HandshakeError::Rejected { code, payload });
+
+        let (auth_sender, _) = self
+            .auth_io
+            .get(auth_socket)
+            .expect("Auth should be set up by this point");
+        if auth_sender.accept(&auth_addr, &identity_token).is_err() {
+            warn!(
+                "Server Error: Cannot send auth reject message to {:?}",
+                &auth_addr
+            );
+        }
+
+        self.user_delete(user_key);
+
+        Ok(())
+    }
+
+    fn handshake_payload<M: Message>(&self, message: M) -> Result<Vec<u8>, NaiaServerError> {
+        let Some(payload) =
+            write_handshake_payload(&self.protocol.message_kinds, Box::new(message))
+        else {
+            return Err(NaiaServerError::Message(format!(
+                "handshake payload Message exceeds the maximum of {} bytes",
+                MAX_HANDSHAKE_PAYLOAD_BYTES
+            )));
+        };
+        Ok(payload)
+    }
+
     fn finalize_connection(&mut self, user_key: &UserKey, user_address: &SocketAddr) {
         let Some(user) = self.users.get_mut(user_key) else {
             warn!("unknown user is finalizing connection...");
@@ -232,17 +501,30 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             self.user_connections.keys().collect::<Vec<_>>()
         );
 
-        let new_connection = Connection::new(
+        let mut new_connection = Connection::new(
             &self.server_config.connection,
             &self.server_config.ping,
             &user.address(),
             user_key,
             &self.protocol.channel_kinds,
             &self.global_world_manager,
+            &self.time_manager.current_tick(),
         );
+        if self.io.bandwidth_monitor_enabled() {
+            new_connection.enable_bandwidth_breakdown();
+        }
+        if let Some(duration) = user.timeout_duration() {
+            new_connection.base.set_timeout_duration(duration);
+        }
+        if let Some(interval) = user.ping_interval() {
+            new_connection.ping_manager.set_ping_interval(interval);
+        }
 
         self.user_connections.insert(user.address(), new_connection);
         self.user_key_to_addr.insert(*user_key, user.address());
+        for room_key in user.room_keys() {
+            self.scope_cache.user_added(room_key, user_key);
+        }
         warn!(
             "    user_connections after insert: {} entries",
             self.user_connections.len()
@@ -256,7 +538,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         if self.io.bandwidth_monitor_enabled() {
             self.io.register_client(&user.address());
         }
-        self.incoming_events.push_connection(user_key);
+        self.io.track_byte_rates(&user.address());
+        self.io.track_compression_stats(&user.address());
+        self.io.bind_client_socket(&user.address());
+        // nothing has been measured of the connection yet
+        let params = ConnectionParams::server(self.protocol.compression.as_ref(), None);
+        self.incoming_events.push_connection(user_key, params);
         warn!("    ConnectEvent pushed for {:?}", user_key);
     }
 
@@ -292,7 +579,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     &self.global_world_manager,
                     &mut connection.base.local_world_manager,
                 );
-                let message = MessageContainer::from_write(message_box, &mut converter);
+                let mut message = MessageContainer::from_write(message_box, &mut converter);
+                if channel_settings.tracks_latency() {
+                    message.set_send_instant(self.time_manager.game_time_now());
+                }
                 connection.base.message_manager.send_message(
                     &self.protocol.message_kinds,
                     &mut converter,
@@ -303,6 +593,100 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    /// Queues up a Message to be sent to the Client associated with a given
+    /// UserKey, like `send_message()`, returning a receipt which is reported
+    /// through `MessageDeliveredEvent` once the Client has acknowledged every
+    /// fragment of the Message. Returns an error if the Channel is not
+    /// reliable, as delivery over it would never be confirmed
+    pub fn send_message_with_receipt<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) -> Result<MessageReceiptKey, NaiaServerError> {
+        let channel_kind = ChannelKind::of::<C>();
+        let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
+
+        if !channel_settings.can_send_to_client() {
+            panic!("Cannot send message to Client on this Channel");
+        }
+        if !channel_settings.reliable() {
+            return Err(NaiaServerError::from_message(
+                "Message receipts can only be tracked over reliable Channels",
+            ));
+        }
+
+        let Some(user) = self.users.get(user_key) else {
+            return Err(NaiaServerError::from_message("user does not exist"));
+        };
+        if !user.has_address() {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        }
+        let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        };
+
+        let receipt_key = MessageReceiptKey::new(self.next_receipt_id);
+        self.next_receipt_id = self.next_receipt_id.wrapping_add(1);
+
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let mut message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        if channel_settings.tracks_latency() {
+            message.set_send_instant(self.time_manager.game_time_now());
+        }
+        connection.base.message_manager.send_message_with_receipt(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            message,
+            receipt_key,
+        );
+        self.undelivered_receipts.insert(receipt_key);
+
+        Ok(receipt_key)
+    }
+
+    /// Returns whether the Message sent with the given receipt has been
+    /// delivered in full. A Message whose Client disconnects before then is
+    /// never reported as delivered
+    pub fn receipt_received(&self, receipt_key: &MessageReceiptKey) -> bool {
+        !self.undelivered_receipts.contains(receipt_key)
+    }
+
+    /// Sets a validator which every Message, Request & tick-buffered Message
+    /// a Client sends is checked against as it is read, before being reported.
+    /// Messages it returns false for are dropped, and reported through
+    /// `MessageRejectedEvent` instead, so that the sending User can be dealt
+    /// with. Useful for centralizing rate limits & sanity checks
+    pub fn set_message_validator(&mut self, validator: MessageValidator) {
+        self.message_validator = Some(validator);
+    }
+
+    /// Removes the validator set with `set_message_validator()`, accepting
+    /// every Message again
+    pub fn clear_message_validator(&mut self) {
+        self.message_validator = None;
+    }
+
+    /// Queues up a stream payload to be sent to the Client associated with a
+    /// given UserKey. Streams are reliable & ordered, and payloads larger than
+    /// a packet are fragmented, so this suits large one-off payloads like
+    /// asset manifests. Received on the Client via `receive_stream_messages()`.
+    /// Panics unless streams are enabled with `Protocol::enable_streams()`
+    pub fn send_stream(&mut self, user_key: &UserKey, bytes: &[u8]) {
+        if !self.protocol.streams {
+            panic!("Cannot send stream payloads: streams are not enabled! Enable them in the Protocol, with the `enable_streams()` method, on both the Client & Server");
+        }
+        let message = StreamMessage::new(bytes);
+        self.send_message::<StreamChannel, StreamMessage>(user_key, &message);
+    }
+
     /// Sends a message to all connected users using a given channel
     pub fn broadcast_message<C: Channel, M: Message>(&mut self, message: &M) {
         let cloned_message = M::clone_box(message);
@@ -319,6 +703,27 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         })
     }
 
+    /// Sets a function producing a Message to send to every Client with each
+    /// Heartbeat, once per heartbeat interval, received as a
+    /// `HeartbeatPayloadEvent`. The function is called once immediately, and
+    /// returns an error if the Message is larger than
+    /// `HEARTBEAT_PAYLOAD_MAX_BYTES`. Later oversized Messages are not sent
+    pub fn set_heartbeat_payload<M: Message>(
+        &mut self,
+        provider: impl FnMut() -> Option<M> + Send + Sync + 'static,
+    ) -> Result<(), NaiaServerError> {
+        let heartbeat_payload =
+            HeartbeatPayload::new(self.server_config.connection.heartbeat_interval, provider)
+                .map_err(|error| NaiaServerError::Wrapped(Box::new(error)))?;
+        self.heartbeat_payload = Some(heartbeat_payload);
+        Ok(())
+    }
+
+    /// Stops sending a payload with each Heartbeat
+    pub fn clear_heartbeat_payload(&mut self) {
+        self.heartbeat_payload = None;
+    }
+
     //
     pub fn send_request<C: Channel, Q: Request>(
         &mut self,
@@ -330,6 +735,27 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         Ok(ResponseReceiveKey::new(id))
     }
 
+    /// Sends a Request to the User, giving up on it if no Response has
+    /// arrived within `timeout`. A Request which times out is cancelled, so a
+    /// Response arriving afterwards is dropped, and is reported through
+    /// `RequestTimeoutEvent<Q>`
+    pub fn send_request_with_timeout<C: Channel, Q: Request>(
+        &mut self,
+        user_key: &UserKey,
+        request: &Q,
+        timeout: Duration,
+    ) -> Result<ResponseReceiveKey<Q::Response>, NaiaServerError> {
+        let response_key = self.send_request::<C, Q>(user_key, request)?;
+        let mut deadline = Instant::now();
+        deadline.add_millis(timeout.as_millis() as u32);
+        self.global_request_manager.set_deadline(
+            &response_key.request_id(),
+            MessageKind::of::<Q>(),
+            deadline,
+        );
+        Ok(response_key)
+    }
+
     fn send_request_inner(
         &mut self,
         user_key: &UserKey,
@@ -365,7 +791,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             &mut connection.base.local_world_manager,
         );
 
-        let message = MessageContainer::from_write(request_box, &mut converter);
+        let mut message = MessageContainer::from_write(request_box, &mut converter);
+        if channel_settings.tracks_latency() {
+            message.set_send_instant(self.time_manager.game_time_now());
+        }
         connection.base.message_manager.send_request(
             &self.protocol.message_kinds,
             &mut converter,
@@ -416,7 +845,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             &self.global_world_manager,
             &mut connection.base.local_world_manager,
         );
-        let response = MessageContainer::from_write(response_box, &mut converter);
+        let mut response = MessageContainer::from_write(response_box, &mut converter);
+        if connection
+            .base
+            .message_manager
+            .tracks_latency(&channel_kind)
+        {
+            response.set_send_instant(self.time_manager.game_time_now());
+        }
         connection.base.message_manager.send_response(
             &self.protocol.message_kinds,
             &mut converter,
@@ -427,6 +863,63 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         return true;
     }
 
+    /// Registers a handler to answer each Request of type Q received over
+    /// Channel C, as it is received during `receive()`. Requests the handler
+    /// declines are reported through `RequestEvent` as usual. Replaces any
+    /// handler already registered for C & Q.
+    ///
+    /// Handlers aren't given the Server, so they can't call back into it;
+    /// whatever they need should be owned by them or shared with them. If
+    /// that state is behind a lock, the thread driving the Server must not
+    /// hold it while calling `receive()`. Deferred Requests are answered with
+    /// `send_response()`, from outside the handler
+    pub fn register_responder<C: Channel, Q: Request>(
+        &mut self,
+        handler: impl FnMut(&UserKey, Q) -> ResponderResult<Q::Response> + Send + Sync + 'static,
+    ) {
+        self.responders.insert(
+            (ChannelKind::of::<C>(), MessageKind::of::<Q>()),
+            erase_responder(handler),
+        );
+    }
+
+    /// Removes the handler registered for Requests of type Q over Channel C,
+    /// returning whether there was one
+    pub fn unregister_responder<C: Channel, Q: Request>(&mut self) -> bool {
+        self.responders
+            .remove(&(ChannelKind::of::<C>(), MessageKind::of::<Q>()))
+            .is_some()
+    }
+
+    fn handle_responders(&mut self) {
+        if self.responders.is_empty() {
+            return;
+        }
+        let mut responses = Vec::new();
+        for ((channel_kind, message_kind), responder) in self.responders.iter_mut() {
+            let requests = self
+                .incoming_events
+                .take_requests_of_kind(channel_kind, message_kind);
+            for (user_key, response_id, request) in requests {
+                match responder(&user_key, &response_id, request) {
+                    ResponderOutcome::Respond(response) => responses.push((response_id, response)),
+                    ResponderOutcome::Deferred => {}
+                    ResponderOutcome::Declined(request) => {
+                        self.incoming_events.push_request(
+                            &user_key,
+                            channel_kind,
+                            response_id,
+                            request,
+                        );
+                    }
+                }
+            }
+        }
+        for (response_id, response) in responses {
+            self.send_response_inner(&response_id, response);
+        }
+    }
+
     pub fn receive_response<S: Response>(
         &mut self,
         response_key: &ResponseReceiveKey<S>,
@@ -443,17 +936,71 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             .unwrap();
         return Some((user_key, response));
     }
+
+    /// Cancels an in-flight Request. Any Response which arrives afterwards will
+    /// be dropped rather than matched. Returns whether a live Request was cancelled.
+    pub fn cancel_request<S: Response>(&mut self, response_key: &ResponseReceiveKey<S>) -> bool {
+        let request_id = response_key.request_id();
+        self.global_request_manager.cancel_request_id(&request_id)
+    }
     //
 
     pub fn receive_tick_buffer_messages(&mut self, tick: &Tick) -> TickBufferMessages {
         let mut tick_buffer_messages = TickBufferMessages::new();
         for (_user_address, connection) in self.user_connections.iter_mut() {
             // receive messages from anyone
-            connection.tick_buffer_messages(tick, &mut tick_buffer_messages);
+            let consecutive_misses = connection.tick_buffer_messages(
+                tick,
+                self.message_validator.as_ref(),
+                &mut tick_buffer_messages,
+                &mut self.incoming_events,
+            );
+
+            if let Some(threshold) = self.server_config.input_gap_threshold {
+                if consecutive_misses > 0 && consecutive_misses >= threshold {
+                    self.incoming_events.push_input_gap(
+                        &connection.user_key,
+                        tick,
+                        consecutive_misses,
+                    );
+                }
+            }
         }
         tick_buffer_messages
     }
 
+    /// Gets how many Ticks' worth of tick-buffered Messages from the given
+    /// User are waiting to be read, including those for Ticks which have
+    /// already passed. Returns 0 if the User is not connected
+    pub fn tick_buffer_depth(&self, user_key: &UserKey) -> usize {
+        let Some(user) = self.users.get(user_key) else {
+            return 0;
+        };
+        if !user.has_address() {
+            return 0;
+        }
+        self.user_connections
+            .get(&user.address())
+            .map(|connection| connection.tick_buffer_depth())
+            .unwrap_or_default()
+    }
+
+    /// Discards all tick-buffered Messages from the given User which are
+    /// waiting to be read, for instance to skip a backlog of stale input after
+    /// the Server has stalled. Messages received afterwards are buffered as
+    /// usual
+    pub fn clear_tick_buffer(&mut self, user_key: &UserKey) {
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        if !user.has_address() {
+            return;
+        }
+        if let Some(connection) = self.user_connections.get_mut(&user.address()) {
+            connection.clear_tick_buffer();
+        }
+    }
+
     // Updates
 
     /// Used to evaluate whether, given a User & Entity that are in the
@@ -464,21 +1011,26 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     ///
     /// Return a collection of Entity Scope Sets, being a unique combination of
     /// a related Room, User, and Entity, used to determine which Entities to
-    /// replicate to which Users
-    pub fn scope_checks(&self) -> Vec<(RoomKey, UserKey, E)> {
-        let mut list: Vec<(RoomKey, UserKey, E)> = Vec::new();
+    /// replicate to which Users. The collection is cached, and only rebuilt
+    /// once Rooms have changed. See `scope_changes()` to only get the ones
+    /// which need re-evaluating
+    pub fn scope_checks(&self) -> Vec<ScopeCheck<E>> {
+        self.scope_cache.checks(&self.rooms)
+    }
 
-        // TODO: precache this, instead of generating a new list every call
-        // likely this is called A LOT
-        for (room_key, room) in self.rooms.iter() {
-            for user_key in room.user_keys() {
-                for entity in room.entities() {
-                    list.push((room_key, *user_key, *entity));
-                }
-            }
-        }
+    /// Like `scope_checks()`, but only yields the Entity Scope Sets which
+    /// need re-evaluating since the last call, i.e. because a User joined a
+    /// Room or connected, or an Entity was added to a Room. Scopes which
+    /// depend on anything else, such as an Entity's position, must be
+    /// re-evaluated by other means or with `reevaluate_all_scopes()`
+    pub fn scope_changes(&mut self) -> impl Iterator<Item = ScopeCheck<E>> {
+        self.scope_cache.take_changes(&self.rooms).into_iter()
+    }
 
-        list
+    /// Makes the next `scope_changes()` yield every Entity Scope Set, and
+    /// re-applies every User's scope on the next `send_all_updates()`
+    pub fn reevaluate_all_scopes(&mut self) {
+        self.scope_cache.invalidate_all();
     }
 
     /// Sends all update messages to all Clients. If you don't call this
@@ -488,13 +1040,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         let now = Instant::now();
 
         // update entity scopes
-        self.update_entity_scopes(&world);
+        self.update_entity_scopes_inner(&world);
 
         // loop through all connections, send packet
         let mut user_addresses: Vec<SocketAddr> = self.user_connections.keys().copied().collect();
 
-        // shuffle order of connections in order to avoid priority among users
-        fastrand::shuffle(&mut user_addresses);
+        // shuffle order of connections in order to avoid priority among users,
+        // starting from a sorted order so that a seeded rng gives the same result
+        user_addresses.sort();
+        let rng = fastrand::Rng::with_seed(self.rng_state);
+        rng.shuffle(&mut user_addresses);
+        self.rng_state = rng.get_seed();
 
         for user_address in user_addresses {
             let connection = self.user_connections.get_mut(&user_address).unwrap();
@@ -510,6 +1066,46 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    /// Applies Room & scope changes to each User's connection, queueing the
+    /// resulting spawns & despawns without sending anything. This is otherwise
+    /// done at the start of `send_all_updates()`
+    pub fn update_entity_scopes<W: WorldRefType<E>>(&mut self, world: W) {
+        self.update_entity_scopes_inner(&world);
+    }
+
+    /// Immediately builds & sends packets for everything queued for the given
+    /// User, i.e. a latency-sensitive Message, rather than waiting for the next
+    /// `send_all_updates()`. No other User's connection is touched, and Room &
+    /// scope changes are not applied
+    pub fn flush_user<W: WorldRefType<E>>(
+        &mut self,
+        world: W,
+        user_key: &UserKey,
+    ) -> Result<(), NaiaServerError> {
+        let Some(user) = self.users.get(user_key) else {
+            return Err(NaiaServerError::from_message("user does not exist"));
+        };
+        if !user.has_address() {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        }
+        let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        };
+        connection.send_packets(
+            &self.protocol,
+            &Instant::now(),
+            &mut self.io,
+            &world,
+            &self.global_world_manager,
+            &self.time_manager,
+        );
+        Ok(())
+    }
+
     // Entities
 
     /// Creates a new Entity and returns an EntityMut which can be used for
@@ -521,44 +1117,508 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         EntityMut::new(self, world, &entity)
     }
 
+    /// Creates a new delegated Entity whose authority is granted to the given
+    /// User from the start, e.g. a player's avatar. The Entity arrives on the
+    /// Client already Granted, so it can be mutated without first requesting
+    /// authority. If the User disconnects, authority reverts to the Server.
+    /// Panics if the User is not connected, or if the Protocol doesn't enable
+    /// client authoritative Entities
+    pub fn spawn_entity_for_user<W: WorldMutType<E>>(
+        &mut self,
+        mut world: W,
+        user_key: &UserKey,
+    ) -> EntityMut<'_, E, W> {
+        if let Err(error) = self.check_can_give_authority(user_key) {
+            panic!("{}", error);
+        }
+
+        let entity = world.spawn_entity();
+        self.spawn_entity_inner(&entity);
+        self.configure_entity_replication(&mut world, &entity, ReplicationConfig::Delegated);
+        if let Err(error) = self.entity_give_authority(user_key, &entity) {
+            panic!("{}", error);
+        }
+
+        EntityMut::new(self, world, &entity)
+    }
+
     /// Creates a new Entity with a specific id
     fn spawn_entity_inner(&mut self, entity: &E) {
         self.global_world_manager
             .spawn_entity_record(entity, EntityOwner::Server);
     }
 
-    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
-    pub fn enable_entity_replication(&mut self, entity: &E) {
-        self.spawn_entity_inner(&entity);
+    /// Exports an Entity's Components, replication config & authority state
+    /// into a self-contained blob, to hand the Entity off to another Server
+    /// via `import_entity()`, i.e. when sharding a world across several
+    /// Servers. The Entity itself is left untouched.
+    ///
+    /// References to other Entities, via EntityProperty, are not exported.
+    pub fn export_entity<W: WorldRefType<E>>(
+        &self,
+        world: W,
+        entity: &E,
+    ) -> Result<EntityExport, NaiaServerError> {
+        self.export_entity_inner(&world, entity)
     }
 
-    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
-    pub fn disable_entity_replication(&mut self, entity: &E) {
-        // Despawn from connections and inner tracking
-        self.despawn_entity_worldless(entity);
-    }
+    fn export_entity_inner<W: WorldRefType<E>>(
+        &self,
+        world: &W,
+        entity: &E,
+    ) -> Result<EntityExport, NaiaServerError> {
+        if !world.has_entity(entity) {
+            return Err(NaiaServerError::EntityDoesNotExist);
+        }
+        let Some(replication_config) = self.global_world_manager.entity_replication_config(entity)
+        else {
+            return Err(NaiaServerError::EntityDoesNotExist);
+        };
 
-    pub fn pause_entity_replication(&mut self, entity: &E) {
-        self.global_world_manager.pause_entity_replication(entity);
-    }
+        // the Server's own status is Granted while it holds Authority, and
+        // Denied while a Client does
+        let authority = match self.global_world_manager.entity_authority_status(entity) {
+            Some(EntityAuthStatus::Granted) => ExportedAuthority::Server,
+            Some(EntityAuthStatus::Denied) => ExportedAuthority::Client,
+            _ => ExportedAuthority::None,
+        };
 
-    pub fn resume_entity_replication(&mut self, entity: &E) {
-        self.global_world_manager.resume_entity_replication(entity);
+        let component_kinds = &self.protocol.component_kinds;
+        let mut components = Vec::new();
+        for component_kind in self
+            .global_world_manager
+            .component_kinds(entity)
+            .unwrap_or_default()
+        {
+            let Some(component) = world.component_of_kind(entity, &component_kind) else {
+                continue;
+            };
+            let mut writer = FileBitWriter::new();
+            component.write_fields(&mut writer, &mut UnmappedEntityConverter);
+            components.push(ExportedComponent {
+                kind_hash: component_kinds.kind_to_hash(&component_kind),
+                type_name: component_kinds.kind_to_name(&component_kind),
+                fields: writer.to_vec(),
+            });
+        }
+
+        let contents = EntityExportContents {
+            replication_config,
+            authority,
+            components,
+        };
+        return Ok(contents.to_export());
     }
 
-    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
-    pub fn entity_replication_config(&self, entity: &E) -> Option<ReplicationConfig> {
-        self.global_world_manager.entity_replication_config(entity)
+    /// Spawns a new Entity from an `EntityExport` created by another Server's
+    /// `export_entity()`. Both Servers must share a Protocol, Components are
+    /// matched by the hash of their type name. If any exported Component is
+    /// not in this Server's Protocol, returns an error listing each of them
+    /// and spawns nothing.
+    ///
+    /// The new Entity is owned by this Server, and must be added to Rooms &
+    /// scopes as usual. If a Client held Authority over a Delegated Entity,
+    /// Authority is made available again, so it can be re-requested once the
+    /// Client has connected to this Server.
+    pub fn import_entity<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        export: EntityExport,
+    ) -> Result<E, NaiaServerError> {
+        let (contents, components) = self.decode_entity_export(&export)?;
+        Ok(self.spawn_decoded_entity(world, contents, components))
     }
 
-    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
-    pub fn entity_take_authority(&mut self, entity: &E) {
-        let did_change = self.global_world_manager.server_take_authority(entity);
+    // Reads an `EntityExport` into its contents & Components, without
+    // touching the World
+    fn decode_entity_export(
+        &self,
+        export: &EntityExport,
+    ) -> Result<(EntityExportContents, Vec<Box<dyn Replicate>>), NaiaServerError> {
+        let Ok(contents) = EntityExportContents::from_export(export) else {
+            return Err(NaiaServerError::from_message(
+                "cannot import Entity, EntityExport is malformed",
+            ));
+        };
+
+        let component_kinds = &self.protocol.component_kinds;
+        let mut components = Vec::new();
+        let mut unknown_kinds = Vec::new();
+        for exported_component in &contents.components {
+            let Some(component_kind) = component_kinds.hash_to_kind(exported_component.kind_hash)
+            else {
+                unknown_kinds.push(exported_component.type_name.as_str());
+                continue;
+            };
+            let mut reader = BitReader::new(&exported_component.fields);
+            let Ok(component) =
+                component_kinds.read_fields(&component_kind, &mut reader, &FakeEntityConverter)
+            else {
+                return Err(NaiaServerError::Message(format!(
+                    "cannot import Entity, Component `{}` is malformed",
+                    exported_component.type_name
+                )));
+            };
+            // read Components are remote-owned, copying makes them host-owned
+            components.push(component.copy_to_box());
+        }
+        if !unknown_kinds.is_empty() {
+            return Err(NaiaServerError::Message(format!(
+                "cannot import Entity, Protocol does not contain Component(s): {}",
+                unknown_kinds.join(", ")
+            )));
+        }
+
+        Ok((contents, components))
+    }
+
+    fn spawn_decoded_entity<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        contents: EntityExportContents,
+        components: Vec<Box<dyn Replicate>>,
+    ) -> E {
+        let entity = world.spawn_entity();
+        self.spawn_entity_inner(&entity);
+        for mut component in components {
+            self.insert_component_worldless(&entity, component.as_mut());
+            world.insert_boxed_component(&entity, component);
+        }
+
+        if contents.replication_config == ReplicationConfig::Delegated {
+            self.configure_entity_replication(world, &entity, ReplicationConfig::Delegated);
+            if contents.authority == ExportedAuthority::Server {
+                self.global_world_manager.server_take_authority(&entity);
+            }
+        }
+
+        return entity;
+    }
+
+    /// Writes the current value of one of an Entity's Components, to be
+    /// stored outside of the network & read back with
+    /// `deserialize_component()`. Returns None if the Entity doesn't have the
+    /// Component.
+    ///
+    /// As with `export_entity()`, references to other Entities, via
+    /// EntityProperty, are not written.
+    pub fn serialize_component<R: ReplicatedComponent, W: WorldRefType<E>>(
+        &self,
+        world: &W,
+        entity: &E,
+    ) -> Option<Vec<u8>> {
+        let component = world.component::<R>(entity)?;
+        let mut writer = FileBitWriter::new();
+        component.write_fields(&mut writer, &mut UnmappedEntityConverter);
+        Some(writer.to_vec())
+    }
+
+    /// Reads a Component written by `serialize_component()`, not yet attached
+    /// to any Entity. Returns None if the bytes are malformed
+    pub fn deserialize_component<R: ReplicatedComponent>(&self, bytes: &[u8]) -> Option<R> {
+        let mut reader = BitReader::new(bytes);
+        let component = self
+            .protocol
+            .component_kinds
+            .read_fields(&ComponentKind::of::<R>(), &mut reader, &FakeEntityConverter)
+            .ok()?;
+        // read Components are remote-owned, copying makes them host-owned
+        let component = component
+            .copy_to_box()
+            .to_boxed_any()
+            .downcast::<R>()
+            .ok()?;
+        Some(*component)
+    }
+
+    /// Writes every Server-owned Entity in the World, with its Components &
+    /// replication config, so that it can be restored with
+    /// `load_world_snapshot()`, i.e. after a Server restart. The snapshot
+    /// starts with a header holding the Protocol's `schema_digest()`, so it
+    /// can only be loaded by a Server with the same Protocol.
+    ///
+    /// Entities owned by Clients, and Delegated Entities, are skipped. As
+    /// with `export_entity()`, references to other Entities, via
+    /// EntityProperty, are not saved.
+    pub fn save_world_snapshot<W: WorldRefType<E>>(
+        &self,
+        world: &W,
+        out: &mut impl Write,
+    ) -> Result<(), SnapshotError> {
+        let mut exports = Vec::new();
+        for entity in world.entities() {
+            if self.global_world_manager.entity_owner(&entity) != Some(EntityOwner::Server) {
+                continue;
+            }
+            if self.global_world_manager.entity_replication_config(&entity)
+                == Some(ReplicationConfig::Delegated)
+            {
+                continue;
+            }
+            let Ok(export) = self.export_entity_inner(world, &entity) else {
+                continue;
+            };
+            exports.push(export);
+        }
+
+        write_snapshot(out, self.protocol.schema_digest(), &exports)
+    }
+
+    /// Spawns a fresh Entity for each one in a snapshot written by
+    /// `save_world_snapshot()`, registered for replication like any other
+    /// Server-owned Entity, and returns them. They must be added to Rooms &
+    /// scopes as usual.
+    ///
+    /// Fails without spawning anything if the snapshot was saved with a
+    /// different Protocol, or if any of it can't be read.
+    pub fn load_world_snapshot<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        input: &mut impl Read,
+    ) -> Result<Vec<E>, SnapshotError> {
+        let exports = read_snapshot(input, self.protocol.schema_digest())?;
+
+        // decode everything up front, so that a bad snapshot spawns nothing
+        let mut decoded = Vec::new();
+        for export in &exports {
+            let decoded_entity = self
+                .decode_entity_export(export)
+                .map_err(|error| SnapshotError::Malformed(error.to_string()))?;
+            decoded.push(decoded_entity);
+        }
+
+        let mut entities = Vec::new();
+        for (contents, components) in decoded {
+            entities.push(self.spawn_decoded_entity(world, contents, components));
+        }
+        Ok(entities)
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    pub fn enable_entity_replication(&mut self, entity: &E) {
+        self.spawn_entity_inner(&entity);
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    pub fn disable_entity_replication(&mut self, entity: &E) {
+        // Despawn from connections and inner tracking
+        self.despawn_entity_worldless(entity);
+    }
+
+    pub fn pause_entity_replication(&mut self, entity: &E) {
+        self.global_world_manager.pause_entity_replication(entity);
+    }
+
+    pub fn resume_entity_replication(&mut self, entity: &E) {
+        self.global_world_manager.resume_entity_replication(entity);
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    pub fn entity_replication_config(&self, entity: &E) -> Option<ReplicationConfig> {
+        self.global_world_manager.entity_replication_config(entity)
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    pub fn entity_take_authority(&mut self, entity: &E) {
+        self.take_authority(entity, EntityAuthResetReason::ManualReset);
+    }
+
+    fn take_authority(&mut self, entity: &E, reason: EntityAuthResetReason) {
+        let did_change = self.global_world_manager.server_take_authority(entity);
 
         if did_change {
+            #[cfg(feature = "tracing")]
+            trace_auth_transition(
+                &self.global_world_manager,
+                entity,
+                None,
+                EntityAuthStatus::Available,
+            );
             self.send_reset_authority_messages(entity);
-            self.incoming_events.push_auth_reset(entity);
+            self.incoming_events.push_auth_reset(entity, reason);
+        }
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    /// Overrides `ServerConfig::auth_idle_timeout` for the given Entity. None
+    /// means authority over it is never reclaimed for being idle
+    pub fn entity_set_auth_idle_timeout(&mut self, entity: &E, timeout: Option<Duration>) {
+        self.auth_idle_timeouts.insert(*entity, timeout);
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    /// Keeps the Entity out of each User's scope until the dependency is
+    /// in it, see `EntityMut::scope_depends_on()`
+    pub fn entity_add_scope_dependency(
+        &mut self,
+        entity: &E,
+        dependency: &E,
+    ) -> Result<(), NaiaServerError> {
+        if !self.global_world_manager.has_entity(entity)
+            || !self.global_world_manager.has_entity(dependency)
+        {
+            return Err(NaiaServerError::EntityDoesNotExist);
+        }
+        if !self.scope_dependencies.insert(entity, dependency) {
+            return Err(NaiaServerError::from_message(
+                "scope dependencies can't form a cycle",
+            ));
+        }
+        Ok(())
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    /// Returns whether the Entity depended on the other
+    pub fn entity_remove_scope_dependency(&mut self, entity: &E, dependency: &E) -> bool {
+        self.scope_dependencies.remove(entity, dependency)
+    }
+
+    /// Returns the Entities which the given Entity's scope depends on
+    pub fn entity_scope_dependencies(&self, entity: &E) -> Vec<E> {
+        self.scope_dependencies
+            .dependencies(entity)
+            .map(|dependencies| dependencies.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    fn entity_auth_idle_timeout(&self, entity: &E) -> Option<Duration> {
+        match self.auth_idle_timeouts.get(entity) {
+            Some(timeout) => *timeout,
+            None => self.server_config.auth_idle_timeout,
+        }
+    }
+
+    // Restarts the idle timeout of an Entity which authority was just granted over
+    fn mark_authority_heard(&mut self, user_key: &UserKey, entity: &E) {
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        if !user.has_address() {
+            return;
+        }
+        if let Some(connection) = self.user_connections.get_mut(&user.address()) {
+            connection.mark_entity_heard(entity, &Instant::now());
+        }
+    }
+
+    fn reclaim_idle_authority(&mut self, now: &Instant) {
+        if self.server_config.auth_idle_timeout.is_none() && self.auth_idle_timeouts.is_empty() {
+            return;
+        }
+
+        let mut idle_entities = Vec::new();
+        for connection in self.user_connections.values() {
+            let Some(owned_entities) = self
+                .global_world_manager
+                .user_all_owned_entities(&connection.user_key)
+            else {
+                continue;
+            };
+            for entity in owned_entities {
+                let Some(timeout) = self.entity_auth_idle_timeout(entity) else {
+                    continue;
+                };
+                let Some(last_heard) = connection.entity_last_heard(entity) else {
+                    continue;
+                };
+                if last_heard.elapsed(now) > timeout {
+                    idle_entities.push((connection.address, *entity));
+                }
+            }
+        }
+
+        for (address, entity) in idle_entities {
+            if let Some(connection) = self.user_connections.get_mut(&address) {
+                connection.forget_entity_heard(&entity);
+            }
+            self.take_authority(&entity, EntityAuthResetReason::IdleTimeout);
+        }
+    }
+
+    /// Grants authority over a delegated Entity to the given User, while no
+    /// one holds it. Users with the Entity in scope are told right away, and
+    /// Users it comes into scope for later learn of it along with the Entity.
+    /// Returns an error if the Entity is not delegated, if its authority is
+    /// not Available, or if the User is not connected
+    pub fn entity_give_authority(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+    ) -> Result<(), NaiaServerError> {
+        self.check_entity_exists(entity)?;
+        self.check_can_give_authority(user_key)?;
+        if !self.global_world_manager.entity_is_delegated(entity) {
+            return Err(NaiaServerError::from_message("Entity is not delegated"));
+        }
+        let requester = AuthOwner::Client(*user_key);
+        if !self
+            .global_world_manager
+            .client_request_authority(entity, &requester)
+        {
+            return Err(NaiaServerError::from_message(
+                "Entity authority is not Available",
+            ));
+        }
+        #[cfg(feature = "tracing")]
+        trace_auth_transition(
+            &self.global_world_manager,
+            entity,
+            Some(user_key),
+            EntityAuthStatus::Granted,
+        );
+
+        // the new owner sends its host entity back with a request for
+        // authority, which is when the Server starts accepting its updates
+        let mut messages_to_send = Vec::new();
+        for (other_user_key, user) in self.users.iter() {
+            if !user.has_address() || user.is_observer() {
+                continue;
+            }
+            let Some(connection) = self.user_connections.get(&user.address()) else {
+                continue;
+            };
+            if !connection.base.host_world_manager.host_has_entity(entity) {
+                continue;
+            }
+            let status = if other_user_key == *user_key {
+                EntityAuthStatus::Granted
+            } else {
+                EntityAuthStatus::Denied
+            };
+            let message = EntityEventMessage::new_update_auth_status(
+                &self.global_world_manager,
+                entity,
+                status,
+            );
+            messages_to_send.push((other_user_key, message));
+        }
+        for (other_user_key, message) in messages_to_send {
+            self.send_message::<SystemChannel, EntityEventMessage>(&other_user_key, &message);
+        }
+
+        self.mark_authority_heard(user_key, entity);
+        self.incoming_events.push_auth_grant(user_key, entity);
+        Ok(())
+    }
+
+    fn check_can_give_authority(&self, user_key: &UserKey) -> Result<(), NaiaServerError> {
+        if !self.protocol.client_authoritative_entities {
+            return Err(NaiaServerError::from_message(
+                "client authoritative entities are not enabled in the Protocol",
+            ));
+        }
+        let Some(user) = self.users.get(user_key) else {
+            return Err(NaiaServerError::from_message("user does not exist"));
+        };
+        if !user.has_address() || !self.user_connections.contains_key(&user.address()) {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
         }
+        if user.is_observer() {
+            return Err(NaiaServerError::from_message("user is an observer"));
+        }
+        Ok(())
     }
 
     fn send_reset_authority_messages(&mut self, entity: &E) {
@@ -573,7 +1633,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 continue;
             }
             if let Some(connection) = self.user_connections.get_mut(&user.address()) {
-                if connection.base.host_world_manager.host_has_entity(entity) {
+                if !user.is_observer() && connection.base.host_world_manager.host_has_entity(entity)
+                {
                     let message = EntityEventMessage::new_update_auth_status(
                         &self.global_world_manager,
                         entity,
@@ -705,59 +1766,73 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         let success = self
             .global_world_manager
             .client_request_authority(&world_entity, &requester);
-        if success {
-            // entity authority was granted for origin user
+        if !success {
+            // another User was granted authority before this request arrived,
+            // & this User was told it's Denied then
+            return;
+        }
+
+        // entity authority was granted for origin user
+        #[cfg(feature = "tracing")]
+        trace_auth_transition(
+            &self.global_world_manager,
+            world_entity,
+            Some(origin_user),
+            EntityAuthStatus::Granted,
+        );
 
-            self.add_redundant_remote_entity_to_host(origin_user, world_entity, remote_entity);
+        self.add_redundant_remote_entity_to_host(origin_user, world_entity, remote_entity);
 
-            // for any users that have this entity in scope, send an `update_authority_status` message
+        // for any users that have this entity in scope, send an `update_authority_status` message
 
-            // TODO: we can make this more efficient in the future by caching which Entities
-            // are in each User's scope
-            let mut messages_to_send = Vec::new();
-            for (user_key, user) in self.users.iter() {
-                if !user.has_address() {
-                    continue;
-                }
-                if let Some(connection) = self.user_connections.get(&user.address()) {
-                    if connection
-                        .base
-                        .host_world_manager
-                        .host_has_entity(world_entity)
-                    {
-                        let mut new_status: EntityAuthStatus = EntityAuthStatus::Denied;
-                        if *origin_user == user_key {
-                            new_status = EntityAuthStatus::Granted;
-                        }
+        // TODO: we can make this more efficient in the future by caching which Entities
+        // are in each User's scope
+        let mut messages_to_send = Vec::new();
+        for (user_key, user) in self.users.iter() {
+            if !user.has_address() || user.is_observer() {
+                continue;
+            }
+            if let Some(connection) = self.user_connections.get(&user.address()) {
+                if connection
+                    .base
+                    .host_world_manager
+                    .host_has_entity(world_entity)
+                {
+                    let mut new_status: EntityAuthStatus = EntityAuthStatus::Denied;
+                    if *origin_user == user_key {
+                        new_status = EntityAuthStatus::Granted;
+                    }
 
-                        // if new_status == EntityAuthStatus::Denied {
-                        //     warn!("Denying status of entity to user: `{:?}`", user_key);
-                        // } else {
-                        //     warn!("Granting status of entity to user: `{:?}`", user_key);
-                        // }
+                    // if new_status == EntityAuthStatus::Denied {
+                    //     warn!("Denying status of entity to user: `{:?}`", user_key);
+                    // } else {
+                    //     warn!("Granting status of entity to user: `{:?}`", user_key);
+                    // }
 
-                        let message = EntityEventMessage::new_update_auth_status(
-                            &self.global_world_manager,
-                            world_entity,
-                            new_status,
-                        );
+                    let message = EntityEventMessage::new_update_auth_status(
+                        &self.global_world_manager,
+                        world_entity,
+                        new_status,
+                    );
 
-                        messages_to_send.push((user_key, message));
-                    }
+                    messages_to_send.push((user_key, message));
                 }
             }
-            for (user_key, message) in messages_to_send {
-                self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
-            }
-
-            self.incoming_events
-                .push_auth_grant(origin_user, &world_entity);
-        } else {
-            panic!("Failed to request authority for entity");
         }
+        for (user_key, message) in messages_to_send {
+            self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
+        }
+
+        self.mark_authority_heard(origin_user, world_entity);
+        self.incoming_events
+            .push_auth_grant(origin_user, &world_entity);
     }
 
     fn entity_enable_delegation_response(&mut self, user_key: &UserKey, entity: &E) {
+        if self.user_holds_authority(user_key, entity) {
+            // already told it was Granted, as the Entity came into scope
+            return;
+        }
         if self.global_world_manager.entity_is_delegated(entity) {
             let Some(auth_status) = self.global_world_manager.entity_authority_status(entity)
             else {
@@ -776,7 +1851,28 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
 
     /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
     pub fn entity_authority_status(&self, entity: &E) -> Option<EntityAuthStatus> {
-        self.global_world_manager.entity_authority_status(entity)
+        self.try_entity_authority_status(entity).ok()
+    }
+
+    /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
+    /// Returns an error if the Entity does not exist or is not delegated.
+    pub fn try_entity_authority_status(
+        &self,
+        entity: &E,
+    ) -> Result<EntityAuthStatus, NaiaServerError> {
+        if !self.global_world_manager.has_entity(entity) {
+            return Err(NaiaServerError::EntityDoesNotExist);
+        }
+        let Some(auth_status) = self.global_world_manager.entity_authority_status(entity) else {
+            return Err(NaiaServerError::from_message("Entity is not delegated"));
+        };
+        return Ok(auth_status);
+    }
+
+    fn user_holds_authority(&self, user_key: &UserKey, entity: &E) -> bool {
+        self.global_world_manager
+            .user_all_owned_entities(user_key)
+            .is_some_and(|entities| entities.contains(entity))
     }
 
     fn add_redundant_remote_entity_to_host(
@@ -831,40 +1927,204 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             .global_world_manager
             .client_release_authority(&entity, &releaser);
         if success {
+            #[cfg(feature = "tracing")]
+            trace_auth_transition(
+                &self.global_world_manager,
+                entity,
+                origin_user,
+                EntityAuthStatus::Available,
+            );
             self.send_reset_authority_messages(entity);
         }
     }
 
-    /// Retrieves an EntityRef that exposes read-only operations for the
-    /// Entity.
-    /// Panics if the Entity does not exist.
-    pub fn entity<W: WorldRefType<E>>(&self, world: W, entity: &E) -> EntityRef<'_, E, W> {
-        if world.has_entity(entity) {
-            return EntityRef::new(self, world, entity);
+    /// Moves authority over a delegated Entity from one User to another in a
+    /// single step. The previous owner is reset & then denied authority, and
+    /// the new owner is granted it, without the Entity becoming Available in
+    /// between. Returns an error if `from_user` does not hold authority, or if
+    /// the Entity is not in scope for `to_user`.
+    pub fn transfer_authority(
+        &mut self,
+        from_user: &UserKey,
+        to_user: &UserKey,
+        entity: &E,
+    ) -> Result<(), NaiaServerError> {
+        self.check_entity_exists(entity)?;
+        if !self.global_world_manager.entity_is_delegated(entity) {
+            return Err(NaiaServerError::from_message("Entity is not delegated"));
         }
-        panic!("No Entity exists for given Key!");
-    }
-
-    /// Retrieves an EntityMut that exposes read and write operations for the
-    /// Entity.
-    /// Panics if the Entity does not exist.
-    pub fn entity_mut<W: WorldMutType<E>>(&mut self, world: W, entity: &E) -> EntityMut<'_, E, W> {
-        if world.has_entity(entity) {
-            return EntityMut::new(self, world, entity);
+        if from_user == to_user {
+            return Err(NaiaServerError::from_message(
+                "cannot transfer authority to the User which holds it",
+            ));
         }
-        panic!("No Entity exists for given Key!");
-    }
-
-    /// Gets a Vec of all Entities in the given World
-    pub fn entities<W: WorldRefType<E>>(&self, world: W) -> Vec<E> {
+        if !self.user_holds_authority(from_user, entity) {
+            return Err(NaiaServerError::from_message(
+                "User does not hold authority over Entity",
+            ));
+        }
+        let Some(user) = self.users.get(to_user) else {
+            return Err(NaiaServerError::from_message("user does not exist"));
+        };
+        if !user.has_address() {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        }
+        if user.is_observer() {
+            return Err(NaiaServerError::from_message("user is an observer"));
+        }
+        let Some(connection) = self.user_connections.get(&user.address()) else {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        };
+        if !connection.base.host_world_manager.host_has_entity(entity) {
+            return Err(NaiaServerError::from_message(
+                "Entity is not in scope for user",
+            ));
+        }
+
+        self.global_world_manager
+            .transfer_authority(entity, from_user, to_user);
+        #[cfg(feature = "tracing")]
+        trace_auth_transition(
+            &self.global_world_manager,
+            entity,
+            Some(to_user),
+            EntityAuthStatus::Granted,
+        );
+
+        // the previous owner no longer sends updates for the Entity
+        if let Some(user) = self.users.get(from_user) {
+            if user.has_address() {
+                if let Some(connection) = self.user_connections.get_mut(&user.address()) {
+                    if connection
+                        .base
+                        .local_world_manager
+                        .has_both_host_and_remote_entity(entity)
+                    {
+                        Self::remove_redundant_remote_entity_from_host(connection, entity);
+                    }
+                }
+            }
+        }
+
+        // other Users in scope are already Denied, so only the two owners are told
+        for (user_key, status) in [
+            (from_user, EntityAuthStatus::Available),
+            (from_user, EntityAuthStatus::Denied),
+            (to_user, EntityAuthStatus::Granted),
+        ] {
+            let message = EntityEventMessage::new_update_auth_status(
+                &self.global_world_manager,
+                entity,
+                status,
+            );
+            self.send_message::<SystemChannel, EntityEventMessage>(user_key, &message);
+        }
+
+        self.mark_authority_heard(to_user, entity);
+        self.incoming_events.push_auth_grant(to_user, entity);
+        Ok(())
+    }
+
+    /// Retrieves an EntityRef that exposes read-only operations for the
+    /// Entity.
+    /// Panics if the Entity does not exist.
+    pub fn entity<W: WorldRefType<E>>(&self, world: W, entity: &E) -> EntityRef<'_, E, W> {
+        match self.try_entity(world, entity) {
+            Ok(entity_ref) => entity_ref,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Retrieves an EntityRef that exposes read-only operations for the
+    /// Entity.
+    /// Returns an error if the Entity does not exist.
+    pub fn try_entity<W: WorldRefType<E>>(
+        &self,
+        world: W,
+        entity: &E,
+    ) -> Result<EntityRef<'_, E, W>, NaiaServerError> {
+        if world.has_entity(entity) {
+            return Ok(EntityRef::new(self, world, entity));
+        }
+        return Err(NaiaServerError::EntityDoesNotExist);
+    }
+
+    /// Retrieves an EntityMut that exposes read and write operations for the
+    /// Entity.
+    /// Panics if the Entity does not exist.
+    pub fn entity_mut<W: WorldMutType<E>>(&mut self, world: W, entity: &E) -> EntityMut<'_, E, W> {
+        match self.try_entity_mut(world, entity) {
+            Ok(entity_mut) => entity_mut,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Retrieves an EntityMut that exposes read and write operations for the
+    /// Entity.
+    /// Returns an error if the Entity does not exist.
+    pub fn try_entity_mut<W: WorldMutType<E>>(
+        &mut self,
+        world: W,
+        entity: &E,
+    ) -> Result<EntityMut<'_, E, W>, NaiaServerError> {
+        if world.has_entity(entity) {
+            return Ok(EntityMut::new(self, world, entity));
+        }
+        return Err(NaiaServerError::EntityDoesNotExist);
+    }
+
+    /// Gets a Vec of all Entities in the given World
+    pub fn entities<W: WorldRefType<E>>(&self, world: W) -> Vec<E> {
         world.entities()
     }
 
+    /// Gets the owner of the Entity. A delegated Entity is owned by the
+    /// User whose Client holds Authority over it, if any
     pub fn entity_owner(&self, entity: &E) -> EntityOwner {
-        if let Some(owner) = self.global_world_manager.entity_owner(entity) {
-            return owner;
+        let Some(owner) = self.global_world_manager.entity_owner(entity) else {
+            return EntityOwner::Local;
+        };
+        if let Some(user_key) = self.global_world_manager.entity_authority_holder(entity) {
+            return EntityOwner::Client(user_key);
+        }
+        return owner;
+    }
+
+    /// Despawns the Entity once every update of its Components which is
+    /// queued for, or in flight to, any User has been acknowledged, so that
+    /// no Client misses the Entity's final state. Until then the Entity stays
+    /// in the World, and any further mutations are sent as usual. The
+    /// despawn itself happens during a later call to `receive()`
+    pub fn despawn_entity_after_ack(&mut self, entity: &E) -> Result<(), NaiaServerError> {
+        if !self.global_world_manager.has_entity(entity) {
+            return Err(NaiaServerError::EntityDoesNotExist);
         }
-        return EntityOwner::Local;
+        self.despawns_after_ack.insert(*entity);
+        Ok(())
+    }
+
+    /// Returns whether the Entity is waiting on acknowledgements before being
+    /// despawned, see `despawn_entity_after_ack()`
+    pub fn entity_despawn_pending(&self, entity: &E) -> bool {
+        self.despawns_after_ack.contains(entity)
+    }
+
+    /// Registers a callback to run, during `receive()`, whenever a Client
+    /// inserts a Component of type R into an Entity. Any number of callbacks
+    /// may be registered for each type. The insert is still reported through
+    /// `InsertComponentEvent` as usual
+    pub fn on_insert_component<R: ReplicatedComponent>(
+        &mut self,
+        handler: impl FnMut(&UserKey, &E) + Send + Sync + 'static,
+    ) {
+        self.insert_component_handlers
+            .entry(ComponentKind::of::<R>())
+            .or_default()
+            .push(Box::new(handler));
     }
 
     // Users
@@ -915,6 +2175,63 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.users.len()
     }
 
+    // whether another User would exceed `ServerConfig::max_users`
+    fn is_full(&self) -> bool {
+        self.server_config
+            .max_users
+            .is_some_and(|max_users| self.user_connections.len() >= max_users)
+    }
+
+    /// Return a list of all Entities owned by the given User, either spawned
+    /// by their Client or with Authority delegated to their Client
+    pub fn user_owned_entities(&self, user_key: &UserKey) -> Vec<E> {
+        self.global_world_manager.user_owned_entities(user_key)
+    }
+
+    /// Return a list of all Entities owned by the Server, leaving out
+    /// delegated Entities while a Client holds Authority over them
+    pub fn server_owned_entities(&self) -> Vec<E> {
+        self.global_world_manager.server_owned_entities()
+    }
+
+    /// Gets a snapshot of every channel receiving Entity actions from the given
+    /// User's Client, including any actions waiting on an earlier one, for
+    /// diagnosing an Entity which has stopped updating. Returns None if the
+    /// User is not connected
+    pub fn user_entity_channels_debug(
+        &self,
+        user_key: &UserKey,
+    ) -> Option<Vec<EntityChannelDebug<RemoteEntity>>> {
+        let user = self.users.get(user_key)?;
+        if !user.has_address() {
+            return None;
+        }
+        let connection = self.user_connections.get(&user.address())?;
+        return Some(connection.base.remote_world_reader.debug_dump());
+    }
+
+    /// Returns the entity actions & component updates which would be written
+    /// to the given User's next packet, without sending or consuming them.
+    /// Scope changes are not applied until `update_entity_scopes()` or
+    /// `send_all_updates()`. Returns None if the User is not connected
+    pub fn pending_host_events<W: WorldRefType<E>>(
+        &self,
+        world: W,
+        user_key: &UserKey,
+    ) -> Option<HostWorldEvents<E>> {
+        let user = self.users.get(user_key)?;
+        if !user.has_address() {
+            return None;
+        }
+        let connection = self.user_connections.get(&user.address())?;
+        return Some(connection.base.host_world_manager.peek_outgoing_events(
+            &world,
+            &self.global_world_manager,
+            &Instant::now(),
+            &connection.ping_manager.rtt_average,
+        ));
+    }
+
     /// Returns a UserScopeRef, which is used to query whether a given user has
     pub fn user_scope(&self, user_key: &UserKey) -> UserScopeRef<'_, E> {
         if self.users.contains_key(user_key) {
@@ -932,6 +2249,55 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         panic!("No User exists for given Key!");
     }
 
+    /// Rebuilds a User's copy of an Entity from scratch, by despawning and
+    /// respawning it within that User's connection only, which resends the
+    /// full state of every Component. Other Users are not affected.
+    /// Intended to recover a Client whose copy of an Entity has diverged.
+    ///
+    /// Returns an error if the Entity is not in scope for the User, or is
+    /// delegated.
+    pub fn resync_entity(&mut self, user_key: &UserKey, entity: &E) -> Result<(), NaiaServerError> {
+        self.check_entity_exists(entity)?;
+        if self.global_world_manager.entity_is_delegated(entity) {
+            return Err(NaiaServerError::from_message(
+                "cannot resync a delegated Entity",
+            ));
+        }
+        let Some(user) = self.users.get(user_key) else {
+            return Err(NaiaServerError::from_message("user does not exist"));
+        };
+        if !user.has_address() {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        }
+        let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+            return Err(NaiaServerError::from_message(
+                "currently not connected to user",
+            ));
+        };
+        let host_world_manager = &mut connection.base.host_world_manager;
+        if !host_world_manager.host_has_entity(entity) {
+            return Err(NaiaServerError::from_message(
+                "Entity is not in scope for user",
+            ));
+        }
+        if !host_world_manager.entity_channel_is_open(entity) {
+            // the spawn is still in flight, and carries the full state already
+            return Ok(());
+        }
+
+        // the respawn goes out once the despawn is acknowledged
+        let component_kinds = self.global_world_manager.component_kinds(entity).unwrap();
+        host_world_manager.despawn_entity(entity);
+        host_world_manager.init_entity(
+            &mut connection.base.local_world_manager,
+            entity,
+            component_kinds,
+        );
+        Ok(())
+    }
+
     // Rooms
 
     /// Creates a new Room on the Server and returns a corresponding RoomMut,
@@ -984,6 +2350,63 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.rooms.len()
     }
 
+    /// Pauses replication of every Entity in the Room, as with
+    /// `pause_entity_replication()`, and stops evaluating the Room's scopes
+    /// until `resume_room_replication()` is called. Entities added to the
+    /// Room while it's paused are paused too. Changes made in the meantime
+    /// are kept, & sent once resumed
+    pub fn pause_room_replication(&mut self, room_key: &RoomKey) {
+        let Some(room) = self.rooms.get_mut(room_key) else {
+            return;
+        };
+        if room.is_paused() {
+            return;
+        }
+        room.set_paused(true);
+        let entities: Vec<E> = room.entities().copied().collect();
+        for entity in entities {
+            if self.global_world_manager.has_entity(&entity) {
+                self.global_world_manager.pause_entity_replication(&entity);
+            }
+        }
+        self.scope_cache.rooms_changed();
+    }
+
+    /// Resumes replication of a Room paused with `pause_room_replication()`,
+    /// and of each of its Entities not also in another paused Room, then
+    /// re-evaluates the Room's scopes
+    pub fn resume_room_replication(&mut self, room_key: &RoomKey) {
+        let Some(room) = self.rooms.get_mut(room_key) else {
+            return;
+        };
+        if !room.is_paused() {
+            return;
+        }
+        room.set_paused(false);
+        let entities: Vec<E> = room.entities().copied().collect();
+        let user_keys: Vec<UserKey> = room.user_keys().copied().collect();
+        for entity in entities {
+            self.resume_entity_unless_room_paused(&entity);
+        }
+        self.scope_cache.room_resumed(room_key, user_keys);
+    }
+
+    /// Returns whether the Room's replication is paused
+    pub fn room_replication_is_paused(&self, room_key: &RoomKey) -> bool {
+        self.rooms
+            .get(room_key)
+            .is_some_and(|room| room.is_paused())
+    }
+
+    /// Return the keys of every Room the given Entity belongs to, which is
+    /// empty if it belongs to none or does not exist
+    pub fn entity_rooms(&self, entity: &E) -> Vec<RoomKey> {
+        let Some(room_keys) = self.entity_room_map.entity_get_rooms(entity) else {
+            return Vec::new();
+        };
+        room_keys.iter().copied().collect()
+    }
+
     // Ticks
 
     /// Gets the current tick of the Server
@@ -996,6 +2419,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.time_manager.average_tick_duration()
     }
 
+    /// Gets how long until the Server's next Tick is due, i.e. how long a
+    /// loop driving the Server may sleep without delaying it
+    pub fn duration_until_next_tick(&self) -> Duration {
+        self.time_manager.duration_until_next_tick(&Instant::now())
+    }
+
+    /// Gets the current GameInstant of the Server, the clock which the send
+    /// instants of `TimedMessageEvent` & `TimedRequestEvent` are given on
+    pub fn game_time_now(&self) -> GameInstant {
+        self.time_manager.game_time_now()
+    }
+
     // Bandwidth monitoring
     pub fn outgoing_bandwidth_total(&mut self) -> f32 {
         self.io.outgoing_bandwidth_total()
@@ -1013,6 +2448,119 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.io.incoming_bandwidth_from_client(address)
     }
 
+    /// The outgoing bandwidth to connected Clients through the Socket at the
+    /// given index, see `listen()`
+    pub fn outgoing_bandwidth_through_socket(&mut self, socket_index: usize) -> f32 {
+        self.io.outgoing_bandwidth_through_socket(socket_index)
+    }
+
+    /// The incoming bandwidth from connected Clients through the Socket at
+    /// the given index, see `listen()`
+    pub fn incoming_bandwidth_through_socket(&mut self, socket_index: usize) -> f32 {
+        self.io.incoming_bandwidth_through_socket(socket_index)
+    }
+
+    /// Breaks down the outgoing bandwidth to a Client by Channel (keyed as
+    /// `"channel:<Name>"`) and by Component kind (keyed as `"component:<Name>"`)
+    pub fn outgoing_bandwidth_breakdown(&mut self, address: &SocketAddr) -> HashMap<String, f32> {
+        self.io.outgoing_bandwidth_breakdown(address)
+    }
+
+    /// Totals of the bytes compressed for the Client at the given address &
+    /// the bytes they were compressed into, over its connection's lifetime.
+    /// None if server-to-client compression is not enabled in the Protocol,
+    /// or if the Client is not connected
+    pub fn compression_stats(&self, address: &SocketAddr) -> Option<CompressionStats> {
+        self.io.compression_stats(address)
+    }
+
+    /// The number of received packets dropped because their checksum did not
+    /// match, see `ConnectionConfig::packet_checksums`
+    pub fn corrupt_packets_count(&self) -> u64 {
+        self.io.corrupt_packets()
+    }
+
+    // Metrics
+
+    /// Collects the Server's internal counters into a MetricsSnapshot
+    #[cfg(feature = "metrics")]
+    pub fn metrics_snapshot(&mut self) -> MetricsSnapshot {
+        let (outgoing_bandwidth, incoming_bandwidth) = if self.io.bandwidth_monitor_enabled() {
+            (
+                self.io.outgoing_bandwidth_total() as f64,
+                self.io.incoming_bandwidth_total() as f64,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        let mut snapshot = MetricsSnapshot {
+            packets_sent: self.io.packets_sent(),
+            packets_received: self.io.packets_received(),
+            bytes_sent: self.io.bytes_sent(),
+            bytes_received: self.io.bytes_received(),
+            packets_corrupt: self.io.corrupt_packets(),
+            outgoing_bandwidth,
+            incoming_bandwidth,
+            connected_users: self.user_connections.len() as u64,
+            rooms: self.rooms.len() as u64,
+            replicated_entities: self.global_world_manager.entities_count() as u64,
+            ..Default::default()
+        };
+
+        let channel_kinds: Vec<ChannelKind> = self
+            .protocol
+            .channel_kinds
+            .channels()
+            .into_iter()
+            .map(|(channel_kind, _)| channel_kind)
+            .collect();
+        for connection in self.user_connections.values() {
+            let message_manager = &connection.base.message_manager;
+            for channel_kind in &channel_kinds {
+                let counts: &mut ChannelMessageCounts =
+                    snapshot.channel_messages.entry(*channel_kind).or_default();
+                counts.sent += message_manager.sent_messages_count(channel_kind);
+                counts.received += message_manager.received_messages_count(channel_kind);
+            }
+            snapshot.messages_resent += message_manager.resent_messages_count();
+            snapshot.packets_dropped += connection.base.dropped_packets_count();
+        }
+        for channel_kind in &channel_kinds {
+            if let Some(stats) = self.channel_latency_of(channel_kind) {
+                snapshot.channel_latencies.insert(*channel_kind, stats);
+            }
+        }
+
+        snapshot
+    }
+
+    /// Registers a callback which will be passed a fresh MetricsSnapshot every
+    /// `interval`, checked during `Server::receive()`. Replaces any previously
+    /// registered callback.
+    #[cfg(feature = "metrics")]
+    pub fn on_metrics_interval(
+        &mut self,
+        interval: Duration,
+        callback: impl FnMut(&MetricsSnapshot) + Send + Sync + 'static,
+    ) {
+        self.metrics_interval = Some(MetricsInterval::new(interval, callback));
+    }
+
+    #[cfg(feature = "metrics")]
+    fn handle_metrics_interval(&mut self) {
+        let Some(metrics_interval) = &self.metrics_interval else {
+            return;
+        };
+        if !metrics_interval.ringing() {
+            return;
+        }
+        let snapshot = self.metrics_snapshot();
+        if let Some(metrics_interval) = &mut self.metrics_interval {
+            metrics_interval.fire(&snapshot);
+        }
+    }
+
     // Ping
     /// Gets the average Round Trip Time measured to the given User's Client
     pub fn rtt(&self, user_key: &UserKey) -> Option<f32> {
@@ -1041,6 +2589,45 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         None
     }
 
+    /// Gets the rolling quality of the connection to the given User's Client:
+    /// packet loss, round trip time, jitter and bytes carried each second
+    pub fn connection_quality(&mut self, user_key: &UserKey) -> Option<ConnectionQuality> {
+        let user = self.users.get(user_key)?;
+        if !user.has_address() {
+            return None;
+        }
+        let connection = self.user_connections.get_mut(&user.address())?;
+        Some(connection.connection_quality(&mut self.io))
+    }
+
+    /// Gets how far behind the connection to the given User's Client is in
+    /// delivering Entity actions: how many are pending, & how long the oldest
+    /// has been. Returns None if the User is not connected
+    pub fn scope_backlog(&self, user_key: &UserKey) -> Option<ScopeBacklog> {
+        let user = self.users.get(user_key)?;
+        if !user.has_address() {
+            return None;
+        }
+        let connection = self.user_connections.get(&user.address())?;
+        Some(connection.base.scope_backlog())
+    }
+
+    /// Gets percentiles of how long recent Messages & Requests received over
+    /// Channel C, from any User, took to arrive. Only Channels configured
+    /// `with_latency_tracking(true)` are measured
+    pub fn channel_latency<C: Channel>(&self) -> Option<ChannelLatencyStats> {
+        self.channel_latency_of(&ChannelKind::of::<C>())
+    }
+
+    fn channel_latency_of(&self, channel_kind: &ChannelKind) -> Option<ChannelLatencyStats> {
+        ChannelLatencyStats::from_samples(self.user_connections.values().flat_map(|connection| {
+            connection
+                .base
+                .message_manager
+                .latency_samples(channel_kind)
+        }))
+    }
+
     // Crate-Public methods
 
     //// Entities
@@ -1060,22 +2647,33 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     }
 
     pub fn despawn_entity_worldless(&mut self, entity: &E) {
-        if !self.global_world_manager.has_entity(entity) {
+        if self.try_despawn_entity_worldless(entity).is_err() {
             info!("attempting to despawn entity that does not exist, this can happen if a delegated entity is being despawned");
-            return;
+        }
+    }
+
+    // This intended to be used by adapter crates, do not use this as it will not update the world
+    pub fn try_despawn_entity_worldless(&mut self, entity: &E) -> Result<(), NaiaServerError> {
+        if !self.global_world_manager.has_entity(entity) {
+            return Err(NaiaServerError::EntityDoesNotExist);
         }
         self.cleanup_entity_replication(entity);
         self.global_world_manager.remove_entity_record(entity);
+        return Ok(());
     }
 
     fn cleanup_entity_replication(&mut self, entity: &E) {
         self.despawn_entity_from_all_connections(entity);
+        self.auth_idle_timeouts.remove(entity);
 
         // Delete scope
         self.entity_scope_map.remove_entity(entity);
+        self.scope_dependencies
+            .remove_entity(entity, self.server_config.despawn_scope_dependents);
 
         // Delete room cache entry
         if let Some(room_keys) = self.entity_room_map.remove_from_all_rooms(entity) {
+            self.scope_cache.rooms_changed();
             for room_key in room_keys {
                 if let Some(room) = self.rooms.get_mut(&room_key) {
                     room.remove_entity(entity, true);
@@ -1096,6 +2694,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 //remove entity from user connection
                 connection.base.host_world_manager.despawn_entity(entity);
             }
+            connection.forget_entity_heard(entity);
         }
     }
 
@@ -1104,6 +2703,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     /// Remove all entities from a User's scope
     pub(crate) fn user_scope_remove_user(&mut self, user_key: &UserKey) {
         self.entity_scope_map.remove_user(user_key);
+        if let Some(user) = self.users.get(user_key) {
+            self.scope_cache
+                .user_dirty(user_key, user.room_keys().iter());
+        }
     }
 
     pub(crate) fn user_scope_set_entity(
@@ -1112,11 +2715,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         entity: &E,
         is_contained: bool,
     ) {
+        if self.entity_scope_map.get(user_key, entity) == Some(&is_contained) {
+            return;
+        }
         self.entity_scope_map
             .insert(*user_key, *entity, is_contained);
+        self.scope_cache.pair_dirty(user_key, entity);
     }
 
     pub(crate) fn user_scope_has_entity(&self, user_key: &UserKey, entity: &E) -> bool {
+        if self.entity_scope_map.is_direct(user_key, entity) {
+            return true;
+        }
         if let Some(in_scope) = self.entity_scope_map.get(user_key, entity) {
             *in_scope
         } else {
@@ -1124,6 +2734,66 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    pub(crate) fn user_scope_set_entity_direct(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+        is_included: bool,
+    ) {
+        if is_included {
+            if !self.global_world_manager.has_entity(entity) {
+                warn!("cannot include an Entity which is not replicating in a User's scope");
+                return;
+            }
+            self.entity_scope_map.include_direct(*user_key, *entity);
+        } else {
+            self.entity_scope_map.exclude_direct(user_key, entity);
+        }
+    }
+
+    pub(crate) fn user_scope_set_entity_paused(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+        is_paused: bool,
+    ) {
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        if !user.has_address() {
+            return;
+        }
+        let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+            return;
+        };
+        let host_world_manager = &mut connection.base.host_world_manager;
+        if !host_world_manager.host_has_entity(entity) {
+            warn!("cannot pause or resume an Entity which is not in the User's scope");
+            return;
+        }
+        if is_paused {
+            host_world_manager.pause_entity_updates(entity);
+        } else {
+            host_world_manager.resume_entity_updates(entity);
+        }
+    }
+
+    pub(crate) fn user_scope_entity_is_paused(&self, user_key: &UserKey, entity: &E) -> bool {
+        let Some(user) = self.users.get(user_key) else {
+            return false;
+        };
+        if !user.has_address() {
+            return false;
+        }
+        let Some(connection) = self.user_connections.get(&user.address()) else {
+            return false;
+        };
+        connection
+            .base
+            .host_world_manager
+            .entity_updates_paused(entity)
+    }
+
     //// Components
 
     /// Adds a Component to an Entity
@@ -1157,16 +2827,31 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
 
     // This intended to be used by adapter crates, do not use this as it will not update the world
     pub fn insert_component_worldless(&mut self, entity: &E, component: &mut dyn Replicate) {
-        let component_kind = component.kind();
+        if let Err(error) = self.try_insert_component_worldless(entity, component) {
+            panic!("{}", error);
+        }
+    }
 
-        if self
-            .global_world_manager
+    // This intended to be used by adapter crates, do not use this as it will not update the world
+    pub fn try_insert_component_worldless(
+        &mut self,
+        entity: &E,
+        component: &mut dyn Replicate,
+    ) -> Result<(), NaiaServerError> {
+        if !self.global_world_manager.has_entity(entity) {
+            return Err(NaiaServerError::EntityDoesNotExist);
+        }
+
+        let component_kind = component.kind();
+
+        if self
+            .global_world_manager
             .has_component_record(entity, &component_kind)
         {
             warn!(
                 "Attempted to add component `{:?}` to entity that already has it, this can happen if a delegated entity's auth is transferred to the Server before the Server Adapter has been able to process the newly inserted Component. Skipping this action.",
                 component.name());
-            return;
+            return Ok(());
         }
 
         self.insert_new_component_into_entity_scopes(entity, &component_kind, None);
@@ -1182,6 +2867,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             let accessor = self.global_world_manager.get_entity_auth_accessor(entity);
             component.enable_delegation(&accessor, None)
         }
+
+        return Ok(());
     }
 
     fn insert_new_component_into_entity_scopes(
@@ -1329,7 +3016,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             // are in each User's scope
             let mut messages_to_send = Vec::new();
             for (user_key, user) in self.users.iter() {
-                if !user.has_address() {
+                if !user.has_address() || user.is_observer() {
                     continue;
                 }
                 if let Some(connection) = self.user_connections.get(&user.address()) {
@@ -1398,7 +3085,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.global_world_manager.migrate_entity_to_server(&entity);
 
         // we set this to true immediately since it's already being replicated out to the remote
-        self.entity_scope_map.insert(user_key, *entity, true);
+        self.user_scope_set_entity(&user_key, entity, true);
 
         // Migrate Entity from Remote -> Host connection
         let Some(user) = self.users.get(&user_key) else {
@@ -1453,7 +3140,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             // are in each User's scope
             let mut messages_to_send = Vec::new();
             for (user_key, user) in self.users.iter() {
-                if !user.has_address() {
+                if !user.has_address() || user.is_observer() {
                     continue;
                 }
                 let connection = self.user_connections.get(&user.address()).unwrap();
@@ -1494,6 +3181,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         return None;
     }
 
+    /// Returns the headers the given User's auth request carried
+    pub(crate) fn user_auth_headers(&self, user_key: &UserKey) -> Option<&[(String, String)]> {
+        if let Some(user) = self.users.get(user_key) {
+            return Some(user.auth_headers());
+        }
+        return None;
+    }
+
     /// Get an count of how many Rooms the given User is inside
     pub(crate) fn user_rooms_count(&self, user_key: &UserKey) -> Option<usize> {
         if let Some(user) = self.users.get(user_key) {
@@ -1521,6 +3216,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 let copied_entities = all_owned_entities.clone();
                 for entity in copied_entities {
                     self.entity_release_authority(Some(user_key), &entity);
+                    self.incoming_events
+                        .push_auth_reset(&entity, EntityAuthResetReason::OwnerDisconnected);
                 }
             }
         }
@@ -1534,6 +3231,66 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         warn!("    DisconnectEvent pushed for {:?}", user_key);
     }
 
+    pub(crate) fn user_set_timeout_duration(&mut self, user_key: &UserKey, duration: Duration) {
+        let Some(user) = self.users.get_mut(user_key) else {
+            panic!("Attempting to set the timeout of a nonexistent user");
+        };
+        user.set_timeout_duration(duration);
+        if let Some(address) = user.address_opt() {
+            if let Some(connection) = self.user_connections.get_mut(&address) {
+                connection.base.set_timeout_duration(duration);
+            }
+        }
+
+        // timeouts must be checked at least as often as the shortest one
+        if duration < self.timeout_timer.duration() {
+            self.timeout_timer.set_duration(duration);
+        }
+    }
+
+    pub(crate) fn user_is_observer(&self, user_key: &UserKey) -> bool {
+        let Some(user) = self.users.get(user_key) else {
+            panic!("Attempting to access a nonexistent user");
+        };
+        user.is_observer()
+    }
+
+    pub(crate) fn user_set_observer(&mut self, user_key: &UserKey, observer: bool) {
+        if !self.users.contains_key(user_key) {
+            panic!("Attempting to make a nonexistent user an observer");
+        }
+        if observer {
+            // released while the User still hears of it
+            if let Some(owned_entities) =
+                self.global_world_manager.user_all_owned_entities(user_key)
+            {
+                for entity in owned_entities.clone() {
+                    self.entity_release_authority(Some(user_key), &entity);
+                    self.incoming_events
+                        .push_auth_reset(&entity, EntityAuthResetReason::OwnerObserving);
+                }
+            }
+        }
+        self.users.get_mut(user_key).unwrap().set_observer(observer);
+    }
+
+    pub(crate) fn user_set_ping_interval(&mut self, user_key: &UserKey, interval: Duration) {
+        let Some(user) = self.users.get_mut(user_key) else {
+            panic!("Attempting to set the ping interval of a nonexistent user");
+        };
+        user.set_ping_interval(interval);
+        if let Some(address) = user.address_opt() {
+            if let Some(connection) = self.user_connections.get_mut(&address) {
+                connection.ping_manager.set_ping_interval(interval);
+            }
+        }
+
+        // pings must be checked at least as often as the shortest interval
+        if interval < self.ping_timer.duration() {
+            self.ping_timer.set_duration(interval);
+        }
+    }
+
     pub(crate) fn user_queue_disconnect(&mut self, user_key: &UserKey) {
         let Some(user) = self.users.get(user_key) else {
             panic!("Attempting to disconnect a nonexistent user");
@@ -1583,11 +3340,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         if let Some(user_addr) = user.address_opt() {
             info!("deleting authenticated user for {}", user.address());
             self.user_connections.remove(&user_addr);
+            self.io.remove_packet_cipher(&user_addr);
         }
 
         self.user_key_to_addr.remove(user_key);
 
         self.entity_scope_map.remove_user(user_key);
+        self.scope_cache.rooms_changed();
 
         self.handshake_manager
             .delete_user(user_key, user.address_opt());
@@ -1598,11 +3357,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 .get_mut(room_key)
                 .unwrap()
                 .unsubscribe_user(user_key);
+            self.room_check_emptied(room_key);
         }
 
         // remove from bandwidth monitor
-        if self.io.bandwidth_monitor_enabled() {
-            self.io.deregister_client(&user.address());
+        if let Some(user_addr) = user.address_opt() {
+            if self.io.bandwidth_monitor_enabled() {
+                self.io.deregister_client(&user_addr);
+            }
+            self.io.untrack_byte_rates(&user_addr);
+            self.io.untrack_compression_stats(&user_addr);
+            self.io.unbind_client_socket(&user_addr);
         }
 
         return user;
@@ -1613,23 +3378,106 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     /// Deletes the Room associated with a given RoomKey on the Server.
     /// Returns true if the Room existed.
     pub(crate) fn room_destroy(&mut self, room_key: &RoomKey) -> bool {
+        self.room_destroy_inner(room_key, RoomCleanupReason::Manual)
+    }
+
+    fn room_destroy_inner(&mut self, room_key: &RoomKey, reason: RoomCleanupReason) -> bool {
+        self.resume_room_replication(room_key);
         self.room_remove_all_entities(room_key);
+        self.emptied_rooms.remove(room_key);
 
         if self.rooms.contains_key(room_key) {
             // TODO: what else kind of cleanup do we need to do here? Scopes?
 
             // actually remove the room from the collection
             let room = self.rooms.remove(room_key).unwrap();
+            self.scope_cache.rooms_changed();
             for user_key in room.user_keys() {
                 self.users.get_mut(user_key).unwrap().uncache_room(room_key);
             }
 
+            self.incoming_events.push_room_destroyed(room_key, reason);
+
             true
         } else {
             false
         }
     }
 
+    /// Destroys each Room set to auto-destroy which has lost its last User,
+    /// applying the Room's cleanup policy to the Entities it contains
+    fn destroy_empty_rooms<W: WorldMutType<E>>(&mut self, world: &mut W) {
+        let room_keys: Vec<RoomKey> = self.emptied_rooms.drain().collect();
+        for room_key in room_keys {
+            let Some(room) = self.rooms.get(&room_key) else {
+                continue;
+            };
+            // a User may have joined again since
+            if room.users_count() > 0 {
+                continue;
+            }
+            let Some(policy) = room.auto_destroy_policy() else {
+                continue;
+            };
+            let entities: Vec<E> = room.entities().copied().collect();
+
+            self.room_destroy_inner(&room_key, RoomCleanupReason::Empty);
+
+            if policy == RoomCleanupPolicy::DespawnEntities {
+                for entity in entities {
+                    if self.global_world_manager.entity_owner(&entity) == Some(EntityOwner::Server)
+                        && world.has_entity(&entity)
+                    {
+                        self.despawn_entity(world, &entity);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Despawns each Entity passed to `despawn_entity_after_ack()` which no
+    /// User has pending updates of
+    fn despawn_acked_entities<W: WorldMutType<E>>(&mut self, world: &mut W) {
+        let entities: Vec<E> = self.despawns_after_ack.iter().copied().collect();
+        for entity in entities {
+            // despawned some other way since
+            if !world.has_entity(&entity) || !self.global_world_manager.has_entity(&entity) {
+                self.despawns_after_ack.remove(&entity);
+                continue;
+            }
+            let has_pending_updates = self.user_connections.values().any(|connection| {
+                connection
+                    .base
+                    .host_world_manager
+                    .has_pending_updates(&entity)
+            });
+            if has_pending_updates {
+                continue;
+            }
+            self.despawns_after_ack.remove(&entity);
+            self.despawn_entity(world, &entity);
+        }
+    }
+
+    pub(crate) fn room_set_auto_destroy(
+        &mut self,
+        room_key: &RoomKey,
+        policy: Option<RoomCleanupPolicy>,
+    ) {
+        if let Some(room) = self.rooms.get_mut(room_key) {
+            room.set_auto_destroy_policy(policy);
+        }
+    }
+
+    fn room_check_emptied(&mut self, room_key: &RoomKey) {
+        let Some(room) = self.rooms.get(room_key) else {
+            return;
+        };
+        if room.users_count() == 0 && room.auto_destroy_policy().is_some() {
+            self.emptied_rooms.insert(*room_key);
+        }
+    }
+
     //////// users
 
     /// Returns whether or not an User is currently in a specific Room, given
@@ -1649,6 +3497,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             if let Some(room) = self.rooms.get_mut(room_key) {
                 room.subscribe_user(user_key);
                 user.cache_room(room_key);
+                self.scope_cache.user_added(room_key, user_key);
             }
         }
     }
@@ -1659,8 +3508,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             if let Some(room) = self.rooms.get_mut(room_key) {
                 room.unsubscribe_user(user_key);
                 user.uncache_room(room_key);
+                self.scope_cache.rooms_changed();
             }
         }
+        self.room_check_emptied(room_key);
     }
 
     /// Get a count of Users in a given Room
@@ -1720,22 +3571,61 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     /// Entities will only ever be in-scope for Users which are in a Room with
     /// them.
     pub(crate) fn room_add_entity(&mut self, room_key: &RoomKey, entity: &E) {
-        let mut is_some = false;
-        if let Some(room) = self.rooms.get_mut(room_key) {
-            room.add_entity(entity);
-            is_some = true;
+        if let Err(error) = self.try_room_add_entity(room_key, entity) {
+            warn!("{}", error);
         }
-        if !is_some {
-            return;
+    }
+
+    /// Add an Entity to a Room associated with the given RoomKey.
+    /// Returns an error if the Room does not exist.
+    pub fn try_room_add_entity(
+        &mut self,
+        room_key: &RoomKey,
+        entity: &E,
+    ) -> Result<(), NaiaServerError> {
+        let Some(room) = self.rooms.get_mut(room_key) else {
+            return Err(NaiaServerError::RoomDoesNotExist);
+        };
+        room.add_entity(entity);
+        if room.is_paused() && self.global_world_manager.has_entity(entity) {
+            self.global_world_manager.pause_entity_replication(entity);
         }
         self.entity_room_map.entity_add_room(entity, room_key);
+        self.scope_cache.entity_added(room_key, entity);
+        return Ok(());
     }
 
     /// Remove an Entity from a Room, associated with the given RoomKey
     pub(crate) fn room_remove_entity(&mut self, room_key: &RoomKey, entity: &E) {
         if let Some(room) = self.rooms.get_mut(room_key) {
             room.remove_entity(entity, false);
+            let room_is_paused = room.is_paused();
             self.entity_room_map.remove_from_room(entity, room_key);
+            self.scope_cache.rooms_changed();
+            if room_is_paused {
+                self.resume_entity_unless_room_paused(entity);
+            }
+        }
+    }
+
+    // resumes an Entity which left a paused Room, or whose paused Room was
+    // resumed, unless another paused Room still holds it
+    fn resume_entity_unless_room_paused(&mut self, entity: &E) {
+        if !self.global_world_manager.has_entity(entity) {
+            return;
+        }
+        let in_paused_room =
+            self.entity_room_map
+                .entity_get_rooms(entity)
+                .is_some_and(|room_keys| {
+                    room_keys.iter().any(|room_key| {
+                        self.rooms
+                            .get(room_key)
+                            .is_some_and(|room| room.is_paused())
+                    })
+                });
+        if !in_paused_room {
+            self.global_world_manager.resume_entity_replication(entity);
         }
     }
 
@@ -1747,6 +3637,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 room.remove_entity(&entity, false);
                 self.entity_room_map.remove_from_room(&entity, room_key);
             }
+            self.scope_cache.rooms_changed();
         }
     }
 
@@ -1761,8 +3652,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     // Private methods
 
     /// Maintain connection with a client and read all incoming packet data
-    fn maintain_socket<W: WorldMutType<E>>(&mut self, mut world: W, now: &Instant) {
-        self.handle_disconnects(&mut world);
+    fn maintain_socket<W: WorldMutType<E>>(&mut self, world: &mut W, now: &Instant) {
+        self.handle_disconnects(world);
         self.handle_heartbeats();
         self.handle_pings();
         self.handle_empty_acks();
@@ -1770,12 +3661,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         let mut addresses: HashSet<SocketAddr> = HashSet::new();
 
         // receive auth events
-        if let Some((_, auth_receiver)) = self.auth_io.as_mut() {
+        for (auth_socket, (_, auth_receiver)) in self.auth_io.iter_mut().enumerate() {
             loop {
                 match auth_receiver.receive() {
-                    Ok(Some((auth_addr, auth_bytes))) => {
+                    Ok(Some((auth_addr, auth_headers, auth_bytes))) => {
                         // create new user
-                        let user_key = self.users.insert(User::new(auth_addr));
+                        let user_key = self.users.insert(User::new(
+                            auth_addr,
+                            auth_socket,
+                            auth_headers.to_vec(),
+                        ));
 
                         // convert bytes into auth object
                         let mut reader = BitReader::new(auth_bytes);
@@ -1806,6 +3701,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         loop {
             match self.io.recv_reader() {
                 Ok(Some((address, owned_reader))) => {
+                    #[cfg(feature = "tracing")]
+                    let _span = tracing::debug_span!("read_packet", %address).entered();
+
                     // receive packet
                     let mut reader = owned_reader.borrow();
 
@@ -1813,6 +3711,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     let Ok(header) = StandardHeader::de(&mut reader) else {
                         // Received a malformed packet
                         // TODO: increase suspicion against packet sender
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(%address, packet_type = "unknown", "malformed packet");
                         continue;
                     };
                     info!("Header received: {:?}", header);
@@ -1826,11 +3726,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                                 .is_err()
                             {
                                 warn!("Server Error: cannot read malformed packet");
+                                #[cfg(feature = "tracing")]
+                                tracing::warn!(%address, packet_type = "data", "malformed packet");
                                 continue;
                             }
                         }
                         PacketType::Ping => {
-                            let response = self.time_manager.process_ping(&mut reader).unwrap();
+                            let Ok(response) = self.time_manager.process_ping(&mut reader) else {
+                                warn!("Server Error: cannot read malformed ping packet");
+                                continue;
+                            };
                             // send packet
                             if self.io.send_packet(&address, response.to_packet()).is_err() {
                                 // TODO: pass this on and handle above
@@ -1849,6 +3754,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                             if let Some(connection) = self.user_connections.get_mut(&address) {
                                 connection.process_incoming_header(&header);
                                 connection.base.mark_heard();
+
+                                // Heartbeats from older Clients end here, which
+                                // reads as no payload
+                                if let Ok(Some(payload)) = HeartbeatPayload::read(
+                                    &self.protocol.message_kinds,
+                                    &mut reader,
+                                ) {
+                                    self.incoming_events
+                                        .push_heartbeat_payload(&connection.user_key, payload);
+                                }
                             }
 
                             continue;
@@ -1889,13 +3804,30 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                                 false
                             };
 
-                            match self.handshake_manager.maintain_handshake(
+                            let mut outcome = self.handshake_manager.process_incoming(
                                 &address,
                                 &mut reader,
                                 has_different_user_connection,
-                            ) {
-                                Ok(HandshakeAction::None) => {}
-                                Ok(HandshakeAction::FinalizeConnection(
+                            );
+                            if let Ok(HandshakeOutcome::FinalizeConnection(user_key, _)) = &outcome
+                            {
+                                if self.is_full() {
+                                    let error = HandshakeError::ServerFull;
+                                    let packet = self
+                                        .handshake_manager
+                                        .write_reject_response(&error)
+                                        .to_packet();
+                                    outcome = Ok(HandshakeOutcome::RejectConnection(
+                                        Some(*user_key),
+                                        packet,
+                                        error,
+                                    ));
+                                }
+                            }
+
+                            match outcome {
+                                Ok(HandshakeOutcome::None) => {}
+                                Ok(HandshakeOutcome::FinalizeConnection(
                                     user_key,
                                     validate_packet,
                                 )) => {
@@ -1908,19 +3840,53 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                                         );
                                     }
                                 }
-                                Ok(HandshakeAction::SendPacket(packet)) => {
+                                Ok(HandshakeOutcome::SendPacket(packet)) => {
                                     if self.io.send_packet(&address, packet).is_err() {
                                         // TODO: pass this on and handle above
                                         warn!("Server Error: Cannot send packet to {}", &address);
                                     }
                                 }
-                                Ok(HandshakeAction::DisconnectUser(user_key)) => {
-                                    self.user_disconnect(&user_key, &mut world);
+                                Ok(HandshakeOutcome::DisconnectUser(user_key)) => {
+                                    self.user_disconnect(&user_key, world);
+                                }
+                                Ok(HandshakeOutcome::RejectConnection(
+                                    user_key_opt,
+                                    reject_packet,
+                                    error,
+                                )) => {
+                                    if self.io.send_packet(&address, reject_packet).is_err() {
+                                        // TODO: pass this on and handle above
+                                        warn!(
+                                            "Server Error: Cannot send reject packet to {}",
+                                            &address
+                                        );
+                                    }
+                                    // the Client keeps retrying until it receives the rejection,
+                                    // so only report it the first time
+                                    if let Some(user_key) = user_key_opt {
+                                        warn!("{} from {}", error, &address);
+                                        self.user_delete(&user_key);
+                                        self.incoming_events
+                                            .push_error(NaiaServerError::Wrapped(Box::new(error)));
+                                    }
                                 }
                                 Err(_err) => {
                                     warn!("Server Error: cannot read malformed packet");
+                                    #[cfg(feature = "tracing")]
+                                    tracing::warn!(
+                                        %address,
+                                        packet_type = "handshake",
+                                        "malformed packet"
+                                    );
                                 }
                             }
+
+                            // keys derived during the handshake apply to every following packet
+                            if let Some(cipher) =
+                                self.handshake_manager.take_packet_cipher(&address)
+                            {
+                                self.io.set_packet_cipher(&address, cipher);
+                            }
                         }
                     }
                 }
@@ -1936,7 +3902,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
 
         for address in addresses {
-            self.process_packets(&address, &mut world, now);
+            self.process_packets(&address, world, now);
         }
     }
 
@@ -1987,19 +3953,31 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         world: &mut W,
         now: &Instant,
     ) {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "process_packets",
+            %address,
+            user_key = tracing::field::Empty
+        )
+        .entered();
+
         // Packets requiring established connection
         let (user_key, response_events) = {
             let Some(connection) = self.user_connections.get_mut(address) else {
                 return;
             };
+            #[cfg(feature = "tracing")]
+            span.record("user_key", tracing::field::debug(&connection.user_key));
             (
                 connection.user_key,
                 connection.process_packets(
                     &self.protocol,
                     now,
+                    &self.time_manager.game_time_now(),
                     &mut self.global_world_manager,
                     &mut self.global_request_manager,
                     &mut self.global_response_manager,
+                    self.message_validator.as_ref(),
                     world,
                     &mut self.incoming_events,
                 ),
@@ -2018,13 +3996,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         for response_event in response_events {
             match response_event {
                 EntityResponseEvent::SpawnEntity(entity) => {
+                    if self.global_world_manager.has_entity(&entity) {
+                        self.incoming_events
+                            .push_error(NaiaServerError::from_message(
+                                "Client attempted to spawn an Entity which already exists",
+                            ));
+                        continue;
+                    }
                     self.global_world_manager
                         .spawn_entity_record(&entity, EntityOwner::Client(*user_key));
-                    let user = self.users.get(user_key).unwrap();
+                    let Some(user) = self.users.get(user_key) else {
+                        continue;
+                    };
                     if !user.has_address() {
                         continue;
                     }
-                    let connection = self.user_connections.get_mut(&user.address()).unwrap();
+                    let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                        continue;
+                    };
                     let local_entity = connection
                         .base
                         .local_world_manager
@@ -2036,8 +4025,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                         .on_entity_channel_opened(&local_entity);
                 }
                 EntityResponseEvent::InsertComponent(entity, component_kind) => {
+                    if let Err(error) = self.check_entity_exists(&entity) {
+                        self.incoming_events.push_error(error);
+                        continue;
+                    }
                     self.global_world_manager
                         .insert_component_record(&entity, &component_kind);
+                    if let Some(handlers) = self.insert_component_handlers.get_mut(&component_kind)
+                    {
+                        for handler in handlers {
+                            handler(user_key, &entity);
+                        }
+                    }
                     if self
                         .global_world_manager
                         .entity_is_public_and_client_owned(&entity)
@@ -2057,12 +4056,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                             );
 
                             // track remote component on the originating connection for the time being
-                            let user = self.users.get(user_key).unwrap();
+                            let Some(user) = self.users.get(user_key) else {
+                                continue;
+                            };
                             if !user.has_address() {
                                 continue;
                             }
                             let addr = user.address();
-                            let connection = self.user_connections.get_mut(&addr).unwrap();
+                            let Some(connection) = self.user_connections.get_mut(&addr) else {
+                                continue;
+                            };
                             connection
                                 .base
                                 .host_world_manager
@@ -2077,6 +4080,20 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     }
                 }
                 EntityResponseEvent::RemoveComponent(entity, component_kind) => {
+                    if let Err(error) = self.check_entity_exists(&entity) {
+                        self.incoming_events.push_error(error);
+                        continue;
+                    }
+                    if !self
+                        .global_world_manager
+                        .has_component_record(&entity, &component_kind)
+                    {
+                        self.incoming_events
+                            .push_error(NaiaServerError::from_message(
+                                "Client attempted to remove a Component which does not exist",
+                            ));
+                        continue;
+                    }
                     if self
                         .global_world_manager
                         .entity_is_public_and_client_owned(&entity)
@@ -2094,10 +4111,29 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             }
         }
 
+        let observer = self
+            .users
+            .get(user_key)
+            .is_some_and(|user| user.is_observer());
         let mut extra_deferred_events = Vec::new();
         // The reason for deferring these events is that they depend on the operations to the world above
         for response_event in deferred_events {
+            if let Some(entity) = Self::response_event_entity(&response_event) {
+                if let Err(error) = self.check_entity_exists(&entity) {
+                    self.incoming_events.push_error(error);
+                    continue;
+                }
+            }
             match response_event {
+                EntityResponseEvent::EnableDelegationEntity(_)
+                | EntityResponseEvent::EnableDelegationEntityResponse(_)
+                | EntityResponseEvent::EntityRequestAuthority(_, _)
+                | EntityResponseEvent::EntityReleaseAuthority(_)
+                    if observer =>
+                {
+                    // observers never take part in delegation
+                    continue;
+                }
                 EntityResponseEvent::PublishEntity(entity) => {
                     info!("received publish entity message!");
                     self.publish_entity(world, &entity, false);
@@ -2116,21 +4152,65 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     self.entity_enable_delegation_response(user_key, &entity);
                 }
                 EntityResponseEvent::DisableDelegationEntity(_) => {
-                    panic!("Clients should not be able to disable entity delegation.");
+                    self.incoming_events
+                        .push_error(NaiaServerError::from_message(
+                            "Clients should not be able to disable entity delegation.",
+                        ));
                 }
                 EntityResponseEvent::EntityRequestAuthority(world_entity, remote_entity) => {
-                    self.client_request_authority(user_key, &world_entity, &remote_entity);
+                    let already_granted = self
+                        .global_world_manager
+                        .user_all_owned_entities(user_key)
+                        .is_some_and(|entities| entities.contains(&world_entity));
+                    if already_granted {
+                        // authority was transferred to this User before its
+                        // request arrived, or without one, so only its Host
+                        // Entity needs recording
+                        self.add_redundant_remote_entity_to_host(
+                            user_key,
+                            &world_entity,
+                            &remote_entity,
+                        );
+                    } else {
+                        self.client_request_authority(user_key, &world_entity, &remote_entity);
+                    }
                 }
                 EntityResponseEvent::EntityReleaseAuthority(entity) => {
                     // info!("received release auth entity message!");
                     self.entity_release_authority(Some(user_key), &entity);
-                    self.incoming_events.push_auth_reset(&entity);
+                    self.incoming_events
+                        .push_auth_reset(&entity, EntityAuthResetReason::Released);
                 }
                 EntityResponseEvent::EntityUpdateAuthority(_, _) => {
-                    panic!("Clients should not be able to update entity authority.");
+                    self.incoming_events
+                        .push_error(NaiaServerError::from_message(
+                            "Clients should not be able to update entity authority.",
+                        ));
                 }
                 EntityResponseEvent::EntityMigrateResponse(_, _) => {
-                    panic!("Clients should not be able to send this message");
+                    self.incoming_events
+                        .push_error(NaiaServerError::from_message(
+                            "Clients should not be able to send this message",
+                        ));
+                }
+                EntityResponseEvent::EntityRequestResync(entity) => {
+                    // a Client may only resync Entities which are in its scope
+                    if !self.user_scope_has_entity(user_key, &entity) {
+                        continue;
+                    }
+                    let Some(user) = self.users.get(user_key) else {
+                        continue;
+                    };
+                    if !user.has_address() {
+                        continue;
+                    }
+                    let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                        continue;
+                    };
+                    if !connection.base.host_world_manager.host_has_entity(&entity) {
+                        continue;
+                    }
+                    connection.base.host_world_manager.resync_entity(&entity);
                 }
                 _ => {
                     extra_deferred_events.push(response_event);
@@ -2141,23 +4221,38 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         for response_event in extra_deferred_events {
             match response_event {
                 EntityResponseEvent::DespawnEntity(entity) => {
+                    if let Err(error) = self.check_entity_exists(&entity) {
+                        self.incoming_events.push_error(error);
+                        continue;
+                    }
                     if self
                         .global_world_manager
                         .entity_is_public_and_client_owned(&entity)
                         || self.global_world_manager.entity_is_delegated(&entity)
                     {
                         // remove from host connection
-                        let user = self.users.get(user_key).unwrap();
+                        let Some(user) = self.users.get(user_key) else {
+                            continue;
+                        };
                         if !user.has_address() {
                             continue;
                         }
-                        let connection = self.user_connections.get_mut(&user.address()).unwrap();
-                        connection
-                            .base
-                            .host_world_manager
-                            .client_initiated_despawn(&entity);
+                        let Some(connection) = self.user_connections.get_mut(&user.address())
+                        else {
+                            continue;
+                        };
+                        // a public Entity is only hosted back to its owner once
+                        // migrated for delegation
+                        if connection.base.host_world_manager.host_has_entity(&entity) {
+                            connection
+                                .base
+                                .host_world_manager
+                                .client_initiated_despawn(&entity);
+                        }
 
-                        self.despawn_entity_worldless(&entity);
+                        if let Err(error) = self.try_despawn_entity_worldless(&entity) {
+                            self.incoming_events.push_error(error);
+                        }
                     } else {
                         self.global_world_manager.remove_entity_record(&entity);
                     }
@@ -2169,6 +4264,27 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    fn check_entity_exists(&self, entity: &E) -> Result<(), NaiaServerError> {
+        if !self.global_world_manager.has_entity(entity) {
+            return Err(NaiaServerError::EntityDoesNotExist);
+        }
+        return Ok(());
+    }
+
+    // The Entity which a deferred EntityResponseEvent must refer to, if any
+    fn response_event_entity(response_event: &EntityResponseEvent<E>) -> Option<E> {
+        match response_event {
+            EntityResponseEvent::PublishEntity(entity)
+            | EntityResponseEvent::UnpublishEntity(entity)
+            | EntityResponseEvent::EnableDelegationEntity(entity)
+            | EntityResponseEvent::EnableDelegationEntityResponse(entity)
+            | EntityResponseEvent::EntityRequestAuthority(entity, _)
+            | EntityResponseEvent::EntityReleaseAuthority(entity)
+            | EntityResponseEvent::EntityRequestResync(entity) => Some(*entity),
+            _ => None,
+        }
+    }
+
     fn handle_disconnects<W: WorldMutType<E>>(&mut self, world: &mut W) {
         // disconnects
         if self.timeout_timer.ringing() {
@@ -2212,21 +4328,40 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     }
 
     fn handle_heartbeats(&mut self) {
+        // a payload is sent every heartbeat interval, even to busy connections
+        let payload_due = self
+            .heartbeat_payload
+            .as_ref()
+            .is_some_and(|heartbeat_payload| heartbeat_payload.should_send());
+
         // heartbeats
-        if self.heartbeat_timer.ringing() {
+        if self.heartbeat_timer.ringing() || payload_due {
             self.heartbeat_timer.reset();
 
             for (user_address, connection) in &mut self.user_connections.iter_mut() {
                 // user heartbeats
-                if connection.base.should_send_heartbeat() {
+                if connection.base.should_send_heartbeat() || payload_due {
+                    let heartbeat_payload = if payload_due {
+                        self.heartbeat_payload.as_mut()
+                    } else {
+                        None
+                    };
                     Self::send_heartbeat_packet(
                         user_address,
                         connection,
+                        &self.protocol.message_kinds,
+                        heartbeat_payload,
                         &self.time_manager,
                         &mut self.io,
                     );
                 }
             }
+
+            if payload_due {
+                if let Some(heartbeat_payload) = self.heartbeat_payload.as_mut() {
+                    heartbeat_payload.mark_sent();
+                }
+            }
         }
     }
 
@@ -2238,6 +4373,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 Self::send_heartbeat_packet(
                     user_address,
                     connection,
+                    &self.protocol.message_kinds,
+                    None,
                     &self.time_manager,
                     &mut self.io,
                 );
@@ -2248,6 +4385,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     fn send_heartbeat_packet(
         user_address: &SocketAddr,
         connection: &mut Connection<E>,
+        message_kinds: &MessageKinds,
+        heartbeat_payload: Option<&mut HeartbeatPayload>,
         time_manager: &TimeManager,
         io: &mut Io,
     ) {
@@ -2266,6 +4405,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         // write server tick instant
         time_manager.current_tick_instant().ser(&mut writer);
 
+        // write payload
+        HeartbeatPayload::write(heartbeat_payload, message_kinds, &mut writer);
+
         // send packet
         if io.send_packet(user_address, writer.to_packet()).is_err() {
             // TODO: pass this on and handle above
@@ -2277,6 +4419,43 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         connection.base.mark_sent();
     }
 
+    fn handle_connection_quality(&mut self) {
+        for connection in self.user_connections.values_mut() {
+            let quality = connection.connection_quality(&mut self.io);
+            self.io.set_connection_quality(&connection.address, quality);
+            if let Some(level) = connection.base.update_quality_level(&quality) {
+                self.incoming_events
+                    .push_connection_quality_change(&connection.user_key, level);
+            }
+        }
+    }
+
+    fn handle_scope_backlogs(&mut self) {
+        for connection in self.user_connections.values_mut() {
+            if let Some(backlog) = connection.base.update_scope_backlog() {
+                warn!(
+                    "Server: {} Entity actions to {} are backlogged, the oldest for {:?}",
+                    backlog.pending_actions, connection.address, backlog.oldest_age
+                );
+                self.incoming_events
+                    .push_scope_backlog(&connection.user_key, backlog);
+            }
+        }
+    }
+
+    fn handle_delivered_receipts(&mut self) {
+        if self.undelivered_receipts.is_empty() {
+            return;
+        }
+        for connection in self.user_connections.values_mut() {
+            for receipt_key in connection.base.message_manager.take_delivered_receipts() {
+                self.undelivered_receipts.remove(&receipt_key);
+                self.incoming_events
+                    .push_message_delivered(&connection.user_key, receipt_key);
+            }
+        }
+    }
+
     fn handle_pings(&mut self) {
         // pings
         if self.ping_timer.ringing() {
@@ -2318,8 +4497,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
 
     // Entity Scopes
 
-    fn update_entity_scopes<W: WorldRefType<E>>(&mut self, world: &W) {
+    fn update_entity_scopes_inner<W: WorldRefType<E>>(&mut self, world: &W) {
         for (_, room) in self.rooms.iter_mut() {
+            // a paused Room's removals wait until it's resumed
+            if room.is_paused() {
+                continue;
+            }
             while let Some((removed_user, removed_entity)) = room.pop_entity_removal_queue() {
                 let Some(user) = self.users.get(&removed_user) else {
                     continue;
@@ -2332,6 +4515,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 };
 
                 // evaluate whether the Entity really needs to be despawned!
+                // what if the Entity was included for this User directly? It shouldn't be despawned!
+                if self
+                    .entity_scope_map
+                    .is_direct(&removed_user, &removed_entity)
+                {
+                    continue;
+                }
                 // what if the Entity shares another Room with this User? It shouldn't be despawned!
                 if let Some(entity_rooms) = self.entity_room_map.entity_get_rooms(&removed_entity) {
                     let user_rooms = user.room_keys();
@@ -2347,6 +4537,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     .host_world_manager
                     .host_has_entity(&removed_entity)
                 {
+                    #[cfg(feature = "tracing")]
+                    trace_scope_change(
+                        &self.global_world_manager,
+                        &removed_user,
+                        &removed_entity,
+                        false,
+                    );
+
                     //remove entity from user connection
                     connection
                         .base
@@ -2356,80 +4554,312 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             }
         }
 
-        for (_, room) in self.rooms.iter_mut() {
-            // TODO: we should be able to cache these tuples of keys to avoid building a new
-            // list each time
-            for user_key in room.user_keys() {
-                let Some(user) = self.users.get(user_key) else {
+        // only (User, Entity) pairs which may have changed since the last
+        // update are evaluated, grouped by User to batch the Entities entering
+        let mut dirty_entities_of_user: HashMap<UserKey, Vec<E>> = HashMap::new();
+        for (user_key, entity) in self.scope_cache.take_dirty(&self.rooms) {
+            dirty_entities_of_user
+                .entry(user_key)
+                .or_default()
+                .push(entity);
+        }
+        for (user_key, entities) in dirty_entities_of_user {
+            let Some(user) = self.users.get(&user_key) else {
+                continue;
+            };
+            if !user.has_address() {
+                continue;
+            }
+            let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                continue;
+            };
+            let mut entering_entities = Vec::new();
+            for entity in entities {
+                // only Entities sharing a Room with the User are scoped here
+                let shares_room =
+                    self.entity_room_map
+                        .entity_get_rooms(&entity)
+                        .is_some_and(|entity_rooms| {
+                            entity_rooms.intersection(user.room_keys()).next().is_some()
+                        });
+                if !shares_room {
                     continue;
-                };
-                if !user.has_address() {
+                }
+                if !world.has_entity(&entity) {
+                    // may yet be spawned, so check again next update
+                    self.scope_cache.pair_dirty(&user_key, &entity);
                     continue;
                 }
-                let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                if self
+                    .global_world_manager
+                    .entity_is_public_and_owned_by_user(&user_key, &entity)
+                {
+                    // entity is owned by client, but it is public, so we don't need to replicate it
+                    // until its ownership changes, so check again next update
+                    self.scope_cache.pair_dirty(&user_key, &entity);
                     continue;
-                };
-                for entity in room.entities() {
-                    if !world.has_entity(entity) {
+                }
+
+                let currently_in_scope =
+                    connection.base.host_world_manager.host_has_entity(&entity);
+
+                let should_be_in_scope = self.entity_scope_map.is_direct(&user_key, &entity)
+                    || if let Some(in_scope) = self.entity_scope_map.get(&user_key, &entity) {
+                        *in_scope
+                    } else {
+                        false
+                    };
+
+                if should_be_in_scope {
+                    if currently_in_scope {
                         continue;
                     }
-                    if self
-                        .global_world_manager
-                        .entity_is_public_and_owned_by_user(user_key, entity)
+                    if !Self::scope_dependencies_met(&self.scope_dependencies, connection, &entity)
                     {
-                        // entity is owned by client, but it is public, so we don't need to replicate it
+                        // waits on its dependencies, so check again next
+                        // update, unless it never can enter
+                        if !self.scope_dependencies.is_orphan(&entity) {
+                            self.scope_cache.pair_dirty(&user_key, &entity);
+                        }
                         continue;
                     }
+                    let component_kinds =
+                        self.global_world_manager.component_kinds(&entity).unwrap();
+                    entering_entities.push((entity, component_kinds));
+                } else if currently_in_scope {
+                    #[cfg(feature = "tracing")]
+                    trace_scope_change(&self.global_world_manager, &user_key, &entity, false);
+
+                    // remove entity from the connections local scope
+                    connection.base.host_world_manager.despawn_entity(&entity);
+                }
+            }
+            if entering_entities.is_empty() {
+                continue;
+            }
+            #[cfg(feature = "tracing")]
+            for (entity, _) in &entering_entities {
+                trace_scope_change(&self.global_world_manager, &user_key, entity, true);
+            }
 
-                    let currently_in_scope =
-                        connection.base.host_world_manager.host_has_entity(entity);
+            Self::init_entering_entities(
+                &self.global_world_manager,
+                &self.protocol,
+                connection,
+                &user_key,
+                user.is_observer(),
+                entering_entities,
+            );
+        }
 
-                    let should_be_in_scope =
-                        if let Some(in_scope) = self.entity_scope_map.get(user_key, entity) {
-                            *in_scope
-                        } else {
-                            false
-                        };
+        // Entities excluded from a User's scope directly leave it, unless a
+        // Room the User shares still keeps them in scope
+        for (user_key, entity) in self.entity_scope_map.take_direct_removals() {
+            let Some(user) = self.users.get(&user_key) else {
+                continue;
+            };
+            if !user.has_address() {
+                continue;
+            }
+            let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                continue;
+            };
+            if !connection.base.host_world_manager.host_has_entity(&entity) {
+                continue;
+            }
+            let in_room_scope = self
+                .entity_scope_map
+                .get(&user_key, &entity)
+                .is_some_and(|in_scope| *in_scope)
+                && self
+                    .entity_room_map
+                    .entity_get_rooms(&entity)
+                    .is_some_and(|entity_rooms| {
+                        entity_rooms.intersection(user.room_keys()).next().is_some()
+                    });
+            if in_room_scope {
+                continue;
+            }
 
-                    if should_be_in_scope {
-                        if currently_in_scope {
-                            continue;
-                        }
-                        let component_kinds =
-                            self.global_world_manager.component_kinds(entity).unwrap();
-                        // add entity & components to the connections local scope
-                        connection.base.host_world_manager.init_entity(
-                            &mut connection.base.local_world_manager,
-                            entity,
-                            component_kinds,
-                        );
+            #[cfg(feature = "tracing")]
+            trace_scope_change(&self.global_world_manager, &user_key, &entity, false);
 
-                        // if entity is delegated, send message to connection
-                        if !self.global_world_manager.entity_is_delegated(entity) {
-                            continue;
-                        }
-                        let event_message = EntityEventMessage::new_enable_delegation(
-                            &self.global_world_manager,
-                            entity,
-                        );
-                        let mut converter = EntityConverterMut::new(
-                            &self.global_world_manager,
-                            &mut connection.base.local_world_manager,
-                        );
-                        let channel_kind = ChannelKind::of::<SystemChannel>();
-                        let message =
-                            MessageContainer::from_write(Box::new(event_message), &mut converter);
-                        connection.base.message_manager.send_message(
-                            &self.protocol.message_kinds,
-                            &mut converter,
-                            &channel_kind,
-                            message,
-                        );
-                    } else if currently_in_scope {
-                        // remove entity from the connections local scope
-                        connection.base.host_world_manager.despawn_entity(entity);
-                    }
+            connection.base.host_world_manager.despawn_entity(&entity);
+        }
+
+        // Entities included in a User's scope directly enter it, whatever
+        // Rooms they are in
+        for (user_key, entities) in self.entity_scope_map.direct_entities_of_user() {
+            let Some(user) = self.users.get(user_key) else {
+                continue;
+            };
+            if !user.has_address() {
+                continue;
+            }
+            let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                continue;
+            };
+            let mut entering_entities = Vec::new();
+            for entity in entities {
+                if !world.has_entity(entity) {
+                    continue;
+                }
+                if self
+                    .global_world_manager
+                    .entity_is_public_and_owned_by_user(user_key, entity)
+                {
+                    continue;
                 }
+                if connection.base.host_world_manager.host_has_entity(entity) {
+                    continue;
+                }
+                if !Self::scope_dependencies_met(&self.scope_dependencies, connection, entity) {
+                    continue;
+                }
+                let component_kinds = self.global_world_manager.component_kinds(entity).unwrap();
+                entering_entities.push((*entity, component_kinds));
+            }
+            if entering_entities.is_empty() {
+                continue;
+            }
+            #[cfg(feature = "tracing")]
+            for (entity, _) in &entering_entities {
+                trace_scope_change(&self.global_world_manager, user_key, entity, true);
+            }
+
+            Self::init_entering_entities(
+                &self.global_world_manager,
+                &self.protocol,
+                connection,
+                user_key,
+                user.is_observer(),
+                entering_entities,
+            );
+        }
+
+        if self.server_config.despawn_scope_dependents {
+            self.despawn_unmet_scope_dependents();
+        }
+    }
+
+    // Whether every Entity the given Entity depends on has been spawned on
+    // the Client, so that it can never arrive before them
+    fn scope_dependencies_met(
+        scope_dependencies: &ScopeDependencies<E>,
+        connection: &Connection<E>,
+        entity: &E,
+    ) -> bool {
+        if scope_dependencies.is_orphan(entity) {
+            return false;
+        }
+        let Some(dependencies) = scope_dependencies.dependencies(entity) else {
+            return true;
+        };
+        dependencies.iter().all(|dependency| {
+            connection
+                .base
+                .host_world_manager
+                .entity_channel_is_open(dependency)
+        })
+    }
+
+    // Entities leave each User's scope once any Entity they depend on has
+    // left it, or has been despawned
+    fn despawn_unmet_scope_dependents(&mut self) {
+        if self.scope_dependencies.is_empty() {
+            return;
+        }
+        for connection in self.user_connections.values_mut() {
+            // each departure may take further dependents with it
+            loop {
+                let host_world_manager = &connection.base.host_world_manager;
+                let leaving: Vec<E> = self
+                    .scope_dependencies
+                    .dependents()
+                    .chain(self.scope_dependencies.orphans())
+                    .filter(|entity| host_world_manager.host_has_entity(entity))
+                    .filter(|entity| {
+                        self.scope_dependencies.is_orphan(entity)
+                            || self.scope_dependencies.dependencies(entity).is_some_and(
+                                |dependencies| {
+                                    !dependencies.iter().all(|dependency| {
+                                        host_world_manager.host_has_entity(dependency)
+                                    })
+                                },
+                            )
+                    })
+                    .copied()
+                    .collect();
+                if leaving.is_empty() {
+                    break;
+                }
+                for entity in leaving {
+                    #[cfg(feature = "tracing")]
+                    trace_scope_change(
+                        &self.global_world_manager,
+                        &connection.user_key,
+                        &entity,
+                        false,
+                    );
+
+                    connection.base.host_world_manager.despawn_entity(&entity);
+                    // to come back once its dependencies do
+                    self.scope_cache.pair_dirty(&connection.user_key, &entity);
+                }
+            }
+        }
+    }
+
+    // Adds Entities & their Components to a connection's local scope, all at
+    // once, telling the Client about any which are Delegated, unless it only
+    // observes them
+    fn init_entering_entities(
+        global_world_manager: &GlobalWorldManager<E>,
+        protocol: &Protocol,
+        connection: &mut Connection<E>,
+        user_key: &UserKey,
+        observer: bool,
+        entering_entities: Vec<(E, Vec<ComponentKind>)>,
+    ) {
+        let delegated_entities: Vec<E> = entering_entities
+            .iter()
+            .map(|(entity, _)| *entity)
+            .filter(|entity| !observer && global_world_manager.entity_is_delegated(entity))
+            .collect();
+        connection
+            .base
+            .host_world_manager
+            .init_entities_batch(&mut connection.base.local_world_manager, entering_entities);
+
+        // if entity is delegated, send message to connection
+        let owned_entities = global_world_manager.user_all_owned_entities(user_key);
+        for entity in delegated_entities {
+            let mut event_messages = vec![EntityEventMessage::new_enable_delegation(
+                global_world_manager,
+                &entity,
+            )];
+            // a User granted authority before the Entity came into scope
+            // is told in the same packet, so it needn't ask
+            if owned_entities.is_some_and(|entities| entities.contains(&entity)) {
+                event_messages.push(EntityEventMessage::new_update_auth_status(
+                    global_world_manager,
+                    &entity,
+                    EntityAuthStatus::Granted,
+                ));
+            }
+            for event_message in event_messages {
+                let mut converter = EntityConverterMut::new(
+                    global_world_manager,
+                    &mut connection.base.local_world_manager,
+                );
+                let channel_kind = ChannelKind::of::<SystemChannel>();
+                let message = MessageContainer::from_write(Box::new(event_message), &mut converter);
+                connection.base.message_manager.send_message(
+                    &protocol.message_kinds,
+                    &mut converter,
+                    &channel_kind,
+                    message,
+                );
             }
         }
     }
@@ -2447,4 +4877,442 @@ impl<E: Copy + Eq + Hash + Send + Sync> EntityAndGlobalEntityConverter<E> for Se
     fn entity_to_global_entity(&self, entity: &E) -> Result<GlobalEntity, EntityDoesNotExistError> {
         self.global_world_manager.entity_to_global_entity(entity)
     }
+
+    fn global_entities_to_entities(&self, global_entities: &[GlobalEntity]) -> Vec<Option<E>> {
+        self.global_world_manager
+            .global_entities_to_entities(global_entities)
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn trace_scope_change<E: Copy + Eq + Hash + Send + Sync>(
+    global_world_manager: &GlobalWorldManager<E>,
+    user_key: &UserKey,
+    entity: &E,
+    in_scope: bool,
+) {
+    let global_entity = global_world_manager.entity_to_global_entity(entity).ok();
+    tracing::debug!(?user_key, ?global_entity, in_scope, "scope change");
+}
+
+#[cfg(feature = "tracing")]
+fn trace_auth_transition<E: Copy + Eq + Hash + Send + Sync>(
+    global_world_manager: &GlobalWorldManager<E>,
+    entity: &E,
+    user_key: Option<&UserKey>,
+    status: EntityAuthStatus,
+) {
+    let global_entity = global_world_manager.entity_to_global_entity(entity).ok();
+    tracing::info!(?global_entity, ?user_key, ?status, "auth transition");
+}
+
+#[cfg(test)]
+mod entity_error_tests {
+    use naia_demo_world::{Entity, World, WorldMutType};
+    use naia_shared::{BigMapKey, ComponentKind, EntityResponseEvent, Protocol};
+
+    use crate::{ErrorEvent, NaiaServerError, RoomKey, Server, ServerConfig, UserKey};
+
+    fn unknown_entity_error_count(server: &mut Server<Entity>) -> usize {
+        server
+            .incoming_events
+            .read::<ErrorEvent>()
+            .filter(|error| matches!(error, NaiaServerError::EntityDoesNotExist))
+            .count()
+    }
+
+    #[test]
+    fn despawn_of_unknown_entity_emits_error() {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), Protocol::builder());
+        let mut world = World::default();
+        let entity = world.proxy_mut().spawn_entity();
+
+        server.process_response_events(
+            &mut world.proxy_mut(),
+            &UserKey::from_u64(0),
+            vec![EntityResponseEvent::DespawnEntity(entity)],
+        );
+
+        assert_eq!(unknown_entity_error_count(&mut server), 1);
+    }
+
+    #[test]
+    fn events_for_unknown_entity_emit_errors() {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), Protocol::builder());
+        let mut world = World::default();
+        let entity = world.proxy_mut().spawn_entity();
+        let component_kind = ComponentKind::from(std::any::TypeId::of::<u8>());
+
+        server.process_response_events(
+            &mut world.proxy_mut(),
+            &UserKey::from_u64(0),
+            vec![
+                EntityResponseEvent::InsertComponent(entity, component_kind),
+                EntityResponseEvent::RemoveComponent(entity, component_kind),
+                EntityResponseEvent::PublishEntity(entity),
+                EntityResponseEvent::EntityReleaseAuthority(entity),
+            ],
+        );
+
+        assert_eq!(unknown_entity_error_count(&mut server), 4);
+    }
+
+    #[test]
+    fn try_variants_return_errors() {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), Protocol::builder());
+        let mut world = World::default();
+        let entity = world.proxy_mut().spawn_entity();
+
+        assert!(matches!(
+            server.try_despawn_entity_worldless(&entity),
+            Err(NaiaServerError::EntityDoesNotExist)
+        ));
+        assert!(matches!(
+            server.try_entity_authority_status(&entity),
+            Err(NaiaServerError::EntityDoesNotExist)
+        ));
+        assert!(matches!(
+            server.try_room_add_entity(&RoomKey::from_u64(0), &entity),
+            Err(NaiaServerError::RoomDoesNotExist)
+        ));
+        assert!(server.try_entity_mut(world.proxy_mut(), &entity).is_ok());
+
+        world.proxy_mut().despawn_entity(&entity);
+        assert!(server.try_entity_mut(world.proxy_mut(), &entity).is_err());
+    }
+}
+
+// the test Components below only implement `ReplicatedComponent` without Bevy
+#[cfg(all(test, not(feature = "bevy_support")))]
+mod spawn_batch_tests {
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use naia_demo_world::{Entity, World};
+    use naia_shared::{
+        BaseConnection, BigMapKey, BitReader, ConnectionConfig, EntityAction, GameInstant,
+        HostType, Instant, Property, Protocol, Replicate, Serde, StandardHeader, Tick,
+    };
+
+    use crate::{
+        connection::{connection::Connection, io::Io, ping_config::PingConfig},
+        transport::{PacketReceiver, PacketSender, RecvError, SendError},
+        Server, ServerConfig, UserKey,
+    };
+
+    // Packet count for the same 1,000 Entities, measured with each spawn
+    // written as its own SpawnEntity action
+    const UNBATCHED_PACKET_COUNT: usize = 26;
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<u8>,
+        pub y: Property<u8>,
+    }
+
+    #[derive(Replicate)]
+    pub struct Health {
+        pub value: Property<u8>,
+    }
+
+    #[derive(Clone)]
+    struct CapturingSender {
+        packets: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl PacketSender for CapturingSender {
+        fn send(&self, _address: &SocketAddr, payload: &[u8]) -> Result<(), SendError> {
+            self.packets.lock().unwrap().push(payload.to_vec());
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct NullReceiver;
+
+    impl PacketReceiver for NullReceiver {
+        fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, RecvError> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn level_load_is_batched_into_few_packets() {
+        let protocol = Protocol::builder()
+            .add_component::<Position>()
+            .add_component::<Health>()
+            .build();
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol);
+        let mut world = World::default();
+
+        let mut entities = Vec::new();
+        for i in 0..1000_u32 {
+            let entity = server
+                .spawn_entity(world.proxy_mut())
+                .insert_component(Position::new_complete(i as u8, 7))
+                .insert_component(Health::new_complete(100))
+                .id();
+            let component_kinds = server
+                .global_world_manager
+                .component_kinds(&entity)
+                .unwrap();
+            entities.push((entity, component_kinds));
+        }
+
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let mut connection = Connection::new(
+            &ConnectionConfig::default(),
+            &PingConfig::default(),
+            &address,
+            &UserKey::from_u64(0),
+            &server.protocol.channel_kinds,
+            &server.global_world_manager,
+            &0,
+        );
+        connection
+            .base
+            .host_world_manager
+            .init_entities_batch(&mut connection.base.local_world_manager, entities);
+
+        let packets = Arc::new(Mutex::new(Vec::new()));
+        let mut io = Io::new(&None, &None, false, false);
+        io.load(
+            Box::new(CapturingSender {
+                packets: packets.clone(),
+            }),
+            Box::new(NullReceiver),
+        );
+        connection.send_packets(
+            &server.protocol,
+            &Instant::now(),
+            &mut io,
+            &world.proxy(),
+            &server.global_world_manager,
+            &server.time_manager,
+        );
+
+        let packets = packets.lock().unwrap();
+        // 9 packets at the time of writing, the rest of the saving is bounded
+        // by the Components' own fields
+        assert!(
+            packets.len() * 5 <= UNBATCHED_PACKET_COUNT * 2,
+            "expected at least a 2.5x reduction from {} packets, but sent {}",
+            UNBATCHED_PACKET_COUNT,
+            packets.len()
+        );
+
+        // the remote host reads back every spawn, with both of its Components
+        let mut remote = BaseConnection::<Entity>::new(
+            &None,
+            HostType::Client,
+            0,
+            &ConnectionConfig::default(),
+            &server.protocol.channel_kinds,
+            &server.global_world_manager,
+        );
+        for packet in packets.iter() {
+            let mut reader = BitReader::new(packet);
+            StandardHeader::de(&mut reader).unwrap();
+            let tick = Tick::de(&mut reader).unwrap();
+            GameInstant::de(&mut reader).unwrap();
+            remote
+                .read_packet(
+                    &server.protocol,
+                    &tick,
+                    &server.global_world_manager,
+                    true,
+                    &mut reader,
+                )
+                .unwrap();
+        }
+        let events = remote.remote_world_reader.take_incoming_events();
+        assert_eq!(events.incoming_actions.len(), 1000);
+        assert_eq!(events.incoming_components.len(), 2000);
+        for action in &events.incoming_actions {
+            let EntityAction::SpawnEntity(_, component_kinds) = action else {
+                panic!("expected only spawns");
+            };
+            assert_eq!(component_kinds.len(), 2);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_tests {
+    use std::{
+        io::Write,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use naia_demo_world::{Entity, World};
+    use naia_shared::Protocol;
+
+    use crate::{
+        transport::{PacketReceiver, PacketSender, RecvError, SendError},
+        Server, ServerConfig,
+    };
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Captured {
+        fn output(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct NullSender;
+
+    impl PacketSender for NullSender {
+        fn send(&self, _address: &SocketAddr, _payload: &[u8]) -> Result<(), SendError> {
+            Ok(())
+        }
+    }
+
+    // receives each of the given payloads once, from the same address
+    #[derive(Clone)]
+    struct QueuedReceiver {
+        payloads: Vec<Vec<u8>>,
+        buffer: Vec<u8>,
+    }
+
+    impl PacketReceiver for QueuedReceiver {
+        fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, RecvError> {
+            if self.payloads.is_empty() {
+                return Ok(None);
+            }
+            self.buffer = self.payloads.remove(0);
+            Ok(Some(("127.0.0.1:14191".parse().unwrap(), &self.buffer)))
+        }
+    }
+
+    #[test]
+    fn malformed_packet_emits_event() {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), Protocol::builder());
+        let mut world = World::default();
+        server.io.load(
+            Box::new(NullSender),
+            Box::new(QueuedReceiver {
+                // too short to hold a packet header
+                payloads: vec![Vec::new()],
+                buffer: Vec::new(),
+            }),
+        );
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            server.receive(world.proxy_mut());
+        });
+
+        let output = captured.output();
+        let event = output
+            .lines()
+            .find(|line| line.contains("malformed packet"))
+            .expect("expected a malformed packet event");
+        assert!(event.contains("WARN"));
+        assert!(event.contains("read_packet{address=127.0.0.1:14191}"));
+        assert!(event.contains("packet_type=\"unknown\""));
+    }
+}
+
+#[cfg(all(test, not(feature = "bevy_support")))]
+mod entity_export_tests {
+    use naia_demo_world::{Entity, World, WorldRefType};
+    use naia_shared::{Property, Protocol, Replicate};
+
+    use crate::{EntityExport, ReplicationConfig, Server, ServerConfig};
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<i16>,
+        pub y: Property<i16>,
+    }
+
+    #[derive(Replicate)]
+    pub struct Name {
+        pub value: Property<String>,
+    }
+
+    fn server(with_name: bool) -> Server<Entity> {
+        let mut protocol = Protocol::builder();
+        protocol.add_component::<Position>();
+        if with_name {
+            protocol.add_component::<Name>();
+        }
+        Server::new(ServerConfig::default(), protocol.build())
+    }
+
+    #[test]
+    fn entity_round_trips_between_servers() {
+        let mut from_server = server(true);
+        let mut from_world = World::default();
+        let entity = from_server
+            .spawn_entity(from_world.proxy_mut())
+            .insert_component(Position::new_complete(-40, 1200))
+            .insert_component(Name::new_complete("crate".to_string()))
+            .id();
+
+        let export = from_server
+            .export_entity(from_world.proxy(), &entity)
+            .unwrap();
+        // exports survive being sent between Servers as plain bytes
+        let export = EntityExport::from_bytes(export.into_bytes());
+
+        let mut to_server = server(true);
+        let mut to_world = World::default();
+        let imported = to_server
+            .import_entity(&mut to_world.proxy_mut(), export)
+            .unwrap();
+
+        let world = to_world.proxy();
+        let position = world.component::<Position>(&imported).unwrap();
+        assert_eq!((*position.x, *position.y), (-40, 1200));
+        assert_eq!(*world.component::<Name>(&imported).unwrap().value, "crate");
+        assert_eq!(
+            to_server.entity_replication_config(&imported),
+            Some(ReplicationConfig::Public)
+        );
+    }
+
+    #[test]
+    fn unknown_component_kind_is_reported() {
+        let mut from_server = server(true);
+        let mut from_world = World::default();
+        let entity = from_server
+            .spawn_entity(from_world.proxy_mut())
+            .insert_component(Position::new_complete(1, 2))
+            .insert_component(Name::new_complete("crate".to_string()))
+            .id();
+        let export = from_server
+            .export_entity(from_world.proxy(), &entity)
+            .unwrap();
+
+        let mut to_server = server(false);
+        let mut to_world = World::default();
+        let Err(error) = to_server.import_entity(&mut to_world.proxy_mut(), export) else {
+            panic!("expected import to fail");
+        };
+
+        assert!(error.to_string().contains("Name"));
+        assert!(to_world.proxy().entities().is_empty());
+    }
 }
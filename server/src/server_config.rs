@@ -1,4 +1,4 @@
-use std::default::Default;
+use std::{default::Default, time::Duration};
 
 use naia_shared::ConnectionConfig;
 
@@ -14,6 +14,31 @@ pub struct ServerConfig {
     pub require_auth: bool,
     /// Configuration used to monitor the ping & jitter on the network
     pub ping: PingConfig,
+    /// The ids which `GlobalEntity`s are allocated from
+    pub entity_id_range: EntityIdRange,
+    /// The most Users which can be connected at once. Clients which finish
+    /// their handshake beyond this are rejected with
+    /// `HandshakeError::ServerFull`. None means no limit.
+    pub max_users: Option<usize>,
+    /// How many Ticks in a row a connected User may send no tick-buffered
+    /// Messages for before `InputGapEvent`s are fired for them, one for each
+    /// further Tick processed with `Server::receive_tick_buffer_messages()`.
+    /// None means no `InputGapEvent`s are fired.
+    pub input_gap_threshold: Option<u16>,
+    /// Configures the cookies which the UDP transport's handshake uses to
+    /// check that a Client really is at the address it sends from
+    pub handshake_cookie: HandshakeCookieConfig,
+    /// How long the User holding authority over a delegated Entity may send
+    /// no update or auth message for it before the Server takes authority
+    /// back, emitting an `EntityAuthResetEvent`. Can be overridden per Entity
+    /// with `EntityMut::set_auth_idle_timeout()`. None means authority is
+    /// never reclaimed this way.
+    pub auth_idle_timeout: Option<Duration>,
+    /// Whether an Entity leaves a User's scope whenever one of the Entities
+    /// it depends on does, see `EntityMut::scope_depends_on()`, & stays out
+    /// of scope once one of them is despawned. Otherwise dependencies only
+    /// hold an Entity back from entering scope, & are dropped on despawn.
+    pub despawn_scope_dependents: bool,
 }
 
 impl Default for ServerConfig {
@@ -22,6 +47,55 @@ impl Default for ServerConfig {
             connection: ConnectionConfig::default(),
             require_auth: true,
             ping: PingConfig::default(),
+            entity_id_range: EntityIdRange::default(),
+            max_users: None,
+            input_gap_threshold: None,
+            handshake_cookie: HandshakeCookieConfig::default(),
+            auth_idle_timeout: None,
+            despawn_scope_dependents: true,
+        }
+    }
+}
+
+/// Allocates the ids `start`, `start + stride`, `start + 2 * stride`, ...
+/// Giving each node of a multi-node deployment the same stride and a different
+/// start keeps ids from colliding when Entities move between nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityIdRange {
+    pub start: u64,
+    pub stride: u64,
+}
+
+impl Default for EntityIdRange {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            stride: 1,
+        }
+    }
+}
+
+/// Before any state is kept for a Client, the UDP transport's handshake
+/// replies with a cookie signed for the Client's address, which the Client
+/// must echo back. Spoofed addresses never receive the cookie, so can't get
+/// any further. Other transports have their own session handshake, so only
+/// use the `lifetime`
+#[derive(Clone)]
+pub struct HandshakeCookieConfig {
+    /// The secret cookies are signed with. Servers behind the same address
+    /// should share one, so a cookie from one is accepted by another. If
+    /// None, a random secret is generated on startup
+    pub secret: Option<Vec<u8>>,
+    /// How long a Client has to echo a cookie back after it is issued, & to
+    /// collect a rejection made with `Server::reject_connection_with()`
+    pub lifetime: Duration,
+}
+
+impl Default for HandshakeCookieConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            lifetime: Duration::from_secs(10),
         }
     }
 }
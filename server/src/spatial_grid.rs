@@ -0,0 +1,177 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{RoomKey, Server, UserKey};
+
+/// A cell of a SpatialGrid, as (column, row)
+pub type GridCell = (i32, i32);
+
+/// Scopes Entities to Users by position, using one Room per cell of a 2D
+/// grid. Each Entity is in the Room of the cell it stands in, and each User
+/// is in the Rooms of every cell within their view radius, so an Entity is
+/// only considered for the Users which can see its cell. As with any Room,
+/// the Server's scope checks then decide whether it's actually in scope.
+///
+/// Room membership is updated through the Server's Room API, so changes are
+/// batched & applied on the next `send_all_updates()`. Moves add to the new
+/// Rooms before leaving the old ones, so an Entity crossing into a cell its
+/// User can also see stays in scope throughout.
+///
+/// ```no_run
+/// # use naia_server::{Server, SpatialGrid, UserKey};
+/// # fn on_move<E: Copy + Eq + std::hash::Hash + Send + Sync>(
+/// #     server: &mut Server<E>,
+/// #     grid: &mut SpatialGrid<E>,
+/// #     user_key: &UserKey,
+/// #     avatar: &E,
+/// #     (x, y): (f32, f32),
+/// # ) {
+/// // the User's avatar moves, and their view of the world follows it
+/// grid.update_entity_position(server, avatar, x, y);
+/// grid.update_user_position(server, user_key, x, y, 2);
+/// # }
+/// ```
+pub struct SpatialGrid<E: Copy + Eq + Hash + Send + Sync> {
+    cell_size: f32,
+    rooms: HashMap<GridCell, RoomKey>,
+    entity_cells: HashMap<E, GridCell>,
+    user_cells: HashMap<UserKey, HashSet<GridCell>>,
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync> SpatialGrid<E> {
+    /// Creates a grid of square cells of the given size. Rooms for cells are
+    /// only made once something enters them
+    pub fn new(cell_size: f32) -> Self {
+        if cell_size <= 0.0 {
+            panic!("SpatialGrid cell size must be positive");
+        }
+        Self {
+            cell_size,
+            rooms: HashMap::new(),
+            entity_cells: HashMap::new(),
+            user_cells: HashMap::new(),
+        }
+    }
+
+    /// Returns the cell containing the given position
+    pub fn cell_at(&self, x: f32, y: f32) -> GridCell {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Returns the key of the Room for the given cell, if one was made
+    pub fn cell_room(&self, cell: &GridCell) -> Option<RoomKey> {
+        self.rooms.get(cell).copied()
+    }
+
+    /// Returns the cell the given Entity was last placed in
+    pub fn entity_cell(&self, entity: &E) -> Option<GridCell> {
+        self.entity_cells.get(entity).copied()
+    }
+
+    /// Places the Entity in the Room of the cell at the given position,
+    /// moving it out of its previous cell's Room if it changed
+    pub fn update_entity_position(&mut self, server: &mut Server<E>, entity: &E, x: f32, y: f32) {
+        let cell = self.cell_at(x, y);
+        let old_cell = self.entity_cells.get(entity).copied();
+        if old_cell == Some(cell) {
+            return;
+        }
+
+        let room_key = self.room_for_cell(server, &cell);
+        server.room_mut(&room_key).add_entity(entity);
+        self.entity_cells.insert(*entity, cell);
+
+        if let Some(old_cell) = old_cell {
+            if let Some(old_room_key) = self.existing_room(server, &old_cell) {
+                if server.room(&old_room_key).has_entity(entity) {
+                    server.room_mut(&old_room_key).remove_entity(entity);
+                }
+            }
+        }
+    }
+
+    /// Removes the Entity from the grid, i.e. before despawning it
+    pub fn remove_entity(&mut self, server: &mut Server<E>, entity: &E) {
+        let Some(cell) = self.entity_cells.remove(entity) else {
+            return;
+        };
+        if let Some(room_key) = self.existing_room(server, &cell) {
+            if server.room(&room_key).has_entity(entity) {
+                server.room_mut(&room_key).remove_entity(entity);
+            }
+        }
+    }
+
+    /// Places the User in the Rooms of every cell within `view_radius_cells`
+    /// cells of the one at the given position, leaving the Rooms of cells
+    /// no longer in view
+    pub fn update_user_position(
+        &mut self,
+        server: &mut Server<E>,
+        user_key: &UserKey,
+        x: f32,
+        y: f32,
+        view_radius_cells: u32,
+    ) {
+        let (column, row) = self.cell_at(x, y);
+        let radius = view_radius_cells as i32;
+        let mut cells = HashSet::new();
+        for column_offset in -radius..=radius {
+            for row_offset in -radius..=radius {
+                cells.insert((column + column_offset, row + row_offset));
+            }
+        }
+
+        let old_cells = self.user_cells.remove(user_key).unwrap_or_default();
+        if old_cells == cells {
+            self.user_cells.insert(*user_key, cells);
+            return;
+        }
+
+        for cell in cells.difference(&old_cells) {
+            let room_key = self.room_for_cell(server, cell);
+            server.room_mut(&room_key).add_user(user_key);
+        }
+        for cell in old_cells.difference(&cells) {
+            if let Some(room_key) = self.existing_room(server, cell) {
+                server.room_mut(&room_key).remove_user(user_key);
+            }
+        }
+        self.user_cells.insert(*user_key, cells);
+    }
+
+    /// Removes the User from the grid, i.e. once they disconnect
+    pub fn remove_user(&mut self, server: &mut Server<E>, user_key: &UserKey) {
+        let Some(cells) = self.user_cells.remove(user_key) else {
+            return;
+        };
+        for cell in &cells {
+            if let Some(room_key) = self.existing_room(server, cell) {
+                server.room_mut(&room_key).remove_user(user_key);
+            }
+        }
+    }
+
+    // the cell's Room, made if it doesn't exist yet
+    fn room_for_cell(&mut self, server: &mut Server<E>, cell: &GridCell) -> RoomKey {
+        if let Some(room_key) = self.existing_room(server, cell) {
+            return room_key;
+        }
+        let room_key = server.make_room().key();
+        self.rooms.insert(*cell, room_key);
+        room_key
+    }
+
+    // the cell's Room, unless it was never made or has since been destroyed
+    fn existing_room(&self, server: &Server<E>, cell: &GridCell) -> Option<RoomKey> {
+        self.rooms
+            .get(cell)
+            .copied()
+            .filter(|room_key| server.room_exists(room_key))
+    }
+}
@@ -40,11 +40,12 @@ impl TimeManager {
         }
     }
 
-    // pub(crate) fn duration_until_next_tick(&self) -> Duration {
-    //     let mut new_instant = self.last_tick_instant.clone();
-    //     new_instant.add_millis(self.tick_interval_millis as u32);
-    //     return new_instant.until();
-    // }
+    /// How long until the next tick is due, or zero if it's overdue
+    pub(crate) fn duration_until_next_tick(&self, now: &Instant) -> Duration {
+        let mut next_tick_instant = self.last_tick_instant.clone();
+        next_tick_instant.add_millis(self.tick_interval_millis as u32);
+        next_tick_instant.until(now)
+    }
 
     /// Whether or not we should emit a tick event
     pub fn recv_server_tick(&mut self, now: &Instant) -> bool {
@@ -14,6 +14,8 @@ pub use inner::{
     AuthReceiver, AuthSender, PacketReceiver, PacketSender, RecvError, SendError, Socket,
 };
 
+pub use crate::user::UserAuthAddr;
+
 mod inner {
 
     use std::net::SocketAddr;
@@ -76,13 +78,17 @@ mod inner {
             address: &UserAuthAddr,
             identity_token: &IdentityToken,
         ) -> Result<(), SendError>;
-        ///
-        fn reject(&self, address: &UserAuthAddr) -> Result<(), SendError>;
+        /// Rejects the auth request, answering with the given HTTP status code
+        fn reject(&self, address: &UserAuthAddr, status: u16) -> Result<(), SendError>;
     }
 
     pub trait AuthReceiver: AuthReceiverClone + Send + Sync {
-        ///
-        fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[u8])>, RecvError>;
+        /// Receives the auth bytes of a Client, along with any headers its
+        /// auth request carried
+        #[allow(clippy::type_complexity)]
+        fn receive(
+            &mut self,
+        ) -> Result<Option<(UserAuthAddr, &[(String, String)], &[u8])>, RecvError>;
     }
 
     /// Used to clone Box<dyn AuthReceiver>
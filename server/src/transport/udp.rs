@@ -159,6 +159,7 @@ pub(crate) struct AuthIo {
     public_udp_addr: SocketAddr,
     socket: TcpListener,
     buffer: [u8; 1472],
+    headers: Vec<(String, String)>,
     outgoing_streams: HashMap<SocketAddr, TcpStream>,
 }
 
@@ -170,11 +171,13 @@ impl AuthIo {
             public_udp_addr,
             socket,
             buffer: [0; 1472],
+            headers: Vec::new(),
             outgoing_streams: HashMap::new(),
         }
     }
 
-    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[u8])>, RecvError> {
+    #[allow(clippy::type_complexity)]
+    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[(String, String)], &[u8])>, RecvError> {
         match self.socket.accept() {
             Ok((mut stream, addr)) => {
                 let recv_len = stream.read(&mut self.buffer).unwrap();
@@ -195,8 +198,18 @@ impl AuthIo {
                         .unwrap();
                     let auth_bytes = base64::decode(auth_bytes).unwrap();
                     self.buffer[0..auth_bytes.len()].copy_from_slice(&auth_bytes);
+                    self.headers = request
+                        .headers()
+                        .iter()
+                        .filter(|(name, _)| *name != http::header::AUTHORIZATION)
+                        .filter_map(|(name, value)| {
+                            let value = value.to_str().ok()?;
+                            Some((name.to_string(), value.to_string()))
+                        })
+                        .collect();
                     return Ok(Some((
                         UserAuthAddr::new(addr),
+                        &self.headers,
                         &self.buffer[..auth_bytes.len()],
                     )));
                 } else {
@@ -238,10 +251,10 @@ impl AuthIo {
     }
 
     /// Sends a rejection packet from the Client Socket
-    fn reject(&mut self, address: &UserAuthAddr) -> Result<(), SendError> {
+    fn reject(&mut self, address: &UserAuthAddr, status: u16) -> Result<(), SendError> {
         if let Some(mut stream) = self.outgoing_streams.remove(&address.addr()) {
             let response = http::Response::builder()
-                .status(401)
+                .status(status)
                 .body(Vec::new())
                 .unwrap();
             let response_bytes = transport_udp::response_to_bytes(response);
@@ -278,8 +291,8 @@ impl TransportAuthSender for AuthSender {
     }
 
     /// Sends a rejection packet from the Client Socket
-    fn reject(&self, address: &UserAuthAddr) -> Result<(), SendError> {
-        self.auth_io.lock().unwrap().reject(address)
+    fn reject(&self, address: &UserAuthAddr, status: u16) -> Result<(), SendError> {
+        self.auth_io.lock().unwrap().reject(address, status)
     }
 }
 
@@ -287,6 +300,7 @@ impl TransportAuthSender for AuthSender {
 #[derive(Clone)]
 pub(crate) struct AuthReceiver {
     auth_io: Arc<Mutex<AuthIo>>,
+    headers: Vec<(String, String)>,
     buffer: Box<[u8]>,
 }
 
@@ -294,19 +308,21 @@ impl AuthReceiver {
     pub fn new(auth_io: Arc<Mutex<AuthIo>>) -> Self {
         Self {
             auth_io,
+            headers: Vec::new(),
             buffer: Box::new([0; 1472]),
         }
     }
 }
 
 impl TransportAuthReceiver for AuthReceiver {
-    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[u8])>, RecvError> {
+    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[(String, String)], &[u8])>, RecvError> {
         let mut guard = self.auth_io.lock().unwrap();
         match guard.receive() {
             Ok(option) => match option {
-                Some((addr, buffer)) => {
+                Some((addr, headers, buffer)) => {
+                    self.headers = headers.to_vec();
                     self.buffer = buffer.into();
-                    Ok(Some((addr, &self.buffer)))
+                    Ok(Some((addr, &self.headers, &self.buffer)))
                 }
                 None => Ok(None),
             },
@@ -55,18 +55,20 @@ impl TransportAuthSender for Box<dyn AuthSender> {
             .map_err(|_| SendError)
     }
     ///
-    fn reject(&self, address: &UserAuthAddr) -> Result<(), SendError> {
-        self.as_ref().reject(&address.addr()).map_err(|_| SendError)
+    fn reject(&self, address: &UserAuthAddr, status: u16) -> Result<(), SendError> {
+        self.as_ref()
+            .reject_with_status(&address.addr(), status)
+            .map_err(|_| SendError)
     }
 }
 
 impl TransportAuthReceiver for Box<dyn AuthReceiver> {
     ///
-    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[u8])>, RecvError> {
+    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[(String, String)], &[u8])>, RecvError> {
         match self.as_mut().receive() {
             Ok(auth_opt) => match auth_opt {
-                Some((addr, payload)) => {
-                    return Ok(Some((UserAuthAddr::new(addr), payload)));
+                Some((addr, headers, payload)) => {
+                    return Ok(Some((UserAuthAddr::new(addr), headers, payload)));
                 }
                 None => {
                     return Ok(None);
@@ -2,6 +2,7 @@ use std::{
     collections::{hash_set::Iter, HashSet},
     hash::Hash,
     net::SocketAddr,
+    time::Duration,
 };
 
 use naia_shared::BigMapKey;
@@ -43,16 +44,31 @@ impl UserAuthAddr {
 #[derive(Clone)]
 pub struct User {
     auth_addr: Option<UserAuthAddr>,
+    // the index of the Socket the auth request arrived on
+    auth_socket: usize,
+    auth_headers: Vec<(String, String)>,
     data_addr: Option<SocketAddr>,
     rooms_cache: HashSet<RoomKey>,
+    timeout_duration: Option<Duration>,
+    ping_interval: Option<Duration>,
+    observer: bool,
 }
 
 impl User {
-    pub fn new(auth_addr: UserAuthAddr) -> User {
+    pub fn new(
+        auth_addr: UserAuthAddr,
+        auth_socket: usize,
+        auth_headers: Vec<(String, String)>,
+    ) -> User {
         Self {
             auth_addr: Some(auth_addr),
+            auth_socket,
+            auth_headers,
             data_addr: None,
             rooms_cache: HashSet::new(),
+            timeout_duration: None,
+            ping_interval: None,
+            observer: false,
         }
     }
 
@@ -72,6 +88,14 @@ impl User {
         self.auth_addr.take().unwrap()
     }
 
+    pub(crate) fn auth_socket(&self) -> usize {
+        self.auth_socket
+    }
+
+    pub(crate) fn auth_headers(&self) -> &[(String, String)] {
+        &self.auth_headers
+    }
+
     pub(crate) fn set_address(&mut self, addr: &SocketAddr) {
         self.data_addr = Some(*addr);
     }
@@ -91,6 +115,30 @@ impl User {
     pub(crate) fn room_count(&self) -> usize {
         self.rooms_cache.len()
     }
+
+    pub(crate) fn timeout_duration(&self) -> Option<Duration> {
+        self.timeout_duration
+    }
+
+    pub(crate) fn set_timeout_duration(&mut self, duration: Duration) {
+        self.timeout_duration = Some(duration);
+    }
+
+    pub(crate) fn ping_interval(&self) -> Option<Duration> {
+        self.ping_interval
+    }
+
+    pub(crate) fn set_ping_interval(&mut self, interval: Duration) {
+        self.ping_interval = Some(interval);
+    }
+
+    pub(crate) fn is_observer(&self) -> bool {
+        self.observer
+    }
+
+    pub(crate) fn set_observer(&mut self, observer: bool) {
+        self.observer = observer;
+    }
 }
 
 // UserRef
@@ -121,6 +169,19 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserRef<'s, E> {
     pub fn room_keys(&self) -> impl Iterator<Item = &RoomKey> {
         self.server.user_room_keys(&self.key).unwrap()
     }
+
+    /// Returns the headers the User's auth request carried, in the order they
+    /// were received. The `Authorization` header, which carries the auth
+    /// Message, is not included
+    pub fn auth_headers(&self) -> &[(String, String)] {
+        self.server.user_auth_headers(&self.key).unwrap()
+    }
+
+    /// Returns whether the User only observes the world, see
+    /// [`UserMut::set_observer`]
+    pub fn is_observer(&self) -> bool {
+        self.server.user_is_observer(&self.key)
+    }
 }
 
 // UserMut
@@ -146,6 +207,37 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserMut<'s, E> {
         self.server.user_queue_disconnect(&self.key);
     }
 
+    /// Sets how long the User's connection may go without hearing from the
+    /// Client before it is dropped, in place of
+    /// `ConnectionConfig::disconnection_timeout_duration`
+    pub fn set_timeout_duration(&mut self, duration: Duration) -> &mut Self {
+        self.server.user_set_timeout_duration(&self.key, duration);
+
+        self
+    }
+
+    /// Sets the interval between pings sent to the User, in place of
+    /// `PingConfig::ping_interval`
+    pub fn set_ping_interval(&mut self, interval: Duration) -> &mut Self {
+        self.server.user_set_ping_interval(&self.key, interval);
+
+        self
+    }
+
+    /// Makes the User a read-only observer, i.e. a spectator. Entities in its
+    /// scope are still spawned & updated for it, but it is never told of their
+    /// delegation, can never be given authority over them, and any authority
+    /// requests it sends are ignored. Authority it holds is released.
+    ///
+    /// Best set when accepting the User's connection: Entities which came
+    /// into scope beforehand stay delegated on the Client, though its requests
+    /// for them go unanswered
+    pub fn set_observer(&mut self, observer: bool) -> &mut Self {
+        self.server.user_set_observer(&self.key, observer);
+
+        self
+    }
+
     // Rooms
 
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
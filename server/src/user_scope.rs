@@ -16,6 +16,11 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserScopeRef<'s, E> {
     pub fn has(&self, entity: &E) -> bool {
         self.server.user_scope_has_entity(&self.key, entity)
     }
+
+    /// Returns true if updates to the Entity are paused for the User
+    pub fn is_paused(&self, entity: &E) -> bool {
+        self.server.user_scope_entity_is_paused(&self.key, entity)
+    }
 }
 
 pub struct UserScopeMut<'s, E: Copy + Eq + Hash + Send + Sync> {
@@ -33,6 +38,11 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserScopeMut<'s, E> {
         self.server.user_scope_has_entity(&self.key, entity)
     }
 
+    /// Returns true if updates to the Entity are paused for the User
+    pub fn is_paused(&self, entity: &E) -> bool {
+        self.server.user_scope_entity_is_paused(&self.key, entity)
+    }
+
     /// Adds an Entity to the User's scope
     pub fn include(&mut self, entity: &E) -> &mut Self {
         self.server.user_scope_set_entity(&self.key, entity, true);
@@ -47,6 +57,46 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserScopeMut<'s, E> {
         self
     }
 
+    /// Adds an Entity to the User's scope directly, so that it replicates to
+    /// them whether or not they share a Room with it, i.e. for an Entity only
+    /// one User should see. Lasts until `exclude_entity()` or `clear()`
+    pub fn include_entity(&mut self, entity: &E) -> &mut Self {
+        self.server
+            .user_scope_set_entity_direct(&self.key, entity, true);
+
+        self
+    }
+
+    /// Undoes `include_entity()`. The Entity leaves the User's scope, unless
+    /// it is still in scope through a Room they share
+    pub fn exclude_entity(&mut self, entity: &E) -> &mut Self {
+        self.server
+            .user_scope_set_entity_direct(&self.key, entity, false);
+
+        self
+    }
+
+    /// Stops sending updates of the Entity's Components to the User, while
+    /// leaving it spawned on their Client with its last-known state.
+    /// Components inserted or removed while paused are still delivered.
+    /// Lasts until resumed, or until the Entity leaves the User's scope.
+    /// The Entity must already be in scope on the User's connection
+    pub fn pause_entity(&mut self, entity: &E) -> &mut Self {
+        self.server
+            .user_scope_set_entity_paused(&self.key, entity, true);
+
+        self
+    }
+
+    /// Resumes sending updates of the Entity's Components to the User. Every
+    /// change made while paused is sent together in the next update
+    pub fn resume_entity(&mut self, entity: &E) -> &mut Self {
+        self.server
+            .user_scope_set_entity_paused(&self.key, entity, false);
+
+        self
+    }
+
     /// Removes all Entities from the User's scope
     pub fn clear(&mut self) -> &mut Self {
         self.server.user_scope_remove_user(&self.key);
@@ -0,0 +1,141 @@
+use naia_shared::{
+    BitReader, EntityDoesNotExistError, FileBitWriter, GlobalEntity, HostEntity,
+    LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, OwnedLocalEntity,
+    RemoteEntity, Serde, SerdeErr, UnsignedInteger,
+};
+
+use crate::ReplicationConfig;
+
+/// A self-contained snapshot of an Entity's Components, replication config &
+/// authority state, created by `Server::export_entity()` and restored by
+/// `Server::import_entity()` on another Server sharing the same Protocol.
+/// How the bytes travel between Servers is up to the application.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntityExport {
+    bytes: Vec<u8>,
+}
+
+impl EntityExport {
+    /// Wraps bytes previously taken from `EntityExport::as_bytes()`
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+// Which host held Authority over a Delegated Entity when it was exported
+#[derive(Serde, Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum ExportedAuthority {
+    None,
+    Server,
+    Client,
+}
+
+#[derive(Serde, Clone, PartialEq)]
+pub(crate) struct ExportedComponent {
+    // see `ComponentKinds::kind_to_hash()`
+    pub kind_hash: u64,
+    // only used to report a Component kind the importing Protocol is missing
+    pub type_name: String,
+    // written with `Replicate::write_fields()`
+    pub fields: Vec<u8>,
+}
+
+pub(crate) struct EntityExportContents {
+    pub replication_config: ReplicationConfig,
+    pub authority: ExportedAuthority,
+    pub components: Vec<ExportedComponent>,
+}
+
+impl EntityExportContents {
+    pub fn to_export(&self) -> EntityExport {
+        let mut writer = FileBitWriter::new();
+
+        let replication_config: u8 = match self.replication_config {
+            ReplicationConfig::Private => 0,
+            ReplicationConfig::Public => 1,
+            ReplicationConfig::Delegated => 2,
+        };
+        UnsignedInteger::<2>::new(replication_config).ser(&mut writer);
+        self.authority.ser(&mut writer);
+        self.components.ser(&mut writer);
+
+        EntityExport::from_bytes(writer.to_vec())
+    }
+
+    pub fn from_export(export: &EntityExport) -> Result<Self, SerdeErr> {
+        let mut reader = BitReader::new(export.as_bytes());
+
+        let replication_config = match UnsignedInteger::<2>::de(&mut reader)?.get() {
+            0 => ReplicationConfig::Private,
+            1 => ReplicationConfig::Public,
+            2 => ReplicationConfig::Delegated,
+            _ => return Err(SerdeErr),
+        };
+        let authority = ExportedAuthority::de(&mut reader)?;
+        let components = Vec::<ExportedComponent>::de(&mut reader)?;
+
+        Ok(Self {
+            replication_config,
+            authority,
+            components,
+        })
+    }
+}
+
+// Entities are identified differently on every Server, so references to other
+// Entities (via EntityProperty) can't be exported, and are written as empty
+pub(crate) struct UnmappedEntityConverter;
+
+impl LocalEntityAndGlobalEntityConverter for UnmappedEntityConverter {
+    fn global_entity_to_host_entity(
+        &self,
+        _global_entity: &GlobalEntity,
+    ) -> Result<HostEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_remote_entity(
+        &self,
+        _global_entity: &GlobalEntity,
+    ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_owned_entity(
+        &self,
+        _global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn host_entity_to_global_entity(
+        &self,
+        _host_entity: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn remote_entity_to_global_entity(
+        &self,
+        _remote_entity: &RemoteEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverterMut for UnmappedEntityConverter {
+    fn get_or_reserve_entity(
+        &mut self,
+        _global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+}
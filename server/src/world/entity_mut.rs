@@ -1,8 +1,8 @@
-use std::hash::Hash;
+use std::{hash::Hash, time::Duration};
 
 use naia_shared::{EntityAuthStatus, ReplicaMutWrapper, ReplicatedComponent, WorldMutType};
 
-use crate::{room::RoomKey, server::Server, ReplicationConfig};
+use crate::{room::RoomKey, server::Server, NaiaServerError, ReplicationConfig};
 
 // EntityMut
 pub struct EntityMut<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> {
@@ -78,6 +78,41 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> EntityMut<'s, E,
         self.server.entity_authority_status(&self.entity)
     }
 
+    /// Overrides `ServerConfig::auth_idle_timeout` for this Entity. None
+    /// means authority over it is never reclaimed for being idle
+    pub fn set_auth_idle_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.server
+            .entity_set_auth_idle_timeout(&self.entity, timeout);
+
+        self
+    }
+
+    // Scope
+
+    /// Keeps this Entity out of each User's scope until the given Entity has
+    /// been spawned on their Client, whichever Room or direct include
+    /// scopes it, i.e. an attachment which references its owner. Any number
+    /// of dependencies may be added. Unless `ServerConfig::despawn_scope_dependents`
+    /// is disabled, this Entity also leaves a User's scope whenever the
+    /// dependency does. Errors if the dependency would form a cycle
+    pub fn scope_depends_on(&mut self, entity: &E) -> Result<&mut Self, NaiaServerError> {
+        self.server
+            .entity_add_scope_dependency(&self.entity, entity)?;
+
+        Ok(self)
+    }
+
+    pub fn remove_scope_dependency(&mut self, entity: &E) -> &mut Self {
+        self.server
+            .entity_remove_scope_dependency(&self.entity, entity);
+
+        self
+    }
+
+    pub fn scope_dependencies(&self) -> Vec<E> {
+        self.server.entity_scope_dependencies(&self.entity)
+    }
+
     // Rooms
 
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
@@ -39,4 +39,10 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>> EntityRef<'s, E,
     pub fn authority(&self) -> Option<EntityAuthStatus> {
         self.server.entity_authority_status(&self.entity)
     }
+
+    /// The Entities which must be in a User's scope before this one enters
+    /// it, see `EntityMut::scope_depends_on()`
+    pub fn scope_dependencies(&self) -> Vec<E> {
+        self.server.entity_scope_dependencies(&self.entity)
+    }
 }
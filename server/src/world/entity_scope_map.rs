@@ -9,6 +9,10 @@ pub struct EntityScopeMap<E: Copy + Eq + Hash> {
     entities_of_user: HashMap<UserKey, HashSet<E>>,
     users_of_entity: HashMap<E, HashSet<UserKey>>,
     main_map: HashMap<(UserKey, E), bool>,
+    // Entities included for a User directly, regardless of Rooms
+    direct_entities_of_user: HashMap<UserKey, HashSet<E>>,
+    // Entities excluded directly, which may need to leave the User's scope
+    direct_removals: Vec<(UserKey, E)>,
 }
 
 impl<E: Copy + Eq + Hash> EntityScopeMap<E> {
@@ -17,6 +21,8 @@ impl<E: Copy + Eq + Hash> EntityScopeMap<E> {
             main_map: HashMap::new(),
             entities_of_user: HashMap::new(),
             users_of_entity: HashMap::new(),
+            direct_entities_of_user: HashMap::new(),
+            direct_removals: Vec::new(),
         }
     }
 
@@ -46,7 +52,47 @@ impl<E: Copy + Eq + Hash> EntityScopeMap<E> {
         self.main_map.insert((user_key, entity), in_scope);
     }
 
+    pub fn include_direct(&mut self, user_key: UserKey, entity: E) {
+        self.direct_entities_of_user
+            .entry(user_key)
+            .or_default()
+            .insert(entity);
+    }
+
+    pub fn exclude_direct(&mut self, user_key: &UserKey, entity: &E) {
+        let Some(entities) = self.direct_entities_of_user.get_mut(user_key) else {
+            return;
+        };
+        if !entities.remove(entity) {
+            return;
+        }
+        if entities.is_empty() {
+            self.direct_entities_of_user.remove(user_key);
+        }
+        self.direct_removals.push((*user_key, *entity));
+    }
+
+    pub fn is_direct(&self, user_key: &UserKey, entity: &E) -> bool {
+        self.direct_entities_of_user
+            .get(user_key)
+            .is_some_and(|entities| entities.contains(entity))
+    }
+
+    pub fn direct_entities_of_user(&self) -> &HashMap<UserKey, HashSet<E>> {
+        &self.direct_entities_of_user
+    }
+
+    pub fn take_direct_removals(&mut self) -> Vec<(UserKey, E)> {
+        std::mem::take(&mut self.direct_removals)
+    }
+
     pub fn remove_user(&mut self, user_key: &UserKey) {
+        if let Some(entities) = self.direct_entities_of_user.remove(user_key) {
+            for entity in entities {
+                self.direct_removals.push((*user_key, entity));
+            }
+        }
+
         if let Some(entities) = self.entities_of_user.get(user_key) {
             for entity in entities {
                 if let Some(users) = self.users_of_entity.get_mut(entity) {
@@ -60,6 +106,11 @@ impl<E: Copy + Eq + Hash> EntityScopeMap<E> {
     }
 
     pub fn remove_entity(&mut self, entity: &E) {
+        self.direct_entities_of_user.retain(|_, entities| {
+            entities.remove(entity);
+            !entities.is_empty()
+        });
+
         if let Some(users) = self.users_of_entity.get(entity) {
             for user in users {
                 if let Some(entities) = self.entities_of_user.get_mut(user) {
@@ -18,7 +18,7 @@ use crate::{
         mut_channel::MutChannelData,
         server_auth_handler::{AuthOwner, ServerAuthHandler},
     },
-    EntityOwner, ReplicationConfig, UserKey,
+    EntityIdRange, EntityOwner, ReplicationConfig, UserKey,
 };
 
 pub struct GlobalWorldManager<E: Copy + Eq + Hash + Send + Sync> {
@@ -33,12 +33,15 @@ pub struct GlobalWorldManager<E: Copy + Eq + Hash + Send + Sync> {
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> GlobalWorldManager<E> {
-    pub fn new() -> Self {
+    pub fn new(entity_id_range: &EntityIdRange) -> Self {
         Self {
             auth_handler: ServerAuthHandler::new(),
             diff_handler: Arc::new(RwLock::new(GlobalDiffHandler::new())),
             entity_records: HashMap::default(),
-            global_entity_map: BigMap::new(),
+            global_entity_map: BigMap::with_key_range(
+                entity_id_range.start,
+                entity_id_range.stride,
+            ),
         }
     }
 
@@ -46,6 +49,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> GlobalWorldManager<E> {
         self.entity_records.contains_key(entity)
     }
 
+    #[cfg(feature = "metrics")]
+    pub fn entities_count(&self) -> usize {
+        self.entity_records.len()
+    }
+
     pub fn entity_owner(&self, entity: &E) -> Option<EntityOwner> {
         if let Some(record) = self.entity_records.get(entity) {
             return Some(record.owner);
@@ -278,10 +286,76 @@ impl<E: Copy + Eq + Hash + Send + Sync> GlobalWorldManager<E> {
         self.auth_handler.client_release_authority(entity, releaser)
     }
 
+    pub(crate) fn transfer_authority(&mut self, entity: &E, from: &UserKey, to: &UserKey) -> bool {
+        self.auth_handler.transfer_authority(entity, from, to)
+    }
+
     pub(crate) fn user_all_owned_entities(&self, user_key: &UserKey) -> Option<&HashSet<E>> {
         self.auth_handler.user_all_owned_entities(user_key)
     }
 
+    pub(crate) fn entity_authority_holder(&self, entity: &E) -> Option<UserKey> {
+        self.auth_handler.authority_holder(entity)
+    }
+
+    /// Get all Entities owned by the Server: those it spawned or which were
+    /// migrated to it, unless a Client currently holds Authority over them
+    pub(crate) fn server_owned_entities(&self) -> Vec<E> {
+        let mut output = Vec::new();
+
+        for record in self.entity_records.values() {
+            if !record.owner.is_server() {
+                continue;
+            }
+            let Some(entity) = self.global_entity_map.get(&record.global_entity) else {
+                continue;
+            };
+            if self.entity_authority_holder(entity).is_none() {
+                output.push(*entity);
+            }
+        }
+
+        output
+    }
+
+    /// Get all Entities owned by the given User, either because they were
+    /// spawned by the User's Client, or because Authority over them has been
+    /// delegated to it
+    pub(crate) fn user_owned_entities(&self, user_key: &UserKey) -> Vec<E> {
+        let mut output = Vec::new();
+
+        for record in self.entity_records.values() {
+            let (EntityOwner::Client(owner_key)
+            | EntityOwner::ClientWaiting(owner_key)
+            | EntityOwner::ClientPublic(owner_key)) = record.owner
+            else {
+                continue;
+            };
+            if owner_key != *user_key {
+                continue;
+            }
+            if let Some(entity) = self.global_entity_map.get(&record.global_entity) {
+                output.push(*entity);
+            }
+        }
+
+        if let Some(delegated_entities) = self.user_all_owned_entities(user_key) {
+            for entity in delegated_entities {
+                let Some(record) = self.entity_records.get(entity) else {
+                    continue;
+                };
+                let Some(entity) = self.global_entity_map.get(&record.global_entity) else {
+                    continue;
+                };
+                if !output.contains(entity) {
+                    output.push(*entity);
+                }
+            }
+        }
+
+        output
+    }
+
     pub(crate) fn pause_entity_replication(&mut self, entity: &E) {
         let Some(record) = self.entity_records.get_mut(entity) else {
             panic!("entity record does not exist!");
@@ -383,4 +457,118 @@ impl<E: Copy + Eq + Hash + Send + Sync> EntityAndGlobalEntityConverter<E>
             Err(EntityDoesNotExistError)
         }
     }
+
+    fn global_entities_to_entities(&self, global_entities: &[GlobalEntity]) -> Vec<Option<E>> {
+        global_entities
+            .iter()
+            .map(|global_entity| self.global_entity_map.get(global_entity).copied())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod user_owned_entities_tests {
+    use naia_shared::BigMapKey;
+
+    use crate::{
+        world::{global_world_manager::GlobalWorldManager, server_auth_handler::AuthOwner},
+        EntityIdRange, EntityOwner, UserKey,
+    };
+
+    #[test]
+    fn client_owned_entity_listed_for_owner_only() {
+        let owner = UserKey::from_u64(0);
+        let other = UserKey::from_u64(1);
+
+        let mut manager = GlobalWorldManager::<u32>::new(&EntityIdRange::default());
+        manager.spawn_entity_record(&1, EntityOwner::Client(owner));
+        manager.spawn_entity_record(&2, EntityOwner::Server);
+
+        assert_eq!(manager.user_owned_entities(&owner), vec![1]);
+        assert!(manager.user_owned_entities(&other).is_empty());
+
+        manager.remove_entity_record(&1);
+        assert!(manager.user_owned_entities(&owner).is_empty());
+    }
+
+    #[test]
+    fn delegated_entity_listed_for_authority_holder() {
+        let holder = UserKey::from_u64(0);
+        let other = UserKey::from_u64(1);
+
+        let mut manager = GlobalWorldManager::<u32>::new(&EntityIdRange::default());
+        manager.spawn_entity_record(&1, EntityOwner::Server);
+        manager.entity_enable_delegation(&1);
+        assert!(manager.client_request_authority(&1, &AuthOwner::Client(holder)));
+
+        assert_eq!(manager.user_owned_entities(&holder), vec![1]);
+        assert!(manager.user_owned_entities(&other).is_empty());
+        assert!(manager.server_owned_entities().is_empty());
+    }
+
+    #[test]
+    fn releasing_one_entity_keeps_authority_over_others() {
+        let holder = UserKey::from_u64(0);
+
+        let mut manager = GlobalWorldManager::<u32>::new(&EntityIdRange::default());
+        for entity in [1, 2] {
+            manager.spawn_entity_record(&entity, EntityOwner::Server);
+            manager.entity_enable_delegation(&entity);
+            assert!(manager.client_request_authority(&entity, &AuthOwner::Client(holder)));
+        }
+
+        assert!(manager.client_release_authority(&1, &AuthOwner::Client(holder)));
+        assert_eq!(manager.user_owned_entities(&holder), vec![2]);
+        assert_eq!(manager.server_owned_entities(), vec![1]);
+
+        // no longer delegated, so no longer anyone's to hold
+        manager.entity_disable_delegation(&2);
+        assert!(manager.user_owned_entities(&holder).is_empty());
+        assert!(manager.user_all_owned_entities(&holder).is_none());
+    }
+}
+
+#[cfg(test)]
+mod entity_id_range_tests {
+    use std::collections::HashSet;
+
+    use naia_shared::{BigMapKey, EntityAndGlobalEntityConverter, GlobalEntity};
+
+    use crate::{world::global_world_manager::GlobalWorldManager, EntityIdRange, EntityOwner};
+
+    fn spawn_global_entities(entity_id_range: EntityIdRange, count: u32) -> Vec<GlobalEntity> {
+        let mut manager = GlobalWorldManager::<u32>::new(&entity_id_range);
+        (0..count)
+            .map(|entity| {
+                manager.spawn_entity_record(&entity, EntityOwner::Server);
+                manager.entity_to_global_entity(&entity).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nodes_with_different_starts_never_share_ids() {
+        let node_0 = spawn_global_entities(
+            EntityIdRange {
+                start: 0,
+                stride: 2,
+            },
+            5000,
+        );
+        let node_1 = spawn_global_entities(
+            EntityIdRange {
+                start: 1,
+                stride: 2,
+            },
+            5000,
+        );
+
+        let node_0_ids: HashSet<GlobalEntity> = node_0.iter().copied().collect();
+        assert_eq!(node_0_ids.len(), node_0.len());
+        assert!(node_1.iter().all(|id| !node_0_ids.contains(id)));
+
+        assert_eq!(node_1[0].to_u64(), 1);
+        assert_eq!(node_1[1].to_u64(), 3);
+        assert!(node_1.iter().all(|id| id.to_u64() % 2 == 1));
+    }
 }
@@ -1,3 +1,4 @@
+pub mod entity_export;
 pub mod entity_mut;
 pub mod entity_owner;
 pub mod entity_ref;
@@ -7,4 +8,7 @@ pub mod global_entity_record;
 pub mod global_world_manager;
 pub mod mut_channel;
 pub mod replication_config;
+pub mod scope_cache;
+pub mod scope_dependencies;
 pub mod server_auth_handler;
+pub mod world_snapshot;
@@ -0,0 +1,214 @@
+use std::{collections::HashSet, hash::Hash, sync::Mutex};
+
+use naia_shared::BigMap;
+
+use crate::{room::Room, RoomKey, UserKey};
+
+/// A unique combination of a Room, a User in it, and an Entity in it, for
+/// which it must be decided whether the Entity is in scope for the User
+pub type ScopeCheck<E> = (RoomKey, UserKey, E);
+
+/// Tracks which (Room, User, Entity) combinations have changed, so that
+/// scopes only need re-evaluating for those rather than every combination.
+/// Changes are tracked twice over: once for the application, through
+/// `Server::scope_changes()`, and once for the Server's own scope updates
+pub struct ScopeCache<E: Copy + Eq + Hash> {
+    // every ScopeCheck, None once any Room has changed
+    checks: Mutex<Option<Vec<ScopeCheck<E>>>>,
+    // changes not yet yielded by `take_changes()`
+    all_changed: bool,
+    changed_users: HashSet<(RoomKey, UserKey)>,
+    changed_entities: HashSet<(RoomKey, E)>,
+    // changes not yet applied to the Users' connections
+    all_dirty: bool,
+    dirty_users: HashSet<(RoomKey, UserKey)>,
+    dirty_entities: HashSet<(RoomKey, E)>,
+    dirty_pairs: HashSet<(UserKey, E)>,
+}
+
+impl<E: Copy + Eq + Hash> ScopeCache<E> {
+    pub fn new() -> Self {
+        Self {
+            checks: Mutex::new(None),
+            all_changed: false,
+            changed_users: HashSet::new(),
+            changed_entities: HashSet::new(),
+            all_dirty: false,
+            dirty_users: HashSet::new(),
+            dirty_entities: HashSet::new(),
+            dirty_pairs: HashSet::new(),
+        }
+    }
+
+    // Invalidation
+
+    /// A User joined a Room, or connected while in it
+    pub fn user_added(&mut self, room_key: &RoomKey, user_key: &UserKey) {
+        self.rooms_changed();
+        self.changed_users.insert((*room_key, *user_key));
+        self.dirty_users.insert((*room_key, *user_key));
+    }
+
+    /// An Entity was added to a Room
+    pub fn entity_added(&mut self, room_key: &RoomKey, entity: &E) {
+        self.rooms_changed();
+        self.changed_entities.insert((*room_key, *entity));
+        self.dirty_entities.insert((*room_key, *entity));
+    }
+
+    /// A User or Entity left a Room, or a Room was destroyed. Whatever must
+    /// leave scope as a result is handled by the Room's removal queue
+    pub fn rooms_changed(&mut self) {
+        *self.checks_mut() = None;
+    }
+
+    /// A paused Room was resumed, so all of its scopes are re-evaluated
+    pub fn room_resumed(&mut self, room_key: &RoomKey, user_keys: Vec<UserKey>) {
+        self.rooms_changed();
+        for user_key in user_keys {
+            self.changed_users.insert((*room_key, user_key));
+            self.dirty_users.insert((*room_key, user_key));
+        }
+    }
+
+    /// Whether the User should see the Entity was decided anew
+    pub fn pair_dirty(&mut self, user_key: &UserKey, entity: &E) {
+        self.dirty_pairs.insert((*user_key, *entity));
+    }
+
+    /// Every scope decision for the User in the given Rooms was reset
+    pub fn user_dirty<'a>(
+        &mut self,
+        user_key: &UserKey,
+        room_keys: impl Iterator<Item = &'a RoomKey>,
+    ) {
+        for room_key in room_keys {
+            self.dirty_users.insert((*room_key, *user_key));
+        }
+    }
+
+    /// Every (Room, User, Entity) is re-evaluated, both by the application &
+    /// the Server
+    pub fn invalidate_all(&mut self) {
+        self.all_changed = true;
+        self.all_dirty = true;
+        self.changed_users.clear();
+        self.changed_entities.clear();
+    }
+
+    // Reading
+
+    /// Every ScopeCheck, rebuilt only if a Room has changed since last time
+    pub fn checks(&self, rooms: &BigMap<RoomKey, Room<E>>) -> Vec<ScopeCheck<E>> {
+        let mut checks = self
+            .checks
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        checks
+            .get_or_insert_with(|| Self::build_checks(rooms))
+            .clone()
+    }
+
+    /// Every ScopeCheck which needs re-evaluating by the application since
+    /// the last call
+    pub fn take_changes(&mut self, rooms: &BigMap<RoomKey, Room<E>>) -> Vec<ScopeCheck<E>> {
+        let changed_users = std::mem::take(&mut self.changed_users);
+        let changed_entities = std::mem::take(&mut self.changed_entities);
+        if std::mem::take(&mut self.all_changed) {
+            return self.checks(rooms);
+        }
+
+        let mut changes = HashSet::new();
+        for (room_key, user_key) in changed_users {
+            // a paused Room's changes are all re-evaluated once resumed
+            let Some(room) = rooms.get(&room_key).filter(|room| !room.is_paused()) else {
+                continue;
+            };
+            if !room.has_user(&user_key) {
+                continue;
+            }
+            for entity in room.entities() {
+                changes.insert((room_key, user_key, *entity));
+            }
+        }
+        for (room_key, entity) in changed_entities {
+            let Some(room) = rooms.get(&room_key).filter(|room| !room.is_paused()) else {
+                continue;
+            };
+            if !room.has_entity(&entity) {
+                continue;
+            }
+            for user_key in room.user_keys() {
+                changes.insert((room_key, *user_key, entity));
+            }
+        }
+        changes.into_iter().collect()
+    }
+
+    /// Every (User, Entity) whose scope must be re-evaluated for the Users'
+    /// connections. They are not checked for sharing a Room
+    pub fn take_dirty(&mut self, rooms: &BigMap<RoomKey, Room<E>>) -> HashSet<(UserKey, E)> {
+        let mut dirty = std::mem::take(&mut self.dirty_pairs);
+        let dirty_users = std::mem::take(&mut self.dirty_users);
+        let dirty_entities = std::mem::take(&mut self.dirty_entities);
+        if std::mem::take(&mut self.all_dirty) {
+            for (_, room) in rooms.iter() {
+                if room.is_paused() {
+                    continue;
+                }
+                for user_key in room.user_keys() {
+                    for entity in room.entities() {
+                        dirty.insert((*user_key, *entity));
+                    }
+                }
+            }
+            return dirty;
+        }
+
+        for (room_key, user_key) in dirty_users {
+            // a paused Room's changes are all re-evaluated once resumed
+            let Some(room) = rooms.get(&room_key).filter(|room| !room.is_paused()) else {
+                continue;
+            };
+            if !room.has_user(&user_key) {
+                continue;
+            }
+            for entity in room.entities() {
+                dirty.insert((user_key, *entity));
+            }
+        }
+        for (room_key, entity) in dirty_entities {
+            let Some(room) = rooms.get(&room_key).filter(|room| !room.is_paused()) else {
+                continue;
+            };
+            if !room.has_entity(&entity) {
+                continue;
+            }
+            for user_key in room.user_keys() {
+                dirty.insert((*user_key, entity));
+            }
+        }
+        dirty
+    }
+
+    fn build_checks(rooms: &BigMap<RoomKey, Room<E>>) -> Vec<ScopeCheck<E>> {
+        let mut checks = Vec::new();
+        for (room_key, room) in rooms.iter() {
+            if room.is_paused() {
+                continue;
+            }
+            for user_key in room.user_keys() {
+                for entity in room.entities() {
+                    checks.push((room_key, *user_key, *entity));
+                }
+            }
+        }
+        checks
+    }
+
+    fn checks_mut(&mut self) -> &mut Option<Vec<ScopeCheck<E>>> {
+        self.checks
+            .get_mut()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
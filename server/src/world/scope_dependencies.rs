@@ -0,0 +1,170 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/// Which Entities may only be in a User's scope while others are, see
+/// `EntityMut::scope_depends_on()`. Always acyclic
+pub struct ScopeDependencies<E: Copy + Eq + Hash> {
+    dependencies_of: HashMap<E, HashSet<E>>,
+    dependents_of: HashMap<E, HashSet<E>>,
+    // Entities which depended on a despawned Entity, & so never enter scope
+    orphans: HashSet<E>,
+}
+
+impl<E: Copy + Eq + Hash> ScopeDependencies<E> {
+    pub fn new() -> Self {
+        Self {
+            dependencies_of: HashMap::new(),
+            dependents_of: HashMap::new(),
+            orphans: HashSet::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dependencies_of.is_empty() && self.orphans.is_empty()
+    }
+
+    /// Returns false, adding nothing, if the dependency would form a cycle
+    pub fn insert(&mut self, dependent: &E, dependency: &E) -> bool {
+        if self.depends_on(dependency, dependent) {
+            return false;
+        }
+        self.dependencies_of
+            .entry(*dependent)
+            .or_default()
+            .insert(*dependency);
+        self.dependents_of
+            .entry(*dependency)
+            .or_default()
+            .insert(*dependent);
+        true
+    }
+
+    pub fn remove(&mut self, dependent: &E, dependency: &E) -> bool {
+        let Some(dependencies) = self.dependencies_of.get_mut(dependent) else {
+            return false;
+        };
+        if !dependencies.remove(dependency) {
+            return false;
+        }
+        if dependencies.is_empty() {
+            self.dependencies_of.remove(dependent);
+        }
+        if let Some(dependents) = self.dependents_of.get_mut(dependency) {
+            dependents.remove(dependent);
+            if dependents.is_empty() {
+                self.dependents_of.remove(dependency);
+            }
+        }
+        true
+    }
+
+    /// Whether `entity` depends on `other`, directly or not. Every Entity
+    /// depends on itself
+    fn depends_on(&self, entity: &E, other: &E) -> bool {
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![*entity];
+        while let Some(visiting) = to_visit.pop() {
+            if visiting == *other {
+                return true;
+            }
+            if !visited.insert(visiting) {
+                continue;
+            }
+            if let Some(dependencies) = self.dependencies_of.get(&visiting) {
+                to_visit.extend(dependencies.iter().copied());
+            }
+        }
+        false
+    }
+
+    pub fn dependencies(&self, entity: &E) -> Option<&HashSet<E>> {
+        self.dependencies_of.get(entity)
+    }
+
+    /// Every Entity which depends on another
+    pub fn dependents(&self) -> impl Iterator<Item = &E> {
+        self.dependencies_of.keys()
+    }
+
+    pub fn orphans(&self) -> impl Iterator<Item = &E> {
+        self.orphans.iter()
+    }
+
+    pub fn is_orphan(&self, entity: &E) -> bool {
+        self.orphans.contains(entity)
+    }
+
+    /// Forgets a despawned Entity. If `orphan_dependents`, the Entities which
+    /// depended on it never enter scope again, otherwise they simply no
+    /// longer depend on it
+    pub fn remove_entity(&mut self, entity: &E, orphan_dependents: bool) {
+        self.orphans.remove(entity);
+        if let Some(dependencies) = self.dependencies_of.remove(entity) {
+            for dependency in dependencies {
+                if let Some(dependents) = self.dependents_of.get_mut(&dependency) {
+                    dependents.remove(entity);
+                    if dependents.is_empty() {
+                        self.dependents_of.remove(&dependency);
+                    }
+                }
+            }
+        }
+        let Some(dependents) = self.dependents_of.remove(entity) else {
+            return;
+        };
+        for dependent in dependents {
+            if let Some(dependencies) = self.dependencies_of.get_mut(&dependent) {
+                dependencies.remove(entity);
+                if dependencies.is_empty() {
+                    self.dependencies_of.remove(&dependent);
+                }
+            }
+            if orphan_dependents {
+                self.orphans.insert(dependent);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScopeDependencies;
+
+    #[test]
+    fn rejects_cycles() {
+        let mut dependencies = ScopeDependencies::new();
+        assert!(!dependencies.insert(&1, &1));
+        assert!(dependencies.insert(&1, &2));
+        assert!(dependencies.insert(&2, &3));
+        assert!(!dependencies.insert(&3, &1));
+        assert!(!dependencies.insert(&2, &1));
+        // diamonds are fine
+        assert!(dependencies.insert(&1, &3));
+        assert!(dependencies.insert(&4, &2));
+        assert!(dependencies.insert(&4, &3));
+
+        assert!(dependencies.remove(&2, &3));
+        assert!(dependencies.insert(&3, &2));
+    }
+
+    #[test]
+    fn despawned_dependencies_orphan_dependents() {
+        let mut dependencies = ScopeDependencies::new();
+        dependencies.insert(&1, &2);
+        dependencies.insert(&3, &2);
+        dependencies.remove_entity(&2, true);
+        assert!(dependencies.dependencies(&1).is_none());
+        assert!(dependencies.is_orphan(&1) && dependencies.is_orphan(&3));
+
+        dependencies.insert(&4, &5);
+        dependencies.remove_entity(&5, false);
+        assert!(dependencies.dependencies(&4).is_none());
+        assert!(!dependencies.is_orphan(&4));
+
+        dependencies.remove_entity(&1, true);
+        dependencies.remove_entity(&3, true);
+        assert!(dependencies.is_empty());
+    }
+}
@@ -50,7 +50,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
 
     pub fn deregister_entity(&mut self, entity: &E) {
         self.host_auth_handler.deregister_entity(entity);
-        self.entity_auth_map.remove(&entity);
+        if let Some(AuthOwner::Client(user_key)) = self.entity_auth_map.remove(&entity) {
+            self.remove_user_entity(&user_key, entity);
+        }
     }
 
     pub(crate) fn authority_status(&self, entity: &E) -> Option<EntityAuthStatus> {
@@ -106,6 +108,26 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
         }
     }
 
+    // returns whether `from` held authority, and so whether it was transferred
+    pub(crate) fn transfer_authority(&mut self, entity: &E, from: &UserKey, to: &UserKey) -> bool {
+        let Some(owner) = self.entity_auth_map.get_mut(entity) else {
+            panic!("Entity not registered with ServerAuthHandler");
+        };
+        if *owner != AuthOwner::Client(*from) {
+            return false;
+        }
+        *owner = AuthOwner::Client(*to);
+
+        self.remove_user_entity(from, entity);
+        self.user_to_entity_map
+            .entry(*to)
+            .or_default()
+            .insert(*entity);
+
+        // the Server's local Authority remains Denied, as a Client still holds it
+        return true;
+    }
+
     // returns whether or not any change needed to be made
     pub(crate) fn server_take_authority(&mut self, entity: &E) -> bool {
         let Some(owner) = self.entity_auth_map.get_mut(entity) else {
@@ -126,14 +148,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
         }
 
         if let AuthOwner::Client(user_key) = owner {
-            let mut remove_user = false;
-            if let Some(entities) = self.user_to_entity_map.get_mut(&user_key) {
-                entities.remove(entity);
-                remove_user = true;
-            }
-            if remove_user {
-                self.user_to_entity_map.remove(&user_key);
-            }
+            self.remove_user_entity(&user_key, entity);
         }
 
         self.host_auth_handler
@@ -148,4 +163,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
         }
         return None;
     }
+
+    /// The User whose Client holds Authority over the Entity, if any
+    pub(crate) fn authority_holder(&self, entity: &E) -> Option<UserKey> {
+        match self.entity_auth_map.get(entity) {
+            Some(AuthOwner::Client(user_key)) => Some(*user_key),
+            _ => None,
+        }
+    }
+
+    // the User's other Entities are left as they are
+    fn remove_user_entity(&mut self, user_key: &UserKey, entity: &E) {
+        if let Some(entities) = self.user_to_entity_map.get_mut(user_key) {
+            entities.remove(entity);
+            if entities.is_empty() {
+                self.user_to_entity_map.remove(user_key);
+            }
+        }
+    }
 }
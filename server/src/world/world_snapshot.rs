@@ -0,0 +1,151 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Read, Write},
+};
+
+use crate::EntityExport;
+
+// Every snapshot starts with these bytes, so that other files are rejected
+const SNAPSHOT_MAGIC: [u8; 8] = *b"NAIASNAP";
+// Bumped whenever the layout below changes
+const SNAPSHOT_VERSION: u16 = 1;
+
+/// An error from `Server::save_world_snapshot()` or
+/// `Server::load_world_snapshot()`
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// Reading or writing the snapshot failed
+    Io(io::Error),
+    /// The bytes do not start with a world snapshot header
+    NotASnapshot,
+    /// The snapshot was written in a layout this version of naia can't read
+    UnsupportedVersion(u16),
+    /// The snapshot was written with a Protocol other than this Server's, see
+    /// `Protocol::schema_digest()`
+    SchemaMismatch { expected: u64, found: u64 },
+    /// The snapshot's contents could not be read
+    Malformed(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SnapshotError::Io(error) => write!(f, "World Snapshot Error: {}", error),
+            SnapshotError::NotASnapshot => {
+                write!(f, "World Snapshot Error: input is not a world snapshot")
+            }
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "World Snapshot Error: snapshot version {} is not supported, expected version {}",
+                version, SNAPSHOT_VERSION
+            ),
+            SnapshotError::SchemaMismatch { expected, found } => write!(
+                f,
+                "World Snapshot Error: snapshot was saved with Protocol schema digest {:#018x}, but this Server's is {:#018x}",
+                found, expected
+            ),
+            SnapshotError::Malformed(reason) => {
+                write!(f, "World Snapshot Error: snapshot is malformed, {}", reason)
+            }
+        }
+    }
+}
+
+impl Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+// Layout, all integers little-endian:
+//   magic: [u8; 8]
+//   version: u16
+//   schema digest: u64
+//   entity count: u32
+//   per Entity: byte length u32, then the bytes of its `EntityExport`
+
+pub(crate) fn write_snapshot(
+    out: &mut impl Write,
+    schema_digest: u64,
+    exports: &[EntityExport],
+) -> Result<(), SnapshotError> {
+    out.write_all(&SNAPSHOT_MAGIC)?;
+    out.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+    out.write_all(&schema_digest.to_le_bytes())?;
+    out.write_all(&(exports.len() as u32).to_le_bytes())?;
+    for export in exports {
+        let bytes = export.as_bytes();
+        out.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        out.write_all(bytes)?;
+    }
+    Ok(())
+}
+
+/// Reads a whole snapshot, checking its header against the given schema
+/// digest before any of the Entities are read
+pub(crate) fn read_snapshot(
+    input: &mut impl Read,
+    schema_digest: u64,
+) -> Result<Vec<EntityExport>, SnapshotError> {
+    let mut magic = [0; 8];
+    read_header_bytes(input, &mut magic)?;
+    if magic != SNAPSHOT_MAGIC {
+        return Err(SnapshotError::NotASnapshot);
+    }
+
+    let mut version = [0; 2];
+    read_header_bytes(input, &mut version)?;
+    let version = u16::from_le_bytes(version);
+    if version != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+
+    let mut found_digest = [0; 8];
+    read_header_bytes(input, &mut found_digest)?;
+    let found_digest = u64::from_le_bytes(found_digest);
+    if found_digest != schema_digest {
+        return Err(SnapshotError::SchemaMismatch {
+            expected: schema_digest,
+            found: found_digest,
+        });
+    }
+
+    let entity_count = read_u32(input, "entity count")?;
+    let mut exports = Vec::new();
+    for _ in 0..entity_count {
+        let length = read_u32(input, "entity length")?;
+        let mut bytes = Vec::new();
+        input.take(length as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != length as usize {
+            return Err(SnapshotError::Malformed("entity is truncated".to_string()));
+        }
+        exports.push(EntityExport::from_bytes(bytes));
+    }
+    Ok(exports)
+}
+
+// A header cut short means the input was never a snapshot
+fn read_header_bytes(input: &mut impl Read, buffer: &mut [u8]) -> Result<(), SnapshotError> {
+    input.read_exact(buffer).map_err(|error| {
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            SnapshotError::NotASnapshot
+        } else {
+            SnapshotError::Io(error)
+        }
+    })
+}
+
+fn read_u32(input: &mut impl Read, field: &str) -> Result<u32, SnapshotError> {
+    let mut bytes = [0; 4];
+    input.read_exact(&mut bytes).map_err(|error| {
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            SnapshotError::Malformed(format!("{} is truncated", field))
+        } else {
+            SnapshotError::Io(error)
+        }
+    })?;
+    Ok(u32::from_le_bytes(bytes))
+}
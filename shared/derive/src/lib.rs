@@ -17,21 +17,34 @@ use replicate::replicate_impl;
 // Replicate
 
 /// Derives the Replicate trait for a given struct
-#[proc_macro_derive(Replicate)]
+///
+/// Mark the struct with `#[replicate(full_update)]` to send every field in
+/// each of its updates, rather than only the fields which changed
+///
+/// Mark a `Property<f32>` field with
+/// `#[quantize(min = -1024.0, max = 1024.0, precision = 0.01)]` to send it in
+/// the fewest bits which keep it within `precision` over that range, or with
+/// `#[angle]` / `#[angle(precision = 0.01)]` to do the same for an angle in
+/// radians, wrapped into `0..2π`
+///
+/// Generic structs are supported, with each instantiation registered as its
+/// own Component, i.e. `add_component::<Inventory<WeaponDef>>()`. Type
+/// parameters without bounds must be `Serde`
+#[proc_macro_derive(Replicate, attributes(replicate, quantize, angle, bits))]
 pub fn replicate_derive_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_shared };
     replicate_impl(input, shared_crate_name)
 }
 
 /// Derives the Replicate trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(ReplicateBevy)]
+#[proc_macro_derive(ReplicateBevy, attributes(replicate, quantize, angle, bits))]
 pub fn replicate_derive_bevy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_bevy_shared };
     replicate_impl(input, shared_crate_name)
 }
 
 /// Derives the Replicate trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(ReplicateHecs)]
+#[proc_macro_derive(ReplicateHecs, attributes(replicate, quantize, angle, bits))]
 pub fn replicate_derive_hecs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_hecs_shared };
     replicate_impl(input, shared_crate_name)
@@ -69,21 +82,24 @@ pub fn message_derive_request(input: proc_macro::TokenStream) -> proc_macro::Tok
 }
 
 /// Derives the Message trait for a given struct
-#[proc_macro_derive(Message)]
+///
+/// Generic structs are supported, with each instantiation registered as its
+/// own Message. Type parameters without bounds must be `Serde`
+#[proc_macro_derive(Message, attributes(serde_version))]
 pub fn message_derive_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_shared };
     message_impl(input, shared_crate_name, false, false)
 }
 
 /// Derives the Message trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(MessageBevy)]
+#[proc_macro_derive(MessageBevy, attributes(serde_version))]
 pub fn message_derive_bevy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_bevy_shared };
     message_impl(input, shared_crate_name, false, false)
 }
 
 /// Derives the Message trait for a given struct, for the Hecs adapter
-#[proc_macro_derive(MessageHecs)]
+#[proc_macro_derive(MessageHecs, attributes(serde_version))]
 pub fn message_derive_hecs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_hecs_shared };
     message_impl(input, shared_crate_name, false, false)
@@ -1,11 +1,14 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, GenericParam, Generics, Ident, Index, LitStr,
-    Member, Type,
+    parse_macro_input, Attribute, Data, DeriveInput, Fields, GenericParam, Generics, Ident, Index,
+    LitInt, Member, Type,
 };
 
-use super::shared::{get_builder_generic_fields, get_generics, get_struct_type, StructType};
+use super::shared::{
+    get_builder_generic_fields, get_generics, get_name_expr, get_struct_type, get_where_clause,
+    validate_generics, StructType,
+};
 
 pub fn message_impl(
     input: proc_macro::TokenStream,
@@ -14,23 +17,21 @@ pub fn message_impl(
     is_request: bool,
 ) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    if let Err(error) = validate_generics(&input.generics, "Message") {
+        return error.to_compile_error().into();
+    }
 
     // Helper Properties
     let struct_type = get_struct_type(&input);
     let fields = get_fields(&input);
+    let serde_version = get_serde_version(&input.attrs);
+    validate_field_versions(&fields, serde_version);
     let (untyped_generics, typed_generics, turbofish) = get_generics(&input);
+    let where_clause = get_where_clause(&input.generics, &shared_crate_name);
 
     // Names
     let struct_name = input.ident;
-    let struct_name_str = LitStr::new(
-        format!(
-            "{}{}",
-            &struct_name.to_string(),
-            &untyped_generics.to_string()
-        )
-        .as_str(),
-        struct_name.span(),
-    );
+    let struct_name_expr = get_name_expr(&struct_name, &input.generics);
     let lowercase_struct_name = Ident::new(
         struct_name.to_string().to_lowercase().as_str(),
         Span::call_site(),
@@ -43,8 +44,8 @@ pub fn message_impl(
     let clone_method = get_clone_method(&fields, &struct_type);
     let relations_waiting_method = get_relations_waiting_method(&fields, &struct_type);
     let relations_complete_method = get_relations_complete_method(&fields, &struct_type);
-    let bit_length_method = get_bit_length_method(&fields, &struct_type);
-    let write_method = get_write_method(&fields, &struct_type);
+    let bit_length_method = get_bit_length_method(&fields, &struct_type, serde_version);
+    let write_method = get_write_method(&fields, &struct_type, serde_version);
     let builder_create_method = get_builder_create_method(&builder_name, &turbofish);
     let builder_new_method = get_builder_new_method(
         &typed_generics,
@@ -52,10 +53,20 @@ pub fn message_impl(
         &untyped_generics,
         &input.generics,
     );
-    let builder_read_method =
-        get_builder_read_method(&struct_name, &fields, &struct_type, &turbofish);
+    let builder_read_method = get_builder_read_method(
+        &struct_name,
+        &fields,
+        &struct_type,
+        &turbofish,
+        serde_version,
+    );
     let is_fragment_method = get_is_fragment_method(is_fragment);
     let is_request_method = get_is_request_method(is_request);
+    let versioned_imports = if serde_version.is_some() {
+        quote! { pub use #shared_crate_name::UnsignedVariableInteger; }
+    } else {
+        quote! {}
+    };
 
     let gen = quote! {
         mod #module_name {
@@ -65,15 +76,16 @@ pub fn message_impl(
                 Named, GlobalEntity, Message, BitWrite, LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut,
                 EntityProperty, MessageKind, MessageKinds, Serde, MessageBuilder, BitReader, SerdeErr, ConstBitLength, MessageContainer, RemoteEntity,
             };
+            #versioned_imports
             use super::*;
 
             struct #builder_name #typed_generics #builder_generic_fields
             #builder_new_method
-            impl #typed_generics MessageBuilder for #builder_name #untyped_generics {
+            impl #typed_generics MessageBuilder for #builder_name #untyped_generics #where_clause {
                 #builder_read_method
             }
 
-            impl #typed_generics Message for #struct_name #untyped_generics {
+            impl #typed_generics Message for #struct_name #untyped_generics #where_clause {
                 fn kind(&self) -> MessageKind {
                     MessageKind::of::<#struct_name #untyped_generics>()
                 }
@@ -88,12 +100,12 @@ pub fn message_impl(
                 #relations_complete_method
                 #write_method
             }
-            impl #typed_generics Named for #struct_name #untyped_generics {
+            impl #typed_generics Named for #struct_name #untyped_generics #where_clause {
                 fn name(&self) -> String {
-                    return #struct_name_str.to_string();
+                    return #struct_name_expr;
                 }
             }
-            impl #typed_generics Clone for #struct_name #untyped_generics {
+            impl #typed_generics Clone for #struct_name #untyped_generics #where_clause {
                 #clone_method
             }
         }
@@ -267,6 +279,7 @@ pub fn get_builder_read_method(
     fields: &[Field],
     struct_type: &StructType,
     turbofish: &TokenStream,
+    serde_version: Option<u32>,
 ) -> TokenStream {
     let mut field_names = quote! {};
     for field in fields.iter() {
@@ -293,8 +306,20 @@ pub fn get_builder_read_method(
             Field::Normal(normal_field) => {
                 let field_name = &normal_field.variable_name;
                 let field_type = &normal_field.field_type;
-                quote! {
-                    let #field_name = <#field_type>::de(reader)?;
+                let since_version = normal_field.since_version;
+                if since_version > 1 {
+                    // fields added after the sender's version are defaulted
+                    quote! {
+                        let #field_name = if sender_version >= #since_version {
+                            <#field_type>::de(reader)?
+                        } else {
+                            <#field_type>::default()
+                        };
+                    }
+                } else {
+                    quote! {
+                        let #field_name = <#field_type>::de(reader)?;
+                    }
                 }
             }
         };
@@ -328,6 +353,29 @@ pub fn get_builder_read_method(
         }
     };
 
+    if serde_version.is_some() {
+        return quote! {
+            fn read(&self, reader: &mut BitReader, converter: &dyn LocalEntityAndGlobalEntityConverter) -> Result<MessageContainer, SerdeErr> {
+                let sender_version = UnsignedVariableInteger::<3>::de(reader)?.get() as u32;
+                let body_bits = UnsignedVariableInteger::<7>::de(reader)?.get() as u32;
+                let body_start = reader.bits_read();
+
+                #field_reads
+
+                let body_read = reader.bits_read() - body_start;
+                if body_read > body_bits {
+                    return Err(SerdeErr);
+                }
+                // skip any trailing fields written by a newer version of this Message
+                for _ in body_read..body_bits {
+                    reader.read_bit()?;
+                }
+
+                return Ok(MessageContainer::from_read(Box::new(#struct_build)));
+            }
+        };
+    }
+
     quote! {
         fn read(&self, reader: &mut BitReader, converter: &dyn LocalEntityAndGlobalEntityConverter) -> Result<MessageContainer, SerdeErr> {
             #field_reads
@@ -337,7 +385,11 @@ pub fn get_builder_read_method(
     }
 }
 
-fn get_write_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
+fn get_write_method(
+    fields: &[Field],
+    struct_type: &StructType,
+    serde_version: Option<u32>,
+) -> TokenStream {
     let mut field_writes = quote! {};
 
     for (index, field) in fields.iter().enumerate() {
@@ -362,15 +414,34 @@ fn get_write_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
         field_writes = new_output_result;
     }
 
+    // versioned Messages are prefixed with their version & the length of their
+    // body, so that readers of a different version know where the body ends
+    let version_prefix = match serde_version {
+        Some(version) => {
+            let field_bit_lengths = get_field_bit_lengths(fields, struct_type);
+            quote! {
+                UnsignedVariableInteger::<3>::new(#version).ser(writer);
+                let body_bits = {
+                    let mut output = 0;
+                    #field_bit_lengths
+                    output
+                };
+                UnsignedVariableInteger::<7>::new(body_bits).ser(writer);
+            }
+        }
+        None => quote! {},
+    };
+
     quote! {
         fn write(&self, message_kinds: &MessageKinds, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
             self.kind().ser(message_kinds, writer);
+            #version_prefix
             #field_writes
         }
     }
 }
 
-fn get_bit_length_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
+fn get_field_bit_lengths(fields: &[Field], struct_type: &StructType) -> TokenStream {
     let mut field_bit_lengths = quote! {};
 
     for (index, field) in fields.iter().enumerate() {
@@ -395,11 +466,30 @@ fn get_bit_length_method(fields: &[Field], struct_type: &StructType) -> TokenStr
         field_bit_lengths = new_output_result;
     }
 
+    field_bit_lengths
+}
+
+fn get_bit_length_method(
+    fields: &[Field],
+    struct_type: &StructType,
+    serde_version: Option<u32>,
+) -> TokenStream {
+    let field_bit_lengths = get_field_bit_lengths(fields, struct_type);
+    let version_prefix_bit_length = match serde_version {
+        Some(version) => quote! {
+            let body_bits = output;
+            output += UnsignedVariableInteger::<3>::new(#version).bit_length();
+            output += UnsignedVariableInteger::<7>::new(body_bits).bit_length();
+        },
+        None => quote! {},
+    };
+
     quote! {
         fn bit_length(&self, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) -> u32 {
             let mut output = 0;
-            output += <MessageKind as ConstBitLength>::const_bit_length();
             #field_bit_lengths
+            #version_prefix_bit_length
+            output += <MessageKind as ConstBitLength>::const_bit_length();
             output
         }
     }
@@ -425,25 +515,34 @@ fn get_fields(input: &DeriveInput) -> Vec<Field> {
             Fields::Named(fields_named) => {
                 for field in fields_named.named.iter() {
                     if let Some(variable_name) = &field.ident {
+                        let since_version = get_serde_version(&field.attrs).unwrap_or(1);
                         match &field.ty {
                             Type::Path(type_path) => {
                                 if let Some(property_seg) = type_path.path.segments.first() {
                                     let property_type = property_seg.ident.clone();
                                     // EntityProperty
                                     if property_type == "EntityProperty" {
-                                        fields.push(Field::entity_property(variable_name.clone()));
+                                        fields.push(Field::entity_property(
+                                            variable_name.clone(),
+                                            since_version,
+                                        ));
                                         continue;
                                         // Property
                                     } else {
                                         fields.push(Field::normal(
                                             variable_name.clone(),
                                             field.ty.clone(),
+                                            since_version,
                                         ));
                                     }
                                 }
                             }
                             _ => {
-                                fields.push(Field::normal(variable_name.clone(), field.ty.clone()));
+                                fields.push(Field::normal(
+                                    variable_name.clone(),
+                                    field.ty.clone(),
+                                    since_version,
+                                ));
                             }
                         }
                     }
@@ -451,16 +550,21 @@ fn get_fields(input: &DeriveInput) -> Vec<Field> {
             }
             Fields::Unnamed(fields_unnamed) => {
                 for (index, field) in fields_unnamed.unnamed.iter().enumerate() {
+                    let since_version = get_serde_version(&field.attrs).unwrap_or(1);
                     if let Type::Path(type_path) = &field.ty {
                         if let Some(property_seg) = type_path.path.segments.first() {
                             let property_type = property_seg.ident.clone();
                             let variable_name =
                                 get_variable_name_for_unnamed_field(index, property_type.span());
                             if property_type == "EntityProperty" {
-                                fields.push(Field::entity_property(variable_name));
+                                fields.push(Field::entity_property(variable_name, since_version));
                                 continue;
                             } else {
-                                fields.push(Field::normal(variable_name, field.ty.clone()))
+                                fields.push(Field::normal(
+                                    variable_name,
+                                    field.ty.clone(),
+                                    since_version,
+                                ))
                             }
                         }
                     }
@@ -475,6 +579,55 @@ fn get_fields(input: &DeriveInput) -> Vec<Field> {
     fields
 }
 
+/// Get the version given by a `#[serde_version(N)]` attribute, if present
+fn get_serde_version(attrs: &[Attribute]) -> Option<u32> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("serde_version"))?;
+    let version = attr
+        .parse_args::<LitInt>()
+        .and_then(|lit| lit.base10_parse::<u32>())
+        .expect("expected an integer version, i.e. `#[serde_version(2)]`");
+    if version == 0 {
+        panic!("`#[serde_version]` starts at 1");
+    }
+    Some(version)
+}
+
+/// Fields added in a later version must be appended after all earlier fields,
+/// so that a reader of an older version can skip them as a trailing block
+fn validate_field_versions(fields: &[Field], serde_version: Option<u32>) {
+    let mut last_version = 1;
+    for field in fields {
+        let since_version = field.since_version();
+        if since_version > 1 {
+            let Some(serde_version) = serde_version else {
+                panic!(
+                    "field `{}` has a `#[serde_version]`, so the Message must also have one",
+                    field.variable_name()
+                );
+            };
+            if since_version > serde_version {
+                panic!(
+                    "field `{}` cannot be newer than the Message's `#[serde_version({})]`",
+                    field.variable_name(),
+                    serde_version
+                );
+            }
+            if let Field::EntityProperty(_) = field {
+                panic!("EntityProperty fields cannot be added in a later `#[serde_version]`");
+            }
+        }
+        if since_version < last_version {
+            panic!(
+                "field `{}` must come before fields added in a later `#[serde_version]`",
+                field.variable_name()
+            );
+        }
+        last_version = since_version;
+    }
+}
+
 /// Get the field name as a TokenStream
 fn get_field_name(field: &Field, index: usize, struct_type: &StructType) -> Member {
     match *struct_type {
@@ -543,11 +696,13 @@ fn get_variable_name_for_unnamed_field(index: usize, span: Span) -> Ident {
 
 pub struct EntityProperty {
     pub variable_name: Ident,
+    pub since_version: u32,
 }
 
 pub struct Normal {
     pub variable_name: Ident,
     pub field_type: Type,
+    pub since_version: u32,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -557,16 +712,18 @@ pub enum Field {
 }
 
 impl Field {
-    pub fn entity_property(variable_name: Ident) -> Self {
+    pub fn entity_property(variable_name: Ident, since_version: u32) -> Self {
         Self::EntityProperty(EntityProperty {
             variable_name: variable_name.clone(),
+            since_version,
         })
     }
 
-    pub fn normal(variable_name: Ident, field_type: Type) -> Self {
+    pub fn normal(variable_name: Ident, field_type: Type, since_version: u32) -> Self {
         Self::Normal(Normal {
             variable_name: variable_name.clone(),
             field_type,
+            since_version,
         })
     }
 
@@ -576,4 +733,11 @@ impl Field {
             Self::Normal(field) => &field.variable_name,
         }
     }
+
+    pub fn since_version(&self) -> u32 {
+        match self {
+            Self::EntityProperty(property) => property.since_version,
+            Self::Normal(field) => field.since_version,
+        }
+    }
 }
@@ -1,13 +1,17 @@
-use proc_macro2::{Punct, Spacing, Span, TokenStream};
+use proc_macro2::{Literal, Punct, Spacing, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Ident, Index, LitStr, Member,
-    PathArguments, Type,
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, ExprLit, ExprUnary, Field,
+    Fields, GenericArgument, Ident, Index, Lit, LitInt, Member, Meta, PathArguments, PathSegment,
+    Type, UnOp,
 };
 
 use crate::{
     message::get_builder_new_method,
-    shared::{get_builder_generic_fields, get_generics, get_struct_type, StructType},
+    shared::{
+        get_builder_generic_fields, get_generics, get_name_expr, get_struct_type, get_where_clause,
+        validate_generics, StructType,
+    },
 };
 
 const UNNAMED_FIELD_PREFIX: &'static str = "unnamed_field_";
@@ -17,6 +21,18 @@ pub struct NormalProperty {
     pub inner_type: Type,
     pub uppercase_variable_name: Ident,
     pub index: usize,
+    /// The `Quantization` of a `Property<f32>` marked with `#[quantize(..)]`
+    /// or `#[angle]`
+    pub quantization: Option<TokenStream>,
+    /// The `SIGNED, BITS` of an integer Property marked with `#[bits(N)]`
+    pub bits: Option<TokenStream>,
+}
+
+pub struct CollectionProperty {
+    pub variable_name: Ident,
+    pub property_type: Type,
+    pub uppercase_variable_name: Ident,
+    pub index: usize,
 }
 
 pub struct EntityProperty {
@@ -33,6 +49,8 @@ pub struct NonReplicatedProperty {
 #[allow(clippy::large_enum_variant)]
 pub enum Property {
     Normal(NormalProperty),
+    Enum(NormalProperty),
+    Collection(CollectionProperty),
     Entity(EntityProperty),
     NonReplicated(NonReplicatedProperty),
 }
@@ -42,23 +60,26 @@ pub fn replicate_impl(
     shared_crate_name: TokenStream,
 ) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    if let Err(error) = validate_generics(&input.generics, "Replicate") {
+        return error.to_compile_error().into();
+    }
 
     // Helper Properties
-    let properties = get_properties(&input);
+    let properties = match get_properties(&input) {
+        Ok(properties) => properties,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let full_update = match get_full_update(&input.attrs) {
+        Ok(full_update) => full_update,
+        Err(error) => return error.to_compile_error().into(),
+    };
     let struct_type = get_struct_type(&input);
     let (untyped_generics, typed_generics, turbofish) = get_generics(&input);
+    let where_clause = get_where_clause(&input.generics, &shared_crate_name);
 
     // Names
     let replica_name = input.ident.clone();
-    let replica_name_str = LitStr::new(
-        format!(
-            "{}{}",
-            &replica_name.to_string(),
-            &untyped_generics.to_string()
-        )
-        .as_str(),
-        replica_name.span(),
-    );
+    let replica_name_expr = get_name_expr(&replica_name, &input.generics);
     let lowercase_replica_name = Ident::new(
         replica_name.to_string().to_lowercase().as_str(),
         Span::call_site(),
@@ -70,14 +91,8 @@ pub fn replicate_impl(
 
     // Definitions
     let property_enum_definition = get_property_enum_definition(&enum_name, &properties);
-    let diff_mask_size: u8 = {
-        let len = properties.len();
-        if len == 0 {
-            0_u8
-        } else {
-            (((len - 1) / 8) + 1) as u8
-        }
-    };
+    let diff_mask_size = get_diff_mask_size(&properties);
+    let property_count = properties.len();
 
     // Methods
     let new_complete_method = get_new_complete_method(&enum_name, &properties, &struct_type);
@@ -105,6 +120,11 @@ pub fn replicate_impl(
         get_enable_delegation_method(&enum_name, &properties, &struct_type);
     let disable_delegation_method = get_disable_delegation_method(&properties, &struct_type);
     let localize_method = get_localize_method(&properties, &struct_type);
+    let set_if_changed_method = get_set_if_changed_method(&properties, &struct_type);
+    let set_defer_mutations_method = get_set_defer_mutations_method(&properties, &struct_type);
+    let flush_deferred_mutations_method =
+        get_flush_deferred_mutations_method(&properties, &struct_type);
+    let set_field_authority_method = get_set_field_authority_method(&properties, &struct_type);
     let read_apply_update_method = get_read_apply_update_method(&properties, &struct_type);
     let read_apply_field_update_method =
         get_read_apply_field_update_method(&properties, &struct_type);
@@ -114,6 +134,8 @@ pub fn replicate_impl(
     let relations_complete_method = get_relations_complete_method(&properties, &struct_type);
     let split_update_method =
         get_split_update_method(&replica_name, &properties, &untyped_generics);
+    let merge_updates_method =
+        get_merge_updates_method(&replica_name, &properties, &untyped_generics);
 
     let gen = quote! {
         mod #module_name {
@@ -122,8 +144,9 @@ pub fn replicate_impl(
             use #shared_crate_name::{
                 DiffMask, PropertyMutate, PropertyMutator, ComponentUpdate,
                 ReplicaDynRef, ReplicaDynMut, LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, ComponentKind, Named,
-                BitReader, BitWrite, BitWriter, OwnedBitReader, SerdeErr, Serde, EntityAuthAccessor, RemoteEntity,
-                EntityProperty, GlobalEntity, Replicate, Property, ComponentKinds, ReplicateBuilder, ComponentFieldUpdate,
+                BitReader, BitWrite, BitWriter, OwnedBitReader, SerdeErr, Serde, EntityAuthAccessor, RemoteEntity, MTU_SIZE_BITS,
+                EntityProperty, EnumProperty, MapProperty, VecProperty, GlobalEntity, Replicate, Property, ComponentKinds, ReplicateBuilder, ComponentFieldUpdate,
+                Quantization,
             };
             use super::*;
 
@@ -131,26 +154,34 @@ pub fn replicate_impl(
 
             struct #builder_name #typed_generics #builder_generic_fields
             #builder_new_method
-            impl #typed_generics ReplicateBuilder for #builder_name #untyped_generics {
+            impl #typed_generics ReplicateBuilder for #builder_name #untyped_generics #where_clause {
                 #builder_read_method
                 #read_create_update_method
                 #split_update_method
+                #merge_updates_method
+                fn full_update(&self) -> bool {
+                    #full_update
+                }
+                fn property_count(&self) -> usize {
+                    #property_count
+                }
             }
-            impl #typed_generics Named for #builder_name #untyped_generics {
+            impl #typed_generics Named for #builder_name #untyped_generics #where_clause {
                 fn name(&self) -> String {
-                    return #replica_name_str.to_string();
+                    return #replica_name_expr;
                 }
             }
 
-            impl #typed_generics #replica_name #untyped_generics {
+            impl #typed_generics #replica_name #untyped_generics #where_clause {
                 #new_complete_method
+                #set_if_changed_method
             }
-            impl #typed_generics Named for #replica_name #untyped_generics {
+            impl #typed_generics Named for #replica_name #untyped_generics #where_clause {
                 fn name(&self) -> String {
-                    return #replica_name_str.to_string();
+                    return #replica_name_expr;
                 }
             }
-            impl #typed_generics Replicate for #replica_name #untyped_generics {
+            impl #typed_generics Replicate for #replica_name #untyped_generics #where_clause {
                 fn kind(&self) -> ComponentKind {
                     ComponentKind::of::<#replica_name #untyped_generics>()
                 }
@@ -176,6 +207,9 @@ pub fn replicate_impl(
                 #enable_delegation_method
                 #disable_delegation_method
                 #localize_method
+                #set_defer_mutations_method
+                #flush_deferred_mutations_method
+                #set_field_authority_method
                 #set_mutator_method
                 #write_method
                 #write_update_method
@@ -184,7 +218,7 @@ pub fn replicate_impl(
                 #relations_waiting_method
                 #relations_complete_method
             }
-            impl #typed_generics Clone for #replica_name #untyped_generics {
+            impl #typed_generics Clone for #replica_name #untyped_generics #where_clause {
                 #clone_method
             }
         }
@@ -193,6 +227,64 @@ pub fn replicate_impl(
     proc_macro::TokenStream::from(gen)
 }
 
+impl NormalProperty {
+    /// `Property::<T>::new_read(reader)`, or its quantized or bit-packed
+    /// equivalent
+    fn new_read(&self) -> TokenStream {
+        let field_type = &self.inner_type;
+        match (&self.quantization, &self.bits) {
+            (Some(quantization), _) => quote! {
+                Property::<#field_type>::new_read_quantized(reader, &#quantization)
+            },
+            (None, Some(bits)) => quote! {
+                Property::<#field_type>::new_read_bits::<#bits>(reader)
+            },
+            (None, None) => quote! { Property::<#field_type>::new_read(reader) },
+        }
+    }
+
+    /// A callable taking a reader & a writer, which copies a value of this
+    /// Property from one to the other
+    fn read_write(&self) -> TokenStream {
+        let field_type = &self.inner_type;
+        match (&self.quantization, &self.bits) {
+            (Some(quantization), _) => quote! { (#quantization).read_write },
+            (None, Some(bits)) => quote! { Property::<#field_type>::read_write_bits::<#bits> },
+            (None, None) => quote! { Property::<#field_type>::read_write },
+        }
+    }
+
+    /// Updates the Property in the given field from `reader`
+    fn read(&self, field_name: &Member) -> TokenStream {
+        match (&self.quantization, &self.bits) {
+            (Some(quantization), _) => quote! {
+                Property::read_quantized(&mut self.#field_name, reader, &#quantization)?;
+            },
+            (None, Some(bits)) => quote! {
+                Property::read_bits::<#bits>(&mut self.#field_name, reader)?;
+            },
+            (None, None) => quote! {
+                Property::read(&mut self.#field_name, reader)?;
+            },
+        }
+    }
+
+    /// Writes the Property in the given field to `writer`
+    fn write(&self, field_name: &Member) -> TokenStream {
+        match (&self.quantization, &self.bits) {
+            (Some(quantization), _) => quote! {
+                Property::write_quantized(&self.#field_name, writer, &#quantization);
+            },
+            (None, Some(bits)) => quote! {
+                Property::write_bits::<#bits>(&self.#field_name, writer);
+            },
+            (None, None) => quote! {
+                Property::write(&self.#field_name, writer);
+            },
+        }
+    }
+}
+
 /// Create a variable name for unnamed fields
 fn get_variable_name_for_unnamed_field(index: usize, span: Span) -> Ident {
     Ident::new(&format!("{}{}", UNNAMED_FIELD_PREFIX, index), span)
@@ -225,6 +317,34 @@ impl Property {
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
             ),
+            quantization: None,
+            bits: None,
+        })
+    }
+
+    pub fn enumeration(index: usize, variable_name: Ident, inner_type: Type) -> Self {
+        Self::Enum(NormalProperty {
+            index,
+            variable_name: variable_name.clone(),
+            inner_type,
+            uppercase_variable_name: Ident::new(
+                variable_name.to_string().to_uppercase().as_str(),
+                Span::call_site(),
+            ),
+            quantization: None,
+            bits: None,
+        })
+    }
+
+    pub fn collection(index: usize, variable_name: Ident, property_type: Type) -> Self {
+        Self::Collection(CollectionProperty {
+            index,
+            variable_name: variable_name.clone(),
+            property_type,
+            uppercase_variable_name: Ident::new(
+                variable_name.to_string().to_uppercase().as_str(),
+                Span::call_site(),
+            ),
         })
     }
 
@@ -248,14 +368,15 @@ impl Property {
 
     pub fn is_replicated(&self) -> bool {
         match self {
-            Self::Normal(_) | Self::Entity(_) => true,
+            Self::Normal(_) | Self::Enum(_) | Self::Collection(_) | Self::Entity(_) => true,
             Self::NonReplicated(_) => false,
         }
     }
 
     pub fn variable_name(&self) -> &Ident {
         match self {
-            Self::Normal(property) => &property.variable_name,
+            Self::Normal(property) | Self::Enum(property) => &property.variable_name,
+            Self::Collection(property) => &property.variable_name,
             Self::Entity(property) => &property.variable_name,
             Self::NonReplicated(property) => &property.variable_name,
         }
@@ -263,7 +384,8 @@ impl Property {
 
     pub fn uppercase_variable_name(&self) -> &Ident {
         match self {
-            Self::Normal(property) => &property.uppercase_variable_name,
+            Self::Normal(property) | Self::Enum(property) => &property.uppercase_variable_name,
+            Self::Collection(property) => &property.uppercase_variable_name,
             Self::Entity(property) => &property.uppercase_variable_name,
             Self::NonReplicated(_) => panic!("Unused for non-replicated properties"),
         }
@@ -271,94 +393,443 @@ impl Property {
 
     pub fn index(&self) -> usize {
         match self {
-            Self::Normal(property) => property.index,
+            Self::Normal(property) | Self::Enum(property) => property.index,
+            Self::Collection(property) => property.index,
             Self::Entity(property) => property.index,
             Self::NonReplicated(_) => panic!("Unused for non-replicated properties"),
         }
     }
 }
 
-fn get_properties(input: &DeriveInput) -> Vec<Property> {
+fn get_properties(input: &DeriveInput) -> Result<Vec<Property>, Error> {
     let mut fields = Vec::new();
 
-    if let Data::Struct(data_struct) = &input.data {
-        match &data_struct.fields {
-            Fields::Named(fields_named) => {
-                for field in fields_named.named.iter() {
-                    if let Some(variable_name) = &field.ident {
-                        if let Type::Path(type_path) = &field.ty {
-                            if let Some(property_seg) = type_path.path.segments.first() {
-                                let property_type = property_seg.ident.clone();
-                                // EntityProperty
-                                if property_type == "EntityProperty" {
-                                    fields.push(Property::entity(
-                                        fields.len(),
-                                        variable_name.clone(),
-                                    ));
-                                    continue;
-                                // Property
-                                } else if property_type == "Property" {
-                                    if let PathArguments::AngleBracketed(angle_args) =
-                                        &property_seg.arguments
-                                    {
-                                        if let Some(GenericArgument::Type(inner_type)) =
-                                            angle_args.args.first()
-                                        {
-                                            fields.push(Property::normal(
-                                                fields.len(),
-                                                variable_name.clone(),
-                                                inner_type.clone(),
-                                            ));
-                                            continue;
-                                        }
-                                    }
-                                // Non-replicated Property
-                                } else {
-                                    fields.push(Property::nonreplicated(
-                                        variable_name.clone(),
-                                        field.ty.clone(),
-                                    ));
-                                }
+    let Data::Struct(data_struct) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Replicate can only be derived for structs",
+        ));
+    };
+
+    match &data_struct.fields {
+        Fields::Named(fields_named) => {
+            for field in fields_named.named.iter() {
+                let quantization = get_quantization(&field.attrs)?;
+                let bits = get_bits(&field.attrs)?;
+                let fields_before = fields.len();
+                if let Some(variable_name) = &field.ident {
+                    if let Type::Path(type_path) = &field.ty {
+                        if let Some(property_seg) = type_path.path.segments.first() {
+                            check_not_optional_property(property_seg)?;
+                            let property_type = property_seg.ident.clone();
+                            // EntityProperty
+                            if property_type == "EntityProperty" {
+                                fields.push(Property::entity(fields.len(), variable_name.clone()));
+                            // EnumProperty
+                            } else if property_type == "EnumProperty" {
+                                let inner_type = get_inner_type(property_seg)?;
+                                fields.push(Property::enumeration(
+                                    fields.len(),
+                                    variable_name.clone(),
+                                    inner_type,
+                                ));
+                            // MapProperty & VecProperty
+                            } else if property_type == "MapProperty"
+                                || property_type == "VecProperty"
+                            {
+                                get_inner_type(property_seg)?;
+                                fields.push(Property::collection(
+                                    fields.len(),
+                                    variable_name.clone(),
+                                    field.ty.clone(),
+                                ));
+                            // Property
+                            } else if property_type == "Property" {
+                                let inner_type = get_inner_type(property_seg)?;
+                                fields.push(Property::normal(
+                                    fields.len(),
+                                    variable_name.clone(),
+                                    inner_type,
+                                ));
+                            // Non-replicated Property
+                            } else {
+                                fields.push(Property::nonreplicated(
+                                    variable_name.clone(),
+                                    field.ty.clone(),
+                                ));
                             }
                         }
                     }
                 }
+                if let Some(quantization) = quantization {
+                    set_quantization(&mut fields[fields_before..], field, quantization)?;
+                }
+                if let Some((bits_attr, bits)) = bits {
+                    set_bits(&mut fields[fields_before..], field, bits_attr, bits)?;
+                }
             }
-            Fields::Unnamed(fields_unnamed) => {
-                for (index, field) in fields_unnamed.unnamed.iter().enumerate() {
-                    if let Type::Path(type_path) = &field.ty {
-                        if let Some(property_seg) = type_path.path.segments.first() {
-                            let property_type = property_seg.ident.clone();
-                            let variable_name =
-                                get_variable_name_for_unnamed_field(index, property_type.span());
-                            if property_type == "EntityProperty" {
-                                fields.push(Property::entity(fields.len(), variable_name));
-                                continue;
-                            } else if let PathArguments::AngleBracketed(angle_args) =
-                                &property_seg.arguments
+        }
+        Fields::Unnamed(fields_unnamed) => {
+            for (index, field) in fields_unnamed.unnamed.iter().enumerate() {
+                let quantization = get_quantization(&field.attrs)?;
+                let bits = get_bits(&field.attrs)?;
+                let fields_before = fields.len();
+                if let Type::Path(type_path) = &field.ty {
+                    if let Some(property_seg) = type_path.path.segments.first() {
+                        check_not_optional_property(property_seg)?;
+                        let property_type = property_seg.ident.clone();
+                        let variable_name =
+                            get_variable_name_for_unnamed_field(index, property_type.span());
+                        if property_type == "EntityProperty" {
+                            fields.push(Property::entity(fields.len(), variable_name));
+                        } else if property_type == "EnumProperty" {
+                            let inner_type = get_inner_type(property_seg)?;
+                            fields.push(Property::enumeration(
+                                fields.len(),
+                                variable_name,
+                                inner_type,
+                            ));
+                        } else if property_type == "MapProperty" || property_type == "VecProperty" {
+                            get_inner_type(property_seg)?;
+                            fields.push(Property::collection(
+                                fields.len(),
+                                variable_name,
+                                field.ty.clone(),
+                            ));
+                        } else if let PathArguments::AngleBracketed(angle_args) =
+                            &property_seg.arguments
+                        {
+                            if let Some(GenericArgument::Type(inner_type)) = angle_args.args.first()
                             {
-                                if let Some(GenericArgument::Type(inner_type)) =
-                                    angle_args.args.first()
-                                {
-                                    fields.push(Property::normal(
-                                        fields.len(),
-                                        variable_name,
-                                        inner_type.clone(),
-                                    ));
-                                    continue;
-                                }
+                                fields.push(Property::normal(
+                                    fields.len(),
+                                    variable_name,
+                                    inner_type.clone(),
+                                ));
                             }
                         }
                     }
                 }
+                if let Some(quantization) = quantization {
+                    set_quantization(&mut fields[fields_before..], field, quantization)?;
+                }
+                if let Some((bits_attr, bits)) = bits {
+                    set_bits(&mut fields[fields_before..], field, bits_attr, bits)?;
+                }
+            }
+        }
+        Fields::Unit => {}
+    }
+
+    Ok(fields)
+}
+
+/// Whether the struct is marked with `#[replicate(full_update)]`
+fn get_full_update(attrs: &[Attribute]) -> Result<bool, Error> {
+    let mut full_update = false;
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("replicate"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("full_update") {
+                full_update = true;
+                return Ok(());
+            }
+            Err(meta.error("expected `full_update`"))
+        })?;
+    }
+    Ok(full_update)
+}
+
+/// The `Quantization` of a field marked with
+/// `#[quantize(min = .., max = .., precision = ..)]` or `#[angle]` /
+/// `#[angle(precision = ..)]`. The number of bits is worked out here, so that
+/// it is known at compile time
+fn get_quantization(attrs: &[Attribute]) -> Result<Option<TokenStream>, Error> {
+    let mut quantization = None;
+    for attr in attrs {
+        let is_angle = attr.path().is_ident("angle");
+        if !is_angle && !attr.path().is_ident("quantize") {
+            continue;
+        }
+        if quantization.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                "a field can only be quantized once",
+            ));
+        }
+
+        let mut min = None;
+        let mut max = None;
+        let mut precision = None;
+        if !is_angle || matches!(attr.meta, Meta::List(_)) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("precision") {
+                    &mut precision
+                } else if !is_angle && meta.path.is_ident("min") {
+                    &mut min
+                } else if !is_angle && meta.path.is_ident("max") {
+                    &mut max
+                } else if is_angle {
+                    return Err(meta.error("expected `precision`"));
+                } else {
+                    return Err(meta.error("expected `min`, `max` or `precision`"));
+                };
+                *slot = Some(parse_number(&meta.value()?.parse::<Expr>()?)?);
+                Ok(())
+            })?;
+        }
+
+        let (min, max) = if is_angle {
+            (0.0, std::f64::consts::TAU)
+        } else {
+            let (Some(min), Some(max)) = (min, max) else {
+                return Err(Error::new_spanned(attr, "expected both `min` and `max`"));
+            };
+            (min, max)
+        };
+        // angles default to about a twentieth of a degree
+        let Some(precision) = precision.or(is_angle.then_some(0.001)) else {
+            return Err(Error::new_spanned(attr, "expected `precision`"));
+        };
+        if min >= max {
+            return Err(Error::new_spanned(attr, "`min` must be less than `max`"));
+        }
+        if precision <= 0.0 {
+            return Err(Error::new_spanned(attr, "`precision` must be positive"));
+        }
+
+        // the fewest bits with at least as many levels as the range has steps
+        let steps = ((max - min) / precision).ceil();
+        let mut bits: u8 = 1;
+        while (((1u64 << bits) - 1) as f64) < steps {
+            bits += 1;
+            if bits > 32 {
+                return Err(Error::new_spanned(
+                    attr,
+                    "the range is too large for the precision, it would take more than 32 bits",
+                ));
             }
-            Fields::Unit => {}
         }
-    } else {
-        panic!("Can only derive Replicate on a struct");
+
+        quantization = Some(if is_angle {
+            quote! { Quantization::angle(#bits) }
+        } else {
+            let min = Literal::f32_suffixed(min as f32);
+            let max = Literal::f32_suffixed(max as f32);
+            quote! { Quantization::new(#min, #max, #bits) }
+        });
+    }
+    Ok(quantization)
+}
+
+/// The width given by a `#[bits(N)]` attribute, if present, with the
+/// attribute itself so that errors can point at it
+fn get_bits(attrs: &[Attribute]) -> Result<Option<(&Attribute, u32)>, Error> {
+    let mut output = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("bits")) {
+        if output.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                "a field can only have one `#[bits(..)]`",
+            ));
+        }
+        let bits: u32 = attr.parse_args::<LitInt>()?.base10_parse()?;
+        output = Some((attr, bits));
     }
+    Ok(output)
+}
 
-    fields
+/// Bit-packs the Property read for a field, which must be a `Property<T>` of
+/// an integer type wide enough for the bits. Signed Properties spend one of
+/// their bits on the sign
+fn set_bits(
+    field_properties: &mut [Property],
+    field: &Field,
+    attr: &Attribute,
+    bits: u32,
+) -> Result<(), Error> {
+    const ERROR: &str =
+        "only `Property<T>` fields of u8, u16, u32, u64, i8, i16, i32 or i64 can be bit-packed";
+    let [Property::Normal(property)] = field_properties else {
+        return Err(Error::new_spanned(field, ERROR));
+    };
+    if property.quantization.is_some() {
+        return Err(Error::new_spanned(
+            attr,
+            "a field can't be both quantized & bit-packed",
+        ));
+    }
+    let type_name = match &property.inner_type {
+        Type::Path(type_path) => type_path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    };
+    let (signed, width) = match type_name.as_deref() {
+        Some("u8") => (false, 8),
+        Some("u16") => (false, 16),
+        Some("u32") => (false, 32),
+        Some("u64") => (false, 64),
+        Some("i8") => (true, 8),
+        Some("i16") => (true, 16),
+        Some("i32") => (true, 32),
+        Some("i64") => (true, 64),
+        _ => return Err(Error::new_spanned(field, ERROR)),
+    };
+    let min_bits = if signed { 2 } else { 1 };
+    if bits < min_bits || bits > width {
+        return Err(Error::new_spanned(
+            attr,
+            format!(
+                "`{}` fields can only be written in {}..={} bits",
+                type_name.unwrap(),
+                min_bits,
+                width
+            ),
+        ));
+    }
+    let value_bits = (if signed { bits - 1 } else { bits }) as u8;
+    property.bits = Some(quote! { #signed, #value_bits });
+    Ok(())
+}
+
+/// A number literal, which may be negative
+fn parse_number(expr: &Expr) -> Result<f64, Error> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.base10_parse(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => Ok(-parse_number(expr)?),
+        _ => Err(Error::new_spanned(expr, "expected a number")),
+    }
+}
+
+/// Quantizes the Property read for a field, which must be a `Property<f32>`
+fn set_quantization(
+    field_properties: &mut [Property],
+    field: &Field,
+    quantization: TokenStream,
+) -> Result<(), Error> {
+    let [Property::Normal(property)] = field_properties else {
+        return Err(Error::new_spanned(
+            field,
+            "only `Property<f32>` fields can be quantized",
+        ));
+    };
+    if !matches!(&property.inner_type, Type::Path(type_path) if type_path.path.is_ident("f32")) {
+        return Err(Error::new_spanned(
+            field,
+            "only `Property<f32>` fields can be quantized",
+        ));
+    }
+    property.quantization = Some(quantization);
+    Ok(())
+}
+
+/// An `Option<Property<T>>` field would silently go unreplicated, so point
+/// towards `Property<Option<T>>` instead
+fn check_not_optional_property(property_seg: &PathSegment) -> Result<(), Error> {
+    if property_seg.ident != "Option" {
+        return Ok(());
+    }
+    let Ok(Type::Path(inner_path)) = get_inner_type(property_seg) else {
+        return Ok(());
+    };
+    let Some(inner_seg) = inner_path.path.segments.first() else {
+        return Ok(());
+    };
+    if inner_seg.ident == "Property"
+        || inner_seg.ident == "EnumProperty"
+        || inner_seg.ident == "MapProperty"
+        || inner_seg.ident == "VecProperty"
+    {
+        return Err(Error::new_spanned(
+            property_seg,
+            format!(
+                "`Option<{}<T>>` fields are not replicated, use `Property<Option<T>>` instead",
+                inner_seg.ident
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Get the `T` of a `Property<T>` or `EnumProperty<T>` field, or the first
+/// type of any other Property
+fn get_inner_type(property_seg: &PathSegment) -> Result<Type, Error> {
+    if let PathArguments::AngleBracketed(angle_args) = &property_seg.arguments {
+        if let Some(GenericArgument::Type(inner_type)) = angle_args.args.first() {
+            return Ok(inner_type.clone());
+        }
+    }
+    Err(Error::new_spanned(
+        property_seg,
+        format!(
+            "expected `{}<T>`, where T is the type of the value to replicate",
+            property_seg.ident
+        ),
+    ))
+}
+
+/// The number of DiffMask bits used by a Property
+fn get_diff_bits(property: &Property) -> TokenStream {
+    match property {
+        Property::Enum(property) => {
+            let inner_type = &property.inner_type;
+            quote! { EnumProperty::<#inner_type>::DIFF_BITS }
+        }
+        Property::Collection(property) => {
+            let property_type = &property.property_type;
+            quote! { <#property_type>::DIFF_BITS }
+        }
+        Property::Normal(_) | Property::Entity(_) | Property::NonReplicated(_) => quote! { 1 },
+    }
+}
+
+/// EnumProperty, MapProperty & VecProperty use several DiffMask bits, so each
+/// Property's first bit is its index offset by the extra bits of any of these
+/// before it
+fn get_diff_mask_offsets(properties: &[Property]) -> Vec<TokenStream> {
+    let mut extra_bits = quote! {};
+    let mut offsets = Vec::new();
+    for property in properties.iter().filter(|p| p.is_replicated()) {
+        let index = property.index() as u8;
+        offsets.push(quote! { #index #extra_bits });
+        if let Property::Enum(_) | Property::Collection(_) = property {
+            let diff_bits = get_diff_bits(property);
+            extra_bits = quote! { #extra_bits + (#diff_bits - 1) };
+        }
+    }
+    offsets
+}
+
+fn get_diff_mask_size(properties: &[Property]) -> TokenStream {
+    let len = properties.len();
+    if len == 0 {
+        return quote! { 0 };
+    }
+    if !properties
+        .iter()
+        .any(|p| matches!(p, Property::Enum(_) | Property::Collection(_)))
+    {
+        let diff_mask_size = (((len - 1) / 8) + 1) as u8;
+        return quote! { #diff_mask_size };
+    }
+    let diff_bits = properties.iter().map(get_diff_bits);
+    quote! {
+        {
+            let bits: usize = 0 #(+ (#diff_bits as usize))*;
+            (((bits - 1) / 8) + 1) as u8
+        }
+    }
 }
 
 fn get_property_enum_definition(enum_name: &Ident, properties: &[Property]) -> TokenStream {
@@ -372,12 +843,13 @@ fn get_property_enum_definition(enum_name: &Ident, properties: &[Property]) -> T
 
     let mut variant_list = quote! {};
 
-    for (_, property) in properties.iter().filter(|p| p.is_replicated()).enumerate() {
-        let index = syn::Index::from(property.index());
+    let offsets = get_diff_mask_offsets(properties);
+
+    for (property, offset) in properties.iter().filter(|p| p.is_replicated()).zip(offsets) {
         let uppercase_variant_name = property.uppercase_variable_name();
 
         let new_output_right = quote! {
-            #uppercase_variant_name = #index as u8,
+            #uppercase_variant_name = (#offset) as u8,
         };
         let new_output_result = quote! {
             #variant_list
@@ -417,7 +889,7 @@ fn get_clone_method(properties: &[Property], struct_type: &StructType) -> TokenS
     for property in properties.iter() {
         let field_name = get_field_name(property, struct_type);
         match property {
-            Property::Normal(_) => {
+            Property::Normal(_) | Property::Enum(_) | Property::Collection(_) => {
                 let new_output_right = quote! {
                     (*self.#field_name).clone(),
                 };
@@ -610,6 +1082,31 @@ fn get_disable_delegation_method(properties: &[Property], struct_type: &StructTy
     }
 }
 
+fn get_set_field_authority_method(
+    properties: &[Property],
+    struct_type: &StructType,
+) -> TokenStream {
+    let mut output = quote! {};
+
+    for property in properties.iter().filter(|p| p.is_replicated()) {
+        let field_name = get_field_name(property, struct_type);
+        let new_output_right = quote! {
+                self.#field_name.set_field_authority(field_authority);
+        };
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        fn set_field_authority(&mut self, field_authority: Option<&DiffMask>) {
+            #output
+        }
+    }
+}
+
 fn get_localize_method(properties: &[Property], struct_type: &StructType) -> TokenStream {
     let mut output = quote! {};
 
@@ -632,6 +1129,93 @@ fn get_localize_method(properties: &[Property], struct_type: &StructType) -> Tok
     }
 }
 
+fn get_set_if_changed_method(properties: &[Property], struct_type: &StructType) -> TokenStream {
+    let mut output = quote! {};
+
+    for property in properties.iter().filter(|p| p.is_replicated()) {
+        let field_name = get_field_name(property, struct_type);
+        let new_output_right = quote! {
+                changed |= self.#field_name.set_if_changed(&other.#field_name);
+        };
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        /// Sets each Property to the value of the same Property of `other`,
+        /// only where they differ, so that updates are only queued for actual
+        /// changes. Returns whether any Property changed
+        pub fn set_if_changed(&mut self, other: &Self) -> bool {
+            let mut changed = false;
+            #output
+            changed
+        }
+    }
+}
+
+fn get_set_defer_mutations_method(
+    properties: &[Property],
+    struct_type: &StructType,
+) -> TokenStream {
+    let mut output = quote! {};
+
+    // only Normal Properties can be written through DerefMut, the others
+    // already compare against their previous value when set
+    for property in properties.iter() {
+        let Property::Normal(_) = property else {
+            continue;
+        };
+        let field_name = get_field_name(property, struct_type);
+        let new_output_right = quote! {
+                self.#field_name.set_defer_mutations(defer_mutations);
+        };
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        fn set_defer_mutations(&mut self, defer_mutations: bool) {
+            #output
+        }
+    }
+}
+
+fn get_flush_deferred_mutations_method(
+    properties: &[Property],
+    struct_type: &StructType,
+) -> TokenStream {
+    let mut output = quote! {};
+
+    for property in properties.iter() {
+        let Property::Normal(_) = property else {
+            continue;
+        };
+        let field_name = get_field_name(property, struct_type);
+        let new_output_right = quote! {
+                mutated |= self.#field_name.flush_deferred_mutation();
+        };
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        fn flush_deferred_mutations(&mut self) -> bool {
+            let mut mutated = false;
+            #output
+            mutated
+        }
+    }
+}
+
 pub fn get_new_complete_method(
     enum_name: &Ident,
     properties: &[Property],
@@ -640,7 +1224,7 @@ pub fn get_new_complete_method(
     let mut args = quote! {};
     for property in properties.iter() {
         match property {
-            Property::Normal(property) => {
+            Property::Normal(property) | Property::Enum(property) => {
                 let field_name = &property.variable_name;
                 let field_type = &property.inner_type;
 
@@ -653,6 +1237,19 @@ pub fn get_new_complete_method(
                 };
                 args = new_output_result;
             }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let property_type = &property.property_type;
+
+                let new_output_right = quote! {
+                    #field_name: <#property_type as std::ops::Deref>::Target,
+                };
+
+                let new_output_result = quote! {
+                    #args #new_output_right
+                };
+                args = new_output_result;
+            }
             Property::NonReplicated(property) => {
                 let field_name = &property.variable_name;
                 let field_type = &property.field_type;
@@ -696,6 +1293,48 @@ pub fn get_new_complete_method(
                     }
                 }
             }
+            Property::Enum(property) => {
+                let field_name = &property.variable_name;
+                let field_type = &property.inner_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+
+                match *struct_type {
+                    StructType::Struct => {
+                        quote! {
+                            #field_name: EnumProperty::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                        }
+                    }
+                    StructType::TupleStruct => {
+                        quote! {
+                            EnumProperty::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                        }
+                    }
+                    _ => {
+                        quote! {}
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let property_type = &property.property_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+
+                match *struct_type {
+                    StructType::Struct => {
+                        quote! {
+                            #field_name: <#property_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                        }
+                    }
+                    StructType::TupleStruct => {
+                        quote! {
+                            <#property_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                        }
+                    }
+                    _ => {
+                        quote! {}
+                    }
+                }
+            }
             Property::Entity(property) => {
                 let field_name = &property.variable_name;
                 let uppercase_variant_name = &property.uppercase_variable_name;
@@ -808,9 +1447,21 @@ pub fn get_builder_read_method(
         let field_name = property.variable_name();
         let new_output_right = match property {
             Property::Normal(inner_property) => {
+                let new_read = inner_property.new_read();
+                quote! {
+                    let #field_name = #new_read?;
+                }
+            }
+            Property::Enum(inner_property) => {
                 let field_type = &inner_property.inner_type;
                 quote! {
-                    let #field_name = Property::<#field_type>::new_read(reader)?;
+                    let #field_name = EnumProperty::<#field_type>::new_read(reader)?;
+                }
+            }
+            Property::Collection(inner_property) => {
+                let property_type = &inner_property.property_type;
+                quote! {
+                    let #field_name = <#property_type>::new_read(reader)?;
                 }
             }
             Property::Entity(_) => {
@@ -874,13 +1525,37 @@ pub fn get_read_create_update_method(
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(inner_property) => {
+                let read_write = inner_property.read_write();
+                quote! {
+                    {
+                        let should_read = bool::de(reader)?;
+                        should_read.ser(&mut update_writer);
+                        if should_read {
+                            #read_write(reader, &mut update_writer)?;
+                        }
+                    }
+                }
+            }
+            Property::Enum(inner_property) => {
                 let field_type = &inner_property.inner_type;
                 quote! {
                     {
                         let should_read = bool::de(reader)?;
                         should_read.ser(&mut update_writer);
                         if should_read {
-                            Property::<#field_type>::read_write(reader, &mut update_writer)?;
+                            EnumProperty::<#field_type>::read_write(reader, &mut update_writer)?;
+                        }
+                    }
+                }
+            }
+            Property::Collection(inner_property) => {
+                let property_type = &inner_property.property_type;
+                quote! {
+                    {
+                        let should_read = bool::de(reader)?;
+                        should_read.ser(&mut update_writer);
+                        if should_read {
+                            <#property_type>::read_write(reader, &mut update_writer)?;
                         }
                     }
                 }
@@ -911,6 +1586,12 @@ pub fn get_read_create_update_method(
     quote! {
         fn read_create_update(&self, reader: &mut BitReader) -> Result<ComponentUpdate, SerdeErr> {
 
+            // the update is copied bit for bit, so it can only overflow the writer
+            // if it is read from a packet larger than any Host would send
+            if reader.bits_remaining() > MTU_SIZE_BITS {
+                return Err(SerdeErr);
+            }
+
             let mut update_writer = BitWriter::new();
 
             #prop_read_writes
@@ -932,12 +1613,34 @@ fn get_split_update_method(
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(inner_property) => {
+                let read_write = inner_property.read_write();
+                quote! {
+                    let should_read = bool::de(reader)?;
+                    should_read.ser(&mut ready_writer);
+                    if should_read {
+                        #read_write(reader, &mut ready_writer)?;
+                        ready_did_write = true;
+                    }
+                }
+            }
+            Property::Enum(inner_property) => {
                 let field_type = &inner_property.inner_type;
                 quote! {
                     let should_read = bool::de(reader)?;
                     should_read.ser(&mut ready_writer);
                     if should_read {
-                        Property::<#field_type>::read_write(reader, &mut ready_writer)?;
+                        EnumProperty::<#field_type>::read_write(reader, &mut ready_writer)?;
+                        ready_did_write = true;
+                    }
+                }
+            }
+            Property::Collection(inner_property) => {
+                let property_type = &inner_property.property_type;
+                quote! {
+                    let should_read = bool::de(reader)?;
+                    should_read.ser(&mut ready_writer);
+                    if should_read {
+                        <#property_type>::read_write(reader, &mut ready_writer)?;
                         ready_did_write = true;
                     }
                 }
@@ -1024,16 +1727,127 @@ fn get_split_update_method(
     }
 }
 
+fn get_merge_updates_method(
+    replica_name: &Ident,
+    properties: &[Property],
+    untyped_generics: &TokenStream,
+) -> TokenStream {
+    let mut output = quote! {};
+
+    for property in properties.iter() {
+        let read_write = match property {
+            Property::Normal(inner_property) => inner_property.read_write(),
+            Property::Enum(inner_property) => {
+                let field_type = &inner_property.inner_type;
+                quote! { EnumProperty::<#field_type>::read_write }
+            }
+            Property::Collection(inner_property) => {
+                // an update of some entries doesn't supersede an earlier one,
+                // so the two are combined instead
+                let property_type = &inner_property.property_type;
+                let new_output_right = quote! {
+                    {
+                        let in_earlier = bool::de(earlier_reader)?;
+                        let in_later = bool::de(later_reader)?;
+                        (in_earlier || in_later).ser(&mut update_writer);
+                        if in_earlier && in_later {
+                            <#property_type>::merge_updates(earlier_reader, later_reader, &mut update_writer)?;
+                        } else if in_earlier {
+                            <#property_type>::read_write(earlier_reader, &mut update_writer)?;
+                        } else if in_later {
+                            <#property_type>::read_write(later_reader, &mut update_writer)?;
+                        }
+                    }
+                };
+                output = quote! {
+                    #output
+                    #new_output_right
+                };
+                continue;
+            }
+            Property::Entity(_) => {
+                quote! { EntityProperty::read_write }
+            }
+            Property::NonReplicated(_) => {
+                continue;
+            }
+        };
+
+        let new_output_right = quote! {
+            {
+                let in_earlier = bool::de(earlier_reader)?;
+                let in_later = bool::de(later_reader)?;
+                (in_earlier || in_later).ser(&mut update_writer);
+                if in_earlier {
+                    if in_later {
+                        // the earlier value is superseded, so is read past but not kept
+                        #read_write(earlier_reader, &mut superseded_writer)?;
+                    } else {
+                        #read_write(earlier_reader, &mut update_writer)?;
+                    }
+                }
+                if in_later {
+                    #read_write(later_reader, &mut update_writer)?;
+                }
+            }
+        };
+
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        fn merge_updates(
+            &self,
+            earlier: &ComponentUpdate,
+            later: &ComponentUpdate,
+        ) -> Result<ComponentUpdate, SerdeErr> {
+            let earlier_reader = &mut earlier.reader();
+            let later_reader = &mut later.reader();
+
+            // the merged update holds each field at most once, as an update
+            // with all of them changed would, so it fits a single writer
+            let mut update_writer = BitWriter::new();
+            let mut superseded_writer = BitWriter::new();
+
+            #output
+
+            let owned_reader = update_writer.to_owned_reader();
+
+            return Ok(ComponentUpdate::new(ComponentKind::of::<#replica_name #untyped_generics>(), owned_reader));
+        }
+    }
+}
+
 fn get_read_apply_update_method(properties: &[Property], struct_type: &StructType) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
         let field_name = get_field_name(property, struct_type);
         let new_output_right = match property {
-            Property::Normal(_) => {
+            Property::Normal(inner_property) => {
+                let read = inner_property.read(&field_name);
+                quote! {
+                    if bool::de(reader)? {
+                        #read
+                    }
+                }
+            }
+            Property::Enum(_) => {
                 quote! {
                     if bool::de(reader)? {
-                        Property::read(&mut self.#field_name, reader)?;
+                        EnumProperty::read(&mut self.#field_name, reader)?;
+                    }
+                }
+            }
+            Property::Collection(inner_property) => {
+                let property_type = &inner_property.property_type;
+                quote! {
+                    if bool::de(reader)? {
+                        <#property_type>::read(&mut self.#field_name, reader)?;
                     }
                 }
             }
@@ -1074,7 +1888,10 @@ fn get_read_apply_field_update_method(
     for property in properties.iter() {
         let field_name = get_field_name(property, struct_type);
         let new_output_right = match property {
-            Property::Normal(_) | Property::NonReplicated(_) => {
+            Property::Normal(_)
+            | Property::Enum(_)
+            | Property::Collection(_)
+            | Property::NonReplicated(_) => {
                 continue;
             }
             Property::Entity(inner_property) => {
@@ -1112,9 +1929,16 @@ fn get_write_method(properties: &[Property], struct_type: &StructType) -> TokenS
     for property in properties.iter() {
         let field_name = get_field_name(property, struct_type);
         let new_output_right = match property {
-            Property::Normal(_) => {
+            Property::Normal(inner_property) => inner_property.write(&field_name),
+            Property::Enum(_) => {
+                quote! {
+                    EnumProperty::write(&self.#field_name, writer);
+                }
+            }
+            Property::Collection(inner_property) => {
+                let property_type = &inner_property.property_type;
                 quote! {
-                    Property::write(&self.#field_name, writer);
+                    <#property_type>::write(&self.#field_name, writer);
                 }
             }
             Property::Entity(_) => {
@@ -1137,6 +1961,9 @@ fn get_write_method(properties: &[Property], struct_type: &StructType) -> TokenS
     quote! {
         fn write(&self, component_kinds: &ComponentKinds, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
             self.kind().ser(component_kinds, writer);
+            self.write_fields(writer, converter);
+        }
+        fn write_fields(&self, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
             #property_writes
         }
     }
@@ -1153,11 +1980,36 @@ fn get_write_update_method(
         let field_name = get_field_name(property, struct_type);
         let new_output_right = match property {
             Property::Normal(property) => {
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                let write = property.write(&field_name);
+                quote! {
+                    if diff_mask.bit(#enum_name::#uppercase_variant_name as u8) == Some(true) && Property::has_field_authority(&self.#field_name) {
+                        true.ser(writer);
+                        #write
+                    } else {
+                        false.ser(writer);
+                    }
+                }
+            }
+            Property::Enum(property) => {
+                let field_type = &property.inner_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    if EnumProperty::<#field_type>::has_update(#enum_name::#uppercase_variant_name as u8, diff_mask) && EnumProperty::has_field_authority(&self.#field_name) {
+                        true.ser(writer);
+                        EnumProperty::write_update(&self.#field_name, #enum_name::#uppercase_variant_name as u8, diff_mask, writer);
+                    } else {
+                        false.ser(writer);
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let property_type = &property.property_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
                 quote! {
-                    if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
+                    if <#property_type>::has_update(#enum_name::#uppercase_variant_name as u8, diff_mask) && <#property_type>::has_field_authority(&self.#field_name) {
                         true.ser(writer);
-                        Property::write(&self.#field_name, writer);
+                        <#property_type>::write_update(&self.#field_name, #enum_name::#uppercase_variant_name as u8, diff_mask, writer);
                     } else {
                         false.ser(writer);
                     }
@@ -1166,7 +2018,7 @@ fn get_write_update_method(
             Property::Entity(property) => {
                 let uppercase_variant_name = &property.uppercase_variable_name;
                 quote! {
-                    if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
+                    if diff_mask.bit(#enum_name::#uppercase_variant_name as u8) == Some(true) && EntityProperty::has_field_authority(&self.#field_name) {
                         true.ser(writer);
                         EntityProperty::write(&self.#field_name, writer, converter);
                     } else {
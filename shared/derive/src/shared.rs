@@ -1,6 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Data, DeriveInput, Fields, GenericParam, Generics};
+use syn::{
+    Data, DeriveInput, Error, Fields, GenericParam, Generics, Ident, LitStr, Type, TypeParam,
+    WherePredicate,
+};
 
 pub enum StructType {
     Struct,
@@ -68,3 +71,94 @@ pub fn get_builder_generic_fields(generics: &Generics) -> TokenStream {
         { #output }
     }
 }
+
+/// Rejects generic parameters the derives can't support. Replicated types
+/// must be `'static`, and a const parameter can't be told apart by its
+/// builder, so only type parameters are allowed
+pub fn validate_generics(generics: &Generics, derive_name: &str) -> Result<(), Error> {
+    for param in generics.params.iter() {
+        match param {
+            GenericParam::Type(_) => {}
+            GenericParam::Lifetime(lifetime_param) => {
+                return Err(Error::new_spanned(
+                    lifetime_param,
+                    format!("`{derive_name}` cannot be derived for a struct with lifetime parameters, as it must be `'static`"),
+                ));
+            }
+            GenericParam::Const(const_param) => {
+                return Err(Error::new_spanned(
+                    const_param,
+                    format!("`{derive_name}` cannot be derived for a struct with const parameters, only type parameters are supported"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The where clause of each derived impl: the struct's own predicates, plus
+/// `Send + Sync + 'static` for every type parameter. Type parameters the
+/// struct leaves unbounded must also be `Serde`, while bounded ones are left
+/// to their bounds (i.e. a protocol trait with `Serde` as a supertrait)
+pub fn get_where_clause(generics: &Generics, shared_crate_name: &TokenStream) -> TokenStream {
+    if generics.lt_token.is_none() {
+        return quote! {};
+    }
+
+    let mut predicates = quote! {};
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in where_clause.predicates.iter() {
+            predicates = quote! { #predicates #predicate, };
+        }
+    }
+    for type_param in generics.type_params() {
+        let ident = &type_param.ident;
+        let predicate = if is_bounded(generics, type_param) {
+            quote! { #ident: Send + Sync + 'static, }
+        } else {
+            quote! { #ident: #shared_crate_name::Serde + Send + Sync + 'static, }
+        };
+        predicates = quote! { #predicates #predicate };
+    }
+
+    quote! {
+        where #predicates
+    }
+}
+
+/// An expression for the name of the struct. For a generic struct, this
+/// names its type arguments, so each instantiation is told apart
+pub fn get_name_expr(name: &Ident, generics: &Generics) -> TokenStream {
+    let name_str = LitStr::new(&name.to_string(), name.span());
+    let type_params: Vec<&Ident> = generics.type_params().map(|param| &param.ident).collect();
+    if type_params.is_empty() {
+        return quote! { #name_str.to_string() };
+    }
+
+    quote! {
+        format!(
+            "{}<{}>",
+            #name_str,
+            [#(std::any::type_name::<#type_params>()),*].join(", ")
+        )
+    }
+}
+
+// whether the type parameter has any bound, inline or in the where clause
+fn is_bounded(generics: &Generics, type_param: &TypeParam) -> bool {
+    if !type_param.bounds.is_empty() {
+        return true;
+    }
+    let Some(where_clause) = &generics.where_clause else {
+        return false;
+    };
+    where_clause.predicates.iter().any(|predicate| {
+        let WherePredicate::Type(predicate_type) = predicate else {
+            return false;
+        };
+        let Type::Path(type_path) = &predicate_type.bounded_ty else {
+            return false;
+        };
+        type_path.qself.is_none() && type_path.path.is_ident(&type_param.ident)
+    })
+}
@@ -1,7 +1,9 @@
+mod bits;
 mod enumeration;
 mod structure;
 mod tuple_structure;
 
+pub use bits::*;
 pub use enumeration::*;
 pub use structure::*;
 pub use tuple_structure::*;
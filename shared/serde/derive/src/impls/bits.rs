@@ -0,0 +1,98 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, Field, LitInt, Type};
+
+/// An integer field marked with `#[bits(N)]`, which is written in exactly N
+/// bits rather than the full width of its type
+pub struct BitsField {
+    signed: bool,
+    bits: u8,
+}
+
+impl BitsField {
+    /// The `UnsignedInteger` or `SignedInteger` the field is written as.
+    /// Signed fields spend one of their bits on the sign
+    fn integer_type(&self, serde_crate_name: &TokenStream) -> TokenStream {
+        if self.signed {
+            let bits = self.bits - 1;
+            quote! { #serde_crate_name::SignedInteger::<#bits> }
+        } else {
+            let bits = self.bits;
+            quote! { #serde_crate_name::UnsignedInteger::<#bits> }
+        }
+    }
+
+    /// Writes the value, panicking if it doesn't fit in the bits
+    pub fn ser(&self, value: TokenStream, serde_crate_name: &TokenStream) -> TokenStream {
+        let integer_type = self.integer_type(serde_crate_name);
+        quote! { #integer_type::new(#value).ser(writer); }
+    }
+
+    pub fn de(&self, serde_crate_name: &TokenStream) -> TokenStream {
+        let integer_type = self.integer_type(serde_crate_name);
+        quote! { #integer_type::de(reader)?.to() }
+    }
+
+    pub fn bit_length(&self, serde_crate_name: &TokenStream) -> TokenStream {
+        let integer_type = self.integer_type(serde_crate_name);
+        quote! { <#integer_type as ConstBitLength>::const_bit_length() }
+    }
+}
+
+/// The width given by a `#[bits(N)]` attribute, if present. N must fit the
+/// field's integer type, which is checked here so that it fails to compile
+pub fn get_bits(field: &Field) -> Result<Option<BitsField>, Error> {
+    let mut output = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("bits"))
+    {
+        if output.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                "a field can only have one `#[bits(..)]`",
+            ));
+        }
+        let bits: u32 = attr.parse_args::<LitInt>()?.base10_parse()?;
+
+        let type_name = match &field.ty {
+            Type::Path(type_path) => type_path.path.get_ident().map(|ident| ident.to_string()),
+            _ => None,
+        };
+        let (signed, width) = match type_name.as_deref() {
+            Some("u8") => (false, 8),
+            Some("u16") => (false, 16),
+            Some("u32") => (false, 32),
+            Some("u64") => (false, 64),
+            Some("i8") => (true, 8),
+            Some("i16") => (true, 16),
+            Some("i32") => (true, 32),
+            Some("i64") => (true, 64),
+            _ => {
+                return Err(Error::new_spanned(
+                    &field.ty,
+                    "`#[bits(..)]` can only be used on u8, u16, u32, u64, i8, i16, i32 or i64 fields",
+                ));
+            }
+        };
+        // signed fields need a bit for the sign, & at least one for the value
+        let min_bits = if signed { 2 } else { 1 };
+        if bits < min_bits || bits > width {
+            return Err(Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` fields can only be written in {}..={} bits",
+                    type_name.unwrap(),
+                    min_bits,
+                    width
+                ),
+            ));
+        }
+        output = Some(BitsField {
+            signed,
+            bits: bits as u8,
+        });
+    }
+    Ok(output)
+}
@@ -1,6 +1,6 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
-use syn::{DataEnum, Fields};
+use syn::{DataEnum, Error, Fields, Variant};
 
 fn bits_needed_for(max_value: usize) -> u8 {
     let mut bits = 1;
@@ -19,6 +19,17 @@ pub fn derive_serde_enum(
     enum_name: &Ident,
     serde_crate_name: TokenStream,
 ) -> TokenStream {
+    for field in enum_
+        .variants
+        .iter()
+        .flat_map(|variant| variant.fields.iter())
+    {
+        if let Some(attr) = field.attrs.iter().find(|attr| attr.path().is_ident("bits")) {
+            return Error::new_spanned(attr, "`#[bits(..)]` is not supported on enum fields")
+                .to_compile_error();
+        }
+    }
+
     let variant_number = enum_.variants.len();
     let bits_needed = bits_needed_for(variant_number);
 
@@ -26,14 +37,19 @@ pub fn derive_serde_enum(
     let de_method = get_de_method(enum_, bits_needed);
     let bit_length_method = get_bit_length_method(enum_, bits_needed);
 
+    let max_variant_fields = get_max_variant_fields(enum_);
+    let diff_fields_method = get_diff_fields_method(enum_);
+    let ser_fields_method = get_ser_fields_method(enum_, bits_needed);
+    let de_fields_method = get_de_fields_method(enum_, bits_needed);
+    let read_write_fields_method = get_read_write_fields_method(enum_, bits_needed);
+
     let lowercase_enum_name = Ident::new(
         enum_name.to_string().to_lowercase().as_str(),
         Span::call_site(),
     );
     let module_name = format_ident!("define_{}", lowercase_enum_name);
 
-    let import_types =
-        quote! { Serde, BitWrite, UnsignedInteger, BitReader, SerdeErr, ConstBitLength, };
+    let import_types = quote! { Serde, SerdeEnum, BitWrite, UnsignedInteger, BitReader, SerdeErr, ConstBitLength, };
     let imports = quote! { use #serde_crate_name::{#import_types}; };
 
     quote! {
@@ -46,6 +62,29 @@ pub fn derive_serde_enum(
                 #de_method
                 #bit_length_method
             }
+
+            impl SerdeEnum for #enum_name {
+                const MAX_VARIANT_FIELDS: u8 = #max_variant_fields;
+                #diff_fields_method
+                #ser_fields_method
+                #de_fields_method
+                #read_write_fields_method
+            }
+
+            // `field` is only used to infer the type of the field to read & write
+            #[allow(dead_code)]
+            fn read_write_field<T: Serde>(
+                _field: fn(&#enum_name) -> Option<&T>,
+                reader: &mut BitReader,
+                writer: &mut dyn BitWrite,
+            ) -> std::result::Result<(), SerdeErr> {
+                let should_write = bool::de(reader)?;
+                should_write.ser(writer);
+                if should_write {
+                    T::de(reader)?.ser(writer);
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -272,3 +311,201 @@ fn get_bit_length_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
          }
     }
 }
+
+// SerdeEnum
+
+fn get_max_variant_fields(enum_: &DataEnum) -> u8 {
+    let max = enum_
+        .variants
+        .iter()
+        .map(|variant| variant.fields.len())
+        .max()
+        .unwrap_or(0);
+    if max > u8::MAX as usize {
+        panic!("cannot diff an enum variant with more than 255 fields!");
+    }
+    max as u8
+}
+
+// A pattern matching the variant, which binds each of its fields to `{prefix}_{index}`
+fn get_variant_pattern(variant: &Variant, prefix: &str) -> (TokenStream, Vec<Ident>) {
+    let variant_name = &variant.ident;
+    let bindings: Vec<Ident> = (0..variant.fields.len())
+        .map(|index| format_ident!("{}_{}", prefix, index))
+        .collect();
+    let pattern = match &variant.fields {
+        Fields::Unit => quote! { Self::#variant_name },
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .expect("expected field to have a name.")
+            });
+            quote! { Self::#variant_name{ #(#names: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self::#variant_name( #(#bindings),* ) },
+    };
+    (pattern, bindings)
+}
+
+// A pattern matching the variant, which binds only the field at `field_index` to `field`
+fn get_variant_field_pattern(variant: &Variant, field_index: usize) -> TokenStream {
+    let variant_name = &variant.ident;
+    match &variant.fields {
+        Fields::Unit => unreachable!("unit variants have no fields"),
+        Fields::Named(fields) => {
+            let name = fields.named[field_index]
+                .ident
+                .as_ref()
+                .expect("expected field to have a name.");
+            quote! { Self::#variant_name{ #name: field, .. } }
+        }
+        Fields::Unnamed(_) => {
+            let skipped = (0..field_index).map(|_| quote! { _ });
+            quote! { Self::#variant_name( #(#skipped,)* field, .. ) }
+        }
+    }
+}
+
+fn get_diff_fields_method(enum_: &DataEnum) -> TokenStream {
+    let mut arms = quote! {};
+    for variant in enum_.variants.iter() {
+        let (self_pattern, self_bindings) = get_variant_pattern(variant, "self");
+        let (other_pattern, other_bindings) = get_variant_pattern(variant, "other");
+        let field_indices = (0..variant.fields.len()).map(|index| index as u8);
+        arms = quote! {
+            #arms
+            (#self_pattern, #other_pattern) => {
+                let mut output = Vec::new();
+                #(
+                    if #self_bindings != #other_bindings {
+                        output.push(#field_indices);
+                    }
+                )*
+                Some(output)
+            }
+        };
+    }
+    quote! {
+        #[allow(unreachable_patterns, unused_mut)]
+        fn diff_fields(&self, other: &Self) -> Option<Vec<u8>> {
+            match (self, other) {
+                #arms
+                _ => None,
+            }
+        }
+    }
+}
+
+fn get_ser_fields_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
+    let mut arms = quote! {};
+    for (index, variant) in enum_.variants.iter().enumerate() {
+        let variant_index = index as u16;
+        let (pattern, bindings) = get_variant_pattern(variant, "self");
+        let field_indices = (0..variant.fields.len()).map(|index| index as u8);
+        arms = quote! {
+            #arms
+            #pattern => {
+                let index = UnsignedInteger::<#bits_needed>::new(#variant_index);
+                index.ser(writer);
+                #(
+                    if has_field(#field_indices) {
+                        true.ser(writer);
+                        #bindings.ser(writer);
+                    } else {
+                        false.ser(writer);
+                    }
+                )*
+            }
+        };
+    }
+    quote! {
+        #[allow(unused_variables)]
+        fn ser_fields(&self, has_field: &dyn Fn(u8) -> bool, writer: &mut dyn BitWrite) {
+            match self {
+                #arms
+            }
+        }
+    }
+}
+
+fn get_de_fields_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
+    let mut arms = quote! {};
+    for (index, variant) in enum_.variants.iter().enumerate() {
+        let variant_index = index as u16;
+        if variant.fields.is_empty() {
+            arms = quote! {
+                #arms
+                #variant_index => {}
+            };
+            continue;
+        }
+        let (pattern, bindings) = get_variant_pattern(variant, "self");
+        let values: Vec<Ident> = (0..variant.fields.len())
+            .map(|index| format_ident!("value_{}", index))
+            .collect();
+        arms = quote! {
+            #arms
+            #variant_index => {
+                #(
+                    let #values = if bool::de(reader)? {
+                        Some(Serde::de(reader)?)
+                    } else {
+                        None
+                    };
+                )*
+                if let #pattern = self {
+                    #(
+                        if let Some(value) = #values {
+                            *#bindings = value;
+                        }
+                    )*
+                }
+            }
+        };
+    }
+    quote! {
+        #[allow(irrefutable_let_patterns)]
+        fn de_fields(&mut self, reader: &mut BitReader) -> std::result::Result<(), SerdeErr> {
+            let index: UnsignedInteger<#bits_needed> = Serde::de(reader)?;
+            match index.get() as u16 {
+                #arms
+                _ => return Err(SerdeErr),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn get_read_write_fields_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
+    let mut arms = quote! {};
+    for (index, variant) in enum_.variants.iter().enumerate() {
+        let variant_index = index as u16;
+        let field_patterns =
+            (0..variant.fields.len()).map(|index| get_variant_field_pattern(variant, index));
+        arms = quote! {
+            #arms
+            #variant_index => {
+                #(
+                    read_write_field(|value| match value {
+                        #field_patterns => Some(field),
+                        _ => None,
+                    }, reader, writer)?;
+                )*
+            }
+        };
+    }
+    quote! {
+        #[allow(unreachable_patterns)]
+        fn read_write_fields(reader: &mut BitReader, writer: &mut dyn BitWrite) -> std::result::Result<(), SerdeErr> {
+            let index: UnsignedInteger<#bits_needed> = Serde::de(reader)?;
+            index.ser(writer);
+            match index.get() as u16 {
+                #arms
+                _ => return Err(SerdeErr),
+            }
+            Ok(())
+        }
+    }
+}
@@ -2,6 +2,8 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::DataStruct;
 
+use super::get_bits;
+
 #[allow(clippy::format_push_string)]
 pub fn derive_serde_struct(
     struct_: &DataStruct,
@@ -14,6 +16,28 @@ pub fn derive_serde_struct(
 
     for field in &struct_.fields {
         let field_name = field.ident.as_ref().expect("expected field to have a name");
+        let bits = match get_bits(field) {
+            Ok(bits) => bits,
+            Err(error) => return error.to_compile_error(),
+        };
+        if let Some(bits) = bits {
+            let ser = bits.ser(quote! { self.#field_name }, &serde_crate_name);
+            let de = bits.de(&serde_crate_name);
+            let bit_length = bits.bit_length(&serde_crate_name);
+            ser_body = quote! {
+                #ser_body
+                #ser
+            };
+            de_body = quote! {
+                #de_body
+                #field_name: #de,
+            };
+            bit_length_body = quote! {
+                #bit_length_body
+                output += #bit_length;
+            };
+            continue;
+        }
         ser_body = quote! {
             #ser_body
             self.#field_name.ser(writer);
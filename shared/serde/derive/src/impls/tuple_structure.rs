@@ -1,6 +1,8 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
-use syn::DataStruct;
+use syn::{DataStruct, Index};
+
+use super::get_bits;
 
 #[allow(clippy::format_push_string)]
 pub fn derive_serde_tuple_struct(
@@ -12,15 +14,37 @@ pub fn derive_serde_tuple_struct(
     let mut de_body = quote! {};
     let mut bit_length_body = quote! {};
 
-    for (i, _) in struct_.fields.iter().enumerate() {
-        let field_index = i;
+    for (i, field) in struct_.fields.iter().enumerate() {
+        let field_index = Index::from(i);
+        let bits = match get_bits(field) {
+            Ok(bits) => bits,
+            Err(error) => return error.to_compile_error(),
+        };
+        if let Some(bits) = bits {
+            let ser = bits.ser(quote! { self.#field_index }, &serde_crate_name);
+            let de = bits.de(&serde_crate_name);
+            let bit_length = bits.bit_length(&serde_crate_name);
+            ser_body = quote! {
+                #ser_body
+                #ser
+            };
+            de_body = quote! {
+                #de_body
+                #de,
+            };
+            bit_length_body = quote! {
+                #bit_length_body
+                output += #bit_length;
+            };
+            continue;
+        }
         ser_body = quote! {
             #ser_body
             self.#field_index.ser(writer);
         };
         de_body = quote! {
             #de_body
-            #field_index: Serde::de(reader)?,
+            Serde::de(reader)?,
         };
         bit_length_body = quote! {
             #bit_length_body
@@ -46,9 +70,9 @@ pub fn derive_serde_tuple_struct(
                     #ser_body
                  }
                  fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-                    Ok(Self {
+                    Ok(Self(
                         #de_body
-                    })
+                    ))
                  }
                  fn bit_length(&self) -> u32 {
                     let mut output = 0;
@@ -1,40 +1,40 @@
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
 
 mod impls;
 use impls::*;
 
-#[proc_macro_derive(Serde)]
+#[proc_macro_derive(Serde, attributes(bits))]
 pub fn derive_serde(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_shared };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeInternal)]
+#[proc_macro_derive(SerdeInternal, attributes(bits))]
 pub fn derive_serde_internal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_serde };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeBevyShared)]
+#[proc_macro_derive(SerdeBevyShared, attributes(bits))]
 pub fn derive_serde_bevy_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_bevy_shared };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeBevyServer)]
+#[proc_macro_derive(SerdeBevyServer, attributes(bits))]
 pub fn derive_serde_bevy_server(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_bevy_server };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeBevyClient)]
+#[proc_macro_derive(SerdeBevyClient, attributes(bits))]
 pub fn derive_serde_bevy_client(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_bevy_client };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeHecs)]
+#[proc_macro_derive(SerdeHecs, attributes(bits))]
 pub fn derive_serde_hecs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_hecs_shared };
     derive_serde_common(input, serde_crate_name)
@@ -47,6 +47,15 @@ fn derive_serde_common(
     let input = parse_macro_input!(input as DeriveInput);
     let input_name = input.ident;
 
+    if !input.generics.params.is_empty() {
+        return Error::new_spanned(
+            &input.generics,
+            "Serde cannot be derived for generic types, implement it manually instead",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     let gen = match &input.data {
         Data::Enum(enum_) => derive_serde_enum(enum_, &input_name, serde_crate_name),
         Data::Struct(struct_) => match struct_.fields {
@@ -55,7 +64,14 @@ fn derive_serde_common(
             }
             Fields::Named(_) => derive_serde_struct(struct_, &input_name, serde_crate_name),
         },
-        _ => unimplemented!("Only structs and enums are supported"),
+        Data::Union(_) => {
+            return Error::new_spanned(
+                &input_name,
+                "Serde can only be derived for structs & enums",
+            )
+            .to_compile_error()
+            .into();
+        }
     };
 
     proc_macro::TokenStream::from(gen)
@@ -23,6 +23,46 @@ impl<'b> BitReader<'b> {
         self.buffer.len()
     }
 
+    /// Returns the number of bits which have been read so far
+    pub fn bits_read(&self) -> u32 {
+        (self.state.buffer_index * 8) as u32 - self.state.scratch_index as u32
+    }
+
+    /// Returns the number of bits left to read, an upper bound on the size of
+    /// anything still to be deserialized
+    pub fn bits_remaining(&self) -> u32 {
+        ((self.buffer.len() - self.state.buffer_index) * 8) as u32 + self.state.scratch_index as u32
+    }
+
+    /// Captures the current read position, to later rewind to with
+    /// `restore()`, e.g. when a speculative `Serde::de` fails
+    pub fn checkpoint(&self) -> ReaderCheckpoint {
+        ReaderCheckpoint { state: self.state }
+    }
+
+    /// Rewinds the read position to a checkpoint taken from this reader
+    pub fn restore(&mut self, checkpoint: ReaderCheckpoint) {
+        self.state = checkpoint.state;
+    }
+
+    /// Moves past the next `bits` bits without reading them, e.g. to discard
+    /// a value whose bit length is known
+    pub fn skip_bits(&mut self, bits: u32) -> Result<(), SerdeErr> {
+        if bits > self.bits_remaining() {
+            return Err(SerdeErr);
+        }
+        let mut bits = bits;
+        while bits > 0 && self.state.scratch_index > 0 {
+            self.read_bit()?;
+            bits -= 1;
+        }
+        self.state.buffer_index += (bits / 8) as usize;
+        for _ in 0..bits % 8 {
+            self.read_bit()?;
+        }
+        Ok(())
+    }
+
     pub fn to_owned(&self) -> OwnedBitReader {
         OwnedBitReader {
             state: self.state,
@@ -66,6 +106,14 @@ impl<'b> BitReader<'b> {
     }
 }
 
+// ReaderCheckpoint
+
+/// A saved read position of a BitReader, see `BitReader::checkpoint()`
+#[derive(Copy, Clone)]
+pub struct ReaderCheckpoint {
+    state: BitReaderState,
+}
+
 // OwnedBitReader
 
 pub struct OwnedBitReader {
@@ -100,3 +148,67 @@ struct BitReaderState {
     scratch_index: u8,
     buffer_index: usize,
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+
+    #[test]
+    fn checkpoint_restore() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        true.ser(&mut writer);
+        1234_u16.ser(&mut writer);
+        "Hello checkpoint!".to_string().ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: bool = Serde::de(&mut reader).unwrap();
+        let checkpoint = reader.checkpoint();
+        let bits_read = reader.bits_read();
+        let out_2: u16 = Serde::de(&mut reader).unwrap();
+
+        reader.restore(checkpoint);
+        assert_eq!(reader.bits_read(), bits_read);
+        let out_2_again: u16 = Serde::de(&mut reader).unwrap();
+        let out_3: String = Serde::de(&mut reader).unwrap();
+
+        assert!(out_1);
+        assert_eq!(out_2, 1234);
+        assert_eq!(out_2, out_2_again);
+        assert_eq!(out_3, "Hello checkpoint!");
+    }
+
+    #[test]
+    fn skip_bits() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        true.ser(&mut writer);
+        "Skipped entirely".to_string().ser(&mut writer);
+        5_u8.ser(&mut writer);
+        1234_u16.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: bool = Serde::de(&mut reader).unwrap();
+        let string_bits = "Skipped entirely".to_string().bit_length();
+        reader.skip_bits(string_bits).unwrap();
+        reader.skip_bits(3).unwrap();
+        reader.skip_bits(5).unwrap();
+        let out_2: u16 = Serde::de(&mut reader).unwrap();
+
+        assert!(out_1);
+        assert_eq!(out_2, 1234);
+        assert!(reader.skip_bits(reader.bits_remaining() + 1).is_err());
+    }
+}
@@ -1,9 +1,16 @@
 mod array;
 mod boxed;
+mod btree;
+#[cfg(feature = "glam_support")]
+mod glam;
 mod hash;
+mod net;
 mod option;
 mod phantom;
 mod scalars;
 mod string;
+mod time;
 mod tuple;
+#[cfg(feature = "uuid_support")]
+mod uuid;
 mod vector;
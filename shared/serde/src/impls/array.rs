@@ -62,7 +62,12 @@ impl<T: ConstBitLength, const N: usize> ConstBitLength for [T; N] {
 
 #[cfg(test)]
 mod tests {
-    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use crate::{
+        bit_counter::BitCounter,
+        bit_reader::BitReader,
+        bit_writer::BitWriter,
+        serde::{ConstBitLength, Serde},
+    };
 
     #[test]
     fn read_write() {
@@ -86,4 +91,13 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn bit_length() {
+        let array: [u16; 5] = [1, 2, 3, 4, 5];
+        let mut counter = BitCounter::new(0, 0, u32::MAX);
+        array.ser(&mut counter);
+        assert_eq!(counter.bits_needed(), array.bit_length());
+        assert_eq!(array.bit_length(), <[u16; 5]>::const_bit_length());
+    }
 }
@@ -39,6 +39,10 @@ impl Serde for Box<[u8]> {
     fn de(reader: &mut BitReader) -> Result<Box<[u8]>, SerdeErr> {
         let length_int = UnsignedVariableInteger::<9>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        // a length longer than the rest of the buffer can only be malformed
+        if length_usize > (reader.bits_remaining() / 8) as usize {
+            return Err(SerdeErr);
+        }
         let mut bytes: Vec<u8> = Vec::with_capacity(length_usize);
         for _ in 0..length_usize {
             bytes.push(reader.read_byte()?);
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr, serde::Serde,
+    UnsignedVariableInteger,
+};
+
+/// Entries are written in sorted key order, so equal maps always serialize to
+/// the same bytes.
+impl<K: Serde + Ord, V: Serde> Serde for BTreeMap<K, V> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        let length = UnsignedVariableInteger::<5>::new(self.len() as u64);
+        length.ser(writer);
+        for (key, value) in self {
+            key.ser(writer);
+            value.ser(writer);
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let length_int = UnsignedVariableInteger::<5>::de(reader)?;
+        let length_usize = length_int.get() as usize;
+        let mut output: BTreeMap<K, V> = BTreeMap::new();
+        for _ in 0..length_usize {
+            let key = K::de(reader)?;
+            let value = V::de(reader)?;
+            output.insert(key, value);
+        }
+        Ok(output)
+    }
+
+    fn bit_length(&self) -> u32 {
+        let mut output = 0;
+        let length = UnsignedVariableInteger::<5>::new(self.len() as u64);
+        output += length.bit_length();
+        for (key, value) in self {
+            output += key.bit_length();
+            output += value.bit_length();
+        }
+        output
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn read_write_btree_map() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let mut in_1 = BTreeMap::<i32, String>::new();
+        in_1.insert(-7, "negative seven".to_string());
+        in_1.insert(331, "three hundred and thiry-one".to_string());
+        in_1.insert(-65, "negative sixty-five".to_string());
+        let in_2 = BTreeMap::<u16, bool>::new();
+        let mut in_3 = BTreeMap::<u16, bool>::new();
+        in_3.insert(5, true);
+        in_3.insert(73, false);
+        in_3.insert(44, false);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+        in_3.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = BTreeMap::<i32, String>::de(&mut reader).unwrap();
+        let out_2 = BTreeMap::<u16, bool>::de(&mut reader).unwrap();
+        let out_3 = BTreeMap::<u16, bool>::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(in_3, out_3);
+    }
+
+    #[test]
+    fn btree_map_bytes_do_not_depend_on_insertion_order() {
+        let mut in_1 = BTreeMap::<u16, u8>::new();
+        let mut in_2 = BTreeMap::<u16, u8>::new();
+        for key in 0..50 {
+            in_1.insert(key, key as u8);
+            in_2.insert(49 - key, (49 - key) as u8);
+        }
+
+        let mut writer_1 = BitWriter::new();
+        in_1.ser(&mut writer_1);
+        let mut writer_2 = BitWriter::new();
+        in_2.ser(&mut writer_2);
+
+        assert_eq!(writer_1.to_bytes(), writer_2.to_bytes());
+        assert_eq!(in_1.bit_length(), in_2.bit_length());
+    }
+}
@@ -0,0 +1,148 @@
+use ::glam::{Quat, Vec2, Vec3};
+
+use crate::{
+    bit_reader::BitReader,
+    bit_writer::BitWrite,
+    error::SerdeErr,
+    serde::{ConstBitLength, Serde},
+};
+
+// glam's types are written as their f32 components, in order, losslessly.
+// Quantize them into integers first where less precision will do
+
+impl Serde for Vec2 {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.x.ser(writer);
+        self.y.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let x = f32::de(reader)?;
+        let y = f32::de(reader)?;
+        Ok(Vec2::new(x, y))
+    }
+
+    fn bit_length(&self) -> u32 {
+        <Self as ConstBitLength>::const_bit_length()
+    }
+}
+
+impl ConstBitLength for Vec2 {
+    fn const_bit_length() -> u32 {
+        2 * f32::const_bit_length()
+    }
+}
+
+impl Serde for Vec3 {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.x.ser(writer);
+        self.y.ser(writer);
+        self.z.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let x = f32::de(reader)?;
+        let y = f32::de(reader)?;
+        let z = f32::de(reader)?;
+        Ok(Vec3::new(x, y, z))
+    }
+
+    fn bit_length(&self) -> u32 {
+        <Self as ConstBitLength>::const_bit_length()
+    }
+}
+
+impl ConstBitLength for Vec3 {
+    fn const_bit_length() -> u32 {
+        3 * f32::const_bit_length()
+    }
+}
+
+impl Serde for Quat {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.x.ser(writer);
+        self.y.ser(writer);
+        self.z.ser(writer);
+        self.w.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let x = f32::de(reader)?;
+        let y = f32::de(reader)?;
+        let z = f32::de(reader)?;
+        let w = f32::de(reader)?;
+        Ok(Quat::from_xyzw(x, y, z, w))
+    }
+
+    fn bit_length(&self) -> u32 {
+        <Self as ConstBitLength>::const_bit_length()
+    }
+}
+
+impl ConstBitLength for Quat {
+    fn const_bit_length() -> u32 {
+        4 * f32::const_bit_length()
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use ::glam::{Quat, Vec2, Vec3};
+
+    use crate::{
+        bit_counter::BitCounter,
+        bit_reader::BitReader,
+        bit_writer::BitWriter,
+        serde::{ConstBitLength, Serde},
+    };
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = Vec2::new(1.5, -20.25);
+        let in_2 = Vec3::new(-0.0, f32::MAX, 3.0);
+        let in_3 = Quat::from_rotation_y(1.2);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+        in_3.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: Vec2 = Serde::de(&mut reader).unwrap();
+        let out_2: Vec3 = Serde::de(&mut reader).unwrap();
+        let out_3: Quat = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(in_3, out_3);
+    }
+
+    fn bits_written(value: &impl Serde) -> u32 {
+        let mut counter = BitCounter::new(0, 0, u32::MAX);
+        value.ser(&mut counter);
+        counter.bits_needed()
+    }
+
+    #[test]
+    fn bit_length() {
+        let vec2 = Vec2::new(1.0, 2.0);
+        assert_eq!(bits_written(&vec2), vec2.bit_length());
+        assert_eq!(vec2.bit_length(), Vec2::const_bit_length());
+
+        let vec3 = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(bits_written(&vec3), vec3.bit_length());
+        assert_eq!(vec3.bit_length(), Vec3::const_bit_length());
+
+        let quat = Quat::IDENTITY;
+        assert_eq!(bits_written(&quat), quat.bit_length());
+        assert_eq!(quat.bit_length(), Quat::const_bit_length());
+    }
+}
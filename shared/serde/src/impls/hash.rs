@@ -38,6 +38,9 @@ impl<K: Serde + Eq + Hash> Serde for HashSet<K> {
     }
 }
 
+/// Entries are written in the `HashMap`'s iteration order, which is
+/// nondeterministic: two equal maps may serialize to different bytes. Use a
+/// `BTreeMap` when the bytes on the wire must be deterministic.
 impl<K: Serde + Eq + Hash, V: Serde> Serde for HashMap<K, V> {
     fn ser(&self, writer: &mut dyn BitWrite) {
         let length = UnsignedVariableInteger::<5>::new(self.len() as u64);
@@ -76,7 +79,9 @@ impl<K: Serde + Eq + Hash, V: Serde> Serde for HashMap<K, V> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use crate::{
+        bit_counter::BitCounter, bit_reader::BitReader, bit_writer::BitWriter, serde::Serde,
+    };
     use std::collections::{HashMap, HashSet};
 
     #[test]
@@ -110,6 +115,29 @@ mod tests {
         assert_eq!(in_2, out_2);
     }
 
+    #[test]
+    fn read_write_empty_hash_map() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = HashMap::<u16, String>::new();
+        let in_2 = 9u8;
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = HashMap::<u16, String>::de(&mut reader).unwrap();
+        let out_2 = u8::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+
     #[test]
     fn read_write_hash_set() {
         // Write
@@ -140,4 +168,14 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn bit_length_hash_set() {
+        for length in [0, 1, 31, 32, 1000] {
+            let set: HashSet<u16> = (0..length).collect();
+            let mut counter = BitCounter::new(0, 0, u32::MAX);
+            set.ser(&mut counter);
+            assert_eq!(counter.bits_needed(), set.bit_length());
+        }
+    }
 }
@@ -0,0 +1,87 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::{bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr, serde::Serde};
+
+// A SocketAddr is written as a tag bit (false for IPv4, true for IPv6),
+// followed by the address bytes and then the port
+impl Serde for SocketAddr {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self.ip() {
+            IpAddr::V4(ip) => {
+                false.ser(writer);
+                ip.octets().ser(writer);
+            }
+            IpAddr::V6(ip) => {
+                true.ser(writer);
+                ip.octets().ser(writer);
+            }
+        }
+        self.port().ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let is_v6 = bool::de(reader)?;
+        let ip = if is_v6 {
+            IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::de(reader)?))
+        } else {
+            IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::de(reader)?))
+        };
+        let port = u16::de(reader)?;
+        Ok(SocketAddr::new(ip, port))
+    }
+
+    fn bit_length(&self) -> u32 {
+        let mut output = 0;
+        output += false.bit_length();
+        output += match self.ip() {
+            IpAddr::V4(ip) => ip.octets().bit_length(),
+            IpAddr::V6(ip) => ip.octets().bit_length(),
+        };
+        output += self.port().bit_length();
+        output
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use crate::{
+        bit_counter::BitCounter, bit_reader::BitReader, bit_writer::BitWriter, serde::Serde,
+    };
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let in_2: SocketAddr = "[2001:db8::ff00:42:8329]:443".parse().unwrap();
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: SocketAddr = Serde::de(&mut reader).unwrap();
+        let out_2: SocketAddr = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+
+    #[test]
+    fn bit_length() {
+        for address in ["127.0.0.1:14191", "[2001:db8::ff00:42:8329]:443"] {
+            let address: SocketAddr = address.parse().unwrap();
+            let mut counter = BitCounter::new(0, 0, u32::MAX);
+            address.ser(&mut counter);
+            assert_eq!(counter.bits_needed(), address.bit_length());
+        }
+    }
+}
@@ -16,13 +16,18 @@ impl Serde for String {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<9>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        // a length longer than the rest of the buffer can only be malformed
+        if length_usize > (reader.bits_remaining() / 8) as usize {
+            return Err(SerdeErr);
+        }
         let mut bytes: Vec<u8> = Vec::with_capacity(length_usize);
         for _ in 0..length_usize {
             bytes.push(reader.read_byte()?);
         }
 
-        let result = String::from_utf8_lossy(&bytes).into_owned();
-        Ok(result)
+        // a String always serializes as valid UTF-8, and replacing invalid bytes
+        // would change the length of the value
+        String::from_utf8(bytes).map_err(|_| SerdeErr)
     }
 
     fn bit_length(&self) -> u32 {
@@ -38,7 +43,12 @@ impl Serde for String {
 
 #[cfg(test)]
 mod tests {
-    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use crate::{
+        bit_reader::BitReader,
+        bit_writer::{BitWrite, BitWriter},
+        serde::Serde,
+        UnsignedVariableInteger,
+    };
 
     #[test]
     fn read_write() {
@@ -62,4 +72,26 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = vec![0xffu8, 0xfe];
+
+        UnsignedVariableInteger::<9>::new(in_1.len() as u64).ser(&mut writer);
+        for byte in &in_1 {
+            writer.write_byte(*byte);
+        }
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: Result<String, _> = Serde::de(&mut reader);
+
+        assert!(out_1.is_err());
+    }
 }
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use crate::{
+    bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr, serde::Serde, UnsignedInteger,
+    UnsignedVariableInteger,
+};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+// A Duration is written losslessly, as its whole seconds in a variable-length
+// integer followed by its sub-second nanoseconds in 30 bits
+impl Serde for Duration {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        UnsignedVariableInteger::<7>::new(self.as_secs()).ser(writer);
+        UnsignedInteger::<30>::new(self.subsec_nanos()).ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let secs = UnsignedVariableInteger::<7>::de(reader)?.get() as u64;
+        let nanos = UnsignedInteger::<30>::de(reader)?.get() as u32;
+        if nanos >= NANOS_PER_SEC {
+            return Err(SerdeErr);
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+
+    fn bit_length(&self) -> u32 {
+        let mut output = 0;
+        output += UnsignedVariableInteger::<7>::new(self.as_secs()).bit_length();
+        output += UnsignedInteger::<30>::new(self.subsec_nanos()).bit_length();
+        output
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        bit_counter::BitCounter, bit_reader::BitReader, bit_writer::BitWriter, serde::Serde,
+    };
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = Duration::from_millis(16);
+        let in_2 = Duration::new(90_061, 999_999_999);
+        let in_3 = Duration::MAX;
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+        in_3.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: Duration = Serde::de(&mut reader).unwrap();
+        let out_2: Duration = Serde::de(&mut reader).unwrap();
+        let out_3: Duration = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(in_3, out_3);
+    }
+
+    #[test]
+    fn bit_length() {
+        for duration in [
+            Duration::ZERO,
+            Duration::from_millis(16),
+            Duration::new(90_061, 999_999_999),
+            Duration::MAX,
+        ] {
+            let mut counter = BitCounter::new(0, 0, u32::MAX);
+            duration.ser(&mut counter);
+            assert_eq!(counter.bits_needed(), duration.bit_length());
+        }
+    }
+}
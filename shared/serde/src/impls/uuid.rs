@@ -0,0 +1,75 @@
+use ::uuid::Uuid;
+
+use crate::{
+    bit_reader::BitReader,
+    bit_writer::BitWrite,
+    error::SerdeErr,
+    serde::{ConstBitLength, Serde},
+};
+
+// A Uuid is written as its 16 bytes, in big-endian order
+impl Serde for Uuid {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.as_bytes().ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Uuid::from_bytes(<[u8; 16]>::de(reader)?))
+    }
+
+    fn bit_length(&self) -> u32 {
+        <Self as ConstBitLength>::const_bit_length()
+    }
+}
+
+impl ConstBitLength for Uuid {
+    fn const_bit_length() -> u32 {
+        <[u8; 16] as ConstBitLength>::const_bit_length()
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use ::uuid::Uuid;
+
+    use crate::{
+        bit_counter::BitCounter,
+        bit_reader::BitReader,
+        bit_writer::BitWriter,
+        serde::{ConstBitLength, Serde},
+    };
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = Uuid::nil();
+        let in_2 = Uuid::from_u128(0x6fa4_59ea_ee8a_3ca4_894e_db77_e160_355e);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: Uuid = Serde::de(&mut reader).unwrap();
+        let out_2: Uuid = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+
+    #[test]
+    fn bit_length() {
+        let uuid = Uuid::from_u128(u128::MAX);
+        let mut counter = BitCounter::new(0, 0, u32::MAX);
+        uuid.ser(&mut counter);
+        assert_eq!(counter.bits_needed(), uuid.bit_length());
+        assert_eq!(uuid.bit_length(), Uuid::const_bit_length());
+    }
+}
@@ -17,7 +17,10 @@ impl<T: Serde> Serde for Vec<T> {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<5>::de(reader)?;
         let length_usize = length_int.get() as usize;
-        let mut output: Vec<T> = Vec::with_capacity(length_usize);
+        // the length may be malformed, so capacity is reserved for no more
+        // items than there are bits left to read
+        let capacity = length_usize.min(reader.bits_remaining() as usize);
+        let mut output: Vec<T> = Vec::with_capacity(capacity);
         for _ in 0..length_usize {
             output.push(T::de(reader)?)
         }
@@ -47,7 +50,8 @@ impl<T: Serde> Serde for VecDeque<T> {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<5>::de(reader)?;
         let length_usize = length_int.get() as usize;
-        let mut output: VecDeque<T> = VecDeque::with_capacity(length_usize);
+        let capacity = length_usize.min(reader.bits_remaining() as usize);
+        let mut output: VecDeque<T> = VecDeque::with_capacity(capacity);
         for _ in 0..length_usize {
             output.push_back(T::de(reader)?)
         }
@@ -69,7 +73,9 @@ impl<T: Serde> Serde for VecDeque<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use crate::{
+        bit_counter::BitCounter, bit_reader::BitReader, bit_writer::BitWriter, serde::Serde,
+    };
     use std::collections::VecDeque;
 
     #[test]
@@ -130,4 +136,14 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn bit_length_vec_deque() {
+        for length in [0, 1, 31, 32, 1000] {
+            let deque: VecDeque<u8> = (0..length).map(|index| index as u8).collect();
+            let mut counter = BitCounter::new(0, 0, u32::MAX);
+            deque.ser(&mut counter);
+            assert_eq!(counter.bits_needed(), deque.bit_length());
+        }
+    }
 }
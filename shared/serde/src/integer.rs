@@ -7,6 +7,12 @@ pub type SignedInteger<const BITS: u8> = SerdeInteger<true, false, BITS>;
 pub type UnsignedVariableInteger<const BITS: u8> = SerdeInteger<false, true, BITS>;
 pub type SignedVariableInteger<const BITS: u8> = SerdeInteger<true, true, BITS>;
 
+/// An integer written in `BITS` bits, plus a sign bit if `SIGNED`. Unless
+/// `VARIABLE`, values which don't fit in the bits panic when created.
+///
+/// Integer fields of a derived `Serde` or `Replicate` type can be marked with
+/// `#[bits(N)]` to be written in exactly N bits this way, sign included,
+/// while keeping their plain integer type.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct SerdeInteger<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> {
     inner: i128,
@@ -14,17 +14,17 @@ mod outgoing_packet;
 mod serde;
 
 pub use bit_counter::BitCounter;
-pub use bit_reader::{BitReader, OwnedBitReader};
+pub use bit_reader::{BitReader, OwnedBitReader, ReaderCheckpoint};
 pub use bit_writer::{BitWrite, BitWriter};
 pub use constants::{MTU_SIZE_BITS, MTU_SIZE_BYTES};
 pub use error::SerdeErr;
 pub use file_bit_writer::FileBitWriter;
 pub use integer::{
-    SerdeIntegerConversion, SignedInteger, SignedVariableInteger, UnsignedInteger,
+    SerdeInteger, SerdeIntegerConversion, SignedInteger, SignedVariableInteger, UnsignedInteger,
     UnsignedVariableInteger,
 };
 pub use outgoing_packet::OutgoingPacket;
 pub use serde::{
     ConstBitLength, Serde, Serde as SerdeInternal, Serde as SerdeBevyShared,
-    Serde as SerdeBevyClient, Serde as SerdeBevyServer, Serde as SerdeHecs,
+    Serde as SerdeBevyClient, Serde as SerdeBevyServer, Serde as SerdeHecs, SerdeEnum,
 };
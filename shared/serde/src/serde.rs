@@ -1,6 +1,11 @@
 use super::{bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr};
 
 /// A trait for objects that can be serialized to a bitstream.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not implement `Serde`",
+    label = "cannot be written to a bitstream",
+    note = "derive it with `#[derive(Serde)]`, or implement `Serde` for it by hand"
+)]
 pub trait Serde: Sized + Clone + PartialEq {
     /// Serialize Self to a BitWriter
     fn ser(&self, writer: &mut dyn BitWrite);
@@ -15,3 +20,27 @@ pub trait Serde: Sized + Clone + PartialEq {
 pub trait ConstBitLength {
     fn const_bit_length() -> u32;
 }
+
+/// Implemented by `#[derive(Serde)]` on enums, so that a value can be diffed
+/// & written one field at a time within its active variant
+pub trait SerdeEnum: Serde {
+    /// The greatest number of fields held by any single variant
+    const MAX_VARIANT_FIELDS: u8;
+
+    /// Returns the indices of the fields of the active variant which differ
+    /// from `other`, or None if `other` holds a different variant
+    fn diff_fields(&self, other: &Self) -> Option<Vec<u8>>;
+
+    /// Writes the active variant's discriminant, then each of its fields for
+    /// which `has_field` returns true. Skipped fields cost a single bit
+    fn ser_fields(&self, has_field: &dyn Fn(u8) -> bool, writer: &mut dyn BitWrite);
+
+    /// Reads fields written by `ser_fields()` into Self. If Self holds a
+    /// different variant than the one written, the fields are discarded
+    fn de_fields(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr>;
+
+    /// Reads fields written by `ser_fields()` and immediately writes them out
+    /// again, used to buffer updates for later
+    fn read_write_fields(reader: &mut BitReader, writer: &mut dyn BitWrite)
+        -> Result<(), SerdeErr>;
+}
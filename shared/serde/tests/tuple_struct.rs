@@ -0,0 +1,43 @@
+use naia_serde::{BitReader, BitWriter, Serde, SerdeInternal};
+
+#[derive(SerdeInternal, Clone, PartialEq, Debug)]
+pub struct Packed(#[bits(10)] pub u16, #[bits(6)] pub i8, pub bool);
+
+#[derive(SerdeInternal, Clone, PartialEq, Debug)]
+pub struct Unpacked(pub u16, pub bool);
+
+// writes a value, returning the bytes & how many bits it took
+fn write<T: Serde>(value: &T) -> (Box<[u8]>, u32) {
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    value.ser(&mut writer);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+#[test]
+fn bits_fields_take_exactly_their_bits() {
+    let packed = Packed(1023, -31, true);
+    let (bytes, bits_written) = write(&packed);
+    assert_eq!(bits_written, 10 + 6 + 1);
+    assert_eq!(packed.bit_length(), 10 + 6 + 1);
+
+    let mut reader = BitReader::new(&bytes);
+    assert_eq!(Packed::de(&mut reader).unwrap(), packed);
+}
+
+#[test]
+#[should_panic(expected = "with 10 bits")]
+fn bits_fields_reject_values_too_wide_for_their_bits() {
+    write(&Packed(1024, 0, true));
+}
+
+#[test]
+fn fields_round_trip_in_order() {
+    let unpacked = Unpacked(40_000, true);
+    let (bytes, bits_written) = write(&unpacked);
+    assert_eq!(bits_written, unpacked.bit_length());
+
+    let mut reader = BitReader::new(&bytes);
+    assert_eq!(Unpacked::de(&mut reader).unwrap(), unpacked);
+}
@@ -38,6 +38,17 @@ impl Timer {
         unsafe { (naia_now() - self.last) > self.duration }
     }
 
+    /// Gets the Duration after which the Timer rings
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration as u64)
+    }
+
+    /// Changes the Duration after which the Timer rings, still counting from
+    /// the last "reset"
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration.as_millis() as f64;
+    }
+
     /// Manually causes the Timer to enter into a "Ringing" state
     pub fn ring_manual(&mut self) {
         self.last -= self.duration;
@@ -1,4 +1,6 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
+
+use naia_socket_shared::Instant;
 
 /// A Timer with a given duration after which it will enter into a "Ringing"
 /// state. The Timer can be reset at an given time, or manually set to start
@@ -26,11 +28,22 @@ impl Timer {
     /// Gets whether or not the Timer is "Ringing" (i.e. the given Duration has
     /// elapsed since the last "reset")
     pub fn ringing(&self) -> bool {
-        Instant::now().saturating_duration_since(self.last) > self.duration
+        self.last.elapsed(&Instant::now()) > self.duration
+    }
+
+    /// Gets the Duration after which the Timer rings
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Changes the Duration after which the Timer rings, still counting from
+    /// the last "reset"
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
     }
 
     /// Manually causes the Timer to enter into a "Ringing" state
     pub fn ring_manual(&mut self) {
-        self.last -= self.duration;
+        self.last.subtract_millis(self.duration.as_millis() as u32);
     }
 }
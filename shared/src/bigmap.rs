@@ -8,22 +8,37 @@ use std::{
     marker::PhantomData,
 };
 
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr, UnsignedVariableInteger};
+
 pub trait BigMapKey: Clone + Copy + Eq + PartialEq + Hash {
     fn to_u64(&self) -> u64;
     fn from_u64(value: u64) -> Self;
 }
 
+#[derive(Clone, PartialEq)]
 pub struct BigMap<K: BigMapKey, V> {
     inner: HashMap<u64, V>,
     current_index: u64,
+    key_stride: u64,
     phantom_k: PhantomData<K>,
 }
 
 impl<K: BigMapKey, V> BigMap<K, V> {
     pub fn new() -> Self {
+        Self::with_key_range(0, 1)
+    }
+
+    /// Creates a map which issues the keys `start`, `start + stride`,
+    /// `start + 2 * stride`, ..., so that maps given the same stride and
+    /// different starts never issue the same key
+    pub fn with_key_range(start: u64, stride: u64) -> Self {
+        if stride == 0 {
+            panic!("BigMap key stride must be greater than 0");
+        }
         Self {
             inner: HashMap::new(),
-            current_index: 0,
+            current_index: start,
+            key_stride: stride,
             phantom_k: PhantomData,
         }
     }
@@ -38,7 +53,7 @@ impl<K: BigMapKey, V> BigMap<K, V> {
 
     pub fn insert(&mut self, value: V) -> K {
         let old_index = self.current_index;
-        self.current_index = self.current_index.wrapping_add(1);
+        self.current_index = self.current_index.wrapping_add(self.key_stride);
 
         self.inner.insert(old_index, value);
 
@@ -71,6 +86,15 @@ impl<K: BigMapKey, V> BigMap<K, V> {
             .map(|(key, value)| (K::from_u64(*key), value));
     }
 
+    /// Iterates over every entry in ascending key order, so that snapshots
+    /// of the same map always come out the same
+    pub fn iter_entries(&self) -> impl Iterator<Item = (K, &V)> {
+        let mut keys: Vec<&u64> = self.inner.keys().collect();
+        keys.sort_unstable();
+        keys.into_iter()
+            .map(|key| (K::from_u64(*key), self.inner.get(key).unwrap()))
+    }
+
     pub fn len(&self) -> usize {
         self.inner.len()
     }
@@ -79,3 +103,51 @@ impl<K: BigMapKey, V> BigMap<K, V> {
         self.inner.is_empty()
     }
 }
+
+// Written as the next key to be issued, the key stride, the entry count, then
+// each key and value, so that a read map hands out exactly the same keys as the
+// original
+impl<K: BigMapKey, V: Serde> Serde for BigMap<K, V> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        UnsignedVariableInteger::<7>::new(self.current_index).ser(writer);
+        UnsignedVariableInteger::<7>::new(self.key_stride).ser(writer);
+        UnsignedVariableInteger::<5>::new(self.len() as u64).ser(writer);
+        for (key, value) in self.iter_entries() {
+            UnsignedVariableInteger::<7>::new(key.to_u64()).ser(writer);
+            value.ser(writer);
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let current_index = UnsignedVariableInteger::<7>::de(reader)?.get() as u64;
+        let key_stride = UnsignedVariableInteger::<7>::de(reader)?.get() as u64;
+        if key_stride == 0 {
+            return Err(SerdeErr);
+        }
+        let length = UnsignedVariableInteger::<5>::de(reader)?.get() as u64;
+        let mut inner = HashMap::new();
+        for _ in 0..length {
+            let key = K::from_u64(UnsignedVariableInteger::<7>::de(reader)?.get() as u64);
+            let value = V::de(reader)?;
+            inner.insert(key.to_u64(), value);
+        }
+        Ok(Self {
+            inner,
+            current_index,
+            key_stride,
+            phantom_k: PhantomData,
+        })
+    }
+
+    fn bit_length(&self) -> u32 {
+        let mut output = 0;
+        output += UnsignedVariableInteger::<7>::new(self.current_index).bit_length();
+        output += UnsignedVariableInteger::<7>::new(self.key_stride).bit_length();
+        output += UnsignedVariableInteger::<5>::new(self.len() as u64).bit_length();
+        for (key, value) in self.iter_entries() {
+            output += UnsignedVariableInteger::<7>::new(key.to_u64()).bit_length();
+            output += value.bit_length();
+        }
+        output
+    }
+}
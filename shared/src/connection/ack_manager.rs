@@ -1,13 +1,15 @@
 use std::{collections::HashMap, hash::Hash};
 
+use naia_socket_shared::Instant;
+
 use crate::{
     messages::message_manager::MessageManager, types::PacketIndex,
     wrapping_number::sequence_greater_than, HostWorldManager, LocalWorldManager,
 };
 
 use super::{
-    packet_notifiable::PacketNotifiable, packet_type::PacketType, sequence_buffer::SequenceBuffer,
-    standard_header::StandardHeader,
+    connection_quality::PacketLossMonitor, packet_notifiable::PacketNotifiable,
+    packet_type::PacketType, sequence_buffer::SequenceBuffer, standard_header::StandardHeader,
 };
 
 pub const REDUNDANT_PACKET_ACKS_SIZE: u16 = 32;
@@ -28,6 +30,11 @@ pub struct AckManager {
     received_packets: SequenceBuffer<ReceivedPacket>,
     // Whether or not we should send an empty ack on the next outgoing packet
     should_send_empty_ack: bool,
+    // Outcomes of sent Data packets, from which packet loss is measured
+    loss_monitor: PacketLossMonitor,
+    // Number of sent packets which were never acknowledged by the remote host
+    #[cfg(feature = "metrics")]
+    dropped_packets: u64,
 }
 
 impl AckManager {
@@ -38,6 +45,9 @@ impl AckManager {
             sent_packets: HashMap::with_capacity(DEFAULT_SEND_PACKETS_SIZE),
             received_packets: SequenceBuffer::with_capacity(REDUNDANT_PACKET_ACKS_SIZE + 1),
             should_send_empty_ack: false,
+            loss_monitor: PacketLossMonitor::new(),
+            #[cfg(feature = "metrics")]
+            dropped_packets: 0,
         }
     }
 
@@ -53,6 +63,16 @@ impl AckManager {
         self.should_send_empty_ack = false;
     }
 
+    /// Get the number of sent packets which the remote host never acknowledged
+    #[cfg(feature = "metrics")]
+    pub fn dropped_packets_count(&self) -> u64 {
+        self.dropped_packets
+    }
+
+    pub fn loss_monitor_mut(&mut self) -> &mut PacketLossMonitor {
+        &mut self.loss_monitor
+    }
+
     /// Get the index of the next outgoing packet
     pub fn next_sender_packet_index(&self) -> PacketIndex {
         self.next_packet_index
@@ -71,6 +91,7 @@ impl AckManager {
         let sender_packet_index = header.sender_packet_index;
         let sender_ack_index = header.sender_ack_index;
         let mut sender_ack_bitfield = header.sender_ack_bitfield;
+        let now = Instant::now();
 
         self.received_packets
             .insert(sender_packet_index, ReceivedPacket {});
@@ -84,6 +105,7 @@ impl AckManager {
         // the current `sender_ack_index` was (clearly) received so we should remove it
         if let Some(sent_packet) = self.sent_packets.get(&sender_ack_index) {
             if sent_packet.packet_type == PacketType::Data {
+                self.loss_monitor.record_delivered(sender_ack_index, &now);
                 self.notify_packet_delivered(
                     sender_ack_index,
                     message_manager,
@@ -104,6 +126,7 @@ impl AckManager {
             if let Some(sent_packet) = self.sent_packets.get(&sent_packet_index) {
                 if sender_ack_bitfield & 1 == 1 {
                     if sent_packet.packet_type == PacketType::Data {
+                        self.loss_monitor.record_delivered(sent_packet_index, &now);
                         self.notify_packet_delivered(
                             sent_packet_index,
                             message_manager,
@@ -115,7 +138,15 @@ impl AckManager {
 
                     self.sent_packets.remove(&sent_packet_index);
                 } else {
+                    if sent_packet.packet_type == PacketType::Data {
+                        self.loss_monitor.record_dropped(sent_packet_index, &now);
+                    }
                     self.sent_packets.remove(&sent_packet_index);
+
+                    #[cfg(feature = "metrics")]
+                    {
+                        self.dropped_packets += 1;
+                    }
                 }
             }
 
@@ -125,6 +156,10 @@ impl AckManager {
 
     /// Records the packet with the given packet index
     fn track_packet(&mut self, packet_type: PacketType, packet_index: PacketIndex) {
+        // only Data packets are acked promptly, so only they measure loss
+        if packet_type == PacketType::Data {
+            self.loss_monitor.record_sent(packet_index, &Instant::now());
+        }
         self.sent_packets
             .insert(packet_index, SentPacket { packet_type });
     }
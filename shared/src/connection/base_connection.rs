@@ -1,4 +1,4 @@
-use std::{hash::Hash, net::SocketAddr};
+use std::{hash::Hash, net::SocketAddr, time::Duration};
 
 use naia_serde::{BitReader, BitWriter, Serde, SerdeErr};
 use naia_socket_shared::Instant;
@@ -17,8 +17,13 @@ use crate::{
 };
 
 use super::{
-    ack_manager::AckManager, connection_config::ConnectionConfig,
-    packet_notifiable::PacketNotifiable, packet_type::PacketType, standard_header::StandardHeader,
+    ack_manager::AckManager,
+    connection_config::ConnectionConfig,
+    connection_quality::{ConnectionQuality, ConnectionQualityLevel, ConnectionQualityThresholds},
+    packet_notifiable::PacketNotifiable,
+    packet_type::PacketType,
+    scope_backlog::{ScopeBacklog, ScopeBacklogConfig},
+    standard_header::StandardHeader,
 };
 
 /// Represents a connection to a remote host, and provides functionality to
@@ -32,6 +37,11 @@ pub struct BaseConnection<E: Copy + Eq + Hash + Send + Sync> {
     heartbeat_timer: Timer,
     timeout_timer: Timer,
     ack_manager: AckManager,
+    quality_thresholds: Option<ConnectionQualityThresholds>,
+    quality_level: ConnectionQualityLevel,
+    scope_backlog_config: Option<ScopeBacklogConfig>,
+    backlogged: bool,
+    max_packets_per_send: Option<u32>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
@@ -48,11 +58,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
             heartbeat_timer: Timer::new(connection_config.heartbeat_interval),
             timeout_timer: Timer::new(connection_config.disconnection_timeout_duration),
             ack_manager: AckManager::new(),
-            message_manager: MessageManager::new(host_type, channel_kinds),
+            message_manager: MessageManager::new(
+                host_type,
+                channel_kinds,
+                connection_config.channel_budget_bytes,
+            ),
             host_world_manager: HostWorldManager::new(address, global_world_manager),
             remote_world_manager: RemoteWorldManager::new(),
-            remote_world_reader: RemoteWorldReader::new(),
+            remote_world_reader: RemoteWorldReader::new(
+                connection_config.stalled_entity_channel_timeout,
+            ),
             local_world_manager: LocalWorldManager::new(user_key),
+            quality_thresholds: connection_config.connection_quality_thresholds.clone(),
+            quality_level: ConnectionQualityLevel::Good,
+            scope_backlog_config: connection_config.scope_backlog.clone(),
+            backlogged: false,
+            max_packets_per_send: connection_config.max_packets_per_send,
         }
     }
 
@@ -84,6 +105,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
         self.timeout_timer.ringing()
     }
 
+    /// Sets how long this connection may go without hearing from the remote
+    /// host before it is dropped, in place of the ConnectionConfig's
+    pub fn set_timeout_duration(&mut self, duration: Duration) {
+        self.timeout_timer.set_duration(duration);
+    }
+
     // Acks & Headers
 
     pub fn mark_should_send_empty_ack(&mut self) {
@@ -121,6 +148,82 @@ impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
             .ser(writer);
     }
 
+    /// Get the number of sent packets which the remote host never acknowledged
+    #[cfg(feature = "metrics")]
+    pub fn dropped_packets_count(&self) -> u64 {
+        self.ack_manager.dropped_packets_count()
+    }
+
+    // Quality
+
+    /// Measures this connection's quality, combining packet loss seen by the
+    /// AckManager with the given round trip time, jitter and byte rates
+    pub fn connection_quality(
+        &mut self,
+        rtt_ms: f32,
+        jitter_ms: f32,
+        bytes_in_per_s: f32,
+        bytes_out_per_s: f32,
+    ) -> ConnectionQuality {
+        let now = Instant::now();
+        let loss_monitor = self.ack_manager.loss_monitor_mut();
+        loss_monitor.expire(&now, rtt_ms, jitter_ms);
+        ConnectionQuality {
+            loss_pct_1s: loss_monitor.loss_pct_1s(&now),
+            loss_pct_10s: loss_monitor.loss_pct_10s(&now),
+            rtt_ms,
+            jitter_ms,
+            bytes_in_per_s,
+            bytes_out_per_s,
+        }
+    }
+
+    /// Grades the given quality against the configured thresholds, returning
+    /// the new level if it differs from the last one graded
+    pub fn update_quality_level(
+        &mut self,
+        quality: &ConnectionQuality,
+    ) -> Option<ConnectionQualityLevel> {
+        let thresholds = self.quality_thresholds.as_ref()?;
+        let level = quality.level(thresholds);
+        if level == self.quality_level {
+            return None;
+        }
+        self.quality_level = level;
+        Some(level)
+    }
+
+    /// Whether another data packet may be sent, once `packets_sent` have been
+    /// sent this time updates are sent
+    pub fn may_send_packet(&self, packets_sent: u32) -> bool {
+        self.max_packets_per_send
+            .map_or(true, |max_packets| packets_sent < max_packets)
+    }
+
+    // Scope Backlog
+
+    /// Measures how far behind this connection is in delivering Entity
+    /// actions to the remote host
+    pub fn scope_backlog(&self) -> ScopeBacklog {
+        self.host_world_manager.action_backlog(&Instant::now())
+    }
+
+    /// Checks the backlog against the configured thresholds, returning it if
+    /// the connection has just become backlogged. Despawns are prioritized
+    /// for as long as it stays so, if configured to be
+    pub fn update_scope_backlog(&mut self) -> Option<ScopeBacklog> {
+        let config = self.scope_backlog_config.as_ref()?;
+        let backlog = self.scope_backlog();
+        let backlogged = backlog.exceeds(config);
+        self.host_world_manager
+            .set_prioritize_despawns(backlogged && config.prioritize_despawns);
+        if backlogged == self.backlogged {
+            return None;
+        }
+        self.backlogged = backlogged;
+        backlogged.then_some(backlog)
+    }
+
     /// Get the next outgoing packet's index
     pub fn next_packet_index(&self) -> PacketIndex {
         self.ack_manager.next_sender_packet_index()
@@ -212,6 +315,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
             self.remote_world_reader.read_world_events(
                 global_world_manager,
                 &mut self.local_world_manager,
+                self.remote_world_manager.ignored_entities(),
                 protocol,
                 client_tick,
                 reader,
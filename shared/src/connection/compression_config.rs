@@ -1,3 +1,6 @@
+#[cfg(feature = "zstd_support")]
+use super::connection_quality::ConnectionQuality;
+
 #[derive(Clone)]
 pub struct CompressionConfig {
     pub server_to_client: Option<CompressionMode>,
@@ -16,7 +19,7 @@ impl CompressionConfig {
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CompressionMode {
     /// Compression mode using default zstd dictionary.
     /// 1st i32 parameter here is the compression level from -7 (fastest) to 22
@@ -31,4 +34,94 @@ pub enum CompressionMode {
     /// (packets) to train on. Obviously, the more samples trained on, the
     /// better theoretical compression.
     Training(usize),
+    /// Compression decided per packet, using the default zstd dictionary.
+    /// Packets smaller than `min_packet_size` bytes, or which compression
+    /// wouldn't shrink, are sent as they are. The rest are compressed at a
+    /// level within `level_range` (fastest, smallest): the fastest while the
+    /// connection has bandwidth to spare, rising towards the smallest as its
+    /// outgoing bytes per second near `bandwidth_cap` or its packets start
+    /// being lost. Every packet is prefixed with a byte saying whether it
+    /// was compressed, so both ends must use this mode.
+    Adaptive {
+        min_packet_size: usize,
+        level_range: (i32, i32),
+        /// Bytes per second a connection is expected to carry at most. If
+        /// None, only packet loss raises the level
+        bandwidth_cap: Option<u32>,
+    },
+}
+
+// Packet loss, as a percentage over the last second, at which Adaptive
+// compression uses its smallest level
+#[cfg(feature = "zstd_support")]
+const ADAPTIVE_MAX_LOSS_PCT: f32 = 10.0;
+
+// Adaptive framing: each packet starts with a byte holding the framing version
+// in its upper bits & whether the rest of the packet is compressed in its
+// lowest bit. Bump the version whenever the framing changes
+const ADAPTIVE_FRAMING_VERSION: u8 = 1;
+
+/// Picks the Adaptive compression level for a connection of the given
+/// quality, by how close it is to its bandwidth cap or to heavy packet loss
+#[cfg(feature = "zstd_support")]
+pub(crate) fn adaptive_level(
+    level_range: (i32, i32),
+    bandwidth_cap: Option<u32>,
+    quality: &ConnectionQuality,
+) -> i32 {
+    let (fastest, smallest) = level_range;
+    let bandwidth_pressure = match bandwidth_cap {
+        Some(cap) if cap > 0 => quality.bytes_out_per_s / cap as f32,
+        _ => 0.0,
+    };
+    let loss_pressure = quality.loss_pct_1s / ADAPTIVE_MAX_LOSS_PCT;
+    let pressure = bandwidth_pressure.max(loss_pressure).clamp(0.0, 1.0);
+    fastest + ((smallest - fastest) as f32 * pressure).round() as i32
+}
+
+/// The first byte of a packet sent in Adaptive mode
+pub(crate) fn adaptive_flag(compressed: bool) -> u8 {
+    (ADAPTIVE_FRAMING_VERSION << 1) | compressed as u8
+}
+
+/// Reads the first byte of a packet sent in Adaptive mode: whether the rest
+/// is compressed, or None if it was framed by an incompatible version
+pub(crate) fn read_adaptive_flag(flag: u8) -> Option<bool> {
+    if flag >> 1 != ADAPTIVE_FRAMING_VERSION {
+        return None;
+    }
+    Some(flag & 1 == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "zstd_support")]
+    fn quality(bytes_out_per_s: f32, loss_pct_1s: f32) -> ConnectionQuality {
+        ConnectionQuality {
+            bytes_out_per_s,
+            loss_pct_1s,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "zstd_support")]
+    #[test]
+    fn level_rises_with_bandwidth_and_loss() {
+        let range = (1, 19);
+        let cap = Some(10_000);
+        assert_eq!(adaptive_level(range, cap, &quality(0.0, 0.0)), 1);
+        assert_eq!(adaptive_level(range, cap, &quality(5_000.0, 0.0)), 10);
+        assert_eq!(adaptive_level(range, cap, &quality(50_000.0, 0.0)), 19);
+        assert_eq!(adaptive_level(range, cap, &quality(0.0, 5.0)), 10);
+        assert_eq!(adaptive_level(range, None, &quality(50_000.0, 0.0)), 1);
+    }
+
+    #[test]
+    fn flag_round_trips() {
+        assert_eq!(read_adaptive_flag(adaptive_flag(true)), Some(true));
+        assert_eq!(read_adaptive_flag(adaptive_flag(false)), Some(false));
+        assert_eq!(read_adaptive_flag(0), None);
+    }
 }
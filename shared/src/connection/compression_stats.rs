@@ -0,0 +1,40 @@
+/// Totals of the bytes given to an Encoder & the bytes it produced from them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompressionStats {
+    pub input_bytes: u64,
+    pub output_bytes: u64,
+    /// `output_bytes / input_bytes`, so lower is better. 1.0 until anything
+    /// has been encoded
+    pub ratio: f32,
+}
+
+impl CompressionStats {
+    pub fn new() -> Self {
+        Self {
+            input_bytes: 0,
+            output_bytes: 0,
+            ratio: 1.0,
+        }
+    }
+
+    /// Record that a payload of `input_bytes` was encoded into `output_bytes`
+    pub fn record(&mut self, input_bytes: usize, output_bytes: usize) {
+        self.input_bytes += input_bytes as u64;
+        self.output_bytes += output_bytes as u64;
+        if self.input_bytes > 0 {
+            self.ratio = self.output_bytes as f32 / self.input_bytes as f32;
+        }
+    }
+
+    /// How many bytes compression has saved. Negative if it has grown the
+    /// payloads instead
+    pub fn bytes_saved(&self) -> i64 {
+        self.input_bytes as i64 - self.output_bytes as i64
+    }
+}
+
+impl Default for CompressionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
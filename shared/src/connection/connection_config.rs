@@ -1,4 +1,13 @@
-use std::{default::Default, time::Duration};
+use std::{default::Default, sync::Arc, time::Duration};
+
+use naia_socket_shared::Clock;
+
+use crate::connection::{
+    connection_quality::ConnectionQualityThresholds, encryption::EncryptionConfig,
+    scope_backlog::ScopeBacklogConfig,
+};
+
+const DEFAULT_CHANNEL_BUDGET_BYTES: u32 = 128;
 
 /// Contains Config properties which will be used by a Server or Client
 #[derive(Clone, Debug)]
@@ -12,6 +21,43 @@ pub struct ConnectionConfig {
     /// The duration over which to measure bandwidth. Set to None to avoid
     /// measure bandwidth at all.
     pub bandwidth_measure_duration: Option<Duration>,
+    /// The Clock to read the current time from, in place of the system clock.
+    /// It is installed on the thread which creates the Server or Client, so
+    /// that thread should also be the one to drive it. Set to None to use the
+    /// system clock.
+    pub clock: Option<Arc<dyn Clock>>,
+    /// Encrypts packets after the handshake, using keys exchanged during it.
+    /// Must match between the Server and Client. Set to None to send packets
+    /// in plaintext.
+    pub encryption: Option<EncryptionConfig>,
+    /// The duration an Entity's channel may hold a received action back,
+    /// waiting on an earlier action, before a `RemoteWorldError::StalledChannel`
+    /// is reported. Set to None to wait indefinitely.
+    pub stalled_entity_channel_timeout: Option<Duration>,
+    /// The bytes of a packet each Channel may fill while other Channels also
+    /// have messages waiting. Channels take turns at filling the rest of the
+    /// packet, so a single busy Channel still gets the whole packet. Set to
+    /// None to let Channels fill packets one after another, unlimited.
+    pub channel_budget_bytes: Option<u32>,
+    /// The packet loss and round trip time at which a connection is graded as
+    /// Degraded or Bad, firing a `ConnectionQualityChangedEvent` whenever its
+    /// grade changes. Set to None to never fire the event; the connection's
+    /// quality can still be read at any time.
+    pub connection_quality_thresholds: Option<ConnectionQualityThresholds>,
+    /// Suffixes every packet with a CRC-32, dropping received packets which
+    /// were corrupted in transit. Must match between the Server and Client.
+    /// Worth enabling over raw UDP, but unnecessary over WebRTC, which has
+    /// integrity checks of its own.
+    pub packet_checksums: bool,
+    /// The most data packets sent to the remote host each time updates are
+    /// sent, capping the connection's bandwidth. Whatever doesn't fit waits
+    /// for a later send. Set to None to send everything pending at once.
+    pub max_packets_per_send: Option<u32>,
+    /// When the Server counts a User's connection as backlogged with Entity
+    /// actions, firing a `ScopeBacklogEvent` each time it becomes so. Set to
+    /// None to never fire the event; the backlog can still be read at any
+    /// time.
+    pub scope_backlog: Option<ScopeBacklogConfig>,
 }
 
 impl ConnectionConfig {
@@ -25,6 +71,14 @@ impl ConnectionConfig {
             disconnection_timeout_duration,
             heartbeat_interval,
             bandwidth_measure_duration,
+            clock: None,
+            encryption: None,
+            stalled_entity_channel_timeout: None,
+            channel_budget_bytes: Some(DEFAULT_CHANNEL_BUDGET_BYTES),
+            connection_quality_thresholds: Some(ConnectionQualityThresholds::default()),
+            packet_checksums: false,
+            max_packets_per_send: None,
+            scope_backlog: Some(ScopeBacklogConfig::default()),
         }
     }
 }
@@ -35,6 +89,14 @@ impl Default for ConnectionConfig {
             disconnection_timeout_duration: Duration::from_secs(30),
             heartbeat_interval: Duration::from_secs(4),
             bandwidth_measure_duration: None,
+            clock: None,
+            encryption: None,
+            stalled_entity_channel_timeout: None,
+            channel_budget_bytes: Some(DEFAULT_CHANNEL_BUDGET_BYTES),
+            connection_quality_thresholds: Some(ConnectionQualityThresholds::default()),
+            packet_checksums: false,
+            max_packets_per_send: None,
+            scope_backlog: Some(ScopeBacklogConfig::default()),
         }
     }
 }
@@ -0,0 +1,43 @@
+use naia_serde::MTU_SIZE_BYTES;
+
+use super::compression_config::{CompressionConfig, CompressionMode};
+
+/// The parameters a connection was established with, reported once it is
+/// ready so that sending can be adapted to them from the start
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionParams {
+    /// How packets sent over the connection are compressed, if at all
+    pub compression: Option<CompressionMode>,
+    /// The most bytes a packet's payload may take
+    pub mtu_bytes: usize,
+    /// The round trip time in milliseconds, if it was measured while
+    /// connecting
+    pub initial_rtt: Option<f32>,
+}
+
+impl ConnectionParams {
+    /// `compression` is the mode of whichever direction this end sends in
+    pub fn new(compression: Option<&CompressionMode>, initial_rtt: Option<f32>) -> Self {
+        Self {
+            compression: compression.cloned(),
+            mtu_bytes: MTU_SIZE_BYTES,
+            initial_rtt,
+        }
+    }
+
+    /// The parameters of the Server's end of a connection
+    pub fn server(compression: Option<&CompressionConfig>, initial_rtt: Option<f32>) -> Self {
+        Self::new(
+            compression.and_then(|config| config.server_to_client.as_ref()),
+            initial_rtt,
+        )
+    }
+
+    /// The parameters of the Client's end of a connection
+    pub fn client(compression: Option<&CompressionConfig>, initial_rtt: Option<f32>) -> Self {
+        Self::new(
+            compression.and_then(|config| config.client_to_server.as_ref()),
+            initial_rtt,
+        )
+    }
+}
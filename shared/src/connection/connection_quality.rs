@@ -0,0 +1,300 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use naia_socket_shared::Instant;
+
+use crate::types::PacketIndex;
+
+const SHORT_LOSS_WINDOW: Duration = Duration::from_secs(1);
+const LONG_LOSS_WINDOW: Duration = Duration::from_secs(10);
+const BYTE_RATE_WINDOW: Duration = Duration::from_secs(1);
+// Acks ride on the remote host's next outgoing packet, so even on a fast link a
+// packet may wait a little longer than the round trip to be acknowledged
+const MIN_IN_FLIGHT_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// A rolling summary of a connection's health, refreshed as packets are sent,
+/// acknowledged and received
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ConnectionQuality {
+    /// Percentage of sent packets lost over the last second
+    pub loss_pct_1s: f32,
+    /// Percentage of sent packets lost over the last ten seconds
+    pub loss_pct_10s: f32,
+    /// Average round trip time, in milliseconds
+    pub rtt_ms: f32,
+    /// Average jitter, in milliseconds
+    pub jitter_ms: f32,
+    /// Bytes received over the last second
+    pub bytes_in_per_s: f32,
+    /// Bytes sent over the last second
+    pub bytes_out_per_s: f32,
+}
+
+impl ConnectionQuality {
+    /// Grades this connection against the given thresholds. Loss is judged
+    /// over the last second, so the level recovers soon after a burst of loss
+    pub fn level(&self, thresholds: &ConnectionQualityThresholds) -> ConnectionQualityLevel {
+        if self.loss_pct_1s >= thresholds.bad_loss_pct || self.rtt_ms >= thresholds.bad_rtt_ms {
+            ConnectionQualityLevel::Bad
+        } else if self.loss_pct_1s >= thresholds.degraded_loss_pct
+            || self.rtt_ms >= thresholds.degraded_rtt_ms
+        {
+            ConnectionQualityLevel::Degraded
+        } else {
+            ConnectionQualityLevel::Good
+        }
+    }
+}
+
+/// A coarse grade of a connection's health
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ConnectionQualityLevel {
+    #[default]
+    Good,
+    Degraded,
+    Bad,
+}
+
+/// The packet loss and round trip time at which a connection is graded as
+/// Degraded or Bad
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionQualityThresholds {
+    /// Percentage of packets lost at which a connection becomes Degraded
+    pub degraded_loss_pct: f32,
+    /// Round trip time, in milliseconds, at which a connection becomes Degraded
+    pub degraded_rtt_ms: f32,
+    /// Percentage of packets lost at which a connection becomes Bad
+    pub bad_loss_pct: f32,
+    /// Round trip time, in milliseconds, at which a connection becomes Bad
+    pub bad_rtt_ms: f32,
+}
+
+impl Default for ConnectionQualityThresholds {
+    fn default() -> Self {
+        Self {
+            degraded_loss_pct: 5.0,
+            degraded_rtt_ms: 250.0,
+            bad_loss_pct: 15.0,
+            bad_rtt_ms: 500.0,
+        }
+    }
+}
+
+/// Measures the share of sent Data packets which the remote host never
+/// acknowledged. Packets still awaiting an ack are left out until they are
+/// acked, reported dropped, or have waited too long to still be in flight
+#[derive(Default)]
+pub struct PacketLossMonitor {
+    in_flight: HashMap<PacketIndex, Instant>,
+    // (resolved at, was lost)
+    outcomes: VecDeque<(Instant, bool)>,
+}
+
+impl PacketLossMonitor {
+    pub fn new() -> Self {
+        Self {
+            in_flight: HashMap::new(),
+            outcomes: VecDeque::new(),
+        }
+    }
+
+    pub fn record_sent(&mut self, packet_index: PacketIndex, now: &Instant) {
+        self.in_flight.insert(packet_index, now.clone());
+    }
+
+    pub fn record_delivered(&mut self, packet_index: PacketIndex, now: &Instant) {
+        self.resolve(packet_index, now, false);
+    }
+
+    pub fn record_dropped(&mut self, packet_index: PacketIndex, now: &Instant) {
+        self.resolve(packet_index, now, true);
+    }
+
+    /// Counts packets which have waited longer than a couple of round trips
+    /// for an ack as lost, and forgets outcomes too old to be reported
+    pub fn expire(&mut self, now: &Instant, rtt_ms: f32, jitter_ms: f32) {
+        let in_flight_timeout =
+            Duration::from_secs_f32(((rtt_ms * 2.0 + jitter_ms * 4.0) / 1000.0).max(0.0))
+                .max(MIN_IN_FLIGHT_TIMEOUT);
+        let mut expired = Vec::new();
+        for (packet_index, sent_at) in &self.in_flight {
+            if sent_at.elapsed(now) > in_flight_timeout {
+                expired.push(*packet_index);
+            }
+        }
+        for packet_index in expired {
+            self.resolve(packet_index, now, true);
+        }
+
+        while let Some((resolved_at, _)) = self.outcomes.front() {
+            if resolved_at.elapsed(now) <= LONG_LOSS_WINDOW {
+                break;
+            }
+            self.outcomes.pop_front();
+        }
+    }
+
+    /// Percentage of the packets resolved within `window` which were lost
+    pub fn loss_pct(&self, now: &Instant, window: Duration) -> f32 {
+        let mut resolved = 0;
+        let mut lost = 0;
+        for (resolved_at, was_lost) in self.outcomes.iter().rev() {
+            if resolved_at.elapsed(now) > window {
+                break;
+            }
+            resolved += 1;
+            if *was_lost {
+                lost += 1;
+            }
+        }
+        if resolved == 0 {
+            return 0.0;
+        }
+        lost as f32 * 100.0 / resolved as f32
+    }
+
+    pub fn loss_pct_1s(&self, now: &Instant) -> f32 {
+        self.loss_pct(now, SHORT_LOSS_WINDOW)
+    }
+
+    pub fn loss_pct_10s(&self, now: &Instant) -> f32 {
+        self.loss_pct(now, LONG_LOSS_WINDOW)
+    }
+
+    fn resolve(&mut self, packet_index: PacketIndex, now: &Instant, was_lost: bool) {
+        if self.in_flight.remove(&packet_index).is_some() {
+            self.outcomes.push_back((now.clone(), was_lost));
+        }
+    }
+}
+
+/// Measures the bytes passing through a connection over the last second
+#[derive(Default)]
+pub struct ByteRateMonitor {
+    samples: VecDeque<(Instant, usize)>,
+    total_bytes: usize,
+}
+
+impl ByteRateMonitor {
+    pub fn new() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            total_bytes: 0,
+        }
+    }
+
+    pub fn record_packet(&mut self, bytes: usize) {
+        let now = Instant::now();
+        self.clear_expired(&now);
+
+        self.total_bytes += bytes;
+        self.samples.push_back((now, bytes));
+    }
+
+    pub fn bytes_per_second(&mut self) -> f32 {
+        self.clear_expired(&Instant::now());
+
+        self.total_bytes as f32 / BYTE_RATE_WINDOW.as_secs_f32()
+    }
+
+    fn clear_expired(&mut self, now: &Instant) {
+        while let Some((recorded_at, bytes)) = self.samples.front() {
+            if recorded_at.elapsed(now) <= BYTE_RATE_WINDOW {
+                break;
+            }
+            self.total_bytes -= bytes;
+            self.samples.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_socket_shared::Instant;
+
+    use super::{
+        ConnectionQuality, ConnectionQualityLevel, ConnectionQualityThresholds, PacketLossMonitor,
+    };
+
+    fn later(instant: &Instant, millis: u32) -> Instant {
+        let mut later = instant.clone();
+        later.add_millis(millis);
+        later
+    }
+
+    #[test]
+    fn in_flight_packets_are_not_counted_as_lost() {
+        let start = Instant::now();
+        let mut monitor = PacketLossMonitor::new();
+        for packet_index in 0..4 {
+            monitor.record_sent(packet_index, &start);
+        }
+        monitor.record_delivered(0, &later(&start, 50));
+        monitor.record_dropped(1, &later(&start, 50));
+
+        let now = later(&start, 100);
+        monitor.expire(&now, 50.0, 5.0);
+        assert_eq!(monitor.loss_pct_1s(&now), 50.0);
+    }
+
+    #[test]
+    fn packets_unacked_past_the_timeout_are_lost() {
+        let start = Instant::now();
+        let mut monitor = PacketLossMonitor::new();
+        monitor.record_sent(0, &start);
+        monitor.record_sent(1, &start);
+        monitor.record_delivered(0, &later(&start, 50));
+
+        let now = later(&start, 500);
+        monitor.expire(&now, 50.0, 5.0);
+        assert_eq!(monitor.loss_pct_1s(&now), 50.0);
+
+        // a late ack no longer changes the outcome
+        monitor.record_delivered(1, &now);
+        assert_eq!(monitor.loss_pct_1s(&now), 50.0);
+    }
+
+    #[test]
+    fn loss_leaves_the_short_window_first() {
+        let start = Instant::now();
+        let mut monitor = PacketLossMonitor::new();
+        monitor.record_sent(0, &start);
+        monitor.record_dropped(0, &start);
+        monitor.record_sent(1, &later(&start, 2000));
+        monitor.record_delivered(1, &later(&start, 2000));
+
+        let now = later(&start, 2000);
+        monitor.expire(&now, 50.0, 5.0);
+        assert_eq!(monitor.loss_pct_1s(&now), 0.0);
+        assert_eq!(monitor.loss_pct_10s(&now), 50.0);
+
+        let now = later(&start, 11000);
+        monitor.expire(&now, 50.0, 5.0);
+        assert_eq!(monitor.loss_pct_10s(&now), 0.0);
+    }
+
+    #[test]
+    fn level_is_the_worst_of_loss_and_rtt() {
+        let thresholds = ConnectionQualityThresholds::default();
+        let quality = |loss_pct_1s, rtt_ms| ConnectionQuality {
+            loss_pct_1s,
+            rtt_ms,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            quality(0.0, 20.0).level(&thresholds),
+            ConnectionQualityLevel::Good
+        );
+        assert_eq!(
+            quality(10.0, 20.0).level(&thresholds),
+            ConnectionQualityLevel::Degraded
+        );
+        assert_eq!(
+            quality(10.0, 600.0).level(&thresholds),
+            ConnectionQualityLevel::Bad
+        );
+    }
+}
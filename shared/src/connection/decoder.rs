@@ -3,18 +3,25 @@ cfg_if! {
     {
         use zstd::bulk::Decompressor;
 
-        use super::compression_config::CompressionMode;
+        use super::{
+            compression_config::{read_adaptive_flag, CompressionMode},
+            encryption::DecoderError,
+        };
+
+        const MAX_DECOMPRESSED_BYTES: u64 = u16::MAX as u64;
 
         pub struct Decoder {
             result: Vec<u8>,
             decoder: Option<Decompressor<'static>>,
+            adaptive: bool,
         }
 
         impl Decoder {
             pub fn new(compression_mode: CompressionMode) -> Self {
+                let adaptive = matches!(compression_mode, CompressionMode::Adaptive { .. });
                 let decoder = match compression_mode {
                     CompressionMode::Training(_) => None,
-                    CompressionMode::Default(_) => {
+                    CompressionMode::Default(_) | CompressionMode::Adaptive { .. } => {
                         Some(Decompressor::new().expect("error creating Decompressor"))
                     }
                     CompressionMode::Dictionary(_, dictionary) => Some(
@@ -25,44 +32,77 @@ cfg_if! {
                 Self {
                     decoder,
                     result: Vec::new(),
+                    adaptive,
                 }
             }
 
-            pub fn decode(&mut self, payload: &[u8]) -> &[u8] {
+            pub fn decode(&mut self, payload: &[u8]) -> Result<&[u8], DecoderError> {
+                let mut payload = payload;
+                if self.adaptive {
+                    let Some((flag, rest)) = payload.split_first() else {
+                        return Err(DecoderError::Malformed);
+                    };
+                    let Some(compressed) = read_adaptive_flag(*flag) else {
+                        return Err(DecoderError::Malformed);
+                    };
+                    if !compressed {
+                        self.result = rest.to_vec();
+                        return Ok(&self.result);
+                    }
+                    payload = rest;
+                }
+
                 if let Some(decoder) = &mut self.decoder {
+                    // the Compressor writes each packet's size into its frame,
+                    // which can't be trusted beyond the largest datagram
+                    let capacity = zstd::zstd_safe::get_frame_content_size(payload)
+                        .ok()
+                        .flatten()
+                        .filter(|size| *size <= MAX_DECOMPRESSED_BYTES)
+                        .ok_or(DecoderError::Malformed)? as usize;
                     self.result = decoder
-                        .decompress(
-                            payload,
-                            Decompressor::<'static>::upper_bound(payload)
-                                .expect("upper bound decode error"),
-                        )
-                        .expect("decode error");
-                    return &self.result;
+                        .decompress(payload, capacity)
+                        .map_err(|_| DecoderError::Malformed)?;
                 } else {
                     self.result = payload.to_vec();
-                    return &self.result;
                 }
+                Ok(&self.result)
             }
         }
     }
     else
     {
-        use super::compression_config::CompressionMode;
+        use super::{
+            compression_config::{read_adaptive_flag, CompressionMode},
+            encryption::DecoderError,
+        };
 
         pub struct Decoder {
             result: Vec<u8>,
+            adaptive: bool,
         }
 
         impl Decoder {
-            pub fn new(_: CompressionMode) -> Self {
+            pub fn new(compression_mode: CompressionMode) -> Self {
                 Self {
                     result: Vec::new(),
+                    adaptive: matches!(compression_mode, CompressionMode::Adaptive { .. }),
                 }
             }
 
-            pub fn decode(&mut self, payload: &[u8]) -> &[u8] {
+            pub fn decode(&mut self, payload: &[u8]) -> Result<&[u8], DecoderError> {
+                let mut payload = payload;
+                if self.adaptive {
+                    // compressed packets can't be read without zstd
+                    match payload.split_first() {
+                        Some((flag, rest)) if read_adaptive_flag(*flag) == Some(false) => {
+                            payload = rest;
+                        }
+                        _ => return Err(DecoderError::Malformed),
+                    }
+                }
                 self.result = payload.to_vec();
-                &self.result
+                Ok(&self.result)
             }
         }
     }
@@ -7,11 +7,16 @@ cfg_if! {
 
         use zstd::{bulk::Compressor, dict::from_continuous};
 
-        use super::compression_config::CompressionMode;
+        use super::{
+            compression_config::{adaptive_flag, adaptive_level, CompressionMode},
+            compression_stats::CompressionStats,
+            connection_quality::ConnectionQuality,
+        };
 
         pub struct Encoder {
             result: Vec<u8>,
             encoder: EncoderType,
+            stats: CompressionStats,
         }
 
         impl Encoder {
@@ -27,33 +32,91 @@ cfg_if! {
                         Compressor::with_dictionary(compression_level, &dictionary)
                             .expect("error creating Compressor with dictionary"),
                     ),
+                    CompressionMode::Adaptive {
+                        min_packet_size,
+                        level_range,
+                        bandwidth_cap,
+                    } => EncoderType::Adaptive(AdaptiveCompressor {
+                        compressor: Compressor::new(level_range.0)
+                            .expect("error creating Compressor"),
+                        level: level_range.0,
+                        min_packet_size,
+                        level_range,
+                        bandwidth_cap,
+                    }),
                 };
 
                 Self {
                     result: Vec::new(),
                     encoder,
+                    stats: CompressionStats::new(),
                 }
             }
 
             pub fn encode(&mut self, payload: &[u8]) -> &[u8] {
-                // TODO: only use compressed packet if the resulting size would be less!
+                self.encode_for(payload, &ConnectionQuality::default())
+            }
+
+            /// Encodes a packet for a connection of the given quality, which
+            /// picks the level in `CompressionMode::Adaptive`
+            pub fn encode_for(&mut self, payload: &[u8], quality: &ConnectionQuality) -> &[u8] {
                 match &mut self.encoder {
                     EncoderType::DictionaryTrainer(trainer) => {
                         trainer.record_bytes(payload);
                         self.result = payload.to_vec();
-                        return &self.result;
                     }
                     EncoderType::Compressor(encoder) => {
                         self.result = encoder.compress(payload).expect("encode error");
-                        return &self.result;
+                    }
+                    EncoderType::Adaptive(encoder) => {
+                        encoder.encode(payload, quality, &mut self.result);
                     }
                 }
+                self.stats.record(payload.len(), self.result.len());
+                return &self.result;
+            }
+
+            /// Totals of the bytes encoded so far
+            pub fn stats(&self) -> CompressionStats {
+                self.stats
             }
         }
 
         pub enum EncoderType {
             Compressor(Compressor<'static>),
             DictionaryTrainer(DictionaryTrainer),
+            Adaptive(AdaptiveCompressor),
+        }
+
+        pub struct AdaptiveCompressor {
+            compressor: Compressor<'static>,
+            level: i32,
+            min_packet_size: usize,
+            level_range: (i32, i32),
+            bandwidth_cap: Option<u32>,
+        }
+
+        impl AdaptiveCompressor {
+            fn encode(&mut self, payload: &[u8], quality: &ConnectionQuality, result: &mut Vec<u8>) {
+                result.clear();
+                if payload.len() >= self.min_packet_size {
+                    let level = adaptive_level(self.level_range, self.bandwidth_cap, quality);
+                    if level != self.level {
+                        self.compressor
+                            .set_compression_level(level)
+                            .expect("error setting compression level");
+                        self.level = level;
+                    }
+                    let compressed = self.compressor.compress(payload).expect("encode error");
+                    if compressed.len() < payload.len() {
+                        result.push(adaptive_flag(true));
+                        result.extend_from_slice(&compressed);
+                        return;
+                    }
+                }
+                result.push(adaptive_flag(false));
+                result.extend_from_slice(payload);
+            }
         }
 
         pub struct DictionaryTrainer {
@@ -124,23 +187,47 @@ cfg_if! {
     }
     else
     {
-        use super::compression_config::CompressionMode;
+        use super::{
+            compression_config::{adaptive_flag, CompressionMode},
+            compression_stats::CompressionStats,
+            connection_quality::ConnectionQuality,
+        };
 
         pub struct Encoder {
-            result: Vec<u8>
+            result: Vec<u8>,
+            adaptive: bool,
+            stats: CompressionStats,
         }
 
         impl Encoder {
-            pub fn new(_: CompressionMode) -> Self {
+            pub fn new(compression_mode: CompressionMode) -> Self {
                 Self {
                     result: Vec::new(),
+                    adaptive: matches!(compression_mode, CompressionMode::Adaptive { .. }),
+                    stats: CompressionStats::new(),
                 }
             }
 
             pub fn encode(&mut self, payload: &[u8]) -> &[u8] {
-                self.result = payload.to_vec();
+                self.encode_for(payload, &ConnectionQuality::default())
+            }
+
+            /// Encodes a packet for a connection of the given quality. Without
+            /// zstd nothing is compressed, but Adaptive framing is kept
+            pub fn encode_for(&mut self, payload: &[u8], _: &ConnectionQuality) -> &[u8] {
+                self.result.clear();
+                if self.adaptive {
+                    self.result.push(adaptive_flag(false));
+                }
+                self.result.extend_from_slice(payload);
+                self.stats.record(payload.len(), self.result.len());
                 &self.result
             }
+
+            /// Totals of the bytes encoded so far
+            pub fn stats(&self) -> CompressionStats {
+                self.stats
+            }
         }
     }
 }
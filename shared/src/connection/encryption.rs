@@ -0,0 +1,361 @@
+//! Optional encryption of packets, enabled by setting
+//! `ConnectionConfig::encryption` on both the Server and the Client.
+//!
+//! Each connection performs an X25519 key exchange during the handshake (the
+//! Client's public key rides on the `ClientConnectRequest`, the Server's on
+//! the `ServerConnectResponse`), and derives one ChaCha20-Poly1305 key per
+//! direction from the shared secret.
+//!
+//! When encryption is enabled, every packet is prefixed with a 1-byte marker:
+//! - `0`: the rest of the packet is plaintext. Only handshake packets are
+//!   accepted in plaintext once a connection's keys are established.
+//! - `1`: followed by a little-endian u64 nonce counter, then the sealed
+//!   (possibly compressed) packet, including its StandardHeader, and a 16-byte
+//!   authentication tag. The marker and counter are authenticated too.
+//!
+//! PacketIndex wraps too quickly to be used as a nonce, so the counter also
+//! provides replay protection: packets older than a 64-packet window, or
+//! already seen within it, are rejected.
+//!
+//! The 25 bytes of overhead fit within the DTLS allowance of `MTU_SIZE_BYTES`.
+
+use std::{error::Error, fmt};
+
+use naia_serde::BitReader;
+
+use crate::{
+    connection::{packet_type::PacketType, standard_header::StandardHeader},
+    Serde,
+};
+
+const PLAINTEXT_MARKER: u8 = 0;
+const ENCRYPTED_MARKER: u8 = 1;
+
+/// Enables encryption of packets, which must be set on both the Server and
+/// the Client. Requires the `encryption` feature.
+#[derive(Clone, Debug, Default)]
+pub struct EncryptionConfig;
+
+/// The reason an incoming packet could not be decrypted, or failed its
+/// checksum. Such packets are dropped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DecoderError {
+    /// The packet is too short, or has an unknown marker
+    Malformed,
+    /// The packet was encrypted, but no keys have been established yet
+    NoKeys,
+    /// The packet arrived in plaintext, but only handshake packets may
+    Unencrypted,
+    /// The packet's nonce counter has already been seen, or is too old
+    Replayed,
+    /// The packet failed authentication, it has been corrupted or tampered
+    /// with
+    Tampered,
+    /// The packet's checksum does not match its contents, it has been
+    /// corrupted in transit
+    Corrupted,
+}
+
+impl fmt::Display for DecoderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let reason = match self {
+            DecoderError::Malformed => "malformed packet",
+            DecoderError::NoKeys => "encrypted packet received before keys were established",
+            DecoderError::Unencrypted => "unencrypted packet received on encrypted connection",
+            DecoderError::Replayed => "replayed packet",
+            DecoderError::Tampered => "packet failed authentication",
+            DecoderError::Corrupted => "packet failed checksum",
+        };
+        write!(f, "Decoder Error: {}", reason)
+    }
+}
+
+impl Error for DecoderError {}
+
+/// Returns whether the (uncompressed) packet is a handshake packet, which are
+/// always sent in plaintext as they carry the key exchange
+pub fn is_handshake_packet(payload: &[u8]) -> bool {
+    let mut reader = BitReader::new(payload);
+    match StandardHeader::de(&mut reader) {
+        Ok(header) => header.packet_type == PacketType::Handshake,
+        Err(_) => false,
+    }
+}
+
+/// Frames an outgoing packet, encrypting it if a PacketCipher is given
+pub fn seal_packet(cipher_opt: Option<&mut PacketCipher>, payload: &[u8]) -> Vec<u8> {
+    if let Some(cipher) = cipher_opt {
+        return cipher.encrypt(payload);
+    }
+    let mut output = Vec::with_capacity(payload.len() + 1);
+    output.push(PLAINTEXT_MARKER);
+    output.extend_from_slice(payload);
+    output
+}
+
+/// Unframes an incoming packet, decrypting it if necessary. Also returns
+/// whether the packet was encrypted.
+pub fn open_packet(
+    cipher_opt: Option<&mut PacketCipher>,
+    payload: &[u8],
+) -> Result<(Vec<u8>, bool), DecoderError> {
+    match payload.first() {
+        Some(&PLAINTEXT_MARKER) => Ok((payload[1..].to_vec(), false)),
+        Some(&ENCRYPTED_MARKER) => {
+            let Some(cipher) = cipher_opt else {
+                return Err(DecoderError::NoKeys);
+            };
+            Ok((cipher.decrypt(payload)?, true))
+        }
+        _ => Err(DecoderError::Malformed),
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "encryption")]
+    {
+        use ring::{
+            aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305},
+            agreement::{self, EphemeralPrivateKey, UnparsedPublicKey, X25519},
+            hkdf::{Salt, HKDF_SHA256},
+            rand::SystemRandom,
+        };
+
+        use crate::{handshake::HandshakeError, HostType};
+
+        const COUNTER_BYTES: usize = 8;
+        const HEADER_BYTES: usize = 1 + COUNTER_BYTES;
+        const CLIENT_TO_SERVER_INFO: &[u8] = b"naia client to server";
+        const SERVER_TO_CLIENT_INFO: &[u8] = b"naia server to client";
+
+        // Sliding window of the most recently received nonce counters
+        struct ReplayWindow {
+            highest: Option<u64>,
+            // bit n is set if (highest - 1 - n) has been received
+            seen: u64,
+        }
+
+        impl ReplayWindow {
+            fn new() -> Self {
+                Self {
+                    highest: None,
+                    seen: 0,
+                }
+            }
+
+            fn is_fresh(&self, counter: u64) -> bool {
+                let Some(highest) = self.highest else {
+                    return true;
+                };
+                if counter > highest {
+                    return true;
+                }
+                let age = highest - counter;
+                if age == 0 || age > 64 {
+                    return false;
+                }
+                self.seen & (1 << (age - 1)) == 0
+            }
+
+            fn record(&mut self, counter: u64) {
+                let Some(highest) = self.highest else {
+                    self.highest = Some(counter);
+                    return;
+                };
+                if counter > highest {
+                    let shift = counter - highest;
+                    self.seen = if shift > 64 {
+                        0
+                    } else {
+                        // the previous highest is now `shift` behind
+                        let shifted = self.seen.checked_shl(shift as u32).unwrap_or(0);
+                        shifted | (1 << (shift - 1))
+                    };
+                    self.highest = Some(counter);
+                } else {
+                    let age = highest - counter;
+                    self.seen |= 1 << (age - 1);
+                }
+            }
+        }
+
+        /// One side of an X25519 key exchange, which is consumed to produce a
+        /// PacketCipher once the remote host's public key is known
+        pub struct KeyExchange {
+            private_key: EphemeralPrivateKey,
+            public_key: Vec<u8>,
+        }
+
+        impl KeyExchange {
+            pub fn new() -> Result<Self, HandshakeError> {
+                let rng = SystemRandom::new();
+                let private_key = EphemeralPrivateKey::generate(&X25519, &rng)
+                    .expect("error generating X25519 private key");
+                let public_key = private_key
+                    .compute_public_key()
+                    .expect("error computing X25519 public key")
+                    .as_ref()
+                    .to_vec();
+                Ok(Self {
+                    private_key,
+                    public_key,
+                })
+            }
+
+            pub fn public_key(&self) -> &[u8] {
+                &self.public_key
+            }
+
+            /// Derives the keys for this connection. Returns None if the
+            /// remote host's public key is invalid.
+            pub fn into_cipher(
+                self,
+                host_type: HostType,
+                remote_public_key: &[u8],
+            ) -> Option<PacketCipher> {
+                // both hosts must salt with the keys in the same order
+                let mut salt = Vec::with_capacity(self.public_key.len() + remote_public_key.len());
+                match host_type {
+                    HostType::Client => {
+                        salt.extend_from_slice(&self.public_key);
+                        salt.extend_from_slice(remote_public_key);
+                    }
+                    HostType::Server => {
+                        salt.extend_from_slice(remote_public_key);
+                        salt.extend_from_slice(&self.public_key);
+                    }
+                }
+                let (sealing_info, opening_info) = match host_type {
+                    HostType::Client => (CLIENT_TO_SERVER_INFO, SERVER_TO_CLIENT_INFO),
+                    HostType::Server => (SERVER_TO_CLIENT_INFO, CLIENT_TO_SERVER_INFO),
+                };
+
+                let remote_public_key = UnparsedPublicKey::new(&X25519, remote_public_key);
+                agreement::agree_ephemeral(
+                    self.private_key,
+                    &remote_public_key,
+                    (),
+                    |shared_secret| {
+                        let prk = Salt::new(HKDF_SHA256, &salt).extract(shared_secret);
+                        let derive_key = |info: &[u8]| -> Result<LessSafeKey, ()> {
+                            let info = [info];
+                            let okm = prk.expand(&info, &CHACHA20_POLY1305).map_err(|_| ())?;
+                            Ok(LessSafeKey::new(UnboundKey::from(okm)))
+                        };
+                        Ok(PacketCipher {
+                            sealing_key: derive_key(sealing_info)?,
+                            opening_key: derive_key(opening_info)?,
+                            next_counter: 0,
+                            replay_window: ReplayWindow::new(),
+                        })
+                    },
+                )
+                .ok()
+            }
+        }
+
+        /// Encrypts outgoing and decrypts incoming packets for a single
+        /// connection
+        pub struct PacketCipher {
+            sealing_key: LessSafeKey,
+            opening_key: LessSafeKey,
+            next_counter: u64,
+            replay_window: ReplayWindow,
+        }
+
+        impl PacketCipher {
+            fn nonce(counter: u64) -> Nonce {
+                let mut nonce = [0; aead::NONCE_LEN];
+                nonce[..COUNTER_BYTES].copy_from_slice(&counter.to_le_bytes());
+                Nonce::assume_unique_for_key(nonce)
+            }
+
+            pub fn encrypt(&mut self, payload: &[u8]) -> Vec<u8> {
+                let counter = self.next_counter;
+                self.next_counter += 1;
+
+                let mut output =
+                    Vec::with_capacity(HEADER_BYTES + payload.len() + CHACHA20_POLY1305.tag_len());
+                output.push(ENCRYPTED_MARKER);
+                output.extend_from_slice(&counter.to_le_bytes());
+                let mut body = payload.to_vec();
+                self.sealing_key
+                    .seal_in_place_append_tag(
+                        Self::nonce(counter),
+                        Aad::from(&output[..HEADER_BYTES]),
+                        &mut body,
+                    )
+                    .expect("error encrypting packet");
+                output.extend_from_slice(&body);
+                output
+            }
+
+            pub fn decrypt(&mut self, payload: &[u8]) -> Result<Vec<u8>, DecoderError> {
+                if payload.len() < HEADER_BYTES + CHACHA20_POLY1305.tag_len() {
+                    return Err(DecoderError::Malformed);
+                }
+                let (header, body) = payload.split_at(HEADER_BYTES);
+                let mut counter_bytes = [0; COUNTER_BYTES];
+                counter_bytes.copy_from_slice(&header[1..]);
+                let counter = u64::from_le_bytes(counter_bytes);
+                if !self.replay_window.is_fresh(counter) {
+                    return Err(DecoderError::Replayed);
+                }
+
+                let mut body = body.to_vec();
+                let plaintext_length = self
+                    .opening_key
+                    .open_in_place(Self::nonce(counter), Aad::from(header), &mut body)
+                    .map_err(|_| DecoderError::Tampered)?
+                    .len();
+                body.truncate(plaintext_length);
+
+                // only record the counter once the packet is known to be authentic
+                self.replay_window.record(counter);
+                Ok(body)
+            }
+        }
+    }
+    else
+    {
+        use crate::{handshake::HandshakeError, HostType};
+
+        /// One side of an X25519 key exchange. Requires the `encryption`
+        /// feature.
+        pub struct KeyExchange;
+
+        impl KeyExchange {
+            /// Always fails, as this build of naia can't encrypt packets
+            pub fn new() -> Result<Self, HandshakeError> {
+                Err(HandshakeError::EncryptionUnavailable)
+            }
+
+            pub fn public_key(&self) -> &[u8] {
+                &[]
+            }
+
+            pub fn into_cipher(
+                self,
+                _host_type: HostType,
+                _remote_public_key: &[u8],
+            ) -> Option<PacketCipher> {
+                None
+            }
+        }
+
+        /// Encrypts outgoing and decrypts incoming packets for a single
+        /// connection. Requires the `encryption` feature, and so can never be
+        /// constructed without it.
+        pub enum PacketCipher {}
+
+        impl PacketCipher {
+            pub fn encrypt(&mut self, _payload: &[u8]) -> Vec<u8> {
+                match *self {}
+            }
+
+            pub fn decrypt(&mut self, _payload: &[u8]) -> Result<Vec<u8>, DecoderError> {
+                match *self {}
+            }
+        }
+    }
+}
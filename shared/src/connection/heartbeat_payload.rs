@@ -0,0 +1,127 @@
+use std::{error::Error, fmt, time::Duration};
+
+use log::warn;
+
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr};
+
+use crate::{
+    messages::{
+        message::Message, message_container::MessageContainer, message_kinds::MessageKinds,
+    },
+    world::entity::entity_converters::FakeEntityConverter,
+    Timer,
+};
+
+/// The largest Message which can be carried by a Heartbeat packet, in bytes
+pub const HEARTBEAT_PAYLOAD_MAX_BYTES: u32 = 64;
+
+type PayloadProvider = Box<dyn FnMut() -> Option<Box<dyn Message>> + Send + Sync>;
+
+/// App data carried by Heartbeat packets, produced by a user-provided function.
+/// While set, a Heartbeat is sent at least once per heartbeat interval, even if
+/// other packets are being sent.
+pub struct HeartbeatPayload {
+    provider: PayloadProvider,
+    // produced when checking the provider at registration, sent first
+    next: Option<Box<dyn Message>>,
+    timer: Timer,
+}
+
+impl HeartbeatPayload {
+    /// The provider is called once immediately, and returns an error if it
+    /// produces a Message larger than `HEARTBEAT_PAYLOAD_MAX_BYTES`. Otherwise
+    /// that Message is sent with the next Heartbeat
+    pub fn new<M: Message>(
+        heartbeat_interval: Duration,
+        mut provider: impl FnMut() -> Option<M> + Send + Sync + 'static,
+    ) -> Result<Self, HeartbeatPayloadTooLargeError> {
+        let mut provider: PayloadProvider =
+            Box::new(move || provider().map(|message| Box::new(message) as Box<dyn Message>));
+        let next = provider();
+        if let Some(message) = &next {
+            check_size(message.as_ref())?;
+        }
+        Ok(Self {
+            provider,
+            next,
+            timer: Timer::new(heartbeat_interval),
+        })
+    }
+
+    /// Returns whether a Heartbeat should be sent to deliver the next payload
+    pub fn should_send(&self) -> bool {
+        self.timer.ringing()
+    }
+
+    /// Record that payloads have been sent, restarting the heartbeat interval
+    pub fn mark_sent(&mut self) {
+        self.timer.reset();
+    }
+
+    /// Writes the next payload into a Heartbeat packet, if there is one. A
+    /// Heartbeat without a payload only carries a single `false` bit
+    pub fn write(
+        payload: Option<&mut Self>,
+        message_kinds: &MessageKinds,
+        writer: &mut dyn BitWrite,
+    ) {
+        let Some(payload) = payload else {
+            false.ser(writer);
+            return;
+        };
+        let message = payload.next.take().or_else(|| (payload.provider)());
+        let Some(message) = message else {
+            false.ser(writer);
+            return;
+        };
+        if let Err(error) = check_size(message.as_ref()) {
+            warn!("{}, it will not be sent", error);
+            false.ser(writer);
+            return;
+        }
+        true.ser(writer);
+        message.write(message_kinds, writer, &mut FakeEntityConverter);
+    }
+
+    /// Reads the payload of an incoming Heartbeat packet, if it has one
+    pub fn read(
+        message_kinds: &MessageKinds,
+        reader: &mut BitReader,
+    ) -> Result<Option<MessageContainer>, SerdeErr> {
+        if !bool::de(reader)? {
+            return Ok(None);
+        }
+        let message = message_kinds.read(reader, &FakeEntityConverter)?;
+        Ok(Some(message))
+    }
+}
+
+fn check_size(message: &dyn Message) -> Result<(), HeartbeatPayloadTooLargeError> {
+    let bytes = message.bit_length(&mut FakeEntityConverter).div_ceil(8);
+    if bytes > HEARTBEAT_PAYLOAD_MAX_BYTES {
+        return Err(HeartbeatPayloadTooLargeError {
+            name: message.name(),
+            bytes,
+        });
+    }
+    Ok(())
+}
+
+/// A Heartbeat payload was larger than `HEARTBEAT_PAYLOAD_MAX_BYTES`
+#[derive(Debug)]
+pub struct HeartbeatPayloadTooLargeError {
+    pub name: String,
+    pub bytes: u32,
+}
+
+impl Error for HeartbeatPayloadTooLargeError {}
+
+impl fmt::Display for HeartbeatPayloadTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "Heartbeat payload `{}` is {} bytes, larger than the maximum of {} bytes",
+            self.name, self.bytes, HEARTBEAT_PAYLOAD_MAX_BYTES
+        )
+    }
+}
@@ -1,12 +1,19 @@
 pub mod ack_manager;
 pub mod bandwidth_monitor;
 pub mod base_connection;
+pub mod checksum;
 pub mod compression_config;
+pub mod compression_stats;
 pub mod connection_config;
+pub mod connection_params;
+pub mod connection_quality;
 pub mod decoder;
 pub mod encoder;
+pub mod encryption;
+pub mod heartbeat_payload;
 pub mod packet_notifiable;
 pub mod packet_type;
 pub mod ping_store;
+pub mod scope_backlog;
 pub mod sequence_buffer;
 pub mod standard_header;
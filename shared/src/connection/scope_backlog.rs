@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+/// How far behind a connection is in delivering Entity actions (spawns,
+/// despawns, Component inserts & removals) to its remote host
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScopeBacklog {
+    /// Entity actions queued & not yet acknowledged by the remote host
+    pub pending_actions: usize,
+    /// How long ago the oldest of those actions was queued
+    pub oldest_age: Duration,
+}
+
+impl ScopeBacklog {
+    /// Whether this backlog is past either of the given config's thresholds
+    pub fn exceeds(&self, config: &ScopeBacklogConfig) -> bool {
+        self.pending_actions >= config.max_pending_actions
+            || self.oldest_age >= config.max_oldest_age
+    }
+}
+
+/// When a connection counts as backlogged with Entity actions, & how it works
+/// through the backlog
+#[derive(Clone, Debug)]
+pub struct ScopeBacklogConfig {
+    /// The count of pending Entity actions at which a connection is backlogged
+    pub max_pending_actions: usize,
+    /// The age of the oldest pending Entity action at which a connection is
+    /// backlogged
+    pub max_oldest_age: Duration,
+    /// While backlogged, write despawns ahead of every other action, so that
+    /// the remote host isn't left showing Entities which are gone
+    pub prioritize_despawns: bool,
+}
+
+impl Default for ScopeBacklogConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_actions: 4096,
+            max_oldest_age: Duration::from_secs(5),
+            prioritize_despawns: false,
+        }
+    }
+}
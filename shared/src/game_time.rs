@@ -1,16 +1,41 @@
-use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, UnsignedInteger};
+use std::time::Duration;
+
+use naia_serde::{
+    BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, UnsignedInteger, UnsignedVariableInteger,
+};
 use naia_socket_shared::Instant;
 
+use crate::{wrapping_diff, Tick};
+
 const GAME_INSANT_BITS: u8 = 22;
 pub const GAME_TIME_LIMIT: u32 = 4194304; // 2^22
 const GAME_TIME_LIMIT_U128: u128 = 4194304;
-const GAME_TIME_MAX: u32 = 4194303; // 2^22 - 1
+pub const GAME_TIME_MAX: u32 = 4194303; // 2^22 - 1
 const TIME_OFFSET_MAX: i32 = 2097151; // 2^21 - 1
 const TIME_OFFSET_MIN: i32 = -2097152; // 2^21 * -1
 
+/// Returns whether or not a GameInstant is after another, accounting for
+/// wrapping at GAME_TIME_LIMIT. Instants half of GAME_TIME_LIMIT or more
+/// apart are treated as having wrapped, so can't be reliably ordered.
+/// game_instant_greater_than(2ms, 1ms) will return true
+/// game_instant_greater_than(0ms, GAME_TIME_MAX) will return true
+/// game_instant_greater_than(1ms, 1ms) will return false
+pub fn game_instant_greater_than(a: &GameInstant, b: &GameInstant) -> bool {
+    a.is_more_than(b)
+}
+
+/// Returns whether or not a GameInstant is before another, accounting for
+/// wrapping at GAME_TIME_LIMIT
+/// game_instant_less_than(1ms, 2ms) will return true
+/// game_instant_less_than(GAME_TIME_MAX, 0ms) will return true
+/// game_instant_less_than(1ms, 1ms) will return false
+pub fn game_instant_less_than(a: &GameInstant, b: &GameInstant) -> bool {
+    game_instant_greater_than(b, a)
+}
+
 // GameInstant measures the # of milliseconds since the start of the Server
 // GameInstant wraps around at 2^22 milliseconds (around one hour)
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct GameInstant {
     millis: u32,
 }
@@ -26,18 +51,110 @@ impl GameInstant {
 
     // This method assumes that `previous_instant` is known to be from the past
     pub fn time_since(&self, previous_instant: &GameInstant) -> GameDuration {
-        let previous_millis = previous_instant.millis;
+        self.duration_since(previous_instant)
+    }
+
+    /// Returns the time elapsed since `earlier`, assuming it is known to be
+    /// from the past. If `earlier` is actually later, the result is the time
+    /// until GameInstant wraps around to it again.
+    pub fn duration_since(&self, earlier: &GameInstant) -> GameDuration {
+        let previous_millis = earlier.millis;
         let current_millis = self.millis;
 
-        if previous_millis == current_millis {
-            return GameDuration { millis: 0 };
+        if previous_millis <= current_millis {
+            GameDuration::from_millis(current_millis - previous_millis)
+        } else {
+            GameDuration::from_millis(GAME_TIME_LIMIT - previous_millis + current_millis)
         }
+    }
 
-        if previous_millis < current_millis {
-            return GameDuration::from_millis(current_millis - previous_millis);
-        } else {
-            return GameDuration::from_millis(GAME_TIME_MAX - previous_millis + current_millis);
+    /// Returns the time elapsed since `earlier`, or None if `earlier` is
+    /// after this GameInstant
+    pub fn checked_duration_since(&self, earlier: &GameInstant) -> Option<GameDuration> {
+        if game_instant_greater_than(earlier, self) {
+            return None;
+        }
+        Some(self.duration_since(earlier))
+    }
+
+    /// Returns the time elapsed since `earlier`, or zero if `earlier` is
+    /// after this GameInstant
+    pub fn saturating_duration_since(&self, earlier: &GameInstant) -> GameDuration {
+        self.checked_duration_since(earlier)
+            .unwrap_or(GameDuration::from_millis(0))
+    }
+
+    /// Returns the GameInstant `duration` after this one, wrapping around at
+    /// GAME_TIME_LIMIT
+    pub fn add_duration(&self, duration: &GameDuration) -> Self {
+        self.add_millis(duration.millis)
+    }
+
+    /// Returns the GameInstant `duration` before this one, wrapping around at
+    /// GAME_TIME_LIMIT
+    pub fn sub_duration(&self, duration: &GameDuration) -> Self {
+        self.sub_millis(duration.millis)
+    }
+
+    /// Returns the GameInstant `duration` after this one, or None if
+    /// `duration` is too long for the result to still be ordered after this
+    /// GameInstant
+    pub fn checked_add_duration(&self, duration: &GameDuration) -> Option<Self> {
+        if duration.millis > TIME_OFFSET_MAX as u32 {
+            return None;
         }
+        Some(self.add_duration(duration))
+    }
+
+    /// Returns the GameInstant `duration` before this one, or None if
+    /// `duration` is too long for the result to still be ordered before this
+    /// GameInstant
+    pub fn checked_sub_duration(&self, duration: &GameDuration) -> Option<Self> {
+        if duration.millis > TIME_OFFSET_MAX as u32 {
+            return None;
+        }
+        Some(self.sub_duration(duration))
+    }
+
+    /// Returns the GameInstant `duration` after this one, clamping
+    /// `duration` to the furthest GameInstant still ordered after this one
+    pub fn saturating_add_duration(&self, duration: &GameDuration) -> Self {
+        self.add_millis(duration.millis.min(TIME_OFFSET_MAX as u32))
+    }
+
+    /// Returns the GameInstant `duration` before this one, clamping
+    /// `duration` to the furthest GameInstant still ordered before this one
+    pub fn saturating_sub_duration(&self, duration: &GameDuration) -> Self {
+        self.sub_millis(duration.millis.min(TIME_OFFSET_MAX as u32))
+    }
+
+    /// Returns the Tick this GameInstant falls within, given the length of a
+    /// Tick & the GameInstant at which some `reference_tick` began, such as
+    /// the Server's current Tick & its GameInstant
+    pub fn to_tick(
+        &self,
+        tick_duration: &Duration,
+        reference_tick: Tick,
+        reference_instant: &GameInstant,
+    ) -> Tick {
+        let tick_millis = tick_millis(tick_duration);
+        let offset_ticks = reference_instant.offset_from(self).div_euclid(tick_millis);
+        // Ticks wrap around far sooner than GameInstants, so truncate
+        reference_tick.wrapping_add(offset_ticks as Tick)
+    }
+
+    /// Returns the GameInstant at which `tick` begins, given the length of a
+    /// Tick & the GameInstant at which some `reference_tick` began, such as
+    /// the Server's current Tick & its GameInstant
+    pub fn from_tick(
+        tick: Tick,
+        tick_duration: &Duration,
+        reference_tick: Tick,
+        reference_instant: &GameInstant,
+    ) -> Self {
+        let tick_millis = tick_millis(tick_duration);
+        let offset_ticks = i32::from(wrapping_diff(reference_tick, tick));
+        reference_instant.add_signed_millis(offset_ticks * tick_millis)
     }
 
     // Returns offset to target time, in milliseconds (possibly negative)
@@ -81,11 +198,12 @@ impl GameInstant {
 
     pub fn add_millis(&self, millis: u32) -> Self {
         Self {
-            millis: (self.millis + millis) % GAME_TIME_LIMIT,
+            millis: (self.millis + (millis % GAME_TIME_LIMIT)) % GAME_TIME_LIMIT,
         }
     }
 
     pub fn sub_millis(&self, millis: u32) -> Self {
+        let millis = millis % GAME_TIME_LIMIT;
         if self.millis >= millis {
             Self {
                 millis: self.millis - millis,
@@ -103,11 +221,19 @@ impl GameInstant {
         if millis >= 0 {
             return self.add_millis(millis as u32);
         } else {
-            return self.sub_millis((millis * -1) as u32);
+            return self.sub_millis(millis.unsigned_abs());
         }
     }
 }
 
+fn tick_millis(tick_duration: &Duration) -> i32 {
+    let tick_millis = tick_duration.as_millis();
+    if tick_millis == 0 || tick_millis > TIME_OFFSET_MAX as u128 {
+        panic!("Tick duration must be between 1ms & half of GAME_TIME_LIMIT");
+    }
+    tick_millis as i32
+}
+
 impl Serde for GameInstant {
     fn ser(&self, writer: &mut dyn BitWrite) {
         let integer = UnsignedInteger::<GAME_INSANT_BITS>::new(self.millis as u64);
@@ -132,7 +258,7 @@ impl ConstBitLength for GameInstant {
 }
 
 // GameDuration measures the duration between two GameInstants, in milliseconds
-#[derive(PartialEq, PartialOrd, Eq, Clone)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Debug, Clone, Copy)]
 pub struct GameDuration {
     millis: u32,
 }
@@ -157,6 +283,49 @@ impl GameDuration {
             millis: self.millis - millis,
         }
     }
+
+    pub fn checked_add_millis(&self, millis: u32) -> Option<Self> {
+        Some(Self::from_millis(self.millis.checked_add(millis)?))
+    }
+
+    pub fn checked_sub_millis(&self, millis: u32) -> Option<Self> {
+        Some(Self::from_millis(self.millis.checked_sub(millis)?))
+    }
+
+    pub fn saturating_add_millis(&self, millis: u32) -> Self {
+        Self::from_millis(self.millis.saturating_add(millis))
+    }
+
+    pub fn saturating_sub_millis(&self, millis: u32) -> Self {
+        Self::from_millis(self.millis.saturating_sub(millis))
+    }
+
+    /// Returns the number of whole Ticks of length `tick_duration` which fit
+    /// in this GameDuration
+    pub fn as_ticks(&self, tick_duration: &Duration) -> u32 {
+        self.millis / (tick_millis(tick_duration) as u32)
+    }
+
+    pub fn from_ticks(ticks: u32, tick_duration: &Duration) -> Self {
+        Self::from_millis(ticks * (tick_millis(tick_duration) as u32))
+    }
+}
+
+impl Serde for GameDuration {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        let integer = UnsignedVariableInteger::<7>::new(self.millis as u64);
+        integer.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let integer = UnsignedVariableInteger::<7>::de(reader)?;
+        let millis = u32::try_from(integer.get()).map_err(|_| SerdeErr)?;
+        Ok(Self { millis })
+    }
+
+    fn bit_length(&self) -> u32 {
+        UnsignedVariableInteger::<7>::new(self.millis as u64).bit_length()
+    }
 }
 
 // Tests
@@ -277,3 +446,253 @@ mod wrapping_diff_tests {
         assert_eq!(result as i64, -i64::from(diff));
     }
 }
+
+#[cfg(test)]
+mod arithmetic_tests {
+    use std::time::Duration;
+
+    use naia_serde::{BitReader, BitWriter, Serde};
+
+    use super::{
+        game_instant_greater_than, game_instant_less_than, GameDuration, GameInstant,
+        GAME_TIME_LIMIT, GAME_TIME_MAX, TIME_OFFSET_MAX, TIME_OFFSET_MIN,
+    };
+    use crate::Tick;
+
+    // instants on either side of the wrap boundary
+    fn boundary_instants() -> Vec<GameInstant> {
+        (GAME_TIME_LIMIT - 4..GAME_TIME_LIMIT)
+            .chain(0..4)
+            .map(|millis| GameInstant { millis })
+            .collect()
+    }
+
+    // durations near zero & near the furthest an instant can be offset
+    fn boundary_durations() -> Vec<u32> {
+        let half = TIME_OFFSET_MIN.unsigned_abs();
+        (0..4).chain(half - 3..=half + 3).collect()
+    }
+
+    #[test]
+    fn duration_since_across_wrap() {
+        let a = GameInstant {
+            millis: GAME_TIME_MAX,
+        };
+        let b = GameInstant { millis: 0 };
+
+        assert_eq!(b.duration_since(&a).as_millis(), 1);
+        assert_eq!(b.time_since(&a).as_millis(), 1);
+    }
+
+    #[test]
+    fn add_then_duration_since() {
+        for start in boundary_instants() {
+            for millis in boundary_durations() {
+                let duration = GameDuration::from_millis(millis);
+                let end = start.add_duration(&duration);
+
+                assert_eq!(end.duration_since(&start), duration);
+                assert_eq!(end.sub_duration(&duration), start);
+            }
+        }
+    }
+
+    #[test]
+    fn sub_then_duration_since() {
+        for start in boundary_instants() {
+            for millis in boundary_durations() {
+                let duration = GameDuration::from_millis(millis);
+                let end = start.sub_duration(&duration);
+
+                assert_eq!(start.duration_since(&end), duration);
+                assert_eq!(end.add_duration(&duration), start);
+            }
+        }
+    }
+
+    #[test]
+    fn checked_add_keeps_ordering() {
+        for start in boundary_instants() {
+            for millis in boundary_durations() {
+                let duration = GameDuration::from_millis(millis);
+                let result = start.checked_add_duration(&duration);
+
+                if millis > TIME_OFFSET_MAX as u32 {
+                    assert!(result.is_none());
+                    continue;
+                }
+                let end = result.unwrap();
+                assert_eq!(game_instant_greater_than(&end, &start), millis > 0);
+                assert_eq!(end.checked_duration_since(&start), Some(duration));
+            }
+        }
+    }
+
+    #[test]
+    fn checked_sub_keeps_ordering() {
+        for start in boundary_instants() {
+            for millis in boundary_durations() {
+                let duration = GameDuration::from_millis(millis);
+                let result = start.checked_sub_duration(&duration);
+
+                if millis > TIME_OFFSET_MAX as u32 {
+                    assert!(result.is_none());
+                    continue;
+                }
+                let end = result.unwrap();
+                assert_eq!(game_instant_less_than(&end, &start), millis > 0);
+                assert_eq!(start.checked_duration_since(&end), Some(duration));
+            }
+        }
+    }
+
+    #[test]
+    fn saturating_clamps_to_ordered_range() {
+        for start in boundary_instants() {
+            let duration = GameDuration::from_millis(GAME_TIME_LIMIT);
+
+            let later = start.saturating_add_duration(&duration);
+            let earlier = start.saturating_sub_duration(&duration);
+
+            assert_eq!(start.offset_from(&later), TIME_OFFSET_MAX);
+            assert_eq!(start.offset_from(&earlier), -TIME_OFFSET_MAX);
+            assert!(game_instant_greater_than(&later, &start));
+            assert!(game_instant_less_than(&earlier, &start));
+        }
+    }
+
+    #[test]
+    fn duration_since_later_instant() {
+        for start in boundary_instants() {
+            let later = start.add_millis(1);
+
+            assert_eq!(start.checked_duration_since(&later), None);
+            assert_eq!(
+                start.saturating_duration_since(&later),
+                GameDuration::from_millis(0)
+            );
+        }
+    }
+
+    #[test]
+    fn large_millis_wrap() {
+        let a = GameInstant { millis: 10 };
+
+        assert_eq!(
+            a.add_millis(u32::MAX).as_millis(),
+            (10 + u32::MAX % GAME_TIME_LIMIT) % GAME_TIME_LIMIT
+        );
+        assert_eq!(a.add_millis(GAME_TIME_LIMIT), a);
+        assert_eq!(a.sub_millis(GAME_TIME_LIMIT), a);
+        assert_eq!(
+            a.sub_millis(GAME_TIME_LIMIT + 11).as_millis(),
+            GAME_TIME_MAX
+        );
+        assert_eq!(a.add_signed_millis(i32::MIN), a.sub_millis(1 << 31));
+    }
+
+    #[test]
+    fn timer_fires_across_wrap() {
+        for start in boundary_instants() {
+            for millis in 1..8 {
+                let deadline = start.add_millis(millis);
+                let mut now = start;
+                let mut elapsed = 0;
+                while game_instant_less_than(&now, &deadline) {
+                    now = now.add_millis(1);
+                    elapsed += 1;
+                    assert!(elapsed <= millis, "timer never fired");
+                }
+                assert_eq!(elapsed, millis);
+            }
+        }
+    }
+
+    #[test]
+    fn tick_conversion_across_wrap() {
+        let tick_duration = Duration::from_millis(50);
+        let reference_tick: Tick = 65530;
+        let reference_instant = GameInstant {
+            millis: GAME_TIME_MAX - 120,
+        };
+
+        for offset in -20i32..20 {
+            let tick = reference_tick.wrapping_add(offset as u16);
+            let instant =
+                GameInstant::from_tick(tick, &tick_duration, reference_tick, &reference_instant);
+
+            assert_eq!(reference_instant.offset_from(&instant), offset * 50);
+            assert_eq!(
+                instant.to_tick(&tick_duration, reference_tick, &reference_instant),
+                tick
+            );
+            // instants within the Tick belong to it
+            assert_eq!(
+                instant
+                    .add_millis(49)
+                    .to_tick(&tick_duration, reference_tick, &reference_instant),
+                tick
+            );
+            assert_eq!(
+                instant
+                    .sub_millis(1)
+                    .to_tick(&tick_duration, reference_tick, &reference_instant),
+                tick.wrapping_sub(1)
+            );
+        }
+    }
+
+    #[test]
+    fn duration_ticks() {
+        let tick_duration = Duration::from_millis(16);
+
+        assert_eq!(GameDuration::from_millis(47).as_ticks(&tick_duration), 2);
+        assert_eq!(GameDuration::from_millis(48).as_ticks(&tick_duration), 3);
+        assert_eq!(
+            GameDuration::from_ticks(3, &tick_duration),
+            GameDuration::from_millis(48)
+        );
+    }
+
+    #[test]
+    fn duration_checked_and_saturating() {
+        let a = GameDuration::from_millis(5);
+
+        assert_eq!(a.checked_sub_millis(6), None);
+        assert_eq!(a.saturating_sub_millis(6), GameDuration::from_millis(0));
+        assert_eq!(a.checked_add_millis(u32::MAX), None);
+        assert_eq!(
+            a.saturating_add_millis(u32::MAX),
+            GameDuration::from_millis(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = GameInstant {
+            millis: GAME_TIME_MAX,
+        };
+        let in_2 = GameDuration::from_millis(0);
+        let in_3 = GameDuration::from_millis(u32::MAX);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+        in_3.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = GameInstant::de(&mut reader).unwrap();
+        let out_2 = GameDuration::de(&mut reader).unwrap();
+        let out_3 = GameDuration::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(in_3, out_3);
+    }
+}
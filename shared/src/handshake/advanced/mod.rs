@@ -1,2 +1,7 @@
 mod header;
 pub use header::HandshakeHeader;
+
+/// How many bytes of padding a ClientChallengeRequest must carry. The
+/// Server's reply is smaller than the padded request, so a spoofed request
+/// can't be used to send a larger packet to someone else
+pub const CHALLENGE_REQUEST_PADDING_BYTES: usize = 64;
@@ -0,0 +1,58 @@
+use std::{error::Error, fmt};
+
+use naia_serde::SerdeInternal;
+
+/// The reason a Server refused to complete a handshake with a Client
+#[derive(SerdeInternal, Debug, PartialEq, Eq, Clone)]
+pub enum HandshakeError {
+    /// The Client and Server Protocols did not register the same Components,
+    /// Messages, and Channels in the same order, so their ids would not line
+    /// up. See `Protocol::schema_hash()`
+    SchemaMismatch { server_hash: u64, client_hash: u64 },
+    /// The Server application rejected the Client with a code, and optionally
+    /// a serialized Message describing why
+    Rejected { code: u16, payload: Option<Vec<u8>> },
+    /// Only one of the Client and Server has encryption enabled. See
+    /// `ConnectionConfig::encryption`
+    EncryptionMismatch,
+    /// Encryption is enabled, but this host was compiled without the
+    /// `encryption` feature
+    EncryptionUnavailable,
+    /// The Server already has as many connected Users as it allows. See
+    /// `ServerConfig::max_users`
+    ServerFull,
+}
+
+impl fmt::Display for HandshakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            HandshakeError::SchemaMismatch {
+                server_hash,
+                client_hash,
+            } => write!(
+                f,
+                "Handshake Error: Protocol schema mismatch (server: {:016x}, client: {:016x}). Make sure the Client and Server register Components, Messages, and Channels in the same order.",
+                server_hash, client_hash
+            ),
+            HandshakeError::Rejected { code, .. } => write!(
+                f,
+                "Handshake Error: Server rejected connection with code {}",
+                code
+            ),
+            HandshakeError::EncryptionMismatch => write!(
+                f,
+                "Handshake Error: Encryption must be enabled on both the Client and Server, or neither"
+            ),
+            HandshakeError::EncryptionUnavailable => write!(
+                f,
+                "Handshake Error: naia must be compiled with the `encryption` feature in order to encrypt packets"
+            ),
+            HandshakeError::ServerFull => write!(
+                f,
+                "Handshake Error: Server has reached its maximum number of connected Users"
+            ),
+        }
+    }
+}
+
+impl Error for HandshakeError {}
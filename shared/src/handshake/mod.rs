@@ -1,3 +1,9 @@
+mod error;
+pub use error::HandshakeError;
+
+mod payload;
+pub use payload::{read_handshake_payload, write_handshake_payload, MAX_HANDSHAKE_PAYLOAD_BYTES};
+
 cfg_if! {
     if #[cfg(feature = "advanced_handshake")] {
         mod advanced;
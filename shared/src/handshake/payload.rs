@@ -0,0 +1,37 @@
+use naia_serde::{BitReader, BitWriter, SerdeErr, MTU_SIZE_BYTES};
+
+use crate::{FakeEntityConverter, Message, MessageContainer, MessageKinds};
+
+// Room left in the packet for the StandardHeader, HandshakeHeader, and the
+// framing around the payload itself
+const HANDSHAKE_HEADER_BUDGET_BYTES: usize = 32;
+
+/// The largest serialized Message which can be attached to a handshake
+/// accept or reject response
+pub const MAX_HANDSHAKE_PAYLOAD_BYTES: usize = MTU_SIZE_BYTES - HANDSHAKE_HEADER_BUDGET_BYTES;
+
+/// Serializes a Message so that it can be attached to a handshake response.
+/// Returns `None` if the Message would not fit within a single packet.
+pub fn write_handshake_payload(
+    message_kinds: &MessageKinds,
+    message: Box<dyn Message>,
+) -> Option<Vec<u8>> {
+    let mut converter = FakeEntityConverter;
+    let message = MessageContainer::from_write(message, &mut converter);
+    if message.bit_length() as usize > MAX_HANDSHAKE_PAYLOAD_BYTES * 8 {
+        return None;
+    }
+
+    let mut writer = BitWriter::new();
+    message.write(message_kinds, &mut writer, &mut converter);
+    Some(writer.to_bytes().to_vec())
+}
+
+/// Deserializes a Message attached to a handshake response
+pub fn read_handshake_payload(
+    message_kinds: &MessageKinds,
+    payload: &[u8],
+) -> Result<MessageContainer, SerdeErr> {
+    let mut reader = BitReader::new(payload);
+    message_kinds.read(&mut reader, &FakeEntityConverter)
+}
@@ -11,6 +11,9 @@ pub enum HandshakeHeader {
     // The handshake message sent by the Server, indicating that the
     // connection has been established
     ServerConnectResponse,
+    // The handshake message sent by the Server, indicating that the
+    // connection has been refused, followed by a HandshakeError
+    ServerRejectResponse,
     // Used to request a graceful Client disconnect from the Server
     Disconnect,
 }
@@ -2,12 +2,15 @@ use std::{collections::VecDeque, marker::PhantomData, time::Duration};
 
 use naia_socket_shared::Instant;
 
+// one more than the largest u16, the number of distinct keys available
+const KEY_SPACE: u32 = u16::MAX as u32 + 1;
+
 /// Simple implementation of a store that manages a recycling pool of u16 keys
 pub struct KeyGenerator<K: From<u16> + Into<u16> + Copy> {
     recycling_keys: VecDeque<(u16, Instant)>,
     recycled_keys: VecDeque<u16>,
     recycle_timeout: Duration,
-    next_new_key: u16,
+    next_new_key: u32,
     phantom: PhantomData<K>,
 }
 
@@ -21,7 +24,9 @@ impl<K: From<u16> + Into<u16> + Copy> KeyGenerator<K> {
             phantom: PhantomData,
         }
     }
-    /// Get a new, unused key
+    /// Get a new, unused key. Recycled keys are reissued before new ones are
+    /// created. Panics if all 65536 keys are in use (or waiting out the
+    /// recycle timeout), rather than wrapping around to a key still in use
     pub fn generate(&mut self) -> K {
         let now = Instant::now();
 
@@ -38,20 +43,40 @@ impl<K: From<u16> + Into<u16> + Copy> KeyGenerator<K> {
         }
 
         // Check whether we can return a recycled key
-        if self.recycled_keys.len() > 0 {
-            let key = self.recycled_keys.pop_front().unwrap();
+        if let Some(key) = self.recycled_keys.pop_front() {
             return K::from(key);
         }
 
         // Create a new key
-        let output = self.next_new_key;
-        self.next_new_key = self.next_new_key.wrapping_add(1);
+        if self.next_new_key >= KEY_SPACE {
+            panic!(
+                "KeyGenerator exhausted: all {} keys have been generated, and none have been recycled for at least {:?}. Keys must be recycled with `recycle_key()` once they are no longer in use",
+                KEY_SPACE, self.recycle_timeout
+            );
+        }
+        let output = self.next_new_key as u16;
+        self.next_new_key += 1;
         K::from(output)
     }
 
-    /// Recycle a used key, freeing it up
+    /// Recycle a used key, freeing it up. The key will not be reissued until
+    /// the recycle timeout has elapsed, so that stale references to it are not
+    /// mistaken for its next owner.
     pub fn recycle_key(&mut self, key: &K) {
         let key_u16: u16 = Into::<u16>::into(*key);
         self.recycling_keys.push_back((key_u16, Instant::now()));
     }
+
+    /// The number of distinct keys which have been created. Never exceeds
+    /// 65536.
+    pub fn generated_count(&self) -> usize {
+        self.next_new_key as usize
+    }
+
+    /// The number of keys which have been recycled and not yet reissued,
+    /// including those still waiting out the recycle timeout. The number of
+    /// keys currently in use is `generated_count() - recycled_count()`.
+    pub fn recycled_count(&self) -> usize {
+        self.recycling_keys.len() + self.recycled_keys.len()
+    }
 }
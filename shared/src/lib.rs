@@ -25,13 +25,13 @@ pub use naia_derive::{
 };
 pub use naia_serde::{
     BitReader, BitWrite, BitWriter, ConstBitLength, FileBitWriter, OutgoingPacket, OwnedBitReader,
-    Serde, SerdeBevyClient, SerdeBevyServer, SerdeBevyShared, SerdeErr, SerdeHecs,
-    SerdeIntegerConversion, SerdeInternal, SignedInteger, SignedVariableInteger, UnsignedInteger,
-    UnsignedVariableInteger, MTU_SIZE_BITS, MTU_SIZE_BYTES,
+    ReaderCheckpoint, Serde, SerdeBevyClient, SerdeBevyServer, SerdeBevyShared, SerdeEnum,
+    SerdeErr, SerdeHecs, SerdeIntegerConversion, SerdeInternal, SignedInteger,
+    SignedVariableInteger, UnsignedInteger, UnsignedVariableInteger, MTU_SIZE_BITS, MTU_SIZE_BYTES,
 };
 pub use naia_socket_shared::{
-    generate_identity_token, link_condition_logic, IdentityToken, Instant, LinkConditionerConfig,
-    Random, SocketConfig, TimeQueue,
+    generate_identity_token, link_condition_logic, set_thread_clock, Clock, IdentityToken, Instant,
+    LinkConditionerConfig, Random, SocketConfig, TimeQueue,
 };
 
 mod backends;
@@ -53,24 +53,49 @@ cfg_if! {
         pub mod transport_udp;
     }
 }
+
+cfg_if! {
+    if #[cfg(feature = "schema_export")]{
+        mod schema_export;
+    }
+}
 pub use backends::{Timer, Timestamp};
 pub use connection::{
     ack_manager::AckManager,
     bandwidth_monitor::BandwidthMonitor,
     base_connection::BaseConnection,
+    checksum::{append_checksum, crc32, verify_checksum, CHECKSUM_BYTES},
     compression_config::{CompressionConfig, CompressionMode},
+    compression_stats::CompressionStats,
     connection_config::ConnectionConfig,
+    connection_params::ConnectionParams,
+    connection_quality::{
+        ByteRateMonitor, ConnectionQuality, ConnectionQualityLevel, ConnectionQualityThresholds,
+        PacketLossMonitor,
+    },
     decoder::Decoder,
     encoder::Encoder,
+    encryption::{
+        is_handshake_packet, open_packet, seal_packet, DecoderError, EncryptionConfig, KeyExchange,
+        PacketCipher,
+    },
+    heartbeat_payload::{
+        HeartbeatPayload, HeartbeatPayloadTooLargeError, HEARTBEAT_PAYLOAD_MAX_BYTES,
+    },
     packet_notifiable::PacketNotifiable,
     packet_type::PacketType,
     ping_store::{PingIndex, PingStore},
+    scope_backlog::{ScopeBacklog, ScopeBacklogConfig},
     standard_header::StandardHeader,
 };
 pub use messages::{
     channels::{
-        channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings, TickBufferSettings},
+        channel::{
+            Channel, ChannelDirection, ChannelMode, ChannelSettings, OverflowPolicy,
+            ReliableSettings, TickBufferSettings,
+        },
         channel_kinds::{ChannelKind, ChannelKinds},
+        channel_latency::ChannelLatencyStats,
         default_channels,
         receivers::{
             channel_receiver::ChannelReceiver, ordered_reliable_receiver::OrderedReliableReceiver,
@@ -81,25 +106,33 @@ pub use messages::{
             reliable_sender::ReliableSender,
             request_sender::LocalResponseId,
         },
+        stream_channel::StreamChannel,
         system_channel::SystemChannel,
     },
     message::{Message, Message as MessageBevy, Message as MessageHecs, MessageBuilder},
     message_container::MessageContainer,
     message_kinds::{MessageKind, MessageKinds},
     message_manager::MessageManager,
+    message_receipt::MessageReceiptKey,
     named::Named,
     request::{
-        GlobalRequestId, GlobalResponseId, Request, Response, ResponseReceiveKey, ResponseSendKey,
+        GlobalRequestId, GlobalResponseId, Request, ResponderResult, Response, ResponseReceiveKey,
+        ResponseSendKey,
     },
+    stream_message::StreamMessage,
 };
 pub use world::{
     component::{
+        collection_property::{MapProperty, VecProperty},
         component_kinds::{ComponentKind, ComponentKinds},
         component_update::{ComponentFieldUpdate, ComponentUpdate},
         diff_mask::DiffMask,
         entity_property::EntityProperty,
+        enum_property::EnumProperty,
         property::Property,
+        property_error::PropertyMutateError,
         property_mutate::{PropertyMutate, PropertyMutator},
+        quantization::Quantization,
         replica_ref::{
             ReplicaDynMut, ReplicaDynMutTrait, ReplicaDynMutWrapper, ReplicaDynRef,
             ReplicaDynRefTrait, ReplicaDynRefWrapper, ReplicaMutTrait, ReplicaMutWrapper,
@@ -117,7 +150,7 @@ pub use world::{
     },
     entity::{
         entity_action::EntityAction,
-        entity_action_receiver::EntityActionReceiver,
+        entity_action_receiver::{ComponentChannelDebug, EntityActionReceiver, EntityChannelDebug},
         entity_action_type::EntityActionType,
         entity_auth_event::{EntityEventMessage, EntityEventMessageAction},
         entity_converters::{
@@ -130,6 +163,7 @@ pub use world::{
         local_entity::{HostEntity, OwnedLocalEntity, RemoteEntity},
     },
     host::{
+        entity_action_event::EntityActionEvent as HostEntityActionEvent,
         global_diff_handler::GlobalDiffHandler,
         host_world_manager::{HostWorldEvents, HostWorldManager},
         mut_channel::{MutChannelType, MutReceiver},
@@ -138,18 +172,23 @@ pub use world::{
     remote::{
         entity_action_event::EntityActionEvent,
         entity_event::{EntityEvent, EntityResponseEvent},
+        remote_world_error::RemoteWorldError,
         remote_world_manager::RemoteWorldManager,
+        remote_world_reader::RemoteWorldEvents,
     },
     shared_global_world_manager::SharedGlobalWorldManager,
-    world_type::{WorldMutType, WorldRefType},
+    world_type::{ProxyWorldMut, WorldMutType, WorldRefType},
 };
 
 pub use bigmap::{BigMap, BigMapKey};
-pub use game_time::{GameDuration, GameInstant, GAME_TIME_LIMIT};
+pub use game_time::{
+    game_instant_greater_than, game_instant_less_than, GameDuration, GameInstant, GAME_TIME_LIMIT,
+    GAME_TIME_MAX,
+};
 pub use key_generator::KeyGenerator;
 pub use messages::channels::senders::request_sender::{
     LocalRequestOrResponseId, RequestOrResponse,
 };
 pub use protocol::{Protocol, ProtocolPlugin};
-pub use types::{HostType, MessageIndex, PacketIndex, ShortMessageIndex, Tick};
+pub use types::{HostType, MessageIndex, PacketIndex, ShortMessageIndex, Tick, TickExt};
 pub use wrapping_number::{sequence_greater_than, sequence_less_than, wrapping_diff};
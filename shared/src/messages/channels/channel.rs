@@ -2,10 +2,11 @@
 pub trait Channel: 'static {}
 
 // ChannelSettings
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct ChannelSettings {
     pub mode: ChannelMode,
     pub direction: ChannelDirection,
+    latency_tracking: bool,
 }
 
 impl ChannelSettings {
@@ -14,7 +15,27 @@ impl ChannelSettings {
             panic!("TickBuffered Messages are only allowed to be sent from Client to Server");
         }
 
-        Self { mode, direction }
+        Self {
+            mode,
+            direction,
+            latency_tracking: false,
+        }
+    }
+
+    /// Stamps every Message & Request sent over this Channel with the
+    /// sender's GameInstant, so that the receiver can tell how long ago it was
+    /// sent. Costs a few bytes per Message, so is off by default
+    pub fn with_latency_tracking(mut self, latency_tracking: bool) -> Self {
+        if latency_tracking && self.mode.tick_buffered() {
+            panic!("TickBuffered Messages already carry the Tick they were sent on, and can't be latency tracked");
+        }
+
+        self.latency_tracking = latency_tracking;
+        self
+    }
+
+    pub fn tracks_latency(&self) -> bool {
+        self.latency_tracking
     }
 
     pub fn reliable(&self) -> bool {
@@ -53,7 +74,7 @@ impl ChannelSettings {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct ReliableSettings {
     pub rtt_resend_factor: f32,
 }
@@ -66,23 +87,43 @@ impl ReliableSettings {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct TickBufferSettings {
     /// Describes a maximum of messages that may be kept in the buffer.
     /// Oldest messages are pruned out first.
     pub message_capacity: usize,
+    /// Maximum number of future Ticks which may have Messages waiting in the
+    /// buffer at once, per connection
+    pub max_buffered_ticks: usize,
+    /// What happens to a Message for a new Tick once `max_buffered_ticks` is
+    /// reached
+    pub overflow_policy: OverflowPolicy,
 }
 
 impl TickBufferSettings {
     pub const fn default() -> Self {
         Self {
             message_capacity: 64,
+            max_buffered_ticks: 128,
+            overflow_policy: OverflowPolicy::DropNewest,
         }
     }
 }
 
+// OverflowPolicy
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+    /// Make room by discarding the earliest buffered Tick
+    DropOldest,
+    /// Make room by discarding the latest buffered Tick, which may be the
+    /// incoming one
+    DropNewest,
+    /// Keep the buffer as it is, and discard the incoming Message
+    Reject,
+}
+
 // ChannelMode
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum ChannelMode {
     UnorderedUnreliable,
     SequencedUnreliable,
@@ -1,8 +1,14 @@
-use std::{any::TypeId, collections::HashMap};
+use std::{
+    any::{type_name, TypeId},
+    collections::HashMap,
+};
 
 use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
 
-use crate::messages::channels::channel::{Channel, ChannelSettings};
+use crate::{
+    messages::channels::channel::{Channel, ChannelSettings},
+    protocol::{check_stable_id_collisions, type_name_hash},
+};
 
 type NetId = u16;
 
@@ -33,7 +39,12 @@ impl ChannelKind {
 
     pub fn de(channel_kinds: &ChannelKinds, reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let net_id: NetId = NetId::de(reader)?;
-        Ok(channel_kinds.net_id_to_kind(&net_id))
+        // the NetId may be garbage, if the packet was malformed
+        channel_kinds
+            .net_id_map
+            .get(&net_id)
+            .copied()
+            .ok_or(SerdeErr)
     }
 }
 
@@ -48,6 +59,7 @@ pub struct ChannelKinds {
     current_net_id: NetId,
     kind_map: HashMap<ChannelKind, (NetId, ChannelSettings)>,
     net_id_map: HashMap<NetId, ChannelKind>,
+    type_names: Vec<&'static str>,
 }
 
 impl ChannelKinds {
@@ -56,19 +68,103 @@ impl ChannelKinds {
             current_net_id: 0,
             kind_map: HashMap::new(),
             net_id_map: HashMap::new(),
+            type_names: Vec::new(),
         }
     }
 
     pub fn add_channel<C: Channel>(&mut self, settings: ChannelSettings) {
-        let channel_kind = ChannelKind::of::<C>();
         //info!("ChannelKinds adding channel: {:?}", channel_kind);
+        self.insert_kind(ChannelKind::of::<C>(), type_name::<C>(), settings);
+    }
+
+    fn insert_kind(
+        &mut self,
+        channel_kind: ChannelKind,
+        type_name: &'static str,
+        settings: ChannelSettings,
+    ) {
         let net_id = self.current_net_id;
         self.kind_map.insert(channel_kind, (net_id, settings));
         self.net_id_map.insert(net_id, channel_kind);
+        self.type_names.push(type_name);
         self.current_net_id += 1;
         //TODO: check for current_id overflow?
     }
 
+    /// Adds each Channel of `other` which isn't registered yet, in `other`'s
+    /// registration order. Panics if a Channel registered in both has
+    /// different settings, or if a different Channel has the same type name
+    /// as one already registered
+    pub(crate) fn merge(&mut self, mut other: ChannelKinds) {
+        let type_names = std::mem::take(&mut other.type_names);
+        for (net_id, type_name) in type_names.into_iter().enumerate() {
+            let channel_kind = other.net_id_to_kind(&(net_id as NetId));
+            let Some((_, settings)) = other.kind_map.remove(&channel_kind) else {
+                continue;
+            };
+            if let Some((_, existing_settings)) = self.kind_map.get(&channel_kind) {
+                if *existing_settings != settings {
+                    panic!("Cannot merge Protocols: Channel `{type_name}` is registered in both with different settings");
+                }
+                continue;
+            }
+            if self
+                .type_names
+                .iter()
+                .any(|existing| type_name_hash(existing) == type_name_hash(type_name))
+            {
+                panic!("Cannot merge Protocols: a different Channel named `{type_name}` is already registered");
+            }
+            self.insert_kind(channel_kind, type_name, settings);
+        }
+    }
+
+    /// Reassigns NetIds in order of each Channel's type name hash, so that
+    /// they no longer depend on registration order
+    pub(crate) fn assign_stable_net_ids(&mut self) {
+        let type_names = std::mem::take(&mut self.type_names);
+        let mut entries: Vec<_> = self
+            .kind_map
+            .drain()
+            .map(|(channel_kind, (net_id, settings))| {
+                let type_name = type_names[net_id as usize];
+                (type_name_hash(type_name), type_name, channel_kind, settings)
+            })
+            .collect();
+        entries.sort_by_key(|(hash, ..)| *hash);
+        check_stable_id_collisions(
+            "Channel",
+            entries
+                .iter()
+                .map(|(hash, type_name, ..)| (*hash, *type_name)),
+        );
+
+        self.current_net_id = 0;
+        self.net_id_map.clear();
+        for (_, type_name, channel_kind, settings) in entries {
+            self.insert_kind(channel_kind, type_name, settings);
+        }
+    }
+
+    /// Get the type names of all registered Channels, in registration order
+    pub fn type_names(&self) -> &[&'static str] {
+        &self.type_names
+    }
+
+    /// Iterate over all registered Channels in NetId order, with each one's
+    /// type name, NetId, and settings
+    pub fn iter(&self) -> impl Iterator<Item = (ChannelKind, &'static str, u16, &ChannelSettings)> {
+        self.type_names
+            .iter()
+            .enumerate()
+            .map(|(net_id, type_name)| {
+                let net_id = net_id as NetId;
+                let channel_kind = self.net_id_to_kind(&net_id);
+                let (_, settings) = self.kind_map.get(&channel_kind).unwrap();
+                (channel_kind, *type_name, net_id, settings)
+            })
+    }
+
     pub fn channels(&self) -> Vec<(ChannelKind, ChannelSettings)> {
         // TODO: is there a better way to do this without copying + cloning?
         // How to return a reference here (behind a Mutex ..)
@@ -84,6 +180,16 @@ impl ChannelKinds {
         settings.clone()
     }
 
+    pub fn kind_to_name(&self, channel_kind: &ChannelKind) -> String {
+        let net_id = self.kind_to_net_id(channel_kind);
+        let type_name = self.type_names[net_id as usize];
+        return type_name
+            .rsplit("::")
+            .next()
+            .unwrap_or(type_name)
+            .to_string();
+    }
+
     fn net_id_to_kind(&self, net_id: &NetId) -> ChannelKind {
         return *self.net_id_map.get(net_id).expect(
             "Must properly initialize Channel with Protocol via `add_channel()` function!",
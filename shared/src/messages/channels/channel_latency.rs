@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+// How many of the most recent Messages the percentiles are taken over
+const LATENCY_SAMPLE_COUNT: usize = 256;
+
+/// Percentiles of how long Messages received over a latency tracked Channel
+/// took to arrive, from the sender's `send_message()` call to being read by
+/// the receiver, over the most recently received Messages
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChannelLatencyStats {
+    /// Number of Messages the percentiles were taken over
+    pub samples: usize,
+    /// Median latency, in milliseconds
+    pub p50_ms: f32,
+    /// 90th percentile latency, in milliseconds
+    pub p90_ms: f32,
+    /// 99th percentile latency, in milliseconds
+    pub p99_ms: f32,
+    /// Highest latency, in milliseconds
+    pub max_ms: f32,
+}
+
+impl ChannelLatencyStats {
+    /// Takes the percentiles of the given latencies, in milliseconds, or
+    /// returns None if there are none
+    pub fn from_samples(samples: impl IntoIterator<Item = u32>) -> Option<Self> {
+        let mut sorted: Vec<u32> = samples.into_iter().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+
+        // nearest-rank percentile
+        let percentile = |pct: usize| -> f32 {
+            let rank = (sorted.len() * pct).div_ceil(100).max(1);
+            sorted[rank - 1] as f32
+        };
+        Some(Self {
+            samples: sorted.len(),
+            p50_ms: percentile(50),
+            p90_ms: percentile(90),
+            p99_ms: percentile(99),
+            max_ms: sorted[sorted.len() - 1] as f32,
+        })
+    }
+}
+
+/// The latencies of the most recent Messages received over a Channel
+#[derive(Default)]
+pub struct ChannelLatencySamples {
+    samples: VecDeque<u32>,
+}
+
+impl ChannelLatencySamples {
+    pub fn record(&mut self, latency_ms: u32) {
+        if self.samples.len() == LATENCY_SAMPLE_COUNT {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency_ms);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.samples.iter().copied()
+    }
+
+    pub fn stats(&self) -> Option<ChannelLatencyStats> {
+        ChannelLatencyStats::from_samples(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelLatencySamples, ChannelLatencyStats, LATENCY_SAMPLE_COUNT};
+
+    #[test]
+    fn no_samples_has_no_stats() {
+        assert_eq!(ChannelLatencySamples::default().stats(), None);
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let stats = ChannelLatencyStats::from_samples((1..=100).rev()).unwrap();
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.p50_ms, 50.0);
+        assert_eq!(stats.p90_ms, 90.0);
+        assert_eq!(stats.p99_ms, 99.0);
+        assert_eq!(stats.max_ms, 100.0);
+
+        let stats = ChannelLatencyStats::from_samples([7]).unwrap();
+        assert_eq!(stats.p50_ms, 7.0);
+        assert_eq!(stats.p99_ms, 7.0);
+    }
+
+    #[test]
+    fn only_recent_samples_are_kept() {
+        let mut samples = ChannelLatencySamples::default();
+        for _ in 0..LATENCY_SAMPLE_COUNT {
+            samples.record(500);
+        }
+        for _ in 0..LATENCY_SAMPLE_COUNT {
+            samples.record(10);
+        }
+        let stats = samples.stats().unwrap();
+        assert_eq!(stats.samples, LATENCY_SAMPLE_COUNT);
+        assert_eq!(stats.max_ms, 10.0);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod channel;
 pub mod channel_kinds;
+pub mod channel_latency;
 pub mod default_channels;
 pub mod receivers;
 pub mod senders;
+pub mod stream_channel;
 pub mod system_channel;
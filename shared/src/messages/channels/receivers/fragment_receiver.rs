@@ -1,15 +1,15 @@
 use std::collections::HashMap;
 
-use naia_serde::BitReader;
+use naia_serde::{BitReader, SerdeErr};
 
 use crate::{
-    messages::fragment::{FragmentId, FragmentIndex, FragmentedMessage},
+    messages::fragment::{FragmentId, FragmentedMessage},
     LocalEntityAndGlobalEntityConverter, MessageContainer, MessageIndex, MessageKinds,
 };
 
 pub struct FragmentReceiver {
-    // <FragmentId, (FragmentsReceived, Option(FirstMessageIndex, FragmentCount), FragmentData)
-    map: HashMap<FragmentId, (u32, Option<(MessageIndex, u32)>, Vec<Box<[u8]>>)>,
+    // <FragmentId, (FragmentCount, Option(FirstMessageIndex), <FragmentIndex, FragmentData>)>
+    map: HashMap<FragmentId, (u32, Option<MessageIndex>, HashMap<u32, Box<[u8]>>)>,
 }
 
 impl FragmentReceiver {
@@ -19,13 +19,17 @@ impl FragmentReceiver {
         }
     }
 
+    /// Collects a fragment, returning the full message once all of its
+    /// fragments have arrived. Fragments come straight off the wire, so any
+    /// which contradict each other are rejected as malformed
     pub(crate) fn receive(
         &mut self,
         message_kinds: &MessageKinds,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         message_index: MessageIndex,
         message: MessageContainer,
-    ) -> Option<(MessageIndex, MessageIndex, MessageContainer)> {
+        latency_tracking: bool,
+    ) -> Result<Option<(MessageIndex, MessageIndex, MessageContainer)>, SerdeErr> {
         if !message.is_fragment() {
             panic!("Received non-fragmented message in FragmentReceiver!");
         }
@@ -36,41 +40,50 @@ impl FragmentReceiver {
             .downcast::<FragmentedMessage>()
             .unwrap();
         let fragment_id = fragment.id();
-        let fragment_index = fragment.index();
-        let fragment_total = fragment.total().as_usize();
+        let fragment_index = fragment.index().as_usize() as u32;
+        let fragment_total = fragment.total().as_usize() as u32;
+        if fragment_index >= fragment_total {
+            return Err(SerdeErr);
+        }
 
-        if !self.map.contains_key(&fragment_id) {
-            self.map
-                .insert(fragment_id, (0, None, vec![Box::new([]); fragment_total]));
+        // fragment storage grows with the fragments actually received, never
+        // with the claimed total
+        let (expected_total, first_message_index_opt, fragments) = self
+            .map
+            .entry(fragment_id)
+            .or_insert_with(|| (fragment_total, None, HashMap::new()));
+        if *expected_total != fragment_total || fragments.contains_key(&fragment_index) {
+            return Err(SerdeErr);
         }
-        let (fragments_received, first_message_id_opt, fragment_list) =
-            self.map.get_mut(&fragment_id).unwrap();
 
-        if fragment_index == FragmentIndex::zero() {
-            if first_message_id_opt.is_some() {
-                panic!("Received first fragment of message that already has a first fragment!");
-            }
-            *first_message_id_opt = Some((message_index, fragment_total as u32));
+        if fragment_index == 0 {
+            *first_message_index_opt = Some(message_index);
         }
 
-        fragment_list[fragment_index.as_usize()] = fragment.to_payload();
-        *fragments_received += 1;
-        if *fragments_received != fragment_total as u32 {
-            return None;
+        fragments.insert(fragment_index, fragment.to_payload());
+        if fragments.len() != fragment_total as usize {
+            return Ok(None);
         }
 
         // we have received all fragments! put it all together
-        let (_, first_index_opt, fragment_list) = self.map.remove(&fragment_id).unwrap();
-        let (first_message_index, fragment_count) = first_index_opt.unwrap();
-        let concat_list = fragment_list.concat();
-        let mut reader = BitReader::new(&concat_list);
-        let full_message_result = message_kinds.read(&mut reader, converter);
-        if full_message_result.is_err() {
-            // TODO: bubble up error instead of panicking here
-            panic!("Cannot read fragmented message!");
+        let Some((_, first_message_index_opt, mut fragments)) = self.map.remove(&fragment_id)
+        else {
+            unreachable!("fragment entry was just accessed");
+        };
+        let Some(first_message_index) = first_message_index_opt else {
+            unreachable!("all fragments, including the first, have been received");
+        };
+        let mut concat_list = Vec::new();
+        for index in 0..fragment_total {
+            let Some(payload) = fragments.remove(&index) else {
+                unreachable!("all fragment indices below the total have been received");
+            };
+            concat_list.extend_from_slice(&payload);
         }
-        let full_message = full_message_result.unwrap();
-        let end_message_index = first_message_index + fragment_count as u16 - 1;
-        Some((first_message_index, end_message_index, full_message))
+        let mut reader = BitReader::new(&concat_list);
+        let full_message =
+            message_kinds.read_with_send_instant(&mut reader, converter, latency_tracking)?;
+        let end_message_index = first_message_index.wrapping_add((fragment_total - 1) as u16);
+        Ok(Some((first_message_index, end_message_index, full_message)))
     }
 }
@@ -12,6 +12,7 @@ impl IndexedMessageReader {
         message_kinds: &MessageKinds,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         reader: &mut BitReader,
+        latency_tracking: bool,
     ) -> Result<Vec<(MessageIndex, MessageContainer)>, SerdeErr> {
         let mut last_read_id: Option<MessageIndex> = None;
         let mut output = Vec::new();
@@ -22,7 +23,13 @@ impl IndexedMessageReader {
                 break;
             }
 
-            let id_w_msg = Self::read_message(message_kinds, converter, reader, &last_read_id)?;
+            let id_w_msg = Self::read_message(
+                message_kinds,
+                converter,
+                reader,
+                &last_read_id,
+                latency_tracking,
+            )?;
             last_read_id = Some(id_w_msg.0);
             output.push(id_w_msg);
         }
@@ -48,12 +55,14 @@ impl IndexedMessageReader {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         reader: &mut BitReader,
         last_read_id: &Option<MessageIndex>,
+        latency_tracking: bool,
     ) -> Result<(MessageIndex, MessageContainer), SerdeErr> {
         // read index
         let message_index = Self::read_message_index(reader, last_read_id)?;
 
         // read payload
-        let new_message = message_kinds.read(reader, converter)?;
+        let new_message =
+            message_kinds.read_with_send_instant(reader, converter, latency_tracking)?;
 
         Ok((message_index, new_message))
     }
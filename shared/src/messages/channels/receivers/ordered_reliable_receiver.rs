@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use log::warn;
+
 use crate::{
     messages::channels::receivers::reliable_message_receiver::{
         ReceiverArranger, ReliableMessageReceiver,
@@ -54,48 +56,42 @@ impl ReceiverArranger for OrderedArranger {
         // Put message where it needs to go in buffer
         loop {
             if current_index < self.buffer.len() {
-                let Some((old_message_index, old_message)) = self.buffer.get_mut(current_index)
-                else {
+                let Some((old_message_index, _)) = self.buffer.get(current_index) else {
                     panic!(
                         "Buffer should be instantiated to slot {:?} !",
                         start_message_index
                     );
                 };
-                let old_message_index = *old_message_index;
-                if old_message_index == start_message_index {
-                    if old_message.is_not_received() {
-                        *old_message = MessageSlot::Received(message);
-
-                        let mut current_message_index = start_message_index;
-                        while current_message_index != end_message_index {
-                            current_index = current_index.wrapping_add(1);
-                            let Some((old_message_index, old_message)) =
-                                self.buffer.get_mut(current_index)
-                            else {
-                                panic!(
-                                    "Buffer should be instantiated to slot {:?} !",
-                                    old_message_index
-                                );
-                            };
-                            let old_message_index = *old_message_index;
-                            current_message_index = old_message_index;
-                            if old_message.is_not_received() {
-                                *old_message = MessageSlot::PreviousFragment;
-                            } else {
-                                panic!(
-                                    "Buffer should not have received message in slot {:?} !",
-                                    old_message_index
-                                );
-                            }
-                        }
-
-                        break;
-                    } else {
-                        panic!(
-                            "Buffer should not have received message in slot {:?} !",
-                            old_message_index
+                if *old_message_index == start_message_index {
+                    // a well-behaved sender never overlaps slots which were already filled,
+                    // so a message which does must come from a malformed packet
+                    let span = end_message_index.wrapping_sub(start_message_index) as usize;
+                    let slots_free = (current_index..=current_index + span).all(|index| {
+                        self.buffer
+                            .get(index)
+                            .is_none_or(|(_, slot)| slot.is_not_received())
+                    });
+                    if !slots_free {
+                        warn!(
+                            "Dropping message which overlaps already received slot {:?}",
+                            start_message_index
                         );
+                        return output;
                     }
+
+                    self.buffer[current_index].1 = MessageSlot::Received(message);
+                    for offset in 1..=span {
+                        if let Some((_, slot)) = self.buffer.get_mut(current_index + offset) {
+                            *slot = MessageSlot::PreviousFragment;
+                        } else {
+                            self.buffer.push_back((
+                                start_message_index.wrapping_add(offset as u16),
+                                MessageSlot::PreviousFragment,
+                            ));
+                        }
+                    }
+
+                    break;
                 }
             } else {
                 let next_message_index = self.messages_received.wrapping_add(current_index as u16);
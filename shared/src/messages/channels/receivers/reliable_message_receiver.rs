@@ -1,3 +1,4 @@
+use log::warn;
 use naia_serde::{BitReader, SerdeErr};
 use naia_socket_shared::Instant;
 
@@ -39,6 +40,7 @@ pub struct ReliableMessageReceiver<A: ReceiverArranger> {
     waitlist_store: WaitlistStore<(MessageIndex, MessageIndex, MessageContainer)>,
     incoming_requests: Vec<(LocalResponseId, MessageContainer)>,
     incoming_responses: Vec<(LocalRequestId, MessageContainer)>,
+    latency_tracking: bool,
 }
 
 impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
@@ -51,9 +53,17 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
             waitlist_store: WaitlistStore::new(),
             incoming_requests: Vec::new(),
             incoming_responses: Vec::new(),
+            latency_tracking: false,
         }
     }
 
+    /// Expects each Message, Request & Response to be followed by the
+    /// GameInstant it was sent at
+    pub fn with_latency_tracking(mut self, latency_tracking: bool) -> Self {
+        self.latency_tracking = latency_tracking;
+        self
+    }
+
     fn push_message(
         &mut self,
         message_kinds: &MessageKinds,
@@ -61,16 +71,21 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         message_index: MessageIndex,
         message: MessageContainer,
-    ) {
+    ) -> Result<(), SerdeErr> {
         let Some((start_message_index, end_message_index, full_message)) = ({
             if message.is_fragment() {
-                self.fragment_receiver
-                    .receive(message_kinds, converter, message_index, message)
+                self.fragment_receiver.receive(
+                    message_kinds,
+                    converter,
+                    message_index,
+                    message,
+                    self.latency_tracking,
+                )?
             } else {
                 Some((message_index, message_index, message))
             }
         }) else {
-            return;
+            return Ok(());
         };
 
         if let Some(entity_set) = full_message.relations_waiting() {
@@ -80,7 +95,7 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
                 &mut self.waitlist_store,
                 (start_message_index, end_message_index, full_message),
             );
-            return;
+            return Ok(());
         } else {
             //info!("Received message!");
         }
@@ -89,8 +104,9 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
             self.arranger
                 .process(start_message_index, end_message_index, full_message);
         for message in incoming_messages {
-            self.receive_message(message_kinds, converter, message);
+            self.receive_message(message_kinds, converter, message)?;
         }
+        Ok(())
     }
 
     pub fn buffer_message(
@@ -100,7 +116,7 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         message_index: MessageIndex,
         message: MessageContainer,
-    ) {
+    ) -> Result<(), SerdeErr> {
         self.reliable_receiver
             .buffer_message(message_index, message);
         let received_messages = self.reliable_receiver.receive_messages();
@@ -111,8 +127,9 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
                 converter,
                 received_message_id,
                 received_message,
-            )
+            )?;
         }
+        Ok(())
     }
 
     fn receive_message(
@@ -120,7 +137,7 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
         message_kinds: &MessageKinds,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         message_container: MessageContainer,
-    ) {
+    ) -> Result<(), SerdeErr> {
         // look at message, see if it's a request or response
         if message_container.is_request_or_response() {
             // it is! cast it
@@ -130,12 +147,11 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
                 .unwrap();
             let (local_id, request_bytes) = request_or_response_container.to_id_and_bytes();
             let mut reader = BitReader::new(&request_bytes);
-            let request_or_response_result = message_kinds.read(&mut reader, converter);
-            if request_or_response_result.is_err() {
-                // TODO: bubble up error instead of panicking here
-                panic!("Cannot read request or response message!");
-            }
-            let request_or_response = request_or_response_result.unwrap();
+            let request_or_response = message_kinds.read_with_send_instant(
+                &mut reader,
+                converter,
+                self.latency_tracking,
+            )?;
 
             // add it to incoming requests or responses
             match local_id {
@@ -154,6 +170,7 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
             // it's not a request, just add it to incoming messages
             self.incoming_messages.push(message_container);
         }
+        Ok(())
     }
 }
 
@@ -172,7 +189,12 @@ impl<A: ReceiverArranger> ChannelReceiver<MessageContainer> for ReliableMessageR
                     self.arranger
                         .process(start_message_index, end_message_index, full_message);
                 for message in incoming_messages {
-                    self.receive_message(message_kinds, converter, message);
+                    if self
+                        .receive_message(message_kinds, converter, message)
+                        .is_err()
+                    {
+                        warn!("Dropping malformed request or response message");
+                    }
                 }
             }
         }
@@ -190,9 +212,14 @@ impl<A: ReceiverArranger> MessageChannelReceiver for ReliableMessageReceiver<A>
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         reader: &mut BitReader,
     ) -> Result<(), SerdeErr> {
-        let id_w_msgs = IndexedMessageReader::read_messages(message_kinds, converter, reader)?;
+        let id_w_msgs = IndexedMessageReader::read_messages(
+            message_kinds,
+            converter,
+            reader,
+            self.latency_tracking,
+        )?;
         for (id, message) in id_w_msgs {
-            self.buffer_message(message_kinds, entity_waitlist, converter, id, message);
+            self.buffer_message(message_kinds, entity_waitlist, converter, id, message)?;
         }
         Ok(())
     }
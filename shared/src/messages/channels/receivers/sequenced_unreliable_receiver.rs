@@ -22,6 +22,7 @@ pub struct SequencedUnreliableReceiver {
     newest_received_message_index: Option<MessageIndex>,
     incoming_messages: Vec<MessageContainer>,
     waitlist_store: WaitlistStore<(MessageIndex, MessageContainer)>,
+    latency_tracking: bool,
 }
 
 impl SequencedUnreliableReceiver {
@@ -30,9 +31,16 @@ impl SequencedUnreliableReceiver {
             newest_received_message_index: None,
             incoming_messages: Vec::new(),
             waitlist_store: WaitlistStore::new(),
+            latency_tracking: false,
         }
     }
 
+    /// Expects each Message to be followed by the GameInstant it was sent at
+    pub fn with_latency_tracking(mut self, latency_tracking: bool) -> Self {
+        self.latency_tracking = latency_tracking;
+        self
+    }
+
     pub fn buffer_message(
         &mut self,
         entity_waitlist: &mut EntityWaitlist,
@@ -93,7 +101,12 @@ impl MessageChannelReceiver for SequencedUnreliableReceiver {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         reader: &mut BitReader,
     ) -> Result<(), SerdeErr> {
-        let id_w_msgs = IndexedMessageReader::read_messages(message_kinds, converter, reader)?;
+        let id_w_msgs = IndexedMessageReader::read_messages(
+            message_kinds,
+            converter,
+            reader,
+            self.latency_tracking,
+        )?;
         for (id, message) in id_w_msgs {
             self.buffer_message(entity_waitlist, id, message);
         }
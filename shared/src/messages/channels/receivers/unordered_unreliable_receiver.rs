@@ -16,6 +16,7 @@ use crate::{
 pub struct UnorderedUnreliableReceiver {
     incoming_messages: VecDeque<MessageContainer>,
     waitlist_store: WaitlistStore<MessageContainer>,
+    latency_tracking: bool,
 }
 
 impl UnorderedUnreliableReceiver {
@@ -23,9 +24,16 @@ impl UnorderedUnreliableReceiver {
         Self {
             incoming_messages: VecDeque::new(),
             waitlist_store: WaitlistStore::new(),
+            latency_tracking: false,
         }
     }
 
+    /// Expects each Message to be followed by the GameInstant it was sent at
+    pub fn with_latency_tracking(mut self, latency_tracking: bool) -> Self {
+        self.latency_tracking = latency_tracking;
+        self
+    }
+
     fn read_message(
         &mut self,
         message_kinds: &MessageKinds,
@@ -33,7 +41,7 @@ impl UnorderedUnreliableReceiver {
         reader: &mut BitReader,
     ) -> Result<MessageContainer, SerdeErr> {
         // read payload
-        message_kinds.read(reader, converter)
+        message_kinds.read_with_send_instant(reader, converter, self.latency_tracking)
     }
 
     fn recv_message(&mut self, entity_waitlist: &mut EntityWaitlist, message: MessageContainer) {
@@ -4,7 +4,10 @@ use naia_socket_shared::Instant;
 use crate::messages::channels::senders::request_sender::LocalRequestId;
 use crate::messages::request::GlobalRequestId;
 use crate::{
-    messages::{message_container::MessageContainer, message_kinds::MessageKinds},
+    messages::{
+        message_container::MessageContainer, message_kinds::MessageKinds,
+        message_receipt::MessageReceiptKey,
+    },
     types::MessageIndex,
     LocalEntityAndGlobalEntityConverterMut, LocalResponseId,
 };
@@ -48,9 +51,24 @@ pub trait MessageChannelSender: ChannelSender<MessageContainer> {
         response: MessageContainer,
     );
 
+    /// Queues a Message to be transmitted to the remote host, reporting the
+    /// given receipt through `take_delivered_receipts()` once it is delivered
+    fn send_message_with_receipt(
+        &mut self,
+        message: MessageContainer,
+        receipt_key: MessageReceiptKey,
+    );
+
+    /// Returns the receipts of Messages delivered since the last call
+    fn take_delivered_receipts(&mut self) -> Vec<MessageReceiptKey>;
+
     /// Request is finished, so clean up the local request id and return the global request id
     fn process_incoming_response(
         &mut self,
         local_request_id: &LocalRequestId,
     ) -> Option<GlobalRequestId>;
+
+    /// Returns the number of Messages that have been re-transmitted on this channel
+    #[cfg(feature = "metrics")]
+    fn resent_messages_count(&self) -> u64;
 }
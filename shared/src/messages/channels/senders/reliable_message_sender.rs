@@ -11,6 +11,7 @@ use crate::{
         },
         message_container::MessageContainer,
         message_kinds::MessageKinds,
+        message_receipt::MessageReceiptKey,
     },
     types::MessageIndex,
     LocalEntityAndGlobalEntityConverterMut, LocalResponseId, ReliableSender,
@@ -98,6 +99,19 @@ impl MessageChannelSender for ReliableMessageSender {
         self.send_message(processed_response);
     }
 
+    fn send_message_with_receipt(
+        &mut self,
+        message: MessageContainer,
+        receipt_key: MessageReceiptKey,
+    ) {
+        self.reliable_sender
+            .send_message_with_receipt(message, receipt_key);
+    }
+
+    fn take_delivered_receipts(&mut self) -> Vec<MessageReceiptKey> {
+        self.reliable_sender.take_delivered_receipts()
+    }
+
     fn process_incoming_response(
         &mut self,
         local_request_id: &LocalRequestId,
@@ -105,4 +119,9 @@ impl MessageChannelSender for ReliableMessageSender {
         self.request_sender
             .process_incoming_response(local_request_id)
     }
+
+    #[cfg(feature = "metrics")]
+    fn resent_messages_count(&self) -> u64 {
+        self.reliable_sender.resent_messages_count()
+    }
 }
@@ -1,8 +1,17 @@
-use std::{collections::VecDeque, mem, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    mem,
+    time::Duration,
+};
 
 use naia_socket_shared::Instant;
 
-use crate::{messages::channels::senders::channel_sender::ChannelSender, types::MessageIndex};
+use crate::{
+    messages::{
+        channels::senders::channel_sender::ChannelSender, message_receipt::MessageReceiptKey,
+    },
+    types::MessageIndex,
+};
 
 // Sender
 pub struct ReliableSender<P: Send + Sync> {
@@ -10,6 +19,10 @@ pub struct ReliableSender<P: Send + Sync> {
     sending_messages: VecDeque<Option<(MessageIndex, Option<Instant>, P)>>,
     next_send_message_index: MessageIndex,
     pub(crate) outgoing_messages: VecDeque<(MessageIndex, P)>,
+    receipts: HashMap<MessageIndex, MessageReceiptKey>,
+    delivered_receipts: Vec<MessageReceiptKey>,
+    #[cfg(feature = "metrics")]
+    resent_messages: u64,
 }
 
 impl<P: Send + Sync> ReliableSender<P> {
@@ -19,9 +32,36 @@ impl<P: Send + Sync> ReliableSender<P> {
             next_send_message_index: 0,
             sending_messages: VecDeque::new(),
             outgoing_messages: VecDeque::new(),
+            receipts: HashMap::new(),
+            delivered_receipts: Vec::new(),
+            #[cfg(feature = "metrics")]
+            resent_messages: 0,
         }
     }
 
+    /// Get the number of times a Message has been re-transmitted after not
+    /// being acknowledged in time
+    #[cfg(feature = "metrics")]
+    pub fn resent_messages_count(&self) -> u64 {
+        self.resent_messages
+    }
+
+    /// Queues a Message like `send_message()`, reporting the given receipt
+    /// through `take_delivered_receipts()` once the Message is delivered
+    pub fn send_message_with_receipt(&mut self, message: P, receipt_key: MessageReceiptKey)
+    where
+        P: Clone,
+    {
+        self.receipts
+            .insert(self.next_send_message_index, receipt_key);
+        self.send_message(message);
+    }
+
+    /// Returns the receipts of Messages delivered since the last call
+    pub fn take_delivered_receipts(&mut self) -> Vec<MessageReceiptKey> {
+        mem::take(&mut self.delivered_receipts)
+    }
+
     fn cleanup_sent_messages(&mut self) {
         // keep popping off Nones from the front of the Vec
         loop {
@@ -39,10 +79,50 @@ impl<P: Send + Sync> ReliableSender<P> {
         }
     }
 
+    /// Get the index the next Message sent will be given
+    pub fn next_message_index(&self) -> MessageIndex {
+        self.next_send_message_index
+    }
+
+    /// Get the number of Messages sent & not yet delivered
+    pub fn pending_messages_count(&self) -> usize {
+        self.sending_messages.iter().flatten().count()
+    }
+
+    /// Get the index of the oldest Message sent & not yet delivered
+    pub fn oldest_pending_message_index(&self) -> Option<MessageIndex> {
+        self.sending_messages
+            .iter()
+            .flatten()
+            .next()
+            .map(|(message_index, _, _)| *message_index)
+    }
+
     pub fn take_next_messages(&mut self) -> VecDeque<(MessageIndex, P)> {
         mem::take(&mut self.outgoing_messages)
     }
 
+    /// Get the messages which `collect_messages()` followed by
+    /// `take_next_messages()` would return, without marking any as sent
+    pub fn peek_next_messages(&self, now: &Instant, rtt_millis: &f32) -> VecDeque<(MessageIndex, P)>
+    where
+        P: Clone,
+    {
+        let resend_duration = Duration::from_millis((self.rtt_resend_factor * rtt_millis) as u64);
+
+        let mut output = self.outgoing_messages.clone();
+        for (message_index, last_sent_opt, message) in self.sending_messages.iter().flatten() {
+            let should_send = match last_sent_opt {
+                Some(last_sent) => last_sent.elapsed(now) >= resend_duration,
+                None => true,
+            };
+            if should_send {
+                output.push_back((*message_index, message.clone()));
+            }
+        }
+        output
+    }
+
     // Called when a message has been delivered
     // If this message has never been delivered before, will clear from the outgoing
     // buffer and return the message previously there
@@ -65,6 +145,9 @@ impl<P: Send + Sync> ReliableSender<P> {
                 // replace found message with nothing
                 let container = self.sending_messages.get_mut(index).unwrap();
                 let output = container.take();
+                if let Some(receipt_key) = self.receipts.remove(message_index) {
+                    self.delivered_receipts.push(receipt_key);
+                }
 
                 self.cleanup_sent_messages();
 
@@ -92,6 +175,11 @@ impl<P: Send + Sync + Clone> ChannelSender<P> for ReliableSender<P> {
             if let Some(last_sent) = last_sent_opt {
                 if last_sent.elapsed(now) >= resend_duration {
                     should_send = true;
+
+                    #[cfg(feature = "metrics")]
+                    {
+                        self.resent_messages += 1;
+                    }
                 }
             } else {
                 should_send = true;
@@ -55,8 +55,9 @@ impl RequestSender {
         &mut self,
         local_request_id: &LocalRequestId,
     ) -> Option<GlobalRequestId> {
+        let global_request_id = self.local_to_global_ids.remove(local_request_id)?;
         self.local_key_generator.recycle_key(local_request_id);
-        self.local_to_global_ids.remove(local_request_id)
+        Some(global_request_id)
     }
 }
 
@@ -13,6 +13,7 @@ use crate::{
         },
         message_container::MessageContainer,
         message_kinds::MessageKinds,
+        message_receipt::MessageReceiptKey,
     },
     types::MessageIndex,
     LocalEntityAndGlobalEntityConverterMut, LocalResponseId,
@@ -96,4 +97,17 @@ impl MessageChannelSender for SequencedUnreliableSender {
     fn process_incoming_response(&mut self, _: &LocalRequestId) -> Option<GlobalRequestId> {
         panic!("SequencedUnreliable channel does not support requests");
     }
+
+    fn send_message_with_receipt(&mut self, _: MessageContainer, _: MessageReceiptKey) {
+        panic!("SequencedUnreliable channel does not support message receipts");
+    }
+
+    fn take_delivered_receipts(&mut self) -> Vec<MessageReceiptKey> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "metrics")]
+    fn resent_messages_count(&self) -> u64 {
+        0
+    }
 }
@@ -10,6 +10,7 @@ use crate::{
         channels::senders::channel_sender::{ChannelSender, MessageChannelSender},
         message_container::MessageContainer,
         message_kinds::MessageKinds,
+        message_receipt::MessageReceiptKey,
     },
     types::MessageIndex,
     LocalEntityAndGlobalEntityConverterMut, LocalResponseId,
@@ -120,6 +121,19 @@ impl MessageChannelSender for UnorderedUnreliableSender {
         panic!("UnorderedUnreliable channel does not support requests");
     }
 
+    fn send_message_with_receipt(&mut self, _: MessageContainer, _: MessageReceiptKey) {
+        panic!("UnorderedUnreliable channel does not support message receipts");
+    }
+
+    fn take_delivered_receipts(&mut self) -> Vec<MessageReceiptKey> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "metrics")]
+    fn resent_messages_count(&self) -> u64 {
+        0
+    }
+
     fn send_outgoing_response(
         &mut self,
         _: &MessageKinds,
@@ -0,0 +1,4 @@
+use crate::Channel;
+
+#[derive(Channel)]
+pub struct StreamChannel;
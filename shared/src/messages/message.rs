@@ -22,6 +22,23 @@ pub trait MessageBuilder: Send + Sync {
 }
 
 // Message
+/// Messages may be generic, as long as every type argument they are
+/// registered with can be written, which is checked at compile time:
+///
+/// ```compile_fail
+/// use naia_shared::{Message, Protocol};
+///
+/// pub struct NotSerde;
+///
+/// #[derive(Message)]
+/// pub struct Broadcast<T> {
+///     pub payload: T,
+/// }
+///
+/// fn main() {
+///     Protocol::builder().add_message::<Broadcast<NotSerde>>();
+/// }
+/// ```
 pub trait Message: Send + Sync + Named + MessageClone + Any {
     /// Gets the MessageKind of this type
     fn kind(&self) -> MessageKind;
@@ -1,8 +1,9 @@
 use std::{any::Any, collections::HashSet};
 
-use naia_serde::BitWrite;
+use naia_serde::{BitWrite, ConstBitLength, Serde};
 
 use crate::{
+    game_time::GameInstant,
     world::entity::{
         entity_converters::LocalEntityAndGlobalEntityConverterMut, local_entity::RemoteEntity,
     },
@@ -13,6 +14,7 @@ use crate::{
 pub struct MessageContainer {
     inner: Box<dyn Message>,
     bit_length: Option<u32>,
+    send_instant: Option<GameInstant>,
 }
 
 impl MessageContainer {
@@ -24,6 +26,7 @@ impl MessageContainer {
         Self {
             inner: message,
             bit_length: Some(bit_length),
+            send_instant: None,
         }
     }
 
@@ -31,6 +34,7 @@ impl MessageContainer {
         Self {
             inner: message,
             bit_length: None,
+            send_instant: None,
         }
     }
 
@@ -38,8 +42,31 @@ impl MessageContainer {
         self.inner.name()
     }
 
+    /// The number of bits this Message takes up on the wire. For a Message
+    /// which was received, this is the number of bits it was read from
     pub fn bit_length(&self) -> u32 {
-        self.bit_length.expect("bit_length should never be called on a MessageContainer that was created from a read operation")
+        let bit_length = self.bit_length.expect("bit_length should only be called on a MessageContainer that was written, or read through MessageKinds");
+        if self.send_instant.is_some() {
+            bit_length + <GameInstant as ConstBitLength>::const_bit_length()
+        } else {
+            bit_length
+        }
+    }
+
+    pub(crate) fn set_read_bit_length(&mut self, bit_length: u32) {
+        self.bit_length = Some(bit_length);
+    }
+
+    /// The GameInstant this Message was sent at, if it was sent over a Channel
+    /// with latency tracking
+    pub fn send_instant(&self) -> Option<GameInstant> {
+        self.send_instant
+    }
+
+    /// Stamps this Message with the GameInstant it was sent at, to be written
+    /// after the Message itself
+    pub fn set_send_instant(&mut self, send_instant: GameInstant) {
+        self.send_instant = Some(send_instant);
     }
 
     pub fn write(
@@ -52,6 +79,9 @@ impl MessageContainer {
             writer.count_bits(self.bit_length());
         } else {
             self.inner.write(message_kinds, writer, converter);
+            if let Some(send_instant) = &self.send_instant {
+                send_instant.ser(writer);
+            }
         }
     }
 
@@ -1,8 +1,14 @@
-use std::{any::TypeId, collections::HashMap};
+use std::{
+    any::{type_name, TypeId},
+    collections::HashMap,
+};
 
 use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
 
-use crate::{LocalEntityAndGlobalEntityConverter, Message, MessageBuilder, MessageContainer};
+use crate::{
+    protocol::{check_stable_id_collisions, type_name_hash},
+    GameInstant, LocalEntityAndGlobalEntityConverter, Message, MessageBuilder, MessageContainer,
+};
 
 type NetId = u16;
 
@@ -33,7 +39,12 @@ impl MessageKind {
 
     pub fn de(message_kinds: &MessageKinds, reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let net_id: NetId = NetId::de(reader)?;
-        Ok(message_kinds.net_id_to_kind(&net_id))
+        // the NetId may be garbage, if the packet was malformed
+        message_kinds
+            .net_id_map
+            .get(&net_id)
+            .copied()
+            .ok_or(SerdeErr)
     }
 }
 
@@ -48,6 +59,7 @@ pub struct MessageKinds {
     current_net_id: NetId,
     kind_map: HashMap<MessageKind, (NetId, Box<dyn MessageBuilder>)>,
     net_id_map: HashMap<NetId, MessageKind>,
+    type_names: Vec<&'static str>,
 }
 
 impl MessageKinds {
@@ -56,27 +68,128 @@ impl MessageKinds {
             current_net_id: 0,
             kind_map: HashMap::new(),
             net_id_map: HashMap::new(),
+            type_names: Vec::new(),
         }
     }
 
     pub fn add_message<M: Message>(&mut self) {
-        let message_kind = MessageKind::of::<M>();
+        self.insert_kind(
+            MessageKind::of::<M>(),
+            type_name::<M>(),
+            M::create_builder(),
+        );
+    }
 
+    fn insert_kind(
+        &mut self,
+        message_kind: MessageKind,
+        type_name: &'static str,
+        builder: Box<dyn MessageBuilder>,
+    ) {
         let net_id = self.current_net_id;
-        self.kind_map
-            .insert(message_kind, (net_id, M::create_builder()));
+        self.kind_map.insert(message_kind, (net_id, builder));
         self.net_id_map.insert(net_id, message_kind);
+        self.type_names.push(type_name);
         self.current_net_id += 1;
         //TODO: check for current_id overflow?
     }
 
+    /// Adds each Message of `other` which isn't registered yet, in `other`'s
+    /// registration order. Panics if a different Message has the same type
+    /// name as one already registered
+    pub(crate) fn merge(&mut self, mut other: MessageKinds) {
+        let type_names = std::mem::take(&mut other.type_names);
+        for (net_id, type_name) in type_names.into_iter().enumerate() {
+            let message_kind = other.net_id_to_kind(&(net_id as NetId));
+            let Some((_, builder)) = other.kind_map.remove(&message_kind) else {
+                continue;
+            };
+            if self.kind_map.contains_key(&message_kind) {
+                continue;
+            }
+            if self
+                .type_names
+                .iter()
+                .any(|existing| type_name_hash(existing) == type_name_hash(type_name))
+            {
+                panic!("Cannot merge Protocols: a different Message named `{type_name}` is already registered");
+            }
+            self.insert_kind(message_kind, type_name, builder);
+        }
+    }
+
+    /// Reassigns NetIds in order of each Message's type name hash, so that
+    /// they no longer depend on registration order
+    pub(crate) fn assign_stable_net_ids(&mut self) {
+        let type_names = std::mem::take(&mut self.type_names);
+        let mut entries: Vec<_> = self
+            .kind_map
+            .drain()
+            .map(|(message_kind, (net_id, builder))| {
+                let type_name = type_names[net_id as usize];
+                (type_name_hash(type_name), type_name, message_kind, builder)
+            })
+            .collect();
+        entries.sort_by_key(|(hash, ..)| *hash);
+        check_stable_id_collisions(
+            "Message",
+            entries
+                .iter()
+                .map(|(hash, type_name, ..)| (*hash, *type_name)),
+        );
+
+        self.current_net_id = 0;
+        self.net_id_map.clear();
+        for (_, type_name, message_kind, builder) in entries {
+            self.insert_kind(message_kind, type_name, builder);
+        }
+    }
+
+    /// Get the type names of all registered Messages, in registration order
+    pub fn type_names(&self) -> &[&'static str] {
+        &self.type_names
+    }
+
+    /// Iterate over all registered Messages in NetId order, with each one's
+    /// type name & NetId
+    pub fn iter(&self) -> impl Iterator<Item = (MessageKind, &'static str, u16)> + '_ {
+        self.type_names
+            .iter()
+            .enumerate()
+            .map(|(net_id, type_name)| {
+                let net_id = net_id as NetId;
+                (self.net_id_to_kind(&net_id), *type_name, net_id)
+            })
+    }
+
     pub fn read(
         &self,
         reader: &mut BitReader,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<MessageContainer, SerdeErr> {
+        let bits_read_before = reader.bits_read();
         let message_kind: MessageKind = MessageKind::de(self, reader)?;
-        return self.kind_to_builder(&message_kind).read(reader, converter);
+        let mut message = self
+            .kind_to_builder(&message_kind)
+            .read(reader, converter)?;
+        message.set_read_bit_length(reader.bits_read() - bits_read_before);
+        Ok(message)
+    }
+
+    /// Reads a Message sent over a Channel, followed by the GameInstant it was
+    /// sent at if the Channel tracks latency. Fragments & Requests/Responses
+    /// carry that GameInstant inside their payload instead
+    pub fn read_with_send_instant(
+        &self,
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        latency_tracking: bool,
+    ) -> Result<MessageContainer, SerdeErr> {
+        let mut message = self.read(reader, converter)?;
+        if latency_tracking && !message.is_fragment() && !message.is_request_or_response() {
+            message.set_send_instant(GameInstant::de(reader)?);
+        }
+        Ok(message)
     }
 
     fn net_id_to_kind(&self, net_id: &NetId) -> MessageKind {
@@ -10,6 +10,7 @@ use crate::{
             channel::ChannelMode,
             channel::ChannelSettings,
             channel_kinds::{ChannelKind, ChannelKinds},
+            channel_latency::{ChannelLatencySamples, ChannelLatencyStats},
             receivers::{
                 channel_receiver::MessageChannelReceiver,
                 ordered_reliable_receiver::OrderedReliableReceiver,
@@ -26,6 +27,7 @@ use crate::{
             },
         },
         message_container::MessageContainer,
+        message_receipt::MessageReceiptKey,
         request::GlobalRequestId,
     },
     types::{HostType, MessageIndex, PacketIndex},
@@ -33,8 +35,8 @@ use crate::{
         entity::entity_converters::LocalEntityAndGlobalEntityConverterMut,
         remote::entity_waitlist::EntityWaitlist,
     },
-    EntityAndGlobalEntityConverter, EntityAndLocalEntityConverter, EntityConverter, MessageKinds,
-    Protocol,
+    EntityAndGlobalEntityConverter, EntityAndLocalEntityConverter, EntityConverter, GameInstant,
+    MessageKinds, Protocol,
 };
 
 /// Handles incoming/outgoing messages, tracks the delivery status of Messages
@@ -45,11 +47,28 @@ pub struct MessageManager {
     channel_settings: HashMap<ChannelKind, ChannelSettings>,
     packet_to_message_map: HashMap<PacketIndex, Vec<(ChannelKind, Vec<MessageIndex>)>>,
     message_fragmenter: MessageFragmenter,
+    written_channel_bits: Option<HashMap<ChannelKind, u32>>,
+    channel_budget_bits: Option<u32>,
+    next_channel_turn: usize,
+    channel_latencies: HashMap<ChannelKind, ChannelLatencySamples>,
+    // the number of fragments of each tracked Message yet to be delivered
+    undelivered_receipts: HashMap<MessageReceiptKey, usize>,
+    delivered_receipts: Vec<MessageReceiptKey>,
+    #[cfg(feature = "metrics")]
+    sent_message_counts: HashMap<ChannelKind, u64>,
+    #[cfg(feature = "metrics")]
+    received_message_counts: HashMap<ChannelKind, u64>,
 }
 
 impl MessageManager {
-    /// Creates a new MessageManager
-    pub fn new(host_type: HostType, channel_kinds: &ChannelKinds) -> Self {
+    /// Creates a new MessageManager. While several Channels have messages
+    /// waiting, each but the last one written into a packet is limited to
+    /// `channel_budget_bytes` of it, and Channels take turns going last
+    pub fn new(
+        host_type: HostType,
+        channel_kinds: &ChannelKinds,
+        channel_budget_bytes: Option<u32>,
+    ) -> Self {
         // initialize all reliable channels
 
         // initialize senders
@@ -95,6 +114,7 @@ impl MessageManager {
         // initialize receivers
         let mut channel_receivers = HashMap::<ChannelKind, Box<dyn MessageChannelReceiver>>::new();
         for (channel_kind, channel_settings) in channel_kinds.channels() {
+            let latency_tracking = channel_settings.tracks_latency();
             match &host_type {
                 HostType::Server => {
                     if !channel_settings.can_send_to_server() {
@@ -112,31 +132,45 @@ impl MessageManager {
                 ChannelMode::UnorderedUnreliable => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(UnorderedUnreliableReceiver::new()),
+                        Box::new(
+                            UnorderedUnreliableReceiver::new()
+                                .with_latency_tracking(latency_tracking),
+                        ),
                     );
                 }
                 ChannelMode::SequencedUnreliable => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(SequencedUnreliableReceiver::new()),
+                        Box::new(
+                            SequencedUnreliableReceiver::new()
+                                .with_latency_tracking(latency_tracking),
+                        ),
                     );
                 }
                 ChannelMode::UnorderedReliable(_) => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(UnorderedReliableReceiver::new()),
+                        Box::new(
+                            UnorderedReliableReceiver::new()
+                                .with_latency_tracking(latency_tracking),
+                        ),
                     );
                 }
                 ChannelMode::SequencedReliable(_) => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(SequencedReliableReceiver::new()),
+                        Box::new(
+                            SequencedReliableReceiver::new()
+                                .with_latency_tracking(latency_tracking),
+                        ),
                     );
                 }
                 ChannelMode::OrderedReliable(_) => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(OrderedReliableReceiver::new()),
+                        Box::new(
+                            OrderedReliableReceiver::new().with_latency_tracking(latency_tracking),
+                        ),
                     );
                 }
                 ChannelMode::TickBuffered(_) => {
@@ -157,11 +191,32 @@ impl MessageManager {
             channel_settings: channel_settings_map,
             packet_to_message_map: HashMap::new(),
             message_fragmenter: MessageFragmenter::new(),
+            written_channel_bits: None,
+            channel_budget_bits: channel_budget_bytes.map(|bytes| bytes * 8),
+            next_channel_turn: 0,
+            channel_latencies: HashMap::new(),
+            undelivered_receipts: HashMap::new(),
+            delivered_receipts: Vec::new(),
+            #[cfg(feature = "metrics")]
+            sent_message_counts: HashMap::new(),
+            #[cfg(feature = "metrics")]
+            received_message_counts: HashMap::new(),
         }
     }
 
     // Outgoing Messages
 
+    /// Begins tallying the bits each Channel writes into outgoing packets
+    pub fn enable_written_bits_tally(&mut self) {
+        self.written_channel_bits = Some(HashMap::new());
+    }
+
+    /// Returns the bits written by each Channel since the last call, if
+    /// tallying has been enabled
+    pub fn take_written_channel_bits(&mut self) -> Option<HashMap<ChannelKind, u32>> {
+        self.written_channel_bits.as_mut().map(std::mem::take)
+    }
+
     /// Queues an Message to be transmitted to the remote host
     pub fn send_message(
         &mut self,
@@ -169,11 +224,47 @@ impl MessageManager {
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         channel_kind: &ChannelKind,
         message: MessageContainer,
+    ) {
+        self.send_message_inner(message_kinds, converter, channel_kind, message, None);
+    }
+
+    /// Queues a Message to be transmitted to the remote host over a reliable
+    /// Channel, reporting the given receipt through
+    /// `take_delivered_receipts()` once every fragment of it is delivered
+    pub fn send_message_with_receipt(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+        receipt_key: MessageReceiptKey,
+    ) {
+        self.send_message_inner(
+            message_kinds,
+            converter,
+            channel_kind,
+            message,
+            Some(receipt_key),
+        );
+    }
+
+    fn send_message_inner(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+        receipt_key: Option<MessageReceiptKey>,
     ) {
         let Some(channel) = self.channel_senders.get_mut(channel_kind) else {
             panic!("Channel not configured correctly! Cannot send message.");
         };
 
+        #[cfg(feature = "metrics")]
+        {
+            *self.sent_message_counts.entry(*channel_kind).or_default() += 1;
+        }
+
         let message_bit_length = message.bit_length();
         if message_bit_length > FRAGMENTATION_LIMIT_BITS {
             let Some(settings) = self.channel_settings.get(channel_kind) else {
@@ -187,14 +278,35 @@ impl MessageManager {
             let messages =
                 self.message_fragmenter
                     .fragment_message(message_kinds, converter, message);
+            if let Some(receipt_key) = receipt_key {
+                self.undelivered_receipts
+                    .insert(receipt_key, messages.len());
+            }
             for message_fragment in messages {
-                channel.send_message(message_fragment);
+                match receipt_key {
+                    Some(receipt_key) => {
+                        channel.send_message_with_receipt(message_fragment, receipt_key)
+                    }
+                    None => channel.send_message(message_fragment),
+                }
             }
         } else {
-            channel.send_message(message);
+            match receipt_key {
+                Some(receipt_key) => {
+                    self.undelivered_receipts.insert(receipt_key, 1);
+                    channel.send_message_with_receipt(message, receipt_key);
+                }
+                None => channel.send_message(message),
+            }
         }
     }
 
+    /// Returns the receipts of Messages which have been delivered in full
+    /// since the last call
+    pub fn take_delivered_receipts(&mut self) -> Vec<MessageReceiptKey> {
+        std::mem::take(&mut self.delivered_receipts)
+    }
+
     pub fn send_request(
         &mut self,
         message_kinds: &MessageKinds,
@@ -248,10 +360,25 @@ impl MessageManager {
         packet_index: PacketIndex,
         has_written: &mut bool,
     ) {
-        for (channel_kind, channel) in &mut self.channel_senders {
-            if !channel.has_messages() {
-                continue;
-            }
+        // channels take turns at being written first, so that a backlogged
+        // channel can't keep the others out of every packet
+        let mut channel_kinds: Vec<ChannelKind> = self
+            .channel_senders
+            .iter()
+            .filter(|(_, channel)| channel.has_messages())
+            .map(|(channel_kind, _)| *channel_kind)
+            .collect();
+        if !channel_kinds.is_empty() {
+            let turn = self.next_channel_turn % channel_kinds.len();
+            channel_kinds.rotate_left(turn);
+            self.next_channel_turn = self.next_channel_turn.wrapping_add(1);
+        }
+        let last_index = channel_kinds.len().saturating_sub(1);
+
+        for (index, channel_kind) in channel_kinds.iter().enumerate() {
+            let channel = self.channel_senders.get_mut(channel_kind).unwrap();
+
+            let bits_free_before = writer.bits_free();
 
             // check that we can at least write a ChannelIndex and a MessageContinue bit
             let mut counter = writer.counter();
@@ -271,10 +398,34 @@ impl MessageManager {
             true.ser(writer);
             // write ChannelIndex
             channel_kind.ser(&protocol.channel_kinds, writer);
+            // the last channel may fill whatever is left of the packet
+            let budget_reserved_bits = match self.channel_budget_bits {
+                Some(budget_bits) if index < last_index => {
+                    writer.bits_free().saturating_sub(budget_bits)
+                }
+                _ => 0,
+            };
             // write Messages
-            if let Some(message_indices) =
+            let written_indices = if budget_reserved_bits == 0 {
                 channel.write_messages(&protocol.message_kinds, converter, writer, has_written)
-            {
+            } else {
+                // a message too large for the budget waits for the channel's
+                // turn at the rest of the packet, rather than being reported
+                // as too large for any packet
+                writer.reserve_bits(budget_reserved_bits);
+                let bits_free = writer.bits_free();
+                let mut budget_has_written = true;
+                let written_indices = channel.write_messages(
+                    &protocol.message_kinds,
+                    converter,
+                    writer,
+                    &mut budget_has_written,
+                );
+                *has_written |= writer.bits_free() < bits_free;
+                writer.release_bits(budget_reserved_bits);
+                written_indices
+            };
+            if let Some(message_indices) = written_indices {
                 self.packet_to_message_map
                     .entry(packet_index)
                     .or_insert_with(Vec::new);
@@ -285,6 +436,11 @@ impl MessageManager {
             // write MessageContinue finish bit, release
             writer.release_bits(1);
             false.ser(writer);
+
+            if let Some(written_bits) = &mut self.written_channel_bits {
+                *written_bits.entry(*channel_kind).or_default() +=
+                    bits_free_before - writer.bits_free();
+            }
         }
 
         // write ChannelContinue finish bit, release
@@ -312,8 +468,10 @@ impl MessageManager {
             // read channel id
             let channel_kind = ChannelKind::de(&protocol.channel_kinds, reader)?;
 
-            // continue read inside channel
-            let channel = self.channel_receivers.get_mut(&channel_kind).unwrap();
+            // continue read inside channel, which may not receive in this direction
+            let Some(channel) = self.channel_receivers.get_mut(&channel_kind) else {
+                return Err(SerdeErr);
+            };
             channel.read_messages(&protocol.message_kinds, entity_waitlist, &converter, reader)?;
         }
 
@@ -336,6 +494,15 @@ impl MessageManager {
         for (channel_kind, channel) in &mut self.channel_receivers {
             let messages =
                 channel.receive_messages(message_kinds, now, entity_waitlist, &entity_converter);
+
+            #[cfg(feature = "metrics")]
+            {
+                *self
+                    .received_message_counts
+                    .entry(*channel_kind)
+                    .or_default() += messages.len() as u64;
+            }
+
             output.push((channel_kind.clone(), messages));
         }
         output
@@ -373,15 +540,88 @@ impl MessageManager {
                     );
                 };
                 for (local_request_id, response) in responses {
-                    let global_request_id = channel_sender
-                        .process_incoming_response(&local_request_id)
-                        .unwrap();
+                    // the remote host may respond to a request it was never sent
+                    let Some(global_request_id) =
+                        channel_sender.process_incoming_response(&local_request_id)
+                    else {
+                        continue;
+                    };
                     response_output.push((global_request_id, response));
                 }
             }
         }
         (request_output, response_output)
     }
+
+    /// Returns whether Messages sent over the given Channel should be stamped
+    /// with the GameInstant they were sent at
+    pub fn tracks_latency(&self, channel_kind: &ChannelKind) -> bool {
+        self.channel_settings
+            .get(channel_kind)
+            .is_some_and(|settings| settings.tracks_latency())
+    }
+
+    /// Records how long a Message received over the given Channel took to
+    /// arrive, if it was stamped with the GameInstant it was sent at. `now`
+    /// must be on the same clock as that stamp
+    pub fn record_latency(
+        &mut self,
+        channel_kind: &ChannelKind,
+        message: &MessageContainer,
+        now: &GameInstant,
+    ) {
+        let Some(send_instant) = message.send_instant() else {
+            return;
+        };
+        // clocks are only synced to within a few milliseconds
+        let latency_ms = send_instant.offset_from(now).max(0) as u32;
+        self.channel_latencies
+            .entry(*channel_kind)
+            .or_default()
+            .record(latency_ms);
+    }
+
+    /// Returns the latencies of the most recent Messages received over the
+    /// given Channel, in milliseconds
+    pub fn latency_samples(&self, channel_kind: &ChannelKind) -> Vec<u32> {
+        self.channel_latencies
+            .get(channel_kind)
+            .map(|samples| samples.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns percentiles of how long recent Messages received over the given
+    /// Channel took to arrive
+    pub fn channel_latency(&self, channel_kind: &ChannelKind) -> Option<ChannelLatencyStats> {
+        self.channel_latencies.get(channel_kind)?.stats()
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl MessageManager {
+    /// Returns the number of Messages queued to be sent on the given Channel
+    pub fn sent_messages_count(&self, channel_kind: &ChannelKind) -> u64 {
+        self.sent_message_counts
+            .get(channel_kind)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of Messages received on the given Channel
+    pub fn received_messages_count(&self, channel_kind: &ChannelKind) -> u64 {
+        self.received_message_counts
+            .get(channel_kind)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of Messages re-transmitted across all reliable Channels
+    pub fn resent_messages_count(&self) -> u64 {
+        self.channel_senders
+            .values()
+            .map(|channel| channel.resent_messages_count())
+            .sum()
+    }
 }
 
 impl MessageManager {
@@ -394,6 +634,17 @@ impl MessageManager {
                     for message_index in message_indices {
                         channel.notify_message_delivered(message_index);
                     }
+                    for receipt_key in channel.take_delivered_receipts() {
+                        let Some(undelivered) = self.undelivered_receipts.get_mut(&receipt_key)
+                        else {
+                            continue;
+                        };
+                        *undelivered -= 1;
+                        if *undelivered == 0 {
+                            self.undelivered_receipts.remove(&receipt_key);
+                            self.delivered_receipts.push(receipt_key);
+                        }
+                    }
                 }
             }
         }
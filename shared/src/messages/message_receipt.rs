@@ -0,0 +1,13 @@
+/// Identifies a reliable Message whose delivery to the remote host is being
+/// tracked. Returned when sending a Message with a receipt, and reported once
+/// every fragment of that Message has been acknowledged
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Debug)]
+pub struct MessageReceiptKey {
+    id: u64,
+}
+
+impl MessageReceiptKey {
+    pub fn new(id: u64) -> Self {
+        Self { id }
+    }
+}
@@ -4,8 +4,10 @@ pub mod message;
 pub mod message_container;
 pub mod message_kinds;
 pub mod message_manager;
+pub mod message_receipt;
 pub mod named;
 pub mod request;
+pub mod stream_message;
 
 #[cfg(test)]
 mod tests;
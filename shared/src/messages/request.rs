@@ -30,6 +30,20 @@ impl<S: Response> ResponseSendKey<S> {
     }
 }
 
+// ResponderResult
+/// What a responder, registered to answer one kind of Request as it is
+/// received, does with each Request
+pub enum ResponderResult<S: Response> {
+    /// Sends the given Response right away
+    Respond(S),
+    /// Hands the Response's key to the given closure, so that the Request can
+    /// be answered later with `send_response()`
+    Defer(Box<dyn FnOnce(ResponseSendKey<S>) + Send>),
+    /// Leaves the Request to be read as a `RequestEvent`, as though no
+    /// responder were registered
+    DeclineToHandle,
+}
+
 // ResponseReceiveKey
 #[derive(Clone, Eq, PartialEq, Hash, Copy)]
 pub struct ResponseReceiveKey<S: Response> {
@@ -0,0 +1,20 @@
+use naia_derive::MessageInternal;
+
+/// An opaque payload sent over the StreamChannel. Streams are reliable and
+/// ordered, and payloads larger than a packet are fragmented along the way
+#[derive(MessageInternal)]
+pub struct StreamMessage {
+    bytes: Vec<u8>,
+}
+
+impl StreamMessage {
+    pub fn new(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
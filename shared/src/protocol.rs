@@ -9,11 +9,13 @@ use crate::{
             channel::{Channel, ChannelDirection, ChannelMode, ChannelSettings},
             channel_kinds::ChannelKinds,
             default_channels::DefaultChannelsPlugin,
+            stream_channel::StreamChannel,
             system_channel::SystemChannel,
         },
         fragment::FragmentedMessage,
         message::Message,
         message_kinds::MessageKinds,
+        stream_message::StreamMessage,
     },
     world::component::{component_kinds::ComponentKinds, replicate::Replicate},
     EntityEventMessage, ReliableSettings, Request, RequestOrResponse,
@@ -37,6 +39,9 @@ pub struct Protocol {
     pub compression: Option<CompressionConfig>,
     /// Whether or not Client Authoritative Entities will be allowed
     pub client_authoritative_entities: bool,
+    /// Whether or not stream payloads may be sent
+    pub streams: bool,
+    stable_ids: bool,
     locked: bool,
 }
 
@@ -61,6 +66,8 @@ impl Default for Protocol {
             tick_interval: Duration::from_millis(50),
             compression: None,
             client_authoritative_entities: false,
+            streams: false,
+            stable_ids: false,
             locked: false,
         }
     }
@@ -111,6 +118,27 @@ impl Protocol {
         self
     }
 
+    /// Registers the Channel & Message which carry stream payloads, sent with
+    /// `Client::send_stream_message()` & `Server::send_stream()`. Like any
+    /// other kinds, they take the next NetIds, so the Client & Server must
+    /// call this at the same point of building their Protocols
+    pub fn enable_streams(&mut self) -> &mut Self {
+        self.check_lock();
+        if self.streams {
+            return self;
+        }
+        self.streams = true;
+        self.message_kinds.add_message::<StreamMessage>();
+        // kept apart from the SystemChannel, so large streams don't hold up
+        // Entity events
+        self.channel_kinds
+            .add_channel::<StreamChannel>(ChannelSettings::new(
+                ChannelMode::OrderedReliable(ReliableSettings::default()),
+                ChannelDirection::Bidirectional,
+            ));
+        self
+    }
+
     pub fn add_default_channels(&mut self) -> &mut Self {
         self.check_lock();
         let plugin = DefaultChannelsPlugin;
@@ -129,6 +157,17 @@ impl Protocol {
         self
     }
 
+    /// Adds a Channel with settings beyond its direction & mode, such as
+    /// `ChannelSettings::with_latency_tracking()`
+    pub fn add_channel_with_settings<C: Channel>(
+        &mut self,
+        settings: ChannelSettings,
+    ) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.add_channel::<C>(settings);
+        self
+    }
+
     pub fn add_message<M: Message>(&mut self) -> &mut Self {
         self.check_lock();
         self.message_kinds.add_message::<M>();
@@ -149,8 +188,43 @@ impl Protocol {
         self
     }
 
+    /// Derives the NetIds of Channels, Messages & Components from a hash of
+    /// their type names, instead of the order they were registered in. A
+    /// Client & Server registering the same kinds in any order, for example
+    /// from plugins loaded in a different order, are then compatible.
+    ///
+    /// Type names are compared without their module paths, so two kinds with
+    /// the same name will panic when the Protocol is built
+    pub fn with_stable_ids(&mut self) -> &mut Self {
+        self.check_lock();
+        self.stable_ids = true;
+        self
+    }
+
+    /// Combines the Channels, Messages & Components registered in `other`,
+    /// such as a Protocol provided by a dynamically loaded plugin, into this
+    /// one. Kinds registered in both are kept once, and kinds new to this
+    /// Protocol are added in `other`'s registration order. Everything else,
+    /// such as the tick interval, is kept from this Protocol.
+    ///
+    /// Unless `with_stable_ids()` is used, NetIds still depend on the order
+    /// Protocols are merged in, so the Client & Server must merge them in the
+    /// same order.
+    ///
+    /// Panics if a Channel is registered in both with different settings, or
+    /// if a different kind of the same type name is already registered
+    pub fn merge(&mut self, other: Protocol) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.merge(other.channel_kinds);
+        self.message_kinds.merge(other.message_kinds);
+        self.component_kinds.merge(other.component_kinds);
+        self.streams |= other.streams;
+        self
+    }
+
     pub fn lock(&mut self) {
         self.check_lock();
+        self.assign_stable_ids();
         self.locked = true;
     }
 
@@ -161,6 +235,187 @@ impl Protocol {
     }
 
     pub fn build(&mut self) -> Self {
-        std::mem::take(self)
+        let mut protocol = std::mem::take(self);
+        protocol.assign_stable_ids();
+        protocol
+    }
+
+    fn assign_stable_ids(&mut self) {
+        if !self.stable_ids {
+            return;
+        }
+        self.channel_kinds.assign_stable_net_ids();
+        self.message_kinds.assign_stable_net_ids();
+        self.component_kinds.assign_stable_net_ids();
+    }
+
+    /// Get a hash of the ordered lists of registered Components, Messages, and
+    /// Channels. Ids are assigned in registration order (or type name order,
+    /// with `with_stable_ids()`), so if the Client and Server hashes differ,
+    /// their Protocols are incompatible. This is
+    /// exchanged & compared during the handshake.
+    pub fn schema_hash(&self) -> u64 {
+        let mut hasher = SchemaHasher::new();
+
+        hasher.write_kinds(b"components", self.component_kinds.type_names());
+        hasher.write_kinds(b"messages", self.message_kinds.type_names());
+        hasher.write_kinds(b"channels", self.channel_kinds.type_names());
+
+        hasher.finish()
+    }
+
+    /// Get a hash of the whole registered layout: everything in
+    /// `schema_hash()`, plus each Component's Property count & update mode
+    /// and each Channel's direction, mode, and settings. Meant for tooling
+    /// which needs to detect any change in the Protocol, it is not checked
+    /// during the handshake.
+    pub fn schema_digest(&self) -> u64 {
+        let mut hasher = SchemaHasher::new();
+
+        hasher.write(b"components");
+        for (kind, type_name, net_id, property_count) in self.component_kinds.iter() {
+            hasher.write_kind(net_id, type_name);
+            hasher.write(&(property_count as u64).to_le_bytes());
+            hasher.write(&[self.component_kinds.is_full_update(&kind) as u8]);
+        }
+
+        hasher.write(b"messages");
+        for (_, type_name, net_id) in self.message_kinds.iter() {
+            hasher.write_kind(net_id, type_name);
+        }
+
+        hasher.write(b"channels");
+        for (_, type_name, net_id, settings) in self.channel_kinds.iter() {
+            hasher.write_kind(net_id, type_name);
+            hasher.write(channel_direction_name(&settings.direction).as_bytes());
+            hasher.write(channel_mode_name(&settings.mode).as_bytes());
+            match &settings.mode {
+                ChannelMode::UnorderedReliable(reliable)
+                | ChannelMode::SequencedReliable(reliable)
+                | ChannelMode::OrderedReliable(reliable) => {
+                    hasher.write(&reliable.rtt_resend_factor.to_bits().to_le_bytes());
+                }
+                ChannelMode::TickBuffered(tick_buffer) => {
+                    hasher.write(&(tick_buffer.message_capacity as u64).to_le_bytes());
+                }
+                ChannelMode::UnorderedUnreliable | ChannelMode::SequencedUnreliable => {}
+            }
+            if settings.tracks_latency() {
+                hasher.write(b"latency_tracking");
+            }
+        }
+
+        hasher.finish()
+    }
+}
+
+pub(crate) fn channel_direction_name(direction: &ChannelDirection) -> &'static str {
+    match direction {
+        ChannelDirection::ClientToServer => "ClientToServer",
+        ChannelDirection::ServerToClient => "ServerToClient",
+        ChannelDirection::Bidirectional => "Bidirectional",
+    }
+}
+
+pub(crate) fn channel_mode_name(mode: &ChannelMode) -> &'static str {
+    match mode {
+        ChannelMode::UnorderedUnreliable => "UnorderedUnreliable",
+        ChannelMode::SequencedUnreliable => "SequencedUnreliable",
+        ChannelMode::UnorderedReliable(_) => "UnorderedReliable",
+        ChannelMode::SequencedReliable(_) => "SequencedReliable",
+        ChannelMode::OrderedReliable(_) => "OrderedReliable",
+        ChannelMode::TickBuffered(_) => "TickBuffered",
+    }
+}
+
+/// Get a stable hash of a single registered type's name, which is identical
+/// for the same type in any Protocol
+pub(crate) fn type_name_hash(type_name: &str) -> u64 {
+    let mut hasher = SchemaHasher::new();
+    hasher.write(short_type_name(type_name).as_bytes());
+    hasher.finish()
+}
+
+/// Panics if any two neighbouring entries of a list sorted by
+/// `type_name_hash()` share a hash, as they would share a stable NetId
+pub(crate) fn check_stable_id_collisions(
+    category: &str,
+    sorted_hashes: impl Iterator<Item = (u64, &'static str)>,
+) {
+    let mut previous: Option<(u64, &'static str)> = None;
+    for (hash, type_name) in sorted_hashes {
+        if let Some((previous_hash, previous_name)) = previous {
+            if previous_hash == hash {
+                panic!("{category} kinds `{previous_name}` and `{type_name}` have colliding stable ids, rename one of them");
+            }
+        }
+        previous = Some((hash, type_name));
+    }
+}
+
+// FNV-1a, used instead of std's DefaultHasher because the result must be
+// identical across builds, platforms, and Rust versions
+struct SchemaHasher {
+    hash: u64,
+}
+
+impl SchemaHasher {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    fn new() -> Self {
+        Self {
+            hash: Self::OFFSET_BASIS,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_kinds(&mut self, category: &[u8], type_names: &[&'static str]) {
+        self.write(category);
+        self.write(&(type_names.len() as u64).to_le_bytes());
+        for type_name in type_names {
+            self.write(short_type_name(type_name).as_bytes());
+            // separator, so that ["ab", "c"] and ["a", "bc"] hash differently
+            self.write(&[0]);
+        }
+    }
+
+    fn write_kind(&mut self, net_id: u16, type_name: &str) {
+        self.write(&net_id.to_le_bytes());
+        self.write(short_type_name(type_name).as_bytes());
+        self.write(&[0]);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+// Strips module paths from a type name (i.e. `my_game::Position<my_game::Unit>`
+// becomes `Position<Unit>`), so that a Client & Server which declare the same
+// types in differently named crates still produce the same schema hash
+fn short_type_name(full_name: &str) -> String {
+    fn last_segment(path: &str) -> &str {
+        path.rsplit("::").next().unwrap_or(path)
+    }
+
+    let mut output = String::with_capacity(full_name.len());
+    let mut path = String::new();
+    for c in full_name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            output.push_str(last_segment(&path));
+            path.clear();
+            output.push(c);
+        }
     }
+    output.push_str(last_segment(&path));
+    output
 }
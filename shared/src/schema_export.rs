@@ -0,0 +1,123 @@
+use std::io::{Result, Write};
+
+use crate::{
+    messages::channels::channel::ChannelMode,
+    protocol::{channel_direction_name, channel_mode_name},
+    Protocol,
+};
+
+impl Protocol {
+    /// Write a JSON description of every registered Channel, Message, and
+    /// Component, with the NetIds they use on the wire. Hashes are written as
+    /// hex strings, since they don't fit in a JSON number
+    pub fn write_schema_json(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, "{{")?;
+        writeln!(
+            writer,
+            "  \"schema_hash\": \"{:016x}\",",
+            self.schema_hash()
+        )?;
+        writeln!(
+            writer,
+            "  \"schema_digest\": \"{:016x}\",",
+            self.schema_digest()
+        )?;
+        writeln!(
+            writer,
+            "  \"tick_interval_ms\": {},",
+            self.tick_interval.as_millis()
+        )?;
+
+        writeln!(writer, "  \"channels\": [")?;
+        let channels: Vec<_> = self.channel_kinds.iter().collect();
+        for (index, (_, type_name, net_id, settings)) in channels.iter().enumerate() {
+            write!(
+                writer,
+                "    {{ \"net_id\": {}, \"name\": {}, \"direction\": \"{}\", \"mode\": \"{}\"",
+                net_id,
+                json_string(type_name),
+                channel_direction_name(&settings.direction),
+                channel_mode_name(&settings.mode)
+            )?;
+            match &settings.mode {
+                ChannelMode::UnorderedReliable(reliable)
+                | ChannelMode::SequencedReliable(reliable)
+                | ChannelMode::OrderedReliable(reliable) => {
+                    write!(
+                        writer,
+                        ", \"rtt_resend_factor\": {}",
+                        reliable.rtt_resend_factor
+                    )?;
+                }
+                ChannelMode::TickBuffered(tick_buffer) => {
+                    write!(
+                        writer,
+                        ", \"message_capacity\": {}, \"max_buffered_ticks\": {}, \"overflow_policy\": \"{:?}\"",
+                        tick_buffer.message_capacity,
+                        tick_buffer.max_buffered_ticks,
+                        tick_buffer.overflow_policy
+                    )?;
+                }
+                ChannelMode::UnorderedUnreliable | ChannelMode::SequencedUnreliable => {}
+            }
+            if settings.tracks_latency() {
+                write!(writer, ", \"latency_tracking\": true")?;
+            }
+            writeln!(writer, " }}{}", separator(index, channels.len()))?;
+        }
+        writeln!(writer, "  ],")?;
+
+        writeln!(writer, "  \"messages\": [")?;
+        let messages: Vec<_> = self.message_kinds.iter().collect();
+        for (index, (_, type_name, net_id)) in messages.iter().enumerate() {
+            writeln!(
+                writer,
+                "    {{ \"net_id\": {}, \"name\": {} }}{}",
+                net_id,
+                json_string(type_name),
+                separator(index, messages.len())
+            )?;
+        }
+        writeln!(writer, "  ],")?;
+
+        writeln!(writer, "  \"components\": [")?;
+        let components: Vec<_> = self.component_kinds.iter().collect();
+        for (index, (kind, type_name, net_id, property_count)) in components.iter().enumerate() {
+            writeln!(
+                writer,
+                "    {{ \"net_id\": {}, \"name\": {}, \"properties\": {}, \"full_update\": {} }}{}",
+                net_id,
+                json_string(type_name),
+                property_count,
+                self.component_kinds.is_full_update(kind),
+                separator(index, components.len())
+            )?;
+        }
+        writeln!(writer, "  ]")?;
+
+        writeln!(writer, "}}")
+    }
+}
+
+fn separator(index: usize, len: usize) -> &'static str {
+    if index + 1 < len {
+        ","
+    } else {
+        ""
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
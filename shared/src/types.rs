@@ -1,8 +1,35 @@
+use crate::{sequence_greater_than, sequence_less_than, wrapping_diff};
+
 pub type PacketIndex = u16;
 pub type Tick = u16;
 pub type MessageIndex = u16;
 pub type ShortMessageIndex = u8;
 
+/// Comparisons between Ticks which account for the Tick wrapping around
+/// after `u16::MAX`, so that plain `<` / `>` are never needed
+pub trait TickExt {
+    /// Returns how many Ticks `self` is ahead of `other`, negative if behind
+    fn diff(&self, other: Tick) -> i32;
+    /// Returns whether `self` comes after `other`
+    fn is_after(&self, other: Tick) -> bool;
+    /// Returns whether `self` comes before `other`
+    fn is_before(&self, other: Tick) -> bool;
+}
+
+impl TickExt for Tick {
+    fn diff(&self, other: Tick) -> i32 {
+        i32::from(wrapping_diff(other, *self))
+    }
+
+    fn is_after(&self, other: Tick) -> bool {
+        sequence_greater_than(*self, other)
+    }
+
+    fn is_before(&self, other: Tick) -> bool {
+        sequence_less_than(*self, other)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum HostType {
     Server,
@@ -0,0 +1,742 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::Hash,
+    ops::{Deref, Range},
+};
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger};
+
+use crate::world::{
+    component::{diff_mask::DiffMask, property::Property, property_mutate::PropertyMutator},
+    delegation::auth_channel::EntityAuthAccessor,
+};
+
+// A collection Property's first DiffMask bit is set when the whole collection
+// must be sent, and its second when only the entries which changed must be
+const WHOLE_OFFSET: u8 = 0;
+const CHANGES_OFFSET: u8 = 1;
+
+// Changes are tracked since the collection was last queued to be sent whole,
+// for every remote host at once. Once more than half of its entries have
+// changed, sending it whole is no more expensive, and lets tracking restart
+fn should_send_whole(changed: usize, len: usize) -> bool {
+    changed * 2 > len
+}
+
+fn write_length(length: usize, writer: &mut dyn BitWrite) {
+    UnsignedVariableInteger::<5>::new(length as u64).ser(writer);
+}
+
+fn read_length(reader: &mut BitReader) -> Result<usize, SerdeErr> {
+    Ok(UnsignedVariableInteger::<5>::de(reader)?.get() as usize)
+}
+
+/// A Property of a Component that contains a map, which is updated one key
+/// at a time. Inserting, updating or removing a key only sends that key,
+/// while newly replicated Components receive the whole map.
+///
+/// A MapProperty occupies `MapProperty::<K, V>::DIFF_BITS` bits of its
+/// Component's DiffMask: the first is set when the whole map must be sent,
+/// and the second when only the keys which changed must be.
+///
+/// ```
+/// use naia_shared::{MapProperty, Replicate, Serde};
+///
+/// #[derive(Serde, Clone, PartialEq)]
+/// pub struct ItemStack {
+///     pub item: u16,
+///     pub count: u8,
+/// }
+///
+/// #[derive(Replicate)]
+/// pub struct Inventory {
+///     pub slots: MapProperty<u16, ItemStack>,
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct MapProperty<K: Serde + Eq + Hash, V: Serde> {
+    inner: Property<HashMap<K, V>>,
+    // keys inserted, updated or removed since the map was last queued to be
+    // sent whole, in the order they first changed
+    changed_keys: Vec<K>,
+    changed_key_set: HashSet<K>,
+}
+
+impl<K: Serde + Eq + Hash, V: Serde> MapProperty<K, V> {
+    /// The number of DiffMask bits used by this Property
+    pub const DIFF_BITS: u8 = 2;
+
+    /// Create a new Local MapProperty
+    pub fn new_local(value: HashMap<K, V>) -> Self {
+        Self::from_property(Property::new_local(value))
+    }
+
+    /// Create a new host-owned MapProperty
+    pub fn host_owned(value: HashMap<K, V>, mutator_index: u8) -> Self {
+        Self::from_property(Property::host_owned(value, mutator_index))
+    }
+
+    /// Given a cursor into incoming packet data, initializes the MapProperty
+    /// with the synced map
+    pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Self::from_property(Property::new_read(reader)?))
+    }
+
+    fn from_property(inner: Property<HashMap<K, V>>) -> Self {
+        Self {
+            inner,
+            changed_keys: Vec::new(),
+            changed_key_set: HashSet::new(),
+        }
+    }
+
+    /// Set an PropertyMutator to track changes to the MapProperty
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.inner.set_mutator(mutator);
+    }
+
+    /// See `Property::set_field_authority()`
+    pub fn set_field_authority(&mut self, field_authority: Option<&DiffMask>) {
+        self.inner.set_field_authority(field_authority);
+    }
+
+    /// See `Property::has_field_authority()`
+    pub fn has_field_authority(&self) -> bool {
+        self.inner.has_field_authority()
+    }
+
+    /// Inserts a value, queueing an update of only its key, unless the key
+    /// already held an equal value. Returns the previous value
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.inner.inner().get(&key) == Some(&value) {
+            return Some(value);
+        }
+        self.changes_mut(&key).insert(key, value)
+    }
+
+    /// Removes a key, queueing an update of only that key if it was present.
+    /// Returns the removed value
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.inner.inner().contains_key(key) {
+            return None;
+        }
+        self.changes_mut(key).remove(key)
+    }
+
+    /// Modifies the value of a key in place, queueing an update of only that
+    /// key. Returns whether the key was present
+    pub fn modify(&mut self, key: &K, f: impl FnOnce(&mut V)) -> bool {
+        if !self.inner.inner().contains_key(key) {
+            return false;
+        }
+        if let Some(value) = self.changes_mut(key).get_mut(key) {
+            f(value);
+        }
+        true
+    }
+
+    /// Removes every key, queueing an update of the whole map unless it was
+    /// already empty
+    pub fn clear(&mut self) {
+        if self.inner.inner().is_empty() {
+            return;
+        }
+        self.whole_mut().clear();
+    }
+
+    /// Replaces the whole map, queueing an update of the whole map unless it
+    /// is unchanged
+    pub fn set_all(&mut self, value: HashMap<K, V>) {
+        if *self.inner.inner() == value {
+            return;
+        }
+        *self.whole_mut() = value;
+    }
+
+    fn changes_mut(&mut self, key: &K) -> &mut HashMap<K, V> {
+        if self.changed_key_set.insert(key.clone()) {
+            self.changed_keys.push(key.clone());
+        }
+        if should_send_whole(self.changed_keys.len(), self.inner.inner().len()) {
+            return self.whole_mut();
+        }
+        self.inner.inner_mut_with_offsets(&[CHANGES_OFFSET])
+    }
+
+    fn whole_mut(&mut self) -> &mut HashMap<K, V> {
+        self.changed_keys.clear();
+        self.changed_key_set.clear();
+        self.inner.inner_mut_with_offsets(&[WHOLE_OFFSET])
+    }
+
+    /// Returns whether any of this MapProperty's bits are set in the DiffMask,
+    /// given the index of its first bit
+    pub fn has_update(index: u8, diff_mask: &DiffMask) -> bool {
+        (index..index + Self::DIFF_BITS).any(|bit| diff_mask.bit(bit) == Some(true))
+    }
+
+    /// Writes the whole map into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.inner.write(writer);
+    }
+
+    /// Writes either the whole map, or the keys which changed, depending on
+    /// which are set in the DiffMask, into outgoing byte stream, given the
+    /// index of this MapProperty's first bit
+    pub fn write_update(&self, index: u8, diff_mask: &DiffMask, writer: &mut dyn BitWrite) {
+        let map = self.inner.writable_inner();
+        let whole = diff_mask.bit(index + WHOLE_OFFSET) == Some(true);
+        whole.ser(writer);
+        if whole {
+            map.ser(writer);
+            return;
+        }
+        write_length(self.changed_keys.len(), writer);
+        for key in &self.changed_keys {
+            key.ser(writer);
+            match map.get(key) {
+                Some(value) => {
+                    true.ser(writer);
+                    value.ser(writer);
+                }
+                None => {
+                    false.ser(writer);
+                }
+            }
+        }
+    }
+
+    /// Given a cursor into incoming packet data, copies an update written by
+    /// `write_update()` into the given writer
+    pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
+        MapUpdate::<K, V>::de(reader)?.ser(writer);
+        Ok(())
+    }
+
+    /// Given cursors into two updates written by `write_update()`, writes a
+    /// single update into the given writer which is the same as applying both
+    /// in order
+    pub fn merge_updates(
+        earlier: &mut BitReader,
+        later: &mut BitReader,
+        writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        let earlier = MapUpdate::<K, V>::de(earlier)?;
+        let later = MapUpdate::<K, V>::de(later)?;
+        earlier.merge(later).ser(writer);
+        Ok(())
+    }
+
+    /// Given a cursor into incoming packet data, applies an update written by
+    /// `write_update()`
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let update = MapUpdate::<K, V>::de(reader)?;
+        self.inner.read_in_place(|map| update.apply(map));
+        Ok(())
+    }
+
+    /// Compare to another MapProperty
+    pub fn equals(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+
+    /// See `Property::set_if_changed()`
+    pub fn set_if_changed(&mut self, other: &Self) -> bool {
+        if self.equals(other) {
+            return false;
+        }
+        self.mirror(other);
+        true
+    }
+
+    /// Set value to the value of another MapProperty, queues the whole map for
+    /// update if it changes
+    pub fn mirror(&mut self, other: &Self) {
+        self.set_all(other.inner.inner().clone());
+    }
+
+    /// Migrate Remote MapProperty to Public version
+    pub fn remote_publish(&mut self, mutator_index: u8, mutator: &PropertyMutator) {
+        self.inner.remote_publish(mutator_index, mutator);
+    }
+
+    /// Migrate Remote MapProperty to Private version
+    pub fn remote_unpublish(&mut self) {
+        self.inner.remote_unpublish();
+    }
+
+    /// Migrate MapProperty to Delegated version
+    pub fn enable_delegation(
+        &mut self,
+        accessor: &EntityAuthAccessor,
+        mutator_opt: Option<(u8, &PropertyMutator)>,
+    ) {
+        self.inner.enable_delegation(accessor, mutator_opt);
+    }
+
+    /// Migrate Delegated MapProperty to Host-Owned (Public) version
+    pub fn disable_delegation(&mut self) {
+        self.inner.disable_delegation();
+    }
+
+    /// Migrate Host MapProperty to Local version
+    pub fn localize(&mut self) {
+        self.inner.localize();
+    }
+}
+
+// Like EnumProperty, there is no DerefMut, as each change must be tracked.
+// Use `insert()`, `remove()` or `modify()` instead
+impl<K: Serde + Eq + Hash, V: Serde> Deref for MapProperty<K, V> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.inner()
+    }
+}
+
+enum MapUpdate<K, V> {
+    Whole(HashMap<K, V>),
+    // each changed key, with its new value or None if it was removed
+    Changes(Vec<(K, Option<V>)>),
+}
+
+impl<K: Serde + Eq + Hash, V: Serde> MapUpdate<K, V> {
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        if bool::de(reader)? {
+            return Ok(Self::Whole(HashMap::de(reader)?));
+        }
+        let count = read_length(reader)?;
+        let mut changes = Vec::with_capacity(count.min(reader.bits_remaining() as usize));
+        for _ in 0..count {
+            let key = K::de(reader)?;
+            let value = Option::<V>::de(reader)?;
+            changes.push((key, value));
+        }
+        Ok(Self::Changes(changes))
+    }
+
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self {
+            Self::Whole(map) => {
+                true.ser(writer);
+                map.ser(writer);
+            }
+            Self::Changes(changes) => {
+                false.ser(writer);
+                write_length(changes.len(), writer);
+                for (key, value) in changes {
+                    key.ser(writer);
+                    value.ser(writer);
+                }
+            }
+        }
+    }
+
+    fn apply(self, map: &mut HashMap<K, V>) {
+        match self {
+            Self::Whole(whole) => {
+                *map = whole;
+            }
+            Self::Changes(changes) => {
+                for (key, value) in changes {
+                    match value {
+                        Some(value) => {
+                            map.insert(key, value);
+                        }
+                        None => {
+                            map.remove(&key);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn merge(self, later: Self) -> Self {
+        match (self, later) {
+            (_, Self::Whole(later)) => Self::Whole(later),
+            (Self::Whole(mut earlier), later) => {
+                later.apply(&mut earlier);
+                Self::Whole(earlier)
+            }
+            (Self::Changes(earlier), Self::Changes(later)) => {
+                let later_keys: HashSet<&K> = later.iter().map(|(key, _)| key).collect();
+                let mut merged: Vec<(K, Option<V>)> = earlier
+                    .into_iter()
+                    .filter(|(key, _)| !later_keys.contains(key))
+                    .collect();
+                merged.extend(later);
+                Self::Changes(merged)
+            }
+        }
+    }
+}
+
+/// A Property of a Component that contains a list, which is updated one
+/// index at a time. Setting or pushing a value only sends its index, while
+/// newly replicated Components receive the whole list.
+///
+/// A VecProperty occupies `VecProperty::<T>::DIFF_BITS` bits of its
+/// Component's DiffMask: the first is set when the whole list must be sent,
+/// and the second when only the indices which changed must be. Inserting or
+/// removing a value changes the index of every value after it.
+///
+/// ```
+/// use naia_shared::{Replicate, VecProperty};
+///
+/// #[derive(Replicate)]
+/// pub struct Path {
+///     pub waypoints: VecProperty<(i16, i16)>,
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct VecProperty<T: Serde> {
+    inner: Property<Vec<T>>,
+    // indices set, pushed or popped since the list was last queued to be
+    // sent whole
+    changed_indices: BTreeSet<usize>,
+}
+
+impl<T: Serde> VecProperty<T> {
+    /// The number of DiffMask bits used by this Property
+    pub const DIFF_BITS: u8 = 2;
+
+    /// Create a new Local VecProperty
+    pub fn new_local(value: Vec<T>) -> Self {
+        Self::from_property(Property::new_local(value))
+    }
+
+    /// Create a new host-owned VecProperty
+    pub fn host_owned(value: Vec<T>, mutator_index: u8) -> Self {
+        Self::from_property(Property::host_owned(value, mutator_index))
+    }
+
+    /// Given a cursor into incoming packet data, initializes the VecProperty
+    /// with the synced list
+    pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Self::from_property(Property::new_read(reader)?))
+    }
+
+    fn from_property(inner: Property<Vec<T>>) -> Self {
+        Self {
+            inner,
+            changed_indices: BTreeSet::new(),
+        }
+    }
+
+    /// Set an PropertyMutator to track changes to the VecProperty
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.inner.set_mutator(mutator);
+    }
+
+    /// See `Property::set_field_authority()`
+    pub fn set_field_authority(&mut self, field_authority: Option<&DiffMask>) {
+        self.inner.set_field_authority(field_authority);
+    }
+
+    /// See `Property::has_field_authority()`
+    pub fn has_field_authority(&self) -> bool {
+        self.inner.has_field_authority()
+    }
+
+    /// Appends a value, queueing an update of only its index
+    pub fn push(&mut self, value: T) {
+        let index = self.inner.inner().len();
+        self.changes_mut(index..index + 1).push(value);
+    }
+
+    /// Removes the last value, queueing an update of only the list's length
+    pub fn pop(&mut self) -> Option<T> {
+        if self.inner.inner().is_empty() {
+            return None;
+        }
+        self.changes_mut(0..0).pop()
+    }
+
+    /// Sets the value at an index, queueing an update of only that index,
+    /// unless it already held an equal value. Returns the previous value.
+    /// Panics if the index is out of bounds
+    pub fn set(&mut self, index: usize, value: T) -> T {
+        if self.inner.inner()[index] == value {
+            return value;
+        }
+        std::mem::replace(&mut self.changes_mut(index..index + 1)[index], value)
+    }
+
+    /// Modifies the value at an index in place, queueing an update of only
+    /// that index. Panics if the index is out of bounds
+    pub fn modify(&mut self, index: usize, f: impl FnOnce(&mut T)) {
+        if index >= self.inner.inner().len() {
+            panic!("index {} out of bounds for VecProperty", index);
+        }
+        f(&mut self.changes_mut(index..index + 1)[index]);
+    }
+
+    /// Inserts a value at an index, queueing an update of it and every index
+    /// after it. Panics if the index is greater than the list's length
+    pub fn insert(&mut self, index: usize, value: T) {
+        let len = self.inner.inner().len();
+        self.changes_mut(index..len + 1).insert(index, value);
+    }
+
+    /// Removes the value at an index, queueing an update of every index after
+    /// it. Panics if the index is out of bounds
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.inner.inner().len();
+        self.changes_mut(index..len).remove(index)
+    }
+
+    /// Shortens the list, queueing an update of only its length if it changes
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.inner.inner().len() {
+            return;
+        }
+        self.changes_mut(0..0).truncate(len);
+    }
+
+    /// Removes every value, queueing an update of the whole list unless it was
+    /// already empty
+    pub fn clear(&mut self) {
+        if self.inner.inner().is_empty() {
+            return;
+        }
+        self.whole_mut().clear();
+    }
+
+    /// Replaces the whole list, queueing an update of the whole list unless it
+    /// is unchanged
+    pub fn set_all(&mut self, value: Vec<T>) {
+        if *self.inner.inner() == value {
+            return;
+        }
+        *self.whole_mut() = value;
+    }
+
+    fn changes_mut(&mut self, indices: Range<usize>) -> &mut Vec<T> {
+        self.changed_indices.extend(indices);
+        if should_send_whole(self.changed_indices.len(), self.inner.inner().len()) {
+            return self.whole_mut();
+        }
+        self.inner.inner_mut_with_offsets(&[CHANGES_OFFSET])
+    }
+
+    fn whole_mut(&mut self) -> &mut Vec<T> {
+        self.changed_indices.clear();
+        self.inner.inner_mut_with_offsets(&[WHOLE_OFFSET])
+    }
+
+    /// Returns whether any of this VecProperty's bits are set in the DiffMask,
+    /// given the index of its first bit
+    pub fn has_update(index: u8, diff_mask: &DiffMask) -> bool {
+        (index..index + Self::DIFF_BITS).any(|bit| diff_mask.bit(bit) == Some(true))
+    }
+
+    /// Writes the whole list into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.inner.write(writer);
+    }
+
+    /// Writes either the whole list, or its length and the indices which
+    /// changed, depending on which are set in the DiffMask, into outgoing
+    /// byte stream, given the index of this VecProperty's first bit
+    pub fn write_update(&self, index: u8, diff_mask: &DiffMask, writer: &mut dyn BitWrite) {
+        let list = self.inner.writable_inner();
+        let whole = diff_mask.bit(index + WHOLE_OFFSET) == Some(true);
+        whole.ser(writer);
+        if whole {
+            list.ser(writer);
+            return;
+        }
+        write_length(list.len(), writer);
+        let changed = self.changed_indices.range(..list.len());
+        write_length(changed.clone().count(), writer);
+        for changed_index in changed {
+            write_length(*changed_index, writer);
+            list[*changed_index].ser(writer);
+        }
+    }
+
+    /// Given a cursor into incoming packet data, copies an update written by
+    /// `write_update()` into the given writer
+    pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
+        VecUpdate::<T>::de(reader)?.ser(writer);
+        Ok(())
+    }
+
+    /// Given cursors into two updates written by `write_update()`, writes a
+    /// single update into the given writer which is the same as applying both
+    /// in order
+    pub fn merge_updates(
+        earlier: &mut BitReader,
+        later: &mut BitReader,
+        writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        let earlier = VecUpdate::<T>::de(earlier)?;
+        let later = VecUpdate::<T>::de(later)?;
+        earlier.merge(later).ser(writer);
+        Ok(())
+    }
+
+    /// Given a cursor into incoming packet data, applies an update written by
+    /// `write_update()`
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let update = VecUpdate::<T>::de(reader)?;
+        self.inner.read_in_place(|list| update.apply(list));
+        Ok(())
+    }
+
+    /// Compare to another VecProperty
+    pub fn equals(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+
+    /// See `Property::set_if_changed()`
+    pub fn set_if_changed(&mut self, other: &Self) -> bool {
+        if self.equals(other) {
+            return false;
+        }
+        self.mirror(other);
+        true
+    }
+
+    /// Set value to the value of another VecProperty, queues the whole list
+    /// for update if it changes
+    pub fn mirror(&mut self, other: &Self) {
+        self.set_all(other.inner.inner().clone());
+    }
+
+    /// Migrate Remote VecProperty to Public version
+    pub fn remote_publish(&mut self, mutator_index: u8, mutator: &PropertyMutator) {
+        self.inner.remote_publish(mutator_index, mutator);
+    }
+
+    /// Migrate Remote VecProperty to Private version
+    pub fn remote_unpublish(&mut self) {
+        self.inner.remote_unpublish();
+    }
+
+    /// Migrate VecProperty to Delegated version
+    pub fn enable_delegation(
+        &mut self,
+        accessor: &EntityAuthAccessor,
+        mutator_opt: Option<(u8, &PropertyMutator)>,
+    ) {
+        self.inner.enable_delegation(accessor, mutator_opt);
+    }
+
+    /// Migrate Delegated VecProperty to Host-Owned (Public) version
+    pub fn disable_delegation(&mut self) {
+        self.inner.disable_delegation();
+    }
+
+    /// Migrate Host VecProperty to Local version
+    pub fn localize(&mut self) {
+        self.inner.localize();
+    }
+}
+
+// Like EnumProperty, there is no DerefMut, as each change must be tracked.
+// Use `set()`, `push()` or `modify()` instead
+impl<T: Serde> Deref for VecProperty<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.inner()
+    }
+}
+
+enum VecUpdate<T> {
+    Whole(Vec<T>),
+    // the list is truncated to `len`, then each changed index is set in
+    // ascending order, with indices at the end of the list pushed
+    Changes { len: usize, values: Vec<(usize, T)> },
+}
+
+impl<T: Serde> VecUpdate<T> {
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        if bool::de(reader)? {
+            return Ok(Self::Whole(Vec::de(reader)?));
+        }
+        let len = read_length(reader)?;
+        let count = read_length(reader)?;
+        let mut values = Vec::with_capacity(count.min(reader.bits_remaining() as usize));
+        for _ in 0..count {
+            let index = read_length(reader)?;
+            let value = T::de(reader)?;
+            values.push((index, value));
+        }
+        Ok(Self::Changes { len, values })
+    }
+
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self {
+            Self::Whole(list) => {
+                true.ser(writer);
+                list.ser(writer);
+            }
+            Self::Changes { len, values } => {
+                false.ser(writer);
+                write_length(*len, writer);
+                write_length(values.len(), writer);
+                for (index, value) in values {
+                    write_length(*index, writer);
+                    value.ser(writer);
+                }
+            }
+        }
+    }
+
+    fn apply(self, list: &mut Vec<T>) {
+        match self {
+            Self::Whole(whole) => {
+                *list = whole;
+            }
+            Self::Changes { len, values } => {
+                list.truncate(len);
+                for (index, value) in values {
+                    if index < list.len() {
+                        list[index] = value;
+                    } else if index == list.len() {
+                        list.push(value);
+                    }
+                    // otherwise an earlier update has not arrived, and the
+                    // value cannot be placed
+                }
+            }
+        }
+    }
+
+    fn merge(self, later: Self) -> Self {
+        match (self, later) {
+            (_, Self::Whole(later)) => Self::Whole(later),
+            (Self::Whole(mut earlier), later) => {
+                later.apply(&mut earlier);
+                Self::Whole(earlier)
+            }
+            (
+                Self::Changes {
+                    len: earlier_len,
+                    values: earlier_values,
+                },
+                Self::Changes {
+                    len: later_len,
+                    values: later_values,
+                },
+            ) => {
+                // values beyond the later truncation are discarded by it
+                let mut merged: BTreeMap<usize, T> = earlier_values
+                    .into_iter()
+                    .filter(|(index, _)| *index < later_len)
+                    .collect();
+                merged.extend(later_values);
+                Self::Changes {
+                    len: earlier_len.min(later_len),
+                    values: merged.into_iter().collect(),
+                }
+            }
+        }
+    }
+}
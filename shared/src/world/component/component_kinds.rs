@@ -1,8 +1,12 @@
-use std::{any::TypeId, collections::HashMap};
+use std::{
+    any::{type_name, TypeId},
+    collections::HashMap,
+};
 
 use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
 
 use crate::{
+    protocol::{check_stable_id_collisions, type_name_hash},
     ComponentFieldUpdate, ComponentUpdate, LocalEntityAndGlobalEntityConverter, RemoteEntity,
     Replicate, ReplicateBuilder,
 };
@@ -39,7 +43,12 @@ impl ComponentKind {
 
     pub fn de(component_kinds: &ComponentKinds, reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let net_id: NetId = NetId::de(reader)?;
-        Ok(component_kinds.net_id_to_kind(&net_id))
+        // the NetId may be garbage, if the packet was malformed
+        component_kinds
+            .net_id_map
+            .get(&net_id)
+            .copied()
+            .ok_or(SerdeErr)
     }
 }
 
@@ -54,6 +63,7 @@ pub struct ComponentKinds {
     current_net_id: NetId,
     kind_map: HashMap<ComponentKind, (NetId, Box<dyn ReplicateBuilder>)>,
     net_id_map: HashMap<NetId, ComponentKind>,
+    type_names: Vec<&'static str>,
 }
 
 impl ComponentKinds {
@@ -62,36 +72,135 @@ impl ComponentKinds {
             current_net_id: 0,
             kind_map: HashMap::new(),
             net_id_map: HashMap::new(),
+            type_names: Vec::new(),
         }
     }
 
     pub fn add_component<C: Replicate>(&mut self) {
-        let component_kind = ComponentKind::of::<C>();
+        self.insert_kind(
+            ComponentKind::of::<C>(),
+            type_name::<C>(),
+            C::create_builder(),
+        );
+    }
 
+    fn insert_kind(
+        &mut self,
+        component_kind: ComponentKind,
+        type_name: &'static str,
+        builder: Box<dyn ReplicateBuilder>,
+    ) {
         let net_id = self.current_net_id;
-        self.kind_map
-            .insert(component_kind, (net_id, C::create_builder()));
+        self.kind_map.insert(component_kind, (net_id, builder));
         self.net_id_map.insert(net_id, component_kind);
+        self.type_names.push(type_name);
         self.current_net_id += 1;
         //TODO: check for current_id overflow?
     }
 
+    /// Adds each Component of `other` which isn't registered yet, in `other`'s
+    /// registration order. Panics if a different Component has the same type
+    /// name as one already registered
+    pub(crate) fn merge(&mut self, mut other: ComponentKinds) {
+        let type_names = std::mem::take(&mut other.type_names);
+        for (net_id, type_name) in type_names.into_iter().enumerate() {
+            let component_kind = other.net_id_to_kind(&(net_id as NetId));
+            let Some((_, builder)) = other.kind_map.remove(&component_kind) else {
+                continue;
+            };
+            if self.kind_map.contains_key(&component_kind) {
+                continue;
+            }
+            if self.hash_to_kind(type_name_hash(type_name)).is_some() {
+                panic!("Cannot merge Protocols: a different Component named `{type_name}` is already registered");
+            }
+            self.insert_kind(component_kind, type_name, builder);
+        }
+    }
+
+    /// Reassigns NetIds in order of each Component's type name hash, so that
+    /// they no longer depend on registration order
+    pub(crate) fn assign_stable_net_ids(&mut self) {
+        let type_names = std::mem::take(&mut self.type_names);
+        let mut entries: Vec<_> = self
+            .kind_map
+            .drain()
+            .map(|(component_kind, (net_id, builder))| {
+                let type_name = type_names[net_id as usize];
+                (
+                    type_name_hash(type_name),
+                    type_name,
+                    component_kind,
+                    builder,
+                )
+            })
+            .collect();
+        entries.sort_by_key(|(hash, ..)| *hash);
+        check_stable_id_collisions(
+            "Component",
+            entries
+                .iter()
+                .map(|(hash, type_name, ..)| (*hash, *type_name)),
+        );
+
+        self.current_net_id = 0;
+        self.net_id_map.clear();
+        for (_, type_name, component_kind, builder) in entries {
+            self.insert_kind(component_kind, type_name, builder);
+        }
+    }
+
+    /// Get the type names of all registered Components, in registration order
+    pub fn type_names(&self) -> &[&'static str] {
+        &self.type_names
+    }
+
+    /// Iterate over all registered Components in NetId order, with each one's
+    /// type name, NetId, and number of Properties
+    pub fn iter(&self) -> impl Iterator<Item = (ComponentKind, &'static str, u16, usize)> + '_ {
+        self.type_names
+            .iter()
+            .enumerate()
+            .map(|(net_id, type_name)| {
+                let net_id = net_id as NetId;
+                let component_kind = self.net_id_to_kind(&net_id);
+                let property_count = self.kind_to_builder(&component_kind).property_count();
+                (component_kind, *type_name, net_id, property_count)
+            })
+    }
+
     pub fn read(
         &self,
         reader: &mut BitReader,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<Box<dyn Replicate>, SerdeErr> {
         let component_kind: ComponentKind = ComponentKind::de(self, reader)?;
-        return self
-            .kind_to_builder(&component_kind)
-            .read(reader, converter);
+        return self.read_fields(&component_kind, reader, converter);
+    }
+
+    /// Reads a Component of a known kind, written with `Replicate::write_fields()`
+    pub fn read_fields(
+        &self,
+        component_kind: &ComponentKind,
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Box<dyn Replicate>, SerdeErr> {
+        return self.kind_to_builder(component_kind).read(reader, converter);
     }
 
     pub fn read_create_update(&self, reader: &mut BitReader) -> Result<ComponentUpdate, SerdeErr> {
         let component_kind: ComponentKind = ComponentKind::de(self, reader)?;
-        return self
-            .kind_to_builder(&component_kind)
-            .read_create_update(reader);
+        return self.read_create_update_of_kind(&component_kind, reader);
+    }
+
+    /// Reads an update to a Component of a known kind
+    pub fn read_create_update_of_kind(
+        &self,
+        component_kind: &ComponentKind,
+        reader: &mut BitReader,
+    ) -> Result<ComponentUpdate, SerdeErr> {
+        self.kind_to_builder(component_kind)
+            .read_create_update(reader)
     }
 
     pub fn split_update(
@@ -111,10 +220,44 @@ impl ComponentKinds {
             .split_update(converter, update);
     }
 
+    pub fn merge_updates(
+        &self,
+        component_kind: &ComponentKind,
+        earlier: &ComponentUpdate,
+        later: &ComponentUpdate,
+    ) -> Result<ComponentUpdate, SerdeErr> {
+        return self
+            .kind_to_builder(component_kind)
+            .merge_updates(earlier, later);
+    }
+
     pub fn kind_to_name(&self, component_kind: &ComponentKind) -> String {
         return self.kind_to_builder(component_kind).name();
     }
 
+    /// Whether updates of the Component always include all of its fields,
+    /// rather than only those which changed
+    pub fn is_full_update(&self, component_kind: &ComponentKind) -> bool {
+        return self.kind_to_builder(component_kind).full_update();
+    }
+
+    /// Get a hash of the Component's type name, which unlike its NetId does
+    /// not depend on registration order, so it can identify the same kind
+    /// across separate processes sharing a Protocol
+    pub fn kind_to_hash(&self, component_kind: &ComponentKind) -> u64 {
+        let net_id = self.kind_to_net_id(component_kind);
+        return type_name_hash(self.type_names[net_id as usize]);
+    }
+
+    /// Get the registered Component kind with the given `kind_to_hash()`
+    pub fn hash_to_kind(&self, hash: u64) -> Option<ComponentKind> {
+        let net_id = self
+            .type_names
+            .iter()
+            .position(|type_name| type_name_hash(type_name) == hash)?;
+        return self.net_id_map.get(&(net_id as NetId)).copied();
+    }
+
     fn net_id_to_kind(&self, net_id: &NetId) -> ComponentKind {
         return *self.net_id_map.get(net_id).expect(
             "Must properly initialize Component with Protocol via `add_component()` function!",
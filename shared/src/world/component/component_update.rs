@@ -19,6 +19,29 @@ impl ComponentUpdate {
         self.buffer.borrow()
     }
 
+    /// Merges a later update of the same Component into this one, so that
+    /// applying the result is the same as applying both in order. Fields
+    /// which only one of them includes are kept as they are, and fields
+    /// which both include take the value from `other`, so superseded values
+    /// are never applied.
+    ///
+    /// Panics if `other` is an update of a different Component.
+    pub fn merge(
+        &mut self,
+        component_kinds: &ComponentKinds,
+        other: ComponentUpdate,
+    ) -> Result<(), SerdeErr> {
+        if self.kind != other.kind {
+            panic!(
+                "Can't merge an update of Component `{}` into an update of Component `{}`",
+                component_kinds.kind_to_name(&other.kind),
+                component_kinds.kind_to_name(&self.kind),
+            );
+        }
+        *self = component_kinds.merge_updates(&self.kind, self, &other)?;
+        Ok(())
+    }
+
     pub(crate) fn split_into_waiting_and_ready(
         self,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
@@ -46,6 +46,12 @@ impl DiffMask {
         self.mask = vec![0; size];
     }
 
+    /// Sets every bit in the DiffMask
+    pub fn fill(&mut self) {
+        let size = self.mask.len();
+        self.mask = vec![u8::MAX; size];
+    }
+
     /// Returns whether any bit has been set in the DiffMask
     pub fn is_clear(&self) -> bool {
         for byte in self.mask.iter() {
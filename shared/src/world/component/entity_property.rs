@@ -4,15 +4,18 @@ use log::{info, warn};
 use naia_serde::{BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr};
 
 use crate::{
-    world::entity::{
-        entity_converters::{
-            EntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverter,
-            LocalEntityAndGlobalEntityConverterMut,
+    world::{
+        component::{property::write_without_authority, property_error::PropertyMutateError},
+        entity::{
+            entity_converters::{
+                EntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverter,
+                LocalEntityAndGlobalEntityConverterMut,
+            },
+            global_entity::GlobalEntity,
+            local_entity::OwnedLocalEntity,
         },
-        global_entity::GlobalEntity,
-        local_entity::OwnedLocalEntity,
     },
-    EntityAuthAccessor, PropertyMutator, RemoteEntity,
+    DiffMask, EntityAuthAccessor, PropertyMutator, RemoteEntity,
 };
 
 #[derive(Clone)]
@@ -126,6 +129,16 @@ impl EntityRelation {
         warn!("Could not get EntityRelation value, because EntityRelation has no GlobalEntity!");
         return None;
     }
+    fn check_mutate(&self) -> Result<(), PropertyMutateError> {
+        match self {
+            EntityRelation::HostOwned(_) | EntityRelation::Local(_) => Ok(()),
+            EntityRelation::Delegated(inner) => inner.check_mutate(),
+            EntityRelation::RemoteOwned(_)
+            | EntityRelation::RemoteWaiting(_)
+            | EntityRelation::RemotePublic(_)
+            | EntityRelation::Invalid => Err(PropertyMutateError::RemoteOwned),
+        }
+    }
     fn set<E: Copy + Eq + Hash>(
         &mut self,
         converter: &dyn EntityAndGlobalEntityConverter<E>,
@@ -339,8 +352,8 @@ impl EntityProperty {
 
                 Ok(new_self)
             } else {
-                if let OwnedLocalEntity::Remote(remote_entity_id) = local_entity {
-                    let new_impl = RemoteWaitingRelation::new(RemoteEntity::new(remote_entity_id));
+                if let Some(remote_entity) = local_entity.as_remote() {
+                    let new_impl = RemoteWaitingRelation::new(remote_entity);
 
                     let new_self = Self {
                         inner: EntityRelation::RemoteWaiting(new_impl),
@@ -665,6 +678,42 @@ impl EntityProperty {
         self.inner.set_mutator(mutator);
     }
 
+    /// Restricts which Properties of the owning Component are written while it
+    /// is Delegated. See `PropertyMutator::set_field_authority()`
+    pub fn set_field_authority(&mut self, field_authority: Option<&DiffMask>) {
+        match &self.inner {
+            EntityRelation::HostOwned(inner) => {
+                if let Some(mutator) = &inner.mutator {
+                    mutator.set_field_authority(field_authority.cloned());
+                }
+            }
+            EntityRelation::RemotePublic(inner) => {
+                inner.mutator.set_field_authority(field_authority.cloned());
+            }
+            EntityRelation::Delegated(inner) => {
+                inner.mutator.set_field_authority(field_authority.cloned());
+            }
+            EntityRelation::RemoteOwned(_)
+            | EntityRelation::RemoteWaiting(_)
+            | EntityRelation::Local(_)
+            | EntityRelation::Invalid => {}
+        }
+    }
+
+    /// Returns whether the current authority permits this EntityProperty to be
+    /// written. Only Delegated EntityProperties can be restricted.
+    pub fn has_field_authority(&self) -> bool {
+        match &self.inner {
+            EntityRelation::Delegated(inner) => inner.mutator.has_field_authority(inner.index),
+            EntityRelation::HostOwned(_)
+            | EntityRelation::RemoteOwned(_)
+            | EntityRelation::RemoteWaiting(_)
+            | EntityRelation::RemotePublic(_)
+            | EntityRelation::Local(_)
+            | EntityRelation::Invalid => true,
+        }
+    }
+
     // Serialization / deserialization
 
     pub fn bit_length(&self, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) -> u32 {
@@ -698,10 +747,48 @@ impl EntityProperty {
         self.inner.set_to_none();
     }
 
+    /// Returns whether this EntityProperty can be written to, which a
+    /// Delegated one only can while Authority over its Entity is held or
+    /// requested
+    pub fn can_mutate(&self) -> bool {
+        self.inner.check_mutate().is_ok()
+    }
+
+    /// Sets the related Entity, or returns why it can't be written to instead
+    /// of panicking
+    pub fn try_set<E: Copy + Eq + Hash>(
+        &mut self,
+        converter: &dyn EntityAndGlobalEntityConverter<E>,
+        entity: &E,
+    ) -> Result<(), PropertyMutateError> {
+        self.inner.check_mutate()?;
+        self.set(converter, entity);
+        Ok(())
+    }
+
+    /// Clears the related Entity, or returns why it can't be written to
+    /// instead of panicking
+    pub fn try_set_to_none(&mut self) -> Result<(), PropertyMutateError> {
+        self.inner.check_mutate()?;
+        self.set_to_none();
+        Ok(())
+    }
+
     pub fn mirror(&mut self, other: &EntityProperty) {
         self.inner.mirror(other);
     }
 
+    /// Set to the Entity of another EntityProperty only if they differ, so
+    /// that an update is only queued for an actual change. Returns whether the
+    /// Entity changed
+    pub fn set_if_changed(&mut self, other: &EntityProperty) -> bool {
+        if self.inner.get_global_entity() == other.inner.get_global_entity() {
+            return false;
+        }
+        self.mirror(other);
+        true
+    }
+
     pub fn waiting_local_entity(&self) -> Option<RemoteEntity> {
         self.inner.waiting_local_entity()
     }
@@ -1086,14 +1173,20 @@ impl DelegatedRelation {
     }
 
     fn mutate(&mut self) {
-        if !self.can_mutate() {
-            panic!("Must request authority to mutate a Delegated EntityProperty.");
+        if let Err(error) = self.check_mutate() {
+            write_without_authority(error);
+            return;
         }
         let _success = self.mutator.mutate(self.index);
     }
 
-    fn can_mutate(&self) -> bool {
-        self.auth_accessor.auth_status().can_mutate()
+    fn check_mutate(&self) -> Result<(), PropertyMutateError> {
+        let auth_status = self.auth_accessor.auth_status();
+        if auth_status.can_mutate() {
+            Ok(())
+        } else {
+            Err(PropertyMutateError::MissingAuthority(auth_status.status()))
+        }
     }
 
     fn can_read(&self) -> bool {
@@ -0,0 +1,252 @@
+use std::ops::Deref;
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeEnum, SerdeErr};
+
+use crate::world::{
+    component::{diff_mask::DiffMask, property::Property, property_mutate::PropertyMutator},
+    delegation::auth_channel::EntityAuthAccessor,
+};
+
+/// A Property of a Component that contains an enum, which is diffed one field
+/// at a time within its active variant.
+///
+/// An EnumProperty occupies `EnumProperty::<T>::DIFF_BITS` bits of its
+/// Component's DiffMask: the first is set when the whole value must be sent
+/// (such as when the variant changes), and each of the rest is set when the
+/// corresponding field of the active variant changes.
+///
+/// ```
+/// use naia_shared::{EnumProperty, Property, Replicate, Serde};
+///
+/// #[derive(Serde, Clone, PartialEq)]
+/// pub enum WeaponState {
+///     Idle,
+///     Reloading { progress: u8, rounds: u8 },
+/// }
+///
+/// #[derive(Replicate)]
+/// pub struct Weapon {
+///     pub ammo: Property<u8>,
+///     pub state: EnumProperty<WeaponState>,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Shapes which cannot be derived are reported at compile time. Replicate
+/// can only be derived for structs:
+///
+/// ```compile_fail
+/// use naia_shared::Replicate;
+///
+/// #[derive(Replicate)]
+/// pub enum Weapon {
+///     Sword,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Property & EnumProperty fields must name the type they contain:
+///
+/// ```compile_fail
+/// use naia_shared::{EnumProperty, Replicate};
+///
+/// #[derive(Replicate)]
+/// pub struct Weapon {
+///     pub state: EnumProperty,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// And Serde cannot be derived for generic types:
+///
+/// ```compile_fail
+/// use naia_shared::Serde;
+///
+/// #[derive(Serde, Clone, PartialEq)]
+/// pub enum Slot<T> {
+///     Empty,
+///     Full(T),
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct EnumProperty<T: SerdeEnum> {
+    inner: Property<T>,
+}
+
+impl<T: SerdeEnum> EnumProperty<T> {
+    /// The number of DiffMask bits used by this Property
+    pub const DIFF_BITS: u8 = 1 + T::MAX_VARIANT_FIELDS;
+
+    /// Create a new Local EnumProperty
+    pub fn new_local(value: T) -> Self {
+        Self {
+            inner: Property::new_local(value),
+        }
+    }
+
+    /// Create a new host-owned EnumProperty
+    pub fn host_owned(value: T, mutator_index: u8) -> Self {
+        Self {
+            inner: Property::host_owned(value, mutator_index),
+        }
+    }
+
+    /// Given a cursor into incoming packet data, initializes the EnumProperty
+    /// with the synced value
+    pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Self {
+            inner: Property::new_read(reader)?,
+        })
+    }
+
+    /// Set an PropertyMutator to track changes to the EnumProperty
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.inner.set_mutator(mutator);
+    }
+
+    /// See `Property::set_field_authority()`
+    pub fn set_field_authority(&mut self, field_authority: Option<&DiffMask>) {
+        self.inner.set_field_authority(field_authority);
+    }
+
+    /// See `Property::has_field_authority()`
+    pub fn has_field_authority(&self) -> bool {
+        self.inner.has_field_authority()
+    }
+
+    /// Sets the contained value, queueing an update of only the fields which
+    /// changed if the variant is the same, or of the whole value otherwise
+    pub fn set(&mut self, value: T) {
+        let offsets: Vec<u8> = match self.inner.inner().diff_fields(&value) {
+            Some(fields) => fields.iter().map(|field| field + 1).collect(),
+            None => vec![0],
+        };
+        if offsets.is_empty() {
+            return;
+        }
+        self.inner.set_with_offsets(value, &offsets);
+    }
+
+    /// Modifies a copy of the contained value, then sets it with `set()`
+    pub fn modify(&mut self, f: impl FnOnce(&mut T)) {
+        let mut value = self.inner.inner().clone();
+        f(&mut value);
+        self.set(value);
+    }
+
+    /// Returns whether any of this EnumProperty's bits are set in the DiffMask,
+    /// given the index of its first bit
+    pub fn has_update(index: u8, diff_mask: &DiffMask) -> bool {
+        (index..index + Self::DIFF_BITS).any(|bit| diff_mask.bit(bit) == Some(true))
+    }
+
+    /// Writes contained value into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.inner.write(writer);
+    }
+
+    /// Writes the parts of the contained value which are set in the DiffMask
+    /// into outgoing byte stream, given the index of this EnumProperty's first
+    /// bit
+    pub fn write_update(&self, index: u8, diff_mask: &DiffMask, writer: &mut dyn BitWrite) {
+        let value = self.inner.writable_inner();
+        let full = diff_mask.bit(index) == Some(true);
+        full.ser(writer);
+        if full {
+            value.ser(writer);
+        } else {
+            value.ser_fields(
+                &|field| diff_mask.bit(index + 1 + field) == Some(true),
+                writer,
+            );
+        }
+    }
+
+    /// Given a cursor into incoming packet data, copies an update written by
+    /// `write_update()` into the given writer
+    pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
+        let full = bool::de(reader)?;
+        full.ser(writer);
+        if full {
+            T::de(reader)?.ser(writer);
+        } else {
+            T::read_write_fields(reader, writer)?;
+        }
+        Ok(())
+    }
+
+    /// Given a cursor into incoming packet data, applies an update written by
+    /// `write_update()`
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let value = if bool::de(reader)? {
+            T::de(reader)?
+        } else {
+            let mut value = self.inner.inner().clone();
+            value.de_fields(reader)?;
+            value
+        };
+        self.inner.read_value(value);
+        Ok(())
+    }
+
+    /// Compare to another EnumProperty
+    pub fn equals(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+
+    /// See `Property::set_if_changed()`
+    pub fn set_if_changed(&mut self, other: &Self) -> bool {
+        if self.equals(other) {
+            return false;
+        }
+        self.mirror(other);
+        true
+    }
+
+    /// Set value to the value of another EnumProperty, queues for update if
+    /// value changes
+    pub fn mirror(&mut self, other: &Self) {
+        self.set(other.inner.inner().clone());
+    }
+
+    /// Migrate Remote EnumProperty to Public version
+    pub fn remote_publish(&mut self, mutator_index: u8, mutator: &PropertyMutator) {
+        self.inner.remote_publish(mutator_index, mutator);
+    }
+
+    /// Migrate Remote EnumProperty to Private version
+    pub fn remote_unpublish(&mut self) {
+        self.inner.remote_unpublish();
+    }
+
+    /// Migrate EnumProperty to Delegated version
+    pub fn enable_delegation(
+        &mut self,
+        accessor: &EntityAuthAccessor,
+        mutator_opt: Option<(u8, &PropertyMutator)>,
+    ) {
+        self.inner.enable_delegation(accessor, mutator_opt);
+    }
+
+    /// Migrate Delegated EnumProperty to Host-Owned (Public) version
+    pub fn disable_delegation(&mut self) {
+        self.inner.disable_delegation();
+    }
+
+    /// Migrate Host EnumProperty to Local version
+    pub fn localize(&mut self) {
+        self.inner.localize();
+    }
+}
+
+// Unlike Property, there is no DerefMut, as the value must be compared
+// against its previous state to know which fields to update. Use `set()` or
+// `modify()` instead
+impl<T: SerdeEnum> Deref for EnumProperty<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.inner()
+    }
+}
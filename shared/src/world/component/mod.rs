@@ -1,8 +1,12 @@
+pub mod collection_property;
 pub mod component_kinds;
 pub mod component_update;
 pub mod diff_mask;
 pub mod entity_property;
+pub mod enum_property;
 pub mod property;
+pub mod property_error;
 pub mod property_mutate;
+pub mod quantization;
 pub mod replica_ref;
 pub mod replicate;
@@ -1,10 +1,14 @@
 use log::warn;
 use std::ops::{Deref, DerefMut};
 
-use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, SerdeInteger};
 
 use crate::world::{
-    component::property_mutate::PropertyMutator, delegation::auth_channel::EntityAuthAccessor,
+    component::{
+        diff_mask::DiffMask, property_error::PropertyMutateError, property_mutate::PropertyMutator,
+        quantization::Quantization,
+    },
+    delegation::auth_channel::EntityAuthAccessor,
 };
 
 #[derive(Clone)]
@@ -33,6 +37,10 @@ impl<T: Serde> PropertyImpl<T> {
 #[derive(Clone)]
 pub struct Property<T: Serde> {
     inner: PropertyImpl<T>,
+    defer_mutations: bool,
+    // while mutations are deferred, the value from before the first write
+    // since they were last flushed
+    value_before_write: Option<T>,
 }
 
 // should be shared
@@ -41,6 +49,8 @@ impl<T: Serde> Property<T> {
     pub fn new_local(value: T) -> Self {
         Self {
             inner: PropertyImpl::Local(LocalProperty::new(value)),
+            defer_mutations: false,
+            value_before_write: None,
         }
     }
 
@@ -48,6 +58,8 @@ impl<T: Serde> Property<T> {
     pub fn host_owned(value: T, mutator_index: u8) -> Self {
         Self {
             inner: PropertyImpl::HostOwned(HostOwnedProperty::new(value, mutator_index)),
+            defer_mutations: false,
+            value_before_write: None,
         }
     }
 
@@ -58,6 +70,8 @@ impl<T: Serde> Property<T> {
 
         Ok(Self {
             inner: PropertyImpl::RemoteOwned(RemoteOwnedProperty::new(inner_value)),
+            defer_mutations: false,
+            value_before_write: None,
         })
     }
 
@@ -79,6 +93,67 @@ impl<T: Serde> Property<T> {
         }
     }
 
+    /// Restricts which Properties of the owning Component are written while it
+    /// is Delegated. See `PropertyMutator::set_field_authority()`
+    pub fn set_field_authority(&mut self, field_authority: Option<&DiffMask>) {
+        match &self.inner {
+            PropertyImpl::HostOwned(inner) => {
+                if let Some(mutator) = &inner.mutator {
+                    mutator.set_field_authority(field_authority.cloned());
+                }
+            }
+            PropertyImpl::RemotePublic(inner) => {
+                inner.mutator.set_field_authority(field_authority.cloned());
+            }
+            PropertyImpl::Delegated(inner) => {
+                inner.mutator.set_field_authority(field_authority.cloned());
+            }
+            PropertyImpl::RemoteOwned(_) | PropertyImpl::Local(_) => {}
+        }
+    }
+
+    /// Returns whether the current authority permits this Property to be
+    /// written. Only Delegated Properties can be restricted.
+    pub fn has_field_authority(&self) -> bool {
+        match &self.inner {
+            PropertyImpl::Delegated(inner) => inner.mutator.has_field_authority(inner.index),
+            PropertyImpl::HostOwned(_)
+            | PropertyImpl::RemoteOwned(_)
+            | PropertyImpl::RemotePublic(_)
+            | PropertyImpl::Local(_) => true,
+        }
+    }
+
+    /// Returns whether this Property can be written to, which a Delegated
+    /// Property only can while Authority over its Entity is held or requested
+    pub fn can_mutate(&self) -> bool {
+        self.check_mutate().is_ok()
+    }
+
+    fn check_mutate(&self) -> Result<(), PropertyMutateError> {
+        match &self.inner {
+            PropertyImpl::HostOwned(_) | PropertyImpl::Local(_) => Ok(()),
+            PropertyImpl::RemoteOwned(_) | PropertyImpl::RemotePublic(_) => {
+                Err(PropertyMutateError::RemoteOwned)
+            }
+            PropertyImpl::Delegated(inner) => inner.check_mutate(),
+        }
+    }
+
+    /// Sets the contained value, or returns why it can't be written to
+    /// instead of panicking
+    pub fn try_set(&mut self, value: T) -> Result<(), PropertyMutateError> {
+        *self.try_mut()? = value;
+        Ok(())
+    }
+
+    /// Returns the contained value to be modified in place, or why it can't
+    /// be written to instead of panicking
+    pub fn try_mut(&mut self) -> Result<&mut T, PropertyMutateError> {
+        self.check_mutate()?;
+        Ok(&mut **self)
+    }
+
     // Serialization / deserialization
 
     /// Writes contained value into outgoing byte stream
@@ -112,33 +187,111 @@ impl<T: Serde> Property<T> {
     /// Given a cursor into incoming packet data, updates the Property with the
     /// synced value
     pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let value = Self::read_inner(reader)?;
+        self.read_value(value);
+        Ok(())
+    }
+
+    /// Updates the Property with a synced value which has already been read
+    pub(crate) fn read_value(&mut self, value: T) {
         match &mut self.inner {
             PropertyImpl::HostOwned(_) => {
                 panic!("Host Property should never read.");
             }
             PropertyImpl::RemoteOwned(inner) => {
-                inner.read(reader)?;
+                inner.read_value(value);
             }
             PropertyImpl::RemotePublic(inner) => {
-                inner.read(reader)?;
+                inner.read_value(value);
             }
             PropertyImpl::Local(_) => {
                 panic!("Local Property should never read.");
             }
             PropertyImpl::Delegated(inner) => {
-                inner.read(reader)?;
+                inner.read_value(value);
+            }
+        }
+    }
+
+    /// Updates the Property in place with a synced change which has already
+    /// been read, rather than replacing the whole value
+    pub(crate) fn read_in_place(&mut self, apply: impl FnOnce(&mut T)) {
+        match &mut self.inner {
+            PropertyImpl::HostOwned(_) => {
+                panic!("Host Property should never read.");
+            }
+            PropertyImpl::RemoteOwned(inner) => {
+                apply(&mut inner.inner);
+            }
+            PropertyImpl::RemotePublic(inner) => {
+                apply(&mut inner.inner);
+                inner.mutate();
+            }
+            PropertyImpl::Local(_) => {
+                panic!("Local Property should never read.");
+            }
+            PropertyImpl::Delegated(inner) => {
+                if inner.can_read() {
+                    apply(&mut inner.inner);
+                    if inner.can_mutate() {
+                        inner.mutate();
+                    }
+                }
             }
         }
-        Ok(())
     }
 
     fn read_inner(reader: &mut BitReader) -> Result<T, SerdeErr> {
         T::de(reader)
     }
 
+    /// Returns the contained value, checking that it is permitted to be written
+    pub(crate) fn writable_inner(&self) -> &T {
+        match &self.inner {
+            PropertyImpl::HostOwned(inner) => &inner.inner,
+            PropertyImpl::RemoteOwned(_) => {
+                panic!("Remote Private Property should never be written.");
+            }
+            PropertyImpl::RemotePublic(inner) => &inner.inner,
+            PropertyImpl::Local(_) => {
+                panic!("Local Property should never be written.");
+            }
+            PropertyImpl::Delegated(inner) => inner.writable_inner(),
+        }
+    }
+
+    /// Sets the contained value, queueing an update of each given offset from
+    /// this Property's mutator index
+    pub(crate) fn set_with_offsets(&mut self, value: T, offsets: &[u8]) {
+        *self.inner_mut_with_offsets(offsets) = value;
+    }
+
+    /// Returns the contained value to be modified in place, queueing an update
+    /// of each given offset from this Property's mutator index
+    pub(crate) fn inner_mut_with_offsets(&mut self, offsets: &[u8]) -> &mut T {
+        match &mut self.inner {
+            PropertyImpl::HostOwned(inner) => {
+                for offset in offsets {
+                    inner.mutate_at(*offset);
+                }
+                &mut inner.inner
+            }
+            PropertyImpl::RemoteOwned(_) | PropertyImpl::RemotePublic(_) => {
+                panic!("Remote Property should never be set manually.");
+            }
+            PropertyImpl::Local(inner) => &mut inner.inner,
+            PropertyImpl::Delegated(inner) => {
+                for offset in offsets {
+                    inner.mutate_at(*offset);
+                }
+                &mut inner.inner
+            }
+        }
+    }
+
     // Comparison
 
-    fn inner(&self) -> &T {
+    pub(crate) fn inner(&self) -> &T {
         match &self.inner {
             PropertyImpl::HostOwned(inner) => &inner.inner,
             PropertyImpl::RemoteOwned(inner) => &inner.inner,
@@ -153,6 +306,58 @@ impl<T: Serde> Property<T> {
         self.inner() == other.inner()
     }
 
+    /// Set value to the value of another Property only if they differ, so
+    /// that an update is only queued for an actual change. Returns whether the
+    /// value changed
+    pub fn set_if_changed(&mut self, other: &Self) -> bool {
+        if self.equals(other) {
+            return false;
+        }
+        self.mirror(other);
+        true
+    }
+
+    /// While deferred, writing to the Property doesn't queue an update right
+    /// away. Instead, `flush_deferred_mutation()` queues one only if the value
+    /// then differs from before it was first written, so writing the same
+    /// value again costs nothing. Undeferring flushes any pending write.
+    pub fn set_defer_mutations(&mut self, defer_mutations: bool) {
+        self.defer_mutations = defer_mutations;
+        if !defer_mutations {
+            self.flush_deferred_mutation();
+        }
+    }
+
+    /// Queues an update if the Property was written since mutations were last
+    /// flushed and its value has changed since. Returns whether an update was
+    /// queued
+    pub fn flush_deferred_mutation(&mut self) -> bool {
+        let Some(value_before_write) = self.value_before_write.take() else {
+            return false;
+        };
+        if value_before_write == *self.inner() {
+            return false;
+        }
+        match &mut self.inner {
+            PropertyImpl::HostOwned(inner) => {
+                inner.mutate();
+            }
+            PropertyImpl::Delegated(inner) => {
+                // authority may have been lost since the write, in which case
+                // the value will be overwritten by the remote one anyway
+                if inner.can_mutate() {
+                    inner.mutate();
+                }
+            }
+            PropertyImpl::RemoteOwned(_)
+            | PropertyImpl::RemotePublic(_)
+            | PropertyImpl::Local(_) => {
+                return false;
+            }
+        }
+        true
+    }
+
     /// Set value to the value of another Property, queues for update if value
     /// changes
     pub fn mirror(&mut self, other: &Self) {
@@ -329,10 +534,18 @@ impl<T: Serde> Deref for Property<T> {
 
 impl<T: Serde> DerefMut for Property<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        // Just assume inner value will be changed, queue for update
+        // Just assume inner value will be changed, queue for update, unless
+        // mutations are deferred, in which case remember the value to compare
+        // against when they are flushed
         match &mut self.inner {
             PropertyImpl::HostOwned(inner) => {
-                inner.mutate();
+                if self.defer_mutations {
+                    if self.value_before_write.is_none() {
+                        self.value_before_write = Some(inner.inner.clone());
+                    }
+                } else {
+                    inner.mutate();
+                }
                 &mut inner.inner
             }
             PropertyImpl::RemoteOwned(_) | PropertyImpl::RemotePublic(_) => {
@@ -340,13 +553,182 @@ impl<T: Serde> DerefMut for Property<T> {
             }
             PropertyImpl::Local(inner) => &mut inner.inner,
             PropertyImpl::Delegated(inner) => {
-                inner.mutate();
+                if self.defer_mutations {
+                    if let Err(error) = inner.check_mutate() {
+                        write_without_authority(error);
+                    } else if self.value_before_write.is_none() {
+                        self.value_before_write = Some(inner.inner.clone());
+                    }
+                } else {
+                    inner.mutate();
+                }
                 &mut inner.inner
             }
         }
     }
 }
 
+/// A `Property<Option<T>>` is an optional value, written as a presence bit
+/// followed by the value if there is one. Toggling it on or off queues an
+/// update like any other write, and clearing it only sends the presence bit.
+///
+/// A field holding an `Option<Property<T>>` would not be replicated at all,
+/// so it is rejected at compile time:
+///
+/// ```compile_fail
+/// use naia_shared::{Property, Replicate};
+///
+/// #[derive(Replicate)]
+/// pub struct Weapon {
+///     pub ammo: Option<Property<u8>>,
+/// }
+/// # fn main() {}
+/// ```
+impl<T: Serde> Property<Option<T>> {
+    /// Returns whether a value is present
+    pub fn is_some(&self) -> bool {
+        self.inner().is_some()
+    }
+
+    /// Returns whether no value is present
+    pub fn is_none(&self) -> bool {
+        self.inner().is_none()
+    }
+
+    /// Removes the value, queueing an update only if there was one
+    pub fn take(&mut self) -> Option<T> {
+        if self.is_none() {
+            return None;
+        }
+        self.deref_mut().take()
+    }
+
+    /// Sets the value, queueing an update only if it changes. Returns the
+    /// previous value
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        if self.inner().as_ref() == Some(&value) {
+            return Some(value);
+        }
+        self.deref_mut().replace(value)
+    }
+
+    /// Sets the value, or removes it if given None, queueing an update only if
+    /// it changes. Returns whether it changed
+    pub fn set_option(&mut self, value: Option<T>) -> bool {
+        if *self.inner() == value {
+            return false;
+        }
+        *self.deref_mut() = value;
+        true
+    }
+}
+
+/// Serialization of a Property marked with `#[quantize(..)]` or `#[angle]`,
+/// see `Quantization`
+impl Property<f32> {
+    /// Given a cursor into incoming packet data, initializes the Property with
+    /// the synced, quantized value
+    pub fn new_read_quantized(
+        reader: &mut BitReader,
+        quantization: &Quantization,
+    ) -> Result<Self, SerdeErr> {
+        let inner_value = quantization.de(reader)?;
+
+        Ok(Self {
+            inner: PropertyImpl::RemoteOwned(RemoteOwnedProperty::new(inner_value)),
+            defer_mutations: false,
+            value_before_write: None,
+        })
+    }
+
+    /// Writes the quantized value into outgoing byte stream
+    pub fn write_quantized(&self, writer: &mut dyn BitWrite, quantization: &Quantization) {
+        match &self.inner {
+            PropertyImpl::RemoteOwned(_) => {
+                panic!("Remote Private Property should never be written.");
+            }
+            PropertyImpl::Local(_) => {
+                panic!("Local Property should never be written.");
+            }
+            PropertyImpl::HostOwned(_)
+            | PropertyImpl::RemotePublic(_)
+            | PropertyImpl::Delegated(_) => {
+                quantization.ser(*self.inner(), writer);
+            }
+        }
+    }
+
+    /// Given a cursor into incoming packet data, updates the Property with the
+    /// synced, quantized value
+    pub fn read_quantized(
+        &mut self,
+        reader: &mut BitReader,
+        quantization: &Quantization,
+    ) -> Result<(), SerdeErr> {
+        let value = quantization.de(reader)?;
+        self.read_value(value);
+        Ok(())
+    }
+}
+
+/// Integer Properties marked with `#[bits(N)]` are written as a
+/// `SerdeInteger` of exactly that many bits, rather than the full width of
+/// their type
+impl<T: Serde + Copy + Into<i128> + TryFrom<i128>> Property<T> {
+    /// Given a cursor into incoming packet data, initializes the Property with
+    /// the synced, bit-packed value
+    pub fn new_read_bits<const SIGNED: bool, const BITS: u8>(
+        reader: &mut BitReader,
+    ) -> Result<Self, SerdeErr> {
+        let inner_value = SerdeInteger::<SIGNED, false, BITS>::de(reader)?.to::<T>();
+
+        Ok(Self {
+            inner: PropertyImpl::RemoteOwned(RemoteOwnedProperty::new(inner_value)),
+            defer_mutations: false,
+            value_before_write: None,
+        })
+    }
+
+    /// Reads a bit-packed value from a stream & immediately writes it to
+    /// another
+    pub fn read_write_bits<const SIGNED: bool, const BITS: u8>(
+        reader: &mut BitReader,
+        writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        SerdeInteger::<SIGNED, false, BITS>::de(reader)?.ser(writer);
+        Ok(())
+    }
+
+    /// Writes the bit-packed value into outgoing byte stream. Panics if the
+    /// value doesn't fit in the bits
+    pub fn write_bits<const SIGNED: bool, const BITS: u8>(&self, writer: &mut dyn BitWrite) {
+        match &self.inner {
+            PropertyImpl::RemoteOwned(_) => {
+                panic!("Remote Private Property should never be written.");
+            }
+            PropertyImpl::Local(_) => {
+                panic!("Local Property should never be written.");
+            }
+            PropertyImpl::HostOwned(_)
+            | PropertyImpl::RemotePublic(_)
+            | PropertyImpl::Delegated(_) => {
+                SerdeInteger::<SIGNED, false, BITS>::new(*self.inner()).ser(writer);
+            }
+        }
+    }
+
+    /// Given a cursor into incoming packet data, updates the Property with the
+    /// synced, bit-packed value
+    pub fn read_bits<const SIGNED: bool, const BITS: u8>(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        let value = SerdeInteger::<SIGNED, false, BITS>::de(reader)?.to::<T>();
+        self.read_value(value);
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub struct HostOwnedProperty<T: Serde> {
     inner: T,
@@ -378,11 +760,15 @@ impl<T: Serde> HostOwnedProperty<T> {
     }
 
     pub fn mutate(&mut self) {
+        self.mutate_at(0);
+    }
+
+    fn mutate_at(&mut self, offset: u8) {
         let Some(mutator) = &mut self.mutator else {
             warn!("Host Property should have a mutator immediately after creation.");
             return;
         };
-        let _success = mutator.mutate(self.index);
+        let _success = mutator.mutate(self.index + offset);
     }
 }
 
@@ -413,9 +799,8 @@ impl<T: Serde> RemoteOwnedProperty<T> {
         Self { inner: value }
     }
 
-    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
-        self.inner = Property::read_inner(reader)?;
-        Ok(())
+    pub fn read_value(&mut self, value: T) {
+        self.inner = value;
     }
 }
 
@@ -436,10 +821,9 @@ impl<T: Serde> RemotePublicProperty<T> {
         }
     }
 
-    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
-        self.inner = Property::read_inner(reader)?;
+    pub fn read_value(&mut self, value: T) {
+        self.inner = value;
         self.mutate();
-        Ok(())
     }
 
     pub fn write(&self, writer: &mut dyn BitWrite) {
@@ -475,24 +859,24 @@ impl<T: Serde> DelegatedProperty<T> {
         }
     }
 
-    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
-        let value = Property::read_inner(reader)?;
-
+    pub fn read_value(&mut self, value: T) {
         if self.can_read() {
             self.inner = value;
             if self.can_mutate() {
                 self.mutate();
             }
         }
-
-        Ok(())
     }
 
     pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.writable_inner().ser(writer);
+    }
+
+    fn writable_inner(&self) -> &T {
         if !self.can_write() {
             panic!("Must have Authority over Entity before performing this operation. Current Authority: {:?}", self.auth_accessor.auth_status());
         }
-        self.inner.ser(writer);
+        &self.inner
     }
 
     pub fn mirror(&mut self, other: &T) {
@@ -501,16 +885,30 @@ impl<T: Serde> DelegatedProperty<T> {
     }
 
     fn mutate(&mut self) {
-        if !self.can_mutate() {
-            panic!("Must request authority to mutate a Delegated Property.");
+        self.mutate_at(0);
+    }
+
+    fn mutate_at(&mut self, offset: u8) {
+        if let Err(error) = self.check_mutate() {
+            write_without_authority(error);
+            return;
         }
-        let _success = self.mutator.mutate(self.index);
+        let _success = self.mutator.mutate(self.index + offset);
     }
 
     fn can_mutate(&self) -> bool {
         self.auth_accessor.auth_status().can_mutate()
     }
 
+    fn check_mutate(&self) -> Result<(), PropertyMutateError> {
+        let auth_status = self.auth_accessor.auth_status();
+        if auth_status.can_mutate() {
+            Ok(())
+        } else {
+            Err(PropertyMutateError::MissingAuthority(auth_status.status()))
+        }
+    }
+
     fn can_read(&self) -> bool {
         self.auth_accessor.auth_status().can_read()
     }
@@ -519,3 +917,13 @@ impl<T: Serde> DelegatedProperty<T> {
         self.auth_accessor.auth_status().can_write()
     }
 }
+
+// Writing to a Delegated Property without Authority is a bug, which debug
+// builds panic on. Release builds only warn, & keep the written value locally
+// until the next update from the remote host overwrites it
+pub(crate) fn write_without_authority(error: PropertyMutateError) {
+    if cfg!(debug_assertions) {
+        panic!("{}", error);
+    }
+    warn!("{}", error);
+}
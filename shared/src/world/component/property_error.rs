@@ -0,0 +1,29 @@
+use std::error::Error;
+
+use crate::EntityAuthStatus;
+
+/// Returned when a Property can't be written to from this end of the
+/// connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyMutateError {
+    /// The Property is replicated from the remote host, & is only ever
+    /// written by it
+    RemoteOwned,
+    /// The Property belongs to a Delegated Entity which this host does not
+    /// hold Authority over
+    MissingAuthority(EntityAuthStatus),
+}
+
+impl Error for PropertyMutateError {}
+impl std::fmt::Display for PropertyMutateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::RemoteOwned => write!(f, "Cannot mutate a Property owned by the remote host"),
+            Self::MissingAuthority(status) => write!(
+                f,
+                "Must have Authority over Entity to mutate a Delegated Property. Current Authority: {:?}",
+                status
+            ),
+        }
+    }
+}
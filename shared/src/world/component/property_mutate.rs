@@ -1,4 +1,9 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Arc, RwLock},
+};
+
+use crate::world::component::diff_mask::DiffMask;
 
 /// Tracks which Properties have changed and need to be queued for syncing with
 /// the Client
@@ -28,12 +33,18 @@ impl Clone for Box<dyn PropertyMutate> {
 #[derive(Clone)]
 pub struct PropertyMutator {
     inner: Box<dyn PropertyMutate>,
+    // shared between every Property of a Component, each bit indicates
+    // whether the Property at that index may be written while Delegated
+    field_authority: Arc<RwLock<Option<DiffMask>>>,
 }
 
 impl PropertyMutator {
     pub fn new<M: PropertyMutate>(mutator: M) -> Self {
         let inner = Box::new(mutator);
-        Self { inner }
+        Self {
+            inner,
+            field_authority: Arc::new(RwLock::new(None)),
+        }
     }
 
     pub fn clone_new(&self) -> Self {
@@ -41,7 +52,32 @@ impl PropertyMutator {
         // PropertyMutateClone;
         let new_inner = self.inner.as_ref().clone_box();
 
-        Self { inner: new_inner }
+        Self {
+            inner: new_inner,
+            field_authority: self.field_authority.clone(),
+        }
+    }
+
+    /// Restricts which Properties are written while the Component is
+    /// Delegated. Each bit of the mask corresponds to the index of a
+    /// Property, `None` permits every Property to be written.
+    pub fn set_field_authority(&self, field_authority: Option<DiffMask>) {
+        let Ok(mut current) = self.field_authority.as_ref().write() else {
+            panic!("Field authority held on current thread");
+        };
+        *current = field_authority;
+    }
+
+    /// Returns whether the Property at the given index is permitted to be
+    /// written
+    pub fn has_field_authority(&self, property_index: u8) -> bool {
+        let Ok(current) = self.field_authority.as_ref().read() else {
+            panic!("Field authority held on current thread");
+        };
+        match current.as_ref() {
+            Some(mask) => mask.bit(property_index).unwrap_or(false),
+            None => true,
+        }
     }
 }
 
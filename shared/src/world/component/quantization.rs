@@ -0,0 +1,140 @@
+use std::f32::consts::TAU;
+
+use naia_serde::{BitReader, BitWrite, BitWriter, SerdeErr};
+
+/// Maps an f32 within a range onto the fewest bits which keep it within a
+/// given precision, for a `Property<f32>` marked with `#[quantize(..)]` or
+/// `#[angle]`. Game code still reads & writes the plain f32 through the
+/// Property, and only the serialized value is quantized.
+///
+/// ```
+/// use naia_shared::{Property, Replicate};
+///
+/// #[derive(Replicate)]
+/// pub struct Transform {
+///     // 18 bits rather than 32
+///     #[quantize(min = -1024.0, max = 1024.0, precision = 0.01)]
+///     pub x: Property<f32>,
+///     // 0..2π, in 13 bits by default
+///     #[angle]
+///     pub rotation: Property<f32>,
+///     // or to a given precision, in radians
+///     #[angle(precision = 0.01)]
+///     pub pitch: Property<f32>,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Only `Property<f32>` fields can be quantized:
+///
+/// ```compile_fail
+/// use naia_shared::{Property, Replicate};
+///
+/// #[derive(Replicate)]
+/// pub struct Transform {
+///     #[quantize(min = 0.0, max = 1.0, precision = 0.1)]
+///     pub x: Property<u32>,
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quantization {
+    min: f32,
+    max: f32,
+    bits: u8,
+    // whether values outside of the range wrap around into it, rather than
+    // being clamped
+    wrap: bool,
+}
+
+impl Quantization {
+    /// Values are clamped to `min..=max` & written in `bits` bits
+    pub const fn new(min: f32, max: f32, bits: u8) -> Self {
+        Self {
+            min,
+            max,
+            bits,
+            wrap: false,
+        }
+    }
+
+    /// Angles in radians are wrapped into `0..2π` & written in `bits` bits
+    pub const fn angle(bits: u8) -> Self {
+        Self {
+            min: 0.0,
+            max: TAU,
+            bits,
+            wrap: true,
+        }
+    }
+
+    /// The number of bits each value is written in
+    pub const fn bit_length(&self) -> u32 {
+        self.bits as u32
+    }
+
+    // the highest level a value can be quantized to
+    fn max_level(&self) -> u64 {
+        (1u64 << self.bits) - 1
+    }
+
+    /// The level which the value is written as. Values out of the range are
+    /// clamped, which is a bug in debug builds, unless this is an angle
+    pub fn quantize(&self, value: f32) -> u64 {
+        let value = if self.wrap {
+            self.min + (value - self.min).rem_euclid(self.max - self.min)
+        } else {
+            debug_assert!(
+                (self.min..=self.max).contains(&value),
+                "quantized value {} is out of the range {}..={}",
+                value,
+                self.min,
+                self.max
+            );
+            value.clamp(self.min, self.max)
+        };
+        let fraction = ((value - self.min) as f64) / ((self.max - self.min) as f64);
+        ((fraction * self.max_level() as f64).round() as u64).min(self.max_level())
+    }
+
+    /// The value which a level read from the stream stands for
+    pub fn dequantize(&self, level: u64) -> f32 {
+        let fraction = (level as f64) / (self.max_level() as f64);
+        (self.min as f64 + fraction * (self.max - self.min) as f64) as f32
+    }
+
+    pub fn ser(&self, value: f32, writer: &mut dyn BitWrite) {
+        let level = self.quantize(value);
+        for bit in 0..self.bits {
+            writer.write_bit(level & (1 << bit) != 0);
+        }
+    }
+
+    pub fn de(&self, reader: &mut BitReader) -> Result<f32, SerdeErr> {
+        Ok(self.dequantize(self.read_level(reader)?))
+    }
+
+    /// Reads a value from a stream & immediately writes it to another, without
+    /// losing any more precision
+    pub fn read_write(
+        &self,
+        reader: &mut BitReader,
+        writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        let level = self.read_level(reader)?;
+        for bit in 0..self.bits {
+            writer.write_bit(level & (1 << bit) != 0);
+        }
+        Ok(())
+    }
+
+    fn read_level(&self, reader: &mut BitReader) -> Result<u64, SerdeErr> {
+        let mut level = 0;
+        for bit in 0..self.bits {
+            if reader.read_bit()? {
+                level |= 1 << bit;
+            }
+        }
+        Ok(level)
+    }
+}
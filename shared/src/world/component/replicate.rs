@@ -39,6 +39,18 @@ pub trait ReplicateBuilder: Send + Sync + Named {
         ),
         SerdeErr,
     >;
+    /// Merge two Component updates into one, keeping the later value of any
+    /// field both of them include
+    fn merge_updates(
+        &self,
+        earlier: &ComponentUpdate,
+        later: &ComponentUpdate,
+    ) -> Result<ComponentUpdate, SerdeErr>;
+    /// Whether every update of the Component includes all of its fields,
+    /// set with `#[replicate(full_update)]`
+    fn full_update(&self) -> bool;
+    /// The number of Properties the Component has
+    fn property_count(&self) -> usize;
 }
 
 /// A struct that implements Replicate is a Component, or otherwise,
@@ -75,6 +87,13 @@ pub trait Replicate: Sync + Send + 'static + Named + Any {
         writer: &mut dyn BitWrite,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
     );
+    /// Writes the same data as `write()`, minus the Component's kind, for
+    /// when the reader already knows which kind to expect
+    fn write_fields(
+        &self,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    );
     /// Write data into an outgoing byte stream, sufficient only to update the
     /// mutated Properties of the Component on the client
     fn write_update(
@@ -111,8 +130,17 @@ pub trait Replicate: Sync + Send + 'static + Named + Any {
     );
     /// Disable Delegation Replicate
     fn disable_delegation(&mut self);
+    /// Restricts which Properties are written while Delegated, each bit of
+    /// the mask corresponding to a Property. `None` permits every Property
+    fn set_field_authority(&mut self, field_authority: Option<&DiffMask>);
     /// Convert to Local Replicate
     fn localize(&mut self);
+    /// Defers the updates queued by writing to the Component's Properties
+    /// until `flush_deferred_mutations()`, see `Property::set_defer_mutations()`
+    fn set_defer_mutations(&mut self, defer_mutations: bool);
+    /// Queues updates for the Properties whose values changed since they were
+    /// first written while deferred. Returns whether any update was queued
+    fn flush_deferred_mutations(&mut self) -> bool;
 }
 
 cfg_if! {
@@ -1,5 +1,6 @@
 use crate::world::component::component_kinds::ComponentKind;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum EntityAction<E: Copy> {
     SpawnEntity(E, Vec<ComponentKind>),
     DespawnEntity(E),
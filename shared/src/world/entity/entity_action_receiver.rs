@@ -2,16 +2,19 @@ use std::{
     collections::{HashMap, VecDeque},
     hash::Hash,
     marker::PhantomData,
+    time::Duration,
 };
 
 use crate::{
-    messages::channels::receivers::reliable_receiver::ReliableReceiver, sequence_less_than,
-    world::component::component_kinds::ComponentKind, EntityAction, MessageIndex as ActionIndex,
+    messages::channels::receivers::reliable_receiver::ReliableReceiver, sequence_greater_than,
+    sequence_less_than, world::component::component_kinds::ComponentKind, EntityAction, Instant,
+    MessageIndex as ActionIndex,
 };
 
 pub struct EntityActionReceiver<E: Copy + Hash + Eq> {
     receiver: ReliableReceiver<EntityAction<E>>,
     entity_channels: HashMap<E, EntityChannel<E>>,
+    stall_timeout: Option<Duration>,
 }
 
 impl<E: Copy + Hash + Eq> EntityActionReceiver<E> {
@@ -19,9 +22,17 @@ impl<E: Copy + Hash + Eq> EntityActionReceiver<E> {
         Self {
             receiver: ReliableReceiver::new(),
             entity_channels: HashMap::default(),
+            stall_timeout: None,
         }
     }
 
+    /// Sets how long an Entity's channel may hold back an action, waiting on
+    /// an earlier action, before it is reported by `take_stalled_entities()`.
+    /// None, the default, never reports
+    pub fn set_stall_timeout(&mut self, stall_timeout: Option<Duration>) {
+        self.stall_timeout = stall_timeout;
+    }
+
     pub fn track_hosts_redundant_remote_entity(
         &mut self,
         entity: &E,
@@ -60,6 +71,7 @@ impl<E: Copy + Hash + Eq> EntityActionReceiver<E> {
                     .or_insert_with(|| EntityChannel::new(entity));
                 let entity_channel = self.entity_channels.get_mut(&entity).unwrap();
                 entity_channel.receive_action(action_index, action, &mut outgoing_actions);
+                entity_channel.update_waiting_since();
             }
         }
 
@@ -70,12 +82,70 @@ impl<E: Copy + Hash + Eq> EntityActionReceiver<E> {
 
         outgoing_actions
     }
+
+    /// Returns the Entities whose channel has been holding back an action for
+    /// longer than the stall timeout. Each stall is only reported once
+    pub fn take_stalled_entities(&mut self, now: &Instant) -> Vec<E> {
+        let Some(stall_timeout) = self.stall_timeout else {
+            return Vec::new();
+        };
+        let mut stalled_entities = Vec::new();
+        for (entity, entity_channel) in self.entity_channels.iter_mut() {
+            if entity_channel.stall_reported {
+                continue;
+            }
+            let Some(waiting_since) = &entity_channel.waiting_since else {
+                continue;
+            };
+            if waiting_since.elapsed(now) > stall_timeout {
+                entity_channel.stall_reported = true;
+                stalled_entities.push(*entity);
+            }
+        }
+        stalled_entities
+    }
+
+    /// Reports the state of every Entity Channel, including any actions which
+    /// have been received but are waiting on an earlier action to be applied.
+    /// Intended to be logged when diagnosing a stuck Entity
+    pub fn debug_dump(&self) -> Vec<EntityChannelDebug<E>> {
+        self.entity_channels
+            .values()
+            .map(|entity_channel| entity_channel.debug_dump())
+            .collect()
+    }
+}
+
+/// A snapshot of one Entity's channel within an [`EntityActionReceiver`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntityChannelDebug<E: Copy> {
+    pub entity: E,
+    pub spawned: bool,
+    /// The newest action received for this Entity, whether applied or not
+    pub highest_seen_index: Option<ActionIndex>,
+    /// The newest action applied to this Entity or any of its Components
+    pub highest_applied_index: Option<ActionIndex>,
+    pub components: Vec<ComponentChannelDebug>,
+    /// Received actions waiting on an earlier action, oldest first
+    pub pending_actions: Vec<(ActionIndex, EntityAction<E>)>,
+}
+
+/// A snapshot of one Component's channel within an [`EntityChannelDebug`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentChannelDebug {
+    pub component_kind: ComponentKind,
+    pub inserted: bool,
+    pub highest_applied_index: Option<ActionIndex>,
 }
 
 // Entity Channel
 struct EntityChannel<E: Copy + Hash + Eq> {
     entity: E,
     last_canonical_index: Option<ActionIndex>,
+    last_received_index: Option<ActionIndex>,
+    // when the oldest action still held back was received
+    waiting_since: Option<Instant>,
+    stall_reported: bool,
     spawned: bool,
     components: HashMap<ComponentKind, ComponentChannel<E>>,
     waiting_spawns: OrderedIds<Vec<ComponentKind>>,
@@ -91,6 +161,29 @@ impl<E: Copy + Hash + Eq> EntityChannel<E> {
             waiting_spawns: OrderedIds::new(),
             waiting_despawns: OrderedIds::new(),
             last_canonical_index: None,
+            last_received_index: None,
+            waiting_since: None,
+            stall_reported: false,
+        }
+    }
+
+    fn has_waiting_actions(&self) -> bool {
+        !self.waiting_spawns.inner.is_empty()
+            || !self.waiting_despawns.inner.is_empty()
+            || self.components.values().any(|component_channel| {
+                !component_channel.waiting_inserts.inner.is_empty()
+                    || !component_channel.waiting_removes.inner.is_empty()
+            })
+    }
+
+    // starts the stall clock when an action is first held back, and stops it
+    // once nothing is
+    fn update_waiting_since(&mut self) {
+        if !self.has_waiting_actions() {
+            self.waiting_since = None;
+            self.stall_reported = false;
+        } else if self.waiting_since.is_none() {
+            self.waiting_since = Some(Instant::now());
         }
     }
 
@@ -109,6 +202,13 @@ impl<E: Copy + Hash + Eq> EntityChannel<E> {
         incoming_action: EntityAction<E>,
         outgoing_actions: &mut Vec<EntityAction<E>>,
     ) {
+        if self
+            .last_received_index
+            .is_none_or(|last_index| sequence_greater_than(incoming_action_index, last_index))
+        {
+            self.last_received_index = Some(incoming_action_index);
+        }
+
         match incoming_action {
             EntityAction::SpawnEntity(_, components) => {
                 self.receive_spawn_entity_action(
@@ -324,6 +424,66 @@ impl<E: Copy + Hash + Eq> EntityChannel<E> {
 
         self.last_canonical_index = Some(index);
     }
+
+    pub fn debug_dump(&self) -> EntityChannelDebug<E> {
+        let mut highest_applied_index = self.last_canonical_index;
+        let mut components = Vec::new();
+        let mut pending_actions = Vec::new();
+
+        for (index, component_kinds) in &self.waiting_spawns.inner {
+            pending_actions.push((
+                *index,
+                EntityAction::SpawnEntity(self.entity, component_kinds.clone()),
+            ));
+        }
+        for (index, _) in &self.waiting_despawns.inner {
+            pending_actions.push((*index, EntityAction::DespawnEntity(self.entity)));
+        }
+        for (component_kind, component_channel) in &self.components {
+            if let Some(index) = component_channel.last_canonical_index {
+                if highest_applied_index
+                    .is_none_or(|highest_index| sequence_greater_than(index, highest_index))
+                {
+                    highest_applied_index = Some(index);
+                }
+            }
+            components.push(ComponentChannelDebug {
+                component_kind: *component_kind,
+                inserted: component_channel.inserted,
+                highest_applied_index: component_channel.last_canonical_index,
+            });
+            for (index, _) in &component_channel.waiting_inserts.inner {
+                pending_actions.push((
+                    *index,
+                    EntityAction::InsertComponent(self.entity, *component_kind),
+                ));
+            }
+            for (index, _) in &component_channel.waiting_removes.inner {
+                pending_actions.push((
+                    *index,
+                    EntityAction::RemoveComponent(self.entity, *component_kind),
+                ));
+            }
+        }
+        pending_actions.sort_by(|(a, _), (b, _)| {
+            if sequence_less_than(*a, *b) {
+                std::cmp::Ordering::Less
+            } else if a == b {
+                std::cmp::Ordering::Equal
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        });
+
+        EntityChannelDebug {
+            entity: self.entity,
+            spawned: self.spawned,
+            highest_seen_index: self.last_received_index,
+            highest_applied_index,
+            components,
+            pending_actions,
+        }
+    }
 }
 
 // Component Channel
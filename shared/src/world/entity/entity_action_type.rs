@@ -14,4 +14,6 @@ pub enum EntityActionType {
     RemoveComponent,
     // Action indicating a non-operation
     Noop,
+    // Several SpawnEntity actions with consecutive ids, sharing a single header
+    SpawnEntityBatch,
 }
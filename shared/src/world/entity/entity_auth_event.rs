@@ -25,6 +25,7 @@ pub enum EntityEventMessageAction {
     ReleaseAuthority,
     UpdateAuthority(EntityAuthStatus),
     EntityMigrateResponse(u16), //u16 here is new Host Entity
+    RequestResync,
 }
 
 impl EntityEventMessageAction {
@@ -59,6 +60,9 @@ impl EntityEventMessageAction {
                     RemoteEntity::new(*remote_entity),
                 )
             }
+            EntityEventMessageAction::RequestResync => {
+                EntityResponseEvent::EntityRequestResync(*entity)
+            }
         }
     }
 }
@@ -158,6 +162,13 @@ impl EntityEventMessage {
         )
     }
 
+    pub fn new_request_resync<E: Copy + Eq + Hash + Send + Sync>(
+        converter: &dyn EntityAndGlobalEntityConverter<E>,
+        entity: &E,
+    ) -> Self {
+        Self::new(converter, entity, EntityEventMessageAction::RequestResync)
+    }
+
     fn new<E: Copy + Eq + Hash + Send + Sync>(
         converter: &dyn EntityAndGlobalEntityConverter<E>,
         entity: &E,
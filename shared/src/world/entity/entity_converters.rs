@@ -43,6 +43,14 @@ pub trait EntityAndGlobalEntityConverter<E: Copy + Eq + Hash> {
         global_entity: &GlobalEntity,
     ) -> Result<E, EntityDoesNotExistError>;
     fn entity_to_global_entity(&self, entity: &E) -> Result<GlobalEntity, EntityDoesNotExistError>;
+    /// Converts many GlobalEntities at once, in order, with `None` for any
+    /// which do not exist
+    fn global_entities_to_entities(&self, global_entities: &[GlobalEntity]) -> Vec<Option<E>> {
+        global_entities
+            .iter()
+            .map(|global_entity| self.global_entity_to_entity(global_entity).ok())
+            .collect()
+    }
 }
 
 pub trait LocalEntityAndGlobalEntityConverter {
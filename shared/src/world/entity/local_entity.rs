@@ -23,6 +23,22 @@ impl OwnedLocalEntity {
         }
     }
 
+    /// Returns the HostEntity this refers to, or None if it's a RemoteEntity
+    pub fn as_host(&self) -> Option<HostEntity> {
+        match self {
+            Self::Host(value) => Some(HostEntity::new(*value)),
+            Self::Remote(_) => None,
+        }
+    }
+
+    /// Returns the RemoteEntity this refers to, or None if it's a HostEntity
+    pub fn as_remote(&self) -> Option<RemoteEntity> {
+        match self {
+            Self::Host(_) => None,
+            Self::Remote(value) => Some(RemoteEntity::new(*value)),
+        }
+    }
+
     pub fn ser(&self, writer: &mut dyn BitWrite) {
         self.is_host().ser(writer);
         UnsignedVariableInteger::<7>::new(self.value()).ser(writer);
@@ -45,21 +61,18 @@ impl OwnedLocalEntity {
         &self,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<GlobalEntity, EntityDoesNotExistError> {
-        match self {
-            OwnedLocalEntity::Host(host_entity) => {
-                converter.host_entity_to_global_entity(&HostEntity::new(*host_entity))
-            }
-            OwnedLocalEntity::Remote(remote_entity) => {
-                converter.remote_entity_to_global_entity(&RemoteEntity::new(*remote_entity))
-            }
+        if let Some(host_entity) = self.as_host() {
+            converter.host_entity_to_global_entity(&host_entity)
+        } else {
+            converter.remote_entity_to_global_entity(&self.take_remote())
         }
     }
 
     pub(crate) fn take_remote(&self) -> RemoteEntity {
-        let OwnedLocalEntity::Remote(remote_entity) = self else {
+        let Some(remote_entity) = self.as_remote() else {
             panic!("Expected RemoteEntity")
         };
-        RemoteEntity::new(*remote_entity)
+        remote_entity
     }
 
     pub(crate) fn to_reversed(&self) -> OwnedLocalEntity {
@@ -125,3 +138,20 @@ impl RemoteEntity {
         OwnedLocalEntity::Remote(self.value())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_are_checked() {
+        let remote = RemoteEntity::new(7).copy_to_owned();
+        assert_eq!(remote.as_host(), None);
+        assert_eq!(remote.as_remote(), Some(RemoteEntity::new(7)));
+
+        let host = HostEntity::new(7).copy_to_owned();
+        assert_eq!(host.as_remote(), None);
+        assert_eq!(host.as_host(), Some(HostEntity::new(7)));
+        assert_eq!(host.to_reversed().as_remote(), Some(RemoteEntity::new(7)));
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
         entity::entity_converters::GlobalWorldManagerType, local_world_manager::LocalWorldManager,
     },
     ComponentKind, DiffMask, EntityAction, HostEntity, Instant, MessageIndex, PacketIndex,
-    WorldRefType,
+    ScopeBacklog, WorldRefType,
 };
 
 use super::{entity_action_event::EntityActionEvent, world_channel::WorldChannel};
@@ -36,6 +36,8 @@ pub struct HostWorldManager<E: Copy + Eq + Hash + Send + Sync> {
     pub sent_updates: HashMap<PacketIndex, (Instant, HashMap<(E, ComponentKind), DiffMask>)>,
     /// Last [`PacketIndex`] where a component update was written by the server
     pub last_update_packet_index: PacketIndex,
+    /// Bits written for each [`ComponentKind`], only tallied when enabled
+    pub written_component_bits: Option<HashMap<ComponentKind, u32>>,
 }
 
 pub struct HostWorldEvents<E: Copy + Eq + Hash + Send + Sync> {
@@ -63,9 +65,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
             // Update
             sent_updates: HashMap::new(),
             last_update_packet_index: 0,
+            written_component_bits: None,
         }
     }
 
+    /// Begins tallying the bits each Component kind writes into outgoing packets
+    pub fn enable_written_bits_tally(&mut self) {
+        self.written_component_bits = Some(HashMap::new());
+    }
+
+    /// Returns the bits written by each Component kind since the last call, if
+    /// tallying has been enabled
+    pub fn take_written_component_bits(&mut self) -> Option<HashMap<ComponentKind, u32>> {
+        self.written_component_bits.as_mut().map(std::mem::take)
+    }
+
     // World
 
     // used when Entity first comes into Connection's scope
@@ -83,6 +97,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
         }
     }
 
+    // used when many Entities come into Connection's scope at once, i.e. when
+    // a User joins a Room. Their spawns are queued back to back, so they can
+    // be packed into shared SpawnEntityBatch actions
+    pub fn init_entities_batch(
+        &mut self,
+        world_manager: &mut LocalWorldManager<E>,
+        entities: Vec<(E, Vec<ComponentKind>)>,
+    ) {
+        for (entity, component_kinds) in entities {
+            self.init_entity(world_manager, &entity, component_kinds);
+        }
+    }
+
     pub fn spawn_entity(
         &mut self,
         world_manager: &mut LocalWorldManager<E>,
@@ -115,6 +142,64 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
         self.world_channel.host_has_entity(entity)
     }
 
+    pub fn entity_channel_is_open(&self, entity: &E) -> bool {
+        self.world_channel.entity_channel_is_open(entity)
+    }
+
+    pub fn pause_entity_updates(&mut self, entity: &E) {
+        self.world_channel.pause_entity_updates(entity);
+    }
+
+    pub fn resume_entity_updates(&mut self, entity: &E) {
+        self.world_channel.resume_entity_updates(entity);
+    }
+
+    pub fn entity_updates_paused(&self, entity: &E) -> bool {
+        self.world_channel.entity_updates_paused(entity)
+    }
+
+    /// Returns whether any Component update of the Entity is queued to be
+    /// sent, or has been sent and not yet acknowledged. Updates queued while
+    /// the Entity's updates are paused are not counted, as they will not be
+    /// sent until resumed
+    pub fn has_pending_updates(&self, entity: &E) -> bool {
+        let in_flight = self.sent_updates.values().any(|(_, diff_mask_map)| {
+            diff_mask_map
+                .keys()
+                .any(|(sent_entity, _)| sent_entity == entity)
+        });
+        if in_flight {
+            return true;
+        }
+        if self.entity_updates_paused(entity) {
+            return false;
+        }
+        let diff_handler = &self.world_channel.diff_handler;
+        self.world_channel
+            .host_component_kinds(entity)
+            .iter()
+            .any(|component_kind| {
+                diff_handler.has_component(entity, component_kind)
+                    && !diff_handler.diff_mask_is_clear(entity, component_kind)
+            })
+    }
+
+    // used when the remote host has requested the full state of an Entity,
+    // marks every field of every replicated Component as changed
+    pub fn resync_entity(&mut self, entity: &E) {
+        for component_kind in self.world_channel.host_component_kinds(entity) {
+            if self
+                .world_channel
+                .diff_handler
+                .has_component(entity, &component_kind)
+            {
+                self.world_channel
+                    .diff_handler
+                    .fill_diff_mask(entity, &component_kind);
+            }
+        }
+    }
+
     // used when Remote Entity gains Write Authority (delegation)
     pub fn track_remote_entity(
         &mut self,
@@ -153,6 +238,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
             .track_remote_component(entity, component_kind);
     }
 
+    /// Measures the Entity actions queued for the remote host & not yet
+    /// delivered
+    pub fn action_backlog(&self, now: &Instant) -> ScopeBacklog {
+        self.world_channel.action_backlog(now)
+    }
+
+    /// Sets whether despawns are sent ahead of every other Entity action
+    pub fn set_prioritize_despawns(&mut self, prioritize_despawns: bool) {
+        self.world_channel
+            .set_prioritize_despawns(prioritize_despawns);
+    }
+
     // Messages
 
     pub fn handle_dropped_packets(&mut self, now: &Instant, rtt_millis: &f32) {
@@ -247,6 +344,23 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
                 .collect_next_updates(world, global_world_manager),
         }
     }
+
+    /// Get the events which `take_outgoing_events()` would return, without
+    /// marking any as sent
+    pub fn peek_outgoing_events<W: WorldRefType<E>>(
+        &self,
+        world: &W,
+        global_world_manager: &dyn GlobalWorldManagerType<E>,
+        now: &Instant,
+        rtt_millis: &f32,
+    ) -> HostWorldEvents<E> {
+        HostWorldEvents {
+            next_send_actions: self.world_channel.peek_next_actions(now, rtt_millis),
+            next_send_updates: self
+                .world_channel
+                .collect_next_updates(world, global_world_manager),
+        }
+    }
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
@@ -4,16 +4,22 @@ use std::{
     hash::Hash,
 };
 
+use naia_serde::BitCounter;
+
 use crate::{
     messages::channels::senders::indexed_message_writer::IndexedMessageWriter,
+    sequence_less_than,
     sequence_list::SequenceList,
     world::{
-        entity::entity_converters::GlobalWorldManagerType, local_world_manager::LocalWorldManager,
+        entity::entity_converters::{
+            GlobalWorldManagerType, LocalEntityAndGlobalEntityConverterMut,
+        },
+        local_world_manager::LocalWorldManager,
     },
     BitWrite, BitWriter, ComponentKind, ComponentKinds, ConstBitLength, EntityAction,
-    EntityActionType, EntityAndLocalEntityConverter, EntityConverterMut, HostWorldEvents,
-    HostWorldManager, Instant, MessageIndex, PacketIndex, Serde, UnsignedVariableInteger,
-    WorldRefType,
+    EntityActionType, EntityAndLocalEntityConverter, EntityConverterMut, HostEntity,
+    HostWorldEvents, HostWorldManager, Instant, MessageIndex, PacketIndex, Serde,
+    UnsignedVariableInteger, WorldRefType,
 };
 
 use super::entity_action_event::EntityActionEvent;
@@ -22,6 +28,19 @@ pub type ActionId = MessageIndex;
 
 pub struct HostWorldWriter;
 
+// The most recently written entry of an open SpawnEntityBatch.
+//
+// Consecutive SpawnEntity actions are written as a single SpawnEntityBatch:
+// the first Entity is written in full, then each following Entity costs a
+// continue bit, a bit for whether its HostEntity directly follows the
+// previous one, and a bit for whether it has the same Component kinds, ahead
+// of its Components' fields. Action ids are implied, as they are consecutive.
+struct SpawnBatchEntry {
+    action_id: ActionId,
+    host_entity: HostEntity,
+    component_kinds: Vec<ComponentKind>,
+}
+
 impl HostWorldWriter {
     fn write_action_id(
         writer: &mut dyn BitWrite,
@@ -87,12 +106,146 @@ impl HostWorldWriter {
     ) {
         let mut last_counted_id: Option<MessageIndex> = None;
         let mut last_written_id: Option<MessageIndex> = None;
+        let mut spawn_batch: Option<SpawnBatchEntry> = None;
 
         loop {
             if next_send_actions.is_empty() {
                 break;
             }
 
+            if let Some(last_entry) = &spawn_batch {
+                if Self::continues_spawn_batch(last_entry, next_send_actions.front().unwrap()) {
+                    // check that we can write the next entry
+                    let mut counter = writer.counter();
+                    // write EntryContinue bit
+                    true.ser(&mut counter);
+                    // write data
+                    Self::write_spawn_batch_entry(
+                        component_kinds,
+                        world,
+                        global_world_manager,
+                        local_world_manager,
+                        &mut counter,
+                        Some(last_entry),
+                        next_send_actions.front().unwrap(),
+                    );
+                    if counter.overflowed() {
+                        break;
+                    }
+
+                    // write EntryContinue bit
+                    true.ser(writer);
+                    // write data
+                    let entry = Self::write_spawn_batch_entry(
+                        component_kinds,
+                        world,
+                        global_world_manager,
+                        local_world_manager,
+                        writer,
+                        Some(last_entry),
+                        next_send_actions.front().unwrap(),
+                    );
+                    Self::record_spawn_written(
+                        component_kinds,
+                        world,
+                        global_world_manager,
+                        local_world_manager,
+                        packet_index,
+                        host_manager,
+                        next_send_actions,
+                    );
+                    last_counted_id = Some(entry.action_id);
+                    last_written_id = Some(entry.action_id);
+                    spawn_batch = Some(entry);
+
+                    // pop action we've written
+                    next_send_actions.pop_front();
+                    continue;
+                }
+
+                // finish batch by writing false EntryContinue bit
+                writer.release_bits(1);
+                false.ser(writer);
+                spawn_batch = None;
+            }
+
+            // action ids only ascend within a packet, so an action queued
+            // behind one taken ahead of it waits for the next packet
+            if let (Some(last_id), Some((next_id, _))) =
+                (last_counted_id, next_send_actions.front())
+            {
+                if sequence_less_than(*next_id, last_id) {
+                    break;
+                }
+            }
+
+            if Self::starts_spawn_batch(next_send_actions) {
+                // check that we can write the batch's first entry
+                let mut counter = writer.counter();
+                // write ActionContinue bit
+                true.ser(&mut counter);
+                // write data
+                let mut last_id = last_counted_id;
+                Self::write_spawn_batch_start(
+                    component_kinds,
+                    world,
+                    global_world_manager,
+                    local_world_manager,
+                    &mut counter,
+                    &mut last_id,
+                    next_send_actions.front().unwrap(),
+                );
+                // reserve EntryContinue finish bit
+                false.ser(&mut counter);
+                if counter.overflowed() {
+                    // if nothing useful has been written in this packet yet,
+                    // send warning about size of component being too big
+                    if !*has_written {
+                        Self::warn_overflow_action(
+                            component_kinds,
+                            counter.bits_needed(),
+                            writer.bits_free(),
+                            next_send_actions,
+                        );
+                    }
+                    break;
+                }
+
+                *has_written = true;
+
+                Self::open_sent_action_packet(now, packet_index, host_manager);
+
+                // write ActionContinue bit
+                true.ser(writer);
+                // write data
+                let entry = Self::write_spawn_batch_start(
+                    component_kinds,
+                    world,
+                    global_world_manager,
+                    local_world_manager,
+                    writer,
+                    &mut last_written_id,
+                    next_send_actions.front().unwrap(),
+                );
+                // reserve EntryContinue finish bit
+                writer.reserve_bits(1);
+                Self::record_spawn_written(
+                    component_kinds,
+                    world,
+                    global_world_manager,
+                    local_world_manager,
+                    packet_index,
+                    host_manager,
+                    next_send_actions,
+                );
+                last_counted_id = Some(entry.action_id);
+                spawn_batch = Some(entry);
+
+                // pop action we've written
+                next_send_actions.pop_front();
+                continue;
+            }
+
             // check that we can write the next message
             let mut counter = writer.counter();
             // write ActionContinue bit
@@ -126,15 +279,7 @@ impl HostWorldWriter {
 
             *has_written = true;
 
-            // optimization
-            if !host_manager
-                .sent_action_packets
-                .contains_scan_from_back(packet_index)
-            {
-                host_manager
-                    .sent_action_packets
-                    .insert_scan_from_back(*packet_index, (now.clone(), Vec::new()));
-            }
+            Self::open_sent_action_packet(now, packet_index, host_manager);
 
             // write ActionContinue bit
             true.ser(writer);
@@ -156,11 +301,185 @@ impl HostWorldWriter {
             next_send_actions.pop_front();
         }
 
+        if spawn_batch.is_some() {
+            // finish batch by writing false EntryContinue bit
+            writer.release_bits(1);
+            false.ser(writer);
+        }
+
         // Finish actions by writing false ActionContinue bit
         writer.release_bits(1);
         false.ser(writer);
     }
 
+    fn open_sent_action_packet<E: Copy + Eq + Hash + Send + Sync>(
+        now: &Instant,
+        packet_index: &PacketIndex,
+        host_manager: &mut HostWorldManager<E>,
+    ) {
+        // optimization
+        if !host_manager
+            .sent_action_packets
+            .contains_scan_from_back(packet_index)
+        {
+            host_manager
+                .sent_action_packets
+                .insert_scan_from_back(*packet_index, (now.clone(), Vec::new()));
+        }
+    }
+
+    // whether the next two actions are SpawnEntity actions which can share a batch
+    fn starts_spawn_batch<E: Copy>(
+        next_send_actions: &VecDeque<(ActionId, EntityActionEvent<E>)>,
+    ) -> bool {
+        let (
+            Some((first_id, EntityActionEvent::SpawnEntity(..))),
+            Some((second_id, EntityActionEvent::SpawnEntity(..))),
+        ) = (next_send_actions.front(), next_send_actions.get(1))
+        else {
+            return false;
+        };
+        *second_id == first_id.wrapping_add(1)
+    }
+
+    fn continues_spawn_batch<E: Copy>(
+        last_entry: &SpawnBatchEntry,
+        (action_id, action): &(ActionId, EntityActionEvent<E>),
+    ) -> bool {
+        matches!(action, EntityActionEvent::SpawnEntity(..))
+            && *action_id == last_entry.action_id.wrapping_add(1)
+    }
+
+    fn write_spawn_batch_start<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
+        component_kinds: &ComponentKinds,
+        world: &W,
+        global_world_manager: &dyn GlobalWorldManagerType<E>,
+        local_world_manager: &mut LocalWorldManager<E>,
+        writer: &mut dyn BitWrite,
+        last_written_id: &mut Option<ActionId>,
+        action: &(ActionId, EntityActionEvent<E>),
+    ) -> SpawnBatchEntry {
+        // write message id
+        Self::write_action_id(writer, last_written_id, &action.0);
+
+        EntityActionType::SpawnEntityBatch.ser(writer);
+
+        Self::write_spawn_batch_entry(
+            component_kinds,
+            world,
+            global_world_manager,
+            local_world_manager,
+            writer,
+            None,
+            action,
+        )
+    }
+
+    fn write_spawn_batch_entry<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
+        component_kinds: &ComponentKinds,
+        world: &W,
+        global_world_manager: &dyn GlobalWorldManagerType<E>,
+        local_world_manager: &mut LocalWorldManager<E>,
+        writer: &mut dyn BitWrite,
+        last_entry: Option<&SpawnBatchEntry>,
+        (action_id, action): &(ActionId, EntityActionEvent<E>),
+    ) -> SpawnBatchEntry {
+        let EntityActionEvent::SpawnEntity(world_entity, component_kind_list) = action else {
+            panic!("only SpawnEntity actions can be batched");
+        };
+        let host_entity = local_world_manager
+            .entity_to_host_entity(world_entity)
+            .unwrap();
+
+        let (write_entity, write_kinds) = match last_entry {
+            Some(last_entry) => {
+                // write whether the HostEntity follows the previous one
+                let is_next_entity =
+                    host_entity.value() == last_entry.host_entity.value().wrapping_add(1);
+                is_next_entity.ser(writer);
+                // write whether the Component kinds are the same as the previous ones,
+                // in any order
+                let is_same_kinds = component_kind_list.len() == last_entry.component_kinds.len()
+                    && component_kind_list
+                        .iter()
+                        .all(|kind| last_entry.component_kinds.contains(kind));
+                is_same_kinds.ser(writer);
+                (!is_next_entity, !is_same_kinds)
+            }
+            None => (true, true),
+        };
+        // when unchanged, Components are written in the previous entry's order
+        let component_kind_list = match last_entry {
+            Some(last_entry) if !write_kinds => &last_entry.component_kinds,
+            _ => component_kind_list,
+        };
+
+        if write_entity {
+            // write net entity
+            host_entity.ser(writer);
+        }
+        if write_kinds {
+            // write number of components
+            let components_num =
+                UnsignedVariableInteger::<3>::new(component_kind_list.len() as i128);
+            components_num.ser(writer);
+
+            for component_kind in component_kind_list {
+                component_kind.ser(component_kinds, writer);
+            }
+        }
+
+        for component_kind in component_kind_list {
+            let mut converter = EntityConverterMut::new(global_world_manager, local_world_manager);
+
+            // write component payload, its kind is already known
+            world
+                .component_of_kind(world_entity, component_kind)
+                .expect("Component does not exist in World")
+                .write_fields(writer, &mut converter);
+        }
+
+        SpawnBatchEntry {
+            action_id: *action_id,
+            host_entity,
+            component_kinds: component_kind_list.clone(),
+        }
+    }
+
+    fn record_spawn_written<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
+        component_kinds: &ComponentKinds,
+        world: &W,
+        global_world_manager: &dyn GlobalWorldManagerType<E>,
+        local_world_manager: &mut LocalWorldManager<E>,
+        packet_index: &PacketIndex,
+        host_manager: &mut HostWorldManager<E>,
+        next_send_actions: &VecDeque<(ActionId, EntityActionEvent<E>)>,
+    ) {
+        let (action_id, action) = next_send_actions.front().unwrap();
+        let EntityActionEvent::SpawnEntity(world_entity, component_kind_list) = action else {
+            panic!("only SpawnEntity actions can be batched");
+        };
+
+        for component_kind in component_kind_list {
+            let mut converter = EntityConverterMut::new(global_world_manager, local_world_manager);
+            Self::record_component_bits(
+                component_kinds,
+                world,
+                world_entity,
+                component_kind,
+                &mut converter,
+                host_manager,
+            );
+        }
+
+        Self::record_action_written(
+            &mut host_manager.sent_action_packets,
+            packet_index,
+            action_id,
+            EntityAction::SpawnEntity(*world_entity, component_kind_list.clone()),
+        );
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn write_action<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
         component_kinds: &ComponentKinds,
@@ -203,6 +522,17 @@ impl HostWorldWriter {
                         .component_of_kind(world_entity, component_kind)
                         .expect("Component does not exist in World")
                         .write(component_kinds, writer, &mut converter);
+
+                    if is_writing {
+                        Self::record_component_bits(
+                            component_kinds,
+                            world,
+                            world_entity,
+                            component_kind,
+                            &mut converter,
+                            host_manager,
+                        );
+                    }
                 }
 
                 // if we are writing to this packet, add it to record
@@ -272,6 +602,15 @@ impl HostWorldWriter {
 
                     // if we are actually writing this packet
                     if is_writing {
+                        Self::record_component_bits(
+                            component_kinds,
+                            world,
+                            world_entity,
+                            component,
+                            &mut converter,
+                            host_manager,
+                        );
+
                         // add it to action record
                         Self::record_action_written(
                             &mut host_manager.sent_action_packets,
@@ -326,6 +665,25 @@ impl HostWorldWriter {
     }
 
     #[allow(clippy::type_complexity)]
+    fn record_component_bits<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
+        component_kinds: &ComponentKinds,
+        world: &W,
+        world_entity: &E,
+        component_kind: &ComponentKind,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        host_manager: &mut HostWorldManager<E>,
+    ) {
+        let Some(written_bits) = &mut host_manager.written_component_bits else {
+            return;
+        };
+        let mut counter = BitCounter::new(0, 0, u32::MAX);
+        world
+            .component_of_kind(world_entity, component_kind)
+            .expect("Component does not exist in World")
+            .write(component_kinds, &mut counter, converter);
+        *written_bits.entry(*component_kind).or_default() += counter.bits_needed();
+    }
+
     fn record_action_written<E: Copy + Eq + Hash + Send + Sync>(
         sent_actions: &mut SequenceList<(Instant, Vec<(ActionId, EntityAction<E>)>)>,
         packet_index: &PacketIndex,
@@ -456,25 +814,34 @@ impl HostWorldWriter {
         let component_kind_set = next_send_updates.get(entity).unwrap();
         for component_kind in component_kind_set {
             // get diff mask
-            let diff_mask = host_manager
+            let mut diff_mask = host_manager
                 .world_channel
                 .diff_handler
                 .diff_mask(entity, component_kind)
                 .clone();
+            if component_kinds.is_full_update(component_kind) {
+                diff_mask.fill();
+            }
 
             let mut converter = EntityConverterMut::new(global_world_manager, local_world_manager);
 
+            // measure the update, so its length can be written ahead of it and
+            // a receiver ignoring the Entity can skip it unread
+            let mut update_counter = BitCounter::new(0, 0, u32::MAX);
+            world
+                .component_of_kind(entity, component_kind)
+                .expect("Component does not exist in World")
+                .write_update(&diff_mask, &mut update_counter, &mut converter);
+            let update_bits = UnsignedVariableInteger::<7>::new(update_counter.bits_needed());
+
             // check that we can write the next component update
             let mut counter = writer.counter();
             // write ComponentContinue bit
             true.ser(&mut counter);
             // write component kind
             counter.count_bits(<ComponentKind as ConstBitLength>::const_bit_length());
-            // write data
-            world
-                .component_of_kind(entity, component_kind)
-                .expect("Component does not exist in World")
-                .write_update(&diff_mask, &mut counter, &mut converter);
+            // write update length & data
+            counter.count_bits(update_bits.bit_length() + update_counter.bits_needed());
             if counter.overflowed() {
                 // if nothing useful has been written in this packet yet,
                 // send warning about size of component being too big
@@ -496,6 +863,8 @@ impl HostWorldWriter {
             true.ser(writer);
             // write component kind
             component_kind.ser(component_kinds, writer);
+            // write update length
+            update_bits.ser(writer);
             // write data
             world
                 .component_of_kind(entity, component_kind)
@@ -504,6 +873,10 @@ impl HostWorldWriter {
 
             written_component_kinds.push(*component_kind);
 
+            if let Some(written_bits) = &mut host_manager.written_component_bits {
+                *written_bits.entry(*component_kind).or_default() += counter.bits_needed();
+            }
+
             // place diff mask in a special transmission record - like map
             host_manager.last_update_packet_index = *packet_index;
 
@@ -1,3 +1,4 @@
+pub mod entity_action_event;
 pub mod global_diff_handler;
 pub mod host_world_manager;
 pub mod host_world_writer;
@@ -5,5 +6,4 @@ pub mod mut_channel;
 pub mod user_diff_handler;
 pub mod world_channel;
 
-mod entity_action_event;
 mod entity_channel;
@@ -95,6 +95,13 @@ impl MutReceiver {
         mask.or(other_mask);
     }
 
+    pub fn fill_mask(&self) {
+        let Ok(mut mask) = self.mask.as_ref().write() else {
+            panic!("Mask held on current thread");
+        };
+        mask.fill();
+    }
+
     pub fn clear_mask(&self) {
         let Ok(mut mask) = self.mask.as_ref().write() else {
             panic!("Mask held on current thread");
@@ -82,6 +82,13 @@ impl<E: Copy + Eq + Hash> UserDiffHandler<E> {
         receiver.or_mask(other_mask);
     }
 
+    pub fn fill_diff_mask(&mut self, entity: &E, component_kind: &ComponentKind) {
+        let Some(receiver) = self.receivers.get_mut(&(*entity, *component_kind)) else {
+            panic!("Should not call this unless we're sure there's a receiver");
+        };
+        receiver.fill_mask();
+    }
+
     pub fn clear_diff_mask(&mut self, entity: &E, component_kind: &ComponentKind) {
         let Some(receiver) = self.receivers.get_mut(&(*entity, *component_kind)) else {
             panic!("Should not call this unless we're sure there's a receiver");
@@ -13,7 +13,7 @@ use super::{
 use crate::{
     world::{host::entity_channel::EntityChannel, local_world_manager::LocalWorldManager},
     ChannelSender, ComponentKind, EntityAction, EntityActionReceiver, GlobalWorldManagerType,
-    HostEntity, Instant, ReliableSender, WorldRefType,
+    HostEntity, Instant, ReliableSender, ScopeBacklog, WorldRefType,
 };
 
 const RESEND_ACTION_RTT_FACTOR: f32 = 1.5;
@@ -32,10 +32,17 @@ pub struct WorldChannel<E: Copy + Eq + Hash + Send + Sync> {
     remote_world: CheckedMap<E, CheckedSet<ComponentKind>>,
     entity_channels: CheckedMap<E, EntityChannel>,
     outgoing_actions: ReliableSender<EntityActionEvent<E>>,
+    /// When each undelivered action was queued, oldest first
+    action_queued_at: VecDeque<(ActionId, Instant)>,
+    /// Whether despawns are taken ahead of every other action
+    prioritize_despawns: bool,
     delivered_actions: EntityActionReceiver<E>,
 
     address: Option<SocketAddr>,
     pub diff_handler: UserDiffHandler<E>,
+    /// Entities whose updates are held back from this connection, their diff
+    /// masks keep accumulating until resumed
+    paused_entities: HashSet<E>,
 
     outgoing_release_auth_messages: Vec<E>,
 }
@@ -50,10 +57,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             remote_world: CheckedMap::new(),
             entity_channels: CheckedMap::new(),
             outgoing_actions: ReliableSender::new(RESEND_ACTION_RTT_FACTOR),
+            action_queued_at: VecDeque::new(),
+            prioritize_despawns: false,
             delivered_actions: EntityActionReceiver::new(),
 
             address: *address,
             diff_handler: UserDiffHandler::new(global_world_manager),
+            paused_entities: HashSet::new(),
 
             outgoing_release_auth_messages: Vec::new(),
         }
@@ -72,6 +82,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         return false;
     }
 
+    pub fn pause_entity_updates(&mut self, entity: &E) {
+        self.paused_entities.insert(*entity);
+    }
+
+    pub fn resume_entity_updates(&mut self, entity: &E) {
+        self.paused_entities.remove(entity);
+    }
+
+    pub fn entity_updates_paused(&self, entity: &E) -> bool {
+        self.paused_entities.contains(entity)
+    }
+
     pub fn host_component_kinds(&self, entity: &E) -> Vec<ComponentKind> {
         if let Some(component_kinds) = self.host_world.get(entity) {
             component_kinds.iter().cloned().collect()
@@ -109,11 +131,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             // spawn entity
             self.entity_channels
                 .insert(*entity, EntityChannel::new_spawning());
-            self.outgoing_actions
-                .send_message(EntityActionEvent::SpawnEntity(
-                    *entity,
-                    component_kinds.clone(),
-                ));
+            self.queue_action(EntityActionEvent::SpawnEntity(
+                *entity,
+                component_kinds.clone(),
+            ));
             self.on_entity_channel_opening(world_manager, entity);
         }
     }
@@ -136,13 +157,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         }
 
         self.host_world.remove(entity);
+        self.paused_entities.remove(entity);
 
         let removing_components = entity_channel.inserted_components();
 
         entity_channel.despawn();
 
-        self.outgoing_actions
-            .send_message(EntityActionEvent::DespawnEntity(*entity));
+        self.queue_action(EntityActionEvent::DespawnEntity(*entity));
 
         for component_kind in removing_components {
             self.on_component_channel_closing(entity, &component_kind);
@@ -155,6 +176,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         }
 
         self.host_world.remove(entity);
+        self.paused_entities.remove(entity);
 
         let Some(entity_channel) = self.entity_channels.get(entity) else {
             panic!("World Channel: cannot despawn entity that isn't spawned");
@@ -193,8 +215,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             if entity_channel.is_spawned() && !entity_channel.has_component(component_kind) {
                 // insert component
                 entity_channel.insert_component(component_kind, false);
-                self.outgoing_actions
-                    .send_message(EntityActionEvent::InsertComponent(*entity, *component_kind));
+                self.queue_action(EntityActionEvent::InsertComponent(*entity, *component_kind));
             }
         }
     }
@@ -212,11 +233,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         if let Some(entity_channel) = self.entity_channels.get_mut(world_entity) {
             if entity_channel.is_spawned() {
                 if entity_channel.remove_component(component_kind) {
-                    self.outgoing_actions
-                        .send_message(EntityActionEvent::RemoveComponent(
-                            *world_entity,
-                            *component_kind,
-                        ));
+                    self.queue_action(EntityActionEvent::RemoveComponent(
+                        *world_entity,
+                        *component_kind,
+                    ));
                     self.on_component_channel_closing(world_entity, component_kind);
                 }
             }
@@ -351,15 +371,15 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
                 entity_channel.insert_component(component_kind, true);
             }
 
-            let send_insert_action_component_kinds: HashSet<&ComponentKind> = host_components
+            let send_insert_action_component_kinds: Vec<ComponentKind> = host_components
                 .inner
                 .difference(&inserted_component_kinds)
+                .copied()
                 .collect();
 
             for component in send_insert_action_component_kinds {
                 // send insert action
-                self.outgoing_actions
-                    .send_message(EntityActionEvent::InsertComponent(*entity, *component));
+                self.queue_action(EntityActionEvent::InsertComponent(*entity, component));
             }
 
             // receive inserted components
@@ -375,8 +395,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             // despawn entity
             entity_channel.despawn();
 
-            self.outgoing_actions
-                .send_message(EntityActionEvent::DespawnEntity(*entity));
+            self.queue_action(EntityActionEvent::DespawnEntity(*entity));
         }
     }
 
@@ -405,11 +424,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             // spawn entity
             self.entity_channels
                 .insert(*entity, EntityChannel::new_spawning());
-            self.outgoing_actions
-                .send_message(EntityActionEvent::SpawnEntity(
-                    *entity,
-                    self.host_component_kinds(entity),
-                ));
+            self.queue_action(EntityActionEvent::SpawnEntity(
+                *entity,
+                self.host_component_kinds(entity),
+            ));
             self.on_entity_channel_opening(local_world_manager, entity);
         }
 
@@ -463,8 +481,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         } else {
             // if component doesn't exist in host, start removal
             entity_channel.remove_component(component_kind);
-            self.outgoing_actions
-                .send_message(EntityActionEvent::RemoveComponent(*entity, *component_kind));
+            self.queue_action(EntityActionEvent::RemoveComponent(*entity, *component_kind));
             self.on_component_channel_closing(entity, component_kind);
         }
     }
@@ -474,7 +491,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             panic!("World Channel: cannot remove component from non-existent entity");
         }
 
-        let components = self.remote_world.get_mut(entity).unwrap();
+        let components = self.remote_world.get(entity).unwrap();
         if !components.contains(component_kind) {
             panic!("World Channel: should not be able to remove non-existent component in remote world");
         }
@@ -503,15 +520,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             if host_has_component {
                 // insert component
                 entity_channel.insert_component(component_kind, false);
-                self.outgoing_actions
-                    .send_message(EntityActionEvent::InsertComponent(*entity, *component_kind));
+                self.queue_action(EntityActionEvent::InsertComponent(*entity, *component_kind));
             }
         } else {
             // entity channel may be despawning, which is okay at this point
             // TODO: enforce this check
         }
 
-        components.remove(component_kind);
+        self.remote_world
+            .get_mut(entity)
+            .unwrap()
+            .remove(component_kind);
     }
 
     // State Transition events
@@ -552,6 +571,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             .deregister_component(entity, component_kind);
     }
 
+    fn queue_action(&mut self, action: EntityActionEvent<E>) {
+        self.action_queued_at
+            .push_back((self.outgoing_actions.next_message_index(), Instant::now()));
+        self.outgoing_actions.send_message(action);
+    }
+
     // Action Delivery
 
     pub fn action_delivered(
@@ -561,6 +586,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         action: EntityAction<E>,
     ) {
         if self.outgoing_actions.deliver_message(&action_id).is_some() {
+            // forget when delivered actions were queued, up to the oldest
+            // which is still pending
+            let oldest_pending = self.outgoing_actions.oldest_pending_message_index();
+            while let Some((queued_id, _)) = self.action_queued_at.front() {
+                if Some(*queued_id) == oldest_pending {
+                    break;
+                }
+                self.action_queued_at.pop_front();
+            }
+
             self.delivered_actions.buffer_action(action_id, action);
             self.process_delivered_actions(local_world_manager);
         }
@@ -591,6 +626,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         }
     }
 
+    /// Measures the actions queued & not yet delivered
+    pub fn action_backlog(&self, now: &Instant) -> ScopeBacklog {
+        ScopeBacklog {
+            pending_actions: self.outgoing_actions.pending_messages_count(),
+            oldest_age: self
+                .action_queued_at
+                .front()
+                .map(|(_, queued_at)| queued_at.elapsed(now))
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn set_prioritize_despawns(&mut self, prioritize_despawns: bool) {
+        self.prioritize_despawns = prioritize_despawns;
+    }
+
     // Collect
 
     pub fn take_next_actions(
@@ -599,7 +650,49 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         rtt_millis: &f32,
     ) -> VecDeque<(ActionId, EntityActionEvent<E>)> {
         self.outgoing_actions.collect_messages(now, rtt_millis);
-        self.outgoing_actions.take_next_messages()
+        let actions = self.outgoing_actions.take_next_messages();
+        Self::order_actions(self.prioritize_despawns, actions)
+    }
+
+    /// Get the actions which `take_next_actions()` would return, without
+    /// marking any as sent
+    pub fn peek_next_actions(
+        &self,
+        now: &Instant,
+        rtt_millis: &f32,
+    ) -> VecDeque<(ActionId, EntityActionEvent<E>)> {
+        let actions = self.outgoing_actions.peek_next_messages(now, rtt_millis);
+        Self::order_actions(self.prioritize_despawns, actions)
+    }
+
+    // moves despawns to the front if prioritized, except behind a pending
+    // action of the same Entity, so each Entity's actions stay in order
+    fn order_actions(
+        prioritize_despawns: bool,
+        actions: VecDeque<(ActionId, EntityActionEvent<E>)>,
+    ) -> VecDeque<(ActionId, EntityActionEvent<E>)> {
+        if !prioritize_despawns {
+            return actions;
+        }
+        let mut despawns = VecDeque::new();
+        let mut others = VecDeque::new();
+        let mut held_entities = HashSet::new();
+        for (action_id, action) in actions {
+            match action {
+                EntityActionEvent::DespawnEntity(entity) if !held_entities.contains(&entity) => {
+                    despawns.push_back((action_id, action));
+                }
+                EntityActionEvent::SpawnEntity(entity, _)
+                | EntityActionEvent::DespawnEntity(entity)
+                | EntityActionEvent::InsertComponent(entity, _)
+                | EntityActionEvent::RemoveComponent(entity, _) => {
+                    held_entities.insert(entity);
+                    others.push_back((action_id, action));
+                }
+            }
+        }
+        despawns.extend(others);
+        despawns
     }
 
     pub fn collect_next_updates<W: WorldRefType<E>>(
@@ -610,6 +703,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         let mut output = HashMap::new();
 
         for (entity, entity_channel) in self.entity_channels.iter() {
+            if self.paused_entities.contains(entity) {
+                continue;
+            }
             if entity_channel.is_spawned() && world.has_entity(entity) {
                 for component_kind in entity_channel.inserted_components() {
                     if self
@@ -730,3 +826,46 @@ impl<K: Eq + Hash> CheckedSet<K> {
         self.inner.iter()
     }
 }
+
+#[cfg(test)]
+mod order_actions_tests {
+    use std::collections::VecDeque;
+
+    use super::{EntityActionEvent, WorldChannel};
+
+    fn ordered_ids(prioritize_despawns: bool, actions: Vec<EntityActionEvent<u32>>) -> Vec<u16> {
+        let actions: VecDeque<_> = actions
+            .into_iter()
+            .enumerate()
+            .map(|(action_id, action)| (action_id as u16, action))
+            .collect();
+        WorldChannel::<u32>::order_actions(prioritize_despawns, actions)
+            .into_iter()
+            .map(|(action_id, _)| action_id)
+            .collect()
+    }
+
+    #[test]
+    fn despawns_jump_ahead_only_when_prioritized() {
+        let actions = || {
+            vec![
+                EntityActionEvent::SpawnEntity(1, Vec::new()),
+                EntityActionEvent::SpawnEntity(2, Vec::new()),
+                EntityActionEvent::DespawnEntity(3),
+            ]
+        };
+        assert_eq!(ordered_ids(false, actions()), vec![0, 1, 2]);
+        assert_eq!(ordered_ids(true, actions()), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn despawn_stays_behind_its_own_unsent_spawn() {
+        let actions = vec![
+            EntityActionEvent::SpawnEntity(1, Vec::new()),
+            EntityActionEvent::SpawnEntity(2, Vec::new()),
+            EntityActionEvent::DespawnEntity(2),
+            EntityActionEvent::DespawnEntity(3),
+        ];
+        assert_eq!(ordered_ids(true, actions), vec![3, 0, 1, 2]);
+    }
+}
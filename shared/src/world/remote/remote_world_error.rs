@@ -0,0 +1,25 @@
+use std::{error::Error, fmt};
+
+use crate::world::entity::local_entity::RemoteEntity;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteWorldError {
+    /// An Entity's channel has held a received action back for longer than
+    /// `ConnectionConfig::stalled_entity_channel_timeout`, waiting on an
+    /// earlier action which has not arrived
+    StalledChannel { entity: RemoteEntity },
+}
+
+impl Error for RemoteWorldError {}
+
+impl fmt::Display for RemoteWorldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::StalledChannel { entity } => write!(
+                f,
+                "Remote World Error: channel of Entity {:?} is stalled waiting on an earlier action",
+                entity
+            ),
+        }
+    }
+}
@@ -27,6 +27,7 @@ pub struct RemoteWorldManager<E: Copy + Eq + Hash + Send + Sync> {
     update_waitlist_store: WaitlistStore<(Tick, E, ComponentKind, ComponentFieldUpdate)>,
     update_waitlist_map: HashMap<(E, ComponentKind), HashMap<u8, WaitlistHandle>>,
     outgoing_events: Vec<EntityEvent<E>>,
+    ignored_entities: HashSet<E>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
@@ -38,9 +39,30 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
             update_waitlist_store: WaitlistStore::new(),
             update_waitlist_map: HashMap::new(),
             outgoing_events: Vec::new(),
+            ignored_entities: HashSet::new(),
         }
     }
 
+    /// Stop applying incoming Component updates to the given Entity.
+    /// Spawn/Despawn and Insert/Remove actions are still processed.
+    pub fn ignore_entity(&mut self, world_entity: &E) {
+        self.ignored_entities.insert(*world_entity);
+    }
+
+    /// Resume applying incoming Component updates to the given Entity.
+    /// Returns whether the Entity was previously ignored.
+    pub fn unignore_entity(&mut self, world_entity: &E) -> bool {
+        self.ignored_entities.remove(world_entity)
+    }
+
+    pub fn entity_is_ignored(&self, world_entity: &E) -> bool {
+        self.ignored_entities.contains(world_entity)
+    }
+
+    pub fn ignored_entities(&self) -> &HashSet<E> {
+        &self.ignored_entities
+    }
+
     pub fn on_entity_channel_opened(&mut self, remote_entity: &RemoteEntity) {
         self.entity_waitlist.add_entity(remote_entity);
     }
@@ -134,19 +156,27 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
                 EntityAction::DespawnEntity(remote_entity) => {
                     let world_entity = local_world_manager.remove_by_remote_entity(&remote_entity);
 
-                    // Generate event for each component, handing references off just in
-                    // case
-                    if let Some(component_kinds) =
-                        global_world_manager.component_kinds(&world_entity)
-                    {
-                        for component_kind in component_kinds {
-                            self.process_remove(world, world_entity, component_kind);
+                    // Generate event for each component, handing its final state off
+                    // before the despawn event. Components inserted earlier in this
+                    // same batch aren't known to the global world manager yet
+                    let mut component_kinds = global_world_manager
+                        .component_kinds(&world_entity)
+                        .unwrap_or_default();
+                    if world.has_entity(&world_entity) {
+                        for component_kind in world.component_kinds(&world_entity) {
+                            if !component_kinds.contains(&component_kind) {
+                                component_kinds.push(component_kind);
+                            }
                         }
                     }
+                    for component_kind in component_kinds {
+                        self.process_remove(world, world_entity, component_kind);
+                    }
 
                     world.despawn_entity(&world_entity);
 
                     self.on_entity_channel_closing(&remote_entity);
+                    self.ignored_entities.remove(&world_entity);
 
                     self.outgoing_events
                         .push(EntityEvent::<E>::DespawnEntity(world_entity));
@@ -232,11 +262,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
             .update_waitlist_map
             .remove(&(world_entity, component_kind))
         {
+            // the Component itself is in the world, waiting updates aside
             for (_index, handle) in handle_map {
                 self.update_waitlist_store.remove(&handle);
                 self.entity_waitlist.remove_waiting_handle(&handle);
             }
-            return;
         }
         // Remove from world
         if let Some(component) = world.remove_component_of_kind(&world_entity, &component_kind) {
@@ -314,6 +344,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
             local_world_manager,
         );
         for (tick, world_entity, component_update) in incoming_updates.drain(..) {
+            if self.ignored_entities.contains(&world_entity) {
+                continue;
+            }
+
             let component_kind = component_update.kind;
 
             // split the component_update into the waiting and ready parts
@@ -422,6 +456,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
                     self.update_waitlist_map.remove(&component_key);
                 }
 
+                if self.ignored_entities.contains(&world_entity) {
+                    continue;
+                }
+
                 if world
                     .component_apply_field_update(
                         &converter,
@@ -1,12 +1,17 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    time::Duration,
+};
 
 use log::warn;
 
 use crate::{
     messages::channels::receivers::indexed_message_reader::IndexedMessageReader,
     world::entity::local_entity::RemoteEntity, world::local_world_manager::LocalWorldManager,
-    BitReader, ComponentKind, ComponentKinds, ComponentUpdate, EntityAction, EntityActionReceiver,
-    EntityActionType, EntityConverter, GlobalWorldManagerType, LocalEntityAndGlobalEntityConverter,
+    world::remote::remote_world_error::RemoteWorldError, BitReader, ComponentKind, ComponentKinds,
+    ComponentUpdate, EntityAction, EntityActionReceiver, EntityActionType, EntityChannelDebug,
+    EntityConverter, GlobalWorldManagerType, Instant, LocalEntityAndGlobalEntityConverter,
     MessageIndex, Protocol, Replicate, Serde, SerdeErr, Tick, UnsignedVariableInteger,
 };
 
@@ -23,9 +28,11 @@ pub struct RemoteWorldEvents<E: Copy + Eq + Hash + Send + Sync> {
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldReader<E> {
-    pub fn new() -> Self {
+    pub fn new(stalled_channel_timeout: Option<Duration>) -> Self {
+        let mut receiver = EntityActionReceiver::new();
+        receiver.set_stall_timeout(stalled_channel_timeout);
         Self {
-            receiver: EntityActionReceiver::new(),
+            receiver,
             received_components: HashMap::default(),
             received_updates: Vec::new(),
         }
@@ -53,6 +60,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldReader<E> {
             .untrack_hosts_redundant_remote_entity(remote_entity);
     }
 
+    pub fn debug_dump(&self) -> Vec<EntityChannelDebug<RemoteEntity>> {
+        self.receiver.debug_dump()
+    }
+
+    /// Returns an error for each Entity channel which has newly stalled
+    pub fn take_stalled_channel_errors(&mut self, now: &Instant) -> Vec<RemoteWorldError> {
+        self.receiver
+            .take_stalled_entities(now)
+            .into_iter()
+            .map(|entity| RemoteWorldError::StalledChannel { entity })
+            .collect()
+    }
+
     // Reading
 
     fn read_message_index(
@@ -71,12 +91,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldReader<E> {
         &mut self,
         global_world_manager: &dyn GlobalWorldManagerType<E>,
         local_world_manager: &mut LocalWorldManager<E>,
+        ignored_entities: &HashSet<E>,
         protocol: &Protocol,
         tick: &Tick,
         reader: &mut BitReader,
     ) -> Result<(), SerdeErr> {
         // read entity updates
-        self.read_updates(local_world_manager, &protocol.component_kinds, tick, reader)?;
+        self.read_updates(
+            local_world_manager,
+            ignored_entities,
+            &protocol.component_kinds,
+            tick,
+            reader,
+        )?;
 
         // read entity actions
         self.read_actions(
@@ -193,15 +220,88 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldReader<E> {
             EntityActionType::Noop => {
                 self.receiver.buffer_action(action_id, EntityAction::Noop);
             }
+            // Several Entity Creations
+            EntityActionType::SpawnEntityBatch => {
+                self.read_spawn_batch(converter, component_kinds, reader, action_id, last_read_id)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Read the entries of a SpawnEntityBatch, buffering a SpawnEntity action
+    /// for each. See `HostWorldWriter` for the format.
+    fn read_spawn_batch(
+        &mut self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        component_kinds: &ComponentKinds,
+        reader: &mut BitReader,
+        first_action_id: MessageIndex,
+        last_read_id: &mut Option<MessageIndex>,
+    ) -> Result<(), SerdeErr> {
+        let mut action_id = first_action_id;
+        let mut last_entry: Option<(RemoteEntity, Vec<ComponentKind>)> = None;
+
+        loop {
+            let (read_entity, read_kinds) = match &last_entry {
+                Some(_) => {
+                    // read entry continue bit
+                    let entry_continue = bool::de(reader)?;
+                    if !entry_continue {
+                        break;
+                    }
+                    action_id = action_id.wrapping_add(1);
+
+                    let is_next_entity = bool::de(reader)?;
+                    let is_same_kinds = bool::de(reader)?;
+                    (!is_next_entity, !is_same_kinds)
+                }
+                None => (true, true),
+            };
+
+            let remote_entity = if read_entity {
+                RemoteEntity::de(reader)?
+            } else {
+                let (last_entity, _) = last_entry.as_ref().unwrap();
+                RemoteEntity::new(last_entity.value().wrapping_add(1))
+            };
+
+            let component_kind_list = if read_kinds {
+                let components_num = UnsignedVariableInteger::<3>::de(reader)?.get();
+                let mut component_kind_list = Vec::new();
+                for _ in 0..components_num {
+                    component_kind_list.push(ComponentKind::de(component_kinds, reader)?);
+                }
+                component_kind_list
+            } else {
+                let (_, last_kinds) = last_entry.as_ref().unwrap();
+                last_kinds.clone()
+            };
+
+            for component_kind in &component_kind_list {
+                let new_component =
+                    component_kinds.read_fields(component_kind, reader, converter)?;
+                self.received_components
+                    .insert((remote_entity, *component_kind), new_component);
+            }
+
+            self.receiver.buffer_action(
+                action_id,
+                EntityAction::SpawnEntity(remote_entity, component_kind_list.clone()),
+            );
+            last_entry = Some((remote_entity, component_kind_list));
+        }
+
+        *last_read_id = Some(action_id);
+
+        Ok(())
+    }
+
     /// Read component updates from raw bits
     fn read_updates(
         &mut self,
         local_world_manager: &LocalWorldManager<E>,
+        ignored_entities: &HashSet<E>,
         component_kinds: &ComponentKinds,
         tick: &Tick,
         reader: &mut BitReader,
@@ -217,6 +317,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldReader<E> {
 
             self.read_update(
                 local_world_manager,
+                ignored_entities,
                 component_kinds,
                 tick,
                 reader,
@@ -231,6 +332,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldReader<E> {
     fn read_update(
         &mut self,
         local_world_manager: &LocalWorldManager<E>,
+        ignored_entities: &HashSet<E>,
         component_kinds: &ComponentKinds,
         tick: &Tick,
         reader: &mut BitReader,
@@ -243,17 +345,25 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldReader<E> {
                 break;
             }
 
-            let component_update = component_kinds.read_create_update(reader)?;
+            let component_kind = ComponentKind::de(component_kinds, reader)?;
+            let update_bits = UnsignedVariableInteger::<7>::de(reader)?.get() as u32;
 
             // At this point, the WorldChannel/EntityReceiver should guarantee the Entity is in scope, correct?
-            if local_world_manager.has_remote_entity(remote_entity) {
-                let world_entity = local_world_manager.world_entity_from_remote(remote_entity);
-
-                self.received_updates
-                    .push((*tick, world_entity, component_update));
-            } else {
+            if !local_world_manager.has_remote_entity(remote_entity) {
                 warn!("read_update(): SKIPPED READ UPDATE!");
+                reader.skip_bits(update_bits)?;
+                continue;
             }
+            let world_entity = local_world_manager.world_entity_from_remote(remote_entity);
+            if ignored_entities.contains(&world_entity) {
+                reader.skip_bits(update_bits)?;
+                continue;
+            }
+
+            let component_update =
+                component_kinds.read_create_update_of_kind(&component_kind, reader)?;
+            self.received_updates
+                .push((*tick, world_entity, component_update));
         }
 
         Ok(())
@@ -1,3 +1,5 @@
+use std::{any::Any, hash::Hash};
+
 use naia_serde::SerdeErr;
 
 use crate::{
@@ -27,7 +29,9 @@ pub trait WorldRefType<E> {
 
     // Components
     /// check whether entity contains component
-    fn has_component<R: ReplicatedComponent>(&self, entity: &E) -> bool;
+    fn has_component<R: ReplicatedComponent>(&self, entity: &E) -> bool {
+        self.has_component_of_kind(entity, &ComponentKind::of::<R>())
+    }
     /// check whether entity contains component, dynamically
     fn has_component_of_kind(&self, entity: &E, component_kind: &ComponentKind) -> bool;
     /// gets an entity's component
@@ -46,14 +50,149 @@ pub trait WorldRefType<E> {
 /// Structures that implement the WorldMutType trait will be able to be loaded
 /// into the Server at which point the Server will use this interface to keep
 /// the WorldMutType in-sync with it's own Entities/Components
+///
+/// Only the core methods need implementing: spawning & despawning Entities,
+/// listing, accessing, inserting and removing Components. Everything else,
+/// including publishing & delegation, has a default built on those, which a
+/// World may override with a faster version.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use naia_shared::{
+///     ComponentKind, ReplicaDynMutWrapper, ReplicaDynRefWrapper, ReplicaMutWrapper,
+///     ReplicaRefWrapper, Replicate, ReplicatedComponent, WorldMutType, WorldRefType,
+/// };
+///
+/// // Entities are indices into `entities`, despawned ones are None
+/// #[derive(Default)]
+/// struct MinimalWorld {
+///     entities: Vec<Option<HashMap<ComponentKind, Box<dyn Replicate>>>>,
+/// }
+///
+/// impl MinimalWorld {
+///     fn components(&self, entity: &usize) -> Option<&HashMap<ComponentKind, Box<dyn Replicate>>> {
+///         self.entities.get(*entity)?.as_ref()
+///     }
+///
+///     fn components_mut(
+///         &mut self,
+///         entity: &usize,
+///     ) -> Option<&mut HashMap<ComponentKind, Box<dyn Replicate>>> {
+///         self.entities.get_mut(*entity)?.as_mut()
+///     }
+/// }
+///
+/// impl WorldRefType<usize> for MinimalWorld {
+///     fn has_entity(&self, entity: &usize) -> bool {
+///         self.components(entity).is_some()
+///     }
+///
+///     fn entities(&self) -> Vec<usize> {
+///         (0..self.entities.len())
+///             .filter(|entity| self.has_entity(entity))
+///             .collect()
+///     }
+///
+///     fn has_component_of_kind(&self, entity: &usize, component_kind: &ComponentKind) -> bool {
+///         self.component_of_kind(entity, component_kind).is_some()
+///     }
+///
+///     fn component<R: ReplicatedComponent>(
+///         &self,
+///         _entity: &usize,
+///     ) -> Option<ReplicaRefWrapper<'_, R>> {
+///         // typed access is left out of this example
+///         None
+///     }
+///
+///     fn component_of_kind<'a>(
+///         &'a self,
+///         entity: &usize,
+///         component_kind: &ComponentKind,
+///     ) -> Option<ReplicaDynRefWrapper<'a>> {
+///         let component = self.components(entity)?.get(component_kind)?;
+///         Some(ReplicaDynRefWrapper::new(component.dyn_ref()))
+///     }
+/// }
+///
+/// impl WorldMutType<usize> for MinimalWorld {
+///     fn spawn_entity(&mut self) -> usize {
+///         self.entities.push(Some(HashMap::new()));
+///         self.entities.len() - 1
+///     }
+///
+///     fn despawn_entity(&mut self, entity: &usize) {
+///         if let Some(slot) = self.entities.get_mut(*entity) {
+///             *slot = None;
+///         }
+///     }
+///
+///     fn component_kinds(&mut self, entity: &usize) -> Vec<ComponentKind> {
+///         self.components(entity)
+///             .map(|components| components.keys().copied().collect())
+///             .unwrap_or_default()
+///     }
+///
+///     fn component_mut<R: ReplicatedComponent>(
+///         &mut self,
+///         _entity: &usize,
+///     ) -> Option<ReplicaMutWrapper<'_, R>> {
+///         None
+///     }
+///
+///     fn component_mut_of_kind<'a>(
+///         &'a mut self,
+///         entity: &usize,
+///         component_kind: &ComponentKind,
+///     ) -> Option<ReplicaDynMutWrapper<'a>> {
+///         let component = self.components_mut(entity)?.get_mut(component_kind)?;
+///         Some(ReplicaDynMutWrapper::new(component.dyn_mut()))
+///     }
+///
+///     fn insert_boxed_component(&mut self, entity: &usize, boxed_component: Box<dyn Replicate>) {
+///         if let Some(components) = self.components_mut(entity) {
+///             components.insert(boxed_component.kind(), boxed_component);
+///         }
+///     }
+///
+///     fn remove_component_of_kind(
+///         &mut self,
+///         entity: &usize,
+///         component_kind: &ComponentKind,
+///     ) -> Option<Box<dyn Replicate>> {
+///         self.components_mut(entity)?.remove(component_kind)
+///     }
+/// }
+///
+/// let mut world = MinimalWorld::default();
+/// let entity = world.spawn_entity();
+/// assert_eq!(world.entities(), vec![entity]);
+/// let duplicate = world.local_duplicate_entity(&entity);
+/// assert!(world.has_entity(&duplicate));
+/// ```
 pub trait WorldMutType<E>: WorldRefType<E> {
     // Entities
     /// spawn an entity
     fn spawn_entity(&mut self) -> E;
     /// duplicate an entity
-    fn local_duplicate_entity(&mut self, entity: &E) -> E;
+    fn local_duplicate_entity(&mut self, entity: &E) -> E {
+        let new_entity = self.spawn_entity();
+        self.local_duplicate_components(&new_entity, entity);
+        new_entity
+    }
     /// make it so one entity has all the same components as another
-    fn local_duplicate_components(&mut self, mutable_entity: &E, immutable_entity: &E);
+    fn local_duplicate_components(&mut self, mutable_entity: &E, immutable_entity: &E) {
+        for component_kind in self.component_kinds(immutable_entity) {
+            let Some(boxed_component) = self
+                .component_of_kind(immutable_entity, &component_kind)
+                .map(|component| component.copy_to_box())
+            else {
+                continue;
+            };
+            self.insert_boxed_component(mutable_entity, boxed_component);
+        }
+    }
     /// despawn an entity
     fn despawn_entity(&mut self, entity: &E);
 
@@ -78,7 +217,12 @@ pub trait WorldMutType<E>: WorldRefType<E> {
         entity: &E,
         component_kind: &ComponentKind,
         update: ComponentUpdate,
-    ) -> Result<(), SerdeErr>;
+    ) -> Result<(), SerdeErr> {
+        if let Some(mut component) = self.component_mut_of_kind(entity, component_kind) {
+            component.read_apply_update(converter, update)?;
+        }
+        Ok(())
+    }
     /// reads an incoming stream into a component
     fn component_apply_field_update(
         &mut self,
@@ -86,10 +230,19 @@ pub trait WorldMutType<E>: WorldRefType<E> {
         entity: &E,
         component_kind: &ComponentKind,
         update: ComponentFieldUpdate,
-    ) -> Result<(), SerdeErr>;
+    ) -> Result<(), SerdeErr> {
+        if let Some(mut component) = self.component_mut_of_kind(entity, component_kind) {
+            component.read_apply_field_update(converter, update)?;
+        }
+        Ok(())
+    }
     /// mirrors the whole state of two different entities
     /// (setting 1st entity's component to 2nd entity's component's state)
-    fn mirror_entities(&mut self, mutable_entity: &E, immutable_entity: &E);
+    fn mirror_entities(&mut self, mutable_entity: &E, immutable_entity: &E) {
+        for component_kind in self.component_kinds(immutable_entity) {
+            self.mirror_components(mutable_entity, immutable_entity, &component_kind);
+        }
+    }
     /// mirrors the state of the same component of two different entities
     /// (setting 1st entity's component to 2nd entity's component's state)
     fn mirror_components(
@@ -97,13 +250,32 @@ pub trait WorldMutType<E>: WorldRefType<E> {
         mutable_entity: &E,
         immutable_entity: &E,
         component_kind: &ComponentKind,
-    );
+    ) {
+        let Some(immutable_component) = self
+            .component_of_kind(immutable_entity, component_kind)
+            .map(|component| component.copy_to_box())
+        else {
+            return;
+        };
+        if let Some(mut mutable_component) =
+            self.component_mut_of_kind(mutable_entity, component_kind)
+        {
+            mutable_component.mirror(immutable_component.as_ref());
+        }
+    }
     /// insert a component
-    fn insert_component<R: ReplicatedComponent>(&mut self, entity: &E, component_ref: R);
+    fn insert_component<R: ReplicatedComponent>(&mut self, entity: &E, component_ref: R) {
+        self.insert_boxed_component(entity, Box::new(component_ref));
+    }
     /// insert a boxed component
     fn insert_boxed_component(&mut self, entity: &E, boxed_component: Box<dyn Replicate>);
     /// remove a component
-    fn remove_component<R: ReplicatedComponent>(&mut self, entity: &E) -> Option<R>;
+    fn remove_component<R: ReplicatedComponent>(&mut self, entity: &E) -> Option<R> {
+        let boxed_component = self.remove_component_of_kind(entity, &ComponentKind::of::<R>())?;
+        Box::<dyn Any + 'static>::downcast::<R>(boxed_component.to_boxed_any())
+            .ok()
+            .map(|boxed_component| *boxed_component)
+    }
     /// remove a component by kind
     fn remove_component_of_kind(
         &mut self,
@@ -112,33 +284,97 @@ pub trait WorldMutType<E>: WorldRefType<E> {
     ) -> Option<Box<dyn Replicate>>;
 
     /// publish entity
-    fn entity_publish(&mut self, global_world_manager: &dyn GlobalWorldManagerType<E>, entity: &E);
+    fn entity_publish(&mut self, global_world_manager: &dyn GlobalWorldManagerType<E>, entity: &E)
+    where
+        E: Copy + Eq + Hash,
+    {
+        for component_kind in self.component_kinds(entity) {
+            self.component_publish(global_world_manager, entity, &component_kind);
+        }
+    }
     /// publish component
     fn component_publish(
         &mut self,
         global_world_manager: &dyn GlobalWorldManagerType<E>,
         entity: &E,
         component_kind: &ComponentKind,
-    );
+    ) where
+        E: Copy + Eq + Hash,
+    {
+        if let Some(mut component) = self.component_mut_of_kind(entity, component_kind) {
+            let diff_mask_size = component.diff_mask_size();
+            let mutator =
+                global_world_manager.register_component(entity, component_kind, diff_mask_size);
+            component.publish(&mutator);
+        }
+    }
     /// unpublish entity
-    fn entity_unpublish(&mut self, entity: &E);
+    fn entity_unpublish(&mut self, entity: &E) {
+        for component_kind in self.component_kinds(entity) {
+            self.component_unpublish(entity, &component_kind);
+        }
+    }
     /// unpublish component
-    fn component_unpublish(&mut self, entity: &E, component_kind: &ComponentKind);
+    fn component_unpublish(&mut self, entity: &E, component_kind: &ComponentKind) {
+        if let Some(mut component) = self.component_mut_of_kind(entity, component_kind) {
+            component.unpublish();
+        }
+    }
     /// enable delegation on entity
     fn entity_enable_delegation(
         &mut self,
         global_world_manager: &dyn GlobalWorldManagerType<E>,
         entity: &E,
-    );
+    ) where
+        E: Copy + Eq + Hash,
+    {
+        for component_kind in self.component_kinds(entity) {
+            self.component_enable_delegation(global_world_manager, entity, &component_kind);
+        }
+    }
     /// enable delegation on component
     fn component_enable_delegation(
         &mut self,
         global_world_manager: &dyn GlobalWorldManagerType<E>,
         entity: &E,
         component_kind: &ComponentKind,
-    );
+    ) where
+        E: Copy + Eq + Hash,
+    {
+        let accessor = global_world_manager.get_entity_auth_accessor(entity);
+        let needs_mutator = global_world_manager.entity_needs_mutator_for_delegation(entity);
+        if let Some(mut component) = self.component_mut_of_kind(entity, component_kind) {
+            if needs_mutator {
+                let diff_mask_size = component.diff_mask_size();
+                let mutator =
+                    global_world_manager.register_component(entity, component_kind, diff_mask_size);
+                component.enable_delegation(&accessor, Some(&mutator));
+            } else {
+                component.enable_delegation(&accessor, None);
+            }
+        }
+    }
     /// disable delegation on entity
-    fn entity_disable_delegation(&mut self, entity: &E);
+    fn entity_disable_delegation(&mut self, entity: &E) {
+        for component_kind in self.component_kinds(entity) {
+            self.component_disable_delegation(entity, &component_kind);
+        }
+    }
     /// disable delegation on component
-    fn component_disable_delegation(&mut self, entity: &E, component_kind: &ComponentKind);
+    fn component_disable_delegation(&mut self, entity: &E, component_kind: &ComponentKind) {
+        if let Some(mut component) = self.component_mut_of_kind(entity, component_kind) {
+            component.disable_delegation();
+        }
+    }
+}
+
+/// Implemented by an owned World which can lend out a WorldMutType for
+/// itself, so that it can be kept behind a lock & driven from another thread
+pub trait ProxyWorldMut<E> {
+    type Proxy<'w>: WorldMutType<E>
+    where
+        Self: 'w;
+
+    /// Lends out a WorldMutType for this World
+    fn proxy_mut(&mut self) -> Self::Proxy<'_>;
 }
@@ -28,7 +28,7 @@ mod some_enum_2 {
     }
 }
 
-use naia_shared::{BitReader, BitWriter, Serde};
+use naia_shared::{BitReader, BitWriter, Serde, SerdeEnum};
 use some_enum::SomeEnum;
 use some_enum_2::SomeEnum2;
 
@@ -95,3 +95,55 @@ fn read_write_enum_2() {
     assert_eq!(in_2, out_2);
     assert_eq!(in_3, out_3);
 }
+
+#[test]
+fn diff_enum_fields() {
+    let variant_3 = SomeEnum::Variant3(5851, "Hello enum!".to_string());
+
+    assert_eq!(SomeEnum::MAX_VARIANT_FIELDS, 3);
+    assert_eq!(variant_3.diff_fields(&variant_3), Some(Vec::new()));
+    assert_eq!(
+        variant_3.diff_fields(&SomeEnum::Variant3(5851, "Bye enum!".to_string())),
+        Some(vec![1])
+    );
+    assert_eq!(variant_3.diff_fields(&SomeEnum::Variant1), None);
+}
+
+#[test]
+fn read_write_enum_fields() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = SomeEnum::Variant4 {
+        some_bool: true,
+        some_number: -7,
+        some_string: "Heya there enum".to_string(),
+    };
+    // only `some_number` is written
+    in_1.ser_fields(&|field| field == 1, &mut writer);
+    // written for a variant the reader does not hold
+    SomeEnum::Variant2(true).ser_fields(&|_| true, &mut writer);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let mut out_1 = SomeEnum::Variant4 {
+        some_bool: false,
+        some_number: 0,
+        some_string: "Untouched".to_string(),
+    };
+    out_1.de_fields(&mut reader).unwrap();
+    out_1.de_fields(&mut reader).unwrap();
+
+    assert_eq!(
+        out_1,
+        SomeEnum::Variant4 {
+            some_bool: false,
+            some_number: -7,
+            some_string: "Untouched".to_string(),
+        }
+    );
+}
@@ -0,0 +1,6 @@
+#[test]
+fn derive_generic_types() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/generic_message.rs");
+    cases.pass("tests/ui/generic_component.rs");
+}
@@ -0,0 +1,64 @@
+use naia_shared::{
+    BitReader, BitWriter, ComponentKind, FakeEntityConverter, Named, Property, Protocol, Replicate,
+    Serde,
+};
+
+pub trait ItemDef: Serde + Send + Sync + 'static {}
+
+#[derive(Clone, PartialEq, Serde)]
+pub struct WeaponDef {
+    pub damage: u16,
+}
+impl ItemDef for WeaponDef {}
+
+#[derive(Clone, PartialEq, Serde)]
+pub struct ConsumableDef {
+    pub charges: u8,
+}
+impl ItemDef for ConsumableDef {}
+
+#[derive(Replicate)]
+pub struct Inventory<T: ItemDef> {
+    pub items: Property<Vec<T>>,
+}
+
+fn main() {
+    // both instantiations are registered separately, in one Protocol
+    let protocol = Protocol::builder()
+        .add_component::<Inventory<WeaponDef>>()
+        .add_component::<Inventory<ConsumableDef>>()
+        .build();
+    let component_kinds = protocol.component_kinds;
+    assert_ne!(
+        ComponentKind::of::<Inventory<WeaponDef>>(),
+        ComponentKind::of::<Inventory<ConsumableDef>>()
+    );
+
+    let weapons = Inventory::<WeaponDef>::new_complete(vec![WeaponDef { damage: 12 }]);
+    let consumables = Inventory::<ConsumableDef>::new_complete(vec![
+        ConsumableDef { charges: 3 },
+        ConsumableDef { charges: 1 },
+    ]);
+    assert_ne!(weapons.name(), consumables.name());
+
+    let mut writer = BitWriter::new();
+    weapons.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+    consumables.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let weapons = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Inventory<WeaponDef>>()
+        .unwrap();
+    assert_eq!(weapons.items[0].damage, 12);
+    let consumables = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Inventory<ConsumableDef>>()
+        .unwrap();
+    assert_eq!(consumables.items.len(), 2);
+}
@@ -0,0 +1,61 @@
+use naia_shared::{
+    BitReader, BitWriter, FakeEntityConverter, Message, MessageKind, Named, Protocol, Serde,
+};
+
+#[derive(Clone, PartialEq, Serde)]
+pub struct Chat {
+    pub text: String,
+}
+
+#[derive(Message)]
+pub struct Broadcast<T> {
+    pub payload: T,
+    pub hops: u8,
+}
+
+fn main() {
+    let protocol = Protocol::builder()
+        .add_message::<Broadcast<Chat>>()
+        .add_message::<Broadcast<u32>>()
+        .build();
+    let message_kinds = protocol.message_kinds;
+    assert_ne!(
+        MessageKind::of::<Broadcast<Chat>>(),
+        MessageKind::of::<Broadcast<u32>>()
+    );
+
+    let message = Broadcast {
+        payload: Chat {
+            text: "hello".to_string(),
+        },
+        hops: 2,
+    };
+    let name = message.name();
+    assert!(name.starts_with("Broadcast<") && name.ends_with("::Chat>"));
+
+    let mut writer = BitWriter::new();
+    message.write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+    Broadcast {
+        payload: 7u32,
+        hops: 1,
+    }
+    .write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let chat = message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Broadcast<Chat>>()
+        .unwrap();
+    assert_eq!(chat.payload.text, "hello");
+    assert_eq!(chat.hops, 2);
+    let number = message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Broadcast<u32>>()
+        .unwrap();
+    assert_eq!(number.payload, 7);
+}
@@ -25,10 +25,12 @@ impl Socket {
         server_addrs: ServerAddrs,
         config: SocketConfig,
         from_client_auth_sender: Option<
-            smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+            smol::channel::Sender<
+                Result<(SocketAddr, Vec<(String, String)>, Box<[u8]>), NaiaServerSocketError>,
+            >,
         >,
         to_session_all_auth_receiver: Option<
-            smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+            smol::channel::Receiver<(SocketAddr, Result<IdentityToken, u16>)>,
         >,
     ) -> Self {
         let (to_client_sender, to_client_receiver) = smol::channel::unbounded();
@@ -6,15 +6,21 @@ use super::error::NaiaServerSocketError;
 
 /// Used to receive Auth messages from the Server Socket
 pub trait AuthReceiver: AuthReceiverClone + Send + Sync {
-    /// Receives an Auth message from the Server Socket
-    fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, NaiaServerSocketError>;
+    /// Receives an Auth message from the Server Socket, along with the
+    /// headers of the session request which carried it
+    #[allow(clippy::type_complexity)]
+    fn receive(
+        &mut self,
+    ) -> Result<Option<(SocketAddr, &[(String, String)], &[u8])>, NaiaServerSocketError>;
 }
 
 /// Used to receive Auth messages from the Server Socket
 #[derive(Clone)]
 pub struct AuthReceiverImpl {
     #[allow(clippy::type_complexity)]
-    channel_receiver: Receiver<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+    channel_receiver:
+        Receiver<Result<(SocketAddr, Vec<(String, String)>, Box<[u8]>), NaiaServerSocketError>>,
+    last_headers: Vec<(String, String)>,
     last_payload: Option<Box<[u8]>>,
 }
 
@@ -22,22 +28,32 @@ impl AuthReceiverImpl {
     /// Creates a new AuthReceiver
     #[allow(clippy::type_complexity)]
     pub fn new(
-        channel_receiver: Receiver<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+        channel_receiver: Receiver<
+            Result<(SocketAddr, Vec<(String, String)>, Box<[u8]>), NaiaServerSocketError>,
+        >,
     ) -> Self {
         Self {
             channel_receiver,
+            last_headers: Vec::new(),
             last_payload: None,
         }
     }
 }
 
 impl AuthReceiver for AuthReceiverImpl {
-    fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, NaiaServerSocketError> {
+    fn receive(
+        &mut self,
+    ) -> Result<Option<(SocketAddr, &[(String, String)], &[u8])>, NaiaServerSocketError> {
         match self.channel_receiver.try_recv() {
             Ok(result) => match result {
-                Ok((address, payload)) => {
+                Ok((address, headers, payload)) => {
+                    self.last_headers = headers;
                     self.last_payload = Some(payload);
-                    return Ok(Some((address, self.last_payload.as_ref().unwrap())));
+                    return Ok(Some((
+                        address,
+                        &self.last_headers,
+                        self.last_payload.as_ref().unwrap(),
+                    )));
                 }
                 Err(_) => Ok(None),
             },
@@ -16,28 +16,35 @@ pub trait AuthSender: AuthSenderClone + Send + Sync {
     ) -> Result<(), NaiaServerSocketError>;
     /// Rejects an incoming connection from the Server Socket
     fn reject(&self, address: &SocketAddr) -> Result<(), NaiaServerSocketError>;
+    /// Rejects an incoming connection from the Server Socket, answering the
+    /// session request with the given HTTP status code
+    fn reject_with_status(
+        &self,
+        address: &SocketAddr,
+        status: u16,
+    ) -> Result<(), NaiaServerSocketError>;
 }
 
 // Impl
 /// Used to send Auth messages to the Server Socket
 #[derive(Clone)]
 pub struct AuthSenderImpl {
-    channel_sender: Sender<(SocketAddr, Option<IdentityToken>)>,
+    channel_sender: Sender<(SocketAddr, Result<IdentityToken, u16>)>,
 }
 
 impl AuthSenderImpl {
     /// Creates a new AuthSender
-    pub fn new(channel_sender: Sender<(SocketAddr, Option<IdentityToken>)>) -> Self {
+    pub fn new(channel_sender: Sender<(SocketAddr, Result<IdentityToken, u16>)>) -> Self {
         Self { channel_sender }
     }
 
     fn send(
         &self,
         address: &SocketAddr,
-        accept: Option<IdentityToken>,
+        answer: Result<IdentityToken, u16>,
     ) -> Result<(), NaiaServerSocketError> {
         self.channel_sender
-            .try_send((*address, accept))
+            .try_send((*address, answer))
             .map_err(|err| match err {
                 TrySendError::Full(_) => unreachable!("the channel is expected to be unbound"),
                 TrySendError::Closed(_) => NaiaServerSocketError::SendError(*address),
@@ -52,12 +59,22 @@ impl AuthSender for AuthSenderImpl {
         address: &SocketAddr,
         identity_token: &IdentityToken,
     ) -> Result<(), NaiaServerSocketError> {
-        self.send(address, Some(identity_token.clone()))
+        self.send(address, Ok(identity_token.clone()))
     }
 
     /// Rejects an incoming connection from the Server Socket
     fn reject(&self, address: &SocketAddr) -> Result<(), NaiaServerSocketError> {
-        self.send(address, None)
+        self.reject_with_status(address, 401)
+    }
+
+    /// Rejects an incoming connection from the Server Socket, answering the
+    /// session request with the given HTTP status code
+    fn reject_with_status(
+        &self,
+        address: &SocketAddr,
+        status: u16,
+    ) -> Result<(), NaiaServerSocketError> {
+        self.send(address, Err(status))
     }
 }
 
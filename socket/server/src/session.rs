@@ -30,10 +30,12 @@ pub fn start_session_server(
     config: SocketConfig,
     session_endpoint: SessionEndpoint,
     from_client_auth_sender: Option<
-        smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+        smol::channel::Sender<
+            Result<(SocketAddr, Vec<(String, String)>, Box<[u8]>), NaiaServerSocketError>,
+        >,
     >,
     to_session_all_auth_receiver: Option<
-        smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+        smol::channel::Receiver<(SocketAddr, Result<IdentityToken, u16>)>,
     >,
 ) {
     RTC_URL_POST_PATH
@@ -61,10 +63,12 @@ async fn listen(
     config: SocketConfig,
     session_endpoint: SessionEndpoint,
     from_client_auth_sender: Option<
-        smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+        smol::channel::Sender<
+            Result<(SocketAddr, Vec<(String, String)>, Box<[u8]>), NaiaServerSocketError>,
+        >,
     >,
     to_session_all_auth_receiver: Option<
-        smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+        smol::channel::Receiver<(SocketAddr, Result<IdentityToken, u16>)>,
     >,
 ) {
     let socket_address = server_addrs.session_listen_addr;
@@ -131,10 +135,10 @@ async fn listen(
 }
 
 async fn setup_auth_mux(
-    to_session_all_auth_receiver: smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+    to_session_all_auth_receiver: smol::channel::Receiver<(SocketAddr, Result<IdentityToken, u16>)>,
 ) -> smol::channel::Sender<(
     SocketAddr,
-    futures_channel::oneshot::Sender<Option<IdentityToken>>,
+    futures_channel::oneshot::Sender<Result<IdentityToken, u16>>,
 )> {
     let (sender_sender, sender_receiver) = smol::channel::unbounded();
 
@@ -162,13 +166,13 @@ async fn serve_auth_mux_in(
             HashMap<
                 SocketAddr,
                 (
-                    Option<futures_channel::oneshot::Sender<Option<IdentityToken>>>,
-                    Option<Option<IdentityToken>>,
+                    Option<futures_channel::oneshot::Sender<Result<IdentityToken, u16>>>,
+                    Option<Result<IdentityToken, u16>>,
                 ),
             >,
         >,
     >,
-    to_session_all_auth_receiver: smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+    to_session_all_auth_receiver: smol::channel::Receiver<(SocketAddr, Result<IdentityToken, u16>)>,
 ) {
     loop {
         let Ok((addr, answer)) = to_session_all_auth_receiver.recv().await else {
@@ -200,15 +204,15 @@ async fn serve_auth_mux_out(
             HashMap<
                 SocketAddr,
                 (
-                    Option<futures_channel::oneshot::Sender<Option<IdentityToken>>>,
-                    Option<Option<IdentityToken>>,
+                    Option<futures_channel::oneshot::Sender<Result<IdentityToken, u16>>>,
+                    Option<Result<IdentityToken, u16>>,
                 ),
             >,
         >,
     >,
     sender_receiver: smol::channel::Receiver<(
         SocketAddr,
-        futures_channel::oneshot::Sender<Option<IdentityToken>>,
+        futures_channel::oneshot::Sender<Result<IdentityToken, u16>>,
     )>,
 ) {
     loop {
@@ -242,10 +246,12 @@ async fn serve(
     mut session_endpoint: SessionEndpoint,
     mut stream: Arc<Async<TcpStream>>,
     from_client_auth_sender: Option<
-        smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+        smol::channel::Sender<
+            Result<(SocketAddr, Vec<(String, String)>, Box<[u8]>), NaiaServerSocketError>,
+        >,
     >,
     to_session_single_auth_receiver: Option<
-        futures_channel::oneshot::Receiver<Option<IdentityToken>>,
+        futures_channel::oneshot::Receiver<Result<IdentityToken, u16>>,
     >,
 ) {
     let remote_addr = stream
@@ -259,10 +265,12 @@ async fn serve(
     let mut headers_been_read: bool = false;
     let mut content_length: Option<usize> = None;
     let mut auth_string: Option<String> = None;
+    let mut auth_headers: Vec<(String, String)> = Vec::new();
     let mut rtc_url_matched = false;
     let mut is_options: bool = false;
     let mut body: Vec<u8> = Vec::new();
     let mut identity_token_opt = None;
+    let mut reject_status: u16 = 401;
 
     let buf_reader = BufReader::new(stream.clone());
     let mut bytes = buf_reader.bytes();
@@ -311,6 +319,8 @@ async fn serve(
                             success = true;
                             break;
                         }
+                    } else if let Some((name, value)) = str.split_once(':') {
+                        auth_headers.push((name.trim().to_string(), value.trim().to_string()));
                     } else {
                         // info!("read leftover line 1: {}", str);
                     }
@@ -340,6 +350,12 @@ async fn serve(
         // handle OPTIONS request
         if success && is_options {
             let mut resp = Response::<String>::new("".to_string());
+            // allow whichever headers the Client wishes to authenticate with
+            let allow_headers = auth_headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("access-control-request-headers"))
+                .and_then(|(_, value)| HeaderValue::from_str(value).ok())
+                .unwrap_or(HeaderValue::from_static("Authorization, Content-Length"));
             resp.headers_mut().insert(
                 header::ACCESS_CONTROL_ALLOW_ORIGIN,
                 HeaderValue::from_static("*"),
@@ -348,10 +364,8 @@ async fn serve(
                 header::ACCESS_CONTROL_ALLOW_METHODS,
                 HeaderValue::from_static("POST"),
             );
-            resp.headers_mut().insert(
-                header::ACCESS_CONTROL_ALLOW_HEADERS,
-                HeaderValue::from_static("Authorization, Content-Length"),
-            );
+            resp.headers_mut()
+                .insert(header::ACCESS_CONTROL_ALLOW_HEADERS, allow_headers);
             resp.headers_mut().insert(
                 header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
                 HeaderValue::from_static("true"),
@@ -380,7 +394,7 @@ async fn serve(
                     match base64::decode(&auth_string) {
                         Ok(decoded_bytes) => {
                             if from_client_auth_sender
-                                .send(Ok((remote_addr, decoded_bytes.into())))
+                                .send(Ok((remote_addr, auth_headers, decoded_bytes.into())))
                                 .await
                                 .is_err()
                             {
@@ -389,16 +403,19 @@ async fn serve(
                                 // info!("Sent auth bytes to server app");
 
                                 // wait for response from app
-                                if let Ok(identity_token_inner_opt) = to_session_auth_receiver.await
-                                {
-                                    if let Some(identity_token) = identity_token_inner_opt {
-                                        // info!("Server app accepted auth with identity token: {}", identity_token);
-                                        identity_token_opt = Some(identity_token);
-                                        success = true;
-                                    } else {
-                                        // warn!("Server app rejected auth");
-                                        identity_token_opt = None;
-                                        success = true;
+                                if let Ok(answer) = to_session_auth_receiver.await {
+                                    match answer {
+                                        Ok(identity_token) => {
+                                            // info!("Server app accepted auth with identity token: {}", identity_token);
+                                            identity_token_opt = Some(identity_token);
+                                            success = true;
+                                        }
+                                        Err(status) => {
+                                            // warn!("Server app rejected auth");
+                                            identity_token_opt = None;
+                                            reject_status = status;
+                                            success = true;
+                                        }
                                     }
                                 }
                             }
@@ -477,9 +494,9 @@ async fn serve(
             } else {
                 // Server rejected auth!
                 let response = Response::builder()
-                    .status(401)
+                    .status(reject_status)
                     .body("".to_string())
-                    .expect("could not build 401 response");
+                    .expect("could not build rejection response");
 
                 let mut out = response_header_to_vec(&response);
                 out.extend_from_slice(response.body().as_bytes());
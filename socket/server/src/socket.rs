@@ -80,10 +80,12 @@ impl Socket {
         server_addrs: &ServerAddrs,
         config: &SocketConfig,
         from_client_auth_sender: Option<
-            channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+            channel::Sender<
+                Result<(SocketAddr, Vec<(String, String)>, Box<[u8]>), NaiaServerSocketError>,
+            >,
         >,
         to_session_all_auth_receiver: Option<
-            channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+            channel::Receiver<(SocketAddr, Result<IdentityToken, u16>)>,
         >,
     ) -> (
         channel::Receiver<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
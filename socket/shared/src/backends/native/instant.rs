@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use crate::clock::thread_clock_now;
+
 /// Represents a specific moment in time
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Instant {
@@ -9,6 +11,9 @@ pub struct Instant {
 impl Instant {
     /// Creates an Instant from the moment the method is called
     pub fn now() -> Self {
+        if let Some(now) = thread_clock_now() {
+            return now;
+        }
         Self {
             inner: std::time::Instant::now(),
         }
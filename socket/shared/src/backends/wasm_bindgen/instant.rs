@@ -1,6 +1,8 @@
 use js_sys::Date;
 use std::{cmp::Ordering, time::Duration};
 
+use crate::clock::thread_clock_now;
+
 /// Represents a specific moment in time
 #[derive(Clone, PartialEq, PartialOrd)]
 pub struct Instant {
@@ -10,6 +12,9 @@ pub struct Instant {
 impl Instant {
     /// Creates an Instant from the moment the method is called
     pub fn now() -> Self {
+        if let Some(now) = thread_clock_now() {
+            return now;
+        }
         Instant { inner: Date::now() }
     }
 
@@ -0,0 +1,25 @@
+use std::{cell::RefCell, fmt::Debug, sync::Arc};
+
+use crate::Instant;
+
+/// A source of the current time. Installing one with `set_thread_clock()`
+/// replaces the system clock for every `Instant::now()` on that thread, which
+/// allows time to be simulated (i.e. advanced instantly in tests)
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time, according to this Clock
+    fn now(&self) -> Instant;
+}
+
+thread_local! {
+    static THREAD_CLOCK: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Installs a Clock which `Instant::now()` will read from on the current
+/// thread. Pass `None` to go back to the system clock.
+pub fn set_thread_clock(clock: Option<Arc<dyn Clock>>) {
+    THREAD_CLOCK.with(|thread_clock| *thread_clock.borrow_mut() = clock);
+}
+
+pub(crate) fn thread_clock_now() -> Option<Instant> {
+    THREAD_CLOCK.with(|thread_clock| thread_clock.borrow().as_ref().map(|clock| clock.now()))
+}
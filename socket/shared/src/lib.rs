@@ -17,6 +17,7 @@ extern crate cfg_if;
 pub mod link_condition_logic;
 
 mod backends;
+mod clock;
 mod identity_token;
 mod link_conditioner_config;
 mod socket_config;
@@ -24,6 +25,7 @@ mod time_queue;
 mod url_parse;
 
 pub use backends::{Instant, Random};
+pub use clock::{set_thread_clock, Clock};
 pub use identity_token::*;
 pub use link_conditioner_config::LinkConditionerConfig;
 pub use socket_config::SocketConfig;
@@ -0,0 +1,54 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use naia_shared::{Clock, Instant};
+
+/// A Clock which only moves forward when told to, so that tests can simulate
+/// seconds of network time without sleeping
+pub struct TestClock {
+    start: Instant,
+    elapsed_millis: AtomicU32,
+}
+
+impl TestClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed_millis: AtomicU32::new(0),
+        }
+    }
+
+    /// Moves the Clock forward by the given Duration, instantly
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed_millis
+            .fetch_add(duration.as_millis() as u32, Ordering::SeqCst);
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Instant {
+        let mut now = self.start.clone();
+        now.add_millis(self.elapsed_millis.load(Ordering::SeqCst));
+        now
+    }
+}
+
+impl fmt::Debug for TestClock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TestClock")
+            .field(
+                "elapsed_millis",
+                &self.elapsed_millis.load(Ordering::SeqCst),
+            )
+            .finish()
+    }
+}
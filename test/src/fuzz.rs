@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use naia_shared::{Message, Property, Protocol, Replicate};
+
+use crate::Auth;
+
+/// A Message whose payload can be large enough to be fragmented
+#[derive(Message)]
+pub struct FuzzBlob {
+    pub bytes: Vec<u8>,
+}
+
+impl FuzzBlob {
+    pub fn new(len: usize) -> Self {
+        Self {
+            bytes: (0..len).map(|i| i as u8).collect(),
+        }
+    }
+}
+
+#[derive(Replicate)]
+pub struct FuzzPosition {
+    pub x: Property<u8>,
+    pub label: Property<String>,
+}
+
+/// The Protocol which the fuzz targets parse packets against, and which the
+/// seed corpus in `fuzz/corpus` is recorded with
+pub fn fuzz_protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_message::<FuzzBlob>()
+        .add_component::<FuzzPosition>()
+        .build()
+}
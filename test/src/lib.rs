@@ -1,3 +1,11 @@
 mod auth;
+mod clock;
+mod fuzz;
+mod local_network;
+mod local_transport;
 
 pub use auth::Auth;
+pub use clock::TestClock;
+pub use fuzz::{fuzz_protocol, FuzzBlob, FuzzPosition};
+pub use local_network::{LocalClientSocket, LocalNetwork, LocalServerSocket};
+pub use local_transport::LocalTransport;
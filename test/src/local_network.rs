@@ -0,0 +1,476 @@
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use naia_client::transport::{self as client_transport, ServerAddr};
+use naia_server::transport::{self as server_transport, UserAuthAddr};
+use naia_shared::IdentityToken;
+
+// Client addresses are handed out from this port upwards
+const FIRST_CLIENT_PORT: u16 = 14200;
+
+/// An in-memory network between a single Server Socket and any number of
+/// Client Sockets, so that they can all run in one test process without the
+/// network. Every Client has its own address, which the Server sees as the
+/// source of that Client's packets.
+#[derive(Clone)]
+pub struct LocalNetwork {
+    hub: SharedHub,
+}
+
+impl LocalNetwork {
+    pub fn new() -> Self {
+        Self::with_first_client_port(FIRST_CLIENT_PORT)
+    }
+
+    /// Creates a network whose Client addresses are handed out from the
+    /// given port upwards, so that Clients of two networks listened on by
+    /// the same Server have distinct addresses
+    pub fn with_first_client_port(first_client_port: u16) -> Self {
+        let hub = Hub {
+            first_client_port,
+            ..Default::default()
+        };
+        Self {
+            hub: Arc::new(Mutex::new(hub)),
+        }
+    }
+
+    /// The Socket to give to `Server::listen()`
+    pub fn server_socket(&self) -> LocalServerSocket {
+        LocalServerSocket(self.hub.clone())
+    }
+
+    /// Adds a new Client to the network, returning the Socket to give to
+    /// `Client::connect()` and the address the Server will see it at
+    pub fn add_client(&self) -> (LocalClientSocket, SocketAddr) {
+        let mut hub = self.hub.lock().unwrap();
+        let address = hub.next_address();
+        let client_id = hub.clients.len();
+        hub.clients.push(LocalClient::new(address));
+        (
+            LocalClientSocket {
+                hub: self.hub.clone(),
+                client_id,
+            },
+            address,
+        )
+    }
+
+    /// Cuts the Client at the given address off from the network. Packets
+    /// sent to or from it, including any still in flight, are dropped
+    pub fn disconnect_client(&self, address: &SocketAddr) {
+        let mut hub = self.hub.lock().unwrap();
+        let Some(client) = hub.client_at(address) else {
+            panic!("no Client at address {}", address);
+        };
+        client.address = None;
+        client.to_client.clear();
+        hub.to_server
+            .retain(|(from_address, _)| from_address != address);
+    }
+
+    /// Moves the Client at the given address to a new address, as a NAT
+    /// rebinding would. Packets it sends from then on appear to come from
+    /// the new address, and packets sent to the old address are dropped
+    pub fn rebind_client(&self, old_address: &SocketAddr) -> SocketAddr {
+        let mut hub = self.hub.lock().unwrap();
+        let new_address = hub.next_address();
+        let Some(client) = hub.client_at(old_address) else {
+            panic!("no Client at address {}", old_address);
+        };
+        client.address = Some(new_address);
+        new_address
+    }
+
+    /// Drops every `drop_every`-th packet the Server sends to the Client at
+    /// the given address, simulating a lossy link. Set to None to stop
+    pub fn set_loss_to_client(&self, address: &SocketAddr, drop_every: Option<usize>) {
+        let mut hub = self.hub.lock().unwrap();
+        let Some(client) = hub.client_at(address) else {
+            panic!("no Client at address {}", address);
+        };
+        client.drop_every = drop_every;
+        client.sent_count = 0;
+    }
+
+    /// Flips a bit in every `corrupt_every`-th packet the Server sends to the
+    /// Client at the given address, simulating a noisy link. Set to None to
+    /// stop
+    pub fn set_corruption_to_client(&self, address: &SocketAddr, corrupt_every: Option<usize>) {
+        let mut hub = self.hub.lock().unwrap();
+        let Some(client) = hub.client_at(address) else {
+            panic!("no Client at address {}", address);
+        };
+        client.corrupt_every = corrupt_every;
+        client.sent_count = 0;
+    }
+
+    /// Every packet the Server Socket has read so far, in order
+    pub fn server_received(&self) -> Vec<Vec<u8>> {
+        self.hub.lock().unwrap().server_received.clone()
+    }
+
+    /// Every packet the Client currently at the given address has read so
+    /// far, in order
+    pub fn client_received(&self, address: &SocketAddr) -> Vec<Vec<u8>> {
+        let mut hub = self.hub.lock().unwrap();
+        let Some(client) = hub.client_at(address) else {
+            panic!("no Client at address {}", address);
+        };
+        client.received.clone()
+    }
+}
+
+impl Default for LocalNetwork {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn server_addr() -> SocketAddr {
+    "127.0.0.1:14192".parse().unwrap()
+}
+
+struct LocalClient {
+    // None once disconnected
+    address: Option<SocketAddr>,
+    identity_token: Option<IdentityToken>,
+    // the status the Server rejected the Client's auth request with
+    reject_status: Option<u16>,
+    to_client: VecDeque<Vec<u8>>,
+    received: Vec<Vec<u8>>,
+    drop_every: Option<usize>,
+    corrupt_every: Option<usize>,
+    sent_count: usize,
+}
+
+impl LocalClient {
+    fn new(address: SocketAddr) -> Self {
+        Self {
+            address: Some(address),
+            identity_token: None,
+            reject_status: None,
+            to_client: VecDeque::new(),
+            received: Vec::new(),
+            drop_every: None,
+            corrupt_every: None,
+            sent_count: 0,
+        }
+    }
+}
+
+// A Client's address, auth headers & auth bytes
+type AuthRequest = (SocketAddr, Vec<(String, String)>, Vec<u8>);
+
+#[derive(Default)]
+struct Hub {
+    first_client_port: u16,
+    next_port: u16,
+    clients: Vec<LocalClient>,
+    auths: VecDeque<AuthRequest>,
+    to_server: VecDeque<(SocketAddr, Vec<u8>)>,
+    server_received: Vec<Vec<u8>>,
+}
+
+impl Hub {
+    fn next_address(&mut self) -> SocketAddr {
+        let port = self.first_client_port + self.next_port;
+        self.next_port += 1;
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn client_at(&mut self, address: &SocketAddr) -> Option<&mut LocalClient> {
+        self.clients
+            .iter_mut()
+            .find(|client| client.address.as_ref() == Some(address))
+    }
+}
+
+type SharedHub = Arc<Mutex<Hub>>;
+
+// Server
+
+pub struct LocalServerSocket(SharedHub);
+
+impl From<LocalServerSocket> for Box<dyn server_transport::Socket> {
+    fn from(socket: LocalServerSocket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl server_transport::Socket for LocalServerSocket {
+    fn listen(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn server_transport::AuthSender>,
+        Box<dyn server_transport::AuthReceiver>,
+        Box<dyn server_transport::PacketSender>,
+        Box<dyn server_transport::PacketReceiver>,
+    ) {
+        (
+            Box::new(ServerEnd::new(&self.0)),
+            Box::new(ServerEnd::new(&self.0)),
+            Box::new(ServerEnd::new(&self.0)),
+            Box::new(ServerEnd::new(&self.0)),
+        )
+    }
+}
+
+// The Server's end of the network, used for every sender & receiver. Each
+// receiver owns the buffers its last payload is lent from
+#[derive(Clone)]
+struct ServerEnd {
+    hub: SharedHub,
+    headers: Vec<(String, String)>,
+    buffer: Vec<u8>,
+}
+
+impl ServerEnd {
+    fn new(hub: &SharedHub) -> Self {
+        Self {
+            hub: hub.clone(),
+            headers: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl server_transport::AuthSender for ServerEnd {
+    fn accept(
+        &self,
+        address: &UserAuthAddr,
+        identity_token: &IdentityToken,
+    ) -> Result<(), server_transport::SendError> {
+        if let Some(client) = self.hub.lock().unwrap().client_at(&address.addr()) {
+            client.identity_token = Some(identity_token.clone());
+        }
+        Ok(())
+    }
+
+    fn reject(
+        &self,
+        address: &UserAuthAddr,
+        status: u16,
+    ) -> Result<(), server_transport::SendError> {
+        if let Some(client) = self.hub.lock().unwrap().client_at(&address.addr()) {
+            client.reject_status = Some(status);
+        }
+        Ok(())
+    }
+}
+
+impl server_transport::AuthReceiver for ServerEnd {
+    #[allow(clippy::type_complexity)]
+    fn receive(
+        &mut self,
+    ) -> Result<Option<(UserAuthAddr, &[(String, String)], &[u8])>, server_transport::RecvError>
+    {
+        let Some((address, headers, auth_bytes)) = self.hub.lock().unwrap().auths.pop_front()
+        else {
+            return Ok(None);
+        };
+        self.headers = headers;
+        self.buffer = auth_bytes;
+        Ok(Some((
+            UserAuthAddr::new(address),
+            &self.headers,
+            &self.buffer,
+        )))
+    }
+}
+
+impl server_transport::PacketSender for ServerEnd {
+    fn send(
+        &self,
+        address: &SocketAddr,
+        payload: &[u8],
+    ) -> Result<(), server_transport::SendError> {
+        if let Some(client) = self.hub.lock().unwrap().client_at(address) {
+            client.sent_count += 1;
+            if let Some(drop_every) = client.drop_every {
+                if client.sent_count % drop_every == 0 {
+                    return Ok(());
+                }
+            }
+            let mut payload = payload.to_vec();
+            if let Some(corrupt_every) = client.corrupt_every {
+                if client.sent_count % corrupt_every == 0 && !payload.is_empty() {
+                    let middle = payload.len() / 2;
+                    payload[middle] ^= 1;
+                }
+            }
+            client.to_client.push_back(payload);
+        }
+        Ok(())
+    }
+}
+
+impl server_transport::PacketReceiver for ServerEnd {
+    fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, server_transport::RecvError> {
+        let mut hub = self.hub.lock().unwrap();
+        let Some((address, payload)) = hub.to_server.pop_front() else {
+            return Ok(None);
+        };
+        hub.server_received.push(payload.clone());
+        self.buffer = payload;
+        Ok(Some((address, &self.buffer)))
+    }
+}
+
+// Client
+
+#[derive(Clone)]
+pub struct LocalClientSocket {
+    hub: SharedHub,
+    client_id: usize,
+}
+
+impl From<LocalClientSocket> for Box<dyn client_transport::Socket> {
+    fn from(socket: LocalClientSocket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl LocalClientSocket {
+    fn open(
+        self,
+        auth_bytes: Option<Vec<u8>>,
+        auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn client_transport::IdentityReceiver>,
+        Box<dyn client_transport::PacketSender>,
+        Box<dyn client_transport::PacketReceiver>,
+    ) {
+        {
+            let mut hub = self.hub.lock().unwrap();
+            let client = &mut hub.clients[self.client_id];
+            client.identity_token = None;
+            client.reject_status = None;
+            if let Some(address) = client.address {
+                hub.auths
+                    .push_back((address, auth_headers, auth_bytes.unwrap_or_default()));
+            }
+        }
+        (
+            Box::new(ClientEnd::new(&self)),
+            Box::new(ClientEnd::new(&self)),
+            Box::new(ClientEnd::new(&self)),
+        )
+    }
+}
+
+impl client_transport::Socket for LocalClientSocket {
+    fn connect(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn client_transport::IdentityReceiver>,
+        Box<dyn client_transport::PacketSender>,
+        Box<dyn client_transport::PacketReceiver>,
+    ) {
+        self.open(None, Vec::new())
+    }
+
+    fn connect_with_auth(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+    ) -> (
+        Box<dyn client_transport::IdentityReceiver>,
+        Box<dyn client_transport::PacketSender>,
+        Box<dyn client_transport::PacketReceiver>,
+    ) {
+        self.open(Some(auth_bytes), Vec::new())
+    }
+
+    fn connect_with_auth_headers(
+        self: Box<Self>,
+        auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn client_transport::IdentityReceiver>,
+        Box<dyn client_transport::PacketSender>,
+        Box<dyn client_transport::PacketReceiver>,
+    ) {
+        self.open(None, auth_headers)
+    }
+
+    fn connect_with_auth_and_headers(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+        auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn client_transport::IdentityReceiver>,
+        Box<dyn client_transport::PacketSender>,
+        Box<dyn client_transport::PacketReceiver>,
+    ) {
+        self.open(Some(auth_bytes), auth_headers)
+    }
+}
+
+// A Client's end of the network, used for every sender & receiver. Each
+// receiver owns the buffer its last payload is lent from
+#[derive(Clone)]
+struct ClientEnd {
+    hub: SharedHub,
+    client_id: usize,
+    buffer: Vec<u8>,
+}
+
+impl ClientEnd {
+    fn new(socket: &LocalClientSocket) -> Self {
+        Self {
+            hub: socket.hub.clone(),
+            client_id: socket.client_id,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl client_transport::IdentityReceiver for ClientEnd {
+    fn receive(&mut self) -> client_transport::IdentityReceiverResult {
+        let hub = self.hub.lock().unwrap();
+        let client = &hub.clients[self.client_id];
+        if let Some(status) = client.reject_status {
+            return client_transport::IdentityReceiverResult::ErrorResponseCode(status);
+        }
+        match &client.identity_token {
+            Some(identity_token) => {
+                client_transport::IdentityReceiverResult::Success(identity_token.clone())
+            }
+            None => client_transport::IdentityReceiverResult::Waiting,
+        }
+    }
+}
+
+impl client_transport::PacketSender for ClientEnd {
+    fn send(&self, payload: &[u8]) -> Result<(), client_transport::SendError> {
+        let mut hub = self.hub.lock().unwrap();
+        // the Client's current address, so a rebind takes effect immediately
+        if let Some(address) = hub.clients[self.client_id].address {
+            hub.to_server.push_back((address, payload.to_vec()));
+        }
+        Ok(())
+    }
+
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Found(server_addr())
+    }
+}
+
+impl client_transport::PacketReceiver for ClientEnd {
+    fn receive(&mut self) -> Result<Option<&[u8]>, client_transport::RecvError> {
+        let mut hub = self.hub.lock().unwrap();
+        let client = &mut hub.clients[self.client_id];
+        let Some(payload) = client.to_client.pop_front() else {
+            return Ok(None);
+        };
+        client.received.push(payload.clone());
+        self.buffer = payload;
+        Ok(Some(&self.buffer))
+    }
+
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Found(server_addr())
+    }
+}
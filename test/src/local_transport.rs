@@ -0,0 +1,51 @@
+use std::net::SocketAddr;
+
+use crate::local_network::{LocalClientSocket, LocalNetwork, LocalServerSocket};
+
+/// An in-memory connection between a single Server Socket and a single Client
+/// Socket, so that both ends can run in one test process without the network.
+/// See `LocalNetwork` for more than one Client
+#[derive(Clone)]
+pub struct LocalTransport {
+    network: LocalNetwork,
+    client_socket: LocalClientSocket,
+    client_address: SocketAddr,
+}
+
+impl LocalTransport {
+    pub fn new() -> Self {
+        let network = LocalNetwork::new();
+        let (client_socket, client_address) = network.add_client();
+        Self {
+            network,
+            client_socket,
+            client_address,
+        }
+    }
+
+    /// The Socket to give to `Server::listen()`
+    pub fn server_socket(&self) -> LocalServerSocket {
+        self.network.server_socket()
+    }
+
+    /// The Socket to give to `Client::connect()`
+    pub fn client_socket(&self) -> LocalClientSocket {
+        self.client_socket.clone()
+    }
+
+    /// Every packet the Server Socket has read so far, in order
+    pub fn server_received(&self) -> Vec<Vec<u8>> {
+        self.network.server_received()
+    }
+
+    /// Every packet the Client Socket has read so far, in order
+    pub fn client_received(&self) -> Vec<Vec<u8>> {
+        self.network.client_received(&self.client_address)
+    }
+}
+
+impl Default for LocalTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
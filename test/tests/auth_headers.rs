@@ -0,0 +1,171 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, ErrorEvent, NaiaClientError, RejectEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::Protocol;
+use naia_test::{Auth, LocalNetwork};
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+// Accepts Clients whose "x-token" header is "valid", rejects the rest with the
+// given status
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    reject_status: u16,
+    auth_headers: Vec<Vec<(String, String)>>,
+    connected: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork, reject_status: u16) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            reject_status,
+            auth_headers: Vec::new(),
+            connected: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, auth) in events.read::<AuthEvent<Auth>>() {
+            assert_eq!(auth.username, "charlie");
+            let headers = self.server.user(&user_key).auth_headers().to_vec();
+            let valid = headers
+                .iter()
+                .any(|(name, value)| name == "x-token" && value == "valid");
+            self.auth_headers.push(headers);
+            if valid {
+                self.server.accept_connection(&user_key);
+            } else {
+                self.server
+                    .reject_with_status(&user_key, self.reject_status);
+            }
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    rejected: bool,
+    error_codes: Vec<u16>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork, token: &str) -> Self {
+        let (socket, _) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.auth_headers(vec![
+            ("x-token".to_string(), token.to_string()),
+            ("x-region".to_string(), "eu".to_string()),
+        ]);
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            rejected: false,
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        if events.read::<RejectEvent>().next().is_some() {
+            self.rejected = true;
+        }
+        for error in events.read::<ErrorEvent>() {
+            if let NaiaClientError::IdError(code) = error {
+                self.error_codes.push(code);
+            }
+        }
+    }
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    done: impl Fn(&TestServer, &TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        sleep(Duration::from_millis(5));
+        client.update();
+        server.update();
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn auth_headers_reach_the_server() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, 401);
+    let mut client = TestClient::new(&network, "valid");
+
+    update_until(&mut server, &mut client, |server, client| {
+        !server.connected.is_empty() && client.client.connection_status().is_connected()
+    });
+
+    assert_eq!(
+        server.auth_headers,
+        vec![vec![
+            ("x-token".to_string(), "valid".to_string()),
+            ("x-region".to_string(), "eu".to_string()),
+        ]]
+    );
+    let user_key = server.connected[0];
+    assert_eq!(server.server.user(&user_key).auth_headers().len(), 2);
+}
+
+#[test]
+fn rejecting_with_401_fires_a_reject_event() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, 401);
+    let mut client = TestClient::new(&network, "expired");
+
+    update_until(&mut server, &mut client, |_, client| client.rejected);
+
+    assert!(client.error_codes.is_empty());
+    assert!(server.connected.is_empty());
+    assert_eq!(server.server.users_count(), 0);
+}
+
+#[test]
+fn rejecting_with_another_status_reaches_the_client() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, 403);
+    let mut client = TestClient::new(&network, "expired");
+
+    update_until(&mut server, &mut client, |_, client| {
+        !client.error_codes.is_empty()
+    });
+
+    assert_eq!(client.error_codes, vec![403]);
+    assert!(!client.rejected);
+    assert!(server.connected.is_empty());
+}
@@ -0,0 +1,219 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, EntityAuthResetEvent, EntityAuthResetReason, ReplicationConfig, RoomKey, Server,
+    ServerConfig,
+};
+use naia_shared::{EntityAuthStatus, Property, Protocol, Replicate};
+use naia_test::{Auth, LocalNetwork};
+
+const IDLE_TIMEOUT: Duration = Duration::from_millis(300);
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    // a silenced Client neither receives nor sends anything
+    silenced: bool,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+        Self {
+            client,
+            world: World::default(),
+            silenced: false,
+        }
+    }
+
+    fn update(&mut self) {
+        if !self.silenced {
+            self.client.receive(self.world.proxy_mut());
+        }
+    }
+
+    fn entity(&self) -> Option<Entity> {
+        self.client.entities(&self.world.proxy()).first().copied()
+    }
+
+    fn authority(&self) -> Option<EntityAuthStatus> {
+        let entity = self.entity()?;
+        self.client.entity(self.world.proxy(), &entity).authority()
+    }
+
+    fn request_authority(&mut self) {
+        let entity = self.entity().unwrap();
+        self.client
+            .entity_mut(self.world.proxy_mut(), &entity)
+            .request_authority();
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    resets: Vec<(Entity, EntityAuthResetReason)>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(
+            ServerConfig {
+                auth_idle_timeout: Some(IDLE_TIMEOUT),
+                ..Default::default()
+            },
+            protocol(),
+        );
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+            resets: Vec::new(),
+        }
+    }
+
+    fn spawn_delegated(&mut self) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.world.proxy_mut())
+            .insert_component(Position::new_complete(7))
+            .configure_replication(ReplicationConfig::Delegated)
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        self.resets.extend(events.read::<EntityAuthResetEvent>());
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+// connects two Clients & grants the first authority over a delegated Entity
+fn first_client_holds_authority(server: &mut TestServer, clients: &mut [TestClient]) {
+    update_until(server, clients, |_, clients| {
+        clients
+            .iter()
+            .all(|client| client.authority() == Some(EntityAuthStatus::Available))
+    });
+    // let the Server finish enabling delegation for both Clients
+    for _ in 0..20 {
+        update(server, clients);
+    }
+
+    clients[0].request_authority();
+    update_until(server, clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+            && clients[1].authority() == Some(EntityAuthStatus::Denied)
+    });
+}
+
+#[test]
+fn silent_owner_loses_authority() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let entity = server.spawn_delegated();
+    let mut clients = vec![TestClient::new(&network), TestClient::new(&network)];
+    first_client_holds_authority(&mut server, &mut clients);
+    assert!(server.resets.is_empty());
+
+    clients[0].silenced = true;
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.server.entity_authority_status(&entity) == Some(EntityAuthStatus::Available)
+            && clients[1].authority() == Some(EntityAuthStatus::Available)
+    });
+    assert!(server.resets == vec![(entity, EntityAuthResetReason::IdleTimeout)]);
+
+    // another Client can then take authority
+    clients[1].request_authority();
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.server.entity_authority_status(&entity) == Some(EntityAuthStatus::Denied)
+            && clients[1].authority() == Some(EntityAuthStatus::Granted)
+    });
+}
+
+#[test]
+fn entity_can_opt_out_of_idle_timeout() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let entity = server.spawn_delegated();
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .set_auth_idle_timeout(None);
+    let mut clients = vec![TestClient::new(&network), TestClient::new(&network)];
+    first_client_holds_authority(&mut server, &mut clients);
+
+    clients[0].silenced = true;
+    let silent_for = IDLE_TIMEOUT * 3;
+    let started = std::time::Instant::now();
+    while started.elapsed() < silent_for {
+        update(&mut server, &mut clients);
+    }
+
+    assert!(server.resets.is_empty());
+    assert_eq!(
+        server.server.entity_authority_status(&entity),
+        Some(EntityAuthStatus::Denied)
+    );
+    assert_eq!(clients[1].authority(), Some(EntityAuthStatus::Denied));
+}
@@ -0,0 +1,226 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ReplicationConfig, RoomKey, Server, ServerConfig, UserKey};
+use naia_shared::{EntityAuthStatus, Property, Protocol, Replicate, WorldMutType, WorldRefType};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+        }
+    }
+
+    fn update(&mut self) {
+        self.client.receive(self.world.proxy_mut());
+    }
+
+    fn entity(&self) -> Option<Entity> {
+        self.client.entities(&self.world.proxy()).first().copied()
+    }
+
+    fn authority(&self) -> Option<EntityAuthStatus> {
+        let entity = self.entity()?;
+        self.client.entity(self.world.proxy(), &entity).authority()
+    }
+
+    fn request_authority(&mut self) {
+        let entity = self.entity().unwrap();
+        self.client
+            .entity_mut(self.world.proxy_mut(), &entity)
+            .request_authority();
+    }
+
+    fn set_x(&mut self, x: u8) {
+        let entity = self.entity().unwrap();
+        let mut world = self.world.proxy_mut();
+        let mut position = world.component_mut::<Position>(&entity).unwrap();
+        *position.x = x;
+    }
+
+    fn release_authority(&mut self) {
+        let entity = self.entity().unwrap();
+        self.client
+            .entity_mut(self.world.proxy_mut(), &entity)
+            .release_authority();
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+        }
+    }
+
+    fn spawn_delegated(&mut self) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.world.proxy_mut())
+            .insert_component(Position::new_complete(7))
+            .configure_replication(ReplicationConfig::Delegated)
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .server
+            .user_keys()
+            .into_iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn authority_moves_between_clients() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let entity = server.spawn_delegated();
+    let mut clients = vec![TestClient::new(&network), TestClient::new(&network)];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients
+            .iter()
+            .all(|client| client.authority() == Some(EntityAuthStatus::Available))
+    });
+    // let the Server finish enabling delegation for both Clients
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+
+    clients[0].request_authority();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+            && clients[1].authority() == Some(EntityAuthStatus::Denied)
+    });
+
+    let from_user = server.user_key(&clients[0]);
+    let to_user = server.user_key(&clients[1]);
+    // only the holder of authority can hand it over
+    assert!(server
+        .server
+        .transfer_authority(&to_user, &from_user, &entity)
+        .is_err());
+    assert!(server
+        .server
+        .transfer_authority(&from_user, &to_user, &entity)
+        .is_ok());
+
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Denied)
+            && clients[1].authority() == Some(EntityAuthStatus::Granted)
+    });
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+    assert_eq!(clients[0].authority(), Some(EntityAuthStatus::Denied));
+    assert_eq!(clients[1].authority(), Some(EntityAuthStatus::Granted));
+    assert_eq!(
+        server.server.entity_authority_status(&entity),
+        Some(EntityAuthStatus::Denied)
+    );
+
+    // the Server accepts updates from the new owner
+    clients[1].set_x(42);
+    update_until(&mut server, &mut clients, |server, _| {
+        let world = server.world.proxy();
+        world
+            .component::<Position>(&entity)
+            .is_some_and(|position| *position.x == 42)
+    });
+
+    // the new owner can hand authority back over as usual
+    clients[1].release_authority();
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.server.entity_authority_status(&entity) == Some(EntityAuthStatus::Available)
+            && clients
+                .iter()
+                .all(|client| client.authority() == Some(EntityAuthStatus::Available))
+    });
+}
@@ -0,0 +1,285 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+
+use naia_client::{AuthDeniedEvent, AuthDeniedReason, Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ReplicationConfig, RoomKey, Server, ServerConfig};
+use naia_shared::{
+    EntityAuthStatus, Property, PropertyMutateError, Protocol, Replicate, WorldMutType,
+    WorldRefType,
+};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    dropped: Vec<(Entity, AuthDeniedReason)>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork, authority_write_timeout: Duration) -> Self {
+        let (socket, _): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                authority_write_timeout,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            dropped: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        self.dropped.extend(events.read::<AuthDeniedEvent>());
+        self.client.run_authority_writes(&mut self.world);
+    }
+
+    fn entity(&self) -> Option<Entity> {
+        self.client.entities(&self.world.proxy()).first().copied()
+    }
+
+    fn authority(&self) -> Option<EntityAuthStatus> {
+        let entity = self.entity()?;
+        self.client.entity(self.world.proxy(), &entity).authority()
+    }
+
+    // queues a write of `x`, setting `ran` once it runs
+    fn request_authority_then_set_x(&mut self, x: u8, ran: &Arc<AtomicBool>) {
+        let entity = self.entity().unwrap();
+        let ran = ran.clone();
+        self.client
+            .request_authority_then(&entity, move |world: &mut World| {
+                *world
+                    .proxy_mut()
+                    .component_mut::<Position>(&entity)
+                    .unwrap()
+                    .x = x;
+                ran.store(true, Ordering::SeqCst);
+            });
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    entity: Entity,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        let mut world = World::default();
+        let entity = server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Position::new_complete(1))
+            .configure_replication(ReplicationConfig::Delegated)
+            .id();
+        server.room_mut(&room_key).add_entity(&entity);
+        Self {
+            server,
+            world,
+            room_key,
+            entity,
+        }
+    }
+
+    fn x(&self) -> u8 {
+        *self
+            .world
+            .proxy()
+            .component::<Position>(&self.entity)
+            .unwrap()
+            .x
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn queued_write_runs_once_authority_is_granted() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(&network, Duration::from_secs(5))];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Available)
+    });
+
+    // without Authority, writing is refused rather than panicking
+    let entity = clients[0].entity().unwrap();
+    assert!(!clients[0].client.can_mutate(&entity));
+    {
+        let mut world = clients[0].world.proxy_mut();
+        let mut position = world.component_mut::<Position>(&entity).unwrap();
+        assert!(!position.x.can_mutate());
+        assert_eq!(
+            position.x.try_set(5),
+            Err(PropertyMutateError::MissingAuthority(
+                EntityAuthStatus::Available
+            ))
+        );
+    }
+
+    // the write waits for the grant, then reaches the Server
+    let ran = Arc::new(AtomicBool::new(false));
+    clients[0].request_authority_then_set_x(9, &ran);
+    assert!(!ran.load(Ordering::SeqCst));
+    update_until(&mut server, &mut clients, |server, _| server.x() == 9);
+    assert!(ran.load(Ordering::SeqCst));
+    assert_eq!(clients[0].authority(), Some(EntityAuthStatus::Granted));
+    assert!(clients[0].client.can_mutate(&entity));
+    assert!(clients[0].dropped.is_empty());
+
+    // once held, writes run on the next call without waiting
+    let ran = Arc::new(AtomicBool::new(false));
+    clients[0].request_authority_then_set_x(4, &ran);
+    update_until(&mut server, &mut clients, |server, _| server.x() == 4);
+    assert!(ran.load(Ordering::SeqCst));
+}
+
+#[test]
+fn queued_write_is_dropped_when_authority_is_denied() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![
+        TestClient::new(&network, Duration::from_secs(5)),
+        TestClient::new(&network, Duration::from_secs(5)),
+    ];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients
+            .iter()
+            .all(|client| client.authority() == Some(EntityAuthStatus::Available))
+    });
+
+    // the first player takes Authority before the rival hears of it
+    let entity = clients[0].entity().unwrap();
+    let client = &mut clients[0];
+    client
+        .client
+        .entity_mut(client.world.proxy_mut(), &entity)
+        .request_authority();
+    update_until(&mut server, &mut clients[..1], |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+    });
+
+    // so the rival's request is denied, & its write never runs
+    let ran = Arc::new(AtomicBool::new(false));
+    clients[1].request_authority_then_set_x(5, &ran);
+    update_until(&mut server, &mut clients, |_, clients| {
+        !clients[1].dropped.is_empty()
+    });
+    let rival_entity = clients[1].entity().unwrap();
+    assert_eq!(
+        clients[1].dropped,
+        vec![(rival_entity, AuthDeniedReason::Denied)]
+    );
+    assert_eq!(clients[1].authority(), Some(EntityAuthStatus::Denied));
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+    assert!(!ran.load(Ordering::SeqCst));
+    assert_eq!(server.x(), 1);
+
+    // & while Denied, writes are dropped right away
+    clients[1].request_authority_then_set_x(6, &ran);
+    update(&mut server, &mut clients);
+    assert_eq!(clients[1].dropped.len(), 2);
+    assert!(!ran.load(Ordering::SeqCst));
+}
+
+#[test]
+fn queued_write_is_dropped_when_authority_times_out() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    // far too short for the grant to make it back in time
+    let mut clients = vec![TestClient::new(&network, Duration::from_millis(1))];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Available)
+    });
+
+    let ran = Arc::new(AtomicBool::new(false));
+    clients[0].request_authority_then_set_x(7, &ran);
+    update_until(&mut server, &mut clients, |_, clients| {
+        !clients[0].dropped.is_empty()
+    });
+    let entity = clients[0].entity().unwrap();
+    assert_eq!(
+        clients[0].dropped,
+        vec![(entity, AuthDeniedReason::TimedOut)]
+    );
+
+    // the request itself still goes through, without the write
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+    });
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+    assert!(!ran.load(Ordering::SeqCst));
+    assert_eq!(server.x(), 1);
+}
@@ -0,0 +1,70 @@
+use naia_shared::{BigMap, BigMapKey, BitReader, BitWriter, Serde};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct RoomKey(u64);
+
+impl BigMapKey for RoomKey {
+    fn to_u64(&self) -> u64 {
+        self.0
+    }
+
+    fn from_u64(value: u64) -> Self {
+        RoomKey(value)
+    }
+}
+
+#[test]
+fn read_write_big_map() {
+    let mut in_map = BigMap::<RoomKey, u32>::new();
+    let keys: Vec<RoomKey> = (0..40).map(|value| in_map.insert(value * 3)).collect();
+    for key in keys.iter().step_by(3) {
+        in_map.remove(key);
+    }
+
+    // Write
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    in_map.ser(&mut writer);
+    assert_eq!(in_map.bit_length(), bits_free - writer.bits_free());
+    let buffer = writer.to_bytes();
+
+    // Read
+    let mut reader = BitReader::new(&buffer);
+    let mut out_map = BigMap::<RoomKey, u32>::de(&mut reader).unwrap();
+
+    assert!(in_map == out_map);
+    assert_eq!(out_map.len(), in_map.len());
+    for (key, value) in in_map.iter_entries() {
+        assert_eq!(out_map.get(&key), Some(value));
+    }
+
+    // keys keep being issued where the original map left off
+    assert_eq!(out_map.insert(7), RoomKey(40));
+}
+
+#[test]
+fn iter_entries_is_in_key_order() {
+    let mut map = BigMap::<RoomKey, u32>::new();
+    for value in 0..100 {
+        map.insert(value);
+    }
+
+    let keys: Vec<RoomKey> = map.iter_entries().map(|(key, _)| key).collect();
+    assert_eq!(keys, (0..100).map(RoomKey).collect::<Vec<RoomKey>>());
+}
+
+#[test]
+fn key_range_survives_round_trip() {
+    let mut in_map = BigMap::<RoomKey, u32>::with_key_range(5, 4);
+    assert_eq!(in_map.insert(1), RoomKey(5));
+    assert_eq!(in_map.insert(2), RoomKey(9));
+
+    let mut writer = BitWriter::new();
+    in_map.ser(&mut writer);
+    let buffer = writer.to_bytes();
+
+    let mut reader = BitReader::new(&buffer);
+    let mut out_map = BigMap::<RoomKey, u32>::de(&mut reader).unwrap();
+
+    assert_eq!(out_map.insert(3), RoomKey(13));
+}
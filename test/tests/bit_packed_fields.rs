@@ -0,0 +1,91 @@
+use naia_shared::{
+    BitReader, BitWriter, DiffMask, FakeEntityConverter, Property, Replicate, Serde,
+};
+
+#[derive(Serde, Clone, PartialEq, Debug)]
+pub struct Status {
+    #[bits(10)]
+    pub health: u16,
+    #[bits(6)]
+    pub offset: i8,
+    pub alive: bool,
+}
+
+#[derive(Replicate)]
+pub struct Vitals {
+    #[bits(10)]
+    pub health: Property<u16>,
+    #[bits(6)]
+    pub offset: Property<i8>,
+}
+
+// writes a value, returning the bytes & how many bits it took
+fn write(status: &Status) -> (Box<[u8]>, u32) {
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    status.ser(&mut writer);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+fn write_fields(component: &dyn Replicate) -> (Box<[u8]>, u32) {
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    component.write_fields(&mut writer, &mut FakeEntityConverter);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+#[test]
+fn serde_fields_take_exactly_their_bits() {
+    let status = Status {
+        health: 1023,
+        offset: -31,
+        alive: true,
+    };
+    let (bytes, bits_written) = write(&status);
+    assert_eq!(bits_written, 10 + 6 + 1);
+    assert_eq!(status.bit_length(), 10 + 6 + 1);
+
+    let mut reader = BitReader::new(&bytes);
+    assert_eq!(Status::de(&mut reader).unwrap(), status);
+}
+
+#[test]
+#[should_panic(expected = "with 10 bits")]
+fn serde_fields_reject_values_too_wide_for_their_bits() {
+    write(&Status {
+        health: 1024,
+        offset: 0,
+        alive: true,
+    });
+}
+
+#[test]
+fn replicated_fields_take_exactly_their_bits() {
+    let vitals = Vitals::new_complete(700, -5);
+    let (bytes, bits_written) = write_fields(&vitals);
+    assert_eq!(bits_written, 10 + 6);
+
+    let mut reader = BitReader::new(&bytes);
+    let remote = Vitals::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap();
+    let remote = remote.to_boxed_any().downcast::<Vitals>().unwrap();
+    assert_eq!(*remote.health, 700);
+    assert_eq!(*remote.offset, -5);
+
+    // updates are bit-packed too, after a bit per field for whether it changed
+    let mut diff_mask = DiffMask::new(vitals.diff_mask_size());
+    diff_mask.set_bit(0, true);
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    vitals.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+    assert_eq!(bits_free - writer.bits_free(), 2 + 10);
+}
+
+#[test]
+#[should_panic(expected = "with 10 bits")]
+fn replicated_fields_reject_values_too_wide_for_their_bits() {
+    write_fields(&Vitals::new_complete(1024, 0));
+}
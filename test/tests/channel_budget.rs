@@ -0,0 +1,151 @@
+use naia_shared::{
+    default_channels::{OrderedReliableChannel, UnorderedReliableChannel},
+    BitWriter, ChannelKind, FakeEntityConverter, HostType, Instant, MessageContainer,
+    MessageManager, Protocol,
+};
+use naia_test::Auth;
+
+// a Channel writing more than this has fit at least one message in, beyond
+// the few bits of its header
+const MESSAGE_BITS: u32 = 64;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+fn queue_backlog(
+    protocol: &Protocol,
+    message_manager: &mut MessageManager,
+    channel_kind: &ChannelKind,
+) {
+    let mut converter = FakeEntityConverter;
+    for _ in 0..200 {
+        let message =
+            MessageContainer::from_write(Box::new(Auth::new("charlie", "12345")), &mut converter);
+        message_manager.send_message(
+            &protocol.message_kinds,
+            &mut converter,
+            channel_kind,
+            message,
+        );
+    }
+}
+
+// writes a single packet, returning the bits each Channel wrote into it
+fn write_packet(
+    protocol: &Protocol,
+    message_manager: &mut MessageManager,
+    packet_index: u16,
+    channel_kinds: &[ChannelKind],
+) -> Vec<u32> {
+    let mut converter = FakeEntityConverter;
+    message_manager.collect_outgoing_messages(&Instant::now(), &100.0);
+    let mut writer = BitWriter::new();
+    let mut has_written = false;
+    message_manager.write_messages(
+        protocol,
+        &mut converter,
+        &mut writer,
+        packet_index,
+        &mut has_written,
+    );
+    let written_bits = message_manager.take_written_channel_bits().unwrap();
+    channel_kinds
+        .iter()
+        .map(|channel_kind| written_bits.get(channel_kind).copied().unwrap_or(0))
+        .collect()
+}
+
+#[test]
+fn backlogged_channels_share_every_packet() {
+    let protocol = protocol();
+    let channel_kinds = [
+        ChannelKind::of::<UnorderedReliableChannel>(),
+        ChannelKind::of::<OrderedReliableChannel>(),
+    ];
+
+    // without a budget, whichever Channel is written first takes the packet
+    let mut message_manager = MessageManager::new(HostType::Server, &protocol.channel_kinds, None);
+    message_manager.enable_written_bits_tally();
+    for channel_kind in &channel_kinds {
+        queue_backlog(&protocol, &mut message_manager, channel_kind);
+    }
+    let written_bits = write_packet(&protocol, &mut message_manager, 0, &channel_kinds);
+    assert!(!written_bits.iter().all(|bits| *bits > MESSAGE_BITS));
+
+    let mut message_manager =
+        MessageManager::new(HostType::Server, &protocol.channel_kinds, Some(128));
+    message_manager.enable_written_bits_tally();
+    for channel_kind in &channel_kinds {
+        queue_backlog(&protocol, &mut message_manager, channel_kind);
+    }
+    for packet_index in 0..10 {
+        let written_bits = write_packet(
+            &protocol,
+            &mut message_manager,
+            packet_index,
+            &channel_kinds,
+        );
+        assert!(written_bits.iter().all(|bits| *bits > MESSAGE_BITS));
+    }
+}
+
+#[test]
+fn single_channel_fills_packet_despite_budget() {
+    let protocol = protocol();
+    let channel_kinds = [ChannelKind::of::<UnorderedReliableChannel>()];
+
+    let mut written = Vec::new();
+    for budget in [None, Some(128)] {
+        let mut message_manager =
+            MessageManager::new(HostType::Server, &protocol.channel_kinds, budget);
+        message_manager.enable_written_bits_tally();
+        queue_backlog(&protocol, &mut message_manager, &channel_kinds[0]);
+        written.push(write_packet(&protocol, &mut message_manager, 0, &channel_kinds)[0]);
+    }
+    assert!(written[0] > 128 * 8);
+    assert_eq!(written[0], written[1]);
+}
+
+#[test]
+fn message_larger_than_budget_waits_for_its_turn() {
+    let protocol = protocol();
+    let channel_kinds = [
+        ChannelKind::of::<UnorderedReliableChannel>(),
+        ChannelKind::of::<OrderedReliableChannel>(),
+    ];
+
+    let mut message_manager =
+        MessageManager::new(HostType::Server, &protocol.channel_kinds, Some(128));
+    message_manager.enable_written_bits_tally();
+    let mut converter = FakeEntityConverter;
+    for channel_kind in &channel_kinds {
+        let username = "c".repeat(300);
+        let message =
+            MessageContainer::from_write(Box::new(Auth::new(&username, "12345")), &mut converter);
+        message_manager.send_message(
+            &protocol.message_kinds,
+            &mut converter,
+            channel_kind,
+            message,
+        );
+    }
+
+    // each message only fits once its Channel is last, & has the rest of the packet
+    let mut written_bits = vec![0; channel_kinds.len()];
+    for packet_index in 0..2 {
+        let packet_bits = write_packet(
+            &protocol,
+            &mut message_manager,
+            packet_index,
+            &channel_kinds,
+        );
+        for (total, bits) in written_bits.iter_mut().zip(packet_bits) {
+            *total += bits;
+        }
+    }
+    assert!(written_bits.iter().all(|bits| *bits > 300 * 8));
+}
@@ -0,0 +1,270 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, TimedMessageEvent as ClientTimedMessageEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, ConnectEvent, Server, ServerConfig, TimedMessageEvent, TimedRequestEvent, UserKey,
+};
+use naia_shared::{
+    default_channels::OrderedReliableChannel, Channel, ChannelDirection, ChannelMode,
+    ChannelSettings, GameInstant, Message, Protocol, ReliableSettings, Request, Response,
+    TickBufferSettings,
+};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Channel)]
+pub struct TrackedChannel;
+
+#[derive(Message)]
+pub struct Ping {
+    pub value: u8,
+}
+
+#[derive(Message)]
+pub struct Question {
+    pub value: u8,
+}
+
+impl Request for Question {
+    type Response = Answer;
+}
+
+#[derive(Message)]
+pub struct Answer {
+    pub value: u8,
+}
+
+impl Response for Answer {}
+
+fn protocol(latency_tracking: bool) -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_channel_with_settings::<TrackedChannel>(
+            ChannelSettings::new(
+                ChannelMode::OrderedReliable(ReliableSettings::default()),
+                ChannelDirection::Bidirectional,
+            )
+            .with_latency_tracking(latency_tracking),
+        )
+        .add_message::<Auth>()
+        .add_message::<Ping>()
+        .add_request::<Question>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_key: Option<UserKey>,
+    tracked: Vec<Option<GameInstant>>,
+    untracked: Vec<Option<GameInstant>>,
+    requests: Vec<Option<GameInstant>>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol(true));
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            user_key: None,
+            tracked: Vec::new(),
+            untracked: Vec::new(),
+            requests: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.user_key = Some(user_key);
+        }
+        for (_, send_instant, _) in events.read::<TimedMessageEvent<TrackedChannel, Ping>>() {
+            self.tracked.push(send_instant);
+        }
+        for (_, send_instant, _) in events.read::<TimedMessageEvent<OrderedReliableChannel, Ping>>()
+        {
+            self.untracked.push(send_instant);
+        }
+        for (_, response_key, send_instant, question) in
+            events.read::<TimedRequestEvent<TrackedChannel, Question>>()
+        {
+            self.requests.push(send_instant);
+            self.server.send_response(
+                &response_key,
+                &Answer {
+                    value: question.value,
+                },
+            );
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    tracked: Vec<Option<GameInstant>>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, _) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(true),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            tracked: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        for (send_instant, _) in events.read::<ClientTimedMessageEvent<TrackedChannel, Ping>>() {
+            self.tracked.push(send_instant);
+        }
+    }
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    mut done: impl FnMut(&mut TestServer, &mut TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        sleep(Duration::from_millis(5));
+        client.update();
+        server.update();
+    }
+    panic!("timed out");
+}
+
+fn connect(network: &LocalNetwork) -> (TestServer, TestClient) {
+    let mut server = TestServer::new(network);
+    let mut client = TestClient::new(network);
+    update_until(&mut server, &mut client, |server, client| {
+        server.user_key.is_some() && client.client.connection_status().is_connected()
+    });
+    (server, client)
+}
+
+// A send instant taken on the other end's clock should be a little in the past
+fn assert_recent(send_instant: GameInstant, now: GameInstant) {
+    let latency_ms = send_instant.offset_from(&now);
+    assert!(
+        (-50..1000).contains(&latency_ms),
+        "latency of {}ms",
+        latency_ms
+    );
+}
+
+#[test]
+fn client_messages_carry_send_instants_on_tracked_channels_only() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+
+    client
+        .client
+        .send_message::<TrackedChannel, Ping>(&Ping { value: 1 });
+    client
+        .client
+        .send_message::<OrderedReliableChannel, Ping>(&Ping { value: 2 });
+    update_until(&mut server, &mut client, |server, _| {
+        !server.tracked.is_empty() && !server.untracked.is_empty()
+    });
+
+    let Some(send_instant) = server.tracked[0] else {
+        panic!("tracked Message has no send instant");
+    };
+    assert_recent(send_instant, server.server.game_time_now());
+    assert_eq!(server.untracked[0], None);
+
+    let Some(stats) = server.server.channel_latency::<TrackedChannel>() else {
+        panic!("no latency stats for the tracked Channel");
+    };
+    assert_eq!(stats.samples, 1);
+    assert!(stats.p50_ms <= stats.max_ms);
+    assert_eq!(
+        server.server.channel_latency::<OrderedReliableChannel>(),
+        None
+    );
+}
+
+#[test]
+fn server_messages_carry_send_instants() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+    let user_key = server.user_key.unwrap();
+
+    for value in 0..3 {
+        server
+            .server
+            .send_message::<TrackedChannel, Ping>(&user_key, &Ping { value });
+    }
+    update_until(&mut server, &mut client, |_, client| {
+        client.tracked.len() == 3
+    });
+
+    // on the Client, the send instant is given on the Client's own clock
+    let now = client.client.game_time_now().unwrap();
+    for send_instant in &client.tracked {
+        assert_recent(send_instant.unwrap(), now);
+    }
+    let stats = client.client.channel_latency::<TrackedChannel>().unwrap();
+    assert_eq!(stats.samples, 3);
+}
+
+#[test]
+fn requests_carry_send_instants() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+
+    let response_key = client
+        .client
+        .send_request::<TrackedChannel, Question>(&Question { value: 4 })
+        .unwrap();
+    let mut answer: Option<Answer> = None;
+    update_until(&mut server, &mut client, |_, client| {
+        answer = client.client.receive_response(&response_key);
+        answer.is_some()
+    });
+    assert_eq!(answer.unwrap().value, 4);
+
+    assert_eq!(server.requests.len(), 1);
+    assert_recent(server.requests[0].unwrap(), server.server.game_time_now());
+}
+
+#[test]
+fn latency_tracking_is_part_of_the_schema() {
+    assert_ne!(
+        protocol(true).schema_digest(),
+        protocol(false).schema_digest()
+    );
+}
+
+#[test]
+#[should_panic]
+fn tick_buffered_channels_cannot_track_latency() {
+    let _ = ChannelSettings::new(
+        ChannelMode::TickBuffered(TickBufferSettings::default()),
+        ChannelDirection::ClientToServer,
+    )
+    .with_latency_tracking(true);
+}
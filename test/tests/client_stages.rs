@@ -0,0 +1,153 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{default_channels::UnorderedReliableChannel, Property, Protocol, Replicate};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    // packets the Server has read from its Socket
+    received: Arc<AtomicUsize>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let received = Arc::new(AtomicUsize::new(0));
+        let counter = received.clone();
+        server.set_incoming_tap(Some(Box::new(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })));
+        let room_key = server.make_room().key();
+        let mut world = World::default();
+        let entity = server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Position::new_complete(7))
+            .id();
+        server.room_mut(&room_key).add_entity(&entity);
+        Self {
+            server,
+            world,
+            room_key,
+            received,
+        }
+    }
+
+    fn received(&self) -> usize {
+        self.received.load(Ordering::SeqCst)
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn new_client(network: &LocalNetwork) -> Client<Entity> {
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    client
+}
+
+#[test]
+fn nothing_is_sent_until_flushed() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut client = new_client(&network);
+    let mut world = World::default();
+
+    for _ in 0..400 {
+        if client.connection_status().is_connected() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        client.receive(world.proxy_mut());
+        server.update();
+    }
+    assert!(client.connection_status().is_connected());
+
+    // several ticks pass with a Message waiting, so a packet is due
+    client.send_message::<UnorderedReliableChannel, Auth>(&Auth::new("charlie", "12345"));
+    let received = server.received();
+    for _ in 0..5 {
+        sleep(Duration::from_millis(15));
+        client.receive_packets();
+        client.process_incoming(world.proxy_mut());
+        client.queue_outgoing(world.proxy());
+        server.update();
+    }
+    assert_eq!(server.received(), received);
+
+    client.flush_packets();
+    server.update();
+    assert!(server.received() > received);
+}
+
+#[test]
+fn stages_at_different_rates_replicate_entities() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut client = new_client(&network);
+    let mut world = World::default();
+
+    for frame in 0..400 {
+        if !client.entities(&world.proxy()).is_empty() {
+            return;
+        }
+        sleep(Duration::from_millis(5));
+
+        // the Socket is drained every frame, but the World is only touched
+        // every other frame, and packets are flushed every third frame
+        client.receive_packets();
+        if frame % 2 == 0 {
+            client.process_incoming(world.proxy_mut());
+            client.queue_outgoing(world.proxy());
+        }
+        if frame % 3 == 0 {
+            client.flush_packets();
+        }
+        server.update();
+    }
+    panic!("timed out");
+}
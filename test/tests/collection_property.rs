@@ -0,0 +1,263 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use naia_shared::{
+    BitReader, BitWriter, ComponentUpdate, DiffMask, FakeEntityConverter, FileBitWriter,
+    MapProperty, Property, PropertyMutate, PropertyMutator, Protocol, Replicate, Serde,
+    VecProperty,
+};
+
+#[derive(Serde, Clone, PartialEq, Debug)]
+pub struct ItemStack {
+    pub item: u16,
+    pub count: u8,
+}
+
+#[derive(Replicate)]
+pub struct Inventory {
+    pub gold: Property<u32>,
+    pub slots: MapProperty<u16, ItemStack>,
+    pub hotbar: VecProperty<u16>,
+}
+
+// records which DiffMask bits were mutated
+#[derive(Clone, Default)]
+struct RecordingMutator {
+    mutated: Arc<Mutex<Vec<u8>>>,
+}
+
+impl RecordingMutator {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.mutated.lock().unwrap())
+    }
+}
+
+impl PropertyMutate for RecordingMutator {
+    fn mutate(&mut self, property_index: u8) -> bool {
+        self.mutated.lock().unwrap().push(property_index);
+        true
+    }
+}
+
+fn stack(item: u16) -> ItemStack {
+    ItemStack { item, count: 1 }
+}
+
+fn slots(count: u16) -> HashMap<u16, ItemStack> {
+    (0..count).map(|slot| (slot, stack(slot))).collect()
+}
+
+fn host_inventory(slot_count: u16, hotbar: Vec<u16>) -> (Inventory, RecordingMutator) {
+    let mutator = RecordingMutator::default();
+    let mut inventory = Inventory::new_complete(0, slots(slot_count), hotbar);
+    inventory.set_mutator(&PropertyMutator::new(mutator.clone()));
+    (inventory, mutator)
+}
+
+// creates the remote copy of a host Inventory, as it would be on first replication
+fn remote_inventory(host: &Inventory) -> Box<dyn Replicate> {
+    // a large map won't fit in a single packet
+    let mut writer = FileBitWriter::new();
+    host.write_fields(&mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    Inventory::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+}
+
+fn diff_mask(bits: &[u8]) -> DiffMask {
+    let mut diff_mask = DiffMask::new(1);
+    for bit in bits {
+        diff_mask.set_bit(*bit, true);
+    }
+    diff_mask
+}
+
+// writes an update of the given bits, returning it & how many bits it took
+fn write_update(inventory: &Inventory, bits: &[u8]) -> (Box<[u8]>, u32) {
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    inventory.write_update(&diff_mask(bits), &mut writer, &mut FakeEntityConverter);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+// writes an update too large for a single packet, to measure it
+fn write_large_update(inventory: &Inventory, bits: &[u8]) -> Box<[u8]> {
+    let mut writer = FileBitWriter::new();
+    inventory.write_update(&diff_mask(bits), &mut writer, &mut FakeEntityConverter);
+    writer.to_bytes()
+}
+
+fn read_update(bytes: &[u8]) -> ComponentUpdate {
+    let mut reader = BitReader::new(bytes);
+    Inventory::create_builder()
+        .read_create_update(&mut reader)
+        .unwrap()
+}
+
+fn apply_update(remote: &mut Box<dyn Replicate>, bytes: &[u8]) {
+    remote
+        .read_apply_update(&FakeEntityConverter, read_update(bytes))
+        .unwrap();
+}
+
+fn as_inventory(remote: &dyn Replicate) -> &Inventory {
+    remote.to_any().downcast_ref::<Inventory>().unwrap()
+}
+
+#[test]
+fn collection_properties_occupy_two_bits() {
+    let (mut inventory, mutator) = host_inventory(500, vec![1, 2, 3, 4, 5, 6]);
+
+    // gold: 0, slots: 1..=2, hotbar: 3..=4
+    assert_eq!(MapProperty::<u16, ItemStack>::DIFF_BITS, 2);
+    assert_eq!(VecProperty::<u16>::DIFF_BITS, 2);
+    assert_eq!(inventory.diff_mask_size(), 1);
+
+    *inventory.gold = 10;
+    assert_eq!(mutator.take(), vec![0]);
+
+    inventory.slots.insert(7, stack(70));
+    assert_eq!(mutator.take(), vec![2]);
+
+    // writing an equal value queues nothing
+    inventory.slots.insert(7, stack(70));
+    assert!(mutator.take().is_empty());
+
+    inventory.slots.set_all(slots(3));
+    assert_eq!(mutator.take(), vec![1]);
+
+    inventory.hotbar.set(0, 9);
+    assert_eq!(mutator.take(), vec![4]);
+
+    inventory.hotbar.clear();
+    assert_eq!(mutator.take(), vec![3]);
+}
+
+#[test]
+fn one_changed_key_of_a_large_map_is_sent_alone() {
+    let (mut inventory, mutator) = host_inventory(500, Vec::new());
+    let mut remote = remote_inventory(&inventory);
+    assert_eq!(*as_inventory(remote.as_ref()).slots, slots(500));
+
+    inventory.slots.modify(&42, |stack| stack.count = 64);
+    let (bytes, _) = write_update(&inventory, &mutator.take());
+    assert!(bytes.len() < 50, "update took {} bytes", bytes.len());
+
+    apply_update(&mut remote, &bytes);
+    let mut expected = slots(500);
+    expected.get_mut(&42).unwrap().count = 64;
+    assert_eq!(*as_inventory(remote.as_ref()).slots, expected);
+
+    // the whole map, as sent after a loss-triggered resync, is far larger
+    let whole_bytes = write_large_update(&inventory, &[1]);
+    assert!(whole_bytes.len() > 500);
+}
+
+#[test]
+fn map_inserts_updates_and_removals_round_trip() {
+    let (mut inventory, mutator) = host_inventory(100, Vec::new());
+    let mut remote = remote_inventory(&inventory);
+
+    inventory.slots.insert(500, stack(5));
+    inventory.slots.insert(3, stack(33));
+    assert_eq!(inventory.slots.remove(&4), Some(stack(4)));
+    assert_eq!(inventory.slots.remove(&4), None);
+    let (bytes, _) = write_update(&inventory, &mutator.take());
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_inventory(remote.as_ref()).slots, *inventory.slots);
+
+    // keys changed before are sent again, so a lost update is recovered by
+    // any later one
+    inventory.slots.insert(501, stack(6));
+    let (bytes, _) = write_update(&inventory, &mutator.take());
+    let mut late_remote = remote_inventory(&Inventory::new_complete(0, slots(100), Vec::new()));
+    apply_update(&mut late_remote, &bytes);
+    assert_eq!(*as_inventory(late_remote.as_ref()).slots, *inventory.slots);
+}
+
+#[test]
+fn changing_most_of_a_map_sends_it_whole() {
+    let (mut inventory, mutator) = host_inventory(4, Vec::new());
+    let mut remote = remote_inventory(&inventory);
+
+    inventory.slots.insert(0, stack(10));
+    inventory.slots.insert(1, stack(11));
+    assert_eq!(mutator.take(), vec![2, 2]);
+
+    // a third changed key is more than half of the map
+    inventory.slots.insert(2, stack(12));
+    assert_eq!(mutator.take(), vec![1]);
+
+    // and tracking restarts from there
+    inventory.slots.insert(3, stack(13));
+    assert_eq!(mutator.take(), vec![2]);
+
+    let (bytes, _) = write_update(&inventory, &[1, 2]);
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_inventory(remote.as_ref()).slots, *inventory.slots);
+}
+
+#[test]
+fn vec_changes_round_trip() {
+    let hotbar: Vec<u16> = (0..20).collect();
+    let (mut inventory, mutator) = host_inventory(0, hotbar);
+    let mut remote = remote_inventory(&inventory);
+
+    inventory.hotbar.set(3, 300);
+    inventory.hotbar.push(20);
+    let (bytes, bits) = write_update(&inventory, &mutator.take());
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_inventory(remote.as_ref()).hotbar, *inventory.hotbar);
+
+    let (_, whole_bits) = write_update(&inventory, &[3]);
+    assert!(bits < whole_bits);
+
+    assert_eq!(inventory.hotbar.pop(), Some(20));
+    inventory.hotbar.truncate(18);
+    inventory.hotbar.modify(0, |value| *value += 100);
+    let (bytes, _) = write_update(&inventory, &mutator.take());
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_inventory(remote.as_ref()).hotbar, *inventory.hotbar);
+
+    inventory.hotbar.insert(15, 1500);
+    assert_eq!(inventory.hotbar.remove(16), 15);
+    let (bytes, _) = write_update(&inventory, &mutator.take());
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_inventory(remote.as_ref()).hotbar, *inventory.hotbar);
+}
+
+#[test]
+fn merged_updates_apply_both_changes() {
+    let protocol = Protocol::builder().add_component::<Inventory>().build();
+
+    let (mut inventory, mutator) = host_inventory(100, (0..10).collect());
+    let mut remote = remote_inventory(&inventory);
+
+    inventory.slots.insert(1, stack(11));
+    inventory.hotbar.push(10);
+    let (earlier, _) = write_update(&inventory, &mutator.take());
+
+    // the later map update resends key 1 along with key 2, while the later
+    // list update replaces the earlier push
+    inventory.slots.remove(&2);
+    inventory.hotbar.set_all(vec![7, 8]);
+    let (later, _) = write_update(&inventory, &mutator.take());
+
+    let mut update = read_update(&earlier);
+    update
+        .merge(&protocol.component_kinds, read_update(&later))
+        .expect("updates should merge");
+    remote
+        .read_apply_update(&FakeEntityConverter, update)
+        .unwrap();
+
+    let remote_inventory = as_inventory(remote.as_ref());
+    assert_eq!(*remote_inventory.slots, *inventory.slots);
+    assert_eq!(*remote_inventory.hotbar, vec![7, 8]);
+}
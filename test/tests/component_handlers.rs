@@ -0,0 +1,135 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, InsertComponentEvent, Server, ServerConfig, SpawnEntityEvent, UserKey,
+};
+use naia_shared::{Property, Protocol, Replicate, WorldRefType};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+#[derive(Replicate)]
+pub struct Color {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .add_component::<Color>()
+        .build()
+}
+
+type Calls = Arc<Mutex<Vec<(UserKey, Entity)>>>;
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_key: Option<UserKey>,
+    spawned: Vec<Entity>,
+    // Position inserts reported through Events
+    position_events: Vec<(UserKey, Entity)>,
+}
+
+impl TestServer {
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.user_key = Some(user_key);
+        }
+        for (_, entity) in events.read::<SpawnEntityEvent>() {
+            self.spawned.push(entity);
+        }
+        self.position_events
+            .extend(events.read::<InsertComponentEvent<Position>>());
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn recorder(calls: &Calls) -> impl FnMut(&UserKey, &Entity) + Send + Sync + 'static {
+    let calls = calls.clone();
+    move |user_key, entity| calls.lock().unwrap().push((*user_key, *entity))
+}
+
+#[test]
+fn handler_fires_for_inserted_component() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer {
+        server: Server::<Entity>::new(ServerConfig::default(), protocol()),
+        world: World::default(),
+        user_key: None,
+        spawned: Vec::new(),
+        position_events: Vec::new(),
+    };
+    server.server.listen(network.server_socket());
+
+    let position_calls = Calls::default();
+    let second_position_calls = Calls::default();
+    let color_calls = Calls::default();
+    server
+        .server
+        .on_insert_component::<Position>(recorder(&position_calls));
+    server
+        .server
+        .on_insert_component::<Position>(recorder(&second_position_calls));
+    server
+        .server
+        .on_insert_component::<Color>(recorder(&color_calls));
+
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    let mut client_world = World::default();
+
+    let mut spawned_on_client = false;
+    for _ in 0..400 {
+        if !server.spawned.is_empty() && !position_calls.lock().unwrap().is_empty() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        client.receive(client_world.proxy_mut());
+        if client.connection_status().is_connected() && !spawned_on_client {
+            client
+                .spawn_entity(client_world.proxy_mut())
+                .insert_component(Position::new_complete(3));
+            spawned_on_client = true;
+        }
+        server.update();
+    }
+
+    assert_eq!(server.spawned.len(), 1);
+    let entity = server.spawned[0];
+    let user_key = server.user_key.unwrap();
+    assert!(*position_calls.lock().unwrap() == vec![(user_key, entity)]);
+    assert!(*second_position_calls.lock().unwrap() == vec![(user_key, entity)]);
+    assert!(color_calls.lock().unwrap().is_empty());
+
+    // the Component is in the Server's World, and the Event is still reported
+    assert!(server
+        .world
+        .proxy()
+        .component::<Position>(&entity)
+        .is_some());
+    assert!(server.position_events == vec![(user_key, entity)]);
+}
@@ -0,0 +1,176 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, ClientTickEvent, UpdateComponentEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{
+    sequence_greater_than, Property, Protocol, Replicate, Tick, WorldMutType, WorldRefType,
+};
+use naia_test::{Auth, LocalNetwork};
+
+const CORRECTED_X: u8 = 100;
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+fn x_of(world: &World, entity: &Entity) -> u8 {
+    *world.proxy().component::<Position>(entity).unwrap().x
+}
+
+// the only input: move one step per Tick
+fn predict(world: &mut World, entity: &Entity) {
+    let mut world = world.proxy_mut();
+    let mut position = world.component_mut::<Position>(entity).unwrap();
+    *position.x = position.x.wrapping_add(1);
+}
+
+#[test]
+fn rollback_resimulates_from_the_corrected_tick() {
+    let network = LocalNetwork::new();
+
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(network.server_socket());
+    let mut server_world = World::default();
+    let room_key: RoomKey = server.make_room().key();
+    let server_entity = server
+        .spawn_entity(server_world.proxy_mut())
+        .insert_component(Position::new_complete(0))
+        .id();
+    server.room_mut(&room_key).add_entity(&server_entity);
+
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    let mut client_world = World::default();
+
+    // (confirmed, predicted, local-only) Entities
+    let mut entities: Option<(Entity, Entity, Entity)> = None;
+    // predicted Ticks, with the local-only Entity's position at their end
+    let mut history: Vec<(Tick, u8)> = Vec::new();
+    let mut server_corrected = false;
+    let mut converged = false;
+
+    for _ in 0..400 {
+        if converged {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+
+        let mut events = client.receive(client_world.proxy_mut());
+
+        if entities.is_none() {
+            let world = client_world.proxy();
+            if let Some(confirmed) = client.entities(&world).first().copied() {
+                if world.has_component::<Position>(&confirmed) {
+                    let mut world = client_world.proxy_mut();
+                    let predicted = world.local_duplicate_entity(&confirmed);
+                    let local_only = world.local_duplicate_entity(&confirmed);
+                    entities = Some((confirmed, predicted, local_only));
+                }
+            }
+        }
+
+        if let Some((confirmed, predicted, local_only)) = entities {
+            for (tick, entity) in events.read::<UpdateComponentEvent<Position>>() {
+                // only the update carrying the Server's correction
+                if entity != confirmed || x_of(&client_world, &entity) != CORRECTED_X {
+                    continue;
+                }
+                let x_before = x_of(&client_world, &local_only);
+
+                assert!(client.rollback_to(client_world.proxy_mut(), &tick));
+                let (_, x_at_tick) = history.iter().find(|(t, _)| *t == tick).unwrap();
+                assert_eq!(x_of(&client_world, &local_only), *x_at_tick);
+
+                // take the authoritative state, then re-apply every later input
+                client_world
+                    .proxy_mut()
+                    .mirror_entities(&predicted, &confirmed);
+                let resimulated: Vec<Tick> = history
+                    .iter()
+                    .map(|(t, _)| *t)
+                    .filter(|t| sequence_greater_than(*t, tick))
+                    .collect();
+                for resimulated_tick in &resimulated {
+                    predict(&mut client_world, &predicted);
+                    predict(&mut client_world, &local_only);
+                    client.store_component_snapshot(client_world.proxy(), resimulated_tick);
+                }
+
+                assert_eq!(x_of(&client_world, &local_only), x_before);
+                assert_eq!(
+                    x_of(&client_world, &predicted),
+                    CORRECTED_X.wrapping_add(resimulated.len() as u8)
+                );
+                converged = true;
+            }
+
+            for (tick, _) in events.read::<ClientTickEvent>() {
+                predict(&mut client_world, &predicted);
+                predict(&mut client_world, &local_only);
+                client.store_component_snapshot(client_world.proxy(), &tick);
+                history.push((tick, x_of(&client_world, &local_only)));
+            }
+        }
+
+        // correct the Client once it's well into predicting
+        if !server_corrected && history.len() >= 20 {
+            let mut world = server_world.proxy_mut();
+            let mut position = world.component_mut::<Position>(&server_entity).unwrap();
+            *position.x = CORRECTED_X;
+            server_corrected = true;
+        }
+
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+            server.room_mut(&room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in server.scope_checks() {
+            server.user_scope_mut(&user_key).include(&entity);
+        }
+        server.send_all_updates(server_world.proxy());
+    }
+
+    assert!(converged);
+}
+
+#[test]
+fn rollback_to_an_unknown_tick_does_nothing() {
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    let mut world = World::default();
+    let entity = world.proxy_mut().spawn_entity();
+    world
+        .proxy_mut()
+        .insert_component(&entity, Position::new_complete(3));
+
+    client.store_component_snapshot(world.proxy(), &10);
+    predict(&mut world, &entity);
+
+    assert!(!client.rollback_to(world.proxy_mut(), &9));
+    assert_eq!(x_of(&world, &entity), 4);
+    assert!(client.rollback_to(world.proxy_mut(), &10));
+    assert_eq!(x_of(&world, &entity), 3);
+
+    // later snapshots are dropped on rollback
+    client.store_component_snapshot(world.proxy(), &11);
+    assert!(client.rollback_to(world.proxy_mut(), &10));
+    assert!(!client.rollback_to(world.proxy_mut(), &11));
+}
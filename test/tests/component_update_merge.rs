@@ -0,0 +1,93 @@
+use naia_shared::{
+    BitReader, BitWriter, ComponentKind, ComponentUpdate, Property, Protocol, Replicate, Serde,
+};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+    pub z: Property<i16>,
+}
+
+#[derive(Replicate)]
+pub struct Health {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_component::<Position>()
+        .add_component::<Health>()
+        .build()
+}
+
+// An update of a Position, in the layout a Host writes it: for each field, a
+// bit for whether it changed, then its new value if so
+fn position_update(x: Option<i16>, y: Option<i16>, z: Option<i16>) -> ComponentUpdate {
+    let mut writer = BitWriter::new();
+    for field in [x, y, z] {
+        field.is_some().ser(&mut writer);
+        if let Some(value) = field {
+            value.ser(&mut writer);
+        }
+    }
+    ComponentUpdate::new(ComponentKind::of::<Position>(), writer.to_owned_reader())
+}
+
+fn read_position_update(update: &ComponentUpdate) -> [Option<i16>; 3] {
+    let mut reader: BitReader = update.reader();
+    let mut fields = [None; 3];
+    for field in &mut fields {
+        if bool::de(&mut reader).unwrap() {
+            *field = Some(i16::de(&mut reader).unwrap());
+        }
+    }
+    fields
+}
+
+#[test]
+fn merged_update_keeps_the_latest_value_of_each_field() {
+    let protocol = protocol();
+
+    let mut update = position_update(Some(1), Some(2), None);
+    let later = position_update(None, Some(5), Some(7));
+    update
+        .merge(&protocol.component_kinds, later)
+        .expect("updates should merge");
+
+    assert_eq!(update.kind, ComponentKind::of::<Position>());
+    assert_eq!(read_position_update(&update), [Some(1), Some(5), Some(7)]);
+
+    // merging again replaces only the fields the newest update includes
+    let latest = position_update(Some(-3), None, None);
+    update
+        .merge(&protocol.component_kinds, latest)
+        .expect("updates should merge");
+    assert_eq!(read_position_update(&update), [Some(-3), Some(5), Some(7)]);
+}
+
+#[test]
+fn merging_updates_with_no_fields_changes_nothing() {
+    let protocol = protocol();
+
+    let mut update = position_update(None, Some(4), None);
+    update
+        .merge(&protocol.component_kinds, position_update(None, None, None))
+        .expect("updates should merge");
+    assert_eq!(read_position_update(&update), [None, Some(4), None]);
+}
+
+#[test]
+#[should_panic]
+fn merging_updates_of_different_components_panics() {
+    let protocol = protocol();
+
+    let mut writer = BitWriter::new();
+    true.ser(&mut writer);
+    9u8.ser(&mut writer);
+    let health_update =
+        ComponentUpdate::new(ComponentKind::of::<Health>(), writer.to_owned_reader());
+
+    let mut update = position_update(Some(1), None, None);
+    let _ = update.merge(&protocol.component_kinds, health_update);
+}
@@ -0,0 +1,210 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, MessageEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig};
+use naia_shared::{
+    default_channels::UnorderedReliableChannel, CompressionConfig, CompressionMode,
+    CompressionStats, ConnectionQuality, Decoder, Encoder, Protocol,
+};
+use naia_test::{Auth, LocalNetwork};
+
+fn protocol(compression: Option<CompressionMode>) -> Protocol {
+    let mut builder = Protocol::builder();
+    builder
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>();
+    if let Some(mode) = compression {
+        builder.compression(CompressionConfig::new(Some(mode.clone()), Some(mode)));
+    }
+    builder.build()
+}
+
+// a payload of long runs of repeated bytes
+fn compressible_payload() -> Vec<u8> {
+    (0..1000).map(|index| (index / 100) as u8).collect()
+}
+
+#[test]
+fn encoder_accumulates_stats() {
+    let mut encoder = Encoder::new(CompressionMode::Default(3));
+    let stats = encoder.stats();
+    assert_eq!(stats.input_bytes, 0);
+    assert_eq!(stats.bytes_saved(), 0);
+
+    let payload = compressible_payload();
+    let output_len = encoder.encode(&payload).len();
+    encoder.encode(&payload);
+
+    let stats = encoder.stats();
+    assert_eq!(stats.input_bytes, 2 * payload.len() as u64);
+    assert_eq!(stats.output_bytes, 2 * output_len as u64);
+    assert!(stats.bytes_saved() > 0);
+    assert!(stats.ratio < 1.0);
+}
+
+// a payload which zstd can't shrink
+fn incompressible_payload() -> Vec<u8> {
+    let mut state: u32 = 12345;
+    (0..1000)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) as u8
+        })
+        .collect()
+}
+
+fn adaptive() -> CompressionMode {
+    CompressionMode::Adaptive {
+        min_packet_size: 100,
+        level_range: (1, 19),
+        bandwidth_cap: Some(10_000),
+    }
+}
+
+#[test]
+fn adaptive_skips_small_packets() {
+    let mut encoder = Encoder::new(adaptive());
+    let payload = vec![7; 50];
+    let output = encoder.encode(&payload).to_vec();
+
+    // only the framing byte is added
+    assert_eq!(output.len(), payload.len() + 1);
+    assert_eq!(&output[1..], &payload[..]);
+    assert_eq!(
+        Decoder::new(adaptive()).decode(&output).unwrap(),
+        &payload[..]
+    );
+}
+
+#[test]
+fn adaptive_compresses_large_packets() {
+    let mut encoder = Encoder::new(adaptive());
+    let payload = compressible_payload();
+    let output = encoder.encode(&payload).to_vec();
+
+    assert!(output.len() < payload.len());
+    assert_eq!(
+        Decoder::new(adaptive()).decode(&output).unwrap(),
+        &payload[..]
+    );
+}
+
+#[test]
+fn adaptive_sends_incompressible_packets_as_they_are() {
+    let mut encoder = Encoder::new(adaptive());
+    let payload = incompressible_payload();
+    assert_eq!(encoder.encode(&payload).len(), payload.len() + 1);
+}
+
+#[test]
+fn adaptive_decodes_mixed_packets() {
+    let mut encoder = Encoder::new(adaptive());
+    let mut decoder = Decoder::new(adaptive());
+    let congested = ConnectionQuality {
+        bytes_out_per_s: 20_000.0,
+        loss_pct_1s: 20.0,
+        ..Default::default()
+    };
+    let payloads = [
+        vec![1; 10],
+        compressible_payload(),
+        incompressible_payload(),
+        vec![2; 99],
+        compressible_payload(),
+    ];
+
+    for (index, payload) in payloads.iter().enumerate() {
+        // alternate between the fastest & the smallest level
+        let quality = if index % 2 == 0 {
+            ConnectionQuality::default()
+        } else {
+            congested
+        };
+        let output = encoder.encode_for(payload, &quality).to_vec();
+        assert_eq!(decoder.decode(&output).unwrap(), &payload[..]);
+    }
+    assert!(encoder.stats().bytes_saved() > 0);
+}
+
+#[test]
+fn adaptive_rejects_unknown_framing() {
+    let mut decoder = Decoder::new(adaptive());
+    assert!(decoder.decode(&[]).is_err());
+    assert!(decoder.decode(&[0xFF, 1, 2, 3]).is_err());
+}
+
+fn connect(
+    compression: Option<CompressionMode>,
+) -> (Option<CompressionStats>, Option<CompressionStats>) {
+    let network = LocalNetwork::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol(compression.clone()));
+    server.listen(network.server_socket());
+    let mut server_world = World::default();
+
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(compression),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    let (socket, address) = network.add_client();
+    client.connect(socket);
+    let mut client_world = World::default();
+
+    let text = "a".repeat(200);
+    let mut received = false;
+    for _ in 0..400 {
+        if received {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        let mut events = client.receive(client_world.proxy_mut());
+        received = events
+            .read::<MessageEvent<UnorderedReliableChannel, Auth>>()
+            .count()
+            > 0;
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            server.send_message::<UnorderedReliableChannel, _>(&user_key, &Auth::new(&text, &text));
+        }
+        server.send_all_updates(server_world.proxy());
+    }
+    assert!(received, "timed out");
+    (
+        server.compression_stats(&address),
+        client.compression_stats(),
+    )
+}
+
+#[test]
+fn server_reports_compression_per_client() {
+    let (Some(stats), _) = connect(Some(CompressionMode::Default(3))) else {
+        panic!("compression stats should be kept for a connected Client");
+    };
+    assert!(stats.input_bytes > 0);
+    assert!(stats.bytes_saved() > 0);
+    assert!(stats.ratio < 1.0);
+}
+
+#[test]
+fn adaptive_compression_connects() {
+    let (Some(server_stats), Some(client_stats)) = connect(Some(adaptive())) else {
+        panic!("compression stats should be kept on both ends");
+    };
+    // the 200 byte Message is compressed
+    assert!(server_stats.bytes_saved() > 0);
+    assert!(client_stats.input_bytes > 0);
+}
+
+#[test]
+fn no_stats_without_compression() {
+    assert_eq!(connect(None), (None, None));
+}
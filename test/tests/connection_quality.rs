@@ -0,0 +1,144 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, ConnectEvent, ConnectionQualityChangedEvent, Server, ServerConfig, UserKey,
+};
+use naia_shared::{default_channels::UnorderedUnreliableChannel, ConnectionQualityLevel, Protocol};
+use naia_test::{Auth, LocalNetwork};
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_key: Option<UserKey>,
+    levels: Vec<ConnectionQualityLevel>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            user_key: None,
+            levels: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.user_key = Some(user_key);
+        }
+        for (user_key, level) in events.read::<ConnectionQualityChangedEvent>() {
+            assert!(Some(user_key) == self.user_key);
+            self.levels.push(level);
+        }
+        // keep Data packets flowing, so that there is something to ack
+        if let Some(user_key) = self.user_key {
+            self.server
+                .send_message::<UnorderedUnreliableChannel, Auth>(&user_key, &Auth::new("a", "b"));
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+        }
+    }
+
+    fn update(&mut self) {
+        let _ = self.client.receive(self.world.proxy_mut());
+    }
+}
+
+fn update(server: &mut TestServer, client: &mut TestClient) {
+    sleep(Duration::from_millis(5));
+    client.update();
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    done: impl Fn(&TestServer, &TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        update(server, client);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn packet_loss_degrades_and_recovers_connection_quality() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut client = TestClient::new(&network);
+    update_until(&mut server, &mut client, |server, client| {
+        server.user_key.is_some() && client.client.connection_status().is_connected()
+    });
+    let user_key = server.user_key.unwrap();
+
+    // a clean link carries traffic without loss
+    for _ in 0..100 {
+        update(&mut server, &mut client);
+    }
+    let quality = server.server.connection_quality(&user_key).unwrap();
+    assert_eq!(quality.loss_pct_1s, 0.0);
+    assert!(quality.bytes_out_per_s > 0.0);
+    assert!(quality.bytes_in_per_s > 0.0);
+    assert!(client.client.connection_quality().bytes_in_per_s > 0.0);
+    assert!(server.levels.is_empty());
+
+    // dropping every other packet to the Client shows up as loss
+    network.set_loss_to_client(&client.address, Some(2));
+    update_until(&mut server, &mut client, |server, _| {
+        server.levels.last() == Some(&ConnectionQualityLevel::Bad)
+    });
+    let quality = server.server.connection_quality(&user_key).unwrap();
+    assert!(quality.loss_pct_10s > 0.0);
+
+    // and the connection recovers once the link does
+    network.set_loss_to_client(&client.address, None);
+    update_until(&mut server, &mut client, |server, _| {
+        server.levels.last() == Some(&ConnectionQualityLevel::Good)
+    });
+}
@@ -0,0 +1,85 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, ConnectionReadyEvent as ClientConnectionReadyEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectionReadyEvent, Server, ServerConfig};
+use naia_shared::{CompressionConfig, CompressionMode, ConnectionParams, Protocol, MTU_SIZE_BYTES};
+use naia_test::{Auth, LocalNetwork};
+
+fn protocol(compression: Option<CompressionMode>) -> Protocol {
+    let mut builder = Protocol::builder();
+    builder
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>();
+    if let Some(mode) = compression {
+        builder.compression(CompressionConfig::new(Some(mode.clone()), Some(mode)));
+    }
+    builder.build()
+}
+
+// the ConnectionParams reported by the Server & the Client, in that order
+fn connect(compression: Option<CompressionMode>) -> (ConnectionParams, ConnectionParams) {
+    let network = LocalNetwork::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol(compression.clone()));
+    server.listen(network.server_socket());
+    let mut server_world = World::default();
+
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(compression),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    let mut client_world = World::default();
+
+    let mut server_params = Vec::new();
+    let mut client_params = Vec::new();
+    for _ in 0..400 {
+        if !server_params.is_empty() && !client_params.is_empty() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        let mut events = client.receive(client_world.proxy_mut());
+        client_params.extend(events.read::<ClientConnectionReadyEvent>());
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server_params.extend(
+            events
+                .read::<ConnectionReadyEvent>()
+                .map(|(_, params)| params),
+        );
+        server.send_all_updates(server_world.proxy());
+    }
+    assert_eq!(server_params.len(), 1, "timed out");
+    assert_eq!(client_params.len(), 1, "timed out");
+    (server_params.remove(0), client_params.remove(0))
+}
+
+#[test]
+fn connection_ready_reports_configured_compression() {
+    let (server_params, client_params) = connect(Some(CompressionMode::Default(3)));
+
+    assert_eq!(server_params.compression, Some(CompressionMode::Default(3)));
+    assert_eq!(server_params.mtu_bytes, MTU_SIZE_BYTES);
+    // the Server hasn't measured anything of the connection yet
+    assert_eq!(server_params.initial_rtt, None);
+
+    assert_eq!(client_params.compression, Some(CompressionMode::Default(3)));
+    assert_eq!(client_params.mtu_bytes, MTU_SIZE_BYTES);
+    // while the Client has, through its handshake pings
+    assert!(client_params.initial_rtt.is_some());
+}
+
+#[test]
+fn connection_ready_without_compression() {
+    let (server_params, client_params) = connect(None);
+    assert_eq!(server_params.compression, None);
+    assert_eq!(client_params.compression, None);
+}
@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex};
+
+use naia_shared::{Property, PropertyMutate, PropertyMutator, Replicate};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+    pub z: Property<i16>,
+}
+
+// records which DiffMask bits were mutated
+#[derive(Clone, Default)]
+struct RecordingMutator {
+    mutated: Arc<Mutex<Vec<u8>>>,
+}
+
+impl RecordingMutator {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.mutated.lock().unwrap())
+    }
+}
+
+impl PropertyMutate for RecordingMutator {
+    fn mutate(&mut self, property_index: u8) -> bool {
+        self.mutated.lock().unwrap().push(property_index);
+        true
+    }
+}
+
+fn host_position(x: i16, y: i16, z: i16) -> (Position, RecordingMutator) {
+    let recorder = RecordingMutator::default();
+    let mut position = Position::new_complete(x, y, z);
+    position.set_mutator(&PropertyMutator::new(recorder.clone()));
+    (position, recorder)
+}
+
+#[test]
+fn set_if_changed_only_mutates_differing_properties() {
+    let (mut position, recorder) = host_position(1, 2, 3);
+
+    assert!(!position.set_if_changed(&Position::new_complete(1, 2, 3)));
+    assert!(recorder.take().is_empty());
+
+    assert!(position.set_if_changed(&Position::new_complete(1, 5, 3)));
+    assert_eq!(recorder.take(), vec![1]);
+    assert_eq!((*position.x, *position.y, *position.z), (1, 5, 3));
+}
+
+#[test]
+fn deferred_writes_of_the_same_value_queue_nothing() {
+    let (mut position, recorder) = host_position(1, 2, 3);
+    position.set_defer_mutations(true);
+
+    *position.x = 1;
+    *position.y = 7;
+    *position.y = 2;
+    assert!(recorder.take().is_empty());
+    assert!(!position.flush_deferred_mutations());
+    assert!(recorder.take().is_empty());
+
+    // only the Properties whose values changed are queued, once each
+    *position.x = 4;
+    *position.x = 6;
+    *position.z = 3;
+    assert!(recorder.take().is_empty());
+    assert!(position.flush_deferred_mutations());
+    assert_eq!(recorder.take(), vec![0]);
+
+    // undeferring flushes a pending write, then queues writes right away
+    *position.z = 9;
+    position.set_defer_mutations(false);
+    assert_eq!(recorder.take(), vec![2]);
+    *position.z = 9;
+    assert_eq!(recorder.take(), vec![2]);
+}
@@ -0,0 +1,212 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, DespawnEntityEvent as ClientDespawnEntityEvent, RemoveComponentEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{Property, Protocol, Replicate, WorldMutType, WorldRefType};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Health {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Health>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    // the Health last seen at the end of an update
+    last_health: Option<u8>,
+    // the Health the Entity had when it was despawned
+    health_at_despawn: Option<Option<u8>>,
+}
+
+impl TestClient {
+    fn new(socket: LocalClientSocket) -> Self {
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            last_health: None,
+            health_at_despawn: None,
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        // a final update may be applied in the same receive as the despawn,
+        // so the Health is read from the removal the despawn reports
+        let removed_health = events
+            .read::<RemoveComponentEvent<Health>>()
+            .map(|(_, health)| *health.value)
+            .last();
+        if events.read::<ClientDespawnEntityEvent>().count() > 0 {
+            self.health_at_despawn = Some(removed_health);
+        }
+        let world = self.world.proxy();
+        self.last_health = self.client.entities(&world).iter().find_map(|entity| {
+            world
+                .component::<Health>(entity)
+                .map(|health| *health.value)
+        });
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+        }
+    }
+
+    fn spawn(&mut self, value: u8) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.world.proxy_mut())
+            .insert_component(Health::new_complete(value))
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn set_health(&mut self, entity: &Entity, value: u8) {
+        let mut world = self.world.proxy_mut();
+        let mut health = world.component_mut::<Health>(entity).unwrap();
+        *health.value = value;
+    }
+
+    fn has_entity(&self, entity: &Entity) -> bool {
+        self.world.proxy().has_entity(entity)
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, client: &mut TestClient) {
+    sleep(Duration::from_millis(5));
+    client.update();
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    done: impl Fn(&TestServer, &TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        update(server, client);
+    }
+    panic!("timed out");
+}
+
+fn connected(network: &LocalNetwork, health: u8) -> (TestServer, TestClient, Entity, SocketAddr) {
+    let mut server = TestServer::new(network);
+    let entity = server.spawn(health);
+    let (socket, address) = network.add_client();
+    let mut client = TestClient::new(socket);
+    update_until(&mut server, &mut client, |_, client| {
+        client.last_health == Some(health)
+    });
+    // let the spawn & its acks settle
+    for _ in 0..20 {
+        update(&mut server, &mut client);
+    }
+    (server, client, entity, address)
+}
+
+#[test]
+fn final_update_arrives_before_despawn() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client, entity, address) = connected(&network, 100);
+
+    server.set_health(&entity, 0);
+    assert!(server.server.despawn_entity_after_ack(&entity).is_ok());
+    assert!(server.server.entity_despawn_pending(&entity));
+
+    // the packet carrying the update is lost, so it is never acknowledged
+    // until it has been sent again
+    network.set_loss_to_client(&address, Some(1));
+    server.update();
+    network.set_loss_to_client(&address, None);
+    assert!(server.has_entity(&entity));
+
+    update_until(&mut server, &mut client, |_, client| {
+        client.health_at_despawn.is_some()
+    });
+
+    assert_eq!(client.health_at_despawn, Some(Some(0)));
+    assert!(!server.has_entity(&entity));
+    assert!(!server.server.entity_despawn_pending(&entity));
+}
+
+#[test]
+fn entity_without_pending_updates_despawns_on_next_receive() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client, entity, _) = connected(&network, 100);
+
+    assert!(server.server.despawn_entity_after_ack(&entity).is_ok());
+    assert!(server.has_entity(&entity));
+    server.update();
+    assert!(!server.has_entity(&entity));
+
+    update_until(&mut server, &mut client, |_, client| {
+        client.health_at_despawn.is_some()
+    });
+    assert_eq!(client.health_at_despawn, Some(Some(100)));
+}
+
+#[test]
+fn despawn_after_ack_of_nonexistent_entity_is_an_error() {
+    let network = LocalNetwork::new();
+    let (mut server, _, entity, _) = connected(&network, 100);
+
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .despawn();
+    assert!(server.server.despawn_entity_after_ack(&entity).is_err());
+    assert!(!server.server.entity_despawn_pending(&entity));
+}
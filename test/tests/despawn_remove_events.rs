@@ -0,0 +1,199 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, DespawnEntityEvent as ClientDespawnEntityEvent, Events as ClientEvents,
+    RemoveComponentEvent as ClientRemoveComponentEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, DespawnEntityEvent as ServerDespawnEntityEvent, Events as ServerEvents,
+    RemoveComponentEvent, RoomKey, Server, ServerConfig,
+};
+use naia_shared::{Property, Protocol, Replicate, WorldMutType, WorldRefType};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+#[derive(Replicate)]
+pub struct Color {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .add_component::<Color>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    room_key: RoomKey,
+    client: Client<Entity>,
+    client_world: World,
+}
+
+impl Test {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+
+        Self {
+            server,
+            server_world: World::default(),
+            room_key,
+            client,
+            client_world: World::default(),
+        }
+    }
+
+    fn update(&mut self) -> (ClientEvents<Entity>, ServerEvents<Entity>) {
+        sleep(Duration::from_millis(5));
+        let client_events = self.client.receive(self.client_world.proxy_mut());
+        let mut server_events = self.server.receive(self.server_world.proxy_mut());
+        for (user_key, _) in server_events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+        (client_events, server_events)
+    }
+
+    fn update_until(&mut self, done: impl Fn(&Self) -> bool) {
+        for _ in 0..400 {
+            if done(self) {
+                return;
+            }
+            self.update();
+        }
+        panic!("timed out");
+    }
+
+    fn client_x(&self) -> Option<u8> {
+        let world = self.client_world.proxy();
+        let entity = self.client.entities(&world).first().copied()?;
+        world
+            .component::<Position>(&entity)
+            .map(|position| *position.x)
+    }
+}
+
+#[test]
+fn client_receives_final_component_state_on_despawn() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    let entity = test
+        .server
+        .spawn_entity(test.server_world.proxy_mut())
+        .insert_component(Position::new_complete(7))
+        .insert_component(Color::new_complete(2))
+        .id();
+    test.server.room_mut(&test.room_key).add_entity(&entity);
+    test.update_until(|test| test.client_x() == Some(7));
+    // let the spawn's ack reach the Server, which only then tracks changes
+    for _ in 0..20 {
+        test.update();
+    }
+
+    // the last change before the despawn
+    *test
+        .server_world
+        .proxy_mut()
+        .component_mut::<Position>(&entity)
+        .unwrap()
+        .x = 9;
+    test.update_until(|test| test.client_x() == Some(9));
+
+    test.server
+        .entity_mut(test.server_world.proxy_mut(), &entity)
+        .despawn();
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    for _ in 0..400 {
+        let (mut client_events, _) = test.update();
+        positions.extend(client_events.read::<ClientRemoveComponentEvent<Position>>());
+        colors.extend(client_events.read::<ClientRemoveComponentEvent<Color>>());
+        let despawns = client_events.read::<ClientDespawnEntityEvent>().count();
+        // removals arrive in the same batch as the despawn
+        assert_eq!(despawns == 0, positions.is_empty());
+        if despawns == 1 {
+            break;
+        }
+    }
+
+    assert_eq!(positions.len(), 1);
+    assert_eq!(*positions[0].1.x, 9);
+    assert_eq!(colors.len(), 1);
+    assert_eq!(*colors[0].1.value, 2);
+    assert!(test.client.entities(&test.client_world.proxy()).is_empty());
+}
+
+#[test]
+fn server_receives_final_component_state_on_client_despawn() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    test.update_until(|test| test.client.connection_status().is_connected());
+
+    let entity = test
+        .client
+        .spawn_entity(test.client_world.proxy_mut())
+        .insert_component(Position::new_complete(3))
+        .id();
+    let server_x = |test: &Test| -> Option<u8> {
+        let world = test.server_world.proxy();
+        let server_entity = world.entities().first().copied()?;
+        world
+            .component::<Position>(&server_entity)
+            .map(|position| *position.x)
+    };
+    test.update_until(|test| server_x(test) == Some(3));
+
+    *test
+        .client_world
+        .proxy_mut()
+        .component_mut::<Position>(&entity)
+        .unwrap()
+        .x = 5;
+    test.update_until(|test| server_x(test) == Some(5));
+
+    test.client
+        .entity_mut(test.client_world.proxy_mut(), &entity)
+        .despawn();
+    let mut positions = Vec::new();
+    for _ in 0..400 {
+        let (_, mut server_events) = test.update();
+        positions.extend(server_events.read::<RemoveComponentEvent<Position>>());
+        let despawns = server_events.read::<ServerDespawnEntityEvent>().count();
+        assert_eq!(despawns == 0, positions.is_empty());
+        if despawns == 1 {
+            break;
+        }
+    }
+
+    assert_eq!(positions.len(), 1);
+    assert_eq!(*positions[0].2.x, 5);
+    assert!(test.server_world.proxy().entities().is_empty());
+}
@@ -0,0 +1,192 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::{Property, Protocol, Replicate, WorldRefType};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Widget {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Widget>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+        }
+    }
+
+    fn update(&mut self) {
+        let _ = self.client.receive(self.world.proxy_mut());
+    }
+
+    fn widget_value(&self) -> Option<u8> {
+        let entity = self.client.entities(&self.world.proxy()).first().copied()?;
+        let world = self.world.proxy();
+        let widget = world.component::<Widget>(&entity)?;
+        Some(*widget.value)
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    connected: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            connected: Vec::new(),
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .connected
+            .iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        *user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn entity_included_directly_replicates_only_to_that_user() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(&network), TestClient::new(&network)];
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.connected.len() == 2
+            && clients
+                .iter()
+                .all(|client| client.client.connection_status().is_connected())
+    });
+    let alice = server.user_key(&clients[0]);
+
+    // the Entity is in no Room
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Widget::new_complete(9))
+        .id();
+    server.server.user_scope_mut(&alice).include_entity(&entity);
+    assert!(server.server.user_scope(&alice).has(&entity));
+
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].widget_value() == Some(9)
+    });
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+    assert_eq!(clients[1].widget_value(), None);
+
+    // once excluded, the Entity leaves the User's scope again
+    server.server.user_scope_mut(&alice).exclude_entity(&entity);
+    assert!(!server.server.user_scope(&alice).has(&entity));
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].widget_value().is_none()
+    });
+}
+
+#[test]
+fn entity_included_directly_stays_when_user_leaves_a_shared_room() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(&network)];
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.connected.len() == 1 && clients[0].client.connection_status().is_connected()
+    });
+    let alice = server.user_key(&clients[0]);
+
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Widget::new_complete(3))
+        .id();
+    let room_key = server.server.make_room().key();
+    server
+        .server
+        .room_mut(&room_key)
+        .add_user(&alice)
+        .add_entity(&entity);
+    server.server.user_scope_mut(&alice).include_entity(&entity);
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].widget_value() == Some(3)
+    });
+
+    server.server.room_mut(&room_key).remove_user(&alice);
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+    assert_eq!(clients[0].widget_value(), Some(3));
+}
@@ -0,0 +1,253 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, MessageEvent as ClientMessageEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, MessageEvent, Server, ServerConfig};
+use naia_shared::{
+    default_channels::OrderedReliableChannel, is_handshake_packet, open_packet, seal_packet,
+    BitWrite, BitWriter, ConnectionConfig, DecoderError, EncryptionConfig, HostType, KeyExchange,
+    Message, PacketCipher, PacketType, Protocol, Serde, StandardHeader,
+};
+use naia_test::{Auth, LocalNetwork};
+
+const SECRET: &str = "the secret password is swordfish";
+
+#[derive(Message)]
+pub struct Secret {
+    pub text: String,
+}
+
+// performs the key exchange which the handshake carries, returning the Client's and Server's ciphers
+fn connected_ciphers() -> (PacketCipher, PacketCipher) {
+    let client_keys = KeyExchange::new().unwrap();
+    let server_keys = KeyExchange::new().unwrap();
+    let client_public_key = client_keys.public_key().to_vec();
+    let server_public_key = server_keys.public_key().to_vec();
+
+    let client_cipher = client_keys
+        .into_cipher(HostType::Client, &server_public_key)
+        .unwrap();
+    let server_cipher = server_keys
+        .into_cipher(HostType::Server, &client_public_key)
+        .unwrap();
+    (client_cipher, server_cipher)
+}
+
+fn packet(packet_type: PacketType, body: &str) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    StandardHeader::new(packet_type, 0, 0, 0).ser(&mut writer);
+    body.to_string().ser(&mut writer);
+    writer.to_bytes().to_vec()
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[test]
+fn sealed_packet_hides_payload() {
+    let (mut client_cipher, mut server_cipher) = connected_ciphers();
+    let plaintext = packet(PacketType::Data, "the secret password is swordfish");
+
+    let sealed = seal_packet(Some(&mut client_cipher), &plaintext);
+    assert!(!contains(&sealed, b"swordfish"));
+
+    let (opened, was_encrypted) = open_packet(Some(&mut server_cipher), &sealed).unwrap();
+    assert!(was_encrypted);
+    assert_eq!(opened, plaintext);
+}
+
+#[test]
+fn each_direction_has_its_own_key() {
+    let (mut client_cipher, _server_cipher) = connected_ciphers();
+    let plaintext = packet(PacketType::Data, "hello");
+
+    // a packet reflected back at its sender fails authentication
+    let sealed = client_cipher.encrypt(&plaintext);
+    assert_eq!(
+        open_packet(Some(&mut client_cipher), &sealed),
+        Err(DecoderError::Tampered)
+    );
+}
+
+#[test]
+fn tampered_packet_is_rejected() {
+    let (mut client_cipher, mut server_cipher) = connected_ciphers();
+    let plaintext = packet(PacketType::Data, "hello");
+
+    let mut sealed = seal_packet(Some(&mut client_cipher), &plaintext);
+    let last = sealed.len() - 1;
+    sealed[last] ^= 1;
+    assert_eq!(
+        open_packet(Some(&mut server_cipher), &sealed),
+        Err(DecoderError::Tampered)
+    );
+
+    // a tampered nonce counter is caught too
+    let mut sealed = seal_packet(Some(&mut client_cipher), &plaintext);
+    sealed[1] ^= 1;
+    assert_eq!(
+        open_packet(Some(&mut server_cipher), &sealed),
+        Err(DecoderError::Tampered)
+    );
+
+    // the connection survives, and authentic packets are still accepted
+    let sealed = seal_packet(Some(&mut client_cipher), &plaintext);
+    assert!(open_packet(Some(&mut server_cipher), &sealed).is_ok());
+}
+
+#[test]
+fn replayed_packet_is_rejected() {
+    let (mut client_cipher, mut server_cipher) = connected_ciphers();
+    let plaintext = packet(PacketType::Data, "hello");
+
+    let first = seal_packet(Some(&mut client_cipher), &plaintext);
+    let second = seal_packet(Some(&mut client_cipher), &plaintext);
+
+    // out-of-order delivery is fine
+    assert!(open_packet(Some(&mut server_cipher), &second).is_ok());
+    assert!(open_packet(Some(&mut server_cipher), &first).is_ok());
+
+    assert_eq!(
+        open_packet(Some(&mut server_cipher), &first),
+        Err(DecoderError::Replayed)
+    );
+}
+
+#[test]
+fn packets_before_key_exchange() {
+    let (mut client_cipher, _server_cipher) = connected_ciphers();
+
+    // handshake packets are framed, but not encrypted
+    let handshake = packet(PacketType::Handshake, "hello");
+    assert!(is_handshake_packet(&handshake));
+    let framed = seal_packet(None, &handshake);
+    assert_eq!(open_packet(None, &framed), Ok((handshake, false)));
+
+    // an encrypted packet cannot be read until keys are established
+    let data = packet(PacketType::Data, "hello");
+    assert!(!is_handshake_packet(&data));
+    let sealed = seal_packet(Some(&mut client_cipher), &data);
+    assert_eq!(open_packet(None, &sealed), Err(DecoderError::NoKeys));
+
+    assert_eq!(open_packet(None, &[]), Err(DecoderError::Malformed));
+}
+
+// the secret's serialized bytes at each bit alignment it may take within a packet
+fn secret_patterns() -> Vec<Vec<u8>> {
+    (0..8)
+        .map(|offset| {
+            let mut writer = BitWriter::new();
+            for _ in 0..offset {
+                writer.write_bit(false);
+            }
+            SECRET.to_string().ser(&mut writer);
+            let bytes = writer.to_bytes();
+            // the first and last bytes are shared with the surrounding bits
+            bytes[1..bytes.len() - 1].to_vec()
+        })
+        .collect()
+}
+
+fn any_packet_reveals_secret(packets: &[Vec<u8>]) -> bool {
+    let patterns = secret_patterns();
+    packets
+        .iter()
+        .any(|packet| patterns.iter().any(|pattern| contains(packet, pattern)))
+}
+
+// sends the secret both ways over a LocalNetwork, returning every packet
+// the Server and then the Client read off the wire
+fn exchange_secret(encryption: Option<EncryptionConfig>) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let protocol = || {
+        Protocol::builder()
+            .tick_interval(Duration::from_millis(10))
+            .add_default_channels()
+            .add_message::<Auth>()
+            .add_message::<Secret>()
+            .build()
+    };
+    let connection = ConnectionConfig {
+        encryption,
+        ..Default::default()
+    };
+    let network = LocalNetwork::new();
+
+    let mut server_world = World::default();
+    let mut server = Server::<Entity>::new(
+        ServerConfig {
+            connection: connection.clone(),
+            ..Default::default()
+        },
+        protocol(),
+    );
+    server.listen(network.server_socket());
+
+    let mut client_world = World::default();
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            connection,
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    let (socket, client_address) = network.add_client();
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(socket);
+
+    let mut server_got_secret = false;
+    let mut client_got_secret = false;
+    for _ in 0..400 {
+        if server_got_secret && client_got_secret {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+
+        let mut events = client.receive(client_world.proxy_mut());
+        for secret in events.read::<ClientMessageEvent<OrderedReliableChannel, Secret>>() {
+            assert_eq!(secret.text, SECRET);
+            client_got_secret = true;
+        }
+
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            let secret = Secret {
+                text: SECRET.to_string(),
+            };
+            server.send_message::<OrderedReliableChannel, _>(&user_key, &secret);
+            client.send_message::<OrderedReliableChannel, _>(&secret);
+        }
+        for (_, secret) in events.read::<MessageEvent<OrderedReliableChannel, Secret>>() {
+            assert_eq!(secret.text, SECRET);
+            server_got_secret = true;
+        }
+        server.send_all_updates(server_world.proxy());
+    }
+    assert!(server_got_secret && client_got_secret, "timed out");
+
+    (
+        network.server_received(),
+        network.client_received(&client_address),
+    )
+}
+
+#[test]
+fn plaintext_payloads_are_readable_on_the_wire() {
+    let (server_received, client_received) = exchange_secret(None);
+    assert!(any_packet_reveals_secret(&server_received));
+    assert!(any_packet_reveals_secret(&client_received));
+}
+
+#[test]
+fn encrypted_payloads_are_unreadable_on_the_wire() {
+    let (server_received, client_received) = exchange_secret(Some(EncryptionConfig));
+    assert!(!any_packet_reveals_secret(&server_received));
+    assert!(!any_packet_reveals_secret(&client_received));
+}
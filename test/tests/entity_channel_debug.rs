@@ -0,0 +1,68 @@
+use std::any::TypeId;
+
+use naia_shared::{ComponentKind, EntityAction, EntityActionReceiver};
+
+const ENTITY: u32 = 7;
+
+fn component_kind() -> ComponentKind {
+    ComponentKind::from(TypeId::of::<u8>())
+}
+
+#[test]
+fn despawn_before_spawn_is_reported_pending() {
+    let mut receiver = EntityActionReceiver::<u32>::new();
+
+    // the despawn arrives first, and must wait on the spawn
+    receiver.buffer_action(1, EntityAction::DespawnEntity(ENTITY));
+    assert!(receiver.receive_actions().is_empty());
+
+    let dump = receiver.debug_dump();
+    assert_eq!(dump.len(), 1);
+    let channel = &dump[0];
+    assert_eq!(channel.entity, ENTITY);
+    assert!(!channel.spawned);
+    assert_eq!(channel.highest_seen_index, Some(1));
+    assert_eq!(channel.highest_applied_index, None);
+    assert_eq!(
+        channel.pending_actions,
+        vec![(1, EntityAction::DespawnEntity(ENTITY))]
+    );
+
+    // once the spawn arrives, both are applied
+    receiver.buffer_action(0, EntityAction::SpawnEntity(ENTITY, Vec::new()));
+    assert_eq!(
+        receiver.receive_actions(),
+        vec![
+            EntityAction::SpawnEntity(ENTITY, Vec::new()),
+            EntityAction::DespawnEntity(ENTITY),
+        ]
+    );
+
+    let channel = &receiver.debug_dump()[0];
+    assert!(!channel.spawned);
+    assert_eq!(channel.highest_seen_index, Some(1));
+    assert_eq!(channel.highest_applied_index, Some(1));
+    assert!(channel.pending_actions.is_empty());
+}
+
+#[test]
+fn remove_before_insert_is_reported_pending() {
+    let mut receiver = EntityActionReceiver::<u32>::new();
+    receiver.buffer_action(0, EntityAction::SpawnEntity(ENTITY, Vec::new()));
+    receiver.buffer_action(2, EntityAction::RemoveComponent(ENTITY, component_kind()));
+    assert_eq!(
+        receiver.receive_actions(),
+        vec![EntityAction::SpawnEntity(ENTITY, Vec::new())]
+    );
+
+    let channel = &receiver.debug_dump()[0];
+    assert!(channel.spawned);
+    assert_eq!(channel.highest_seen_index, Some(2));
+    assert_eq!(channel.highest_applied_index, Some(0));
+    assert_eq!(channel.components.len(), 1);
+    assert!(!channel.components[0].inserted);
+    assert_eq!(
+        channel.pending_actions,
+        vec![(2, EntityAction::RemoveComponent(ENTITY, component_kind()))]
+    );
+}
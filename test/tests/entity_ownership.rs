@@ -0,0 +1,276 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, EntityOwner as ClientEntityOwner,
+    ReplicationConfig as ClientReplicationConfig,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, EntityOwner, ReplicationConfig, RoomKey, Server, ServerConfig, UserKey,
+};
+use naia_shared::{EntityAuthStatus, Property, Protocol, Replicate};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+        }
+    }
+
+    fn update(&mut self) {
+        self.client.receive(self.world.proxy_mut());
+    }
+
+    fn entity(&self) -> Option<Entity> {
+        self.client.entities(&self.world.proxy()).first().copied()
+    }
+
+    fn authority(&self) -> Option<EntityAuthStatus> {
+        let entity = self.entity()?;
+        self.client.entity(self.world.proxy(), &entity).authority()
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .server
+            .user_keys()
+            .into_iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn delegated_entity_ownership_follows_authority() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(7))
+        .configure_replication(ReplicationConfig::Delegated)
+        .id();
+    server.server.room_mut(&server.room_key).add_entity(&entity);
+    let mut clients = vec![TestClient::new(&network), TestClient::new(&network)];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients
+            .iter()
+            .all(|client| client.authority() == Some(EntityAuthStatus::Available))
+    });
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+    let first_user = server.user_key(&clients[0]);
+    let second_user = server.user_key(&clients[1]);
+
+    // nobody holds authority, so the Server owns it
+    assert_eq!(server.server.server_owned_entities(), vec![entity]);
+    assert!(server.server.user_owned_entities(&first_user).is_empty());
+    let client_entity = clients[0].entity().unwrap();
+    assert_eq!(
+        clients[0].client.server_owned_entities(),
+        vec![client_entity]
+    );
+    assert!(clients[0].client.owned_entities().is_empty());
+
+    // granting authority hands ownership to the requesting Client
+    let client = &mut clients[0];
+    client
+        .client
+        .entity_mut(client.world.proxy_mut(), &client_entity)
+        .request_authority();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+    });
+    assert_eq!(
+        server.server.entity_owner(&entity),
+        EntityOwner::Client(first_user)
+    );
+    assert_eq!(server.server.user_owned_entities(&first_user), vec![entity]);
+    assert!(server.server.server_owned_entities().is_empty());
+    assert_eq!(
+        clients[0].client.entity_owner(&client_entity),
+        ClientEntityOwner::Client
+    );
+    assert_eq!(clients[0].client.owned_entities(), vec![client_entity]);
+    assert!(clients[0].client.server_owned_entities().is_empty());
+
+    // and transferring it moves ownership along
+    server
+        .server
+        .transfer_authority(&first_user, &second_user, &entity)
+        .unwrap();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[1].authority() == Some(EntityAuthStatus::Granted)
+            && clients[0].authority() == Some(EntityAuthStatus::Denied)
+    });
+    assert!(server.server.user_owned_entities(&first_user).is_empty());
+    assert_eq!(
+        server.server.user_owned_entities(&second_user),
+        vec![entity]
+    );
+    assert!(clients[0].client.owned_entities().is_empty());
+    assert_eq!(clients[1].client.owned_entities().len(), 1);
+
+    // once released, it's the Server's again
+    let client_entity = clients[1].entity().unwrap();
+    let client = &mut clients[1];
+    client
+        .client
+        .entity_mut(client.world.proxy_mut(), &client_entity)
+        .release_authority();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[1].authority() == Some(EntityAuthStatus::Available)
+    });
+    assert_eq!(server.server.entity_owner(&entity), EntityOwner::Server);
+    assert_eq!(server.server.server_owned_entities(), vec![entity]);
+    assert!(server.server.user_owned_entities(&second_user).is_empty());
+    assert!(clients[1].client.owned_entities().is_empty());
+    assert_eq!(
+        clients[1].client.entity_owner(&client_entity),
+        ClientEntityOwner::Server
+    );
+}
+
+#[test]
+fn client_spawned_entity_is_owned_through_publishing() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(&network)];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].client.connection_status().is_connected()
+    });
+    let user_key = server.user_key(&clients[0]);
+
+    let client = &mut clients[0];
+    let client_entity = client
+        .client
+        .spawn_entity(client.world.proxy_mut())
+        .insert_component(Position::new_complete(3))
+        .id();
+    assert_eq!(clients[0].client.owned_entities(), vec![client_entity]);
+    assert!(clients[0].client.server_owned_entities().is_empty());
+
+    update_until(&mut server, &mut clients, |server, _| {
+        server.server.user_owned_entities(&user_key).len() == 1
+    });
+    let entity = server.server.user_owned_entities(&user_key)[0];
+    assert_eq!(
+        server.server.entity_owner(&entity),
+        EntityOwner::Client(user_key)
+    );
+    assert!(server.server.server_owned_entities().is_empty());
+
+    let client = &mut clients[0];
+    client
+        .client
+        .entity_mut(client.world.proxy_mut(), &client_entity)
+        .configure_replication(ClientReplicationConfig::Public);
+    update_until(&mut server, &mut clients, |server, _| {
+        server.server.entity_owner(&entity) == EntityOwner::ClientPublic(user_key)
+    });
+    assert_eq!(server.server.user_owned_entities(&user_key), vec![entity]);
+    assert_eq!(clients[0].client.owned_entities(), vec![client_entity]);
+
+    // despawning drops it from every list
+    let client = &mut clients[0];
+    client
+        .client
+        .entity_mut(client.world.proxy_mut(), &client_entity)
+        .despawn();
+    update_until(&mut server, &mut clients, |server, _| {
+        server.server.user_owned_entities(&user_key).is_empty()
+    });
+    assert!(clients[0].client.owned_entities().is_empty());
+}
@@ -0,0 +1,254 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig, UserKey};
+use naia_shared::{Property, Protocol, Replicate, WorldMutType, WorldRefType};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+    pub y: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+}
+
+impl TestClient {
+    fn new(socket: LocalClientSocket) -> Self {
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+        }
+    }
+
+    fn update(&mut self) {
+        self.client.receive(self.world.proxy_mut());
+    }
+
+    fn positions(&self) -> Vec<(u8, u8)> {
+        let world = self.world.proxy();
+        self.client
+            .entities(&world)
+            .iter()
+            .filter_map(|entity| {
+                world
+                    .component::<Position>(entity)
+                    .map(|position| (*position.x, *position.y))
+            })
+            .collect()
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+
+    fn move_to(&mut self, entity: &Entity, x: u8, y: u8) {
+        let mut world = self.world.proxy_mut();
+        let mut position = world.component_mut::<Position>(entity).unwrap();
+        *position.x = x;
+        *position.y = y;
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient], count: usize) {
+    for _ in 0..count {
+        sleep(Duration::from_millis(5));
+        for client in clients.iter_mut() {
+            client.update();
+        }
+        server.update();
+    }
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(clients) {
+            return;
+        }
+        update(server, clients, 1);
+    }
+    panic!("timed out");
+}
+
+fn user_at(server: &TestServer, address: &SocketAddr) -> UserKey {
+    let Some(user_key) = server
+        .server
+        .user_keys()
+        .into_iter()
+        .find(|user_key| server.server.user(user_key).address() == *address)
+    else {
+        panic!("no User at {}", address);
+    };
+    user_key
+}
+
+#[test]
+fn paused_entity_stays_stale_then_catches_up_on_resume() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(1, 1))
+        .id();
+    server.server.room_mut(&server.room_key).add_entity(&entity);
+
+    let (socket, paused_address) = network.add_client();
+    let mut clients = vec![
+        TestClient::new(socket),
+        TestClient::new(network.add_client().0),
+    ];
+    update_until(&mut server, &mut clients, |clients| {
+        clients
+            .iter()
+            .all(|client| client.positions() == vec![(1, 1)])
+    });
+
+    let user_key = user_at(&server, &paused_address);
+    server
+        .server
+        .user_scope_mut(&user_key)
+        .pause_entity(&entity);
+    assert!(server.server.user_scope(&user_key).is_paused(&entity));
+
+    // two separate changes while paused
+    server.move_to(&entity, 2, 1);
+    update(&mut server, &mut clients, 20);
+    server.move_to(&entity, 2, 3);
+    update_until(&mut server, &mut clients, |clients| {
+        clients[1].positions() == vec![(2, 3)]
+    });
+    update(&mut server, &mut clients, 20);
+
+    // the paused Client keeps the Entity, with its last-known state
+    assert_eq!(clients[0].positions(), vec![(1, 1)]);
+
+    server
+        .server
+        .user_scope_mut(&user_key)
+        .resume_entity(&entity);
+    assert!(!server.server.user_scope(&user_key).is_paused(&entity));
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].positions() != vec![(1, 1)]
+    });
+
+    // every change made while paused arrives in the same update
+    assert_eq!(clients[0].positions(), vec![(2, 3)]);
+}
+
+#[test]
+fn paused_room_stays_stale_then_catches_up_on_resume() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(1, 1))
+        .id();
+    server.server.room_mut(&server.room_key).add_entity(&entity);
+
+    let mut clients = vec![TestClient::new(network.add_client().0)];
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].positions() == vec![(1, 1)]
+    });
+
+    let room_key = server.room_key;
+    server.server.pause_room_replication(&room_key);
+    assert!(server.server.room_replication_is_paused(&room_key));
+    assert!(server.server.scope_checks().is_empty());
+
+    server.move_to(&entity, 2, 1);
+    update(&mut server, &mut clients, 20);
+    server.move_to(&entity, 2, 3);
+    update(&mut server, &mut clients, 20);
+    assert_eq!(clients[0].positions(), vec![(1, 1)]);
+
+    server.server.resume_room_replication(&room_key);
+    assert!(!server.server.room_replication_is_paused(&room_key));
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].positions() != vec![(1, 1)]
+    });
+
+    // the change accumulated while paused arrives once resumed
+    assert_eq!(clients[0].positions(), vec![(2, 3)]);
+}
+
+#[test]
+fn paused_room_defers_scope_changes_until_resumed() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(network.add_client().0)];
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].client.connection_status().is_connected()
+    });
+
+    let room_key = server.room_key;
+    server.server.pause_room_replication(&room_key);
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(4, 4))
+        .id();
+    server.server.room_mut(&room_key).add_entity(&entity);
+    update(&mut server, &mut clients, 40);
+    assert!(clients[0].positions().is_empty());
+
+    server.server.resume_room_replication(&room_key);
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].positions() == vec![(4, 4)]
+    });
+}
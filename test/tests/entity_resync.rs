@@ -0,0 +1,219 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, DespawnEntityEvent as ClientDespawnEntityEvent,
+    SpawnEntityEvent as ClientSpawnEntityEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{
+    BitReader, BitWriter, Property, Protocol, Replicate, Serde, WorldMutType, WorldRefType,
+};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    // Entities spawned & despawned since the counts were last reset
+    spawns: usize,
+    despawns: usize,
+}
+
+impl TestClient {
+    fn new(socket: LocalClientSocket) -> Self {
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            spawns: 0,
+            despawns: 0,
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        self.spawns += events.read::<ClientSpawnEntityEvent>().count();
+        self.despawns += events.read::<ClientDespawnEntityEvent>().count();
+    }
+
+    fn positions(&self) -> Vec<u8> {
+        let world = self.world.proxy();
+        self.client
+            .entities(&world)
+            .iter()
+            .filter_map(|entity| {
+                world
+                    .component::<Position>(entity)
+                    .map(|position| *position.x)
+            })
+            .collect()
+    }
+
+    // overwrites the local copy of the Position, as a corrupted packet might
+    fn corrupt(&mut self, x: u8) {
+        let entities = self.client.entities(&self.world.proxy());
+        let mut world = self.world.proxy_mut();
+        let mut position = world.component_mut::<Position>(&entities[0]).unwrap();
+        let mut writer = BitWriter::new();
+        x.ser(&mut writer);
+        let bytes = writer.to_bytes();
+        position.x.read(&mut BitReader::new(&bytes)).unwrap();
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+        }
+    }
+
+    fn spawn(&mut self, x: u8) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.world.proxy_mut())
+            .insert_component(Position::new_complete(x))
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn resync_restores_a_corrupted_client_copy() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let entity = server.spawn(7);
+
+    let (socket, corrupted_address) = network.add_client();
+    let mut clients = vec![
+        TestClient::new(socket),
+        TestClient::new(network.add_client().0),
+    ];
+    update_until(&mut server, &mut clients, |clients| {
+        clients.iter().all(|client| client.positions() == vec![7])
+    });
+
+    clients[0].corrupt(99);
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+    // nothing changed on the Server, so nothing corrects the Client
+    assert_eq!(clients[0].positions(), vec![99]);
+
+    for client in clients.iter_mut() {
+        client.spawns = 0;
+        client.despawns = 0;
+    }
+    let Some(user_key) = server
+        .server
+        .user_keys()
+        .into_iter()
+        .find(|user_key| server.server.user(user_key).address() == corrupted_address)
+    else {
+        panic!("no User for the corrupted Client");
+    };
+    assert!(server.server.resync_entity(&user_key, &entity).is_ok());
+
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].positions() == vec![7] && clients[0].spawns == 1
+    });
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+
+    assert_eq!(clients[0].despawns, 1);
+    assert_eq!(clients[0].positions(), vec![7]);
+
+    // the other Client's copy is left alone
+    assert_eq!(clients[1].spawns, 0);
+    assert_eq!(clients[1].despawns, 0);
+    assert_eq!(clients[1].positions(), vec![7]);
+}
+
+#[test]
+fn resync_of_out_of_scope_entity_is_an_error() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(network.add_client().0)];
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].client.connection_status().is_connected()
+    });
+
+    // spawned outside of the Room, so never in scope
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(7))
+        .id();
+    let user_key = server.server.user_keys()[0];
+    assert!(server.server.resync_entity(&user_key, &entity).is_err());
+}
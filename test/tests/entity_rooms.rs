@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use naia_demo_world::{Entity, World};
+use naia_server::{Server, ServerConfig};
+use naia_shared::{Property, Protocol, Replicate};
+use naia_test::Auth;
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+#[test]
+fn entity_rooms_follow_room_membership() {
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    let mut world = World::default();
+    let entity = server
+        .spawn_entity(world.proxy_mut())
+        .insert_component(Position::new_complete(1))
+        .id();
+    assert!(server.entity_rooms(&entity).is_empty());
+
+    let room_a = server.make_room().key();
+    let room_b = server.make_room().key();
+    server.room_mut(&room_a).add_entity(&entity);
+    server.room_mut(&room_b).add_entity(&entity);
+
+    let rooms = server.entity_rooms(&entity);
+    assert_eq!(rooms.len(), 2);
+    assert!(rooms.contains(&room_a));
+    assert!(rooms.contains(&room_b));
+
+    server.room_mut(&room_a).remove_entity(&entity);
+    let rooms = server.entity_rooms(&entity);
+    assert_eq!(rooms.len(), 1);
+    assert!(rooms.contains(&room_b));
+
+    // a despawned Entity belongs to no Room
+    server.entity_mut(world.proxy_mut(), &entity).despawn();
+    assert!(server.entity_rooms(&entity).is_empty());
+}
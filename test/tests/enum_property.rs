@@ -0,0 +1,201 @@
+use std::sync::{Arc, Mutex};
+
+use naia_shared::{
+    BitReader, BitWriter, DiffMask, EnumProperty, FakeEntityConverter, Property, PropertyMutate,
+    PropertyMutator, Replicate, Serde,
+};
+
+#[derive(Serde, Clone, PartialEq, Debug)]
+pub enum WeaponState {
+    Idle,
+    Reloading { progress: u8, rounds: u8 },
+    Firing(u16),
+}
+
+#[derive(Serde, Clone, PartialEq, Debug)]
+pub struct Target {
+    pub x: u16,
+    pub y: u16,
+}
+
+#[derive(Replicate)]
+pub struct Weapon {
+    pub ammo: Property<u8>,
+    pub state: EnumProperty<WeaponState>,
+    pub target: Property<Option<Target>>,
+    pub heat: Property<u8>,
+}
+
+// records which DiffMask bits were mutated
+#[derive(Clone, Default)]
+struct RecordingMutator {
+    mutated: Arc<Mutex<Vec<u8>>>,
+}
+
+impl RecordingMutator {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.mutated.lock().unwrap())
+    }
+}
+
+impl PropertyMutate for RecordingMutator {
+    fn mutate(&mut self, property_index: u8) -> bool {
+        self.mutated.lock().unwrap().push(property_index);
+        true
+    }
+}
+
+fn reloading(progress: u8) -> WeaponState {
+    WeaponState::Reloading {
+        progress,
+        rounds: 30,
+    }
+}
+
+fn host_weapon() -> (Weapon, RecordingMutator) {
+    let mutator = RecordingMutator::default();
+    let mut weapon = Weapon::new_complete(12, reloading(0), None, 0);
+    weapon.set_mutator(&PropertyMutator::new(mutator.clone()));
+    (weapon, mutator)
+}
+
+// creates the remote copy of a host Weapon, as it would be on first replication
+fn remote_weapon(host: &Weapon) -> Box<dyn Replicate> {
+    let mut writer = BitWriter::new();
+    host.write_fields(&mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    Weapon::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+}
+
+fn diff_mask(bits: &[u8]) -> DiffMask {
+    let mut diff_mask = DiffMask::new(1);
+    for bit in bits {
+        diff_mask.set_bit(*bit, true);
+    }
+    diff_mask
+}
+
+// writes an update of the given bits, returning it & how many bits it took
+fn write_update(weapon: &Weapon, bits: &[u8]) -> (Box<[u8]>, u32) {
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    weapon.write_update(&diff_mask(bits), &mut writer, &mut FakeEntityConverter);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+fn apply_update(remote: &mut Box<dyn Replicate>, bytes: &[u8]) {
+    let mut reader = BitReader::new(bytes);
+    let update = Weapon::create_builder()
+        .read_create_update(&mut reader)
+        .unwrap();
+    remote
+        .read_apply_update(&FakeEntityConverter, update)
+        .unwrap();
+}
+
+fn as_weapon(remote: &dyn Replicate) -> &Weapon {
+    remote.to_any().downcast_ref::<Weapon>().unwrap()
+}
+
+#[test]
+fn enum_property_occupies_a_bit_per_variant_field() {
+    let (mut weapon, mutator) = host_weapon();
+
+    // ammo: 0, state: 1..=3, target: 4, heat: 5
+    assert_eq!(EnumProperty::<WeaponState>::DIFF_BITS, 3);
+    assert_eq!(weapon.diff_mask_size(), 1);
+
+    *weapon.ammo = 11;
+    assert_eq!(mutator.take(), vec![0]);
+
+    weapon.state.modify(|state| {
+        if let WeaponState::Reloading { progress, .. } = state {
+            *progress = 50;
+        }
+    });
+    assert_eq!(mutator.take(), vec![2]);
+
+    weapon.state.set(reloading(50));
+    assert!(mutator.take().is_empty());
+
+    weapon.state.set(WeaponState::Firing(3));
+    assert_eq!(mutator.take(), vec![1]);
+
+    *weapon.target = Some(Target { x: 1, y: 2 });
+    assert_eq!(mutator.take(), vec![4]);
+
+    *weapon.heat = 9;
+    assert_eq!(mutator.take(), vec![5]);
+}
+
+#[test]
+fn field_update_is_smaller_than_full_variant() {
+    let (mut weapon, mutator) = host_weapon();
+    let mut remote = remote_weapon(&weapon);
+
+    // changing one field of the active variant only sends that field
+    weapon.state.set(reloading(50));
+    let field_bits = mutator.take();
+    let (bytes, field_update_bits) = write_update(&weapon, &field_bits);
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_weapon(remote.as_ref()).state, reloading(50));
+
+    // changing the variant sends the whole new variant
+    weapon.state.set(WeaponState::Firing(3));
+    let (bytes, _) = write_update(&weapon, &mutator.take());
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_weapon(remote.as_ref()).state, WeaponState::Firing(3));
+
+    // switching back sends every field, which costs more than a single one
+    weapon.state.set(reloading(50));
+    let (_, full_update_bits) = write_update(&weapon, &mutator.take());
+    assert!(field_update_bits < full_update_bits);
+}
+
+#[test]
+fn field_update_for_stale_variant_is_discarded() {
+    let (mut weapon, mutator) = host_weapon();
+    let mut remote = remote_weapon(&weapon);
+
+    // an update to a field of `Reloading` is written, but not yet delivered
+    weapon.state.set(reloading(50));
+    let (stale_bytes, _) = write_update(&weapon, &mutator.take());
+
+    // meanwhile the variant changes, and that is delivered first
+    weapon.state.set(WeaponState::Idle);
+    let (bytes, _) = write_update(&weapon, &mutator.take());
+    apply_update(&mut remote, &bytes);
+
+    apply_update(&mut remote, &stale_bytes);
+    assert_eq!(*as_weapon(remote.as_ref()).state, WeaponState::Idle);
+}
+
+#[test]
+fn all_properties_round_trip() {
+    let (mut weapon, mutator) = host_weapon();
+    let mut remote = remote_weapon(&weapon);
+
+    *weapon.ammo = 3;
+    weapon.state.set(WeaponState::Firing(700));
+    *weapon.target = Some(Target { x: 40, y: 41 });
+    *weapon.heat = 90;
+    let (bytes, _) = write_update(&weapon, &mutator.take());
+    apply_update(&mut remote, &bytes);
+
+    let remote_weapon = as_weapon(remote.as_ref());
+    assert_eq!(*remote_weapon.ammo, 3);
+    assert_eq!(*remote_weapon.state, WeaponState::Firing(700));
+    assert_eq!(*remote_weapon.target, Some(Target { x: 40, y: 41 }));
+    assert_eq!(*remote_weapon.heat, 90);
+
+    // Option-of-struct Properties send the whole value, including clearing it
+    *weapon.target = None;
+    let (bytes, _) = write_update(&weapon, &mutator.take());
+    apply_update(&mut remote, &bytes);
+    assert_eq!(*as_weapon(remote.as_ref()).target, None);
+}
@@ -0,0 +1,82 @@
+use naia_shared::{
+    BitReader, BitWriter, DiffMask, EntityAuthStatus, FakeEntityConverter, HostAuthHandler,
+    HostType, Property, PropertyMutate, PropertyMutator, Replicate, Serde,
+};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+    pub y: Property<u8>,
+}
+
+#[derive(Clone)]
+struct NullMutator;
+
+impl PropertyMutate for NullMutator {
+    fn mutate(&mut self, _property_index: u8) -> bool {
+        true
+    }
+}
+
+// creates a Position which is Delegated to, and currently Granted to, a Client
+fn granted_position(auth_handler: &mut HostAuthHandler<u32>) -> Position {
+    let accessor = auth_handler.register_entity(HostType::Client, &0);
+    auth_handler.set_auth_status(&0, EntityAuthStatus::Granted);
+
+    let mut position = Position::new_complete(0, 0);
+    position.set_mutator(&PropertyMutator::new(NullMutator));
+    position.enable_delegation(&accessor, None);
+    position
+}
+
+// writes an update of every field, then reads back which fields were written
+fn write_full_update(position: &Position) -> (Option<u8>, Option<u8>) {
+    let mut diff_mask = DiffMask::new(position.diff_mask_size());
+    diff_mask.fill();
+
+    let mut writer = BitWriter::new();
+    position.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let mut read_field = || {
+        if bool::de(&mut reader).unwrap() {
+            Some(u8::de(&mut reader).unwrap())
+        } else {
+            None
+        }
+    };
+    let x = read_field();
+    let y = read_field();
+    (x, y)
+}
+
+#[test]
+fn unrestricted_delegated_component_writes_all_fields() {
+    let mut auth_handler = HostAuthHandler::new();
+    let mut position = granted_position(&mut auth_handler);
+
+    *position.x = 5;
+    *position.y = 7;
+
+    assert_eq!(write_full_update(&position), (Some(5), Some(7)));
+}
+
+#[test]
+fn only_authorized_field_is_written() {
+    let mut auth_handler = HostAuthHandler::new();
+    let mut position = granted_position(&mut auth_handler);
+
+    let mut field_authority = DiffMask::new(position.diff_mask_size());
+    field_authority.set_bit(0, true);
+    position.set_field_authority(Some(&field_authority));
+
+    *position.x = 5;
+    *position.y = 7;
+
+    assert_eq!(write_full_update(&position), (Some(5), None));
+
+    // lifting the restriction permits the other field again
+    position.set_field_authority(None);
+    assert_eq!(write_full_update(&position), (Some(5), Some(7)));
+}
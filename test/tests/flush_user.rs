@@ -0,0 +1,135 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, MessageEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::{default_channels::OrderedReliableChannel, Message, Protocol};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Message)]
+pub struct MatchStart {
+    pub round: u8,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_message::<MatchStart>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_keys: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            user_keys: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.user_keys.push(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    rounds: Vec<u8>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, _) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            rounds: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        for match_start in events.read::<MessageEvent<OrderedReliableChannel, MatchStart>>() {
+            self.rounds.push(match_start.round);
+        }
+    }
+}
+
+#[test]
+fn flushed_message_arrives_before_the_next_send_all_updates() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = [TestClient::new(&network), TestClient::new(&network)];
+    for _ in 0..400 {
+        if server.user_keys.len() == 2
+            && clients
+                .iter()
+                .all(|client| client.client.connection_status().is_connected())
+        {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        for client in clients.iter_mut() {
+            client.update();
+        }
+        server.update();
+    }
+    assert_eq!(server.user_keys.len(), 2);
+
+    let user_key = server.user_keys[0];
+    server
+        .server
+        .send_message::<OrderedReliableChannel, MatchStart>(&user_key, &MatchStart { round: 1 });
+    server
+        .server
+        .flush_user(server.world.proxy(), &user_key)
+        .expect("user is connected");
+
+    // no `send_all_updates()` from here on, only the Clients read
+    for _ in 0..20 {
+        sleep(Duration::from_millis(5));
+        for client in clients.iter_mut() {
+            client.update();
+        }
+        if clients.iter().any(|client| !client.rounds.is_empty()) {
+            break;
+        }
+    }
+
+    let flushed_client = clients
+        .iter()
+        .position(|client| client.rounds == vec![1])
+        .expect("flushed Message never arrived");
+    assert!(clients
+        .iter()
+        .enumerate()
+        .all(|(index, client)| index == flushed_client || client.rounds.is_empty()));
+}
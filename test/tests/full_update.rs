@@ -0,0 +1,139 @@
+use std::sync::{Arc, Mutex};
+
+use naia_shared::{
+    BitReader, BitWriter, ComponentKind, DiffMask, FakeEntityConverter, Property, PropertyMutate,
+    PropertyMutator, Protocol, Replicate,
+};
+
+#[derive(Replicate)]
+#[replicate(full_update)]
+pub struct Position {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+    pub z: Property<i16>,
+}
+
+#[derive(Replicate)]
+pub struct Velocity {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+    pub z: Property<i16>,
+}
+
+// records which DiffMask bits were mutated
+#[derive(Clone, Default)]
+struct RecordingMutator {
+    mutated: Arc<Mutex<Vec<u8>>>,
+}
+
+impl RecordingMutator {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.mutated.lock().unwrap())
+    }
+}
+
+impl PropertyMutate for RecordingMutator {
+    fn mutate(&mut self, property_index: u8) -> bool {
+        self.mutated.lock().unwrap().push(property_index);
+        true
+    }
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_component::<Position>()
+        .add_component::<Velocity>()
+        .build()
+}
+
+// creates a remote copy of the given Component, as it would be on replication
+fn remote_copy<C: Replicate>(component: &C) -> Box<dyn Replicate> {
+    let mut writer = BitWriter::new();
+    component.write_fields(&mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    C::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+}
+
+// writes an update of the mutated bits as the host would, then applies it to
+// the observer's remote copy
+fn apply_update<C: Replicate>(
+    protocol: &Protocol,
+    host: &C,
+    observer: &mut Box<dyn Replicate>,
+    bits: &[u8],
+) {
+    let mut diff_mask = DiffMask::new(host.diff_mask_size());
+    for bit in bits {
+        diff_mask.set_bit(*bit, true);
+    }
+    if protocol.component_kinds.is_full_update(&host.kind()) {
+        diff_mask.fill();
+    }
+
+    let mut writer = BitWriter::new();
+    host.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let update = C::create_builder().read_create_update(&mut reader).unwrap();
+    observer
+        .read_apply_update(&FakeEntityConverter, update)
+        .unwrap();
+}
+
+#[test]
+fn full_update_is_wired_through_component_kinds() {
+    let protocol = protocol();
+
+    assert!(protocol
+        .component_kinds
+        .is_full_update(&ComponentKind::of::<Position>()));
+    assert!(!protocol
+        .component_kinds
+        .is_full_update(&ComponentKind::of::<Velocity>()));
+}
+
+#[test]
+fn full_update_contains_all_fields() {
+    let protocol = protocol();
+    let mutator = RecordingMutator::default();
+    let mut host = Position::new_complete(1, 2, 3);
+    host.set_mutator(&PropertyMutator::new(mutator.clone()));
+
+    // whichever field changes, an observer with no prior state receives all
+    for field in 0..3 {
+        match field {
+            0 => *host.x += 10,
+            1 => *host.y += 10,
+            _ => *host.z += 10,
+        }
+        // as if the observer joined late, and missed every prior update
+        let mut observer = remote_copy(&Position::new_complete(0, 0, 0));
+        apply_update(&protocol, &host, &mut observer, &mutator.take());
+
+        let observer = observer.to_any().downcast_ref::<Position>().unwrap();
+        assert_eq!(
+            (*observer.x, *observer.y, *observer.z),
+            (*host.x, *host.y, *host.z)
+        );
+    }
+}
+
+#[test]
+fn delta_update_contains_changed_fields_only() {
+    let protocol = protocol();
+    let mutator = RecordingMutator::default();
+    let mut host = Velocity::new_complete(1, 2, 3);
+    host.set_mutator(&PropertyMutator::new(mutator.clone()));
+
+    *host.y = 20;
+    let mut observer = remote_copy(&Velocity::new_complete(0, 0, 0));
+    apply_update(&protocol, &host, &mut observer, &mutator.take());
+
+    let observer = observer.to_any().downcast_ref::<Velocity>().unwrap();
+    assert_eq!((*observer.x, *observer.y, *observer.z), (0, 20, 0));
+}
@@ -0,0 +1,194 @@
+use std::{fs, net::SocketAddr, path::PathBuf, thread::sleep, time::Duration};
+
+use naia_client::{fuzz_targets::read_client_data_packet, Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    fuzz_targets::{read_handshake, read_server_data_packet},
+    AuthEvent, ConnectEvent, RoomKey, Server, ServerConfig, UserKey,
+};
+use naia_shared::{
+    default_channels::{
+        OrderedReliableChannel, SequencedUnreliableChannel, TickBufferedChannel,
+        UnorderedReliableChannel,
+    },
+    BitReader, PacketType, Protocol, Serde, StandardHeader, WorldMutType,
+};
+use naia_test::{fuzz_protocol, Auth, FuzzBlob, FuzzPosition, LocalNetwork};
+
+// Packets recorded from a short session exercising messages, fragments,
+// tick-buffered messages & replication in both directions
+struct Recording {
+    server_received: Vec<Vec<u8>>,
+    client_received: Vec<Vec<u8>>,
+}
+
+fn record_session() -> Recording {
+    let network = LocalNetwork::new();
+
+    let mut server = Server::<Entity>::new(ServerConfig::default(), fuzz_protocol());
+    server.listen(network.server_socket());
+    let room_key: RoomKey = server.make_room().key();
+    let mut server_world = World::default();
+
+    let (socket, address): (_, SocketAddr) = network.add_client();
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        fuzz_protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(socket);
+    let mut client_world = World::default();
+
+    let mut user_key: Option<UserKey> = None;
+    let mut sent = false;
+    let mut server_entity: Option<Entity> = None;
+    for step in 0..200 {
+        sleep(Duration::from_millis(5));
+        client.receive(client_world.proxy_mut());
+
+        let mut events = server.receive(server_world.proxy_mut());
+        for (key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&key);
+            server.room_mut(&room_key).add_user(&key);
+        }
+        for key in events.read::<ConnectEvent>() {
+            user_key = Some(key);
+        }
+
+        if let (Some(user_key), false) = (user_key, sent) {
+            if client.connection_status().is_connected() {
+                sent = true;
+
+                let entity = server
+                    .spawn_entity(server_world.proxy_mut())
+                    .insert_component(FuzzPosition::new_complete(7, "seed".to_string()))
+                    .id();
+                server.room_mut(&room_key).add_entity(&entity);
+                server_entity = Some(entity);
+                server.send_message::<OrderedReliableChannel, _>(&user_key, &FuzzBlob::new(2000));
+                server.send_message::<SequencedUnreliableChannel, _>(&user_key, &FuzzBlob::new(8));
+
+                client
+                    .spawn_entity(client_world.proxy_mut())
+                    .insert_component(FuzzPosition::new_complete(3, "client".to_string()));
+                client.send_message::<UnorderedReliableChannel, _>(&FuzzBlob::new(2000));
+                client.send_message::<SequencedUnreliableChannel, _>(&FuzzBlob::new(8));
+            }
+        }
+        if sent && step % 10 == 0 {
+            if let Some(tick) = client.client_tick() {
+                client.send_tick_buffer_message::<TickBufferedChannel, _>(&tick, &FuzzBlob::new(4));
+            }
+            if let Some(entity) = server_entity {
+                let mut world = server_world.proxy_mut();
+                let mut position = world.component_mut::<FuzzPosition>(&entity).unwrap();
+                *position.x = step as u8;
+            }
+        }
+
+        for (_, user_key, entity) in server.scope_checks() {
+            server.user_scope_mut(&user_key).include(&entity);
+        }
+        server.send_all_updates(server_world.proxy());
+    }
+    assert!(sent, "Client never connected");
+
+    Recording {
+        server_received: network.server_received(),
+        client_received: network.client_received(&address),
+    }
+}
+
+fn packet_type(bytes: &[u8]) -> Option<PacketType> {
+    let mut reader = BitReader::new(bytes);
+    StandardHeader::de(&mut reader)
+        .ok()
+        .map(|header| header.packet_type)
+}
+
+// The packets each fuzz target is seeded with
+fn seeds(recording: &Recording) -> Vec<(&'static str, Vec<Vec<u8>>)> {
+    let of_type = |packets: &Vec<Vec<u8>>, packet_type_wanted: PacketType| {
+        packets
+            .iter()
+            .filter(|bytes| packet_type(bytes) == Some(packet_type_wanted))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    vec![
+        (
+            "server_data_packet",
+            of_type(&recording.server_received, PacketType::Data),
+        ),
+        (
+            "client_data_packet",
+            of_type(&recording.client_received, PacketType::Data),
+        ),
+        (
+            "handshake",
+            of_type(&recording.server_received, PacketType::Handshake),
+        ),
+    ]
+}
+
+fn run_target(target: &str, protocol: &Protocol, bytes: &[u8]) -> bool {
+    match target {
+        "server_data_packet" => read_server_data_packet(protocol, bytes).is_ok(),
+        "client_data_packet" => read_client_data_packet(protocol, bytes).is_ok(),
+        "handshake" => read_handshake(protocol, bytes).is_ok(),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn recorded_packets_parse() {
+    let recording = record_session();
+    let protocol = fuzz_protocol();
+    for (target, packets) in seeds(&recording) {
+        assert!(!packets.is_empty(), "no {} packets recorded", target);
+        for bytes in packets {
+            assert!(run_target(target, &protocol, &bytes), "{} rejected", target);
+        }
+    }
+}
+
+#[test]
+fn malformed_packets_are_rejected_without_panicking() {
+    let recording = record_session();
+    let protocol = fuzz_protocol();
+    for (target, packets) in seeds(&recording) {
+        for bytes in packets.iter().take(20) {
+            // truncated
+            for len in 0..bytes.len() {
+                run_target(target, &protocol, &bytes[..len]);
+            }
+            // corrupted
+            for bit in 0..(bytes.len() * 8).min(512) {
+                let mut corrupted = bytes.clone();
+                corrupted[bit / 8] ^= 1 << (bit % 8);
+                run_target(target, &protocol, &corrupted);
+            }
+        }
+    }
+}
+
+/// Rewrites `fuzz/corpus` from a fresh recording. Run with
+/// `cargo test -p naia-test --test fuzz_corpus -- --ignored`
+#[test]
+#[ignore]
+fn write_seed_corpus() {
+    let recording = record_session();
+    let corpus_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../fuzz/corpus");
+    for (target, packets) in seeds(&recording) {
+        let target_dir = corpus_dir.join(target);
+        let _ = fs::remove_dir_all(&target_dir);
+        fs::create_dir_all(&target_dir).unwrap();
+        for (index, bytes) in packets.iter().enumerate() {
+            fs::write(target_dir.join(format!("seed-{:03}", index)), bytes).unwrap();
+        }
+    }
+}
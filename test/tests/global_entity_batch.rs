@@ -0,0 +1,80 @@
+use std::cell::Cell;
+
+use naia_demo_world::{Entity, World};
+use naia_server::{Server, ServerConfig};
+use naia_shared::{
+    BigMapKey, EntityAndGlobalEntityConverter, EntityDoesNotExistError, GlobalEntity, Protocol,
+};
+
+// Maps GlobalEntity n to n * 10, for n < 5, and counts the lookups made
+#[derive(Default)]
+struct CountingConverter {
+    lookups: Cell<usize>,
+}
+
+impl EntityAndGlobalEntityConverter<u64> for CountingConverter {
+    fn global_entity_to_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<u64, EntityDoesNotExistError> {
+        self.lookups.set(self.lookups.get() + 1);
+        let value = global_entity.to_u64();
+        if value < 5 {
+            Ok(value * 10)
+        } else {
+            Err(EntityDoesNotExistError)
+        }
+    }
+
+    fn entity_to_global_entity(&self, _: &u64) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+}
+
+#[test]
+fn batch_conversion_looks_up_each_entity_once() {
+    let converter = CountingConverter::default();
+    let global_entities: Vec<GlobalEntity> = (0..8).map(GlobalEntity::from_u64).collect();
+
+    let entities = converter.global_entities_to_entities(&global_entities);
+    assert_eq!(converter.lookups.get(), global_entities.len());
+    assert_eq!(
+        entities,
+        vec![
+            Some(0),
+            Some(10),
+            Some(20),
+            Some(30),
+            Some(40),
+            None,
+            None,
+            None
+        ]
+    );
+}
+
+#[test]
+fn batch_conversion_matches_individual_conversion() {
+    let mut server = Server::<Entity>::new(ServerConfig::default(), Protocol::builder().build());
+    let mut world = World::default();
+
+    let mut global_entities = Vec::new();
+    for _ in 0..16 {
+        let entity = server.spawn_entity(world.proxy_mut()).id();
+        global_entities.push(server.entity_to_global_entity(&entity).unwrap());
+    }
+    // one despawned Entity, and one which never existed
+    let despawned = server.spawn_entity(world.proxy_mut()).id();
+    global_entities.push(server.entity_to_global_entity(&despawned).unwrap());
+    server.entity_mut(world.proxy_mut(), &despawned).despawn();
+    global_entities.push(GlobalEntity::from_u64(u64::MAX - 1));
+
+    let individually: Vec<Option<Entity>> = global_entities
+        .iter()
+        .map(|global_entity| server.global_entity_to_entity(global_entity).ok())
+        .collect();
+    let batched = server.global_entities_to_entities(&global_entities);
+
+    assert!(batched == individually);
+    assert_eq!(batched.iter().filter(|entity| entity.is_none()).count(), 2);
+}
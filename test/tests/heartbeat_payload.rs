@@ -0,0 +1,179 @@
+use std::{sync::Arc, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, HeartbeatPayloadEvent as ClientHeartbeatPayloadEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, HeartbeatPayloadEvent as ServerHeartbeatPayloadEvent, Server, ServerConfig,
+};
+use naia_shared::{
+    set_thread_clock, BitReader, BitWriter, ConnectionConfig, HeartbeatPayload, Message, Protocol,
+};
+use naia_test::{Auth, LocalTransport, TestClock};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Message)]
+pub struct Status {
+    pub sequence: u16,
+}
+
+#[derive(Message)]
+pub struct Essay {
+    pub text: String,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_message::<Status>()
+        .add_message::<Essay>()
+        .build()
+}
+
+fn essay() -> Essay {
+    Essay {
+        text: "a".repeat(100),
+    }
+}
+
+// a provider counting up from 0
+fn counting_provider() -> impl FnMut() -> Option<Status> + Send + Sync {
+    let mut sequence = 0;
+    move || {
+        let status = Status { sequence };
+        sequence += 1;
+        Some(status)
+    }
+}
+
+#[test]
+fn oversized_payload_is_rejected_at_registration() {
+    let Err(error) = HeartbeatPayload::new(HEARTBEAT_INTERVAL, || Some(essay())) else {
+        panic!("expected a 100 byte payload to be rejected");
+    };
+    assert_eq!(error.name, "Essay");
+    assert!(error.bytes > 64);
+
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    assert!(server.set_heartbeat_payload(|| Some(essay())).is_err());
+    assert!(server
+        .set_heartbeat_payload(|| Some(Status { sequence: 0 }))
+        .is_ok());
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    assert!(client.set_heartbeat_payload(|| Some(essay())).is_err());
+    assert!(client
+        .set_heartbeat_payload(|| Some(Status { sequence: 0 }))
+        .is_ok());
+}
+
+#[test]
+fn payload_is_due_once_per_heartbeat_interval() {
+    let clock = Arc::new(TestClock::new());
+    set_thread_clock(Some(clock.clone()));
+
+    let protocol = protocol();
+    let mut payload = HeartbeatPayload::new(HEARTBEAT_INTERVAL, counting_provider()).unwrap();
+
+    let mut sequences = Vec::new();
+    for _ in 0..3 {
+        clock.advance(HEARTBEAT_INTERVAL - Duration::from_millis(1));
+        assert!(!payload.should_send());
+        clock.advance(Duration::from_millis(2));
+        assert!(payload.should_send());
+
+        let mut writer = BitWriter::new();
+        HeartbeatPayload::write(Some(&mut payload), &protocol.message_kinds, &mut writer);
+        payload.mark_sent();
+
+        let bytes = writer.to_bytes();
+        let mut reader = BitReader::new(&bytes);
+        let container = HeartbeatPayload::read(&protocol.message_kinds, &mut reader)
+            .unwrap()
+            .unwrap();
+        let status = container.to_boxed_any().downcast::<Status>().unwrap();
+        sequences.push(status.sequence);
+    }
+    // the first payload is the one produced at registration
+    assert_eq!(sequences, vec![0, 1, 2]);
+
+    set_thread_clock(None);
+}
+
+#[test]
+fn heartbeat_without_payload_reads_as_none() {
+    let protocol = protocol();
+    let mut writer = BitWriter::new();
+    HeartbeatPayload::write(None, &protocol.message_kinds, &mut writer);
+
+    let bytes = writer.to_bytes();
+    let mut reader = BitReader::new(&bytes);
+    assert!(HeartbeatPayload::read(&protocol.message_kinds, &mut reader)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn payloads_flow_both_ways_at_the_heartbeat_interval() {
+    let connection = ConnectionConfig {
+        heartbeat_interval: HEARTBEAT_INTERVAL,
+        ..Default::default()
+    };
+    let transport = LocalTransport::new();
+
+    let mut server_world = World::default();
+    let mut server = Server::<Entity>::new(
+        ServerConfig {
+            connection: connection.clone(),
+            ..Default::default()
+        },
+        protocol(),
+    );
+    server.listen(transport.server_socket());
+    server.set_heartbeat_payload(counting_provider()).unwrap();
+
+    let mut client_world = World::default();
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            connection,
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(transport.client_socket());
+    client.set_heartbeat_payload(counting_provider()).unwrap();
+
+    let mut server_received = Vec::new();
+    let mut client_received = Vec::new();
+    for _ in 0..400 {
+        if server_received.len() >= 3 && client_received.len() >= 3 {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+
+        let mut events = client.receive(client_world.proxy_mut());
+        for status in events.read::<ClientHeartbeatPayloadEvent<Status>>() {
+            client_received.push(status.sequence);
+        }
+
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        for (_, status) in events.read::<ServerHeartbeatPayloadEvent<Status>>() {
+            server_received.push(status.sequence);
+        }
+        server.send_all_updates(server_world.proxy());
+    }
+
+    // payloads sent before the connection was established are lost, but
+    // each one after is delivered in order
+    assert!(server_received.len() >= 3, "{:?}", server_received);
+    assert!(client_received.len() >= 3, "{:?}", client_received);
+    assert!(server_received.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(client_received.windows(2).all(|pair| pair[0] < pair[1]));
+}
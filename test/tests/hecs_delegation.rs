@@ -0,0 +1,272 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use hecs::{Entity, World};
+
+use naia_hecs_client::{
+    Client, ClientConfig, ConnectEvent, EntityAuthGrantedEvent, EntityAuthResetEvent,
+    ReplicationConfig as ClientReplicationConfig, WorldWrapper as ClientWorld,
+};
+use naia_hecs_server::{
+    AuthEvent, DelegateEntityEvent, EntityAuthGrantEvent, PublishEntityEvent, ReplicationConfig,
+    RoomKey, Server, ServerConfig, SpawnEntityEvent, WorldWrapper as ServerWorld,
+};
+use naia_hecs_shared::{EntityAuthStatus, Property, Protocol, Replicate};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: ClientWorld,
+    connected: bool,
+    granted: Vec<Entity>,
+    reset: Vec<Entity>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, _): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut protocol = protocol();
+        let world = protocol.wrap_world(World::new());
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol,
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world,
+            connected: false,
+            granted: Vec::new(),
+            reset: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(&mut self.world);
+        if events.read::<ConnectEvent>().next().is_some() {
+            self.connected = true;
+        }
+        self.granted.extend(events.read::<EntityAuthGrantedEvent>());
+        self.reset.extend(events.read::<EntityAuthResetEvent>());
+    }
+
+    // spawns a plain hecs Entity, then publishes it
+    fn spawn_published(&mut self, x: u8) -> Entity {
+        use naia_hecs_client::WorldExt;
+
+        let entity = self.world.spawn((Position::new_complete(x),));
+        self.world.enable_replication(&mut self.client, &entity);
+        self.world.configure_replication(
+            &mut self.client,
+            &entity,
+            ClientReplicationConfig::Public,
+        );
+        entity
+    }
+
+    fn delegate(&mut self, entity: &Entity) {
+        use naia_hecs_client::WorldExt;
+
+        self.world.configure_replication(
+            &mut self.client,
+            entity,
+            ClientReplicationConfig::Delegated,
+        );
+    }
+
+    fn request_authority(&mut self, entity: &Entity) {
+        use naia_hecs_client::WorldExt;
+
+        self.world.request_authority(&mut self.client, entity);
+    }
+
+    fn authority(&self, entity: &Entity) -> Option<EntityAuthStatus> {
+        use naia_hecs_client::WorldExt;
+
+        self.world.authority(&self.client, entity)
+    }
+
+    fn x(&self, entity: &Entity) -> u8 {
+        *self.world.get::<&Position>(*entity).unwrap().x
+    }
+
+    fn set_x(&mut self, entity: &Entity, x: u8) {
+        *self.world.get::<&mut Position>(*entity).unwrap().x = x;
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: ServerWorld,
+    room_key: RoomKey,
+    spawned: Vec<Entity>,
+    published: Vec<Entity>,
+    delegated: Vec<Entity>,
+    granted: Vec<Entity>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut protocol = protocol();
+        let world = protocol.wrap_world(World::new());
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol);
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world,
+            room_key,
+            spawned: Vec::new(),
+            published: Vec::new(),
+            delegated: Vec::new(),
+            granted: Vec::new(),
+        }
+    }
+
+    fn x(&self, entity: &Entity) -> u8 {
+        *self.world.get::<&Position>(*entity).unwrap().x
+    }
+
+    fn set_x(&mut self, entity: &Entity, x: u8) {
+        *self.world.get::<&mut Position>(*entity).unwrap().x = x;
+    }
+
+    fn replication_config(&self, entity: &Entity) -> Option<ReplicationConfig> {
+        use naia_hecs_server::WorldExt;
+
+        self.world.replication_config(&self.server, entity)
+    }
+
+    fn take_authority(&mut self, entity: &Entity) {
+        use naia_hecs_server::WorldExt;
+
+        self.world.take_authority(&mut self.server, entity);
+    }
+
+    fn authority(&self, entity: &Entity) -> Option<EntityAuthStatus> {
+        use naia_hecs_server::WorldExt;
+
+        self.world.authority(&self.server, entity)
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(&mut self.world);
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, entity) in events.read::<SpawnEntityEvent>() {
+            self.spawned.push(entity);
+        }
+        for (_, entity) in events.read::<PublishEntityEvent>() {
+            self.server.room_mut(&self.room_key).add_entity(&entity);
+            self.published.push(entity);
+        }
+        for (_, entity) in events.read::<DelegateEntityEvent>() {
+            self.delegated.push(entity);
+        }
+        for (_, entity) in events.read::<EntityAuthGrantEvent>() {
+            self.granted.push(entity);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(&self.world);
+    }
+}
+
+fn update(server: &mut TestServer, client: &mut TestClient) {
+    sleep(Duration::from_millis(5));
+    client.update();
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    done: impl Fn(&TestServer, &TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        update(server, client);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn hecs_client_delegates_and_regains_authority() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut client = TestClient::new(&network);
+    update_until(&mut server, &mut client, |_, client| client.connected);
+
+    // client spawns and publishes
+    let client_entity = client.spawn_published(7);
+    update_until(&mut server, &mut client, |server, _| {
+        !server.published.is_empty()
+    });
+    let server_entity = server.published[0];
+    assert_eq!(server.spawned, vec![server_entity]);
+    assert_eq!(server.x(&server_entity), 7);
+
+    // client delegates, keeping authority
+    client.delegate(&client_entity);
+    update_until(&mut server, &mut client, |server, client| {
+        server.delegated.contains(&server_entity)
+            && client.authority(&client_entity) == Some(EntityAuthStatus::Granted)
+    });
+    assert_eq!(
+        server.replication_config(&server_entity),
+        Some(ReplicationConfig::Delegated)
+    );
+
+    // server takes authority back, and its writes reach the client
+    server.take_authority(&server_entity);
+    assert_eq!(
+        server.authority(&server_entity),
+        Some(EntityAuthStatus::Available)
+    );
+    update_until(&mut server, &mut client, |_, client| {
+        client.authority(&client_entity) == Some(EntityAuthStatus::Available)
+    });
+    assert!(client.reset.contains(&client_entity));
+    server.set_x(&server_entity, 9);
+    update_until(&mut server, &mut client, |_, client| {
+        client.x(&client_entity) == 9
+    });
+
+    // client regains authority
+    client.request_authority(&client_entity);
+    update_until(&mut server, &mut client, |server, client| {
+        client.authority(&client_entity) == Some(EntityAuthStatus::Granted)
+            && server.granted.contains(&server_entity)
+    });
+    assert!(client.granted.contains(&client_entity));
+
+    // client's writes reach the server again
+    client.set_x(&client_entity, 11);
+    update_until(&mut server, &mut client, |server, _| {
+        server.x(&server_entity) == 11
+    });
+}
@@ -0,0 +1,148 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{Property, Protocol, Replicate, WorldMutType, WorldRefType};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub id: Property<u8>,
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    room_key: RoomKey,
+    client: Client<Entity>,
+    client_world: World,
+}
+
+impl Test {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+
+        Self {
+            server,
+            server_world: World::default(),
+            room_key,
+            client,
+            client_world: World::default(),
+        }
+    }
+
+    fn update(&mut self) {
+        sleep(Duration::from_millis(5));
+        self.client.receive(self.client_world.proxy_mut());
+        let mut server_events = self.server.receive(self.server_world.proxy_mut());
+        for (user_key, _) in server_events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+    }
+
+    fn update_until(&mut self, done: impl Fn(&Self) -> bool) {
+        for _ in 0..400 {
+            if done(self) {
+                return;
+            }
+            self.update();
+        }
+        panic!("timed out");
+    }
+
+    fn spawn(&mut self, id: u8) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.server_world.proxy_mut())
+            .insert_component(Position::new_complete(id, 0))
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn set_x(&mut self, entity: &Entity, x: u8) {
+        *self
+            .server_world
+            .proxy_mut()
+            .component_mut::<Position>(entity)
+            .unwrap()
+            .x = x;
+    }
+
+    // the Client's copy of the Entity with the given id
+    fn client_entity(&self, id: u8) -> Option<Entity> {
+        let world = self.client_world.proxy();
+        self.client.entities(&world).into_iter().find(|entity| {
+            world
+                .component::<Position>(entity)
+                .is_some_and(|position| *position.id == id)
+        })
+    }
+
+    fn client_x(&self, id: u8) -> Option<u8> {
+        let entity = self.client_entity(id)?;
+        let world = self.client_world.proxy();
+        let position = world.component::<Position>(&entity)?;
+        Some(*position.x)
+    }
+}
+
+#[test]
+fn ignored_entity_is_skipped_then_resynced() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    let ignored = test.spawn(1);
+    let watched = test.spawn(2);
+    test.update_until(|test| test.client_x(1) == Some(0) && test.client_x(2) == Some(0));
+
+    // let the spawns' acks reach the Server, which only then tracks changes
+    for _ in 0..20 {
+        test.update();
+    }
+
+    let client_ignored = test.client_entity(1).unwrap();
+    test.client.ignore_entity(&client_ignored);
+    assert!(test.client.entity_is_ignored(&client_ignored));
+
+    // updates to both Entities share packets, so the watched Entity's are
+    // only read correctly if the ignored Entity's are skipped exactly
+    for x in 1..=10 {
+        test.set_x(&ignored, x);
+        test.set_x(&watched, x);
+        test.update_until(|test| test.client_x(2) == Some(x));
+    }
+    assert_eq!(test.client_x(1), Some(0));
+
+    test.client.unignore_entity(&client_ignored);
+    assert!(!test.client.entity_is_ignored(&client_ignored));
+    test.update_until(|test| test.client_x(1) == Some(10));
+}
@@ -0,0 +1,227 @@
+use std::{collections::HashMap, net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, ClientTickEvent, ConnectEvent as ClientConnectEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, ConnectEvent, InputGapEvent, Server, ServerConfig, TickEvent, UserKey,
+};
+use naia_shared::{
+    Channel, ChannelDirection, ChannelKind, ChannelMode, ChannelSettings, Message, Protocol, Tick,
+    TickBufferSettings,
+};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+const INPUT_GAP_THRESHOLD: u16 = 3;
+
+#[derive(Channel)]
+pub struct InputChannel;
+
+#[derive(Message)]
+pub struct Input {
+    pub value: u8,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_channel_with_settings::<InputChannel>(ChannelSettings::new(
+            ChannelMode::TickBuffered(TickBufferSettings::default()),
+            ChannelDirection::ClientToServer,
+        ))
+        .add_message::<Auth>()
+        .add_message::<Input>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+    connected: bool,
+    sends_input: bool,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork, sends_input: bool) -> Self {
+        let (socket, address): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+            connected: false,
+            sends_input,
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            self.connected = true;
+        }
+        for (client_tick, _) in events.read::<ClientTickEvent>() {
+            if self.sends_input {
+                self.client.send_tick_buffer_message::<InputChannel, Input>(
+                    &client_tick,
+                    &Input { value: 1 },
+                );
+            }
+        }
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    connected: Vec<UserKey>,
+    received: HashMap<UserKey, usize>,
+    missing: HashMap<UserKey, Vec<Tick>>,
+    gaps: Vec<(UserKey, Tick, u16)>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork, input_gap_threshold: Option<u16>) -> Self {
+        let mut server = Server::<Entity>::new(
+            ServerConfig {
+                input_gap_threshold,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            connected: Vec::new(),
+            received: HashMap::new(),
+            missing: HashMap::new(),
+            gaps: Vec::new(),
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .connected
+            .iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        *user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        self.gaps.extend(events.read::<InputGapEvent>());
+        for server_tick in events.read::<TickEvent>() {
+            let mut messages = self.server.receive_tick_buffer_messages(&server_tick);
+            for (user_key, _) in messages.read::<InputChannel, Input>() {
+                *self.received.entry(user_key).or_default() += 1;
+            }
+            for user_key in messages.missing_for_tick(&ChannelKind::of::<InputChannel>()) {
+                self.missing.entry(user_key).or_default().push(server_tick);
+            }
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn silent_user_is_reported_missing_and_gaps_fire_past_threshold() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, Some(INPUT_GAP_THRESHOLD));
+    let mut clients = vec![
+        TestClient::new(&network, true),
+        TestClient::new(&network, false),
+    ];
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.connected.len() == 2 && clients.iter().all(|client| client.connected)
+    });
+    let sender = server.user_key(&clients[0]);
+    let silent = server.user_key(&clients[1]);
+
+    update_until(&mut server, &mut clients, |server, _| {
+        server.received.get(&sender).copied().unwrap_or_default() >= 10
+            && server
+                .gaps
+                .iter()
+                .any(|(user_key, _, _)| *user_key == silent)
+    });
+
+    let missing = &server.missing[&silent];
+    assert!(!missing.is_empty());
+
+    // gaps are only fired once past the threshold
+    let silent_gaps: Vec<_> = server
+        .gaps
+        .iter()
+        .filter(|(user_key, _, _)| *user_key == silent)
+        .collect();
+    assert!(silent_gaps
+        .iter()
+        .all(|(_, _, consecutive_misses)| *consecutive_misses >= INPUT_GAP_THRESHOLD));
+
+    // the sending User is no longer reported missing once its input flows
+    let last_silent_miss = *missing.last().unwrap();
+    let sender_missing = server.missing.get(&sender).cloned().unwrap_or_default();
+    assert!(!sender_missing.contains(&last_silent_miss));
+}
+
+#[test]
+fn no_gaps_fire_without_a_threshold() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, None);
+    let mut clients = vec![TestClient::new(&network, false)];
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.connected.len() == 1 && clients[0].connected
+    });
+    let silent = server.user_key(&clients[0]);
+
+    update_until(&mut server, &mut clients, |server, _| {
+        server
+            .missing
+            .get(&silent)
+            .map(Vec::len)
+            .unwrap_or_default()
+            > 10
+    });
+    assert!(server.gaps.is_empty());
+}
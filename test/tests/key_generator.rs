@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use naia_shared::KeyGenerator;
+
+#[test]
+fn recycled_keys_are_reissued_before_new_ones() {
+    let mut generator = KeyGenerator::<u16>::new(Duration::ZERO);
+
+    let keys: Vec<u16> = (0..1000).map(|_| generator.generate()).collect();
+    assert_eq!(keys, (0..1000).collect::<Vec<u16>>());
+
+    for key in keys.iter().step_by(2) {
+        generator.recycle_key(key);
+    }
+    assert_eq!(generator.generated_count(), 1000);
+    assert_eq!(generator.recycled_count(), 500);
+
+    // every recycled key comes back, in the order it was recycled
+    let reissued: Vec<u16> = (0..500).map(|_| generator.generate()).collect();
+    assert_eq!(
+        reissued,
+        keys.iter().step_by(2).copied().collect::<Vec<u16>>()
+    );
+    assert_eq!(generator.recycled_count(), 0);
+
+    // only then are new keys created
+    assert_eq!(generator.generate(), 1000);
+    assert_eq!(generator.generated_count(), 1001);
+}
+
+#[test]
+fn recycled_keys_wait_out_the_timeout() {
+    let mut generator = KeyGenerator::<u16>::new(Duration::from_secs(60));
+
+    let key = generator.generate();
+    generator.recycle_key(&key);
+    assert_eq!(generator.recycled_count(), 1);
+
+    // the recycled key may still be referenced, so a new key is created
+    assert_eq!(generator.generate(), 1);
+    assert_eq!(generator.generated_count(), 2);
+}
+
+#[test]
+fn recycling_prevents_exhaustion() {
+    let mut generator = KeyGenerator::<u16>::new(Duration::ZERO);
+
+    for _ in 0..=u16::MAX {
+        generator.generate();
+    }
+    assert_eq!(generator.generated_count(), 65536);
+
+    generator.recycle_key(&7);
+    assert_eq!(generator.generate(), 7);
+}
+
+#[test]
+#[should_panic(expected = "KeyGenerator exhausted")]
+fn exhaustion_panics_instead_of_wrapping() {
+    let mut generator = KeyGenerator::<u16>::new(Duration::ZERO);
+
+    for _ in 0..=u16::MAX {
+        generator.generate();
+    }
+    generator.generate();
+}
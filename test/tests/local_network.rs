@@ -0,0 +1,84 @@
+use naia_client::transport::{
+    PacketReceiver as ClientReceiver, PacketSender as ClientSender, Socket as ClientSocket,
+};
+use naia_server::transport::{
+    PacketReceiver as ServerReceiver, PacketSender as ServerSender, Socket as ServerSocket,
+};
+use naia_test::{LocalClientSocket, LocalNetwork};
+
+fn listen(network: &LocalNetwork) -> (Box<dyn ServerSender>, Box<dyn ServerReceiver>) {
+    let socket: Box<dyn ServerSocket> = network.server_socket().into();
+    let (_, _, sender, receiver) = socket.listen();
+    (sender, receiver)
+}
+
+fn connect(socket: LocalClientSocket) -> (Box<dyn ClientSender>, Box<dyn ClientReceiver>) {
+    let socket: Box<dyn ClientSocket> = socket.into();
+    let (_, sender, receiver) = socket.connect();
+    (sender, receiver)
+}
+
+#[test]
+fn server_sees_each_client_address() {
+    let network = LocalNetwork::new();
+    let (server_sender, mut server_receiver) = listen(&network);
+    let (socket_a, address_a) = network.add_client();
+    let (socket_b, address_b) = network.add_client();
+    assert_ne!(address_a, address_b);
+    let (sender_a, mut receiver_a) = connect(socket_a);
+    let (sender_b, mut receiver_b) = connect(socket_b);
+
+    assert!(sender_b.send(b"from b").is_ok());
+    assert!(sender_a.send(b"from a").is_ok());
+    assert_eq!(
+        server_receiver.receive().ok().flatten(),
+        Some((address_b, &b"from b"[..]))
+    );
+    assert_eq!(
+        server_receiver.receive().ok().flatten(),
+        Some((address_a, &b"from a"[..]))
+    );
+
+    assert!(server_sender.send(&address_a, b"to a").is_ok());
+    assert_eq!(receiver_a.receive().ok().flatten(), Some(&b"to a"[..]));
+    assert_eq!(receiver_b.receive().ok().flatten(), None);
+}
+
+#[test]
+fn rebound_client_appears_at_new_address() {
+    let network = LocalNetwork::new();
+    let (server_sender, mut server_receiver) = listen(&network);
+    let (socket, old_address) = network.add_client();
+    let (sender, mut receiver) = connect(socket);
+
+    let new_address = network.rebind_client(&old_address);
+    assert_ne!(old_address, new_address);
+
+    assert!(sender.send(b"hello").is_ok());
+    assert_eq!(
+        server_receiver.receive().ok().flatten(),
+        Some((new_address, &b"hello"[..]))
+    );
+
+    assert!(server_sender.send(&old_address, b"lost").is_ok());
+    assert!(server_sender.send(&new_address, b"found").is_ok());
+    assert_eq!(receiver.receive().ok().flatten(), Some(&b"found"[..]));
+    assert_eq!(receiver.receive().ok().flatten(), None);
+}
+
+#[test]
+fn disconnected_client_is_cut_off() {
+    let network = LocalNetwork::new();
+    let (server_sender, mut server_receiver) = listen(&network);
+    let (socket, address) = network.add_client();
+    let (sender, mut receiver) = connect(socket);
+
+    assert!(sender.send(b"in flight").is_ok());
+    assert!(server_sender.send(&address, b"in flight").is_ok());
+    network.disconnect_client(&address);
+
+    assert!(sender.send(b"after").is_ok());
+    assert!(server_sender.send(&address, b"after").is_ok());
+    assert_eq!(server_receiver.receive().ok().flatten(), None);
+    assert_eq!(receiver.receive().ok().flatten(), None);
+}
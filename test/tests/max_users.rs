@@ -0,0 +1,184 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, ConnectEvent as ClientConnectEvent, ErrorEvent, NaiaClientError,
+    RejectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, DisconnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::{handshake::HandshakeError, ConnectionConfig, Protocol};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+const MAX_USERS: usize = 2;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+fn connection_config() -> ConnectionConfig {
+    ConnectionConfig {
+        disconnection_timeout_duration: Duration::from_millis(200),
+        heartbeat_interval: Duration::from_millis(20),
+        ..Default::default()
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+    connected: bool,
+    rejected: bool,
+    errors: Vec<NaiaClientError>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                connection: connection_config(),
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+            connected: false,
+            rejected: false,
+            errors: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            self.connected = true;
+        }
+        if events.read::<RejectEvent>().next().is_some() {
+            self.rejected = true;
+        }
+        self.errors.extend(events.read::<ErrorEvent>());
+    }
+
+    fn rejected_as_full(&self) -> bool {
+        self.rejected
+            && self.errors.iter().any(|error| {
+                let NaiaClientError::Wrapped(error) = error else {
+                    return false;
+                };
+                error.downcast_ref::<HandshakeError>() == Some(&HandshakeError::ServerFull)
+            })
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    connected: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(
+            ServerConfig {
+                connection: connection_config(),
+                max_users: Some(MAX_USERS),
+                ..Default::default()
+            },
+            protocol(),
+        );
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            connected: Vec::new(),
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .connected
+            .iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        *user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        for (user_key, _) in events.read::<DisconnectEvent>() {
+            self.connected.retain(|connected| *connected != user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn client_beyond_max_users_is_rejected_as_full() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients: Vec<TestClient> = (0..MAX_USERS).map(|_| TestClient::new(&network)).collect();
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.connected.len() == MAX_USERS && clients.iter().all(|client| client.connected)
+    });
+
+    clients.push(TestClient::new(&network));
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[MAX_USERS].rejected_as_full()
+    });
+    assert!(!clients[MAX_USERS].connected);
+    assert_eq!(server.connected.len(), MAX_USERS);
+    assert_eq!(server.server.users_count(), MAX_USERS);
+
+    // once a User leaves, another Client can take its place
+    let user_key = server.user_key(&clients[0]);
+    server.server.user_mut(&user_key).disconnect();
+    update_until(&mut server, &mut clients, |server, _| {
+        server.connected.len() == MAX_USERS - 1
+    });
+    clients.push(TestClient::new(&network));
+    update_until(&mut server, &mut clients, |server, clients| {
+        clients[MAX_USERS + 1].connected && server.connected.len() == MAX_USERS
+    });
+}
@@ -0,0 +1,251 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, MessageDeliveredEvent as ClientMessageDeliveredEvent,
+    MessageEvent as ClientMessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, MessageDeliveredEvent, MessageEvent, Server, ServerConfig, UserKey};
+use naia_shared::{
+    default_channels::{UnorderedReliableChannel, UnorderedUnreliableChannel},
+    Message, MessageReceiptKey, Protocol,
+};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Message)]
+pub struct TradeOffer {
+    pub items: Vec<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_message::<TradeOffer>()
+        .build()
+}
+
+fn offer(len: usize) -> TradeOffer {
+    TradeOffer {
+        items: (0..len).map(|index| index as u8).collect(),
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    received: Vec<usize>,
+    delivered: Vec<MessageReceiptKey>,
+}
+
+impl TestClient {
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        for offer in events.read::<ClientMessageEvent<UnorderedReliableChannel, TradeOffer>>() {
+            self.received.push(offer.items.len());
+        }
+        self.delivered
+            .extend(events.read::<ClientMessageDeliveredEvent>());
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_key: Option<UserKey>,
+    received: Vec<usize>,
+    delivered: Vec<(UserKey, MessageReceiptKey)>,
+}
+
+impl TestServer {
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.user_key = Some(user_key);
+        }
+        for (_, offer) in events.read::<MessageEvent<UnorderedReliableChannel, TradeOffer>>() {
+            self.received.push(offer.items.len());
+        }
+        self.delivered
+            .extend(events.read::<MessageDeliveredEvent>());
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, client: &mut TestClient) {
+    sleep(Duration::from_millis(5));
+    client.update();
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    done: impl Fn(&TestServer, &TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        update(server, client);
+    }
+    panic!("timed out");
+}
+
+// the Client acks packets as they arrive, but only reads the messages in them
+// on its next tick
+fn update_until_client_ticks(server: &mut TestServer, client: &mut TestClient) {
+    let tick = client.client.client_tick();
+    update_until(server, client, |_, client| {
+        client.client.client_tick() != tick
+    });
+}
+
+fn connected(network: &LocalNetwork) -> (TestServer, TestClient, SocketAddr) {
+    let mut server = TestServer {
+        server: Server::<Entity>::new(ServerConfig::default(), protocol()),
+        world: World::default(),
+        user_key: None,
+        received: Vec::new(),
+        delivered: Vec::new(),
+    };
+    server.server.listen(network.server_socket());
+
+    let (socket, address) = network.add_client();
+    let mut client = TestClient {
+        client: Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        ),
+        world: World::default(),
+        received: Vec::new(),
+        delivered: Vec::new(),
+    };
+    client.client.auth(Auth::new("charlie", "12345"));
+    client.client.connect(socket);
+
+    update_until(&mut server, &mut client, |server, client| {
+        server.user_key.is_some() && client.client.connection_status().is_connected()
+    });
+    (server, client, address)
+}
+
+#[test]
+fn receipt_fires_only_once_the_client_acks() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client, address) = connected(&network);
+    let user_key = server.user_key.unwrap();
+
+    // nothing the Server sends reaches the Client
+    network.set_loss_to_client(&address, Some(1));
+    let receipt_key = server
+        .server
+        .send_message_with_receipt::<UnorderedReliableChannel, TradeOffer>(&user_key, &offer(4))
+        .unwrap();
+    for _ in 0..40 {
+        update(&mut server, &mut client);
+    }
+    assert!(client.received.is_empty());
+    assert!(server.delivered.is_empty());
+    assert!(!server.server.receipt_received(&receipt_key));
+
+    network.set_loss_to_client(&address, None);
+    update_until(&mut server, &mut client, |server, _| {
+        !server.delivered.is_empty()
+    });
+    // the message is read on a later tick, which may take more than one
+    // update to come around on a busy machine
+    update_until(&mut server, &mut client, |_, client| {
+        !client.received.is_empty()
+    });
+    assert_eq!(client.received, vec![4]);
+    assert!(server.delivered == vec![(user_key, receipt_key)]);
+    assert!(server.server.receipt_received(&receipt_key));
+}
+
+#[test]
+fn fragmented_message_receipt_waits_for_every_fragment() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client, address) = connected(&network);
+    let user_key = server.user_key.unwrap();
+
+    // drop every other packet, so some fragments must be resent
+    network.set_loss_to_client(&address, Some(2));
+    let small_key = server
+        .server
+        .send_message_with_receipt::<UnorderedReliableChannel, TradeOffer>(&user_key, &offer(4))
+        .unwrap();
+    let large_key = server
+        .server
+        .send_message_with_receipt::<UnorderedReliableChannel, TradeOffer>(&user_key, &offer(3_000))
+        .unwrap();
+    assert_ne!(small_key, large_key);
+
+    update_until(&mut server, &mut client, |server, _| {
+        server.server.receipt_received(&large_key)
+    });
+    assert!(server
+        .delivered
+        .iter()
+        .any(|(_, receipt_key)| *receipt_key == large_key));
+    // had the receipt fired before every fragment arrived, the Client
+    // couldn't read the message on its next tick
+    update_until_client_ticks(&mut server, &mut client);
+    assert!(client.received.contains(&3_000));
+
+    // each receipt is reported once
+    update_until(&mut server, &mut client, |server, _| {
+        server.server.receipt_received(&small_key)
+    });
+    for _ in 0..20 {
+        update(&mut server, &mut client);
+    }
+    let delivered: Vec<MessageReceiptKey> = server
+        .delivered
+        .iter()
+        .map(|(_, receipt_key)| *receipt_key)
+        .collect();
+    assert_eq!(delivered.len(), 2);
+    assert!(delivered.contains(&small_key) && delivered.contains(&large_key));
+}
+
+#[test]
+fn client_receipt_fires_once_the_server_acks() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client, _) = connected(&network);
+
+    let receipt_key = client
+        .client
+        .send_message_with_receipt::<UnorderedReliableChannel, TradeOffer>(&offer(2_000))
+        .unwrap();
+    assert!(!client.client.receipt_received(&receipt_key));
+
+    update_until(&mut server, &mut client, |_, client| {
+        !client.delivered.is_empty()
+    });
+    assert_eq!(server.received, vec![2_000]);
+    assert_eq!(client.delivered, vec![receipt_key]);
+    assert!(client.client.receipt_received(&receipt_key));
+}
+
+#[test]
+fn unreliable_channel_receipts_are_an_error() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client, _) = connected(&network);
+    let user_key = server.user_key.unwrap();
+
+    assert!(server
+        .server
+        .send_message_with_receipt::<UnorderedUnreliableChannel, TradeOffer>(&user_key, &offer(4))
+        .is_err());
+    assert!(client
+        .client
+        .send_message_with_receipt::<UnorderedUnreliableChannel, TradeOffer>(&offer(4))
+        .is_err());
+}
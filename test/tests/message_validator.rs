@@ -0,0 +1,114 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, MessageEvent, MessageRejectedEvent, Server, ServerConfig, UserKey};
+use naia_shared::{
+    default_channels::UnorderedReliableChannel, ChannelKind, Message, MessageKind, Protocol,
+};
+use naia_test::{Auth, LocalNetwork};
+
+const MAX_MESSAGE_BYTES: u32 = 200;
+
+#[derive(Message)]
+pub struct Chat {
+    pub text: Vec<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_message::<Chat>()
+        .build()
+}
+
+fn chat(len: usize) -> Chat {
+    Chat {
+        text: vec![b'a'; len],
+    }
+}
+
+#[test]
+fn rejected_messages_never_reach_events() {
+    let network = LocalNetwork::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(network.server_socket());
+    server.set_message_validator(Box::new(|_, _, message| {
+        message.bit_length() <= MAX_MESSAGE_BYTES * 8
+    }));
+    let mut server_world = World::default();
+
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    let mut client_world = World::default();
+
+    let mut user_key: Option<UserKey> = None;
+    let mut sent = false;
+    let mut received = Vec::new();
+    let mut rejected = Vec::new();
+    for _ in 0..400 {
+        if received.len() == 2 && rejected.len() == 2 {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+
+        client.receive(client_world.proxy_mut());
+        if !sent && client.connection_status().is_connected() {
+            // the 1000 byte Message is fragmented, and checked once reassembled
+            for len in [4, 1000, 20, 300] {
+                client.send_message::<UnorderedReliableChannel, Chat>(&chat(len));
+            }
+            sent = true;
+        }
+
+        let mut events = server.receive(server_world.proxy_mut());
+        for (new_user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&new_user_key);
+            user_key = Some(new_user_key);
+        }
+        for (_, chat) in events.read::<MessageEvent<UnorderedReliableChannel, Chat>>() {
+            received.push(chat.text.len());
+        }
+        rejected.extend(events.read::<MessageRejectedEvent>());
+        server.send_all_updates(server_world.proxy());
+    }
+
+    received.sort();
+    assert_eq!(received, vec![4, 20]);
+    assert_eq!(rejected.len(), 2);
+    let user_key = user_key.unwrap();
+    for (rejected_user_key, channel_kind, message_kind) in rejected {
+        assert!(rejected_user_key == user_key);
+        assert!(channel_kind == ChannelKind::of::<UnorderedReliableChannel>());
+        assert!(message_kind == MessageKind::of::<Chat>());
+    }
+
+    // once cleared, every Message is accepted again
+    server.clear_message_validator();
+    client.send_message::<UnorderedReliableChannel, Chat>(&chat(300));
+    let mut received_after = Vec::new();
+    for _ in 0..400 {
+        if !received_after.is_empty() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        client.receive(client_world.proxy_mut());
+        let mut events = server.receive(server_world.proxy_mut());
+        for (_, chat) in events.read::<MessageEvent<UnorderedReliableChannel, Chat>>() {
+            received_after.push(chat.text.len());
+        }
+        assert!(!events.has::<MessageRejectedEvent>());
+        server.send_all_updates(server_world.proxy());
+    }
+    assert_eq!(received_after, vec![300]);
+}
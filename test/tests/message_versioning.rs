@@ -0,0 +1,100 @@
+use naia_shared::{
+    BitReader, BitWriter, FakeEntityConverter, Message, MessageContainer, Protocol, Serde,
+};
+
+mod v1 {
+    use naia_shared::Message;
+
+    #[derive(Message)]
+    #[serde_version(1)]
+    pub struct Chat {
+        pub text: String,
+    }
+}
+
+mod v2 {
+    use naia_shared::Message;
+
+    #[derive(Message)]
+    #[serde_version(2)]
+    pub struct Chat {
+        pub text: String,
+        #[serde_version(2)]
+        pub color: u32,
+    }
+}
+
+// writes the Message followed by a marker, to check that the reader ends up
+// exactly at the end of the Message
+fn write_with_marker(protocol: &Protocol, message: Box<dyn Message>) -> Box<[u8]> {
+    let mut converter = FakeEntityConverter;
+    let message = MessageContainer::from_write(message, &mut converter);
+    let mut writer = BitWriter::new();
+    message.write(&protocol.message_kinds, &mut writer, &mut converter);
+    0xABu8.ser(&mut writer);
+    writer.to_bytes()
+}
+
+#[test]
+fn v1_payload_read_by_v2_defaults_missing_field() {
+    let protocol_v1 = Protocol::builder().add_message::<v1::Chat>().build();
+    let protocol_v2 = Protocol::builder().add_message::<v2::Chat>().build();
+
+    let bytes = write_with_marker(
+        &protocol_v1,
+        Box::new(v1::Chat {
+            text: "hello".to_string(),
+        }),
+    );
+
+    let mut reader = BitReader::new(&bytes);
+    let message = protocol_v2
+        .message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap();
+    let chat = message.to_boxed_any().downcast::<v2::Chat>().unwrap();
+    assert_eq!(chat.text, "hello");
+    assert_eq!(chat.color, 0);
+    assert_eq!(u8::de(&mut reader).unwrap(), 0xAB);
+}
+
+#[test]
+fn v2_payload_read_by_v1_skips_extra_field() {
+    let protocol_v1 = Protocol::builder().add_message::<v1::Chat>().build();
+    let protocol_v2 = Protocol::builder().add_message::<v2::Chat>().build();
+
+    let bytes = write_with_marker(
+        &protocol_v2,
+        Box::new(v2::Chat {
+            text: "hello".to_string(),
+            color: 0xFF00FF,
+        }),
+    );
+
+    let mut reader = BitReader::new(&bytes);
+    let message = protocol_v1
+        .message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap();
+    let chat = message.to_boxed_any().downcast::<v1::Chat>().unwrap();
+    assert_eq!(chat.text, "hello");
+    assert_eq!(u8::de(&mut reader).unwrap(), 0xAB);
+}
+
+#[test]
+fn versioned_bit_length_matches_written_bits() {
+    let protocol = Protocol::builder().add_message::<v2::Chat>().build();
+    let mut converter = FakeEntityConverter;
+    let message = MessageContainer::from_write(
+        Box::new(v2::Chat {
+            text: "hello".to_string(),
+            color: 7,
+        }),
+        &mut converter,
+    );
+
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    message.write(&protocol.message_kinds, &mut writer, &mut converter);
+    assert_eq!(bits_free - writer.bits_free(), message.bit_length());
+}
@@ -0,0 +1,309 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{ConnectionConfig, Property, Protocol, Replicate, WorldMutType, WorldRefType};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+}
+
+impl TestClient {
+    fn new(socket: LocalClientSocket) -> Self {
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+        }
+    }
+
+    fn update(&mut self) {
+        self.client.receive(self.world.proxy_mut());
+    }
+
+    fn positions(&self) -> Vec<u8> {
+        let world = self.world.proxy();
+        self.client
+            .entities(&world)
+            .iter()
+            .filter_map(|entity| {
+                world
+                    .component::<Position>(entity)
+                    .map(|position| *position.x)
+            })
+            .collect()
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+}
+
+impl TestServer {
+    // listens on each network, in order
+    fn new(networks: &[&LocalNetwork]) -> Self {
+        let mut server = Self::unlistened();
+        for network in networks {
+            server.server.listen(network.server_socket());
+        }
+        server
+    }
+
+    // listens on each network by name, in order
+    fn named(networks: &[(&str, &LocalNetwork)]) -> Self {
+        let mut server = Self::unlistened();
+        for (name, network) in networks {
+            server.server.listen_named(name, network.server_socket());
+        }
+        server
+    }
+
+    fn unlistened() -> Self {
+        let mut server = Server::<Entity>::new(
+            ServerConfig {
+                connection: ConnectionConfig {
+                    bandwidth_measure_duration: Some(Duration::from_secs(1)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            protocol(),
+        );
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+        }
+    }
+
+    fn spawn(&mut self, x: u8) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.world.proxy_mut())
+            .insert_component(Position::new_complete(x))
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn set_position(&mut self, entity: &Entity, x: u8) {
+        let mut world = self.world.proxy_mut();
+        let mut position = world.component_mut::<Position>(entity).unwrap();
+        *position.x = x;
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn clients_on_different_sockets_sync_the_same_world() {
+    let udp_network = LocalNetwork::new();
+    let webrtc_network = LocalNetwork::with_first_client_port(15200);
+    let mut server = TestServer::new(&[&udp_network, &webrtc_network]);
+    assert_eq!(server.server.sockets_count(), 2);
+    let entity = server.spawn(7);
+
+    let (udp_socket, udp_address) = udp_network.add_client();
+    let (webrtc_socket, webrtc_address) = webrtc_network.add_client();
+    assert_ne!(udp_address, webrtc_address);
+    let mut clients = vec![TestClient::new(udp_socket), TestClient::new(webrtc_socket)];
+
+    update_until(&mut server, &mut clients, |clients| {
+        clients.iter().all(|client| client.positions() == vec![7])
+    });
+    // let the spawns' acks reach the Server, which only then tracks changes
+    for _ in 0..20 {
+        update(&mut server, &mut clients);
+    }
+
+    server.set_position(&entity, 8);
+    update_until(&mut server, &mut clients, |clients| {
+        clients.iter().all(|client| client.positions() == vec![8])
+    });
+
+    // each User is answered through the Socket it connected through
+    let mut socket_indices: Vec<(SocketAddr, Option<usize>)> = server
+        .server
+        .user_keys()
+        .iter()
+        .map(|user_key| {
+            (
+                server.server.user(user_key).address(),
+                server.server.user_socket_index(user_key),
+            )
+        })
+        .collect();
+    socket_indices.sort();
+    assert_eq!(
+        socket_indices,
+        vec![(udp_address, Some(0)), (webrtc_address, Some(1))]
+    );
+    assert!(!udp_network.client_received(&udp_address).is_empty());
+    assert!(!webrtc_network.client_received(&webrtc_address).is_empty());
+
+    // and bandwidth is measured per Socket
+    assert!(server.server.outgoing_bandwidth_through_socket(0) > 0.0);
+    assert!(server.server.outgoing_bandwidth_through_socket(1) > 0.0);
+    assert!(server.server.incoming_bandwidth_through_socket(0) > 0.0);
+    assert!(server.server.incoming_bandwidth_through_socket(1) > 0.0);
+    assert_eq!(server.server.outgoing_bandwidth_through_socket(2), 0.0);
+}
+
+#[test]
+fn disconnected_socket_does_not_hold_up_the_other() {
+    let udp_network = LocalNetwork::new();
+    let webrtc_network = LocalNetwork::with_first_client_port(15200);
+    let mut server = TestServer::new(&[&udp_network, &webrtc_network]);
+    server.spawn(7);
+
+    let (udp_socket, udp_address) = udp_network.add_client();
+    let mut clients = vec![
+        TestClient::new(udp_socket),
+        TestClient::new(webrtc_network.add_client().0),
+    ];
+    update_until(&mut server, &mut clients, |clients| {
+        clients.iter().all(|client| client.positions() == vec![7])
+    });
+
+    udp_network.disconnect_client(&udp_address);
+    server.spawn(9);
+    update_until(&mut server, &mut clients, |clients| {
+        let mut positions = clients[1].positions();
+        positions.sort();
+        positions == vec![7, 9]
+    });
+    assert_eq!(clients[0].positions(), vec![7]);
+}
+
+#[test]
+fn clients_are_told_apart_by_socket_name() {
+    let udp_network = LocalNetwork::new();
+    let webrtc_network = LocalNetwork::with_first_client_port(15200);
+    let mut server = TestServer::named(&[("udp", &udp_network), ("webrtc", &webrtc_network)]);
+    assert_eq!(server.server.socket_index("udp"), Some(0));
+    assert_eq!(server.server.socket_index("webrtc"), Some(1));
+    assert_eq!(server.server.socket_index("tcp"), None);
+    server.spawn(7);
+
+    let (udp_socket, udp_address) = udp_network.add_client();
+    let (webrtc_socket, webrtc_address) = webrtc_network.add_client();
+    let mut clients = vec![TestClient::new(udp_socket), TestClient::new(webrtc_socket)];
+    update_until(&mut server, &mut clients, |clients| {
+        clients.iter().all(|client| client.positions() == vec![7])
+    });
+
+    let mut socket_names: Vec<(SocketAddr, Option<&str>)> = server
+        .server
+        .user_keys()
+        .iter()
+        .map(|user_key| {
+            (
+                server.server.user(user_key).address(),
+                server.server.user_socket_name(user_key),
+            )
+        })
+        .collect();
+    socket_names.sort();
+    assert_eq!(
+        socket_names,
+        vec![(udp_address, Some("udp")), (webrtc_address, Some("webrtc"))]
+    );
+
+    let webrtc_index = server.server.socket_index("webrtc").unwrap();
+    assert!(
+        server
+            .server
+            .outgoing_bandwidth_through_socket(webrtc_index)
+            > 0.0
+    );
+}
+
+#[test]
+fn unnamed_sockets_have_no_name() {
+    let udp_network = LocalNetwork::new();
+    let webrtc_network = LocalNetwork::with_first_client_port(15200);
+    let mut server = TestServer::new(&[&udp_network]);
+    server
+        .server
+        .listen_named("webrtc", webrtc_network.server_socket());
+    assert_eq!(server.server.sockets_count(), 2);
+    assert_eq!(server.server.socket_index("webrtc"), Some(1));
+
+    let (udp_socket, _) = udp_network.add_client();
+    let mut clients = vec![TestClient::new(udp_socket)];
+    update_until(&mut server, &mut clients, |clients| {
+        clients[0].client.connection_status().is_connected()
+    });
+    let user_key = server.server.user_keys()[0];
+    assert_eq!(server.server.user_socket_index(&user_key), Some(0));
+    assert_eq!(server.server.user_socket_name(&user_key), None);
+}
+
+#[test]
+#[should_panic(expected = "already being listened on")]
+fn socket_names_are_unique() {
+    let udp_network = LocalNetwork::new();
+    let other_udp_network = LocalNetwork::with_first_client_port(15200);
+    TestServer::named(&[("udp", &udp_network), ("udp", &other_udp_network)]);
+}
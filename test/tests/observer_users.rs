@@ -0,0 +1,277 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, EntityAuthResetEvent, EntityAuthResetReason, EntityOwner, ReplicationConfig,
+    RoomKey, Server, ServerConfig, UserKey,
+};
+use naia_shared::{EntityAuthStatus, Property, Protocol, Replicate, WorldMutType, WorldRefType};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork, username: &str) -> Self {
+        let (socket, address): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new(username, "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+        }
+    }
+
+    fn update(&mut self) {
+        self.client.receive(self.world.proxy_mut());
+    }
+
+    fn entity(&self) -> Option<Entity> {
+        self.client.entities(&self.world.proxy()).first().copied()
+    }
+
+    fn authority(&self) -> Option<EntityAuthStatus> {
+        let entity = self.entity()?;
+        self.client.entity(self.world.proxy(), &entity).authority()
+    }
+
+    fn x(&self) -> Option<u8> {
+        let entity = self.entity()?;
+        self.world
+            .proxy()
+            .component::<Position>(&entity)
+            .map(|position| *position.x)
+    }
+
+    fn set_x(&mut self, x: u8) {
+        let entity = self.entity().unwrap();
+        *self
+            .world
+            .proxy_mut()
+            .component_mut::<Position>(&entity)
+            .unwrap()
+            .x = x;
+    }
+
+    fn request_authority(&mut self) {
+        let entity = self.entity().unwrap();
+        self.client
+            .entity_mut(self.world.proxy_mut(), &entity)
+            .request_authority();
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    entity: Entity,
+    resets: Vec<(Entity, EntityAuthResetReason)>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        let mut world = World::default();
+        let entity = server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Position::new_complete(1))
+            .configure_replication(ReplicationConfig::Delegated)
+            .id();
+        server.room_mut(&room_key).add_entity(&entity);
+        Self {
+            server,
+            world,
+            room_key,
+            entity,
+            resets: Vec::new(),
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .server
+            .user_keys()
+            .into_iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, auth) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            if auth.username == "observer" {
+                self.server.user_mut(&user_key).set_observer(true);
+            }
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        self.resets.extend(events.read::<EntityAuthResetEvent>());
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn observer_sees_updates_but_never_delegation() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![
+        TestClient::new(&network, "player"),
+        TestClient::new(&network, "observer"),
+    ];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Available) && clients[1].x() == Some(1)
+    });
+    let player = server.user_key(&clients[0]);
+    let observer = server.user_key(&clients[1]);
+    assert!(server.server.user(&observer).is_observer());
+
+    // to the observer, the Entity is just the Server's
+    assert_eq!(clients[1].authority(), None);
+    assert!(server
+        .server
+        .entity_give_authority(&observer, &server.entity)
+        .is_err());
+
+    // the player takes authority & moves it, which the observer sees
+    clients[0].request_authority();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+    });
+    assert_eq!(
+        server.server.entity_owner(&server.entity),
+        EntityOwner::Client(player)
+    );
+    clients[0].set_x(9);
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[1].x() == Some(9)
+    });
+    assert_eq!(clients[1].authority(), None);
+
+    // and authority can't be passed to the observer
+    assert!(server
+        .server
+        .transfer_authority(&player, &observer, &server.entity)
+        .is_err());
+}
+
+#[test]
+fn observer_authority_requests_are_ignored() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![
+        TestClient::new(&network, "player"),
+        TestClient::new(&network, "spectator"),
+    ];
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients
+            .iter()
+            .all(|client| client.authority() == Some(EntityAuthStatus::Available))
+    });
+    let player = server.user_key(&clients[0]);
+    let spectator = server.user_key(&clients[1]);
+
+    // becoming an observer gives up the authority it held
+    clients[1].request_authority();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[1].authority() == Some(EntityAuthStatus::Granted)
+    });
+    server.server.user_mut(&spectator).set_observer(true);
+    // the reset is reported on the next receive
+    update(&mut server, &mut clients);
+    assert_eq!(
+        server.resets,
+        vec![(server.entity, EntityAuthResetReason::OwnerObserving)]
+    );
+    assert_eq!(
+        server.server.entity_owner(&server.entity),
+        EntityOwner::Server
+    );
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[1].authority() == Some(EntityAuthStatus::Available)
+    });
+
+    // its own requests go unanswered
+    clients[1].request_authority();
+    for _ in 0..50 {
+        update(&mut server, &mut clients);
+    }
+    assert_eq!(
+        server.server.entity_owner(&server.entity),
+        EntityOwner::Server
+    );
+    assert!(server.server.user_owned_entities(&spectator).is_empty());
+    assert_eq!(clients[1].authority(), Some(EntityAuthStatus::Requested));
+
+    // while the player's are granted as usual
+    clients[0].request_authority();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+    });
+    assert_eq!(
+        server.server.entity_owner(&server.entity),
+        EntityOwner::Client(player)
+    );
+    assert_eq!(
+        server.server.user_owned_entities(&player),
+        vec![server.entity]
+    );
+}
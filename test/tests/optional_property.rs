@@ -0,0 +1,147 @@
+use std::sync::{Arc, Mutex};
+
+use naia_shared::{
+    BitReader, BitWriter, DiffMask, FakeEntityConverter, Property, PropertyMutate, PropertyMutator,
+    Replicate, Serde,
+};
+
+#[derive(Serde, Clone, PartialEq, Debug)]
+pub struct Scope {
+    pub zoom: u8,
+    pub range: u16,
+}
+
+#[derive(Replicate)]
+pub struct Rifle {
+    pub ammo: Property<u8>,
+    pub scope: Property<Option<Scope>>,
+}
+
+// records which DiffMask bits were mutated
+#[derive(Clone, Default)]
+struct RecordingMutator {
+    mutated: Arc<Mutex<Vec<u8>>>,
+}
+
+impl RecordingMutator {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.mutated.lock().unwrap())
+    }
+}
+
+impl PropertyMutate for RecordingMutator {
+    fn mutate(&mut self, property_index: u8) -> bool {
+        self.mutated.lock().unwrap().push(property_index);
+        true
+    }
+}
+
+fn scope() -> Scope {
+    Scope {
+        zoom: 4,
+        range: 800,
+    }
+}
+
+fn host_rifle() -> (Rifle, RecordingMutator) {
+    let mutator = RecordingMutator::default();
+    let mut rifle = Rifle::new_complete(30, None);
+    rifle.set_mutator(&PropertyMutator::new(mutator.clone()));
+    (rifle, mutator)
+}
+
+// creates the remote copy of a host Rifle, as it would be on first replication
+fn remote_rifle(host: &Rifle) -> Box<dyn Replicate> {
+    let mut writer = BitWriter::new();
+    host.write_fields(&mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    Rifle::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+}
+
+// writes an update of the given bits, returning it & how many bits it took
+fn write_update(rifle: &Rifle, bits: &[u8]) -> (Box<[u8]>, u32) {
+    let mut diff_mask = DiffMask::new(1);
+    for bit in bits {
+        diff_mask.set_bit(*bit, true);
+    }
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    rifle.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+fn apply_update(remote: &mut Box<dyn Replicate>, bytes: &[u8]) {
+    let mut reader = BitReader::new(bytes);
+    let update = Rifle::create_builder()
+        .read_create_update(&mut reader)
+        .unwrap();
+    remote
+        .read_apply_update(&FakeEntityConverter, update)
+        .unwrap();
+}
+
+fn as_rifle(remote: &dyn Replicate) -> &Rifle {
+    remote.to_any().downcast_ref::<Rifle>().unwrap()
+}
+
+#[test]
+fn toggling_presence_converges_on_remote() {
+    let (mut rifle, mutator) = host_rifle();
+    let mut remote = remote_rifle(&rifle);
+    assert_eq!(*as_rifle(remote.as_ref()).scope, None);
+
+    for _ in 0..2 {
+        assert_eq!(rifle.scope.replace(scope()), None);
+        let (bytes, _) = write_update(&rifle, &mutator.take());
+        apply_update(&mut remote, &bytes);
+        assert_eq!(*as_rifle(remote.as_ref()).scope, Some(scope()));
+
+        assert_eq!(rifle.scope.take(), Some(scope()));
+        let (bytes, _) = write_update(&rifle, &mutator.take());
+        apply_update(&mut remote, &bytes);
+        assert_eq!(*as_rifle(remote.as_ref()).scope, None);
+    }
+    assert_eq!(*as_rifle(remote.as_ref()).ammo, 30);
+}
+
+#[test]
+fn only_changes_queue_an_update() {
+    let (mut rifle, mutator) = host_rifle();
+
+    assert_eq!(rifle.scope.take(), None);
+    assert!(!rifle.scope.set_option(None));
+    assert!(mutator.take().is_empty());
+
+    assert!(rifle.scope.set_option(Some(scope())));
+    assert!(rifle.scope.is_some());
+    assert_eq!(mutator.take(), vec![1]);
+
+    assert_eq!(rifle.scope.replace(scope()), Some(scope()));
+    assert!(mutator.take().is_empty());
+
+    assert!(rifle.scope.set_option(None));
+    assert!(rifle.scope.is_none());
+    assert_eq!(mutator.take(), vec![1]);
+}
+
+#[test]
+fn clearing_only_sends_the_presence_bit() {
+    let (mut rifle, mutator) = host_rifle();
+
+    rifle.scope.replace(scope());
+    let (_, some_bits) = write_update(&rifle, &mutator.take());
+
+    rifle.scope.take();
+    let (_, none_bits) = write_update(&rifle, &mutator.take());
+
+    assert_eq!(
+        some_bits - none_bits,
+        scope().bit_length(),
+        "a cleared Property shouldn't carry a value"
+    );
+}
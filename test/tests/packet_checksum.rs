@@ -0,0 +1,144 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::{
+    append_checksum, default_channels::UnorderedUnreliableChannel, verify_checksum, BitWriter,
+    ConnectionConfig, DecoderError, PacketType, Protocol, Serde, StandardHeader, CHECKSUM_BYTES,
+};
+use naia_test::{Auth, LocalNetwork};
+
+fn packet(packet_type: PacketType, body: &str) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    StandardHeader::new(packet_type, 7, 3, 0b1011).ser(&mut writer);
+    body.to_string().ser(&mut writer);
+    writer.to_bytes().to_vec()
+}
+
+#[test]
+fn checksummed_packet_round_trips() {
+    let plaintext = packet(PacketType::Data, "hello");
+    let checksummed = append_checksum(&plaintext);
+    assert_eq!(checksummed.len(), plaintext.len() + CHECKSUM_BYTES);
+    assert_eq!(verify_checksum(&checksummed), Ok(plaintext.as_slice()));
+}
+
+#[test]
+fn any_flipped_bit_is_rejected() {
+    let plaintext = packet(PacketType::Data, "hello");
+    let checksummed = append_checksum(&plaintext);
+
+    // including bits of the StandardHeader, and of the checksum itself
+    for byte in 0..checksummed.len() {
+        for bit in 0..8 {
+            let mut corrupted = checksummed.clone();
+            corrupted[byte] ^= 1 << bit;
+            assert_eq!(
+                verify_checksum(&corrupted),
+                Err(DecoderError::Corrupted),
+                "flipping bit {} of byte {} went unnoticed",
+                bit,
+                byte
+            );
+        }
+    }
+}
+
+#[test]
+fn truncated_packet_is_rejected() {
+    assert_eq!(verify_checksum(&[]), Err(DecoderError::Malformed));
+    assert_eq!(verify_checksum(&[1, 2, 3]), Err(DecoderError::Malformed));
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+fn connection_config() -> ConnectionConfig {
+    ConnectionConfig {
+        packet_checksums: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn corrupted_packets_are_dropped_and_counted() {
+    let network = LocalNetwork::new();
+
+    let mut server_world = World::default();
+    let mut server = Server::<Entity>::new(
+        ServerConfig {
+            connection: connection_config(),
+            ..Default::default()
+        },
+        protocol(),
+    );
+    server.listen(network.server_socket());
+
+    let (socket, address): (_, SocketAddr) = network.add_client();
+    let mut client_world = World::default();
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            connection: connection_config(),
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(socket);
+
+    let mut user_key: Option<UserKey> = None;
+    let mut update = |server: &mut Server<Entity>, client: &mut Client<Entity>| {
+        sleep(Duration::from_millis(5));
+        let _ = client.receive(client_world.proxy_mut());
+        let mut events = server.receive(server_world.proxy_mut());
+        for (key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&key);
+        }
+        for key in events.read::<ConnectEvent>() {
+            user_key = Some(key);
+        }
+        // keep Data packets flowing, so that some of them are corrupted
+        if let Some(key) = user_key {
+            server.send_message::<UnorderedUnreliableChannel, Auth>(&key, &Auth::new("a", "b"));
+        }
+        server.send_all_updates(server_world.proxy());
+        user_key
+    };
+
+    for _ in 0..400 {
+        if update(&mut server, &mut client).is_some() && client.connection_status().is_connected() {
+            break;
+        }
+    }
+    assert!(client.connection_status().is_connected());
+    assert_eq!(client.corrupt_packets_count(), 0);
+
+    // every third packet to the Client has a bit flipped, which must be
+    // dropped rather than read
+    network.set_corruption_to_client(&address, Some(3));
+    for _ in 0..60 {
+        update(&mut server, &mut client);
+    }
+    assert!(client.corrupt_packets_count() > 0);
+    assert!(client.connection_status().is_connected());
+
+    // untouched packets still get through once the link is clean again
+    network.set_corruption_to_client(&address, None);
+    // packets corrupted on their way out are still read on the next update
+    update(&mut server, &mut client);
+    let corrupt_packets = client.corrupt_packets_count();
+    for _ in 0..20 {
+        update(&mut server, &mut client);
+    }
+    assert_eq!(client.corrupt_packets_count(), corrupt_packets);
+    assert!(client.connection_status().is_connected());
+    assert_eq!(server.corrupt_packets_count(), 0);
+}
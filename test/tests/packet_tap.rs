@@ -0,0 +1,128 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, Server, ServerConfig};
+use naia_shared::{ConnectionConfig, EncryptionConfig, Protocol};
+use naia_test::{Auth, LocalNetwork};
+
+type Tapped = Arc<Mutex<Vec<(SocketAddr, Vec<u8>)>>>;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+fn payloads(tapped: &Tapped) -> Vec<Vec<u8>> {
+    tapped
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, payload)| payload.clone())
+        .collect()
+}
+
+// every stage of the packet pipeline is enabled, so the taps only match
+// the wire if they see its bytes after encryption & checksums
+#[test]
+fn taps_see_the_bytes_on_the_wire() {
+    let connection = ConnectionConfig {
+        encryption: Some(EncryptionConfig),
+        packet_checksums: true,
+        ..Default::default()
+    };
+    let network = LocalNetwork::new();
+
+    let mut server_world = World::default();
+    let mut server = Server::<Entity>::new(
+        ServerConfig {
+            connection: connection.clone(),
+            ..Default::default()
+        },
+        protocol(),
+    );
+    let outgoing = Tapped::default();
+    let incoming = Tapped::default();
+    let tap_outgoing = outgoing.clone();
+    server.set_outgoing_tap(Some(Box::new(move |address, payload| {
+        tap_outgoing
+            .lock()
+            .unwrap()
+            .push((*address, payload.to_vec()));
+    })));
+    let tap_incoming = incoming.clone();
+    server.set_incoming_tap(Some(Box::new(move |address, payload| {
+        tap_incoming
+            .lock()
+            .unwrap()
+            .push((*address, payload.to_vec()));
+    })));
+    server.listen(network.server_socket());
+
+    let mut client_world = World::default();
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            connection,
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    let (socket, client_address) = network.add_client();
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(socket);
+
+    let mut update = || {
+        sleep(Duration::from_millis(5));
+        client.receive(client_world.proxy_mut());
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+        client.connection_status().is_connected()
+    };
+    let mut connected = false;
+    for _ in 0..400 {
+        if update() {
+            connected = true;
+            break;
+        }
+    }
+    assert!(connected, "client never connected");
+
+    // let some encrypted traffic flow too
+    for _ in 0..20 {
+        update();
+    }
+
+    // every packet the Client read was seen by the outgoing tap first, in order
+    let client_received = network.client_received(&client_address);
+    let tapped_outgoing = payloads(&outgoing);
+    assert!(!client_received.is_empty());
+    assert!(tapped_outgoing.len() >= client_received.len());
+    assert_eq!(
+        tapped_outgoing[..client_received.len()],
+        client_received[..]
+    );
+
+    // every packet the Server read was seen by the incoming tap
+    assert_eq!(payloads(&incoming), network.server_received());
+
+    // all traffic was to & from the one Client
+    assert!(outgoing
+        .lock()
+        .unwrap()
+        .iter()
+        .chain(incoming.lock().unwrap().iter())
+        .all(|(address, _)| *address == client_address));
+}
@@ -0,0 +1,132 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig, UserKey};
+use naia_shared::{ComponentKind, HostEntityActionEvent, Property, Protocol, Replicate};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    user_key: Option<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+            user_key: None,
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+            self.user_key = Some(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn new_client(network: &LocalNetwork) -> Client<Entity> {
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    client
+}
+
+#[test]
+fn pending_spawn_is_visible_before_sending() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut client = new_client(&network);
+    let mut world = World::default();
+
+    for _ in 0..400 {
+        if client.connection_status().is_connected() && server.user_key.is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        client.receive(world.proxy_mut());
+        server.update();
+    }
+    let user_key = server.user_key.expect("timed out");
+
+    let events = server
+        .server
+        .pending_host_events(server.world.proxy(), &user_key)
+        .unwrap();
+    assert!(events.next_send_actions.is_empty());
+
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(7))
+        .id();
+    server.server.room_mut(&server.room_key).add_entity(&entity);
+    for (_, user_key, entity) in server.server.scope_checks() {
+        server.server.user_scope_mut(&user_key).include(&entity);
+    }
+    server.server.update_entity_scopes(server.world.proxy());
+
+    // peeking doesn't consume anything, so the spawn is still pending afterwards
+    for _ in 0..2 {
+        let events = server
+            .server
+            .pending_host_events(server.world.proxy(), &user_key)
+            .unwrap();
+        assert!(events.next_send_actions.iter().any(|(_, action)| {
+            *action
+                == HostEntityActionEvent::SpawnEntity(entity, vec![ComponentKind::of::<Position>()])
+        }));
+    }
+    assert!(client.entities(&world.proxy()).is_empty());
+
+    // once sent, the spawn isn't due again until it's time to resend
+    server.server.send_all_updates(server.world.proxy());
+    let events = server
+        .server
+        .pending_host_events(server.world.proxy(), &user_key)
+        .unwrap();
+    assert!(events.next_send_actions.is_empty());
+
+    for _ in 0..400 {
+        if !client.entities(&world.proxy()).is_empty() {
+            return;
+        }
+        sleep(Duration::from_millis(5));
+        client.receive(world.proxy_mut());
+        server.update();
+    }
+    panic!("timed out");
+}
@@ -0,0 +1,89 @@
+use naia_shared::{
+    Channel, ChannelDirection, ChannelKind, ChannelMode, ComponentKind, MessageKind, Property,
+    Protocol, ReliableSettings, Replicate,
+};
+use naia_test::Auth;
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+}
+
+#[derive(Replicate)]
+#[replicate(full_update)]
+pub struct Health {
+    pub value: Property<u8>,
+}
+
+#[derive(Channel)]
+pub struct GameplayChannel;
+
+fn protocol(resend_factor: f32) -> Protocol {
+    Protocol::builder()
+        .add_channel::<GameplayChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ReliableSettings {
+                rtt_resend_factor: resend_factor,
+            }),
+        )
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .add_component::<Health>()
+        .build()
+}
+
+#[test]
+fn kinds_are_listed_in_net_id_order() {
+    let protocol = protocol(1.5);
+
+    let components: Vec<_> = protocol.component_kinds.iter().collect();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0].0, ComponentKind::of::<Position>());
+    assert!(components[0].1.ends_with("Position"));
+    assert_eq!((components[0].2, components[0].3), (0, 2));
+    assert_eq!(components[1].0, ComponentKind::of::<Health>());
+    assert_eq!((components[1].2, components[1].3), (1, 1));
+
+    // the built-in Messages are registered first
+    let (kind, type_name, net_id) = protocol.message_kinds.iter().last().unwrap();
+    assert_eq!(kind, MessageKind::of::<Auth>());
+    assert!(type_name.ends_with("Auth"));
+    assert_eq!(net_id as usize, protocol.message_kinds.iter().count() - 1);
+
+    // as is the System Channel
+    let (kind, type_name, net_id, settings) = protocol.channel_kinds.iter().last().unwrap();
+    assert_eq!(kind, ChannelKind::of::<GameplayChannel>());
+    assert!(type_name.ends_with("GameplayChannel"));
+    assert_eq!(net_id, 1);
+    assert!(settings.reliable());
+    assert!(settings.direction == ChannelDirection::ServerToClient);
+}
+
+#[test]
+fn digest_covers_channel_settings() {
+    assert_eq!(protocol(1.5).schema_digest(), protocol(1.5).schema_digest());
+    // the schema hash only covers registration order, the digest also
+    // covers settings
+    assert_eq!(protocol(1.5).schema_hash(), protocol(2.0).schema_hash());
+    assert_ne!(protocol(1.5).schema_digest(), protocol(2.0).schema_digest());
+}
+
+#[test]
+fn schema_json_describes_each_kind() {
+    let protocol = protocol(1.5);
+    let mut output = Vec::new();
+    protocol.write_schema_json(&mut output).unwrap();
+    let json = String::from_utf8(output).unwrap();
+
+    assert!(json.contains(&format!(
+        "\"schema_digest\": \"{:016x}\"",
+        protocol.schema_digest()
+    )));
+    assert!(json.contains(
+        "\"direction\": \"ServerToClient\", \"mode\": \"OrderedReliable\", \"rtt_resend_factor\": 1.5 }"
+    ));
+    assert!(json.contains("Position\", \"properties\": 2, \"full_update\": false }"));
+    assert!(json.contains("Health\", \"properties\": 1, \"full_update\": true }"));
+    assert!(json.trim_end().ends_with("]\n}"));
+}
@@ -0,0 +1,237 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, MessageEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{
+    BitReader, BitWriter, Channel, ChannelDirection, ChannelMode, FakeEntityConverter, Message,
+    MessageContainer, Property, Protocol, ReliableSettings, Replicate, WorldRefType,
+};
+use naia_test::{Auth, LocalNetwork};
+
+// "combat" game mode
+
+#[derive(Channel)]
+pub struct CombatChannel;
+
+#[derive(Message)]
+pub struct Attack {
+    pub damage: u8,
+}
+
+#[derive(Replicate)]
+pub struct Health {
+    pub value: Property<u8>,
+}
+
+fn combat_plugin() -> Protocol {
+    Protocol::builder()
+        .add_channel::<CombatChannel>(
+            ChannelDirection::ClientToServer,
+            ChannelMode::UnorderedReliable(ReliableSettings::default()),
+        )
+        .add_message::<Attack>()
+        .add_component::<Health>()
+        .build()
+}
+
+// "chat" game mode
+
+#[derive(Channel)]
+pub struct ChatChannel;
+
+#[derive(Message)]
+pub struct Chat {
+    pub text: String,
+}
+
+#[derive(Message)]
+pub struct Emote {
+    pub id: u8,
+}
+
+#[derive(Replicate)]
+pub struct Nameplate {
+    pub color: Property<u8>,
+}
+
+fn chat_plugin() -> Protocol {
+    Protocol::builder()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .add_message::<Emote>()
+        .add_message::<Chat>()
+        .add_component::<Nameplate>()
+        .build()
+}
+
+fn base() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_message::<Auth>()
+        .build()
+}
+
+// loads the game modes in the given order
+fn protocol(combat_first: bool, stable_ids: bool) -> Protocol {
+    let mut protocol = base();
+    if stable_ids {
+        protocol.with_stable_ids();
+    }
+    if combat_first {
+        protocol.merge(combat_plugin()).merge(chat_plugin());
+    } else {
+        protocol.merge(chat_plugin()).merge(combat_plugin());
+    }
+    protocol.build()
+}
+
+fn message_ids(protocol: &Protocol) -> Vec<(String, u16)> {
+    let mut ids: Vec<(String, u16)> = protocol
+        .message_kinds
+        .iter()
+        .map(|(_, type_name, net_id)| (type_name.to_string(), net_id))
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn stable_ids_ignore_registration_order() {
+    let combat_first = protocol(true, true);
+    let chat_first = protocol(false, true);
+
+    assert_eq!(combat_first.schema_hash(), chat_first.schema_hash());
+    assert_eq!(combat_first.schema_digest(), chat_first.schema_digest());
+    assert_eq!(message_ids(&combat_first), message_ids(&chat_first));
+
+    // a Message written by one is read back by the other
+    let mut converter = FakeEntityConverter;
+    let message = MessageContainer::from_write(Box::new(Emote { id: 9 }), &mut converter);
+    let mut writer = BitWriter::new();
+    message.write(&combat_first.message_kinds, &mut writer, &mut converter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let message = chat_first
+        .message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap();
+    let emote = message.to_boxed_any().downcast::<Emote>().unwrap();
+    assert_eq!(emote.id, 9);
+}
+
+#[test]
+fn registration_order_matters_without_stable_ids() {
+    let combat_first = protocol(true, false);
+    let chat_first = protocol(false, false);
+
+    assert_ne!(combat_first.schema_hash(), chat_first.schema_hash());
+    assert_ne!(message_ids(&combat_first), message_ids(&chat_first));
+}
+
+fn kind_counts(protocol: &Protocol) -> (usize, usize, usize) {
+    (
+        protocol.channel_kinds.iter().count(),
+        protocol.message_kinds.iter().count(),
+        protocol.component_kinds.iter().count(),
+    )
+}
+
+#[test]
+fn merged_kinds_are_kept_once() {
+    let mut once = base();
+    once.merge(combat_plugin()).merge(chat_plugin());
+
+    let mut twice = base();
+    twice
+        .merge(combat_plugin())
+        .merge(chat_plugin())
+        .merge(combat_plugin());
+
+    assert_eq!(kind_counts(&once.build()), kind_counts(&twice.build()));
+}
+
+#[test]
+#[should_panic(expected = "different settings")]
+fn merging_a_channel_with_other_settings_panics() {
+    let mut other = Protocol::builder();
+    other.add_channel::<CombatChannel>(
+        ChannelDirection::Bidirectional,
+        ChannelMode::UnorderedReliable(ReliableSettings::default()),
+    );
+
+    let mut protocol = base();
+    protocol.merge(combat_plugin()).merge(other.build());
+}
+
+#[test]
+fn client_and_server_loading_plugins_in_different_orders_interoperate() {
+    let network = LocalNetwork::new();
+
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol(true, true));
+    server.listen(network.server_socket());
+    let mut server_world = World::default();
+    let room_key: RoomKey = server.make_room().key();
+    let entity = server
+        .spawn_entity(server_world.proxy_mut())
+        .insert_component(Nameplate::new_complete(4))
+        .insert_component(Health::new_complete(100))
+        .id();
+    server.room_mut(&room_key).add_entity(&entity);
+
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(false, true),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    let mut client_world = World::default();
+
+    let mut sent = false;
+    let mut received = Vec::new();
+    let mut replicated = None;
+    for _ in 0..400 {
+        if !received.is_empty() && replicated.is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+
+        client.receive(client_world.proxy_mut());
+        if !sent && client.connection_status().is_connected() {
+            client.send_message::<CombatChannel, Attack>(&Attack { damage: 12 });
+            sent = true;
+        }
+        let world = client_world.proxy();
+        if let Some(client_entity) = client.entities(&world).first() {
+            if let (Some(nameplate), Some(health)) = (
+                world.component::<Nameplate>(client_entity),
+                world.component::<Health>(client_entity),
+            ) {
+                replicated = Some((*nameplate.color, *health.value));
+            }
+        }
+
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+            server.room_mut(&room_key).add_user(&user_key);
+        }
+        for (_, attack) in events.read::<MessageEvent<CombatChannel, Attack>>() {
+            received.push(attack.damage);
+        }
+        for (_, user_key, entity) in server.scope_checks() {
+            server.user_scope_mut(&user_key).include(&entity);
+        }
+        server.send_all_updates(server_world.proxy());
+    }
+
+    assert_eq!(received, vec![12]);
+    assert_eq!(replicated, Some((4, 100)));
+}
@@ -0,0 +1,137 @@
+use std::f32::consts::{PI, TAU};
+
+use naia_shared::{
+    BitReader, BitWriter, DiffMask, FakeEntityConverter, Property, Quantization, Replicate,
+};
+
+#[derive(Replicate)]
+pub struct Transform {
+    #[quantize(min = -1024.0, max = 1024.0, precision = 0.01)]
+    pub x: Property<f32>,
+    #[quantize(min = -1024.0, max = 1024.0, precision = 0.01)]
+    pub y: Property<f32>,
+    #[angle]
+    pub rotation: Property<f32>,
+    #[angle(precision = 0.1)]
+    pub pitch: Property<f32>,
+}
+
+#[derive(Replicate)]
+pub struct RawTransform {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub rotation: Property<f32>,
+    pub pitch: Property<f32>,
+}
+
+// writes every field, returning the bytes & how many bits they took
+fn write_fields(component: &dyn Replicate) -> (Box<[u8]>, u32) {
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    component.write_fields(&mut writer, &mut FakeEntityConverter);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+fn round_trip(transform: &Transform) -> Transform {
+    let (bytes, _) = write_fields(transform);
+    let mut reader = BitReader::new(&bytes);
+    let remote = Transform::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap();
+    *remote.to_boxed_any().downcast::<Transform>().unwrap()
+}
+
+#[test]
+fn quantized_fields_take_the_fewest_bits() {
+    // 2048 / 0.01 = 204800 steps, which fit in 18 bits
+    let (_, quantized_bits) = write_fields(&Transform::new_complete(1.0, 2.0, 3.0, 4.0));
+    // 2π / 0.001 = 6284 steps in 13 bits, 2π / 0.1 = 63 steps in 6 bits
+    assert_eq!(quantized_bits, 18 + 18 + 13 + 6);
+
+    let (_, raw_bits) = write_fields(&RawTransform::new_complete(1.0, 2.0, 3.0, 4.0));
+    assert_eq!(raw_bits, 4 * 32);
+}
+
+#[test]
+fn updates_are_quantized_too() {
+    let transform = Transform::new_complete(1.0, 2.0, 3.0, 4.0);
+    let mut diff_mask = DiffMask::new(transform.diff_mask_size());
+    diff_mask.set_bit(0, true);
+
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    transform.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+    // a bit per field for whether it changed, & the 18 bits of x
+    assert_eq!(bits_free - writer.bits_free(), 4 + 18);
+
+    let bytes = writer.to_bytes();
+    let mut reader = BitReader::new(&bytes);
+    let update = Transform::create_builder()
+        .read_create_update(&mut reader)
+        .unwrap();
+    let mut remote = round_trip(&Transform::new_complete(0.0, 0.0, 0.0, 0.0));
+    remote
+        .read_apply_update(&FakeEntityConverter, update)
+        .unwrap();
+    assert!((*remote.x - 1.0).abs() <= 0.005);
+    assert!(remote.y.abs() <= 0.005);
+}
+
+#[test]
+fn round_trip_error_is_within_half_the_precision() {
+    for value in [
+        -1024.0, -1023.456, -0.005, 0.0, 0.123, 512.5, 1023.999, 1024.0,
+    ] {
+        let remote = round_trip(&Transform::new_complete(value, -value, 0.0, 0.0));
+        assert!(
+            (*remote.x - value).abs() <= 0.005,
+            "{} was read as {}",
+            value,
+            *remote.x
+        );
+        assert!((*remote.y + value).abs() <= 0.005);
+    }
+
+    for angle in [0.0, 0.25, PI, TAU - 0.01] {
+        let remote = round_trip(&Transform::new_complete(0.0, 0.0, angle, angle));
+        assert!((*remote.rotation - angle).abs() <= 0.0005);
+        assert!((*remote.pitch - angle).abs() <= 0.05);
+    }
+}
+
+#[test]
+fn angles_wrap_into_range() {
+    let remote = round_trip(&Transform::new_complete(0.0, 0.0, TAU + 1.0, -1.0));
+    assert!((*remote.rotation - 1.0).abs() <= 0.0005);
+    assert!((*remote.pitch - (TAU - 1.0)).abs() <= 0.05);
+}
+
+#[test]
+fn range_ends_are_the_lowest_and_highest_levels() {
+    let quantization = Quantization::new(-1.0, 1.0, 8);
+    assert_eq!(quantization.bit_length(), 8);
+    assert_eq!(quantization.quantize(-1.0), 0);
+    assert_eq!(quantization.quantize(1.0), 255);
+    assert_eq!(quantization.dequantize(0), -1.0);
+    assert_eq!(quantization.dequantize(255), 1.0);
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn out_of_range_writes_are_clamped() {
+    let quantization = Quantization::new(-1.0, 1.0, 8);
+    assert_eq!(quantization.quantize(5.0), 255);
+    assert_eq!(quantization.quantize(-5.0), 0);
+
+    let remote = round_trip(&Transform::new_complete(2048.0, -2048.0, 0.0, 0.0));
+    assert_eq!(*remote.x, 1024.0);
+    assert_eq!(*remote.y, -1024.0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "out of the range")]
+fn out_of_range_writes_are_caught_in_debug_builds() {
+    write_fields(&Transform::new_complete(2048.0, 0.0, 0.0, 0.0));
+}
@@ -0,0 +1,218 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, RequestEvent as ClientRequestEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, RequestEvent, Server, ServerConfig, UserKey};
+use naia_shared::{
+    default_channels::OrderedReliableChannel, Message, Protocol, Request, Response, ResponseSendKey,
+};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Message)]
+pub struct Question {
+    pub value: u8,
+}
+
+impl Request for Question {
+    type Response = Answer;
+}
+
+#[derive(Message)]
+pub struct Answer {
+    pub value: u8,
+}
+
+impl Response for Answer {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_request::<Question>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_key: Option<UserKey>,
+    // Questions held until the test answers them
+    questions: Vec<(ResponseSendKey<Answer>, u8)>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            user_key: None,
+            questions: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.user_key = Some(user_key);
+        }
+        for (_, response_key, question) in
+            events.read::<RequestEvent<OrderedReliableChannel, Question>>()
+        {
+            self.questions.push((response_key, question.value));
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+
+    fn answer_all(&mut self) {
+        for (response_key, value) in std::mem::take(&mut self.questions) {
+            self.server
+                .send_response(&response_key, &Answer { value: value * 2 });
+        }
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    // Questions held until the test answers them
+    questions: Vec<(ResponseSendKey<Answer>, u8)>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, _) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            questions: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        for (response_key, question) in
+            events.read::<ClientRequestEvent<OrderedReliableChannel, Question>>()
+        {
+            self.questions.push((response_key, question.value));
+        }
+    }
+
+    fn answer_all(&mut self) {
+        for (response_key, value) in std::mem::take(&mut self.questions) {
+            self.client
+                .send_response(&response_key, &Answer { value: value * 2 });
+        }
+    }
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    mut done: impl FnMut(&mut TestServer, &mut TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        sleep(Duration::from_millis(5));
+        client.update();
+        server.update();
+    }
+    panic!("timed out");
+}
+
+fn connect(network: &LocalNetwork) -> (TestServer, TestClient) {
+    let mut server = TestServer::new(network);
+    let mut client = TestClient::new(network);
+    update_until(&mut server, &mut client, |server, client| {
+        server.user_key.is_some() && client.client.connection_status().is_connected()
+    });
+    (server, client)
+}
+
+#[test]
+fn late_response_to_cancelled_client_request_is_dropped() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+
+    let cancelled_key = client
+        .client
+        .send_request::<OrderedReliableChannel, Question>(&Question { value: 1 })
+        .unwrap();
+    update_until(&mut server, &mut client, |server, _| {
+        !server.questions.is_empty()
+    });
+    assert!(client.client.cancel_request(&cancelled_key));
+    assert!(!client.client.cancel_request(&cancelled_key));
+
+    // the Response to the later Request arrives after the cancelled one's
+    let later_key = client
+        .client
+        .send_request::<OrderedReliableChannel, Question>(&Question { value: 2 })
+        .unwrap();
+    update_until(&mut server, &mut client, |server, _| {
+        server.questions.len() == 2
+    });
+    server.answer_all();
+    let mut later_answer = None;
+    update_until(&mut server, &mut client, |_, client| {
+        assert!(client.client.receive_response(&cancelled_key).is_none());
+        later_answer = client.client.receive_response(&later_key);
+        later_answer.is_some()
+    });
+    assert_eq!(later_answer.unwrap().value, 4);
+    assert!(client.client.receive_response(&cancelled_key).is_none());
+}
+
+#[test]
+fn late_response_to_cancelled_server_request_is_dropped() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+    let user_key = server.user_key.unwrap();
+
+    let cancelled_key = server
+        .server
+        .send_request::<OrderedReliableChannel, Question>(&user_key, &Question { value: 1 })
+        .unwrap();
+    update_until(&mut server, &mut client, |_, client| {
+        !client.questions.is_empty()
+    });
+    assert!(server.server.cancel_request(&cancelled_key));
+    assert!(!server.server.cancel_request(&cancelled_key));
+
+    // the Response to the later Request arrives after the cancelled one's
+    let later_key = server
+        .server
+        .send_request::<OrderedReliableChannel, Question>(&user_key, &Question { value: 2 })
+        .unwrap();
+    update_until(&mut server, &mut client, |_, client| {
+        client.questions.len() == 2
+    });
+    client.answer_all();
+    let mut later_answer = None;
+    update_until(&mut server, &mut client, |server, _| {
+        assert!(server.server.receive_response(&cancelled_key).is_none());
+        later_answer = server.server.receive_response(&later_key);
+        later_answer.is_some()
+    });
+    let (answer_user_key, answer) = later_answer.unwrap();
+    assert_eq!(answer_user_key, user_key);
+    assert_eq!(answer.value, 4);
+    assert!(server.server.receive_response(&cancelled_key).is_none());
+}
@@ -0,0 +1,247 @@
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
+
+use naia_client::{Client, ClientConfig, RequestEvent as ClientRequestEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, RequestEvent, Server, ServerConfig, UserKey};
+use naia_shared::{
+    default_channels::OrderedReliableChannel, Message, Protocol, Request, ResponderResult,
+    Response, ResponseSendKey,
+};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Message)]
+pub struct Question {
+    pub value: u8,
+}
+
+impl Request for Question {
+    type Response = Answer;
+}
+
+#[derive(Message)]
+pub struct Answer {
+    pub value: u8,
+}
+
+impl Response for Answer {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_request::<Question>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    user_key: Option<UserKey>,
+    server_requests: Vec<u8>,
+    client: Client<Entity>,
+    client_world: World,
+    client_requests: Vec<u8>,
+}
+
+impl Test {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+
+        let mut test = Self {
+            server,
+            server_world: World::default(),
+            user_key: None,
+            server_requests: Vec::new(),
+            client,
+            client_world: World::default(),
+            client_requests: Vec::new(),
+        };
+        test.update_until(|test| {
+            test.user_key.is_some() && test.client.connection_status().is_connected()
+        });
+        test
+    }
+
+    fn update(&mut self) {
+        sleep(Duration::from_millis(5));
+        let mut client_events = self.client.receive(self.client_world.proxy_mut());
+        for (_, question) in
+            client_events.read::<ClientRequestEvent<OrderedReliableChannel, Question>>()
+        {
+            self.client_requests.push(question.value);
+        }
+        let mut server_events = self.server.receive(self.server_world.proxy_mut());
+        for (user_key, _) in server_events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in server_events.read::<ConnectEvent>() {
+            self.user_key = Some(user_key);
+        }
+        for (_, _, question) in
+            server_events.read::<RequestEvent<OrderedReliableChannel, Question>>()
+        {
+            self.server_requests.push(question.value);
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+    }
+
+    fn update_until(&mut self, mut done: impl FnMut(&mut Self) -> bool) {
+        for _ in 0..400 {
+            if done(self) {
+                return;
+            }
+            self.update();
+        }
+        panic!("timed out");
+    }
+
+    fn ask_server(&mut self, value: u8) -> Answer {
+        let response_key = self
+            .client
+            .send_request::<OrderedReliableChannel, Question>(&Question { value })
+            .unwrap();
+        let mut answer = None;
+        self.update_until(|test| {
+            answer = test.client.receive_response(&response_key);
+            answer.is_some()
+        });
+        answer.unwrap()
+    }
+}
+
+#[test]
+fn server_responder_answers_and_declines_requests() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+
+    let asked = Arc::new(Mutex::new(Vec::new()));
+    let handler_asked = asked.clone();
+    test.server
+        .register_responder::<OrderedReliableChannel, Question>(move |user_key, question| {
+            handler_asked.lock().unwrap().push(*user_key);
+            if question.value == 0 {
+                ResponderResult::DeclineToHandle
+            } else {
+                ResponderResult::Respond(Answer {
+                    value: question.value * 2,
+                })
+            }
+        });
+
+    // answered by the responder, without a RequestEvent
+    assert_eq!(test.ask_server(21).value, 42);
+    assert!(test.server_requests.is_empty());
+    assert_eq!(*asked.lock().unwrap(), vec![test.user_key.unwrap()]);
+
+    // declined Requests fall through as RequestEvents
+    test.client
+        .send_request::<OrderedReliableChannel, Question>(&Question { value: 0 })
+        .unwrap();
+    test.update_until(|test| !test.server_requests.is_empty());
+    assert_eq!(test.server_requests, vec![0]);
+
+    // and so do all Requests, once the responder is gone
+    assert!(test
+        .server
+        .unregister_responder::<OrderedReliableChannel, Question>());
+    assert!(!test
+        .server
+        .unregister_responder::<OrderedReliableChannel, Question>());
+    test.client
+        .send_request::<OrderedReliableChannel, Question>(&Question { value: 3 })
+        .unwrap();
+    test.update_until(|test| test.server_requests.len() == 2);
+    assert_eq!(test.server_requests, vec![0, 3]);
+    assert_eq!(asked.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn server_responder_defers_requests() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+
+    let (sender, receiver) = mpsc::channel::<(ResponseSendKey<Answer>, u8)>();
+    let sender = Mutex::new(sender);
+    test.server
+        .register_responder::<OrderedReliableChannel, Question>(move |_, question| {
+            let sender = sender.lock().unwrap().clone();
+            ResponderResult::Defer(Box::new(move |response_key| {
+                sender.send((response_key, question.value)).unwrap();
+            }))
+        });
+
+    let response_key = test
+        .client
+        .send_request::<OrderedReliableChannel, Question>(&Question { value: 5 })
+        .unwrap();
+    let mut deferred = None;
+    test.update_until(|_| {
+        deferred = receiver.try_recv().ok();
+        deferred.is_some()
+    });
+    assert!(test.server_requests.is_empty());
+
+    // answered later, from outside the handler
+    let (send_key, value) = deferred.unwrap();
+    assert!(test
+        .server
+        .send_response(&send_key, &Answer { value: value + 1 }));
+    let mut answer = None;
+    test.update_until(|test| {
+        answer = test.client.receive_response(&response_key);
+        answer.is_some()
+    });
+    assert_eq!(answer.unwrap().value, 6);
+}
+
+#[test]
+fn client_responder_answers_server_requests() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    test.client
+        .register_responder::<OrderedReliableChannel, Question>(|question| {
+            if question.value == 0 {
+                ResponderResult::DeclineToHandle
+            } else {
+                ResponderResult::Respond(Answer {
+                    value: question.value + 100,
+                })
+            }
+        });
+    let user_key = test.user_key.unwrap();
+
+    let response_key = test
+        .server
+        .send_request::<OrderedReliableChannel, Question>(&user_key, &Question { value: 7 })
+        .unwrap();
+    let mut answer = None;
+    test.update_until(|test| {
+        answer = test.server.receive_response(&response_key);
+        answer.is_some()
+    });
+    assert_eq!(answer.unwrap().1.value, 107);
+    assert!(test.client_requests.is_empty());
+
+    test.server
+        .send_request::<OrderedReliableChannel, Question>(&user_key, &Question { value: 0 })
+        .unwrap();
+    test.update_until(|test| !test.client_requests.is_empty());
+    assert_eq!(test.client_requests, vec![0]);
+}
@@ -0,0 +1,230 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, RequestEvent as ClientRequestEvent,
+    RequestTimeoutEvent as ClientRequestTimeoutEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, ConnectEvent, RequestEvent, RequestTimeoutEvent, Server, ServerConfig, UserKey,
+};
+use naia_shared::{
+    default_channels::OrderedReliableChannel, GlobalRequestId, Message, Protocol, Request,
+    Response, ResponseReceiveKey,
+};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Message)]
+pub struct Question {
+    pub value: u8,
+}
+
+impl Request for Question {
+    type Response = Answer;
+}
+
+#[derive(Message)]
+pub struct Answer {
+    pub value: u8,
+}
+
+impl Response for Answer {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_request::<Question>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_key: Option<UserKey>,
+    answer_questions: bool,
+    timed_out: Vec<(UserKey, GlobalRequestId)>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            user_key: None,
+            answer_questions: true,
+            timed_out: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.user_key = Some(user_key);
+        }
+        for (_, response_key, question) in
+            events.read::<RequestEvent<OrderedReliableChannel, Question>>()
+        {
+            if self.answer_questions {
+                let answer = Answer {
+                    value: question.value * 2,
+                };
+                self.server.send_response(&response_key, &answer);
+            }
+        }
+        for (user_key, response_key) in events.read::<RequestTimeoutEvent<Question>>() {
+            self.timed_out.push((user_key, response_key.request_id()));
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    answer_questions: bool,
+    timed_out: Vec<GlobalRequestId>,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, _) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            answer_questions: true,
+            timed_out: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        for (response_key, question) in
+            events.read::<ClientRequestEvent<OrderedReliableChannel, Question>>()
+        {
+            if self.answer_questions {
+                let answer = Answer {
+                    value: question.value * 2,
+                };
+                self.client.send_response(&response_key, &answer);
+            }
+        }
+        for response_key in events.read::<ClientRequestTimeoutEvent<Question>>() {
+            self.timed_out.push(response_key.request_id());
+        }
+    }
+}
+
+fn update(server: &mut TestServer, client: &mut TestClient) {
+    sleep(Duration::from_millis(5));
+    client.update();
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    client: &mut TestClient,
+    mut done: impl FnMut(&mut TestServer, &mut TestClient) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, client) {
+            return;
+        }
+        update(server, client);
+    }
+    panic!("timed out");
+}
+
+fn connect(network: &LocalNetwork) -> (TestServer, TestClient) {
+    let mut server = TestServer::new(network);
+    let mut client = TestClient::new(network);
+    update_until(&mut server, &mut client, |server, client| {
+        server.user_key.is_some() && client.client.connection_status().is_connected()
+    });
+    (server, client)
+}
+
+#[test]
+fn client_request_is_answered_before_its_timeout() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+
+    let response_key = client
+        .client
+        .send_request_with_timeout::<OrderedReliableChannel, Question>(
+            &Question { value: 21 },
+            Duration::from_secs(5),
+        )
+        .unwrap();
+    let mut answer: Option<Answer> = None;
+    update_until(&mut server, &mut client, |_, client| {
+        answer = client.client.receive_response(&response_key);
+        answer.is_some()
+    });
+    assert_eq!(answer.unwrap().value, 42);
+    assert!(client.timed_out.is_empty());
+}
+
+#[test]
+fn unanswered_client_request_times_out() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+    server.answer_questions = false;
+
+    let response_key: ResponseReceiveKey<Answer> = client
+        .client
+        .send_request_with_timeout::<OrderedReliableChannel, Question>(
+            &Question { value: 1 },
+            Duration::from_millis(100),
+        )
+        .unwrap();
+    update_until(&mut server, &mut client, |_, client| {
+        !client.timed_out.is_empty()
+    });
+    assert!(client.timed_out == vec![response_key.request_id()]);
+
+    // the timed out Request no longer receives a Response
+    server.answer_questions = true;
+    for _ in 0..20 {
+        update(&mut server, &mut client);
+    }
+    assert!(client.client.receive_response(&response_key).is_none());
+}
+
+#[test]
+fn unanswered_server_request_times_out() {
+    let network = LocalNetwork::new();
+    let (mut server, mut client) = connect(&network);
+    client.answer_questions = false;
+    let user_key = server.user_key.unwrap();
+
+    let response_key = server
+        .server
+        .send_request_with_timeout::<OrderedReliableChannel, Question>(
+            &user_key,
+            &Question { value: 1 },
+            Duration::from_millis(100),
+        )
+        .unwrap();
+    update_until(&mut server, &mut client, |server, _| {
+        !server.timed_out.is_empty()
+    });
+    assert!(server.timed_out == vec![(user_key, response_key.request_id())]);
+    assert!(server.server.receive_response(&response_key).is_none());
+}
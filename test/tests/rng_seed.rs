@@ -0,0 +1,123 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::{default_channels::UnorderedUnreliableChannel, Protocol};
+use naia_test::{Auth, LocalNetwork};
+
+const CLIENT_COUNT: usize = 4;
+const ROUNDS: usize = 10;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    user_keys: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.user_keys.push(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn new_client(network: &LocalNetwork) -> (Client<Entity>, World) {
+    let (socket, _) = network.add_client();
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(socket);
+    (client, World::default())
+}
+
+/// Connects a Server to `CLIENT_COUNT` Clients, then records the order in
+/// which it sends packets to them over a number of `send_all_updates()` calls
+fn send_order(seed: u64) -> Vec<SocketAddr> {
+    let network = LocalNetwork::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(network.server_socket());
+    let mut server = TestServer {
+        server,
+        world: World::default(),
+        user_keys: Vec::new(),
+    };
+    let mut clients: Vec<(Client<Entity>, World)> =
+        (0..CLIENT_COUNT).map(|_| new_client(&network)).collect();
+
+    for _ in 0..400 {
+        if server.user_keys.len() == CLIENT_COUNT
+            && clients
+                .iter()
+                .all(|(client, _)| client.connection_status().is_connected())
+        {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        for (client, world) in &mut clients {
+            let _ = client.receive(world.proxy_mut());
+        }
+        server.update();
+    }
+    assert_eq!(server.user_keys.len(), CLIENT_COUNT);
+
+    let sent_to = Arc::new(Mutex::new(Vec::new()));
+    let tap_sent_to = sent_to.clone();
+    server
+        .server
+        .set_outgoing_tap(Some(Box::new(move |address, _| {
+            tap_sent_to.lock().unwrap().push(*address);
+        })));
+    server.server.set_rng_seed(seed);
+
+    // give every User one packet's worth of data per call, without reading
+    // anything in between, so that only the send order can differ
+    for _ in 0..ROUNDS {
+        for user_key in &server.user_keys {
+            server
+                .server
+                .send_message::<UnorderedUnreliableChannel, Auth>(user_key, &Auth::new("a", "b"));
+        }
+        server.server.send_all_updates(server.world.proxy());
+    }
+
+    let sent_to = sent_to.lock().unwrap().clone();
+    sent_to
+}
+
+#[test]
+fn seeded_servers_send_to_users_in_the_same_order() {
+    let first = send_order(7);
+    let second = send_order(7);
+    assert_eq!(first.len(), CLIENT_COUNT * ROUNDS);
+    assert_eq!(first, second);
+
+    // the seed is what decides the order
+    let other = send_order(8);
+    assert_ne!(first, other);
+}
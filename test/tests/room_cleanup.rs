@@ -0,0 +1,222 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, DisconnectEvent, RoomCleanupPolicy, RoomCleanupReason, RoomDestroyedEvent, RoomKey,
+    Server, ServerConfig, UserKey,
+};
+use naia_shared::{ConnectionConfig, Property, Protocol, Replicate, WorldRefType};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+fn connection_config() -> ConnectionConfig {
+    ConnectionConfig {
+        disconnection_timeout_duration: Duration::from_millis(200),
+        heartbeat_interval: Duration::from_millis(20),
+        ..Default::default()
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    entity: Entity,
+    user_keys: Vec<UserKey>,
+    disconnects: usize,
+    destroyed: Vec<(RoomKey, RoomCleanupReason)>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork, policy: RoomCleanupPolicy) -> Self {
+        let mut server = Server::<Entity>::new(
+            ServerConfig {
+                connection: connection_config(),
+                ..Default::default()
+            },
+            protocol(),
+        );
+        server.listen(network.server_socket());
+        let mut world = World::default();
+        let entity = server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Position::new_complete(7))
+            .id();
+        let room_key = server
+            .make_room()
+            .set_auto_destroy_policy(policy)
+            .add_entity(&entity)
+            .key();
+        Self {
+            server,
+            world,
+            room_key,
+            entity,
+            user_keys: Vec::new(),
+            disconnects: 0,
+            destroyed: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+            self.user_keys.push(user_key);
+        }
+        self.disconnects += events.read::<DisconnectEvent>().count();
+        self.destroyed.extend(events.read::<RoomDestroyedEvent>());
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                connection: connection_config(),
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+        Self {
+            client,
+            world: World::default(),
+        }
+    }
+
+    fn has_entity(&self) -> bool {
+        !self.client.entities(&self.world.proxy()).is_empty()
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.client.receive(client.world.proxy_mut());
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+fn connect(network: &LocalNetwork, server: &mut TestServer) -> Vec<TestClient> {
+    let mut clients = vec![TestClient::new(network), TestClient::new(network)];
+    update_until(server, &mut clients, |_, clients| {
+        clients.iter().all(TestClient::has_entity)
+    });
+    clients
+}
+
+#[test]
+fn users_disconnecting_together_destroy_room_once() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, RoomCleanupPolicy::DespawnEntities);
+    let mut clients = connect(&network, &mut server);
+
+    let user_keys = server.user_keys.clone();
+    for user_key in &user_keys {
+        server.server.user_mut(user_key).disconnect();
+    }
+    update_until(&mut server, &mut clients, |server, _| {
+        server.disconnects == 2
+    });
+    for _ in 0..10 {
+        update(&mut server, &mut clients);
+    }
+
+    assert!(server.destroyed == vec![(server.room_key, RoomCleanupReason::Empty)]);
+    assert!(!server.server.room_exists(&server.room_key));
+    assert!(!server.world.proxy().has_entity(&server.entity));
+}
+
+#[test]
+fn users_leaving_together_destroy_room_once() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, RoomCleanupPolicy::LeaveEntities);
+    let mut clients = connect(&network, &mut server);
+
+    let user_keys = server.user_keys.clone();
+    for user_key in &user_keys {
+        server
+            .server
+            .user_mut(user_key)
+            .leave_room(&server.room_key);
+    }
+    for _ in 0..10 {
+        update(&mut server, &mut clients);
+    }
+
+    assert!(server.destroyed == vec![(server.room_key, RoomCleanupReason::Empty)]);
+    assert!(!server.server.room_exists(&server.room_key));
+    assert!(server.world.proxy().has_entity(&server.entity));
+}
+
+#[test]
+fn rejoining_before_cleanup_keeps_room() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network, RoomCleanupPolicy::DespawnEntities);
+    let mut clients = connect(&network, &mut server);
+
+    let user_keys = server.user_keys.clone();
+    for user_key in &user_keys {
+        server
+            .server
+            .user_mut(user_key)
+            .leave_room(&server.room_key);
+    }
+    server
+        .server
+        .user_mut(&user_keys[0])
+        .enter_room(&server.room_key);
+    for _ in 0..10 {
+        update(&mut server, &mut clients);
+    }
+    assert!(server.destroyed.is_empty());
+    assert!(server.server.room_exists(&server.room_key));
+
+    // destroying a Room by hand is reported too
+    server.server.room_mut(&server.room_key).destroy();
+    update(&mut server, &mut clients);
+    assert!(server.destroyed == vec![(server.room_key, RoomCleanupReason::Manual)]);
+    assert!(server.world.proxy().has_entity(&server.entity));
+}
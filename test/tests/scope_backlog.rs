@@ -0,0 +1,185 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, DespawnEntityEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    AuthEvent, ConnectEvent, RoomKey, ScopeBacklogEvent, Server, ServerConfig, UserKey,
+};
+use naia_shared::{
+    ConnectionConfig, Property, Protocol, Replicate, ScopeBacklog, ScopeBacklogConfig,
+};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    room_key: RoomKey,
+    user_key: Option<UserKey>,
+    backlogs: Vec<(UserKey, ScopeBacklog)>,
+    client: Client<Entity>,
+    client_world: World,
+    despawned: usize,
+}
+
+impl Test {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(
+            ServerConfig {
+                connection: ConnectionConfig {
+                    // a tiny bandwidth cap
+                    max_packets_per_send: Some(1),
+                    scope_backlog: Some(ScopeBacklogConfig {
+                        max_pending_actions: 1000,
+                        max_oldest_age: Duration::from_secs(60),
+                        prioritize_despawns: true,
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            protocol(),
+        );
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+
+        Self {
+            server,
+            server_world: World::default(),
+            room_key,
+            user_key: None,
+            backlogs: Vec::new(),
+            client,
+            client_world: World::default(),
+            despawned: 0,
+        }
+    }
+
+    fn spawn(&mut self, count: usize) -> Vec<Entity> {
+        let entities: Vec<Entity> = (0..count)
+            .map(|_| {
+                self.server
+                    .spawn_entity(self.server_world.proxy_mut())
+                    .insert_component(Position::new_complete(1))
+                    .id()
+            })
+            .collect();
+        let mut room = self.server.room_mut(&self.room_key);
+        for entity in &entities {
+            room.add_entity(entity);
+        }
+        entities
+    }
+
+    fn update(&mut self) {
+        sleep(Duration::from_millis(5));
+        let mut client_events = self.client.receive(self.client_world.proxy_mut());
+        self.despawned += client_events.read::<DespawnEntityEvent>().count();
+
+        let mut server_events = self.server.receive(self.server_world.proxy_mut());
+        for (user_key, _) in server_events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        if let Some(user_key) = server_events.read::<ConnectEvent>().next() {
+            self.user_key = Some(user_key);
+        }
+        self.backlogs
+            .extend(server_events.read::<ScopeBacklogEvent>());
+        let changes: Vec<_> = self.server.scope_changes().collect();
+        for (_, user_key, entity) in changes {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+    }
+
+    fn update_until(&mut self, done: impl Fn(&Self) -> bool) {
+        for _ in 0..400 {
+            if done(self) {
+                return;
+            }
+            self.update();
+        }
+        panic!("timed out");
+    }
+
+    fn client_entity_count(&self) -> usize {
+        self.client.entities(&self.client_world.proxy()).len()
+    }
+
+    fn backlog(&self) -> ScopeBacklog {
+        self.server.scope_backlog(&self.user_key.unwrap()).unwrap()
+    }
+}
+
+#[test]
+fn backlog_is_reported_and_despawns_jump_the_queue() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    test.update_until(|test| test.user_key.is_some());
+    let early = test.spawn(5);
+    test.update_until(|test| test.client_entity_count() == 5);
+    assert!(test.backlogs.is_empty());
+
+    // far more comes into scope than fits through the connection
+    test.spawn(10_000);
+    test.update_until(|test| !test.backlogs.is_empty());
+    let (user_key, backlog) = test.backlogs[0];
+    assert_eq!(Some(user_key), test.user_key);
+    assert!(backlog.pending_actions >= 1000);
+    assert!(test.backlog().pending_actions > 0);
+
+    // despawns are sent ahead of the pending spawns
+    for entity in &early {
+        test.server
+            .entity_mut(test.server_world.proxy_mut(), entity)
+            .despawn();
+    }
+    test.update_until(|test| test.despawned == 5);
+    assert!(test.backlog().pending_actions > 1000);
+    assert!(test.client_entity_count() < 10_000);
+
+    // the event only fires once per backlog
+    assert_eq!(test.backlogs.len(), 1);
+}
+
+#[test]
+fn backlog_drains_once_delivered() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    test.update_until(|test| test.user_key.is_some());
+
+    test.spawn(20);
+    test.update();
+    assert!(test.backlog().pending_actions > 0);
+
+    test.update_until(|test| {
+        test.client_entity_count() == 20 && test.backlog().pending_actions == 0
+    });
+    assert_eq!(test.backlog().oldest_age, Duration::ZERO);
+    assert!(test.backlogs.is_empty());
+}
@@ -0,0 +1,273 @@
+use std::{
+    collections::HashSet,
+    net::SocketAddr,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, RoomKey, Server, ServerConfig, UserKey};
+use naia_shared::{Property, Protocol, Replicate};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Marker {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Marker>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+        }
+    }
+
+    fn update(&mut self) {
+        let _ = self.client.receive(self.world.proxy_mut());
+    }
+
+    fn entity_count(&self) -> usize {
+        self.client.entities(&self.world.proxy()).len()
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    connected: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            connected: Vec::new(),
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .connected
+            .iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        *user_key
+    }
+
+    fn spawn(&mut self) -> Entity {
+        self.server
+            .spawn_entity(self.world.proxy_mut())
+            .insert_component(Marker::new_complete(1))
+            .id()
+    }
+
+    // includes every Entity yielded by `scope_changes()`
+    fn include_changes(&mut self) -> usize {
+        let changes: Vec<_> = self.server.scope_changes().collect();
+        for (_, user_key, entity) in &changes {
+            self.server.user_scope_mut(user_key).include(entity);
+        }
+        changes.len()
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+// a Server with one connected User, in a Room of its own
+fn connected(network: &LocalNetwork) -> (TestServer, Vec<TestClient>, UserKey, RoomKey) {
+    let mut server = TestServer::new(network);
+    let mut clients = vec![TestClient::new(network)];
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.connected.len() == 1 && clients[0].client.connection_status().is_connected()
+    });
+    let user_key = server.user_key(&clients[0]);
+    let room_key = server.server.make_room().key();
+    server.server.room_mut(&room_key).add_user(&user_key);
+    (server, clients, user_key, room_key)
+}
+
+#[test]
+fn scope_changes_only_yields_new_combinations() {
+    let network = LocalNetwork::new();
+    let (mut server, _clients, user_key, room_key) = connected(&network);
+
+    let entities: Vec<Entity> = (0..3).map(|_| server.spawn()).collect();
+    for entity in &entities {
+        server.server.room_mut(&room_key).add_entity(entity);
+    }
+    let changes: HashSet<_> = server.server.scope_changes().collect();
+    let expected: HashSet<_> = entities
+        .iter()
+        .map(|entity| (room_key, user_key, *entity))
+        .collect();
+    assert!(changes == expected);
+
+    // nothing changed since
+    assert_eq!(server.server.scope_changes().count(), 0);
+
+    let entity = server.spawn();
+    server.server.room_mut(&room_key).add_entity(&entity);
+    let changes: Vec<_> = server.server.scope_changes().collect();
+    assert!(changes == vec![(room_key, user_key, entity)]);
+
+    // everything can be re-evaluated on demand
+    server.server.reevaluate_all_scopes();
+    assert_eq!(server.server.scope_changes().count(), 4);
+    assert_eq!(server.server.scope_checks().len(), 4);
+
+    // and scope_checks() reflects Rooms changing
+    server.server.room_mut(&room_key).remove_entity(&entity);
+    assert_eq!(server.server.scope_checks().len(), 3);
+    assert_eq!(server.server.scope_changes().count(), 0);
+}
+
+#[test]
+fn scopes_follow_changes() {
+    let network = LocalNetwork::new();
+    let (mut server, mut clients, user_key, room_key) = connected(&network);
+
+    let entities: Vec<Entity> = (0..3).map(|_| server.spawn()).collect();
+    for entity in &entities {
+        server.server.room_mut(&room_key).add_entity(entity);
+    }
+    assert_eq!(server.include_changes(), 3);
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].entity_count() == 3
+    });
+
+    // excluding an Entity takes it out of scope, with nothing re-yielded
+    server
+        .server
+        .user_scope_mut(&user_key)
+        .exclude(&entities[0]);
+    assert_eq!(server.include_changes(), 0);
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].entity_count() == 2
+    });
+
+    // as does removing an Entity from the Room
+    server
+        .server
+        .room_mut(&room_key)
+        .remove_entity(&entities[1]);
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].entity_count() == 1
+    });
+
+    // and clearing the User's scope
+    server.server.user_scope_mut(&user_key).clear();
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].entity_count() == 0
+    });
+}
+
+// how long an update of every User's scope takes, once they are all applied
+// and nothing has changed since
+fn idle_scope_update_duration(entity_count: usize) -> (Duration, Duration) {
+    let network = LocalNetwork::new();
+    let (mut server, _clients, _, room_key) = connected(&network);
+    for _ in 0..entity_count {
+        let entity = server.spawn();
+        server.server.room_mut(&room_key).add_entity(&entity);
+    }
+    server.include_changes();
+
+    let start = Instant::now();
+    server.server.update_entity_scopes(server.world.proxy());
+    let first_update = start.elapsed();
+
+    const FRAMES: u32 = 100;
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        assert_eq!(server.include_changes(), 0);
+        server.server.update_entity_scopes(server.world.proxy());
+    }
+    (first_update, start.elapsed() / FRAMES)
+}
+
+#[test]
+fn idle_scope_update_does_not_scale_with_entity_count() {
+    let (_, small_idle) = idle_scope_update_duration(100);
+    let (large_first, large_idle) = idle_scope_update_duration(10_000);
+
+    // evaluating 10,000 Entities takes far longer than a frame in which
+    // nothing needs evaluating
+    assert!(
+        large_idle * 20 < large_first,
+        "idle {:?}, first {:?}",
+        large_idle,
+        large_first
+    );
+    // and an idle frame costs about the same with 100x the Entities
+    assert!(
+        large_idle < small_idle * 10 + Duration::from_micros(50),
+        "idle with 100 Entities {:?}, with 10,000 {:?}",
+        small_idle,
+        large_idle
+    );
+}
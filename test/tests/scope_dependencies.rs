@@ -0,0 +1,226 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, RoomKey, Server, ServerConfig};
+use naia_shared::{Property, Protocol, Replicate, WorldRefType};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Avatar {
+    pub health: Property<u8>,
+}
+
+#[derive(Replicate)]
+pub struct Gun {
+    pub ammo: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Avatar>()
+        .add_component::<Gun>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    room_key: RoomKey,
+    avatar: Entity,
+    gun: Entity,
+    // whether the application scopes the avatar in
+    avatar_in_scope: bool,
+    dependents_follow: bool,
+    client: Client<Entity>,
+    client_world: World,
+}
+
+impl Test {
+    fn new(network: &LocalNetwork, server_config: ServerConfig) -> Self {
+        let dependents_follow = server_config.despawn_scope_dependents;
+        let mut server = Server::<Entity>::new(server_config, protocol());
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        let mut server_world = World::default();
+        let avatar = server
+            .spawn_entity(server_world.proxy_mut())
+            .insert_component(Avatar::new_complete(100))
+            .enter_room(&room_key)
+            .id();
+        let gun = server
+            .spawn_entity(server_world.proxy_mut())
+            .insert_component(Gun::new_complete(6))
+            .enter_room(&room_key)
+            .scope_depends_on(&avatar)
+            .unwrap()
+            .id();
+
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+
+        Self {
+            server,
+            server_world,
+            room_key,
+            avatar,
+            gun,
+            avatar_in_scope: false,
+            dependents_follow,
+            client,
+            client_world: World::default(),
+        }
+    }
+
+    fn update(&mut self) {
+        sleep(Duration::from_millis(5));
+        self.client.receive(self.client_world.proxy_mut());
+        let mut events = self.server.receive(self.server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            if entity != self.avatar || self.avatar_in_scope {
+                self.server.user_scope_mut(&user_key).include(&entity);
+            } else {
+                self.server.user_scope_mut(&user_key).exclude(&entity);
+            }
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+
+        // the Client never has the gun without its avatar, unless the gun may
+        // outlive it
+        if self.dependents_follow {
+            assert!(!self.client_has::<Gun>() || self.client_has::<Avatar>());
+        }
+    }
+
+    fn update_until(&mut self, done: impl Fn(&Self) -> bool) {
+        for _ in 0..400 {
+            if done(self) {
+                return;
+            }
+            self.update();
+        }
+        panic!("timed out");
+    }
+
+    fn client_has<R: Replicate>(&self) -> bool {
+        let world = self.client_world.proxy();
+        self.client
+            .entities(&world)
+            .iter()
+            .any(|entity| world.has_component::<R>(entity))
+    }
+}
+
+#[test]
+fn dependent_never_arrives_before_its_dependency() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network, ServerConfig::default());
+    assert_eq!(
+        test.server
+            .entity(test.server_world.proxy(), &test.gun)
+            .scope_dependencies(),
+        vec![test.avatar]
+    );
+
+    // the gun is in scope, but waits on its avatar
+    test.update_until(|test| test.client.connection_status().is_connected());
+    for _ in 0..50 {
+        test.update();
+    }
+    assert!(!test.client_has::<Gun>());
+
+    test.avatar_in_scope = true;
+    test.update_until(|test| test.client_has::<Gun>());
+    assert!(test.client_has::<Avatar>());
+
+    // & leaves along with it
+    test.avatar_in_scope = false;
+    test.update_until(|test| !test.client_has::<Avatar>());
+    assert!(!test.client_has::<Gun>());
+
+    // until it comes back
+    test.avatar_in_scope = true;
+    test.update_until(|test| test.client_has::<Gun>());
+
+    // once its avatar is despawned, the gun goes too, for good
+    test.server
+        .entity_mut(test.server_world.proxy_mut(), &test.avatar)
+        .despawn();
+    test.update_until(|test| !test.client_has::<Gun>());
+    for _ in 0..50 {
+        test.update();
+    }
+    assert!(!test.client_has::<Gun>());
+}
+
+#[test]
+fn dependents_can_outlive_dependencies() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(
+        &network,
+        ServerConfig {
+            despawn_scope_dependents: false,
+            ..Default::default()
+        },
+    );
+    test.avatar_in_scope = true;
+    test.update_until(|test| test.client_has::<Gun>());
+
+    // the gun only waited on its avatar to enter scope
+    test.avatar_in_scope = false;
+    test.update_until(|test| !test.client_has::<Avatar>());
+    for _ in 0..20 {
+        test.update();
+    }
+    assert!(test.client_has::<Gun>());
+}
+
+#[test]
+fn scope_dependencies_cannot_form_cycles() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network, ServerConfig::default());
+    let (avatar, gun) = (test.avatar, test.gun);
+    let holster = test
+        .server
+        .spawn_entity(test.server_world.proxy_mut())
+        .scope_depends_on(&gun)
+        .unwrap()
+        .id();
+
+    let mut avatar_mut = test
+        .server
+        .entity_mut(test.server_world.proxy_mut(), &avatar);
+    assert!(avatar_mut.scope_depends_on(&avatar).is_err());
+    assert!(avatar_mut.scope_depends_on(&gun).is_err());
+    assert!(avatar_mut.scope_depends_on(&holster).is_err());
+    assert!(avatar_mut.scope_dependencies().is_empty());
+
+    // once the gun no longer depends on the avatar, the avatar may depend on it
+    test.server
+        .entity_mut(test.server_world.proxy_mut(), &gun)
+        .remove_scope_dependency(&avatar);
+    assert!(test
+        .server
+        .entity_mut(test.server_world.proxy_mut(), &avatar)
+        .scope_depends_on(&holster)
+        .is_ok());
+    assert_eq!(
+        test.server.entity_scope_dependencies(&avatar),
+        vec![holster]
+    );
+}
@@ -0,0 +1,90 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, sleep},
+    time::Duration,
+};
+
+use naia_client::{Client, ClientConfig, MessageEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, ServerRunner};
+use naia_shared::{default_channels::UnorderedReliableChannel, Message, Protocol, WorldRefType};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Message)]
+pub struct Greeting {
+    pub room_count: u8,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_message::<Greeting>()
+        .build()
+}
+
+#[tokio::test]
+async fn runner_drives_the_server_from_async_code() {
+    let network = LocalNetwork::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(network.server_socket());
+    let world = Arc::new(Mutex::new(World::default()));
+    let (mut events, control) =
+        ServerRunner::spawn(server, world.clone(), Duration::from_millis(2));
+
+    // the Client keeps its own manual loop, on a thread of its own
+    let (socket, _) = network.add_client();
+    let client_thread = thread::spawn(move || {
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        let mut world = World::default();
+        for _ in 0..400 {
+            sleep(Duration::from_millis(5));
+            let mut events = client.receive(world.proxy_mut());
+            if let Some(greeting) = events
+                .read::<MessageEvent<UnorderedReliableChannel, Greeting>>()
+                .next()
+            {
+                return Some(greeting.room_count);
+            }
+        }
+        None
+    });
+
+    while let Some(mut events) = events.next().await {
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            control.accept_connection(&user_key);
+        }
+        let Some(user_key) = events.read::<ConnectEvent>().next() else {
+            continue;
+        };
+        let room_key = control.make_room().await.unwrap();
+        let room_count = control
+            .query(move |server| {
+                server.room_mut(&room_key).add_user(&user_key);
+                server.room_keys().len() as u8
+            })
+            .await
+            .unwrap();
+        control.send_message::<UnorderedReliableChannel, _>(&user_key, Greeting { room_count });
+        break;
+    }
+    assert_eq!(client_thread.join().unwrap(), Some(1));
+
+    // the World is free to be used between the runner's updates
+    assert!(world.lock().unwrap().proxy().entities().is_empty());
+
+    // shutting down ends the stream
+    control.shutdown();
+    while events.next().await.is_some() {}
+    assert!(control.make_room().await.is_none());
+}
@@ -0,0 +1,157 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, DespawnEntityEvent, SpawnEntityEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, Server, ServerConfig, SpatialGrid};
+use naia_shared::{Property, Protocol, Replicate};
+use naia_test::{Auth, LocalNetwork};
+
+const CELL_SIZE: f32 = 10.0;
+
+#[derive(Replicate)]
+pub struct Marker {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_message::<Auth>()
+        .add_component::<Marker>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    grid: SpatialGrid<Entity>,
+    entity: Entity,
+    client: Client<Entity>,
+    client_world: World,
+    spawns: usize,
+    despawns: usize,
+}
+
+impl Test {
+    fn new(network: &LocalNetwork, entity_x: f32) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        let mut server_world = World::default();
+        let entity = server
+            .spawn_entity(server_world.proxy_mut())
+            .insert_component(Marker::new_complete(1))
+            .id();
+        let mut grid = SpatialGrid::new(CELL_SIZE);
+        grid.update_entity_position(&mut server, &entity, entity_x, 5.0);
+
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+
+        Self {
+            server,
+            server_world,
+            grid,
+            entity,
+            client,
+            client_world: World::default(),
+            spawns: 0,
+            despawns: 0,
+        }
+    }
+
+    fn update(&mut self) {
+        sleep(Duration::from_millis(5));
+
+        let mut events = self.client.receive(self.client_world.proxy_mut());
+        self.spawns += events.read::<SpawnEntityEvent>().count();
+        self.despawns += events.read::<DespawnEntityEvent>().count();
+
+        let mut events = self.server.receive(self.server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            // sees one cell around (0, 0) in every direction
+            self.grid
+                .update_user_position(&mut self.server, &user_key, 5.0, 5.0, 1);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+    }
+
+    fn wait_until(&mut self, condition: impl Fn(&Self) -> bool) {
+        for _ in 0..400 {
+            if condition(self) {
+                return;
+            }
+            self.update();
+        }
+        panic!("condition never met");
+    }
+
+    fn move_entity(&mut self, x: f32) {
+        self.grid
+            .update_entity_position(&mut self.server, &self.entity, x, 5.0);
+    }
+}
+
+#[test]
+fn entity_crossing_a_cell_boundary_stays_in_scope() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network, 5.0);
+    test.wait_until(|test| test.spawns == 1);
+
+    // walk from cell (0, 0) into cell (1, 0), both in view
+    for step in 0..=10 {
+        test.move_entity(5.0 + step as f32);
+        test.update();
+        test.update();
+    }
+    assert_eq!(test.grid.entity_cell(&test.entity), Some((1, 0)));
+    assert_eq!(
+        test.server.entity_rooms(&test.entity),
+        vec![test.grid.cell_room(&(1, 0)).unwrap()]
+    );
+    for _ in 0..20 {
+        test.update();
+    }
+
+    assert_eq!(test.spawns, 1);
+    assert_eq!(test.despawns, 0);
+    assert_eq!(test.client.entities(&test.client_world.proxy()).len(), 1);
+}
+
+#[test]
+fn entity_leaves_scope_once_out_of_view() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network, 15.0);
+    test.wait_until(|test| test.spawns == 1);
+
+    // cell (3, 0) is two cells past the User's view
+    test.move_entity(35.0);
+    test.wait_until(|test| test.despawns == 1);
+    assert!(test.client.entities(&test.client_world.proxy()).is_empty());
+
+    // and re-enters once back in view
+    test.move_entity(-5.0);
+    test.wait_until(|test| test.spawns == 2);
+}
+
+#[test]
+fn entity_out_of_view_is_never_spawned() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network, 25.0);
+    test.wait_until(|test| test.client.connection_status().is_connected());
+    for _ in 0..100 {
+        test.update();
+    }
+    assert_eq!(test.spawns, 0);
+}
@@ -0,0 +1,242 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, RoomKey, Server, ServerConfig, UserKey};
+use naia_shared::{
+    ConnectionConfig, EntityAuthStatus, Property, Protocol, Replicate, WorldMutType, WorldRefType,
+};
+use naia_test::{Auth, LocalClientSocket, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .enable_client_authoritative_entities()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+fn connection_config() -> ConnectionConfig {
+    ConnectionConfig {
+        disconnection_timeout_duration: Duration::from_millis(200),
+        heartbeat_interval: Duration::from_millis(20),
+        ..Default::default()
+    }
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+    saw_available: bool,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address): (LocalClientSocket, SocketAddr) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                connection: connection_config(),
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+            saw_available: false,
+        }
+    }
+
+    fn update(&mut self) {
+        self.client.receive(self.world.proxy_mut());
+        if self.authority() == Some(EntityAuthStatus::Available) {
+            self.saw_available = true;
+        }
+    }
+
+    fn entity(&self) -> Option<Entity> {
+        self.client.entities(&self.world.proxy()).first().copied()
+    }
+
+    fn authority(&self) -> Option<EntityAuthStatus> {
+        let entity = self.entity()?;
+        self.client.entity(self.world.proxy(), &entity).authority()
+    }
+
+    fn set_x(&mut self, x: u8) {
+        let entity = self.entity().unwrap();
+        let mut world = self.world.proxy_mut();
+        let mut position = world.component_mut::<Position>(&entity).unwrap();
+        *position.x = x;
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    connected: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(
+            ServerConfig {
+                connection: connection_config(),
+                ..Default::default()
+            },
+            protocol(),
+        );
+        server.listen(network.server_socket());
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+            connected: Vec::new(),
+        }
+    }
+
+    fn spawn_for_user(&mut self, user_key: &UserKey) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity_for_user(self.world.proxy_mut(), user_key)
+            .insert_component(Position::new_complete(7))
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .server
+            .user_keys()
+            .into_iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn spawned_entity_arrives_granted_to_its_owner() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(&network), TestClient::new(&network)];
+    update_until(&mut server, &mut clients, |server, _| {
+        server.connected.len() == 2
+    });
+
+    let owner = server.user_key(&clients[0]);
+    let entity = server.spawn_for_user(&owner);
+    assert_eq!(
+        server.server.entity_authority_status(&entity),
+        Some(EntityAuthStatus::Denied)
+    );
+
+    update_until(&mut server, &mut clients, |_, clients| {
+        clients[0].authority() == Some(EntityAuthStatus::Granted)
+            && clients[1].authority() == Some(EntityAuthStatus::Denied)
+    });
+    // the owner never had to ask for authority
+    assert!(!clients[0].saw_available);
+
+    // the Server accepts updates from the owner
+    clients[0].set_x(42);
+    update_until(&mut server, &mut clients, |server, _| {
+        let world = server.world.proxy();
+        world
+            .component::<Position>(&entity)
+            .is_some_and(|position| *position.x == 42)
+    });
+
+    // authority reverts to the Server once the owner leaves
+    server.server.user_mut(&owner).disconnect();
+    update_until(&mut server, &mut clients, |server, clients| {
+        server.server.entity_authority_status(&entity) == Some(EntityAuthStatus::Available)
+            && clients[1].authority() == Some(EntityAuthStatus::Available)
+    });
+}
+
+#[test]
+fn spawning_for_a_disconnected_user_is_refused() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = vec![TestClient::new(&network)];
+    update_until(&mut server, &mut clients, |server, _| {
+        server.connected.len() == 1
+    });
+    let user_key = server.user_key(&clients[0]);
+
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(7))
+        .id();
+    // authority can only be given away once the Entity is delegated
+    assert!(server
+        .server
+        .entity_give_authority(&user_key, &entity)
+        .is_err());
+
+    server.server.user_mut(&user_key).disconnect();
+    update_until(&mut server, &mut clients, |server, _| {
+        server.server.user_keys().is_empty()
+    });
+    assert!(server
+        .server
+        .entity_give_authority(&user_key, &entity)
+        .is_err());
+}
@@ -0,0 +1,194 @@
+use std::{net::SocketAddr, thread::sleep, time::Duration};
+
+use naia_client::{
+    Client, ClientConfig, InsertComponentEvent, SpawnEntityEvent, SpawnEntityWithComponentsEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::{ComponentKind, Property, Protocol, Replicate};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<u8>,
+}
+
+#[derive(Replicate)]
+pub struct Color {
+    pub value: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .add_component::<Color>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+    spawns: Vec<Entity>,
+    spawns_with_components: Vec<(Entity, Vec<ComponentKind>)>,
+    inserts: usize,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork, spawn_with_components_events: bool) -> Self {
+        let (socket, address) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                spawn_with_components_events,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+            spawns: Vec::new(),
+            spawns_with_components: Vec::new(),
+            inserts: 0,
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.client.receive(self.world.proxy_mut());
+        self.spawns.extend(events.read::<SpawnEntityEvent>());
+        self.spawns_with_components
+            .extend(events.read::<SpawnEntityWithComponentsEvent>());
+        self.inserts += events.read::<InsertComponentEvent<Position>>().count();
+        self.inserts += events.read::<InsertComponentEvent<Color>>().count();
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    connected: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            connected: Vec::new(),
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .connected
+            .iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        *user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+fn update(server: &mut TestServer, clients: &mut [TestClient]) {
+    sleep(Duration::from_millis(5));
+    for client in clients.iter_mut() {
+        client.update();
+    }
+    server.update();
+}
+
+fn update_until(
+    server: &mut TestServer,
+    clients: &mut [TestClient],
+    done: impl Fn(&TestServer, &[TestClient]) -> bool,
+) {
+    for _ in 0..400 {
+        if done(server, clients) {
+            return;
+        }
+        update(server, clients);
+    }
+    panic!("timed out");
+}
+
+// spawns an Entity with a Position & Color, in scope for every connected User
+fn connect_and_spawn(network: &LocalNetwork, clients: &mut [TestClient]) -> TestServer {
+    let mut server = TestServer::new(network);
+    update_until(&mut server, clients, |server, clients| {
+        server.connected.len() == clients.len()
+            && clients
+                .iter()
+                .all(|client| client.client.connection_status().is_connected())
+    });
+
+    let entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new_complete(1))
+        .insert_component(Color::new_complete(2))
+        .id();
+    let room_key = server.server.make_room().key();
+    server.server.room_mut(&room_key).add_entity(&entity);
+    for client in clients.iter() {
+        let user_key = server.user_key(client);
+        server.server.room_mut(&room_key).add_user(&user_key);
+        server.server.user_scope_mut(&user_key).include(&entity);
+    }
+    server
+}
+
+#[test]
+fn spawn_arrives_with_its_components() {
+    let network = LocalNetwork::new();
+    let mut clients = vec![TestClient::new(&network, true)];
+    let mut server = connect_and_spawn(&network, &mut clients);
+    update_until(&mut server, &mut clients, |_, clients| {
+        !clients[0].spawns.is_empty()
+    });
+
+    let client = &clients[0];
+    assert_eq!(client.spawns_with_components.len(), 1);
+    let (entity, component_kinds) = &client.spawns_with_components[0];
+    assert!(*entity == client.spawns[0]);
+    assert_eq!(component_kinds.len(), 2);
+    assert!(component_kinds.contains(&ComponentKind::of::<Position>()));
+    assert!(component_kinds.contains(&ComponentKind::of::<Color>()));
+
+    // separate insert events are still fired
+    assert_eq!(client.inserts, 2);
+}
+
+#[test]
+fn spawn_with_components_is_opt_in() {
+    let network = LocalNetwork::new();
+    let mut clients = vec![TestClient::new(&network, false)];
+    let mut server = connect_and_spawn(&network, &mut clients);
+    update_until(&mut server, &mut clients, |_, clients| {
+        !clients[0].spawns.is_empty()
+    });
+
+    assert!(clients[0].spawns_with_components.is_empty());
+    assert_eq!(clients[0].inserts, 2);
+}
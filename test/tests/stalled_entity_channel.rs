@@ -0,0 +1,76 @@
+use std::{sync::Arc, time::Duration};
+
+use naia_shared::{set_thread_clock, EntityAction, EntityActionReceiver, Instant};
+use naia_test::TestClock;
+
+const ENTITY: u32 = 7;
+const STALL_TIMEOUT: Duration = Duration::from_secs(2);
+
+fn install_test_clock() -> Arc<TestClock> {
+    let clock = Arc::new(TestClock::new());
+    set_thread_clock(Some(clock.clone()));
+    clock
+}
+
+fn receiver() -> EntityActionReceiver<u32> {
+    let mut receiver = EntityActionReceiver::new();
+    receiver.set_stall_timeout(Some(STALL_TIMEOUT));
+    receiver
+}
+
+#[test]
+fn held_back_action_is_reported_after_timeout() {
+    let clock = install_test_clock();
+    let mut receiver = receiver();
+
+    // the despawn arrives, but the spawn before it never does
+    receiver.buffer_action(1, EntityAction::DespawnEntity(ENTITY));
+    assert!(receiver.receive_actions().is_empty());
+
+    clock.advance(STALL_TIMEOUT / 2);
+    assert!(receiver.take_stalled_entities(&Instant::now()).is_empty());
+
+    clock.advance(STALL_TIMEOUT);
+    assert_eq!(
+        receiver.take_stalled_entities(&Instant::now()),
+        vec![ENTITY]
+    );
+
+    // only reported once
+    clock.advance(STALL_TIMEOUT);
+    assert!(receiver.take_stalled_entities(&Instant::now()).is_empty());
+
+    set_thread_clock(None);
+}
+
+#[test]
+fn channel_which_catches_up_is_not_reported() {
+    let clock = install_test_clock();
+    let mut receiver = receiver();
+
+    receiver.buffer_action(1, EntityAction::DespawnEntity(ENTITY));
+    receiver.receive_actions();
+    clock.advance(STALL_TIMEOUT / 2);
+
+    receiver.buffer_action(0, EntityAction::SpawnEntity(ENTITY, Vec::new()));
+    assert_eq!(receiver.receive_actions().len(), 2);
+
+    clock.advance(STALL_TIMEOUT * 2);
+    assert!(receiver.take_stalled_entities(&Instant::now()).is_empty());
+
+    set_thread_clock(None);
+}
+
+#[test]
+fn stalls_are_not_reported_without_a_timeout() {
+    let clock = install_test_clock();
+    let mut receiver = EntityActionReceiver::<u32>::new();
+
+    receiver.buffer_action(1, EntityAction::DespawnEntity(ENTITY));
+    receiver.receive_actions();
+
+    clock.advance(Duration::from_secs(3600));
+    assert!(receiver.take_stalled_entities(&Instant::now()).is_empty());
+
+    set_thread_clock(None);
+}
@@ -0,0 +1,165 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    net::SocketAddr,
+    time::Duration,
+};
+
+use glam::{Quat, Vec2, Vec3};
+use uuid::Uuid;
+
+use naia_shared::{
+    BitReader, BitWriter, ConstBitLength, FakeEntityConverter, Message, Property, Protocol,
+    Replicate, Serde,
+};
+
+#[derive(Replicate)]
+pub struct Session {
+    pub id: Property<Uuid>,
+    pub address: Property<SocketAddr>,
+    pub uptime: Property<Duration>,
+    pub scores: Property<[u16; 3]>,
+    pub recent: Property<VecDeque<u8>>,
+    pub members: Property<HashSet<u16>>,
+}
+
+#[derive(Replicate)]
+pub struct Transform {
+    pub position: Property<Vec3>,
+    pub rotation: Property<Quat>,
+    pub scale: Property<Vec2>,
+}
+
+#[derive(Message)]
+pub struct Hello {
+    pub id: Uuid,
+    pub address: SocketAddr,
+    pub uptime: Duration,
+    pub recent: VecDeque<u8>,
+    pub members: HashSet<u16>,
+    pub facing: Quat,
+}
+
+fn session() -> Session {
+    Session::new_complete(
+        Uuid::from_u128(0x6fa4_59ea_ee8a_3ca4_894e_db77_e160_355e),
+        "[2001:db8::ff00:42:8329]:443".parse().unwrap(),
+        Duration::new(90_061, 500),
+        [1, 20, 300],
+        VecDeque::from([4, 5, 6]),
+        HashSet::from([7, 8]),
+    )
+}
+
+fn hello() -> Hello {
+    Hello {
+        id: Uuid::from_u128(42),
+        address: "127.0.0.1:14191".parse().unwrap(),
+        uptime: Duration::from_millis(1500),
+        recent: VecDeque::from([1, 2]),
+        members: HashSet::from([3]),
+        facing: Quat::from_rotation_z(0.5),
+    }
+}
+
+// writes a Component's fields, returning the bytes & how many bits it took
+fn write_fields(component: &dyn Replicate) -> (Box<[u8]>, u32) {
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    component.write_fields(&mut writer, &mut FakeEntityConverter);
+    let bits_written = bits_free - writer.bits_free();
+    (writer.to_bytes(), bits_written)
+}
+
+#[test]
+fn std_types_round_trip_in_properties() {
+    let local = session();
+    let (bytes, bits_written) = write_fields(&local);
+    // the packet writer relies on each field reporting its size exactly
+    assert_eq!(
+        bits_written,
+        local.id.bit_length()
+            + local.address.bit_length()
+            + local.uptime.bit_length()
+            + local.scores.bit_length()
+            + local.recent.bit_length()
+            + local.members.bit_length()
+    );
+
+    let mut reader = BitReader::new(&bytes);
+    let remote = Session::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Session>()
+        .unwrap();
+    assert_eq!(*remote.id, *local.id);
+    assert_eq!(*remote.address, *local.address);
+    assert_eq!(*remote.uptime, *local.uptime);
+    assert_eq!(*remote.scores, *local.scores);
+    assert_eq!(*remote.recent, *local.recent);
+    assert_eq!(*remote.members, *local.members);
+}
+
+#[test]
+fn math_types_round_trip_in_properties() {
+    let transform = Transform::new_complete(
+        Vec3::new(1.0, -2.5, 3.25),
+        Quat::from_rotation_y(1.2),
+        Vec2::ONE,
+    );
+    let (bytes, bits_written) = write_fields(&transform);
+    assert_eq!(bits_written, 96 + 128 + 64);
+    let mut reader = BitReader::new(&bytes);
+    let remote = Transform::create_builder()
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Transform>()
+        .unwrap();
+    assert_eq!(*remote.position, *transform.position);
+    assert_eq!(*remote.rotation, *transform.rotation);
+    assert_eq!(*remote.scale, *transform.scale);
+}
+
+#[test]
+fn std_types_round_trip_in_messages() {
+    let protocol = Protocol::builder().add_message::<Hello>().build();
+    let message = hello();
+
+    let mut writer = BitWriter::new();
+    let bits_free = writer.bits_free();
+    message.write(
+        &protocol.message_kinds,
+        &mut writer,
+        &mut FakeEntityConverter,
+    );
+    assert_eq!(
+        bits_free - writer.bits_free(),
+        message.bit_length(&mut FakeEntityConverter)
+    );
+
+    let bytes = writer.to_bytes();
+    let mut reader = BitReader::new(&bytes);
+    let remote = protocol
+        .message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Hello>()
+        .unwrap();
+    assert_eq!(remote.id, message.id);
+    assert_eq!(remote.address, message.address);
+    assert_eq!(remote.uptime, message.uptime);
+    assert_eq!(remote.recent, message.recent);
+    assert_eq!(remote.members, message.members);
+    assert_eq!(remote.facing, message.facing);
+}
+
+#[test]
+fn fixed_size_types_have_const_bit_lengths() {
+    assert_eq!(Uuid::const_bit_length(), 128);
+    assert_eq!(<[u16; 3]>::const_bit_length(), 48);
+    assert_eq!(Vec2::const_bit_length(), 64);
+    assert_eq!(Vec3::const_bit_length(), 96);
+    assert_eq!(Quat::const_bit_length(), 128);
+}
@@ -0,0 +1,119 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, StreamMessageEvent, UserKey};
+use naia_shared::Protocol;
+use naia_test::{Auth, LocalNetwork};
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .enable_streams()
+        .build()
+}
+
+fn payload(len: usize, seed: u8) -> Vec<u8> {
+    (0..len)
+        .map(|index| (index as u8).wrapping_mul(31).wrapping_add(seed))
+        .collect()
+}
+
+#[test]
+fn large_streams_arrive_whole_and_in_order() {
+    let network = LocalNetwork::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(network.server_socket());
+    let mut server_world = World::default();
+
+    let mut client = Client::<Entity>::new(
+        ClientConfig {
+            send_handshake_interval: Duration::from_millis(10),
+            handshake_pings: 2,
+            ..Default::default()
+        },
+        protocol(),
+    );
+    client.auth(Auth::new("charlie", "12345"));
+    client.connect(network.add_client().0);
+    let mut client_world = World::default();
+
+    // larger than a single packet, so each is fragmented
+    let to_server = payload(20_000, 1);
+    let to_client = vec![payload(12_000, 2), payload(3, 3), payload(9_000, 4)];
+
+    let mut user_key: Option<UserKey> = None;
+    let mut client_sent = false;
+    let mut server_received = Vec::new();
+    let mut client_received = Vec::new();
+    for _ in 0..400 {
+        if !server_received.is_empty() && client_received.len() == to_client.len() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+
+        client.receive(client_world.proxy_mut());
+        client_received.extend(client.receive_stream_messages());
+        if !client_sent && client.connection_status().is_connected() {
+            client.send_stream_message(&to_server);
+            client_sent = true;
+        }
+
+        let mut events = server.receive(server_world.proxy_mut());
+        for (new_user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&new_user_key);
+        }
+        for new_user_key in events.read::<ConnectEvent>() {
+            user_key = Some(new_user_key);
+            for bytes in &to_client {
+                server.send_stream(&new_user_key, bytes);
+            }
+        }
+        for (sender, bytes) in events.read::<StreamMessageEvent>() {
+            assert!(Some(sender) == user_key);
+            server_received.push(bytes);
+        }
+        server.send_all_updates(server_world.proxy());
+    }
+
+    assert!(server_received == vec![to_server]);
+    assert!(client_received == to_client);
+}
+
+#[test]
+fn streams_are_opt_in() {
+    let auth_net_id = |protocol: &Protocol| {
+        protocol
+            .message_kinds
+            .iter()
+            .find(|(_, type_name, _)| type_name.ends_with("Auth"))
+            .map(|(_, _, net_id)| net_id)
+            .unwrap()
+    };
+    let has_stream_kinds = |protocol: &Protocol| {
+        protocol
+            .message_kinds
+            .iter()
+            .any(|(_, type_name, _)| type_name.ends_with("StreamMessage"))
+            || protocol
+                .channel_kinds
+                .iter()
+                .any(|(_, type_name, _, _)| type_name.ends_with("StreamChannel"))
+    };
+
+    let without_streams = Protocol::builder()
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build();
+    assert!(!has_stream_kinds(&without_streams));
+
+    // enabled after the application's kinds, they keep their NetIds
+    let with_streams = protocol();
+    assert!(has_stream_kinds(&with_streams));
+    assert_eq!(auth_net_id(&without_streams), auth_net_id(&with_streams));
+
+    // a Client & Server which disagree are caught during the handshake
+    assert_ne!(without_streams.schema_hash(), with_streams.schema_hash());
+}
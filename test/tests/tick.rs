@@ -0,0 +1,42 @@
+use naia_shared::{Tick, TickExt};
+
+#[test]
+fn ticks_compare_across_the_wrap() {
+    let before_wrap: Tick = 65530;
+    let after_wrap: Tick = 3;
+
+    assert!(after_wrap.is_after(before_wrap));
+    assert!(before_wrap.is_before(after_wrap));
+    assert!(!after_wrap.is_before(before_wrap));
+    assert!(!before_wrap.is_after(after_wrap));
+
+    assert_eq!(after_wrap.diff(before_wrap), 9);
+    assert_eq!(before_wrap.diff(after_wrap), -9);
+}
+
+#[test]
+fn ticks_compare_at_the_wrap_boundary() {
+    assert!(0.is_after(u16::MAX));
+    assert_eq!(0.diff(u16::MAX), 1);
+    assert_eq!(u16::MAX.diff(0), -1);
+}
+
+#[test]
+fn ticks_compare_without_wrap() {
+    let earlier: Tick = 100;
+    let later: Tick = 250;
+
+    assert!(later.is_after(earlier));
+    assert!(earlier.is_before(later));
+    assert_eq!(later.diff(earlier), 150);
+    assert_eq!(earlier.diff(later), -150);
+}
+
+#[test]
+fn equal_ticks_are_neither_before_nor_after() {
+    let tick: Tick = 65535;
+
+    assert!(!tick.is_after(tick));
+    assert!(!tick.is_before(tick));
+    assert_eq!(tick.diff(tick), 0);
+}
@@ -0,0 +1,168 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, ClientTickEvent};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, Server, ServerConfig, TickEvent, UserKey};
+use naia_shared::{
+    Channel, ChannelDirection, ChannelMode, ChannelSettings, Message, Protocol, TickBufferSettings,
+};
+use naia_test::{Auth, LocalNetwork};
+
+#[derive(Channel)]
+pub struct InputChannel;
+
+#[derive(Message)]
+pub struct Input {
+    pub value: u8,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_channel_with_settings::<InputChannel>(ChannelSettings::new(
+            ChannelMode::TickBuffered(TickBufferSettings::default()),
+            ChannelDirection::ClientToServer,
+        ))
+        .add_message::<Auth>()
+        .add_message::<Input>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    user_key: Option<UserKey>,
+    // whether the Server reads its tick buffer, which it stops doing to
+    // simulate a hitch
+    reading: bool,
+    received: usize,
+    client: Client<Entity>,
+    client_world: World,
+    sending: bool,
+    sent: usize,
+}
+
+impl Test {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(network.add_client().0);
+
+        Self {
+            server,
+            server_world: World::default(),
+            user_key: None,
+            reading: true,
+            received: 0,
+            client,
+            client_world: World::default(),
+            sending: false,
+            sent: 0,
+        }
+    }
+
+    fn update(&mut self) {
+        sleep(Duration::from_millis(5));
+        let mut client_events = self.client.receive(self.client_world.proxy_mut());
+        for (client_tick, _) in client_events.read::<ClientTickEvent>() {
+            if self.sending {
+                self.client.send_tick_buffer_message::<InputChannel, Input>(
+                    &client_tick,
+                    &Input { value: 1 },
+                );
+                self.sent += 1;
+            }
+        }
+
+        let mut server_events = self.server.receive(self.server_world.proxy_mut());
+        for (user_key, _) in server_events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        if let Some(user_key) = server_events.read::<ConnectEvent>().next() {
+            self.user_key = Some(user_key);
+        }
+        for server_tick in server_events.read::<TickEvent>() {
+            if self.reading {
+                let mut messages = self.server.receive_tick_buffer_messages(&server_tick);
+                self.received += messages.read::<InputChannel, Input>().len();
+            }
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+    }
+
+    fn update_until(&mut self, done: impl Fn(&Self) -> bool) {
+        for _ in 0..400 {
+            if done(self) {
+                return;
+            }
+            self.update();
+        }
+        panic!("timed out");
+    }
+
+    fn depth(&self) -> usize {
+        self.server.tick_buffer_depth(&self.user_key.unwrap())
+    }
+}
+
+#[test]
+fn cleared_backlog_is_never_read() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    test.update_until(|test| {
+        test.user_key.is_some() && test.client.connection_status().is_connected()
+    });
+    assert_eq!(test.depth(), 0);
+
+    // the Server stalls while the Client keeps sending input
+    test.reading = false;
+    test.sending = true;
+    test.update_until(|test| test.sent >= 8);
+    test.sending = false;
+    test.update_until(|test| test.depth() >= 8);
+    // let any input still in flight arrive, with the Client long past it
+    for _ in 0..20 {
+        test.update();
+    }
+    assert!(test.depth() >= 8);
+    let user_key = test.user_key.unwrap();
+
+    test.server.clear_tick_buffer(&user_key);
+    assert_eq!(test.depth(), 0);
+
+    // once the Server catches up, none of the stale input is read
+    test.reading = true;
+    for _ in 0..40 {
+        test.update();
+    }
+    assert_eq!(test.received, 0);
+    assert_eq!(test.depth(), 0);
+
+    // while fresh input flows as usual
+    test.sending = true;
+    test.update_until(|test| test.received > 0);
+}
+
+#[test]
+fn unknown_users_have_no_backlog() {
+    let network = LocalNetwork::new();
+    let mut test = Test::new(&network);
+    test.update_until(|test| test.user_key.is_some());
+    let user_key = test.user_key.unwrap();
+    test.server.user_mut(&user_key).disconnect();
+    test.update();
+
+    assert_eq!(test.server.tick_buffer_depth(&user_key), 0);
+    test.server.clear_tick_buffer(&user_key);
+}
@@ -0,0 +1,176 @@
+use std::{
+    net::SocketAddr,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use naia_client::{Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{AuthEvent, ConnectEvent, DisconnectEvent, Server, ServerConfig, UserKey};
+use naia_shared::Protocol;
+use naia_test::{Auth, LocalNetwork};
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .tick_interval(Duration::from_millis(10))
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build()
+}
+
+struct TestClient {
+    client: Client<Entity>,
+    world: World,
+    address: SocketAddr,
+}
+
+impl TestClient {
+    fn new(network: &LocalNetwork) -> Self {
+        let (socket, address) = network.add_client();
+        let mut client = Client::<Entity>::new(
+            ClientConfig {
+                send_handshake_interval: Duration::from_millis(10),
+                handshake_pings: 2,
+                ..Default::default()
+            },
+            protocol(),
+        );
+        client.auth(Auth::new("charlie", "12345"));
+        client.connect(socket);
+        Self {
+            client,
+            world: World::default(),
+            address,
+        }
+    }
+
+    fn update(&mut self) {
+        let _ = self.client.receive(self.world.proxy_mut());
+    }
+}
+
+struct TestServer {
+    server: Server<Entity>,
+    world: World,
+    connected: Vec<UserKey>,
+    disconnected: Vec<UserKey>,
+}
+
+impl TestServer {
+    fn new(network: &LocalNetwork) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(network.server_socket());
+        Self {
+            server,
+            world: World::default(),
+            connected: Vec::new(),
+            disconnected: Vec::new(),
+        }
+    }
+
+    fn user_key(&self, client: &TestClient) -> UserKey {
+        let Some(user_key) = self
+            .connected
+            .iter()
+            .find(|user_key| self.server.user(user_key).address() == client.address)
+        else {
+            panic!("no User for the Client");
+        };
+        *user_key
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            self.connected.push(user_key);
+        }
+        for (user_key, _) in events.read::<DisconnectEvent>() {
+            self.disconnected.push(user_key);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+#[test]
+fn only_short_timeout_user_is_disconnected() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = [TestClient::new(&network), TestClient::new(&network)];
+    for _ in 0..400 {
+        if server.connected.len() == 2
+            && clients
+                .iter()
+                .all(|client| client.client.connection_status().is_connected())
+        {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        for client in clients.iter_mut() {
+            client.update();
+        }
+        server.update();
+    }
+    let short = server.user_key(&clients[0]);
+    let long = server.user_key(&clients[1]);
+
+    server
+        .server
+        .user_mut(&short)
+        .set_timeout_duration(Duration::from_millis(200));
+    server
+        .server
+        .user_mut(&long)
+        .set_timeout_duration(Duration::from_secs(60));
+
+    // both Clients fall silent, well past the short timeout
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(600) {
+        sleep(Duration::from_millis(5));
+        server.update();
+    }
+
+    assert!(server.disconnected == vec![short]);
+    assert!(server.server.user_exists(&long));
+}
+
+#[test]
+fn ping_interval_override_applies_before_connecting() {
+    let network = LocalNetwork::new();
+    let mut server = TestServer::new(&network);
+    let mut clients = [TestClient::new(&network)];
+
+    // the override is held by the User until its connection is made
+    for _ in 0..400 {
+        if !server.connected.is_empty() {
+            break;
+        }
+        sleep(Duration::from_millis(5));
+        clients[0].update();
+        let mut events = server.server.receive(server.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server
+                .server
+                .user_mut(&user_key)
+                .set_ping_interval(Duration::from_millis(20));
+            server.server.accept_connection(&user_key);
+        }
+        for user_key in events.read::<ConnectEvent>() {
+            server.connected.push(user_key);
+        }
+        server.server.send_all_updates(server.world.proxy());
+    }
+    let user_key = server.connected[0];
+
+    // the round trip estimate starts at 200ms, & only frequent pings bring it
+    // down to the (tiny) local round trip before the 1 second default interval
+    for _ in 0..100 {
+        sleep(Duration::from_millis(5));
+        clients[0].update();
+        server.update();
+    }
+    let rtt = server.server.rtt(&user_key).unwrap();
+    assert!(rtt < 150.0, "rtt {}", rtt);
+}
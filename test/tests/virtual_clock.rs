@@ -0,0 +1,72 @@
+use std::{sync::Arc, time::Duration};
+
+use naia_shared::{
+    default_channels::UnorderedReliableChannel, set_thread_clock, BitWriter, ChannelKind,
+    FakeEntityConverter, HostType, Instant, MessageContainer, MessageManager, Protocol, Timer,
+};
+use naia_test::{Auth, TestClock};
+
+fn install_test_clock() -> Arc<TestClock> {
+    let clock = Arc::new(TestClock::new());
+    set_thread_clock(Some(clock.clone()));
+    clock
+}
+
+#[test]
+fn timer_rings_after_virtual_time_advances() {
+    let clock = install_test_clock();
+
+    let timer = Timer::new(Duration::from_secs(4));
+    assert!(!timer.ringing());
+
+    clock.advance(Duration::from_secs(5));
+    assert!(timer.ringing());
+
+    set_thread_clock(None);
+}
+
+#[test]
+fn reliable_message_resends_in_virtual_time() {
+    let wall_start = std::time::Instant::now();
+    let clock = install_test_clock();
+
+    let protocol = Protocol::builder()
+        .add_default_channels()
+        .add_message::<Auth>()
+        .build();
+    let channel_kind = ChannelKind::of::<UnorderedReliableChannel>();
+    let mut message_manager = MessageManager::new(HostType::Server, &protocol.channel_kinds, None);
+    let mut converter = FakeEntityConverter;
+    let rtt_millis = 100.0;
+
+    let message =
+        MessageContainer::from_write(Box::new(Auth::new("charlie", "1234567")), &mut converter);
+    message_manager.send_message(
+        &protocol.message_kinds,
+        &mut converter,
+        &channel_kind,
+        message,
+    );
+
+    // first send
+    message_manager.collect_outgoing_messages(&Instant::now(), &rtt_millis);
+    assert!(message_manager.has_outgoing_messages());
+    let mut writer = BitWriter::new();
+    let mut has_written = false;
+    message_manager.write_messages(&protocol, &mut converter, &mut writer, 0, &mut has_written);
+    assert!(!message_manager.has_outgoing_messages());
+
+    // no time has passed, so nothing is resent
+    message_manager.collect_outgoing_messages(&Instant::now(), &rtt_millis);
+    assert!(!message_manager.has_outgoing_messages());
+
+    // the packet was never acknowledged, so after a few simulated seconds
+    // the message is resent
+    clock.advance(Duration::from_secs(3));
+    message_manager.collect_outgoing_messages(&Instant::now(), &rtt_millis);
+    assert!(message_manager.has_outgoing_messages());
+
+    assert!(wall_start.elapsed() < Duration::from_secs(1));
+
+    set_thread_clock(None);
+}
@@ -0,0 +1,213 @@
+use naia_demo_world::{Entity, World};
+use naia_server::{ReplicationConfig, Server, ServerConfig, SnapshotError};
+use naia_shared::{Property, Protocol, Replicate, Serde, WorldRefType};
+
+#[derive(Replicate)]
+pub struct Position {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+}
+
+#[derive(Replicate)]
+pub struct Name {
+    pub value: Property<String>,
+}
+
+#[derive(Serde, Clone, Copy, PartialEq, Debug)]
+pub enum Team {
+    Red,
+    Blue,
+}
+
+#[derive(Replicate)]
+pub struct Membership {
+    pub team: Property<Team>,
+    pub rank: Property<u8>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_component::<Position>()
+        .add_component::<Name>()
+        .add_component::<Membership>()
+        .enable_client_authoritative_entities()
+        .build()
+}
+
+fn new_server(protocol: Protocol) -> (Server<Entity>, World) {
+    (
+        Server::<Entity>::new(ServerConfig::default(), protocol),
+        World::default(),
+    )
+}
+
+// (name, position, membership) of an Entity, for comparing Worlds
+type EntityState = (Option<String>, Option<(i16, i16)>, Option<(Team, u8)>);
+
+fn entity_state(world: &World, entity: &Entity) -> EntityState {
+    let world = world.proxy();
+    let name = world
+        .component::<Name>(entity)
+        .map(|name| (*name.value).clone());
+    let position = world
+        .component::<Position>(entity)
+        .map(|position| (*position.x, *position.y));
+    let membership = world
+        .component::<Membership>(entity)
+        .map(|membership| (*membership.team, *membership.rank));
+    (name, position, membership)
+}
+
+fn sorted_states(world: &World, entities: &[Entity]) -> Vec<EntityState> {
+    let mut states: Vec<EntityState> = entities
+        .iter()
+        .map(|entity| entity_state(world, entity))
+        .collect();
+    states.sort_by(|a, b| format!("{:?}", a).cmp(&format!("{:?}", b)));
+    states
+}
+
+// A World with three Server-owned Entities & one Delegated Entity
+fn populated_server() -> (Server<Entity>, World, Vec<Entity>) {
+    let (mut server, mut world) = new_server(protocol());
+    let saved = vec![
+        server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Name::new_complete("alice".to_string()))
+            .insert_component(Position::new_complete(3, -4))
+            .insert_component(Membership::new_complete(Team::Red, 2))
+            .id(),
+        server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Name::new_complete("bob".to_string()))
+            .insert_component(Membership::new_complete(Team::Blue, 7))
+            .id(),
+        server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Position::new_complete(100, 200))
+            .id(),
+    ];
+
+    let delegated = server
+        .spawn_entity(world.proxy_mut())
+        .insert_component(Name::new_complete("delegated".to_string()))
+        .id();
+    server.configure_entity_replication(
+        &mut world.proxy_mut(),
+        &delegated,
+        ReplicationConfig::Delegated,
+    );
+
+    (server, world, saved)
+}
+
+#[test]
+fn snapshot_round_trips_server_owned_entities() {
+    let (server, world, saved) = populated_server();
+    let mut snapshot = Vec::new();
+    server
+        .save_world_snapshot(&world.proxy(), &mut snapshot)
+        .unwrap();
+
+    let (mut loaded_server, mut loaded_world) = new_server(protocol());
+    let loaded = loaded_server
+        .load_world_snapshot(&mut loaded_world.proxy_mut(), &mut snapshot.as_slice())
+        .unwrap();
+
+    // the Delegated Entity is left out
+    assert_eq!(loaded.len(), saved.len());
+    assert_eq!(
+        sorted_states(&loaded_world, &loaded),
+        sorted_states(&world, &saved)
+    );
+
+    // and the loaded Entities replicate like any other Server-owned Entity
+    let replicated = loaded_server.entities(loaded_world.proxy());
+    assert_eq!(replicated.len(), loaded.len());
+    for entity in &loaded {
+        assert!(replicated.contains(entity));
+        assert_eq!(
+            loaded_server.entity_replication_config(entity),
+            Some(ReplicationConfig::Public)
+        );
+    }
+}
+
+#[test]
+fn snapshot_from_another_protocol_is_rejected() {
+    let (server, world, _) = populated_server();
+    let mut snapshot = Vec::new();
+    server
+        .save_world_snapshot(&world.proxy(), &mut snapshot)
+        .unwrap();
+
+    // the same Components, registered in a different order
+    let other_protocol = Protocol::builder()
+        .add_component::<Name>()
+        .add_component::<Position>()
+        .add_component::<Membership>()
+        .enable_client_authoritative_entities()
+        .build();
+    let (mut loaded_server, mut loaded_world) = new_server(other_protocol);
+    let result =
+        loaded_server.load_world_snapshot(&mut loaded_world.proxy_mut(), &mut snapshot.as_slice());
+
+    let Err(SnapshotError::SchemaMismatch { expected, found }) = result else {
+        panic!("expected a schema mismatch");
+    };
+    assert_ne!(expected, found);
+    assert!(loaded_server.entities(loaded_world.proxy()).is_empty());
+}
+
+#[test]
+fn damaged_snapshot_spawns_nothing() {
+    let (server, world, _) = populated_server();
+    let mut snapshot = Vec::new();
+    server
+        .save_world_snapshot(&world.proxy(), &mut snapshot)
+        .unwrap();
+
+    let (mut loaded_server, mut loaded_world) = new_server(protocol());
+
+    let result = loaded_server.load_world_snapshot(
+        &mut loaded_world.proxy_mut(),
+        &mut b"not a snapshot".as_slice(),
+    );
+    assert!(matches!(result, Err(SnapshotError::NotASnapshot)));
+
+    let truncated = &snapshot[..snapshot.len() - 3];
+    let result =
+        loaded_server.load_world_snapshot(&mut loaded_world.proxy_mut(), &mut &truncated[..]);
+    assert!(matches!(result, Err(SnapshotError::Malformed(_))));
+
+    assert!(loaded_server.entities(loaded_world.proxy()).is_empty());
+}
+
+#[test]
+fn single_component_round_trips() {
+    let (server, world, saved) = populated_server();
+    let bytes = server
+        .serialize_component::<Position, _>(&world.proxy(), &saved[0])
+        .unwrap();
+    let position = server.deserialize_component::<Position>(&bytes).unwrap();
+    assert_eq!((*position.x, *position.y), (3, -4));
+    assert!(server
+        .deserialize_component::<Position>(&bytes[..1])
+        .is_none());
+
+    // and it can be inserted like any new Component
+    let (mut loaded_server, mut loaded_world) = new_server(protocol());
+    let entity = loaded_server
+        .spawn_entity(loaded_world.proxy_mut())
+        .insert_component(position)
+        .id();
+    assert_eq!(
+        entity_state(&loaded_world, &entity),
+        (None, Some((3, -4)), None)
+    );
+
+    // an Entity without the Component has nothing to write
+    assert!(server
+        .serialize_component::<Position, _>(&world.proxy(), &saved[1])
+        .is_none());
+}